@@ -109,12 +109,15 @@ pub struct ObservingConditionsConfig {
     pub device_number: u32,
 }
 
-/// Dome equipment entry.
+/// Dome equipment entry. `slaving` is emitted verbatim as the dome's
+/// `slaving` block (rp.md § Dome Slaving); `None` ⇒ the dome only moves
+/// on explicit tool calls.
 #[derive(Debug, Clone)]
 pub struct DomeConfig {
     pub id: String,
     pub alpaca_url: String,
     pub device_number: u32,
+    pub slaving: Option<Value>,
 }
 
 /// Plate-solver service config — emitted as the top-level
@@ -597,11 +600,15 @@ impl RpConfigBuilder {
             .domes
             .iter()
             .map(|d| {
-                serde_json::json!({
+                let mut dome = serde_json::json!({
                     "id": d.id,
                     "alpaca_url": d.alpaca_url,
                     "device_number": d.device_number,
-                })
+                });
+                if let Some(slaving) = &d.slaving {
+                    dome["slaving"] = slaving.clone();
+                }
+                dome
             })
             .collect();
 
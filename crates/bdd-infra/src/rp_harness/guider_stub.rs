@@ -172,6 +172,10 @@ impl GuiderStub {
             .route("/api/v1/equipment", get(equipment_handler))
             .route("/api/v1/calibration/clear", post(calibration_clear_handler))
             .route("/api/v1/star/reselect", post(reselect_handler))
+            .route(
+                "/api/v1/guiding/settings",
+                get(settings_handler).post(settings_handler),
+            )
             .route("/health", get(health_handler))
             .with_state(state);
 
@@ -458,6 +462,39 @@ async fn reselect_handler(
     Json(serde_json::json!({ "state": "selected" })).into_response()
 }
 
+/// `GET` and `POST /api/v1/guiding/settings`: PHD2-shaped settings
+/// (Hysteresis RA, Resist Switch Dec, multi-star and backlash
+/// compensation not exposed). A `POST` overlays the requested
+/// `dec_guide_mode` so the read-back reflects the switch.
+async fn settings_handler(
+    State(state): State<StubState>,
+    uri: axum::http::Uri,
+    body: axum::body::Bytes,
+) -> axum::response::Response {
+    let requested_mode = serde_json::from_slice::<Value>(&body)
+        .ok()
+        .and_then(|b| b.get("dec_guide_mode").cloned());
+    if let Some(err) = record_and_check(&state, &uri, body).await {
+        return err;
+    }
+    Json(serde_json::json!({
+        "dec_guide_mode": requested_mode.unwrap_or_else(|| Value::from("Auto")),
+        "ra": {
+            "min_move_px": 0.15,
+            "aggressiveness": 0.7,
+            "params": { "minMove": 0.15, "hysteresis": 0.1, "aggression": 0.7 },
+        },
+        "dec": {
+            "min_move_px": 0.15,
+            "aggressiveness": 0.7,
+            "params": { "minMove": 0.15, "aggression": 0.7, "fastSwitch": 1.0 },
+        },
+        "multi_star": null,
+        "backlash_compensation": null,
+    }))
+    .into_response()
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 #[allow(clippy::unwrap_used, clippy::expect_used, clippy::unreachable)]
@@ -582,6 +619,35 @@ mod tests {
         assert_eq!(clear_requests.len(), 1);
     }
 
+    #[tokio::test]
+    async fn a_settings_post_reads_back_the_requested_dec_guide_mode() {
+        let stub = GuiderStub::start(GuiderStubBehavior::Canned(CannedGuiding::default())).await;
+        let client = reqwest::Client::new();
+
+        let current: Value = client
+            .get(format!("{}/api/v1/guiding/settings", stub.url))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(current["dec_guide_mode"], "Auto");
+        assert_eq!(current["multi_star"], Value::Null);
+
+        let updated: Value = client
+            .post(format!("{}/api/v1/guiding/settings", stub.url))
+            .json(&serde_json::json!({ "dec_guide_mode": "North" }))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(updated["dec_guide_mode"], "North");
+        assert_eq!(stub.requests_to("/guiding/settings").await.len(), 2);
+    }
+
     #[tokio::test]
     async fn the_t4_endpoints_map_the_error_behavior() {
         let stub = GuiderStub::start(GuiderStubBehavior::Error {
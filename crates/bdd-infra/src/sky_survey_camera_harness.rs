@@ -19,6 +19,10 @@
 //!   `imagebytes` payload directly to a downstream consumer that
 //!   *does* honour camera-side WCS (e.g. unit tests that pair the
 //!   stub with [`crate::rp_harness::StubBehavior::EchoFitsCenter`]).
+//!   [`SkyViewStub::start_star_field`] instead fills the cutout with a
+//!   deterministic star field fixed on the sky, so a camera following
+//!   the mount sees the stars shift as the mount moves — what
+//!   `native-guider`'s guiding scenarios centroid on.
 //! - [`SkySurveyCameraConfig`] / [`SkySurveyCameraConfigBuilder`] —
 //!   builds the JSON config the production binary expects.
 //! - [`start_sky_survey_camera`] — spawns the binary via
@@ -33,7 +37,7 @@ use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use axum::extract::RawQuery;
+use axum::extract::{RawQuery, State};
 use axum::http::{Method, StatusCode};
 use axum::response::IntoResponse;
 use axum::routing::any;
@@ -54,10 +58,32 @@ pub struct SkyViewStub {
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
 }
 
+/// What the stub renders into a cutout's pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SkyContent {
+    /// All zeros; only the WCS header carries information.
+    Blank,
+    /// [`render_star_field`]'s stars on a flat, lightly noisy sky.
+    StarField,
+}
+
 impl SkyViewStub {
     /// Bind a `SkyView` stub on `127.0.0.1:0` and return its public URL.
     pub async fn start() -> Self {
-        let app = Router::new().fallback(any(handle_skyview));
+        Self::serve(SkyContent::Blank).await
+    }
+
+    /// Like [`Self::start`], but every cutout is a view of the same
+    /// star field, fixed on the sky: two requests at nearby positions
+    /// show the same stars, shifted by the pointing difference.
+    pub async fn start_star_field() -> Self {
+        Self::serve(SkyContent::StarField).await
+    }
+
+    async fn serve(content: SkyContent) -> Self {
+        let app = Router::new()
+            .fallback(any(handle_skyview))
+            .with_state(content);
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
             .await
             .expect("SkyViewStub bind");
@@ -101,7 +127,11 @@ impl Drop for SkyViewStub {
 /// process.
 const MAX_PIXELS_PER_AXIS: u32 = 8192;
 
-async fn handle_skyview(method: Method, RawQuery(query): RawQuery) -> axum::response::Response {
+async fn handle_skyview(
+    State(content): State<SkyContent>,
+    method: Method,
+    RawQuery(query): RawQuery,
+) -> axum::response::Response {
     if method == Method::HEAD {
         return (StatusCode::OK, Vec::<u8>::new()).into_response();
     }
@@ -135,7 +165,11 @@ async fn handle_skyview(method: Method, RawQuery(query): RawQuery) -> axum::resp
         )
             .into_response();
     }
-    let bytes = synth_fits_with_wcs(w, h, ra_deg, dec_deg, pixel_scale_arcsec);
+    let pixels = match content {
+        SkyContent::Blank => None,
+        SkyContent::StarField => Some(render_star_field(w, h, ra_deg, dec_deg, pixel_scale_arcsec)),
+    };
+    let bytes = synth_fits_with_wcs(w, h, ra_deg, dec_deg, pixel_scale_arcsec, pixels);
     (StatusCode::OK, bytes).into_response()
 }
 
@@ -205,10 +239,107 @@ fn parse_pixels(s: Option<&str>) -> Option<(u32, u32)> {
     Some((w, h))
 }
 
+/// Star-field grid spacing on the sky. Wide enough that a guider's
+/// search box never holds two stars at test plate scales.
+const STAR_SPACING_ARCSEC: f64 = 120.0;
+
+/// Gaussian profile width of every star, in pixels.
+const STAR_SIGMA_PX: f64 = 1.5;
+
+const SKY_LEVEL_ADU: f64 = 1000.0;
+
+/// `SplitMix64`: a tiny, good-enough hash for deterministic per-star
+/// and per-pixel variation.
+const fn splitmix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// A hash of `(a, b)` mapped onto `[0, 1)`.
+fn unit_hash(a: i64, b: i64, salt: u64) -> f64 {
+    let h = splitmix64((a as u64).wrapping_mul(0x1000_0000_01B3) ^ (b as u64) ^ salt);
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Render a `width × height` view of a star field fixed on the sky,
+/// centred on `(ra_center_deg, dec_center_deg)`, row-major.
+///
+/// Stars sit on a grid of [`STAR_SPACING_ARCSEC`] in Dec, with each
+/// row's RA spacing widened by `1 / cos(dec)` so the on-sky spacing
+/// stays even, and are jittered within their cell. Each star's
+/// position and brightness come from a hash of its grid cell, so every
+/// request sees the same sky. North is +y and East is −x, matching the
+/// `CDELT1 < 0` header [`synth_fits_with_wcs`] writes.
+fn render_star_field(
+    width: u32,
+    height: u32,
+    ra_center_deg: f64,
+    dec_center_deg: f64,
+    pixel_scale_arcsec: f64,
+) -> Vec<u16> {
+    let w = width as usize;
+    let h = height as usize;
+    let mut sky = vec![SKY_LEVEL_ADU; w * h];
+
+    let cx = (f64::from(width) - 1.0) / 2.0;
+    let cy = (f64::from(height) - 1.0) / 2.0;
+    let spacing_deg = STAR_SPACING_ARCSEC / 3600.0;
+    let stamp_px = (5.0 * STAR_SIGMA_PX).ceil();
+    let margin_deg =
+        (stamp_px + STAR_SPACING_ARCSEC / pixel_scale_arcsec) * pixel_scale_arcsec / 3600.0;
+    let half_dec_deg = f64::from(height) / 2.0 * pixel_scale_arcsec / 3600.0 + margin_deg;
+    let cos_center = dec_center_deg.to_radians().cos().max(0.05);
+    let half_ra_deg =
+        (f64::from(width) / 2.0 * pixel_scale_arcsec / 3600.0 + margin_deg) / cos_center;
+
+    let row_lo = ((dec_center_deg - half_dec_deg) / spacing_deg).floor() as i64;
+    let row_hi = ((dec_center_deg + half_dec_deg) / spacing_deg).ceil() as i64;
+    for row in row_lo..=row_hi {
+        let row_dec = row as f64 * spacing_deg;
+        let ra_spacing_deg = spacing_deg / row_dec.to_radians().cos().max(0.05);
+        let col_lo = ((ra_center_deg - half_ra_deg) / ra_spacing_deg).floor() as i64;
+        let col_hi = ((ra_center_deg + half_ra_deg) / ra_spacing_deg).ceil() as i64;
+        for col in col_lo..=col_hi {
+            let jitter_ra = (unit_hash(row, col, 1) - 0.5) * 0.5;
+            let jitter_dec = (unit_hash(row, col, 2) - 0.5) * 0.5;
+            let peak = 2000.0 + 10000.0 * unit_hash(row, col, 3);
+            let star_ra = (col as f64 + jitter_ra) * ra_spacing_deg;
+            let star_dec = (row as f64 + jitter_dec) * spacing_deg;
+            let x = cx - (star_ra - ra_center_deg) * cos_center * 3600.0 / pixel_scale_arcsec;
+            let y = cy + (star_dec - dec_center_deg) * 3600.0 / pixel_scale_arcsec;
+            stamp_star(&mut sky, w, h, x, y, peak, stamp_px);
+        }
+    }
+
+    sky.iter()
+        .enumerate()
+        .map(|(i, v)| {
+            let noise = (unit_hash(i as i64, 0, 4) - 0.5) * 20.0;
+            (v + noise).round().clamp(0.0, f64::from(u16::MAX)) as u16
+        })
+        .collect()
+}
+
+fn stamp_star(sky: &mut [f64], w: usize, h: usize, x: f64, y: f64, peak: f64, radius: f64) {
+    let x_lo = (x - radius).floor().max(0.0) as usize;
+    let y_lo = (y - radius).floor().max(0.0) as usize;
+    let x_hi = ((x + radius).ceil().max(-1.0) + 1.0) as usize;
+    let y_hi = ((y + radius).ceil().max(-1.0) + 1.0) as usize;
+    let two_sigma_sq = 2.0 * STAR_SIGMA_PX * STAR_SIGMA_PX;
+    for py in y_lo..y_hi.min(h) {
+        for px in x_lo..x_hi.min(w) {
+            let r2 = (px as f64 - x).powi(2) + (py as f64 - y).powi(2);
+            sky[py * w + px] += peak * (-r2 / two_sigma_sq).exp();
+        }
+    }
+}
+
 /// Build a minimal `BITPIX=16` FITS that advertises a TAN WCS at
 /// `(ra_center_deg, dec_center_deg)` with the given plate scale.
-/// Pixel data is zero-filled — the centering loop's plate-solver stub
-/// reads only header records.
+/// Pixel data is `pixels` (row-major) or, when `None`, zero-filled —
+/// the centering loop's plate-solver stub reads only header records.
 ///
 /// `width` and `height` must satisfy
 /// `width * height <= isize::MAX as usize` (Rust's `Vec` ceiling);
@@ -222,6 +353,7 @@ fn synth_fits_with_wcs(
     ra_center_deg: f64,
     dec_center_deg: f64,
     pixel_scale_arcsec: f64,
+    pixels: Option<Vec<u16>>,
 ) -> Vec<u8> {
     use rp_fits::writer::{write_u16_image, Keyword, KeywordValue};
 
@@ -249,7 +381,7 @@ fn synth_fits_with_wcs(
     let pixel_count = w
         .checked_mul(h)
         .expect("width * height overflows usize (callers must cap dimensions)");
-    let pixels = vec![0u16; pixel_count];
+    let pixels = pixels.unwrap_or_else(|| vec![0u16; pixel_count]);
     let mut out = Vec::new();
     write_u16_image(&mut out, &pixels, w, h, &extras).expect("write_u16_image");
     out
@@ -463,6 +595,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn the_star_field_is_fixed_on_the_sky() {
+        let (w, h, scale) = (200usize, 150usize, 2.0);
+        let a = render_star_field(w as u32, h as u32, 150.0, 0.0, scale);
+        assert_eq!(a.len(), w * h);
+        assert!(a.iter().any(|&v| v > 3000), "no stars rendered");
+        // Same pointing, same sky.
+        assert_eq!(a, render_star_field(w as u32, h as u32, 150.0, 0.0, scale));
+        // Pointing 10 px (20") further North moves every star 10 rows
+        // down the image; only the per-pixel noise (±10) differs. At
+        // the equator the RA scale is flat in Dec, so the shift is a
+        // pure row offset.
+        let b = render_star_field(w as u32, h as u32, 150.0, 20.0 / 3600.0, scale);
+        for y in 0..h - 10 {
+            for x in 0..w {
+                let (va, vb) = (i32::from(a[(y + 10) * w + x]), i32::from(b[y * w + x]));
+                assert!((va - vb).abs() <= 21, "({x}, {y}): {va} vs {vb}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn the_star_field_stub_serves_stars() {
+        let stub = SkyViewStub::start_star_field().await;
+        let url = format!(
+            "{}?Position=150.0,20.0&Pixels=64,48&Size=0.04,0.03",
+            stub.url
+        );
+        let bytes = reqwest::Client::new()
+            .get(&url)
+            .send()
+            .await
+            .unwrap()
+            .bytes()
+            .await
+            .unwrap();
+        let (data, width, height) =
+            rp_fits::reader::read_primary_as_i32(std::io::Cursor::new(&bytes)).unwrap();
+        assert_eq!((width, height), (64, 48));
+        assert!(data.iter().all(|&v| v > 900), "sky floor missing");
+    }
+
     #[tokio::test]
     async fn skyview_stub_responds_to_head() {
         let stub = SkyViewStub::start().await;
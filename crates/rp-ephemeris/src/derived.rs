@@ -4,6 +4,7 @@
 use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
 
 use crate::erfars_impl::{alt_az_at, lst_hours, time_jds};
+use crate::horizon::HorizonProfile;
 use crate::site::Site;
 use crate::types::{IcrsCoord, RiseSet, TwilightKind, TwilightWindow};
 
@@ -85,13 +86,25 @@ pub fn transit(site: &Site, target: IcrsCoord, date: NaiveDate) -> Option<DateTi
     Some(refined)
 }
 
-/// Rise/set times above `min_alt_deg`.
+/// Spacing of the altitude samples [`rise_set`] scans the day with
+/// before bisecting a crossing. A horizon notch narrower than the
+/// target's travel in this time can be stepped over; five minutes is
+/// ≈1.25° of hour angle, finer than any horizon file's resolution.
+const RISE_SET_SCAN_MINUTES: i64 = 5;
+
+/// Rise/set times above the azimuth-dependent floor
+/// `horizon.floor_at(az, min_alt_deg)`: the first time in the
+/// antitransit-to-antitransit day around the UTC `date`'s transit the
+/// target clears it, and the last time it still does. An obstruction
+/// that hides the target mid-pass is not a gap in the result — a
+/// [`RiseSet`] carries one interval.
 pub fn rise_set(
     _eph: &impl crate::Ephemeris, // unused for v1; reserved for future
     site: &Site,
     target: IcrsCoord,
     date: NaiveDate,
     min_alt_deg: f64,
+    horizon: &HorizonProfile,
 ) -> Option<RiseSet> {
     let transit_t = transit(site, target, date)?;
     // Antitransit is 12 sidereal hours away; use 11h57.97m solar.
@@ -100,27 +113,51 @@ pub fn rise_set(
     let antitransit_before = transit_t - half_sidereal_day_solar;
     let antitransit_after = transit_t + half_sidereal_day_solar;
 
-    let alt_minus_thresh = |t: DateTime<Utc>| -> f64 {
+    let alt_minus_floor = |t: DateTime<Utc>| -> f64 {
         match alt_az_at(site, target, &time_jds(t)) {
-            Ok(aa) => aa.altitude_degrees - min_alt_deg,
+            Ok(aa) => aa.altitude_degrees - horizon.floor_at(aa.azimuth_degrees, min_alt_deg),
             Err(_) => f64::NAN,
         }
     };
 
-    let alt_at_transit = alt_minus_thresh(transit_t);
-    if alt_at_transit < 0.0 {
-        return None; // never reaches threshold
+    // Sample the day, remembering the bracket of the first upward and
+    // the last downward crossing.
+    let step = Duration::minutes(RISE_SET_SCAN_MINUTES);
+    let mut rise_bracket: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    let mut set_bracket: Option<(DateTime<Utc>, DateTime<Utc>)> = None;
+    let mut any_below = false;
+    let mut prev_t = antitransit_before;
+    let mut prev = alt_minus_floor(prev_t);
+    if prev.is_nan() {
+        return None;
     }
-    let alt_anti_before = alt_minus_thresh(antitransit_before);
-    let alt_anti_after = alt_minus_thresh(antitransit_after);
-    if alt_anti_before >= 0.0 && alt_anti_after >= 0.0 {
-        return None; // always above threshold (circumpolar-up)
+    any_below |= prev < 0.0;
+    let mut t = prev_t;
+    while t < antitransit_after {
+        t = (t + step).min(antitransit_after);
+        let cur = alt_minus_floor(t);
+        if cur.is_nan() {
+            return None;
+        }
+        any_below |= cur < 0.0;
+        if prev < 0.0 && cur >= 0.0 && rise_bracket.is_none() {
+            rise_bracket = Some((prev_t, t));
+        }
+        if prev >= 0.0 && cur < 0.0 {
+            set_bracket = Some((prev_t, t));
+        }
+        prev_t = t;
+        prev = cur;
+    }
+    if !any_below {
+        return None; // always above the floor (circumpolar-up)
     }
 
-    let rise = bisect_dt(alt_minus_thresh, antitransit_before, transit_t, 1);
-    let set = bisect_dt(alt_minus_thresh, transit_t, antitransit_after, 1);
+    let rise = rise_bracket.and_then(|(lo, hi)| bisect_dt(alt_minus_floor, lo, hi, 1));
+    let set = set_bracket.and_then(|(lo, hi)| bisect_dt(alt_minus_floor, lo, hi, 1));
     match (rise, set) {
         (Some(rise_utc), Some(set_utc)) => Some(RiseSet { rise_utc, set_utc }),
+        // Never clears the floor, or only at one end of the window.
         _ => None,
     }
 }
@@ -182,6 +219,7 @@ pub fn twilight(
 mod tests {
     use super::*;
     use crate::erfars_impl::ErfarsEphemeris;
+    use crate::horizon::HorizonPoint;
     use crate::Ephemeris;
     use chrono::{NaiveDateTime, TimeZone};
 
@@ -199,7 +237,15 @@ mod tests {
         let date = NaiveDate::from_ymd_opt(2026, 5, 3).unwrap();
         // Polaris never sets at Seattle (lat ~47.6°), so above
         // min_alt_deg = 10° is always-up.
-        assert!(rise_set(&eph, &site_seattle(), polaris, date, 10.0).is_none());
+        assert!(rise_set(
+            &eph,
+            &site_seattle(),
+            polaris,
+            date,
+            10.0,
+            &HorizonProfile::default()
+        )
+        .is_none());
     }
 
     #[test]
@@ -212,7 +258,15 @@ mod tests {
             dec_degrees: -85.0,
         };
         let date = NaiveDate::from_ymd_opt(2026, 5, 3).unwrap();
-        assert!(rise_set(&eph, &site_seattle(), target, date, 10.0).is_none());
+        assert!(rise_set(
+            &eph,
+            &site_seattle(),
+            target,
+            date,
+            10.0,
+            &HorizonProfile::default()
+        )
+        .is_none());
     }
 
     #[test]
@@ -223,14 +277,56 @@ mod tests {
             dec_degrees: 41.2689,
         };
         let date = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
-        let rs = rise_set(&eph, &site_seattle(), m31, date, 30.0)
-            .expect("M31 should rise above 30° at Seattle in autumn");
+        let rs = rise_set(
+            &eph,
+            &site_seattle(),
+            m31,
+            date,
+            30.0,
+            &HorizonProfile::default(),
+        )
+        .expect("M31 should rise above 30° at Seattle in autumn");
         assert!(rs.set_utc > rs.rise_utc, "set must follow rise");
         let span = rs.set_utc - rs.rise_utc;
         assert!(span > Duration::hours(1));
         assert!(span < Duration::hours(24));
     }
 
+    #[test]
+    fn horizon_profile_delays_rise_behind_an_eastern_obstruction() {
+        let eph = ErfarsEphemeris::new();
+        let m31 = IcrsCoord {
+            ra_hours: 0.7122,
+            dec_degrees: 41.2689,
+        };
+        let date = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+        let flat = rise_set(
+            &eph,
+            &site_seattle(),
+            m31,
+            date,
+            30.0,
+            &HorizonProfile::default(),
+        )
+        .unwrap();
+        // A 60° wall across the whole eastern sky, open to the west:
+        // M31 (culminating near 83°) rises later, sets unchanged.
+        let point = |azimuth_degrees, altitude_degrees| HorizonPoint {
+            azimuth_degrees,
+            altitude_degrees,
+        };
+        let wall = HorizonProfile::new(vec![
+            point(0.0, 60.0),
+            point(179.0, 60.0),
+            point(181.0, 0.0),
+            point(359.0, 0.0),
+        ])
+        .unwrap();
+        let blocked = rise_set(&eph, &site_seattle(), m31, date, 30.0, &wall).unwrap();
+        assert!(blocked.rise_utc > flat.rise_utc + Duration::minutes(10));
+        assert!((blocked.set_utc - flat.set_utc).num_seconds().abs() < 5);
+    }
+
     #[test]
     fn transit_within_one_day_of_requested_date() {
         let m31 = IcrsCoord {
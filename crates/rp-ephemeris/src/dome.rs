@@ -0,0 +1,181 @@
+//! Dome-slit azimuth for an off-center German equatorial mount.
+//!
+//! A telescope whose optical axis does not pass through the dome's
+//! center looks out through a different part of the dome than its
+//! pointing azimuth suggests: the mount's pier is offset from the dome
+//! center, and a GEM carries the tube on one side of the RA axis, by a
+//! distance that swings with hour angle and swaps sides across a
+//! meridian flip. [`DomeGeometry::slit_azimuth`] intersects the
+//! optical axis with the dome sphere and returns the azimuth of that
+//! point — where the slit has to be.
+//!
+//! Pure vector math in a local east/north/up frame centered on the
+//! dome: no time, no ERFA. Callers supply the hour angle from the
+//! mount's own sidereal time and right ascension.
+
+use crate::types::SideOfPier;
+
+/// The mount's placement inside the dome. Offsets are in meters in a
+/// local east/north/up frame whose origin is the dome sphere's center.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DomeGeometry {
+    /// Dome radius, meters.
+    pub radius_m: f64,
+    /// Position of the RA/Dec axis intersection relative to the dome
+    /// center, meters east.
+    pub mount_offset_east_m: f64,
+    /// Same, meters north.
+    pub mount_offset_north_m: f64,
+    /// Same, meters up.
+    pub mount_offset_up_m: f64,
+    /// Distance from the RA axis to the optical axis, measured along
+    /// the Dec axis (the GEM offset), meters.
+    pub gem_offset_m: f64,
+}
+
+impl DomeGeometry {
+    /// Azimuth, degrees from north through east in `[0, 360)`, of the
+    /// point where the optical axis leaves the dome, for a mount at
+    /// `hour_angle_hours` / `dec_deg` on a site at `latitude_deg`.
+    ///
+    /// `side` places the tube: [`SideOfPier::East`] (the ASCOM normal
+    /// state, counterweight down while looking west) puts it on the
+    /// side of the RA axis that faces east at the meridian;
+    /// [`SideOfPier::West`] on the opposite side. [`SideOfPier::Unknown`]
+    /// ignores the GEM offset and uses the pier offset alone.
+    ///
+    /// `None` when the optical axis starts outside the dome (a radius
+    /// smaller than the offsets) or an input is not finite.
+    #[must_use]
+    pub fn slit_azimuth(
+        &self,
+        latitude_deg: f64,
+        hour_angle_hours: f64,
+        dec_deg: f64,
+        side: SideOfPier,
+    ) -> Option<f64> {
+        let phi = latitude_deg.to_radians();
+        let h = (hour_angle_hours * 15.0).to_radians();
+        let dec = dec_deg.to_radians();
+
+        // Pointing direction in east/north/up.
+        let view = [
+            -dec.cos() * h.sin(),
+            phi.cos() * dec.sin() - phi.sin() * dec.cos() * h.cos(),
+            phi.sin() * dec.sin() + phi.cos() * dec.cos() * h.cos(),
+        ];
+        // Unit vector along the Dec axis: the point on the celestial
+        // equator six hours east of the pointing hour angle. Points
+        // due east when looking at the meridian.
+        let dec_axis = [h.cos(), -phi.sin() * h.sin(), phi.cos() * h.sin()];
+        let sign = match side {
+            SideOfPier::East => 1.0,
+            SideOfPier::West => -1.0,
+            SideOfPier::Unknown => 0.0,
+        };
+        let offset = sign * self.gem_offset_m;
+        let origin = [
+            self.mount_offset_east_m + offset * dec_axis[0],
+            self.mount_offset_north_m + offset * dec_axis[1],
+            self.mount_offset_up_m + offset * dec_axis[2],
+        ];
+
+        // Ray–sphere intersection: |origin + t·view| = radius, t > 0.
+        let b = dot(origin, view);
+        let c = dot(origin, origin) - self.radius_m * self.radius_m;
+        if !(b.is_finite() && c.is_finite()) || c > 0.0 {
+            return None;
+        }
+        let t = -b + (b * b - c).sqrt();
+        let east = origin[0] + t * view[0];
+        let north = origin[1] + t * view[1];
+        let az = east.atan2(north).to_degrees().rem_euclid(360.0);
+        az.is_finite().then_some(az)
+    }
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn centered(radius_m: f64) -> DomeGeometry {
+        DomeGeometry {
+            radius_m,
+            ..DomeGeometry::default()
+        }
+    }
+
+    #[test]
+    fn a_centered_mount_follows_the_pointing_azimuth() {
+        let g = centered(2.0);
+        // On the meridian, south of zenith → due south.
+        let az = g.slit_azimuth(45.0, 0.0, 0.0, SideOfPier::Unknown).unwrap();
+        assert!((az - 180.0).abs() < 1e-9, "{az}");
+        // Six hours west on the equator → due west.
+        let az = g.slit_azimuth(45.0, 6.0, 0.0, SideOfPier::Unknown).unwrap();
+        assert!((az - 270.0).abs() < 1e-9, "{az}");
+        // Six hours east → due east.
+        let az = g
+            .slit_azimuth(45.0, -6.0, 0.0, SideOfPier::Unknown)
+            .unwrap();
+        assert!((az - 90.0).abs() < 1e-9, "{az}");
+    }
+
+    #[test]
+    fn the_gem_offset_swings_the_slit_with_the_pier_side() {
+        // Looking at the meridian at dec 0 from 45° N with the tube
+        // 0.5 m off the RA axis in a 2 m dome: the ray starts 0.5 m
+        // east (pier East) or west (pier West) of center.
+        let g = DomeGeometry {
+            gem_offset_m: 0.5,
+            ..centered(2.0)
+        };
+        // t = sqrt(R² − d²) = sqrt(3.75); exit point (±0.5, −t·√½, …).
+        let t = 3.75_f64.sqrt();
+        let expected_east = 0.5_f64
+            .atan2(-t * std::f64::consts::FRAC_1_SQRT_2)
+            .to_degrees();
+        let east = g.slit_azimuth(45.0, 0.0, 0.0, SideOfPier::East).unwrap();
+        let west = g.slit_azimuth(45.0, 0.0, 0.0, SideOfPier::West).unwrap();
+        assert!(
+            (east - expected_east).abs() < 1e-9,
+            "{east} vs {expected_east}"
+        );
+        assert!(east < 180.0, "a tube east of the axis exits east of south");
+        assert!(
+            (east + west - 360.0).abs() < 1e-9,
+            "the two pier sides mirror about the meridian: {east} / {west}"
+        );
+    }
+
+    #[test]
+    fn a_pier_offset_shifts_the_slit_toward_the_mount() {
+        // Mount 0.5 m north of center, looking due east at the horizon:
+        // the exit point is north of due east.
+        let g = DomeGeometry {
+            mount_offset_north_m: 0.5,
+            ..centered(2.0)
+        };
+        let az = g.slit_azimuth(0.0, -6.0, 0.0, SideOfPier::Unknown).unwrap();
+        // Exit point (sqrt(R² − d²), 0.5, 0).
+        let expected = 3.75_f64.sqrt().atan2(0.5).to_degrees();
+        assert!((az - expected).abs() < 1e-9, "{az} vs {expected}");
+        assert!(az < 90.0);
+    }
+
+    #[test]
+    fn an_origin_outside_the_dome_has_no_slit() {
+        let g = DomeGeometry {
+            mount_offset_east_m: 3.0,
+            ..centered(2.0)
+        };
+        assert!(g
+            .slit_azimuth(45.0, 0.0, 0.0, SideOfPier::Unknown)
+            .is_none());
+    }
+}
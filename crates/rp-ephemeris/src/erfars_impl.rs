@@ -11,6 +11,7 @@ use erfars::timescales::{Dtf2d, Taitt, Utctai};
 use erfars::ERFAResult;
 
 use crate::derived;
+use crate::horizon::HorizonProfile;
 use crate::site::Site;
 use crate::types::{
     AltAz, EphemerisError, IcrsCoord, LocalSiderealTime, MoonInfo, RefractionConditions, RiseSet,
@@ -447,9 +448,10 @@ impl Ephemeris for ErfarsEphemeris {
         target: IcrsCoord,
         date: NaiveDate,
         min_alt_deg: f64,
+        horizon: &HorizonProfile,
     ) -> Option<RiseSet> {
         run_with_guard("rise_set", None, || {
-            derived::rise_set(self, site, target, date, min_alt_deg)
+            derived::rise_set(self, site, target, date, min_alt_deg, horizon)
         })
     }
 
@@ -756,7 +758,9 @@ mod tests {
         // Date-based helpers bisect over LST/sun-altitude; both go NaN
         // upstream and the bisector returns None.
         assert!(eph.transit(&site, target, d).is_none());
-        assert!(eph.rise_set(&site, target, d, 0.0).is_none());
+        assert!(eph
+            .rise_set(&site, target, d, 0.0, &HorizonProfile::default())
+            .is_none());
     }
 
     /// `run_with_guard` returns the closure's value when the closure
@@ -0,0 +1,293 @@
+//! Per-azimuth horizon profile: the local obstruction altitude (trees,
+//! buildings, terrain) as a function of azimuth. Points are linearly
+//! interpolated and the curve wraps through north, so a profile with a
+//! single point is a flat horizon at that altitude and the empty
+//! profile obstructs nothing.
+//!
+//! Pure data plus a text parser for the common horizon-file formats —
+//! reading the file is the consumer's job, like everything else that
+//! touches I/O in this crate's callers.
+
+use serde::{Deserialize, Serialize};
+
+/// One `(azimuth, altitude)` sample of a [`HorizonProfile`]. Azimuth is
+/// measured from north through east, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HorizonPoint {
+    pub azimuth_degrees: f64,
+    pub altitude_degrees: f64,
+}
+
+/// A validated horizon profile. Construct with [`HorizonProfile::new`]
+/// or [`HorizonProfile::parse`]; the [`Default`] profile is empty and
+/// obstructs nothing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HorizonProfile {
+    /// Sorted by azimuth, each in `[0, 360)`, no azimuth repeated.
+    points: Vec<HorizonPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum HorizonError {
+    #[error("horizon point azimuth must be a finite number in [0, 360]; got {0}")]
+    AzimuthOutOfRange(f64),
+    #[error("horizon point altitude must be in [-90, 90]; got {0}")]
+    AltitudeOutOfRange(f64),
+    #[error("horizon file line {line}: expected `azimuth altitude`, got {content:?}")]
+    Malformed { line: usize, content: String },
+    #[error("horizon file contains no points")]
+    NoPoints,
+}
+
+impl HorizonProfile {
+    /// Validate and normalise `points`: azimuth must be in `[0, 360]`
+    /// (360 folds onto 0 — most horizon files close the circle with
+    /// both), altitude in `[-90, 90]`. Points are sorted by azimuth;
+    /// where two share an azimuth the higher altitude wins, so a
+    /// duplicate can only ever make the profile more conservative.
+    ///
+    /// # Errors
+    ///
+    /// The first out-of-range azimuth or altitude.
+    pub fn new(points: Vec<HorizonPoint>) -> Result<Self, HorizonError> {
+        let mut normalised: Vec<HorizonPoint> = Vec::with_capacity(points.len());
+        for p in points {
+            if !(0.0..=360.0).contains(&p.azimuth_degrees) {
+                return Err(HorizonError::AzimuthOutOfRange(p.azimuth_degrees));
+            }
+            if !(-90.0..=90.0).contains(&p.altitude_degrees) {
+                return Err(HorizonError::AltitudeOutOfRange(p.altitude_degrees));
+            }
+            normalised.push(HorizonPoint {
+                azimuth_degrees: p.azimuth_degrees.rem_euclid(360.0),
+                altitude_degrees: p.altitude_degrees,
+            });
+        }
+        normalised.sort_by(|a, b| a.azimuth_degrees.total_cmp(&b.azimuth_degrees));
+        normalised.dedup_by(|later, kept| {
+            let same = later.azimuth_degrees == kept.azimuth_degrees;
+            if same {
+                kept.altitude_degrees = kept.altitude_degrees.max(later.altitude_degrees);
+            }
+            same
+        });
+        Ok(Self { points: normalised })
+    }
+
+    /// Parse the text of a horizon file. Accepts the two-column
+    /// `azimuth altitude` shape shared by N.I.N.A. (`.hrz`), Stellarium
+    /// (`horizon_list.txt`), APCC and most CSV exports: one point per
+    /// line, columns separated by whitespace, `,` or `;`. Blank lines,
+    /// `#` / `//` comments, a single leading non-numeric header line
+    /// (`azimuth,altitude`) and any columns past the second are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`HorizonError::Malformed`] for an unparseable data line (with
+    /// its 1-based number), [`HorizonError::NoPoints`] for a file with
+    /// no data, or a range error from [`HorizonProfile::new`].
+    pub fn parse(text: &str) -> Result<Self, HorizonError> {
+        let mut points = Vec::new();
+        let mut seen_content = false;
+        for (index, raw) in text.lines().enumerate() {
+            let line = raw
+                .split_once('#')
+                .map_or(raw, |(data, _)| data)
+                .split("//")
+                .next()
+                .unwrap_or_default()
+                .trim();
+            if line.is_empty() {
+                continue;
+            }
+            let mut fields = line
+                .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+                .filter(|f| !f.is_empty());
+            let parsed = match (fields.next(), fields.next()) {
+                (Some(az), Some(alt)) => az.parse::<f64>().ok().zip(alt.parse::<f64>().ok()),
+                _ => None,
+            };
+            match parsed {
+                Some((azimuth_degrees, altitude_degrees)) => points.push(HorizonPoint {
+                    azimuth_degrees,
+                    altitude_degrees,
+                }),
+                // Only the first content line may be a header.
+                None if !seen_content => {}
+                None => {
+                    return Err(HorizonError::Malformed {
+                        line: index + 1,
+                        content: raw.to_string(),
+                    })
+                }
+            }
+            seen_content = true;
+        }
+        if points.is_empty() {
+            return Err(HorizonError::NoPoints);
+        }
+        Self::new(points)
+    }
+
+    /// The profile's points, sorted by azimuth in `[0, 360)`.
+    #[must_use]
+    pub fn points(&self) -> &[HorizonPoint] {
+        &self.points
+    }
+
+    /// Whether the profile has no points, i.e. obstructs nothing.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// The obstruction altitude at `azimuth_degrees` (any value; taken
+    /// mod 360), linearly interpolated between the neighbouring points
+    /// and across north between the last and first. `-90` for the
+    /// empty profile.
+    #[must_use]
+    pub fn altitude_at(&self, azimuth_degrees: f64) -> f64 {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return -90.0;
+        };
+        let az = azimuth_degrees.rem_euclid(360.0);
+        for pair in self.points.windows(2) {
+            if let [a, b] = pair {
+                if (a.azimuth_degrees..=b.azimuth_degrees).contains(&az) {
+                    return interpolate(a, b.azimuth_degrees, b.altitude_degrees, az);
+                }
+            }
+        }
+        // The gap across north: from the last point round to the first,
+        // 360° on.
+        let az = if az < first.azimuth_degrees {
+            az + 360.0
+        } else {
+            az
+        };
+        interpolate(
+            last,
+            first.azimuth_degrees + 360.0,
+            first.altitude_degrees,
+            az,
+        )
+    }
+
+    /// The altitude a target at `azimuth_degrees` must clear: the
+    /// higher of `min_altitude_degrees` and the profile there.
+    #[must_use]
+    pub fn floor_at(&self, azimuth_degrees: f64, min_altitude_degrees: f64) -> f64 {
+        min_altitude_degrees.max(self.altitude_at(azimuth_degrees))
+    }
+}
+
+/// Linear interpolation from `a` to `(b_az, b_alt)` at `az`. A
+/// zero-width span (the single-point profile) is flat.
+fn interpolate(a: &HorizonPoint, b_az: f64, b_alt: f64, az: f64) -> f64 {
+    let span = b_az - a.azimuth_degrees;
+    if span <= 0.0 {
+        return a.altitude_degrees;
+    }
+    a.altitude_degrees + (b_alt - a.altitude_degrees) * (az - a.azimuth_degrees) / span
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn point(azimuth_degrees: f64, altitude_degrees: f64) -> HorizonPoint {
+        HorizonPoint {
+            azimuth_degrees,
+            altitude_degrees,
+        }
+    }
+
+    #[test]
+    fn empty_profile_obstructs_nothing() {
+        let p = HorizonProfile::default();
+        assert!(p.is_empty());
+        assert_eq!(p.floor_at(123.0, 20.0), 20.0);
+    }
+
+    #[test]
+    fn single_point_is_a_flat_horizon() {
+        let p = HorizonProfile::new(vec![point(90.0, 12.0)]).unwrap();
+        assert_eq!(p.altitude_at(0.0), 12.0);
+        assert_eq!(p.altitude_at(270.0), 12.0);
+    }
+
+    #[test]
+    fn interpolates_between_points_and_across_north() {
+        // Trees east (30° at 90°), house south (40° at 180°), open west.
+        let p = HorizonProfile::new(vec![
+            point(180.0, 40.0),
+            point(90.0, 30.0),
+            point(270.0, 10.0),
+        ])
+        .unwrap();
+        assert!((p.altitude_at(135.0) - 35.0).abs() < 1e-9);
+        // 270° → 90° across north is a 180° span: at 0° it's halfway.
+        assert!((p.altitude_at(0.0) - 20.0).abs() < 1e-9);
+        assert!((p.altitude_at(360.0) - 20.0).abs() < 1e-9);
+        assert!((p.altitude_at(-90.0) - 10.0).abs() < 1e-9);
+        assert_eq!(p.floor_at(180.0, 20.0), 40.0);
+        assert_eq!(p.floor_at(270.0, 20.0), 20.0);
+    }
+
+    #[test]
+    fn azimuth_360_folds_onto_north_keeping_the_higher_altitude() {
+        let p = HorizonProfile::new(vec![point(0.0, 5.0), point(360.0, 8.0)]).unwrap();
+        assert_eq!(p.points(), &[point(0.0, 8.0)]);
+    }
+
+    #[test]
+    fn out_of_range_points_are_rejected() {
+        assert_eq!(
+            HorizonProfile::new(vec![point(361.0, 0.0)]),
+            Err(HorizonError::AzimuthOutOfRange(361.0))
+        );
+        assert_eq!(
+            HorizonProfile::new(vec![point(10.0, 91.0)]),
+            Err(HorizonError::AltitudeOutOfRange(91.0))
+        );
+    }
+
+    #[test]
+    fn parses_whitespace_csv_comments_and_a_header() {
+        let text = "# N.I.N.A. style\n\
+                    azimuth,altitude\n\
+                    0 10\n\
+                    90\t25   // trees\n\
+                    \n\
+                    180;30;extra\n\
+                    270, 5\n";
+        let p = HorizonProfile::parse(text).unwrap();
+        assert_eq!(
+            p.points(),
+            &[
+                point(0.0, 10.0),
+                point(90.0, 25.0),
+                point(180.0, 30.0),
+                point(270.0, 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_reports_the_malformed_line() {
+        let err = HorizonProfile::parse("0 10\n90 x\n").unwrap_err();
+        assert_eq!(
+            err,
+            HorizonError::Malformed {
+                line: 2,
+                content: "90 x".to_string()
+            }
+        );
+        assert_eq!(
+            HorizonProfile::parse("# nothing\n"),
+            Err(HorizonError::NoPoints)
+        );
+    }
+}
@@ -12,12 +12,16 @@
 #![deny(unsafe_code)]
 
 mod derived;
+mod dome;
 mod erfars_impl;
+mod horizon;
 mod site;
 mod types;
 mod vocabulary;
 
+pub use dome::DomeGeometry;
 pub use erfars_impl::ErfarsEphemeris;
+pub use horizon::{HorizonError, HorizonPoint, HorizonProfile};
 pub use site::{Site, SiteError};
 pub use types::{
     AltAz, EphemerisError, IcrsCoord, LocalSiderealTime, MoonInfo, RefractionConditions, RiseSet,
@@ -54,15 +58,20 @@ pub trait Ephemeris {
     /// `Some` for every target every day.
     fn transit(&self, site: &Site, target: IcrsCoord, date: NaiveDate) -> Option<DateTime<Utc>>;
 
-    /// Rise and set times above `min_alt_deg` on the given UTC date.
-    /// `None` if the target never reaches `min_alt_deg` (always-down
-    /// circumpolar) or never falls below it (always-up circumpolar).
+    /// Rise and set times on the given UTC date above the floor
+    /// `horizon.floor_at(azimuth, min_alt_deg)` — `min_alt_deg`, raised
+    /// wherever the site's horizon profile is higher (pass
+    /// `&HorizonProfile::default()` for a flat floor). `None` if the
+    /// target never clears the floor (always-down circumpolar, or
+    /// hidden all night) or never falls below it (always-up
+    /// circumpolar).
     fn rise_set(
         &self,
         site: &Site,
         target: IcrsCoord,
         date: NaiveDate,
         min_alt_deg: f64,
+        horizon: &HorizonProfile,
     ) -> Option<RiseSet>;
 
     /// Time until the target next crosses the meridian (HA = 0). Side
@@ -104,6 +104,22 @@ pub struct MoonInfo {
     pub illumination_fraction: f64,
 }
 
+/// Mean synodic month, in days — new Moon to new Moon.
+const SYNODIC_MONTH_DAYS: f64 = 29.530_589;
+
+impl MoonInfo {
+    /// Moon age measured from full, in days `[0, ~14.77]`: 0 at full,
+    /// half a synodic month at new. Derived from the elongation
+    /// assuming the Moon's elongation advances uniformly, which is good
+    /// to a few hours — ample for the day-scale curves (e.g. Lorentzian
+    /// Moon avoidance) that key on it. Waxing and waning are not told
+    /// apart; the Moon's glare is symmetric about full.
+    #[must_use]
+    pub fn days_from_full(&self) -> f64 {
+        (180.0 - self.phase_degrees) / 180.0 * (SYNODIC_MONTH_DAYS / 2.0)
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum EphemerisError {
     #[error("ERFA reported an unrepresentable time/date input (status {0})")]
@@ -111,3 +127,28 @@ pub enum EphemerisError {
     #[error("ERFA refused the alt/az transform (status {0}); inputs out of valid range")]
     InvalidAltAzInputs(i32),
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_from_full_spans_full_to_new_moon() {
+        let moon = |phase_degrees: f64| MoonInfo {
+            coords: IcrsCoord {
+                ra_hours: 0.0,
+                dec_degrees: 0.0,
+            },
+            alt_az: AltAz {
+                altitude_degrees: 0.0,
+                azimuth_degrees: 0.0,
+            },
+            phase_degrees,
+            illumination_fraction: 0.0,
+        };
+        assert!(moon(180.0).days_from_full().abs() < 1e-12);
+        assert!((moon(90.0).days_from_full() - 7.38).abs() < 0.01);
+        assert!((moon(0.0).days_from_full() - 14.77).abs() < 0.01);
+    }
+}
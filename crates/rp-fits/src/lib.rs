@@ -1,7 +1,9 @@
 //! FITS reader/writer wrapper used by every workspace consumer that
 //! needs FITS I/O. Internally delegates reads to [`fitsrs`] and emits
 //! writes via a hand-rolled pure-Rust serializer that supports BITPIX
-//! 8/16/32 (integer) image HDUs.
+//! 8/16/32 (integer) image HDUs, plain or Rice tile-compressed.
+//! [`xisf`] reads and writes the same images as XISF, `PixInsight`'s
+//! native format, with the same keyword model.
 //!
 //! See `docs/decisions/001-fits-file-support.md` (Amendment A) for the
 //! design rationale.
@@ -10,7 +12,11 @@
 
 pub mod atomic;
 pub mod error;
+mod lz4;
 pub mod reader;
+mod rice;
+mod tiled;
 pub mod writer;
+pub mod xisf;
 
 pub use error::FitsError;
@@ -0,0 +1,219 @@
+//! LZ4 block codec, as XISF's `lz4` / `lz4hc` data-block compression
+//! uses it: raw blocks with no frame header, the uncompressed size
+//! carried out-of-band in the `compression` attribute.
+//!
+//! The compressor is the single-pass greedy matcher of the reference
+//! implementation's fast mode (4-byte hash, one candidate per bucket),
+//! and honours the block format's end-of-block rules — the last five
+//! bytes are literals and no match starts within the last twelve — so
+//! any conforming decoder accepts its output. The decoder handles
+//! `lz4hc` blocks too: the two differ only in how hard the compressor
+//! searched.
+
+use crate::error::FitsError;
+
+const MIN_MATCH: usize = 4;
+/// The block always ends in at least this many literals.
+const LAST_LITERALS: usize = 5;
+/// No match may start closer than this to the end of the block.
+const MF_LIMIT: usize = 12;
+const MAX_OFFSET: usize = 65_535;
+const HASH_LOG: u32 = 12;
+
+/// Compress `input` into a single LZ4 block.
+pub(crate) fn compress(input: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(input.len() / 2 + 16);
+    let mut anchor = 0;
+    if input.len() > MF_LIMIT {
+        // Bucket entries are positions + 1, so zero means empty.
+        let mut table = vec![0usize; 1 << HASH_LOG];
+        let limit = input.len() - MF_LIMIT;
+        let match_end_limit = input.len() - LAST_LITERALS;
+        let mut pos = 0;
+        while pos < limit {
+            let seq = read_u32(input, pos);
+            let bucket = hash(seq);
+            let candidate = table[bucket];
+            table[bucket] = pos + 1;
+            if let Some(cand) = candidate.checked_sub(1) {
+                if pos - cand <= MAX_OFFSET && read_u32(input, cand) == seq {
+                    let mut len = MIN_MATCH;
+                    while pos + len < match_end_limit && input[cand + len] == input[pos + len] {
+                        len += 1;
+                    }
+                    push_sequence(&mut out, &input[anchor..pos], pos - cand, len);
+                    pos += len;
+                    anchor = pos;
+                    continue;
+                }
+            }
+            pos += 1;
+        }
+    }
+    let literals = &input[anchor..];
+    out.push(nibble(literals.len()) << 4);
+    push_length(&mut out, literals.len());
+    out.extend_from_slice(literals);
+    out
+}
+
+/// Decompress one LZ4 block that must expand to exactly `size` bytes.
+pub(crate) fn decompress(input: &[u8], size: usize) -> Result<Vec<u8>, FitsError> {
+    let corrupt = |what: &str| FitsError::Parse(format!("corrupt LZ4 block: {what}"));
+    // `size` comes from the file. An LZ4 block expands at most 255-fold,
+    // so a claim beyond that is corrupt; capping the reservation keeps a
+    // crafted header from allocating it up front.
+    let mut out = Vec::with_capacity(size.min(input.len().saturating_mul(255)));
+    let mut pos = 0;
+    while let Some(&token) = input.get(pos) {
+        pos += 1;
+        let literals = read_length(input, &mut pos, token >> 4)?;
+        let end = pos
+            .checked_add(literals)
+            .filter(|&end| end <= input.len() && out.len() + literals <= size)
+            .ok_or_else(|| corrupt("literal run overruns the block"))?;
+        out.extend_from_slice(&input[pos..end]);
+        pos = end;
+        if pos == input.len() {
+            break;
+        }
+        let offset = input
+            .get(pos..pos + 2)
+            .map(|b| usize::from(u16::from_le_bytes([b[0], b[1]])))
+            .ok_or_else(|| corrupt("truncated match offset"))?;
+        pos += 2;
+        let len = read_length(input, &mut pos, token & 0x0F)? + MIN_MATCH;
+        if offset == 0 || offset > out.len() {
+            return Err(corrupt("match offset outside the output"));
+        }
+        if out.len() + len > size {
+            return Err(corrupt("match overruns the declared size"));
+        }
+        // Byte by byte: a match may overlap the bytes it produces.
+        let start = out.len() - offset;
+        for i in start..start + len {
+            let b = out[i];
+            out.push(b);
+        }
+    }
+    if out.len() != size {
+        return Err(corrupt(&format!(
+            "decoded {} bytes, expected {size}",
+            out.len()
+        )));
+    }
+    Ok(out)
+}
+
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes([data[at], data[at + 1], data[at + 2], data[at + 3]])
+}
+
+const fn hash(seq: u32) -> usize {
+    (seq.wrapping_mul(2_654_435_761) >> (32 - HASH_LOG)) as usize
+}
+
+fn nibble(len: usize) -> u8 {
+    u8::try_from(len.min(15)).unwrap_or(15)
+}
+
+fn push_sequence(out: &mut Vec<u8>, literals: &[u8], offset: usize, match_len: usize) {
+    let extra = match_len - MIN_MATCH;
+    out.push(nibble(literals.len()) << 4 | nibble(extra));
+    push_length(out, literals.len());
+    out.extend_from_slice(literals);
+    // `offset` is at most MAX_OFFSET, the caller's bound.
+    out.extend_from_slice(&(offset as u16).to_le_bytes());
+    push_length(out, extra);
+}
+
+/// The 255-run continuation of a length whose token nibble saturated.
+fn push_length(out: &mut Vec<u8>, len: usize) {
+    if len < 15 {
+        return;
+    }
+    let mut rest = len - 15;
+    while rest >= 255 {
+        out.push(255);
+        rest -= 255;
+    }
+    out.push(rest as u8);
+}
+
+fn read_length(input: &[u8], pos: &mut usize, token_nibble: u8) -> Result<usize, FitsError> {
+    let mut len = usize::from(token_nibble);
+    if len < 15 {
+        return Ok(len);
+    }
+    loop {
+        let &b = input
+            .get(*pos)
+            .ok_or_else(|| FitsError::Parse("corrupt LZ4 block: truncated length".into()))?;
+        *pos += 1;
+        len = len
+            .checked_add(usize::from(b))
+            .ok_or_else(|| FitsError::Parse("corrupt LZ4 block: length overflows".into()))?;
+        if b != 255 {
+            return Ok(len);
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &[u8]) -> Vec<u8> {
+        let packed = compress(data);
+        assert_eq!(decompress(&packed, data.len()).unwrap(), data);
+        packed
+    }
+
+    #[test]
+    fn round_trips_short_and_incompressible_input() {
+        round_trip(&[]);
+        round_trip(b"abc");
+        let noise: Vec<u8> = (0u32..1000)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect();
+        round_trip(&noise);
+    }
+
+    #[test]
+    fn repetitive_input_compresses_with_long_runs() {
+        let data: Vec<u8> = b"rusty-photon "
+            .iter()
+            .copied()
+            .cycle()
+            .take(5000)
+            .collect();
+        let packed = round_trip(&data);
+        assert!(packed.len() < 100, "packed to {} bytes", packed.len());
+        // A 5000-byte run of one value needs both 255-continued lengths
+        // and an overlapping offset-1 match.
+        round_trip(&[7u8; 5000]);
+    }
+
+    #[test]
+    fn decodes_a_hand_assembled_block() {
+        // "abcd" literal, then a 6-byte offset-4 match, then "xyzzy".
+        let block = [
+            0x42, b'a', b'b', b'c', b'd', 4, 0, 0x50, b'x', b'y', b'z', b'z', b'y',
+        ];
+        assert_eq!(decompress(&block, 15).unwrap(), b"abcdabcdabxyzzy".to_vec());
+    }
+
+    #[test]
+    fn rejects_corrupt_blocks() {
+        // Offset reaching before the start of the output.
+        assert!(decompress(&[0x10, b'a', 2, 0, 0x00], 10).is_err());
+        // Declared size disagrees with the block.
+        assert!(decompress(&compress(b"hello"), 6).is_err());
+        // Literal run past the end of the input.
+        assert!(decompress(&[0x50, b'a'], 5).is_err());
+        // A declared size no block could expand to fails without
+        // reserving it.
+        assert!(decompress(&[0x10, b'a'], usize::MAX).is_err());
+    }
+}
@@ -1,7 +1,6 @@
 //! Thin reader facade over [`fitsrs`].
 //!
-//! Three entry points cover what the workspace's three FITS consumers
-//! need:
+//! Four entry points cover what the workspace's FITS consumers need:
 //!
 //! - [`read_primary`] returns the on-disk pixel type plus header
 //!   metadata (`bscale`, `bzero`, `blank`). Callers that need exact
@@ -9,14 +8,26 @@
 //! - [`read_primary_as_i32`] applies BSCALE/BZERO and saturates to
 //!   `i32`, matching the `Vec<i32>` shape sky-survey-camera and rp's
 //!   imaging pipeline expect.
+//! - [`read_primary_as_f32`] applies BSCALE/BZERO without saturating,
+//!   for float images (calibrated masters, stacks); undefined pixels
+//!   come back as NaN. [`FitsImage::into_i32`] / [`FitsImage::into_f32`]
+//!   do the same conversions on an already-read image, for callers that
+//!   pick the path by [`Pixels::is_float`].
 //! - [`read_primary_keyword`] reads only the primary header — much
 //!   cheaper than [`read_primary`] when the caller only needs one
 //!   keyword (e.g. rp's `DOC_ID` lookup).
 //!
+//! All of them also read a Rice tile-compressed image (the `ZIMAGE`
+//! convention [`crate::writer::write_u16_image_rice`] emits, and
+//! `fpack`'s output): the stream is probed for the convention first
+//! and, when it matches, the image and its header are taken from the
+//! compressed extension as if they were the primary HDU.
+//!
 //! BLANK handling: the raw integer sentinel value is surfaced via
-//! [`FitsImage::blank`] and **not** filtered or replaced. Per
-//! ADR-001 Amendment A, silently dropping pixels (the previous
-//! `fitrs`-based path's behaviour) is a bug we're fixing here.
+//! [`FitsImage::blank`] and **not** filtered or replaced — the `f32`
+//! path maps it to NaN, which keeps the pixel in place. Per ADR-001
+//! Amendment A, silently dropping pixels (the previous `fitrs`-based
+//! path's behaviour) is a bug we're fixing here.
 
 use std::fmt::Debug;
 use std::io::{Read, Seek};
@@ -78,12 +89,22 @@ impl Pixels {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Whether the on-disk type is IEEE floating point (BITPIX -32/-64).
+    #[must_use]
+    pub const fn is_float(&self) -> bool {
+        matches!(self, Self::F32(_) | Self::F64(_))
+    }
 }
 
 /// Read the primary HDU of a FITS stream. Returns the on-disk pixel
 /// data plus BSCALE/BZERO/BLANK metadata. The reader must support
 /// seeking — `Cursor<&[u8]>` and `BufReader<File>` both qualify.
-pub fn read_primary<R: Read + Seek + Debug>(reader: R) -> Result<FitsImage, FitsError> {
+pub fn read_primary<R: Read + Seek + Debug>(mut reader: R) -> Result<FitsImage, FitsError> {
+    if let Some(header) = crate::tiled::probe(&mut reader)? {
+        return crate::tiled::read_image(&mut reader, &header);
+    }
+    reader.rewind()?;
     let mut hdu_list = Fits::from_reader(reader);
     let hdu = hdu_list
         .next()
@@ -155,36 +176,92 @@ pub fn read_primary_as_i32<R: Read + Seek + Debug>(
     reader: R,
 ) -> Result<(Vec<i32>, usize, usize), FitsError> {
     let img = read_primary(reader)?;
-    let scale = |v: f64| -> i32 {
-        let scaled = v * img.bscale + img.bzero;
-        if scaled.is_nan() {
-            0
-        } else if scaled >= f64::from(i32::MAX) {
-            i32::MAX
-        } else if scaled <= f64::from(i32::MIN) {
-            i32::MIN
-        } else {
-            scaled as i32
+    let (width, height) = (img.width, img.height);
+    Ok((img.into_i32(), width, height))
+}
+
+/// Read the primary HDU and return scaled-to-`f32` pixels in row-major
+/// order — the path for calibrated masters and stacks, whose values a
+/// saturating `i32` read would truncate. See [`FitsImage::into_f32`]
+/// for how undefined pixels come back.
+pub fn read_primary_as_f32<R: Read + Seek + Debug>(
+    reader: R,
+) -> Result<(Vec<f32>, usize, usize), FitsError> {
+    let img = read_primary(reader)?;
+    let (width, height) = (img.width, img.height);
+    Ok((img.into_f32(), width, height))
+}
+
+impl FitsImage {
+    /// Physical pixel values saturated to `i32`: BSCALE/BZERO applied,
+    /// NaN mapped to 0, fractions truncated. `BLANK` pixels are scaled
+    /// like any other.
+    #[must_use]
+    pub fn into_i32(self) -> Vec<i32> {
+        let (bscale, bzero) = (self.bscale, self.bzero);
+        let scale = |v: f64| -> i32 {
+            let scaled = v * bscale + bzero;
+            if scaled.is_nan() {
+                0
+            } else if scaled >= f64::from(i32::MAX) {
+                i32::MAX
+            } else if scaled <= f64::from(i32::MIN) {
+                i32::MIN
+            } else {
+                scaled as i32
+            }
+        };
+        match self.data {
+            Pixels::U8(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
+            Pixels::I16(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
+            Pixels::I32(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
+            Pixels::I64(v) => v.into_iter().map(|p| scale(p as f64)).collect(),
+            Pixels::F32(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
+            Pixels::F64(v) => v.into_iter().map(scale).collect(),
         }
-    };
-    let pixels: Vec<i32> = match img.data {
-        Pixels::U8(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
-        Pixels::I16(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
-        Pixels::I32(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
-        Pixels::I64(v) => v.into_iter().map(|p| scale(p as f64)).collect(),
-        Pixels::F32(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
-        Pixels::F64(v) => v.into_iter().map(scale).collect(),
-    };
-    Ok((pixels, img.width, img.height))
+    }
+
+    /// Physical pixel values as `f32`, BSCALE/BZERO applied. Undefined
+    /// pixels come back as NaN under either FITS convention: a float
+    /// image's NaN passes through, and an integer image's raw `BLANK`
+    /// value is mapped to NaN instead of being scaled.
+    #[must_use]
+    pub fn into_f32(self) -> Vec<f32> {
+        let (bscale, bzero, blank) = (self.bscale, self.bzero, self.blank);
+        let scale = |v: f64| (v * bscale + bzero) as f32;
+        let int = |raw: i64| {
+            if blank == Some(raw) {
+                f32::NAN
+            } else {
+                scale(raw as f64)
+            }
+        };
+        match self.data {
+            Pixels::U8(v) => v.into_iter().map(|p| int(p.into())).collect(),
+            Pixels::I16(v) => v.into_iter().map(|p| int(p.into())).collect(),
+            Pixels::I32(v) => v.into_iter().map(|p| int(p.into())).collect(),
+            Pixels::I64(v) => v.into_iter().map(int).collect(),
+            Pixels::F32(v) => v.into_iter().map(|p| scale(f64::from(p))).collect(),
+            Pixels::F64(v) => v.into_iter().map(scale).collect(),
+        }
+    }
 }
 
 /// Read a single keyword from the primary HDU's header. Cheaper than
 /// [`read_primary`] when the caller only needs metadata (e.g. rp's
 /// `DOC_ID` resolver). Returns `Ok(None)` when the keyword is absent.
+/// For a tile-compressed file the keyword is looked up on the
+/// compressed image's header, with `BITPIX`/`NAXIS*` answered from
+/// their `Z*` counterparts.
 pub fn read_primary_keyword<R: Read + Seek + Debug>(
-    reader: R,
+    mut reader: R,
     key: &str,
 ) -> Result<Option<KeywordValue>, FitsError> {
+    let upper = key.to_ascii_uppercase();
+    if let Some(header) = crate::tiled::probe(&mut reader)? {
+        return Ok(header.image_keyword(&upper).cloned());
+    }
+    reader.rewind()?;
     let mut hdu_list = Fits::from_reader(reader);
     let hdu = hdu_list
         .next()
@@ -195,7 +272,6 @@ pub fn read_primary_keyword<R: Read + Seek + Debug>(
             "first HDU is not a primary image HDU".into(),
         ));
     };
-    let upper = key.to_ascii_uppercase();
     let value = image_hdu.get_header().get(upper.as_str());
     match value {
         None => Ok(None),
@@ -233,7 +309,10 @@ mod tests {
     use super::*;
     use std::io::Cursor;
 
-    use crate::writer::{write_i32_image, write_u16_image, write_u8_image, Keyword};
+    use crate::writer::{
+        write_f32_image, write_f64_image, write_i32_image, write_i32_image_rice, write_i64_image,
+        write_u16_image, write_u16_image_rice, write_u8_image, Keyword,
+    };
 
     #[test]
     fn round_trip_i32() {
@@ -324,6 +403,53 @@ mod tests {
         assert_eq!(got, pixels);
     }
 
+    #[test]
+    fn read_primary_as_f32_keeps_fractions_and_nan() {
+        let pixels = vec![0.125f32, -7.5, f32::NAN, 1.0e6];
+        let mut buf = Vec::new();
+        write_f32_image(&mut buf, &pixels, 2, 2, &[]).unwrap();
+        let img = read_primary(Cursor::new(&buf[..])).unwrap();
+        assert!(img.data.is_float());
+        let (got, w, h) = read_primary_as_f32(Cursor::new(&buf[..])).unwrap();
+        assert_eq!((w, h), (2, 2));
+        assert_eq!((got[0], got[1], got[3]), (0.125, -7.5, 1.0e6));
+        assert!(got[2].is_nan());
+
+        // The saturating path still truncates, and NaN becomes 0.
+        let (ints, _, _) = read_primary_as_i32(Cursor::new(&buf[..])).unwrap();
+        assert_eq!(ints, vec![0, -7, 0, 1_000_000]);
+    }
+
+    #[test]
+    fn read_primary_as_f32_narrows_f64() {
+        let mut buf = Vec::new();
+        write_f64_image(&mut buf, &[0.5, f64::NAN, 1e10], 3, 1, &[]).unwrap();
+        let (got, _, _) = read_primary_as_f32(Cursor::new(&buf[..])).unwrap();
+        assert_eq!((got[0], got[2]), (0.5, 1e10));
+        assert!(got[1].is_nan());
+    }
+
+    #[test]
+    fn read_primary_as_f32_maps_blank_to_nan() {
+        let blank = [Keyword::new("BLANK", KeywordValue::Int(-1)).unwrap()];
+        let mut buf = Vec::new();
+        write_i64_image(&mut buf, &[5, -1, 1 << 40, 0], 2, 2, &blank).unwrap();
+        let img = read_primary(Cursor::new(&buf[..])).unwrap();
+        assert_eq!(img.blank, Some(-1));
+        assert!(!img.data.is_float());
+        let got = img.into_f32();
+        assert_eq!((got[0], got[2], got[3]), (5.0, (1u64 << 40) as f32, 0.0));
+        assert!(got[1].is_nan());
+
+        // u16 through BZERO: BLANK is compared against the raw value.
+        let blank = [Keyword::new("BLANK", KeywordValue::Int(-32768)).unwrap()];
+        let mut buf = Vec::new();
+        write_u16_image(&mut buf, &[0, 1, 65535], 3, 1, &blank).unwrap();
+        let (got, _, _) = read_primary_as_f32(Cursor::new(&buf[..])).unwrap();
+        assert!(got[0].is_nan());
+        assert_eq!((got[1], got[2]), (1.0, 65535.0));
+    }
+
     #[test]
     fn read_primary_keyword_returns_string() {
         let mut buf = Vec::new();
@@ -422,4 +548,90 @@ mod tests {
         let err = read_primary(Cursor::new(&bytes[..])).unwrap_err();
         assert!(matches!(err, FitsError::Unsupported(_)));
     }
+
+    /// A sky-like u16 frame: smooth background, a few hot pixels and
+    /// both ends of the range.
+    fn sky_u16(width: usize, height: usize) -> Vec<u16> {
+        (0..width * height)
+            .map(|i| match i % 97 {
+                0 => 65535,
+                1 => 0,
+                _ => (1200 + (i / width) * 3 + i % 5) as u16,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rice_u16_round_trips_to_physical_values() {
+        let pixels = sky_u16(70, 9);
+        let mut buf = Vec::new();
+        write_u16_image_rice(&mut buf, &pixels, 70, 9, &[]).unwrap();
+        let (got, w, h) = read_primary_as_i32(Cursor::new(&buf[..])).unwrap();
+        assert_eq!((w, h), (70, 9));
+        assert_eq!(
+            got,
+            pixels.iter().map(|&p| i32::from(p)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn rice_u16_is_smaller_than_plain() {
+        let pixels = sky_u16(512, 64);
+        let (mut plain, mut rice) = (Vec::new(), Vec::new());
+        write_u16_image(&mut plain, &pixels, 512, 64, &[]).unwrap();
+        write_u16_image_rice(&mut rice, &pixels, 512, 64, &[]).unwrap();
+        assert!(
+            rice.len() * 2 < plain.len(),
+            "{} vs {}",
+            rice.len(),
+            plain.len()
+        );
+    }
+
+    #[test]
+    fn rice_i32_round_trips_through_read_primary() {
+        let pixels = vec![1i32, -1, 1_000_000, i32::MIN, i32::MAX, 0];
+        let mut buf = Vec::new();
+        write_i32_image_rice(&mut buf, &pixels, 3, 2, &[]).unwrap();
+        let img = read_primary(Cursor::new(&buf[..])).unwrap();
+        assert_eq!((img.width, img.height), (3, 2));
+        match img.data {
+            Pixels::I32(v) => assert_eq!(v, pixels),
+            other => panic!("expected I32, got {other:?}"),
+        }
+        assert_eq!(img.bzero, 0.0);
+    }
+
+    #[test]
+    fn rice_keywords_are_read_from_the_compressed_header() {
+        let kw = vec![Keyword::new("DOC_ID", KeywordValue::Str("uuid-here".into())).unwrap()];
+        let mut buf = Vec::new();
+        write_u16_image_rice(&mut buf, &[0u16; 6], 3, 2, &kw).unwrap();
+        let key = |k: &str| read_primary_keyword(Cursor::new(&buf[..]), k).unwrap();
+        assert_eq!(key("doc_id"), Some(KeywordValue::Str("uuid-here".into())));
+        // The image's geometry, not the binary table's.
+        assert_eq!(key("NAXIS1"), Some(KeywordValue::Int(3)));
+        assert_eq!(key("BITPIX"), Some(KeywordValue::Int(16)));
+        assert_eq!(key("OBJECT"), None);
+    }
+
+    #[test]
+    fn rice_truncated_heap_is_a_parse_error() {
+        let pixels = sky_u16(64, 64);
+        let mut buf = Vec::new();
+        write_u16_image_rice(&mut buf, &pixels, 64, 64, &[]).unwrap();
+        buf.truncate(2 * 2880 + 100);
+        let err = read_primary(Cursor::new(&buf[..])).unwrap_err();
+        assert!(matches!(err, FitsError::Parse(_)), "{err:?}");
+    }
+
+    #[test]
+    fn other_tile_compressions_are_unsupported() {
+        let mut buf = Vec::new();
+        write_u16_image_rice(&mut buf, &[7u16; 4], 2, 2, &[]).unwrap();
+        let at = buf.windows(10).position(|w| w == b"'RICE_1  '").unwrap();
+        buf[at..at + 10].copy_from_slice(b"'GZIP_1  '");
+        let err = read_primary(Cursor::new(&buf[..])).unwrap_err();
+        assert!(matches!(err, FitsError::Unsupported(_)), "{err:?}");
+    }
 }
@@ -0,0 +1,532 @@
+//! Reader side of the FITS tiled-image convention (`ZIMAGE = T`).
+//!
+//! `fitsrs` reads the primary HDU of a compressed file as the empty
+//! image it is, so [`crate::reader`] probes for the convention first:
+//! an empty primary (`NAXIS = 0`) followed by a `BINTABLE` extension
+//! carrying `ZIMAGE = T`. Only that extension header is parsed here —
+//! a plain file costs one extra primary-header read before the stream
+//! is rewound and handed to `fitsrs`.
+//!
+//! Supported: integer images (`ZBITPIX` 8/16/32) under `RICE_1`, any
+//! 2-D tiling, `BYTEPIX` matching the pixel width. Anything else in
+//! the convention (GZIP, quantized floats, per-tile fallback columns)
+//! is [`FitsError::Unsupported`].
+
+use std::io::Read;
+
+use crate::error::FitsError;
+use crate::reader::{FitsImage, Pixels};
+use crate::rice;
+use crate::writer::{KeywordValue, BLOCK_SIZE, CARD_SIZE};
+
+/// The `KEY = value` cards of one header, in order. Commentary cards
+/// and values this reader cannot type are dropped.
+#[derive(Debug, Default)]
+pub(crate) struct Header {
+    cards: Vec<(String, KeywordValue)>,
+}
+
+impl Header {
+    pub(crate) fn get(&self, key: &str) -> Option<&KeywordValue> {
+        self.cards.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+
+    fn int(&self, key: &str) -> Option<i64> {
+        match self.get(key)? {
+            KeywordValue::Int(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn float(&self, key: &str) -> Option<f64> {
+        match self.get(key)? {
+            KeywordValue::Float(f) => Some(*f),
+            KeywordValue::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    fn text(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            KeywordValue::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn flag(&self, key: &str) -> bool {
+        matches!(self.get(key), Some(KeywordValue::Bool(true)))
+    }
+
+    /// A required non-negative integer card, as a size.
+    fn size(&self, key: &'static str) -> Result<usize, FitsError> {
+        let n = self.int(key).ok_or(FitsError::MissingKeyword(key))?;
+        usize::try_from(n).map_err(|_| FitsError::Parse(format!("{key} out of range: {n}")))
+    }
+
+    /// The header keyword a plain file would carry for `key`: the
+    /// compressed image's `BITPIX`/`NAXIS*` are stored as `Z*` cards,
+    /// the unprefixed ones describing the binary table instead.
+    pub(crate) fn image_keyword(&self, key: &str) -> Option<&KeywordValue> {
+        match key {
+            "BITPIX" | "NAXIS" | "NAXIS1" | "NAXIS2" => self.get(&format!("Z{key}")),
+            _ => self.get(key),
+        }
+    }
+}
+
+/// The compressed image's extension header when `reader` (positioned
+/// at the start of the stream) holds a tile-compressed image, leaving
+/// the reader at the extension's data. `None` for anything else,
+/// including streams too broken to tell — the caller rewinds and lets
+/// `fitsrs` report those.
+pub(crate) fn probe<R: Read>(reader: &mut R) -> Result<Option<Header>, FitsError> {
+    let Ok(primary) = read_header(reader) else {
+        return Ok(None);
+    };
+    if primary.int("NAXIS") != Some(0) {
+        return Ok(None);
+    }
+    let Ok(ext) = read_header(reader) else {
+        return Ok(None);
+    };
+    if ext.text("XTENSION") != Some("BINTABLE") || !ext.flag("ZIMAGE") {
+        return Ok(None);
+    }
+    Ok(Some(ext))
+}
+
+/// Decode the compressed image whose extension header [`probe`] just
+/// returned. BSCALE/BZERO/BLANK come from the same header, as they do
+/// for `funpack`.
+pub(crate) fn read_image<R: Read>(reader: &mut R, header: &Header) -> Result<FitsImage, FitsError> {
+    let cmptype = header
+        .text("ZCMPTYPE")
+        .ok_or(FitsError::MissingKeyword("ZCMPTYPE"))?;
+    if cmptype != "RICE_1" && cmptype != "RICE_ONE" {
+        return Err(FitsError::Unsupported(format!(
+            "tile compression {cmptype} (only RICE_1)"
+        )));
+    }
+    if header.size("ZNAXIS")? != 2 {
+        return Err(FitsError::Unsupported(format!(
+            "only 2-D images are supported (ZNAXIS = {})",
+            header.size("ZNAXIS")?
+        )));
+    }
+    let width = header.size("ZNAXIS1")?;
+    let height = header.size("ZNAXIS2")?;
+    let tile_w = header.size("ZTILE1").unwrap_or(width).max(1);
+    let tile_h = header.size("ZTILE2").unwrap_or(1).max(1);
+    let zbitpix = header
+        .int("ZBITPIX")
+        .ok_or(FitsError::MissingKeyword("ZBITPIX"))?;
+    let (block_size, bytepix) = rice_params(header)?;
+    if i64::from(bytepix) * 8 != zbitpix {
+        return Err(FitsError::Unsupported(format!(
+            "Rice BYTEPIX {bytepix} for ZBITPIX {zbitpix}"
+        )));
+    }
+
+    let tiles = Tiles::read(reader, header)?;
+    let grid = Grid {
+        width,
+        height,
+        tile_w,
+        tile_h,
+    };
+    if tiles.count() != grid.count() {
+        return Err(FitsError::Parse(format!(
+            "{} table rows for {} tiles",
+            tiles.count(),
+            grid.count()
+        )));
+    }
+    grid.check(&tiles, |len| rice::max_pixels(bytepix, len, block_size))?;
+
+    let data = match zbitpix {
+        8 => Pixels::U8(grid.assemble(&tiles, |b, n| rice::decompress_u8(b, n, block_size))?),
+        16 => Pixels::I16(grid.assemble(&tiles, |b, n| rice::decompress_i16(b, n, block_size))?),
+        32 => Pixels::I32(grid.assemble(&tiles, |b, n| rice::decompress_i32(b, n, block_size))?),
+        other => {
+            return Err(FitsError::Unsupported(format!(
+                "compressed ZBITPIX {other} (integer images only)"
+            )));
+        }
+    };
+
+    Ok(FitsImage {
+        width,
+        height,
+        data,
+        bscale: header.float("BSCALE").unwrap_or(1.0),
+        bzero: header.float("BZERO").unwrap_or(0.0),
+        blank: header.int("BLANK").or_else(|| header.int("ZBLANK")),
+    })
+}
+
+/// Largest Rice `BLOCKSIZE` accepted. fpack writes 16 or 32; the cap
+/// keeps [`rice::max_pixels`] a meaningful bound against a hostile
+/// header.
+const MAX_BLOCK_SIZE: usize = 1024;
+
+/// `BLOCKSIZE` and `BYTEPIX` from the `ZNAMEi`/`ZVALi` pairs, with
+/// the convention's defaults (32 and 4).
+fn rice_params(header: &Header) -> Result<(usize, u8), FitsError> {
+    let mut block_size = rice::BLOCK_SIZE;
+    let mut bytepix = 4u8;
+    for i in 1.. {
+        let Some(name) = header.text(&format!("ZNAME{i}")) else {
+            break;
+        };
+        let value = header.int(&format!("ZVAL{i}"));
+        match (name, value) {
+            ("BLOCKSIZE", Some(v)) => {
+                block_size = usize::try_from(v)
+                    .ok()
+                    .filter(|&b| (1..=MAX_BLOCK_SIZE).contains(&b))
+                    .ok_or_else(|| FitsError::Parse(format!("Rice BLOCKSIZE out of range: {v}")))?;
+            }
+            ("BYTEPIX", Some(v @ (1 | 2 | 4))) => bytepix = v as u8,
+            ("BYTEPIX", v) => {
+                return Err(FitsError::Unsupported(format!("Rice BYTEPIX {v:?}")));
+            }
+            _ => {}
+        }
+    }
+    Ok((block_size, bytepix))
+}
+
+/// The `COMPRESSED_DATA` byte stream of every table row.
+struct Tiles {
+    data: Vec<u8>,
+    /// `(start, len)` of each row's stream within `data`.
+    spans: Vec<(usize, usize)>,
+}
+
+impl Tiles {
+    fn read<R: Read>(reader: &mut R, header: &Header) -> Result<Self, FitsError> {
+        let row_len = header.size("NAXIS1")?;
+        let rows = header.size("NAXIS2")?;
+        let pcount = header.size("PCOUNT")?;
+        let table_len = row_len
+            .checked_mul(rows)
+            .ok_or_else(|| FitsError::Parse("binary table size overflows".into()))?;
+        let heap_start = header.size("THEAP").unwrap_or(table_len);
+        let total = table_len
+            .checked_add(pcount)
+            .ok_or_else(|| FitsError::Parse("binary table size overflows".into()))?;
+
+        // `take` + `read_to_end` instead of a `total`-sized buffer: a
+        // corrupt PCOUNT then fails as truncation, not as an
+        // allocation of whatever it claims.
+        let mut data = Vec::new();
+        reader.by_ref().take(total as u64).read_to_end(&mut data)?;
+        if data.len() != total {
+            return Err(FitsError::Parse(format!(
+                "compressed image data truncated: {} of {total} bytes",
+                data.len()
+            )));
+        }
+
+        let (column, wide) = compressed_column(header)?;
+        let mut spans = Vec::with_capacity(rows);
+        for row in 0..rows {
+            let at = row * row_len + column;
+            let (len, offset) = if wide {
+                (read_be::<8>(&data, at)?, read_be::<8>(&data, at + 8)?)
+            } else {
+                (read_be::<4>(&data, at)?, read_be::<4>(&data, at + 4)?)
+            };
+            let start = heap_start
+                .checked_add(offset)
+                .filter(|s| s.checked_add(len).is_some_and(|end| end <= data.len()))
+                .ok_or_else(|| FitsError::Parse(format!("tile {row} points outside the heap")))?;
+            spans.push((start, len));
+        }
+        Ok(Self { data, spans })
+    }
+
+    fn count(&self) -> usize {
+        self.spans.len()
+    }
+
+    fn get(&self, index: usize) -> Option<&[u8]> {
+        let &(start, len) = self.spans.get(index)?;
+        self.data.get(start..start + len)
+    }
+}
+
+/// Byte offset of `COMPRESSED_DATA` within a table row, and whether
+/// its descriptors are the 64-bit `Q` form.
+fn compressed_column(header: &Header) -> Result<(usize, bool), FitsError> {
+    let fields = header.size("TFIELDS")?;
+    let mut offset = 0usize;
+    for i in 1..=fields {
+        let tform = header
+            .text(&format!("TFORM{i}"))
+            .ok_or_else(|| FitsError::Parse(format!("TFORM{i} missing")))?;
+        let (repeat, code, element) = parse_tform(tform)?;
+        if header.text(&format!("TTYPE{i}")) == Some("COMPRESSED_DATA") {
+            return match (repeat, code, element) {
+                (1, b'P', Some(b'B')) => Ok((offset, false)),
+                (1, b'Q', Some(b'B')) => Ok((offset, true)),
+                _ => Err(FitsError::Unsupported(format!(
+                    "COMPRESSED_DATA column form {tform}"
+                ))),
+            };
+        }
+        let width = match code {
+            b'L' | b'B' | b'A' => 1,
+            b'I' => 2,
+            b'J' | b'E' => 4,
+            b'K' | b'D' | b'C' | b'P' => 8,
+            b'M' | b'Q' => 16,
+            b'X' => {
+                offset += repeat.div_ceil(8);
+                continue;
+            }
+            other => {
+                return Err(FitsError::Parse(format!(
+                    "unknown TFORM{i} type {:?}",
+                    char::from(other)
+                )));
+            }
+        };
+        offset += repeat * width;
+    }
+    Err(FitsError::MissingKeyword("TTYPEn = 'COMPRESSED_DATA'"))
+}
+
+/// `rTa(max)` → repeat count, type code and, for a `P`/`Q`
+/// descriptor, the element type.
+fn parse_tform(tform: &str) -> Result<(usize, u8, Option<u8>), FitsError> {
+    let bytes = tform.trim().as_bytes();
+    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+    let repeat = if digits == 0 {
+        1
+    } else {
+        std::str::from_utf8(bytes.get(..digits).unwrap_or_default())
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| FitsError::Parse(format!("bad TFORM repeat: {tform}")))?
+    };
+    let code = *bytes
+        .get(digits)
+        .ok_or_else(|| FitsError::Parse(format!("bad TFORM: {tform}")))?;
+    Ok((repeat, code, bytes.get(digits + 1).copied()))
+}
+
+fn read_be<const N: usize>(data: &[u8], at: usize) -> Result<usize, FitsError> {
+    let bytes: [u8; N] = data
+        .get(at..at + N)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| FitsError::Parse("tile descriptor truncated".into()))?;
+    let mut value = 0u64;
+    for b in bytes {
+        value = (value << 8) | u64::from(b);
+    }
+    usize::try_from(value)
+        .ok()
+        // Descriptors are signed on disk; a negative one is corrupt.
+        .filter(|_| bytes.first().is_some_and(|b| b & 0x80 == 0))
+        .ok_or_else(|| FitsError::Parse("tile descriptor out of range".into()))
+}
+
+/// How the image is cut into tiles: row-major over the tile grid,
+/// edge tiles clipped to the image.
+struct Grid {
+    width: usize,
+    height: usize,
+    tile_w: usize,
+    tile_h: usize,
+}
+
+impl Grid {
+    const fn across(&self) -> usize {
+        self.width.div_ceil(self.tile_w)
+    }
+
+    fn count(&self) -> usize {
+        self.across()
+            .saturating_mul(self.height.div_ceil(self.tile_h))
+    }
+
+    /// Origin and clipped size `(x0, y0, w, h)` of tile `index`.
+    fn tile(&self, index: usize) -> (usize, usize, usize, usize) {
+        let x0 = (index % self.across()) * self.tile_w;
+        let y0 = (index / self.across()) * self.tile_h;
+        let w = self.tile_w.min(self.width - x0);
+        let h = self.tile_h.min(self.height - y0);
+        (x0, y0, w, h)
+    }
+
+    /// Check every tile's pixel count against what its compressed bytes
+    /// can decode to (`capacity`), before anything image-sized is
+    /// allocated: ZNAXISn come from the header, so a few-byte file could
+    /// otherwise demand gigabytes.
+    fn check(&self, tiles: &Tiles, capacity: impl Fn(usize) -> usize) -> Result<(), FitsError> {
+        for index in 0..self.count() {
+            let (_, _, w, h) = self.tile(index);
+            let bytes = tiles
+                .get(index)
+                .ok_or_else(|| FitsError::Parse(format!("tile {index} missing")))?;
+            if bytes.is_empty() && w * h > 0 {
+                return Err(FitsError::Unsupported(format!(
+                    "tile {index} has no COMPRESSED_DATA (uncompressed fallback)"
+                )));
+            }
+            if w * h > capacity(bytes.len()) {
+                return Err(FitsError::Parse(format!(
+                    "tile {index} claims {} pixels but holds {} bytes",
+                    w * h,
+                    bytes.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Decode and place every tile. Call [`Self::check`] first; that is
+    /// what bounds the image allocation here.
+    fn assemble<T: Copy + Default>(
+        &self,
+        tiles: &Tiles,
+        decode: impl Fn(&[u8], usize) -> Result<Vec<T>, FitsError>,
+    ) -> Result<Vec<T>, FitsError> {
+        let len = self
+            .width
+            .checked_mul(self.height)
+            .ok_or_else(|| FitsError::Parse("ZNAXIS overflows a buffer".into()))?;
+        let mut image = vec![T::default(); len];
+        for index in 0..self.count() {
+            let (x0, y0, w, h) = self.tile(index);
+            let bytes = tiles
+                .get(index)
+                .ok_or_else(|| FitsError::Parse(format!("tile {index} missing")))?;
+            let pixels = decode(bytes, w * h)?;
+            for (row, src) in pixels.chunks(w.max(1)).enumerate() {
+                let at = (y0 + row) * self.width + x0;
+                if let Some(dst) = image.get_mut(at..at + w) {
+                    dst.copy_from_slice(src);
+                }
+            }
+        }
+        Ok(image)
+    }
+}
+
+/// Read one header — 2880-byte blocks of cards up to `END`.
+fn read_header<R: Read>(reader: &mut R) -> Result<Header, FitsError> {
+    let mut header = Header::default();
+    let mut block = [0u8; BLOCK_SIZE];
+    loop {
+        reader.read_exact(&mut block)?;
+        for card in block.chunks(CARD_SIZE) {
+            let key = std::str::from_utf8(card.get(..8).unwrap_or_default())
+                .map_err(|_| FitsError::MalformedHeader("non-ASCII keyword".into()))?
+                .trim_end();
+            if key == "END" {
+                return Ok(header);
+            }
+            if card.get(8..10) != Some(b"= ".as_slice()) {
+                continue;
+            }
+            if let Some(value) = card.get(10..).and_then(parse_value) {
+                header.cards.push((key.to_string(), value));
+            }
+        }
+    }
+}
+
+/// The typed value of a card's value/comment field.
+pub(crate) fn parse_value(field: &[u8]) -> Option<KeywordValue> {
+    let text = std::str::from_utf8(field).ok()?.trim_start();
+    if let Some(quoted) = text.strip_prefix('\'') {
+        // Strings run to the first lone quote; `''` is an escaped one.
+        let mut value = String::new();
+        let mut chars = quoted.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                } else {
+                    return Some(KeywordValue::Str(value.trim_end().to_string()));
+                }
+            }
+            value.push(c);
+        }
+        return None;
+    }
+    let token = text.split('/').next().unwrap_or_default().trim();
+    match token {
+        "T" => Some(KeywordValue::Bool(true)),
+        "F" => Some(KeywordValue::Bool(false)),
+        _ => token.parse().map(KeywordValue::Int).ok().or_else(|| {
+            token
+                .replace('D', "E")
+                .parse()
+                .ok()
+                .map(KeywordValue::Float)
+        }),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_card_values() {
+        let v = |s: &str| parse_value(s.as_bytes());
+        assert_eq!(v("                   T"), Some(KeywordValue::Bool(true)));
+        assert_eq!(v("                  42 / c"), Some(KeywordValue::Int(42)));
+        assert_eq!(v("    1.5000000000E+01"), Some(KeywordValue::Float(15.0)));
+        assert_eq!(v("              2.5D-1"), Some(KeywordValue::Float(0.25)));
+        assert_eq!(
+            v("'O''Brien  ' / who"),
+            Some(KeywordValue::Str("O'Brien".into()))
+        );
+        assert_eq!(v("'unterminated"), None);
+    }
+
+    #[test]
+    fn parses_tforms() {
+        assert_eq!(parse_tform("1PB(1234)").unwrap(), (1, b'P', Some(b'B')));
+        assert_eq!(parse_tform("QB").unwrap(), (1, b'Q', Some(b'B')));
+        assert_eq!(parse_tform("16A").unwrap(), (16, b'A', None));
+        assert!(parse_tform("").is_err());
+    }
+
+    #[test]
+    fn grid_clips_edge_tiles() {
+        let grid = Grid {
+            width: 5,
+            height: 3,
+            tile_w: 2,
+            tile_h: 2,
+        };
+        assert_eq!(grid.across(), 3);
+        assert_eq!(grid.count(), 6);
+        assert_eq!(grid.tile(5), (4, 2, 1, 1));
+    }
+
+    #[test]
+    fn oversized_dimensions_fail_before_allocating() {
+        // One 8-byte tile claiming a 100k × 100k image.
+        let grid = Grid {
+            width: 100_000,
+            height: 100_000,
+            tile_w: 100_000,
+            tile_h: 100_000,
+        };
+        let tiles = Tiles {
+            data: vec![0; 8],
+            spans: vec![(0, 8)],
+        };
+        let err = grid
+            .check(&tiles, |len| rice::max_pixels(4, len, rice::BLOCK_SIZE))
+            .unwrap_err();
+        assert!(matches!(err, FitsError::Parse(_)), "{err}");
+    }
+}
@@ -1,7 +1,8 @@
 //! Hand-rolled FITS primary-HDU writer.
 //!
-//! Emits BITPIX 8, 16 (signed) or 32 (signed) integer image HDUs in
-//! the format defined by FITS Standard v4.0. The native unsigned 16-bit
+//! Emits BITPIX 8, 16 (signed), 32 or 64 (signed) integer and -32 / -64
+//! IEEE floating-point image HDUs in the format defined by FITS
+//! Standard v4.0. The native unsigned 16-bit
 //! path uses the standard `BITPIX=16 + BZERO=32768` convention so each
 //! `u16` value `p` is serialized as `i16 = (p as i32 - 32768) as i16`.
 //!
@@ -10,8 +11,24 @@
 //! with ASCII spaces to the next 2880-byte boundary; the data array
 //! is big-endian, then zero-padded to the next 2880-byte boundary.
 //!
-//! Floating-point and i64 BITPIX writes are out of scope per
-//! ADR-001 Amendment A.
+//! Undefined pixels follow the standard's two conventions (`FITSv4`
+//! §4.4.2.5): a floating-point image marks them with IEEE NaN, written
+//! through unchanged, and must not carry a `BLANK` card — the float
+//! writers reject one among the caller's keywords. An integer image
+//! names its sentinel with a caller-supplied `BLANK` card.
+//!
+//! [`write_u16_image_rice`] and [`write_i32_image_rice`] emit the same
+//! images losslessly Rice-compressed under the FITS tiled-image
+//! convention (`ZIMAGE = T`, one tile per row — `fpack`'s default): an
+//! empty primary HDU followed by a `BINTABLE` extension whose heap holds
+//! the compressed rows. `funpack`, CFITSIO-based tools and
+//! [`crate::reader`] all restore the original image from it; the
+//! caller's keywords live on the extension header, where those readers
+//! look for them.
+//!
+//! [`StandardHeader`] turns capture metadata into the conventional
+//! observatory keywords, and [`merge_keywords`] layers operator-supplied
+//! cards on top of them.
 
 use std::io::Write;
 
@@ -22,8 +39,12 @@ pub(crate) const BLOCK_SIZE: usize = 2880;
 /// Size of a FITS header card.
 pub(crate) const CARD_SIZE: usize = 80;
 /// Reserved keywords managed by the writer; users may not supply them.
+/// The tile-compressed layout adds the extension's structural cards and
+/// the `ZIMAGE` convention's own.
 const RESERVED: &[&str] = &[
-    "SIMPLE", "BITPIX", "NAXIS", "NAXIS1", "NAXIS2", "BSCALE", "BZERO", "END",
+    "SIMPLE", "BITPIX", "NAXIS", "NAXIS1", "NAXIS2", "BSCALE", "BZERO", "END", "EXTEND",
+    "XTENSION", "PCOUNT", "GCOUNT", "TFIELDS", "TTYPE1", "TFORM1", "ZIMAGE", "ZBITPIX", "ZNAXIS",
+    "ZNAXIS1", "ZNAXIS2", "ZTILE1", "ZTILE2", "ZCMPTYPE", "ZNAME1", "ZVAL1", "ZNAME2", "ZVAL2",
 ];
 
 /// Typed value of a FITS header card. The variants map 1:1 to the
@@ -57,7 +78,8 @@ impl Keyword {
     /// Validates per `FITSv4` §4.1.2.1: name is ≤ 8 chars, drawn from the
     /// restricted set `[A-Z0-9_-]` (case-insensitive — the input is
     /// uppercased). Reserved keywords (the writer emits SIMPLE, BITPIX,
-    /// NAXIS{,1,2}, BSCALE, BZERO, END itself) are rejected. Float
+    /// NAXIS{,1,2}, BSCALE, BZERO, END itself, plus the binary-table and
+    /// `Z*` cards of the tile-compressed layout) are rejected. Float
     /// values must be finite (no NaN/±Inf — those are not valid FITS
     /// numeric forms). String values must be printable ASCII and short
     /// enough to fit in a single 80-byte card.
@@ -131,6 +153,254 @@ impl Keyword {
         self.comment = Some(comment.into());
         self
     }
+
+    /// The validated, uppercased key without its column padding.
+    #[must_use]
+    pub fn key(&self) -> &str {
+        std::str::from_utf8(&self.key).unwrap_or("").trim_end()
+    }
+
+    /// The card's typed value.
+    #[must_use]
+    pub const fn value(&self) -> &KeywordValue {
+        &self.value
+    }
+
+    /// The card's `/ comment`, if one was attached.
+    #[must_use]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+}
+
+/// The conventional observatory keywords a capture carries, as read
+/// by the common stacking and inspection tools (`PixInsight`, Siril,
+/// ASTAP, N.I.N.A.). Every field is optional: whatever the capture
+/// could not read is simply not written.
+///
+/// Units follow the de-facto conventions rather than SI: exposure in
+/// seconds, temperatures in °C, pixel size in microns *including*
+/// binning, coordinates in decimal degrees (`RA`, `DEC`, `SITELAT`,
+/// `SITELONG`) plus the sexagesimal `OBJCTRA` / `OBJCTDEC` strings.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StandardHeader {
+    /// `IMAGETYP`, e.g. `Light Frame`.
+    pub image_type: Option<String>,
+    /// `EXPTIME`, seconds.
+    pub exposure_secs: Option<f64>,
+    /// `DATE-OBS`: UTC exposure start, ISO-8601 without a zone suffix
+    /// (`2026-10-16T03:12:45.123`). The caller formats it; the writer
+    /// has no clock dependency.
+    pub date_obs: Option<String>,
+    /// `OBJECT`.
+    pub object: Option<String>,
+    /// `FILTER`.
+    pub filter: Option<String>,
+    /// `XBINNING` / `YBINNING`.
+    pub binning: Option<(u8, u8)>,
+    /// `CCD-TEMP`, °C.
+    pub ccd_temp_c: Option<f64>,
+    /// `SET-TEMP`, °C.
+    pub set_temp_c: Option<f64>,
+    /// `GAIN`.
+    pub gain: Option<i32>,
+    /// `OFFSET`.
+    pub offset: Option<i32>,
+    /// `RA` / `DEC`: the telescope pointing, degrees.
+    pub pointing_deg: Option<(f64, f64)>,
+    /// `OBJCTRA` / `OBJCTDEC`: the object's coordinates, degrees,
+    /// written as `HH MM SS.ss` / `+DD MM SS.s`.
+    pub object_deg: Option<(f64, f64)>,
+    /// `FOCALLEN`, millimetres.
+    pub focal_length_mm: Option<f64>,
+    /// `XPIXSZ` / `YPIXSZ`, microns per *binned* pixel.
+    pub pixel_size_um: Option<(f64, f64)>,
+    /// `SITELAT` / `SITELONG`, degrees (east positive).
+    pub site_deg: Option<(f64, f64)>,
+    /// `FOCPOS`, focuser steps.
+    pub focuser_position: Option<i32>,
+    /// `ROTATANG`, degrees.
+    pub rotator_angle_deg: Option<f64>,
+    /// `BAYERPAT`, e.g. `RGGB`. `None` for a monochrome sensor.
+    pub bayer_pattern: Option<String>,
+    /// `PIERSIDE`, `EAST` or `WEST`.
+    pub pier_side: Option<String>,
+    /// `AIRMASS`.
+    pub airmass: Option<f64>,
+    /// `INSTRUME`.
+    pub instrument: Option<String>,
+    /// `TELESCOP`.
+    pub telescope: Option<String>,
+}
+
+impl StandardHeader {
+    /// The header's cards in the conventional order. A bad value costs
+    /// only its own card: strings are transliterated to printable ASCII
+    /// (anything else becomes `?`), and a card [`Keyword::new`] still
+    /// rejects — a non-finite reading, a string too long for one card —
+    /// is left out with a warning while the rest are written.
+    #[must_use]
+    pub fn keywords(&self) -> Vec<Keyword> {
+        let mut out = Vec::new();
+        let mut push =
+            |key: &str, value: KeywordValue, comment: &str| match Keyword::new(key, value) {
+                Ok(kw) => out.push(kw.with_comment(comment)),
+                Err(e) => tracing::warn!(key, error = %e, "omitting FITS header card"),
+            };
+        let text = |s: &String| KeywordValue::Str(printable_ascii(s));
+
+        if let Some(v) = &self.image_type {
+            push("IMAGETYP", text(v), "Type of exposure");
+        }
+        if let Some(v) = self.exposure_secs {
+            push("EXPTIME", KeywordValue::Float(v), "[s] Exposure duration");
+        }
+        if let Some(v) = &self.date_obs {
+            push("DATE-OBS", text(v), "UTC start of exposure");
+        }
+        if let Some(v) = &self.object {
+            push("OBJECT", text(v), "Name of the object");
+        }
+        if let Some(v) = &self.filter {
+            push("FILTER", text(v), "Filter in the light path");
+        }
+        if let Some((x, y)) = self.binning {
+            push("XBINNING", KeywordValue::Int(x.into()), "Binning factor, X");
+            push("YBINNING", KeywordValue::Int(y.into()), "Binning factor, Y");
+        }
+        if let Some(v) = self.ccd_temp_c {
+            push("CCD-TEMP", KeywordValue::Float(v), "[C] Sensor temperature");
+        }
+        if let Some(v) = self.set_temp_c {
+            push("SET-TEMP", KeywordValue::Float(v), "[C] Cooler setpoint");
+        }
+        if let Some(v) = self.gain {
+            push("GAIN", KeywordValue::Int(v.into()), "Sensor gain");
+        }
+        if let Some(v) = self.offset {
+            push("OFFSET", KeywordValue::Int(v.into()), "Sensor offset");
+        }
+        if let Some((ra, dec)) = self.pointing_deg {
+            push("RA", KeywordValue::Float(ra), "[deg] Telescope pointing RA");
+            push(
+                "DEC",
+                KeywordValue::Float(dec),
+                "[deg] Telescope pointing Dec",
+            );
+        }
+        if let Some((ra, dec)) = self.object_deg {
+            // The sexagesimal strings would format NaN as zero, so the
+            // finiteness check `Keyword::new` does for floats has to
+            // happen here.
+            if ra.is_finite() && dec.is_finite() {
+                push(
+                    "OBJCTRA",
+                    KeywordValue::Str(format_ra_hms(ra)),
+                    "[HMS] Object RA",
+                );
+                push(
+                    "OBJCTDEC",
+                    KeywordValue::Str(format_dec_dms(dec)),
+                    "[DMS] Object Dec",
+                );
+            } else {
+                tracing::warn!(ra, dec, "omitting non-finite OBJCTRA/OBJCTDEC");
+            }
+        }
+        if let Some(v) = self.focal_length_mm {
+            push("FOCALLEN", KeywordValue::Float(v), "[mm] Focal length");
+        }
+        if let Some((x, y)) = self.pixel_size_um {
+            push(
+                "XPIXSZ",
+                KeywordValue::Float(x),
+                "[um] Pixel size incl. binning, X",
+            );
+            push(
+                "YPIXSZ",
+                KeywordValue::Float(y),
+                "[um] Pixel size incl. binning, Y",
+            );
+        }
+        if let Some((lat, long)) = self.site_deg {
+            push("SITELAT", KeywordValue::Float(lat), "[deg] Site latitude");
+            push(
+                "SITELONG",
+                KeywordValue::Float(long),
+                "[deg] Site longitude, east +",
+            );
+        }
+        if let Some(v) = self.focuser_position {
+            push(
+                "FOCPOS",
+                KeywordValue::Int(v.into()),
+                "[step] Focuser position",
+            );
+        }
+        if let Some(v) = self.rotator_angle_deg {
+            push("ROTATANG", KeywordValue::Float(v), "[deg] Rotator angle");
+        }
+        if let Some(v) = &self.bayer_pattern {
+            push("BAYERPAT", text(v), "Bayer color pattern");
+        }
+        if let Some(v) = &self.pier_side {
+            push("PIERSIDE", text(v), "Side of pier");
+        }
+        if let Some(v) = self.airmass {
+            push("AIRMASS", KeywordValue::Float(v), "Airmass at exposure");
+        }
+        if let Some(v) = &self.instrument {
+            push("INSTRUME", text(v), "Imaging instrument");
+        }
+        if let Some(v) = &self.telescope {
+            push("TELESCOP", text(v), "Telescope");
+        }
+        out
+    }
+}
+
+/// `s` with every character outside printable ASCII replaced by `?`,
+/// so a device name like `Ω-Cam` still lands as `?-Cam`.
+fn printable_ascii(s: &str) -> String {
+    s.chars()
+        .map(|c| if (' '..='~').contains(&c) { c } else { '?' })
+        .collect()
+}
+
+/// Layer operator-supplied cards over `base`: a card whose key is
+/// already present replaces it in place (so an operator's `TELESCOP`
+/// wins over the derived one without moving it), anything new is
+/// appended in `overrides` order.
+#[must_use]
+pub fn merge_keywords(mut base: Vec<Keyword>, overrides: &[Keyword]) -> Vec<Keyword> {
+    for kw in overrides {
+        match base.iter_mut().find(|b| b.key == kw.key) {
+            Some(slot) => *slot = kw.clone(),
+            None => base.push(kw.clone()),
+        }
+    }
+    base
+}
+
+/// `HH MM SS.ss` for an RA in degrees, wrapped into `[0, 24h)`.
+/// Rounds once on the total so `59.999s` carries into the minute
+/// rather than printing `60.00`.
+fn format_ra_hms(ra_deg: f64) -> String {
+    const DAY: u64 = 24 * 360_000;
+    let centis = ((ra_deg / 15.0).rem_euclid(24.0) * 360_000.0).round() as u64 % DAY;
+    let (h, rest) = (centis / 360_000, centis % 360_000);
+    let (m, rest) = (rest / 6_000, rest % 6_000);
+    format!("{h:02} {m:02} {:02}.{:02}", rest / 100, rest % 100)
+}
+
+/// `+DD MM SS.s` for a declination in degrees, rounded once on the
+/// total like [`format_ra_hms`].
+fn format_dec_dms(dec_deg: f64) -> String {
+    let sign = if dec_deg < 0.0 { '-' } else { '+' };
+    let decis = (dec_deg.abs() * 36_000.0).round() as u64;
+    let (d, rest) = (decis / 36_000, decis % 36_000);
+    let (m, rest) = (rest / 600, rest % 600);
+    format!("{sign}{d:02} {m:02} {:02}.{}", rest / 10, rest % 10)
 }
 
 /// Write a `u8` (BITPIX=8) image HDU.
@@ -189,6 +459,222 @@ pub fn write_i32_image<W: Write + ?Sized>(
     Ok(())
 }
 
+/// Write an `i64` (BITPIX=64) image HDU. Undefined pixels, if any,
+/// are flagged by a `BLANK` keyword in `extra` like the other integer
+/// writers.
+pub fn write_i64_image<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[i64],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+) -> Result<(), FitsError> {
+    let body = serialize_image(64, &[], pixels, width, height, extra, |out, &p| {
+        out.extend_from_slice(&p.to_be_bytes());
+    })?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// Write an `f32` (BITPIX=-32) image HDU. NaN marks an undefined pixel;
+/// a `BLANK` keyword in `extra` is rejected.
+pub fn write_f32_image<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[f32],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+) -> Result<(), FitsError> {
+    reject_blank(extra)?;
+    let body = serialize_image(-32, &[], pixels, width, height, extra, |out, &p| {
+        out.extend_from_slice(&p.to_be_bytes());
+    })?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// Write an `f64` (BITPIX=-64) image HDU. NaN marks an undefined pixel;
+/// a `BLANK` keyword in `extra` is rejected.
+pub fn write_f64_image<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[f64],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+) -> Result<(), FitsError> {
+    reject_blank(extra)?;
+    let body = serialize_image(-64, &[], pixels, width, height, extra, |out, &p| {
+        out.extend_from_slice(&p.to_be_bytes());
+    })?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// `FITSv4` §4.4.2.5: `BLANK` "shall not be used" on a floating-point
+/// image, whose undefined pixels are NaN.
+fn reject_blank(extra: &[Keyword]) -> Result<(), FitsError> {
+    if extra.iter().any(|kw| kw.key() == "BLANK") {
+        return Err(FitsError::InvalidKeyword(
+            "BLANK is not allowed on a floating-point image; undefined pixels are NaN".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Write a `u16` image Rice-compressed (`ZBITPIX=16` + `BZERO=32768`).
+/// Same pixel encoding as [`write_u16_image`]; see the module docs for
+/// the layout.
+pub fn write_u16_image_rice<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[u16],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+) -> Result<(), FitsError> {
+    let managed = [
+        reserved_card("BSCALE", KeywordValue::Float(1.0)),
+        reserved_card("BZERO", KeywordValue::Float(32768.0)),
+    ];
+    let body = serialize_rice(16, &managed, pixels, width, height, extra, |row| {
+        let raw: Vec<i16> = row.iter().map(|&p| (i32::from(p) - 32768) as i16).collect();
+        crate::rice::compress_i16(&raw)
+    })?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// Write an `i32` image Rice-compressed (`ZBITPIX=32`).
+pub fn write_i32_image_rice<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[i32],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+) -> Result<(), FitsError> {
+    let body = serialize_rice(
+        32,
+        &[],
+        pixels,
+        width,
+        height,
+        extra,
+        crate::rice::compress_i32,
+    )?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// Internal: the tile-compressed counterpart of [`serialize_image`].
+/// Each image row is one tile and one table row; the row's `1PB`
+/// descriptor points at its Rice stream in the heap.
+fn serialize_rice<T, F>(
+    zbitpix: i8,
+    managed_pre_user: &[Card],
+    pixels: &[T],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+    mut compress_row: F,
+) -> Result<Vec<u8>, FitsError>
+where
+    F: FnMut(&[T]) -> Vec<u8>,
+{
+    let expected = width
+        .checked_mul(height)
+        .ok_or_else(|| FitsError::Unsupported(format!("dimensions overflow: {width}x{height}")))?;
+    if pixels.len() != expected {
+        return Err(FitsError::DimensionMismatch {
+            got: pixels.len(),
+            width,
+            height,
+            expected,
+        });
+    }
+    let (Ok(znaxis1), Ok(znaxis2)) = (i64::try_from(width), i64::try_from(height)) else {
+        return Err(FitsError::Unsupported(format!(
+            "dimensions exceed a NAXIS card: {width}x{height}"
+        )));
+    };
+
+    // Compress first: the header needs the heap size and the longest
+    // tile (`TFORM1 = '1PB(max)'`).
+    let tiles: Vec<Vec<u8>> = if width == 0 {
+        vec![Vec::new(); height]
+    } else {
+        pixels.chunks(width).map(&mut compress_row).collect()
+    };
+    let heap_len: usize = tiles.iter().map(Vec::len).sum();
+    let max_tile = tiles.iter().map(Vec::len).max().unwrap_or(0);
+    // `1PB` descriptors are two 32-bit integers; a heap past that range
+    // needs the `1QB` form, which no 60 MP frame comes near.
+    let too_big = || FitsError::Unsupported(format!("compressed heap too large: {heap_len} bytes"));
+    let heap_card = i32::try_from(heap_len)
+        .map(i64::from)
+        .map_err(|_| too_big())?;
+
+    let mut out = Vec::with_capacity(3 * BLOCK_SIZE + 8 * height + heap_len);
+
+    // Empty primary HDU.
+    write_card(&mut out, &Card::logical("SIMPLE", true))?;
+    write_card(&mut out, &Card::integer("BITPIX", 8))?;
+    write_card(&mut out, &Card::integer("NAXIS", 0))?;
+    write_card(&mut out, &Card::logical("EXTEND", true))?;
+    write_end_card(&mut out);
+    pad_to_block(&mut out, b' ');
+
+    // Compressed-image extension header.
+    let text = |key: &str, value: &str| reserved_card(key, KeywordValue::Str(value.to_string()));
+    write_card(&mut out, &text("XTENSION", "BINTABLE"))?;
+    write_card(&mut out, &Card::integer("BITPIX", 8))?;
+    write_card(&mut out, &Card::integer("NAXIS", 2))?;
+    write_card(&mut out, &Card::integer("NAXIS1", 8))?;
+    write_card(&mut out, &Card::integer("NAXIS2", znaxis2))?;
+    write_card(&mut out, &Card::integer("PCOUNT", heap_card))?;
+    write_card(&mut out, &Card::integer("GCOUNT", 1))?;
+    write_card(&mut out, &Card::integer("TFIELDS", 1))?;
+    write_card(&mut out, &text("TTYPE1", "COMPRESSED_DATA"))?;
+    write_card(&mut out, &text("TFORM1", &format!("1PB({max_tile})")))?;
+    write_card(&mut out, &Card::logical("ZIMAGE", true))?;
+    write_card(&mut out, &Card::integer("ZBITPIX", i64::from(zbitpix)))?;
+    write_card(&mut out, &Card::integer("ZNAXIS", 2))?;
+    write_card(&mut out, &Card::integer("ZNAXIS1", znaxis1))?;
+    write_card(&mut out, &Card::integer("ZNAXIS2", znaxis2))?;
+    write_card(&mut out, &Card::integer("ZTILE1", znaxis1))?;
+    write_card(&mut out, &Card::integer("ZTILE2", 1))?;
+    write_card(&mut out, &text("ZCMPTYPE", "RICE_1"))?;
+    write_card(&mut out, &text("ZNAME1", "BLOCKSIZE"))?;
+    write_card(
+        &mut out,
+        &Card::integer("ZVAL1", crate::rice::BLOCK_SIZE as i64),
+    )?;
+    write_card(&mut out, &text("ZNAME2", "BYTEPIX"))?;
+    write_card(&mut out, &Card::integer("ZVAL2", i64::from(zbitpix / 8)))?;
+    for c in managed_pre_user {
+        write_card(&mut out, c)?;
+    }
+    for kw in extra {
+        write_card(&mut out, &Card::from_keyword(kw))?;
+    }
+    write_end_card(&mut out);
+    pad_to_block(&mut out, b' ');
+
+    // Table rows (descriptors), then the heap right behind them — the
+    // default `THEAP`.
+    let mut offset = 0usize;
+    for tile in &tiles {
+        // Both fit: each is bounded by `heap_len`, checked above.
+        out.extend_from_slice(&(tile.len() as i32).to_be_bytes());
+        out.extend_from_slice(&(offset as i32).to_be_bytes());
+        offset += tile.len();
+    }
+    for tile in &tiles {
+        out.extend_from_slice(tile);
+    }
+    pad_to_block(&mut out, 0);
+
+    Ok(out)
+}
+
 /// Internal: shared header+data serializer parameterized by per-pixel
 /// big-endian emit. `managed_pre_user` are reserved keyword cards we
 /// emit ourselves between the mandatory block and user-supplied cards
@@ -217,7 +703,7 @@ where
         });
     }
 
-    // `bitpix` is paired with `T` by the three public entry points, so
+    // `bitpix` is paired with `T` by the public entry points, so
     // this is `size_of::<T>()`, and the check above established
     // `expected == pixels.len()`. The product is therefore the byte
     // length of a live slice, which Rust caps at `isize::MAX` — the
@@ -416,7 +902,7 @@ fn push_padded_left(out: &mut Vec<u8>, value: &[u8], width: usize) {
     }
 }
 
-fn format_float(f: f64) -> String {
+pub(crate) fn format_float(f: f64) -> String {
     // %20.10E form. `{:.10E}` gives `1.0000000000E0`; expand to
     // `1.0000000000E+00` to match the FITS canonical form.
     let raw = format!("{f:.10E}");
@@ -454,6 +940,8 @@ mod tests {
     use fitsrs::Pixels;
     use fitsrs::HDU;
 
+    use crate::reader::Pixels as Decoded;
+
     fn fitsrs_read_pixels_i32(bytes: &[u8]) -> Vec<i32> {
         let mut hdu_list = Fits::from_reader(Cursor::new(bytes));
         let HDU::Primary(hdu) = hdu_list.next().expect("hdu present").expect("hdu ok") else {
@@ -535,6 +1023,79 @@ mod tests {
         assert_eq!(recovered, pixels);
     }
 
+    /// Decode through `fitsrs` directly (not [`crate::reader`]'s
+    /// scaling path) and keep the pixel type `pick` selects; any other
+    /// type fails the test.
+    fn fitsrs_read_pixels<T>(bytes: &[u8], pick: impl FnOnce(Decoded) -> Option<Vec<T>>) -> Vec<T> {
+        let mut hdu_list = Fits::from_reader(Cursor::new(bytes));
+        let hdu = match hdu_list.next().unwrap().unwrap() {
+            HDU::Primary(h) => h,
+            _ => panic!(),
+        };
+        let image = hdu_list.get_data(&hdu);
+        let decoded = match image.pixels() {
+            Pixels::U8(it) => Decoded::U8(it.collect()),
+            Pixels::I16(it) => Decoded::I16(it.collect()),
+            Pixels::I32(it) => Decoded::I32(it.collect()),
+            Pixels::I64(it) => Decoded::I64(it.collect()),
+            Pixels::F32(it) => Decoded::F32(it.collect()),
+            Pixels::F64(it) => Decoded::F64(it.collect()),
+        };
+        pick(decoded).expect("unexpected pixel type")
+    }
+
+    #[test]
+    fn writes_float_images_with_nan_intact() {
+        let pixels = [1.5f32, -0.25, f32::NAN, 65_535.0];
+        let mut buf = Vec::new();
+        write_f32_image(&mut buf, &pixels, 2, 2, &[]).unwrap();
+        assert!(buf.len().is_multiple_of(BLOCK_SIZE));
+        let got = fitsrs_read_pixels(&buf, |p| match p {
+            Decoded::F32(v) => Some(v),
+            _ => None,
+        });
+        assert_eq!(got[..2], pixels[..2]);
+        assert!(got[2].is_nan());
+        assert_eq!(got[3], 65_535.0);
+
+        let pixels = [1e-300f64, f64::NAN, -3.25, 1e300];
+        let mut buf = Vec::new();
+        write_f64_image(&mut buf, &pixels, 4, 1, &[]).unwrap();
+        let got = fitsrs_read_pixels(&buf, |p| match p {
+            Decoded::F64(v) => Some(v),
+            _ => None,
+        });
+        assert_eq!((got[0], got[2], got[3]), (1e-300, -3.25, 1e300));
+        assert!(got[1].is_nan());
+    }
+
+    #[test]
+    fn writes_i64_image_with_its_blank_card() {
+        let pixels = vec![i64::MIN, -1, 0, 1 << 40];
+        let blank = [Keyword::new("BLANK", KeywordValue::Int(i64::MIN)).unwrap()];
+        let mut buf = Vec::new();
+        write_i64_image(&mut buf, &pixels, 2, 2, &blank).unwrap();
+        assert!(cards(&buf).iter().any(|c| c.starts_with("BLANK   =")));
+        assert_eq!(
+            fitsrs_read_pixels(&buf, |p| match p {
+                Decoded::I64(v) => Some(v),
+                _ => None,
+            }),
+            pixels
+        );
+    }
+
+    #[test]
+    fn float_writers_reject_a_blank_card() {
+        let blank = [Keyword::new("BLANK", KeywordValue::Int(-1)).unwrap()];
+        let mut buf = Vec::new();
+        let err = write_f32_image(&mut buf, &[0.0], 1, 1, &blank).unwrap_err();
+        assert!(matches!(err, FitsError::InvalidKeyword(_)), "{err:?}");
+        let err = write_f64_image(&mut buf, &[0.0], 1, 1, &blank).unwrap_err();
+        assert!(matches!(err, FitsError::InvalidKeyword(_)), "{err:?}");
+        assert!(buf.is_empty(), "nothing is written on rejection");
+    }
+
     #[test]
     fn writes_keyword_card_visible_to_fitsrs() {
         let mut buf = Vec::new();
@@ -770,4 +1331,166 @@ mod tests {
         let s_zero = format_float(0.0);
         assert!(s_zero.contains('E'), "got {s_zero:?}");
     }
+
+    fn keys(kws: &[Keyword]) -> Vec<&str> {
+        kws.iter().map(Keyword::key).collect()
+    }
+
+    #[test]
+    fn standard_header_writes_only_what_it_has() {
+        assert!(StandardHeader::default().keywords().is_empty());
+
+        let header = StandardHeader {
+            image_type: Some("Light Frame".into()),
+            exposure_secs: Some(300.0),
+            binning: Some((2, 2)),
+            pier_side: Some("WEST".into()),
+            ..Default::default()
+        };
+        let kws = header.keywords();
+        assert_eq!(
+            keys(&kws),
+            ["IMAGETYP", "EXPTIME", "XBINNING", "YBINNING", "PIERSIDE"]
+        );
+        assert_eq!(kws[1].value(), &KeywordValue::Float(300.0));
+        assert_eq!(kws[2].value(), &KeywordValue::Int(2));
+    }
+
+    #[test]
+    fn standard_header_round_trips_through_fitsrs() {
+        let header = StandardHeader {
+            date_obs: Some("2026-10-16T03:12:45.123".into()),
+            ccd_temp_c: Some(-10.2),
+            object_deg: Some((10.684_708, 41.268_75)),
+            site_deg: Some((47.6062, -122.3321)),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        write_u16_image(&mut buf, &[0u16; 4], 2, 2, &header.keywords()).unwrap();
+
+        let mut hdu_list = Fits::from_reader(Cursor::new(&buf[..]));
+        let hdu = match hdu_list.next().unwrap().unwrap() {
+            HDU::Primary(h) => h,
+            _ => panic!(),
+        };
+        let h = hdu.get_header();
+        assert!(matches!(
+            h.get("DATE-OBS"),
+            Some(fitsrs::card::Value::String { value, .. }) if value == "2026-10-16T03:12:45.123"
+        ));
+        assert!(matches!(
+            h.get("OBJCTRA"),
+            Some(fitsrs::card::Value::String { value, .. }) if value == "00 42 44.33"
+        ));
+        assert!(matches!(
+            h.get("OBJCTDEC"),
+            Some(fitsrs::card::Value::String { value, .. }) if value == "+41 16 07.5"
+        ));
+        assert!(matches!(
+            h.get("SITELONG"),
+            Some(fitsrs::card::Value::Float { value, .. }) if (value + 122.3321).abs() < 1e-9
+        ));
+    }
+
+    #[test]
+    fn standard_header_omits_only_the_bad_cards() {
+        let header = StandardHeader {
+            exposure_secs: Some(60.0),
+            ccd_temp_c: Some(f64::NAN),
+            object_deg: Some((f64::INFINITY, 41.0)),
+            filter: Some("Hα".into()),
+            instrument: Some("x".repeat(80)),
+            telescope: Some("Main".into()),
+            ..Default::default()
+        };
+        let kws = header.keywords();
+        assert_eq!(keys(&kws), ["EXPTIME", "FILTER", "TELESCOP"]);
+        assert_eq!(kws[1].value(), &KeywordValue::Str("H?".into()));
+
+        let mut buf = Vec::new();
+        write_u16_image(&mut buf, &[0u16; 4], 2, 2, &kws).unwrap();
+    }
+
+    #[test]
+    fn sexagesimal_rounding_carries_instead_of_printing_sixty() {
+        // 23h59m59.999s rounds up to the next day, which wraps to 0h.
+        assert_eq!(format_ra_hms(359.999_999_9), "00 00 00.00");
+        assert_eq!(format_ra_hms(-15.0), "23 00 00.00");
+        assert_eq!(format_dec_dms(-0.999_999_99), "-01 00 00.0");
+        assert_eq!(format_dec_dms(-5.5), "-05 30 00.0");
+        assert_eq!(format_dec_dms(89.0), "+89 00 00.0");
+    }
+
+    #[test]
+    fn merge_keywords_replaces_in_place_and_appends_new() {
+        let base = vec![
+            Keyword::new("TELESCOP", KeywordValue::Str("main".into())).unwrap(),
+            Keyword::new("GAIN", KeywordValue::Int(100)).unwrap(),
+        ];
+        let overrides = [
+            Keyword::new("OBSERVER", KeywordValue::Str("Igor".into())).unwrap(),
+            Keyword::new("telescop", KeywordValue::Str("RC8".into())).unwrap(),
+        ];
+        let merged = merge_keywords(base, &overrides);
+        assert_eq!(keys(&merged), ["TELESCOP", "GAIN", "OBSERVER"]);
+        assert_eq!(merged[0].value(), &KeywordValue::Str("RC8".into()));
+    }
+
+    fn cards(block: &[u8]) -> Vec<String> {
+        block
+            .chunks(CARD_SIZE)
+            .map(|c| String::from_utf8_lossy(c).trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn rice_layout_is_an_empty_primary_plus_a_zimage_bintable() {
+        let kw = [Keyword::new("DOC_ID", KeywordValue::Str("abc".into())).unwrap()];
+        let mut buf = Vec::new();
+        write_u16_image_rice(&mut buf, &[1, 2, 3, 4, 5, 6], 3, 2, &kw).unwrap();
+        assert_eq!(buf.len() % BLOCK_SIZE, 0);
+
+        let primary = cards(&buf[..BLOCK_SIZE]);
+        assert!(primary.contains(&format!("{:<8}= {:>20}", "NAXIS", 0)));
+        assert!(primary.contains(&format!("{:<8}= {:>20}", "EXTEND", "T")));
+
+        let ext = cards(&buf[BLOCK_SIZE..2 * BLOCK_SIZE]);
+        assert_eq!(ext[0], "XTENSION= 'BINTABLE'");
+        for expected in [
+            format!("{:<8}= {:>20}", "NAXIS2", 2),
+            format!("{:<8}= {:>20}", "ZIMAGE", "T"),
+            format!("{:<8}= {:>20}", "ZBITPIX", 16),
+            format!("{:<8}= {:>20}", "ZNAXIS1", 3),
+            format!("{:<8}= {:>20}", "ZTILE2", 1),
+            "ZCMPTYPE= 'RICE_1  '".to_string(),
+            format!("{:<8}= {:>20}", "ZVAL2", 2),
+            "DOC_ID  = 'abc     '".to_string(),
+        ] {
+            assert!(ext.contains(&expected), "missing {expected:?} in {ext:?}");
+        }
+
+        // Row descriptors: (length, heap offset) per tile, back to back.
+        let table = &buf[2 * BLOCK_SIZE..];
+        let word = |i: usize| i32::from_be_bytes(table[4 * i..4 * i + 4].try_into().unwrap());
+        assert_eq!(word(1), 0);
+        assert_eq!(word(3), word(0));
+    }
+
+    #[test]
+    fn rice_writers_reject_dimension_mismatch() {
+        let mut buf = Vec::new();
+        let err = write_i32_image_rice(&mut buf, &[1, 2, 3], 2, 2, &[]).unwrap_err();
+        assert!(
+            matches!(err, FitsError::DimensionMismatch { .. }),
+            "{err:?}"
+        );
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn compression_cards_are_reserved() {
+        for key in ["ZIMAGE", "ZCMPTYPE", "XTENSION", "PCOUNT", "EXTEND"] {
+            assert!(Keyword::new(key, KeywordValue::Int(1)).is_err(), "{key}");
+        }
+    }
 }
@@ -0,0 +1,1067 @@
+//! XISF 1.0 monolithic-file writer and reader — the format `PixInsight`
+//! prefers over FITS.
+//!
+//! A monolithic XISF file is a 16-byte preamble (`XISF0100`, the
+//! little-endian length of the XML header, four reserved bytes), the
+//! XML header, and the attached data blocks it points at by absolute
+//! `attachment:<position>:<size>` locations. The writers emit one
+//! single-channel `Image` whose block starts on a 4096-byte boundary
+//! (`PixInsight`'s default alignment), little-endian, and carry the
+//! caller's [`Keyword`]s as `FITSKeyword` elements: `PixInsight` keeps
+//! those in sync with the image's FITS header, so a frame written here
+//! carries the same metadata as its FITS counterpart. Values use the
+//! FITS card spelling (`'quoted'` strings, `T`/`F`), as `PixInsight`
+//! does.
+//!
+//! [`Compression::Zlib`] and [`Compression::Lz4`] compress the block
+//! after byte-shuffling it by sample size (`zlib+sh` / `lz4+sh`), which
+//! is what makes them pay off on 16- and 32-bit data. A block that does
+//! not shrink is stored uncompressed instead.
+//!
+//! The reader accepts what `PixInsight` writes for a grayscale image:
+//! any `UInt8/16/32` or `Float32/64` sample format, either byte order,
+//! attached blocks compressed with `zlib`, `lz4` or `lz4hc`, shuffled
+//! or not. Multi-channel images, `inline`/`embedded` blocks and other
+//! codecs are [`FitsError::Unsupported`]. Only the first `Image` in the
+//! header is read.
+//!
+//! The XML handling is a scanner for the element-and-attribute subset
+//! XISF headers use — tags, attributes, the predefined and numeric
+//! entities, comments and processing instructions — not a general XML
+//! parser.
+
+use std::fmt::Write as _;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::time::SystemTime;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+
+use crate::error::FitsError;
+use crate::writer::{format_float, Keyword, KeywordValue};
+
+const SIGNATURE: &[u8; 8] = b"XISF0100";
+/// Signature, header length and reserved field.
+const PREAMBLE: usize = 16;
+const BLOCK_ALIGNMENT: usize = 4096;
+const CREATOR: &str = concat!("rusty-photon ", env!("CARGO_PKG_VERSION"));
+
+/// Data-block compression for the XISF writers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compression {
+    #[default]
+    None,
+    /// zlib (RFC 1950), byte-shuffled.
+    Zlib,
+    /// LZ4 block format, byte-shuffled. Faster than zlib to write and
+    /// read, at a somewhat lower ratio.
+    Lz4,
+}
+
+/// Decoded XISF image. Pixels keep their on-disk sample format;
+/// [`read_image_as_i32`] and [`read_image_as_f32`] convert them the
+/// way [`crate::reader`]'s counterparts do for FITS.
+#[derive(Debug, Clone)]
+pub struct XisfImage {
+    pub width: usize,
+    pub height: usize,
+    pub data: XisfPixels,
+    /// The image's `FITSKeyword` elements in header order, keys
+    /// uppercased. Keywords without a value (`COMMENT`, `HISTORY`) are
+    /// skipped.
+    pub keywords: Vec<(String, KeywordValue)>,
+}
+
+impl XisfImage {
+    /// First value of `key` among [`Self::keywords`], case-insensitively.
+    #[must_use]
+    pub fn keyword(&self, key: &str) -> Option<&KeywordValue> {
+        find_keyword(&self.keywords, key)
+    }
+
+    /// Pixels as `i32`. `UInt32` values above `i32::MAX` saturate;
+    /// floating-point samples are truncated and saturated with NaN
+    /// mapped to 0, as [`crate::reader::FitsImage::into_i32`] treats
+    /// them.
+    #[must_use]
+    pub fn into_i32(self) -> Vec<i32> {
+        match self.data {
+            XisfPixels::U8(v) => v.into_iter().map(i32::from).collect(),
+            XisfPixels::U16(v) => v.into_iter().map(i32::from).collect(),
+            XisfPixels::U32(v) => v
+                .into_iter()
+                .map(|p| i32::try_from(p).unwrap_or(i32::MAX))
+                .collect(),
+            // `as` saturates and maps NaN to 0.
+            XisfPixels::F32(v) => v.into_iter().map(|p| p as i32).collect(),
+            XisfPixels::F64(v) => v.into_iter().map(|p| p as i32).collect(),
+        }
+    }
+
+    /// Pixels as `f32`, sample values unchanged (`PixInsight`'s float
+    /// images are normalised to `[0, 1]`; that range is kept). NaN
+    /// passes through.
+    #[must_use]
+    pub fn into_f32(self) -> Vec<f32> {
+        match self.data {
+            XisfPixels::U8(v) => v.into_iter().map(f32::from).collect(),
+            XisfPixels::U16(v) => v.into_iter().map(f32::from).collect(),
+            XisfPixels::U32(v) => v.into_iter().map(|p| p as f32).collect(),
+            XisfPixels::F32(v) => v,
+            XisfPixels::F64(v) => v.into_iter().map(|p| p as f32).collect(),
+        }
+    }
+}
+
+/// Pixel data in an XISF sample format.
+#[derive(Debug, Clone)]
+pub enum XisfPixels {
+    U8(Vec<u8>),
+    U16(Vec<u16>),
+    U32(Vec<u32>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+}
+
+impl XisfPixels {
+    /// Whether the sample format is `Float32` or `Float64`.
+    #[must_use]
+    pub const fn is_float(&self) -> bool {
+        matches!(self, Self::F32(_) | Self::F64(_))
+    }
+}
+
+/// Write a `u16` (`UInt16`) grayscale image.
+pub fn write_u16_image<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[u16],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+    compression: Compression,
+) -> Result<(), FitsError> {
+    let body = serialize(
+        SampleFormat::UInt16,
+        pixels,
+        width,
+        height,
+        extra,
+        compression,
+        |out, p| out.extend_from_slice(&p.to_le_bytes()),
+    )?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// Write a `u32` (`UInt32`) grayscale image. XISF has no signed
+/// integer sample format; callers holding `i32` data clamp it first.
+pub fn write_u32_image<W: Write + ?Sized>(
+    w: &mut W,
+    pixels: &[u32],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+    compression: Compression,
+) -> Result<(), FitsError> {
+    let body = serialize(
+        SampleFormat::UInt32,
+        pixels,
+        width,
+        height,
+        extra,
+        compression,
+        |out, p| out.extend_from_slice(&p.to_le_bytes()),
+    )?;
+    w.write_all(&body)?;
+    Ok(())
+}
+
+/// Read the first image of an XISF stream.
+pub fn read_image<R: Read + Seek>(mut reader: R) -> Result<XisfImage, FitsError> {
+    let header = read_header(&mut reader)?;
+    let image = header.image;
+    reader.seek(SeekFrom::Start(image.position))?;
+    let mut block = Vec::new();
+    reader.by_ref().take(image.size).read_to_end(&mut block)?;
+    if u64::try_from(block.len()).ok() != Some(image.size) {
+        return Err(FitsError::Parse(format!(
+            "XISF data block truncated: {} of {} bytes",
+            block.len(),
+            image.size
+        )));
+    }
+    let raw = match &image.codec {
+        Some(codec) => codec.decode(&block)?,
+        None => block,
+    };
+
+    let expected = image
+        .width
+        .checked_mul(image.height)
+        .and_then(|n| n.checked_mul(image.format.size()))
+        .ok_or_else(|| {
+            FitsError::Parse(format!(
+                "XISF geometry {}x{} overflows a buffer",
+                image.width, image.height
+            ))
+        })?;
+    if raw.len() != expected {
+        return Err(FitsError::Parse(format!(
+            "XISF data block holds {} bytes, geometry {}x{} {} needs {expected}",
+            raw.len(),
+            image.width,
+            image.height,
+            image.format.name()
+        )));
+    }
+
+    let big = image.big_endian;
+    let data = match image.format {
+        SampleFormat::UInt8 => XisfPixels::U8(raw),
+        SampleFormat::UInt16 => XisfPixels::U16(samples(&raw, big, u16::from_le_bytes)),
+        SampleFormat::UInt32 => XisfPixels::U32(samples(&raw, big, u32::from_le_bytes)),
+        SampleFormat::Float32 => XisfPixels::F32(samples(&raw, big, f32::from_le_bytes)),
+        SampleFormat::Float64 => XisfPixels::F64(samples(&raw, big, f64::from_le_bytes)),
+    };
+    Ok(XisfImage {
+        width: image.width,
+        height: image.height,
+        data,
+        keywords: header.keywords,
+    })
+}
+
+/// Read the first image and return its pixels as row-major `i32`; see
+/// [`XisfImage::into_i32`].
+pub fn read_image_as_i32<R: Read + Seek>(reader: R) -> Result<(Vec<i32>, usize, usize), FitsError> {
+    let img = read_image(reader)?;
+    let (width, height) = (img.width, img.height);
+    Ok((img.into_i32(), width, height))
+}
+
+/// Read the first image and return its pixels as row-major `f32`; see
+/// [`XisfImage::into_f32`].
+pub fn read_image_as_f32<R: Read + Seek>(reader: R) -> Result<(Vec<f32>, usize, usize), FitsError> {
+    let img = read_image(reader)?;
+    let (width, height) = (img.width, img.height);
+    Ok((img.into_f32(), width, height))
+}
+
+/// Read one `FITSKeyword` of the first image from the XML header alone,
+/// without touching the data block. `Ok(None)` when it is absent.
+pub fn read_keyword<R: Read>(mut reader: R, key: &str) -> Result<Option<KeywordValue>, FitsError> {
+    let header = read_header(&mut reader)?;
+    Ok(find_keyword(&header.keywords, key).cloned())
+}
+
+fn find_keyword<'a>(keywords: &'a [(String, KeywordValue)], key: &str) -> Option<&'a KeywordValue> {
+    keywords
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(key))
+        .map(|(_, v)| v)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SampleFormat {
+    UInt8,
+    UInt16,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl SampleFormat {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::UInt8 => "UInt8",
+            Self::UInt16 => "UInt16",
+            Self::UInt32 => "UInt32",
+            Self::Float32 => "Float32",
+            Self::Float64 => "Float64",
+        }
+    }
+
+    const fn size(self) -> usize {
+        match self {
+            Self::UInt8 => 1,
+            Self::UInt16 => 2,
+            Self::UInt32 | Self::Float32 => 4,
+            Self::Float64 => 8,
+        }
+    }
+
+    fn parse(name: &str) -> Result<Self, FitsError> {
+        [
+            Self::UInt8,
+            Self::UInt16,
+            Self::UInt32,
+            Self::Float32,
+            Self::Float64,
+        ]
+        .into_iter()
+        .find(|f| f.name() == name)
+        .ok_or_else(|| FitsError::Unsupported(format!("XISF sample format {name:?}")))
+    }
+}
+
+/// Internal: lay out preamble, header and the single aligned data block.
+fn serialize<T, F>(
+    format: SampleFormat,
+    pixels: &[T],
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+    compression: Compression,
+    mut emit: F,
+) -> Result<Vec<u8>, FitsError>
+where
+    F: FnMut(&mut Vec<u8>, &T),
+{
+    let expected = width
+        .checked_mul(height)
+        .ok_or_else(|| FitsError::Unsupported(format!("dimensions overflow: {width}x{height}")))?;
+    if pixels.len() != expected {
+        return Err(FitsError::DimensionMismatch {
+            got: pixels.len(),
+            width,
+            height,
+            expected,
+        });
+    }
+
+    let mut raw = Vec::with_capacity(expected * format.size());
+    for p in pixels {
+        emit(&mut raw, p);
+    }
+    let (block, codec) = encode(raw, format.size(), compression)?;
+
+    // The header names the block's position, and the position depends
+    // on the header's length: grow it an alignment step at a time until
+    // the header fits in front of it.
+    let created = timestamp(SystemTime::now());
+    let mut position = BLOCK_ALIGNMENT;
+    let xml = loop {
+        let xml = header_xml(
+            format,
+            width,
+            height,
+            extra,
+            (position, block.len()),
+            codec.as_deref(),
+            &created,
+        );
+        let needed = (PREAMBLE + xml.len()).next_multiple_of(BLOCK_ALIGNMENT);
+        if needed <= position {
+            break xml;
+        }
+        position = needed;
+    };
+    let xml_len = u32::try_from(xml.len()).map_err(|_| {
+        FitsError::Unsupported(format!("XISF header too large: {} bytes", xml.len()))
+    })?;
+
+    let mut out = Vec::with_capacity(position + block.len());
+    out.extend_from_slice(SIGNATURE);
+    out.extend_from_slice(&xml_len.to_le_bytes());
+    out.extend_from_slice(&[0; 4]);
+    out.extend_from_slice(xml.as_bytes());
+    out.resize(position, 0);
+    out.extend_from_slice(&block);
+    Ok(out)
+}
+
+/// Compress `raw` per `compression`, returning the block and the
+/// `compression` attribute describing it (`None`: stored as-is).
+fn encode(
+    raw: Vec<u8>,
+    item_size: usize,
+    compression: Compression,
+) -> Result<(Vec<u8>, Option<String>), FitsError> {
+    let codec = match compression {
+        Compression::None => return Ok((raw, None)),
+        Compression::Zlib => "zlib",
+        Compression::Lz4 => "lz4",
+    };
+    let shuffled = shuffle(&raw, item_size);
+    let packed = if compression == Compression::Zlib {
+        let mut enc = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        enc.write_all(&shuffled)?;
+        enc.finish()?
+    } else {
+        crate::lz4::compress(&shuffled)
+    };
+    if packed.len() >= raw.len() {
+        return Ok((raw, None));
+    }
+    let attr = if item_size > 1 {
+        format!("{codec}+sh:{}:{item_size}", raw.len())
+    } else {
+        format!("{codec}:{}", raw.len())
+    };
+    Ok((packed, Some(attr)))
+}
+
+/// `block` is the data block's `(position, size)`.
+fn header_xml(
+    format: SampleFormat,
+    width: usize,
+    height: usize,
+    extra: &[Keyword],
+    (position, size): (usize, usize),
+    codec: Option<&str>,
+    created: &str,
+) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(
+        "<xisf version=\"1.0\" xmlns=\"http://www.pixinsight.com/xisf\" \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+         xsi:schemaLocation=\"http://www.pixinsight.com/xisf \
+         http://pixinsight.com/xisf/xisf-1.0.xsd\">\n",
+    );
+    let _ = write!(
+        xml,
+        "<Image geometry=\"{width}:{height}:1\" sampleFormat=\"{}\" colorSpace=\"Gray\" \
+         location=\"attachment:{position}:{size}\"",
+        format.name()
+    );
+    if let Some(codec) = codec {
+        let _ = write!(xml, " compression=\"{codec}\"");
+    }
+    xml.push_str(">\n");
+    for kw in extra {
+        let _ = writeln!(
+            xml,
+            "<FITSKeyword name=\"{}\" value=\"{}\" comment=\"{}\"/>",
+            kw.key(),
+            escape(&card_value(kw.value())),
+            escape(kw.comment().unwrap_or_default())
+        );
+    }
+    xml.push_str("</Image>\n<Metadata>\n");
+    let _ = writeln!(
+        xml,
+        "<Property id=\"XISF:CreationTime\" type=\"TimePoint\" value=\"{created}\"/>\n\
+         <Property id=\"XISF:CreatorApplication\" type=\"String\">{}</Property>",
+        escape(CREATOR)
+    );
+    xml.push_str("</Metadata>\n</xisf>\n");
+    xml
+}
+
+/// A keyword value as it would read on a FITS card.
+fn card_value(value: &KeywordValue) -> String {
+    match value {
+        KeywordValue::Bool(b) => String::from(if *b { "T" } else { "F" }),
+        KeywordValue::Int(i) => i.to_string(),
+        KeywordValue::Float(f) => format_float(*f),
+        KeywordValue::Str(s) => format!("'{}'", s.replace('\'', "''")),
+    }
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Byte-shuffle: every item's first byte, then every item's second
+/// byte, and so on; a trailing partial item is copied as-is.
+fn shuffle(data: &[u8], item_size: usize) -> Vec<u8> {
+    let items = data.len() / item_size;
+    let mut out = Vec::with_capacity(data.len());
+    for byte in 0..item_size {
+        out.extend((0..items).map(|i| data[i * item_size + byte]));
+    }
+    out.extend_from_slice(&data[items * item_size..]);
+    out
+}
+
+fn unshuffle(data: &[u8], item_size: usize) -> Vec<u8> {
+    let items = data.len() / item_size;
+    let mut out = vec![0; data.len()];
+    for byte in 0..item_size {
+        for i in 0..items {
+            out[i * item_size + byte] = data[byte * items + i];
+        }
+    }
+    out[items * item_size..].copy_from_slice(&data[items * item_size..]);
+    out
+}
+
+fn samples<T, const N: usize>(raw: &[u8], big_endian: bool, from_le: fn([u8; N]) -> T) -> Vec<T> {
+    raw.chunks_exact(N)
+        .map(|chunk| {
+            let mut bytes = [0; N];
+            bytes.copy_from_slice(chunk);
+            if big_endian {
+                bytes.reverse();
+            }
+            from_le(bytes)
+        })
+        .collect()
+}
+
+/// ISO 8601 UTC, millisecond precision — an XISF `TimePoint`.
+fn timestamp(now: SystemTime) -> String {
+    DateTime::<Utc>::from(now).to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+struct Header {
+    image: ImageElement,
+    keywords: Vec<(String, KeywordValue)>,
+}
+
+struct ImageElement {
+    width: usize,
+    height: usize,
+    format: SampleFormat,
+    position: u64,
+    size: u64,
+    codec: Option<Codec>,
+    big_endian: bool,
+}
+
+struct Codec {
+    lz4: bool,
+    size: usize,
+    /// Shuffle item size, for the `+sh` variants.
+    item_size: Option<usize>,
+}
+
+impl Codec {
+    /// Parse a `compression` attribute: `<codec>:<size>` or
+    /// `<codec>+sh:<size>:<item-size>`.
+    fn parse(attr: &str) -> Result<Self, FitsError> {
+        let mut parts = attr.split(':');
+        let name = parts.next().unwrap_or_default();
+        let (base, shuffled) = match name.strip_suffix("+sh") {
+            Some(base) => (base, true),
+            None => (name, false),
+        };
+        let lz4 = match base {
+            "zlib" => false,
+            "lz4" | "lz4hc" => true,
+            other => {
+                return Err(FitsError::Unsupported(format!(
+                    "XISF compression codec {other:?}"
+                )))
+            }
+        };
+        let mut number = |what: &str| {
+            parts
+                .next()
+                .and_then(|s| s.parse::<usize>().ok())
+                .ok_or_else(|| malformed(&format!("compression {attr:?} lacks a valid {what}")))
+        };
+        let size = number("uncompressed size")?;
+        let item_size = if shuffled {
+            Some(number("item size")?.max(1))
+        } else {
+            None
+        };
+        Ok(Self {
+            lz4,
+            size,
+            item_size,
+        })
+    }
+
+    fn decode(&self, block: &[u8]) -> Result<Vec<u8>, FitsError> {
+        let packed = if self.lz4 {
+            crate::lz4::decompress(block, self.size)?
+        } else {
+            let mut out = Vec::with_capacity(self.size);
+            ZlibDecoder::new(block)
+                .take(self.size as u64 + 1)
+                .read_to_end(&mut out)
+                .map_err(|e| FitsError::Parse(format!("corrupt zlib block: {e}")))?;
+            if out.len() != self.size {
+                return Err(FitsError::Parse(format!(
+                    "zlib block inflates to {} bytes, expected {}",
+                    out.len(),
+                    self.size
+                )));
+            }
+            out
+        };
+        Ok(match self.item_size {
+            Some(n) => unshuffle(&packed, n),
+            None => packed,
+        })
+    }
+}
+
+fn malformed(what: &str) -> FitsError {
+    FitsError::MalformedHeader(format!("XISF: {what}"))
+}
+
+fn read_header<R: Read>(reader: &mut R) -> Result<Header, FitsError> {
+    let mut preamble = [0u8; PREAMBLE];
+    reader.read_exact(&mut preamble)?;
+    if &preamble[..8] != SIGNATURE {
+        return Err(FitsError::Parse("not an XISF 1.0 stream".into()));
+    }
+    let len = u32::from_le_bytes([preamble[8], preamble[9], preamble[10], preamble[11]]);
+    let mut xml = Vec::new();
+    reader.take(u64::from(len)).read_to_end(&mut xml)?;
+    if u64::try_from(xml.len()).ok() != Some(u64::from(len)) {
+        return Err(malformed("header truncated"));
+    }
+    parse_header(&xml)
+}
+
+fn parse_header(xml: &[u8]) -> Result<Header, FitsError> {
+    let mut image = None;
+    let mut keywords = Vec::new();
+    let mut in_image = false;
+    for tag in tags(xml)? {
+        match (tag.name.as_str(), tag.close) {
+            ("Image", false) if image.is_none() => {
+                image = Some(image_element(&tag)?);
+                in_image = !tag.empty;
+            }
+            ("Image", true) => in_image = false,
+            ("FITSKeyword", false) if in_image => {
+                let value = tag
+                    .attr("value")
+                    .and_then(|v| crate::tiled::parse_value(v.as_bytes()));
+                if let (Some(name), Some(value)) = (tag.attr("name"), value) {
+                    keywords.push((name.trim().to_ascii_uppercase(), value));
+                }
+            }
+            _ => {}
+        }
+    }
+    let image = image.ok_or_else(|| malformed("header has no Image element"))?;
+    Ok(Header { image, keywords })
+}
+
+fn image_element(tag: &Tag) -> Result<ImageElement, FitsError> {
+    let attr = |name: &'static str| {
+        tag.attr(name)
+            .ok_or_else(|| malformed(&format!("Image lacks {name}")))
+    };
+
+    let geometry = attr("geometry")?;
+    let dims: Vec<usize> = geometry
+        .split(':')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .map_err(|_| malformed(&format!("bad geometry {geometry:?}")))?;
+    let &[width, height, channels] = dims.as_slice() else {
+        return Err(FitsError::Unsupported(format!(
+            "only 2-D XISF images are supported (geometry {geometry:?})"
+        )));
+    };
+    if channels != 1 {
+        return Err(FitsError::Unsupported(format!(
+            "only single-channel XISF images are supported ({channels} channels)"
+        )));
+    }
+
+    let location = attr("location")?;
+    let mut parts = location.split(':');
+    let (Some("attachment"), Some(position), Some(size), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(FitsError::Unsupported(format!(
+            "XISF block location {location:?} (only attachments are supported)"
+        )));
+    };
+    let (Ok(position), Ok(size)) = (position.parse(), size.parse()) else {
+        return Err(malformed(&format!("bad location {location:?}")));
+    };
+
+    Ok(ImageElement {
+        width,
+        height,
+        format: SampleFormat::parse(attr("sampleFormat")?)?,
+        position,
+        size,
+        codec: tag.attr("compression").map(Codec::parse).transpose()?,
+        big_endian: tag.attr("byteOrder") == Some("big"),
+    })
+}
+
+/// A start (`close == false`) or end tag, with its attributes unescaped.
+struct Tag {
+    name: String,
+    attrs: Vec<(String, String)>,
+    close: bool,
+    /// `<name ... />`: no content, no end tag.
+    empty: bool,
+}
+
+impl Tag {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+fn tags(xml: &[u8]) -> Result<Vec<Tag>, FitsError> {
+    let mut tags = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = xml[pos..].iter().position(|&b| b == b'<') {
+        let start = pos + offset + 1;
+        let rest = &xml[start..];
+        pos = if rest.starts_with(b"!--") {
+            find(xml, start, b"-->")? + 3
+        } else if rest.starts_with(b"![CDATA[") {
+            find(xml, start, b"]]>")? + 3
+        } else if rest.starts_with(b"?") || rest.starts_with(b"!") {
+            find(xml, start, b">")? + 1
+        } else {
+            let (tag, next) = parse_tag(xml, start)?;
+            tags.push(tag);
+            next
+        };
+    }
+    Ok(tags)
+}
+
+fn find(xml: &[u8], from: usize, pattern: &[u8]) -> Result<usize, FitsError> {
+    xml[from..]
+        .windows(pattern.len())
+        .position(|w| w == pattern)
+        .map(|i| from + i)
+        .ok_or_else(|| malformed("unterminated markup"))
+}
+
+/// Parse the tag whose name starts at `pos`; returns it and the
+/// position just past its `>`.
+fn parse_tag(xml: &[u8], mut pos: usize) -> Result<(Tag, usize), FitsError> {
+    let close = xml.get(pos) == Some(&b'/');
+    if close {
+        pos += 1;
+    }
+    let name = take_name(xml, &mut pos);
+    if name.is_empty() {
+        return Err(malformed("tag without a name"));
+    }
+    let mut tag = Tag {
+        name,
+        attrs: Vec::new(),
+        close,
+        empty: false,
+    };
+    loop {
+        while xml.get(pos).is_some_and(u8::is_ascii_whitespace) {
+            pos += 1;
+        }
+        match xml.get(pos) {
+            None => return Err(malformed(&format!("unterminated <{}> tag", tag.name))),
+            Some(b'>') => return Ok((tag, pos + 1)),
+            Some(b'/') => {
+                tag.empty = true;
+                pos += 1;
+            }
+            Some(_) => {
+                let key = take_name(xml, &mut pos);
+                while xml.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                    pos += 1;
+                }
+                if key.is_empty() || xml.get(pos) != Some(&b'=') {
+                    return Err(malformed(&format!("bad attribute in <{}>", tag.name)));
+                }
+                pos += 1;
+                while xml.get(pos).is_some_and(u8::is_ascii_whitespace) {
+                    pos += 1;
+                }
+                let quote = match xml.get(pos) {
+                    Some(&q @ (b'"' | b'\'')) => q,
+                    _ => return Err(malformed(&format!("unquoted attribute {key:?}"))),
+                };
+                let end = find(xml, pos + 1, &[quote])?;
+                tag.attrs.push((key, unescape(&xml[pos + 1..end])?));
+                pos = end + 1;
+            }
+        }
+    }
+}
+
+fn take_name(xml: &[u8], pos: &mut usize) -> String {
+    let start = *pos;
+    while xml
+        .get(*pos)
+        .is_some_and(|b| !b.is_ascii_whitespace() && !b"=/>\"'".contains(b))
+    {
+        *pos += 1;
+    }
+    String::from_utf8_lossy(&xml[start..*pos]).into_owned()
+}
+
+fn unescape(raw: &[u8]) -> Result<String, FitsError> {
+    let text = std::str::from_utf8(raw).map_err(|_| malformed("attribute is not UTF-8"))?;
+    let mut parts = text.split('&');
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let (entity, rest) = part
+            .split_once(';')
+            .ok_or_else(|| malformed("unterminated entity"))?;
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .map_or_else(
+                    || entity.strip_prefix('#').and_then(|d| d.parse().ok()),
+                    |hex| u32::from_str_radix(hex, 16).ok(),
+                )
+                .and_then(char::from_u32),
+        };
+        out.push(c.ok_or_else(|| malformed(&format!("unknown entity &{entity};")))?);
+        out.push_str(rest);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn kw(key: &str, value: KeywordValue) -> Keyword {
+        Keyword::new(key, value).unwrap()
+    }
+
+    /// A smooth gradient plus a little texture — compressible, like sky.
+    fn frame(width: usize, height: usize) -> Vec<u16> {
+        (0..width * height)
+            .map(|i| 1000 + (i % width) as u16 * 3 + (i / width) as u16 + (i % 7) as u16)
+            .collect()
+    }
+
+    fn xisf_file(xml: &str, block: &[u8]) -> Vec<u8> {
+        let mut out = SIGNATURE.to_vec();
+        out.extend_from_slice(&(xml.len() as u32).to_le_bytes());
+        out.extend_from_slice(&[0; 4]);
+        out.extend_from_slice(xml.as_bytes());
+        out.extend_from_slice(block);
+        out
+    }
+
+    #[test]
+    fn u16_round_trips_under_every_compression() {
+        let pixels = frame(64, 48);
+        let extra = [
+            kw("DOC_ID", KeywordValue::Str("abc-123".into())),
+            kw("EXPTIME", KeywordValue::Float(300.0)).with_comment("seconds"),
+            kw("GAIN", KeywordValue::Int(100)),
+        ];
+        let mut sizes = Vec::new();
+        for compression in [Compression::None, Compression::Zlib, Compression::Lz4] {
+            let mut bytes = Vec::new();
+            write_u16_image(&mut bytes, &pixels, 64, 48, &extra, compression).unwrap();
+            sizes.push(bytes.len());
+
+            let img = read_image(Cursor::new(&bytes)).unwrap();
+            assert_eq!((img.width, img.height), (64, 48));
+            let XisfPixels::U16(data) = img.data else {
+                panic!("expected UInt16 samples");
+            };
+            assert_eq!(data, pixels, "{compression:?}");
+            assert_eq!(
+                img.keywords,
+                vec![
+                    ("DOC_ID".to_string(), KeywordValue::Str("abc-123".into())),
+                    ("EXPTIME".to_string(), KeywordValue::Float(300.0)),
+                    ("GAIN".to_string(), KeywordValue::Int(100)),
+                ]
+            );
+        }
+        assert!(
+            sizes[1] < sizes[0] && sizes[2] < sizes[0],
+            "sizes {sizes:?}"
+        );
+    }
+
+    #[test]
+    fn u32_round_trips_and_narrows_to_i32() {
+        let pixels = vec![0u32, 1, 70_000, u32::MAX];
+        for compression in [Compression::None, Compression::Zlib, Compression::Lz4] {
+            let mut bytes = Vec::new();
+            write_u32_image(&mut bytes, &pixels, 2, 2, &[], compression).unwrap();
+            let (read, w, h) = read_image_as_i32(Cursor::new(&bytes)).unwrap();
+            assert_eq!(read, vec![0, 1, 70_000, i32::MAX]);
+            assert_eq!((w, h), (2, 2));
+        }
+    }
+
+    #[test]
+    fn layout_is_preamble_header_then_aligned_block() {
+        let mut bytes = Vec::new();
+        write_u16_image(&mut bytes, &[1, 2, 3, 4], 2, 2, &[], Compression::None).unwrap();
+        assert_eq!(&bytes[..8], b"XISF0100");
+        let len = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+        let xml = std::str::from_utf8(&bytes[16..16 + len]).unwrap();
+        assert!(xml.contains("geometry=\"2:2:1\""), "{xml}");
+        assert!(xml.contains("location=\"attachment:4096:8\""), "{xml}");
+        assert!(xml.contains("XISF:CreationTime"), "{xml}");
+        assert_eq!(bytes.len(), 4096 + 8);
+        assert_eq!(&bytes[4096..], &[1, 0, 2, 0, 3, 0, 4, 0]);
+    }
+
+    #[test]
+    fn a_long_header_pushes_the_block_to_the_next_boundary() {
+        let extra: Vec<Keyword> = (0..100)
+            .map(|i| kw(&format!("KEY{i}"), KeywordValue::Str("x".repeat(60))))
+            .collect();
+        let mut bytes = Vec::new();
+        write_u16_image(&mut bytes, &[7; 4], 2, 2, &extra, Compression::None).unwrap();
+        assert_eq!(bytes.len() % BLOCK_ALIGNMENT, 8);
+        assert!(bytes.len() > BLOCK_ALIGNMENT * 2);
+        let img = read_image(Cursor::new(&bytes)).unwrap();
+        assert_eq!(img.keywords.len(), 100);
+    }
+
+    #[test]
+    fn incompressible_blocks_are_stored_plain() {
+        let mut bytes = Vec::new();
+        write_u16_image(&mut bytes, &[9], 1, 1, &[], Compression::Zlib).unwrap();
+        let len = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+        let xml = std::str::from_utf8(&bytes[16..16 + len]).unwrap();
+        assert!(!xml.contains("compression="), "{xml}");
+    }
+
+    #[test]
+    fn string_values_escape_for_both_fits_and_xml() {
+        let extra = [kw("OBJECT", KeywordValue::Str("O'Brien <&> \"x\"".into()))];
+        let mut bytes = Vec::new();
+        write_u16_image(&mut bytes, &[1], 1, 1, &extra, Compression::None).unwrap();
+        assert_eq!(
+            read_keyword(Cursor::new(&bytes), "object").unwrap(),
+            Some(KeywordValue::Str("O'Brien <&> \"x\"".into()))
+        );
+        assert_eq!(read_keyword(Cursor::new(&bytes), "FILTER").unwrap(), None);
+    }
+
+    #[test]
+    fn reads_a_pixinsight_style_header() {
+        // Big-endian Float32, a comment, a valueless HISTORY keyword and
+        // numeric entities; keywords outside the first image are ignored.
+        let xml = "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\
+            <!-- written by hand -->\
+            <xisf version='1.0'>\
+            <Image geometry=\"2:1:1\" sampleFormat=\"Float32\" bounds=\"0:1\" \
+              byteOrder=\"big\" location=\"attachment:2048:8\" >\
+            <FITSKeyword name=\"IMAGETYP\" value=\"'Light Frame'\" comment=\"\"/>\
+            <FITSKeyword name=\"HISTORY\" value=\"\" comment=\"calibrated\"/>\
+            <FITSKeyword name=\"EXPTIME\" value=\"300.\" comment=\"&#x5B;s&#93;\"/>\
+            <Property id=\"PCL:Note\" type=\"String\">a &gt; b</Property>\
+            </Image>\
+            <Image geometry=\"1:1:1\" sampleFormat=\"UInt8\" location=\"attachment:2056:1\">\
+            <FITSKeyword name=\"SECOND\" value=\"T\" comment=\"\"/>\
+            </Image>\
+            </xisf>";
+        let mut bytes = xisf_file(xml, &[]);
+        bytes.resize(2048, 0);
+        bytes.extend_from_slice(&0.5f32.to_be_bytes());
+        bytes.extend_from_slice(&2.0f32.to_be_bytes());
+
+        let img = read_image(Cursor::new(&bytes)).unwrap();
+        let XisfPixels::F32(data) = &img.data else {
+            panic!("expected Float32 samples");
+        };
+        assert_eq!(data, &vec![0.5, 2.0]);
+        assert_eq!(
+            img.keyword("imagetyp"),
+            Some(&KeywordValue::Str("Light Frame".into()))
+        );
+        assert_eq!(img.keyword("EXPTIME"), Some(&KeywordValue::Float(300.0)));
+        assert_eq!(img.keywords.len(), 2);
+        assert_eq!(img.keyword("SECOND"), None);
+
+        assert!(img.data.is_float());
+        assert_eq!(img.clone().into_f32(), vec![0.5, 2.0]);
+        assert_eq!(img.into_i32(), vec![0, 2]);
+        let (data, w, h) = read_image_as_f32(Cursor::new(&bytes)).unwrap();
+        assert_eq!((data, w, h), (vec![0.5, 2.0], 2, 1));
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_read() {
+        let read = |image: &str| {
+            let xml = format!("<xisf version=\"1.0\">{image}</xisf>");
+            read_image(Cursor::new(xisf_file(&xml, &[0; 64])))
+        };
+        let unsupported =
+            |r: Result<XisfImage, FitsError>| matches!(r, Err(FitsError::Unsupported(_)));
+        assert!(unsupported(read(
+            "<Image geometry=\"2:2:3\" sampleFormat=\"UInt16\" location=\"attachment:0:24\"/>"
+        )));
+        assert!(unsupported(read(
+            "<Image geometry=\"2:2:1\" sampleFormat=\"UInt16\" location=\"inline:base64\"/>"
+        )));
+        assert!(unsupported(read(
+            "<Image geometry=\"2:2:1\" sampleFormat=\"Complex32\" location=\"attachment:0:8\"/>"
+        )));
+        assert!(unsupported(read(
+            "<Image geometry=\"2:2:1\" sampleFormat=\"UInt16\" location=\"attachment:0:8\" \
+             compression=\"zstd:8\"/>"
+        )));
+        assert!(matches!(
+            read("<Metadata/>"),
+            Err(FitsError::MalformedHeader(_))
+        ));
+        assert!(matches!(
+            read_image(Cursor::new(b"SIMPLE  =                    T".to_vec())),
+            Err(FitsError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn a_block_short_of_its_geometry_is_a_parse_error() {
+        let mut bytes = Vec::new();
+        write_u16_image(&mut bytes, &[1, 2, 3, 4], 2, 2, &[], Compression::None).unwrap();
+        bytes.truncate(bytes.len() - 1);
+        assert!(matches!(
+            read_image(Cursor::new(&bytes)),
+            Err(FitsError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_dimension_mismatch() {
+        let mut bytes = Vec::new();
+        let err = write_u16_image(&mut bytes, &[1, 2, 3], 2, 2, &[], Compression::Lz4).unwrap_err();
+        assert!(matches!(err, FitsError::DimensionMismatch { got: 3, .. }));
+    }
+
+    #[test]
+    fn shuffle_groups_bytes_by_significance() {
+        let data = [1, 2, 3, 4, 5, 6, 7];
+        let shuffled = shuffle(&data, 2);
+        assert_eq!(shuffled, vec![1, 3, 5, 2, 4, 6, 7]);
+        assert_eq!(unshuffle(&shuffled, 2), data);
+    }
+
+    #[test]
+    fn timestamps_are_utc_iso_8601() {
+        let at = |secs: u64, millis: u64| {
+            timestamp(UNIX_EPOCH + Duration::from_millis(secs * 1000 + millis))
+        };
+        assert_eq!(at(0, 0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(at(951_868_800, 5), "2000-03-01T00:00:00.005Z");
+        assert_eq!(at(1_709_164_800 + 3661, 0), "2024-02-29T01:01:01.000Z");
+    }
+}
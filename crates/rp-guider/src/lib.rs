@@ -4,10 +4,12 @@
 //! Wraps the guider service's frozen HTTP contract (the
 //! `phd2-guider` binary's `serve` mode — `POST
 //! /api/v1/guiding/{start,stop,pause,resume}`, `POST /api/v1/dither`,
-//! `GET /api/v1/guiding/stats`) behind a small, mockable Rust API.
-//! Used by `rp`'s guiding MCP tools (`start_guiding`, `stop_guiding`,
-//! `dither`, `pause_guiding`, `resume_guiding`, `get_guiding_stats`)
-//! and by the safety enforcer's stop-guiding-on-unsafe path.
+//! `GET /api/v1/guiding/stats`, `GET`/`POST /api/v1/guiding/settings`)
+//! behind a small, mockable Rust API. Used by `rp`'s guiding MCP tools
+//! (`start_guiding`, `stop_guiding`, `dither`, `pause_guiding`,
+//! `resume_guiding`, `get_guiding_stats`, `get_guiding_settings`,
+//! `set_guiding_settings`) and by the safety enforcer's
+//! stop-guiding-on-unsafe path.
 //!
 //! Wire types are defined here, not pulled from the `phd2-guider`
 //! crate — the inter-service contract is HTTP, not in-process Rust
@@ -16,6 +18,7 @@
 //! is a thin transport. All positional quantities are **guide-camera
 //! pixels** (`*_px`, `settle.pixels`), matching the service.
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -162,6 +165,91 @@ pub struct PhdEquipment {
     pub rotator: Option<EquipmentSlot>,
 }
 
+/// Declination guide mode, in PHD2's spelling: which Dec corrections
+/// the guider issues. A one-sided mode (`North` / `South`) keeps a
+/// mount with Dec backlash from ever reversing the axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DecGuideMode {
+    Off,
+    Auto,
+    North,
+    South,
+}
+
+/// One axis of the guide algorithm settings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AxisSettings {
+    /// Smallest error, in guide-camera pixels, the axis corrects;
+    /// `None` when the axis' algorithm has no min-move.
+    pub min_move_px: Option<f64>,
+    /// Fraction of each error corrected; `None` when the axis'
+    /// algorithm has no aggressiveness.
+    pub aggressiveness: Option<f64>,
+    /// Every numeric parameter of the axis' algorithm, under the
+    /// guider's own names (PHD2's `minMove`, `hysteresis`, ...).
+    #[serde(default)]
+    pub params: BTreeMap<String, f64>,
+}
+
+/// Dec backlash compensation: an extra pulse added to the first Dec
+/// correction after the axis reverses.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct BacklashCompensation {
+    pub enabled: bool,
+    /// Length of the extra pulse.
+    #[serde(with = "humantime_serde")]
+    pub pulse: Duration,
+}
+
+/// Success body of `GET` / `POST /api/v1/guiding/settings`. The
+/// settings a guider keeps out of reach of its API (PHD2's
+/// multi-star and backlash compensation) are `None`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GuidingSettings {
+    pub dec_guide_mode: DecGuideMode,
+    pub ra: AxisSettings,
+    pub dec: AxisSettings,
+    pub multi_star: Option<bool>,
+    pub backlash_compensation: Option<BacklashCompensation>,
+}
+
+/// One axis of a settings update. `None` / empty means "leave as is".
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct AxisSettingsUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_move_px: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggressiveness: Option<f64>,
+    /// Algorithm parameters to set, by the guider's own names.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, f64>,
+}
+
+impl AxisSettingsUpdate {
+    /// `true` when the update leaves the axis untouched — the caller
+    /// should send no axis object at all.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.min_move_px.is_none() && self.aggressiveness.is_none() && self.params.is_empty()
+    }
+}
+
+/// Request body for `POST /api/v1/guiding/settings`: a partial update,
+/// every field optional and omitted from the wire when unset.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GuidingSettingsUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dec_guide_mode: Option<DecGuideMode>,
+    #[serde(skip_serializing_if = "AxisSettingsUpdate::is_empty")]
+    pub ra: AxisSettingsUpdate,
+    #[serde(skip_serializing_if = "AxisSettingsUpdate::is_empty")]
+    pub dec: AxisSettingsUpdate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multi_star: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backlash_compensation: Option<BacklashCompensation>,
+}
+
 /// Success body of the state-only endpoints (`stop`, `pause`,
 /// `resume`, `calibration/clear`, `star/reselect`). Parsed to
 /// validate the response shape; callers get `()`.
@@ -250,6 +338,17 @@ pub trait GuiderClient: Send + Sync {
     /// `POST /api/v1/star/reselect` — auto-select a guide star on
     /// the current frame (after a rotation moved it).
     async fn reselect_star(&self) -> Result<(), GuiderError>;
+
+    /// `GET /api/v1/guiding/settings` — the Dec guide mode and the
+    /// per-axis guide algorithm settings.
+    async fn guiding_settings(&self) -> Result<GuidingSettings, GuiderError>;
+
+    /// `POST /api/v1/guiding/settings` — apply a partial settings
+    /// update; returns the settings as read back afterwards.
+    async fn update_guiding_settings(
+        &self,
+        update: GuidingSettingsUpdate,
+    ) -> Result<GuidingSettings, GuiderError>;
 }
 
 /// Concrete reqwest-backed implementation of [`GuiderClient`].
@@ -446,6 +545,21 @@ impl GuiderClient for GuiderServiceClient {
             .await
             .map(|_| ())
     }
+
+    async fn guiding_settings(&self) -> Result<GuidingSettings, GuiderError> {
+        let url = format!("{}/api/v1/guiding/settings", self.base_url);
+        self.execute(self.client.get(&url).timeout(self.timeout))
+            .await
+    }
+
+    async fn update_guiding_settings(
+        &self,
+        update: GuidingSettingsUpdate,
+    ) -> Result<GuidingSettings, GuiderError> {
+        let url = format!("{}/api/v1/guiding/settings", self.base_url);
+        self.execute(self.client.post(&url).timeout(self.timeout).json(&update))
+            .await
+    }
 }
 
 #[cfg(test)]
@@ -499,6 +613,10 @@ mod tests {
             .route("/api/v1/guiding/resume", post(state_handler))
             .route("/api/v1/dither", post(settled_handler))
             .route("/api/v1/guiding/stats", get(stats_handler))
+            .route(
+                "/api/v1/guiding/settings",
+                get(settings_handler).post(settings_handler),
+            )
             .with_state(state);
 
         let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -594,6 +712,32 @@ mod tests {
         )
     }
 
+    async fn settings_handler(
+        State(state): State<StubState>,
+        uri: axum::http::Uri,
+        body: axum::body::Bytes,
+    ) -> axum::response::Response {
+        record(&state, &uri, &body).await;
+        behavior_response(
+            &state.behavior,
+            serde_json::json!({
+                "dec_guide_mode": "Auto",
+                "ra": {
+                    "min_move_px": 0.15,
+                    "aggressiveness": 0.7,
+                    "params": { "minMove": 0.15, "hysteresis": 0.1, "aggression": 0.7 },
+                },
+                "dec": {
+                    "min_move_px": 0.2,
+                    "aggressiveness": null,
+                    "params": { "minMove": 0.2 },
+                },
+                "multi_star": null,
+                "backlash_compensation": null,
+            }),
+        )
+    }
+
     fn client_for(stub: &TestStub) -> GuiderServiceClient {
         GuiderServiceClient::new(stub.url.clone(), Duration::from_secs(5), None, None).unwrap()
     }
@@ -738,6 +882,54 @@ mod tests {
         assert_eq!(stats.sample_count, 2);
     }
 
+    #[tokio::test]
+    async fn guiding_settings_parse_with_the_unexposed_settings_as_none() {
+        let stub = spawn_stub(StubBehavior::Success).await;
+        let client = client_for(&stub);
+
+        let settings = client.guiding_settings().await.unwrap();
+
+        assert_eq!(settings.dec_guide_mode, DecGuideMode::Auto);
+        assert_eq!(settings.ra.min_move_px, Some(0.15));
+        assert_eq!(settings.ra.params["hysteresis"], 0.1);
+        assert!(settings.dec.aggressiveness.is_none());
+        assert!(settings.multi_star.is_none());
+        assert!(settings.backlash_compensation.is_none());
+    }
+
+    #[tokio::test]
+    async fn a_settings_update_sends_only_what_it_sets() {
+        let stub = spawn_stub(StubBehavior::Success).await;
+        let client = client_for(&stub);
+
+        client
+            .update_guiding_settings(GuidingSettingsUpdate {
+                dec_guide_mode: Some(DecGuideMode::North),
+                dec: AxisSettingsUpdate {
+                    min_move_px: Some(0.3),
+                    ..AxisSettingsUpdate::default()
+                },
+                backlash_compensation: Some(BacklashCompensation {
+                    enabled: true,
+                    pulse: Duration::from_millis(450),
+                }),
+                ..GuidingSettingsUpdate::default()
+            })
+            .await
+            .unwrap();
+
+        let (path, body) = stub.requests.read().await[0].clone();
+        assert_eq!(path, "/api/v1/guiding/settings");
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "dec_guide_mode": "North",
+                "dec": { "min_move_px": 0.3 },
+                "backlash_compensation": { "enabled": true, "pulse": "450ms" },
+            })
+        );
+    }
+
     #[tokio::test]
     async fn null_telemetry_fields_parse_as_none() {
         // The Error arm doubles as "return this exact body with this
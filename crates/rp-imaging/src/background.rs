@@ -10,7 +10,7 @@
 
 use ndarray::ArrayView2;
 
-use super::pixel::Pixel;
+use crate::pixel::Pixel;
 
 /// Result of a sigma-clipped statistics pass.
 #[derive(Debug, Clone, Copy)]
@@ -23,14 +23,20 @@ pub struct BackgroundStats {
 
 /// Iterative sigma-clip with caller-controlled `k` and iteration cap.
 ///
-/// Returns `None` if the input view is empty or all pixels are clipped away.
+/// Returns `None` if the input view is empty (or holds only NaN) or all
+/// pixels are clipped away.
 #[must_use]
 pub fn sigma_clipped_stats<T: Pixel>(
     view: ArrayView2<T>,
     k: f64,
     max_iters: usize,
 ) -> Option<BackgroundStats> {
-    let mut values: Vec<f64> = view.iter().map(|p| p.to_f64()).collect();
+    // Undefined (NaN) pixels of a float frame take no part.
+    let mut values: Vec<f64> = view
+        .iter()
+        .map(|p| p.to_f64())
+        .filter(|v| v.is_finite())
+        .collect();
     if values.is_empty() {
         return None;
     }
@@ -190,4 +196,17 @@ mod tests {
         assert!(tight.n_pixels < loose.n_pixels);
         assert_eq!(loose.n_pixels, 100);
     }
+
+    #[test]
+    fn nan_pixels_are_skipped() {
+        let mut arr: Array2<f32> = Array2::from_elem((10, 10), 0.25);
+        arr[[0, 0]] = f32::NAN;
+        arr[[9, 9]] = f32::NAN;
+        let stats = estimate_background(arr.view()).unwrap();
+        assert_eq!(stats.mean, 0.25);
+        assert_eq!(stats.n_pixels, 98);
+
+        let all_nan: Array2<f32> = Array2::from_elem((4, 4), f32::NAN);
+        assert!(estimate_background(all_nan.view()).is_none());
+    }
 }
@@ -23,7 +23,7 @@
 use ndarray::ArrayView2;
 use rmpfit::{MPFitter, MPResult};
 
-use super::pixel::Pixel;
+use crate::pixel::Pixel;
 
 /// FWHM = 2 · √(2 · ln 2) · σ for a Gaussian. ≈ 2.3548.
 pub const FWHM_OVER_SIGMA: f64 = 2.354_820_045_030_949_4;
@@ -89,9 +89,15 @@ pub fn fit_2d_gaussian<T: Pixel>(
     let mut pixel_v = Vec::with_capacity(n);
     for r in r_min..=r_max {
         for c in c_min..=c_max {
+            // An undefined (NaN) pixel of a float frame is left out of
+            // the fit rather than poisoning it.
+            let v = view[[r.cast_unsigned(), c.cast_unsigned()]].to_f64();
+            if !v.is_finite() {
+                continue;
+            }
             pixel_x.push(r as f64);
             pixel_y.push(c as f64);
-            pixel_v.push(view[[r.cast_unsigned(), c.cast_unsigned()]].to_f64());
+            pixel_v.push(v);
         }
     }
 
@@ -12,8 +12,8 @@
 
 use ndarray::ArrayView2;
 
-use super::pixel::Pixel;
-use super::stars::Star;
+use crate::pixel::Pixel;
+use crate::stars::Star;
 
 /// Per-star half-flux radius in pixels. `None` if the star's total
 /// background-subtracted flux is non-positive.
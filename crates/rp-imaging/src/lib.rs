@@ -0,0 +1,27 @@
+//! Pure image-analysis kernels: single-purpose math over `ArrayView2`.
+//!
+//! Each module is generic over [`Pixel`] and free of I/O, async, and
+//! persistence concerns, so `rp`'s analysis tools and `native-guider`'s
+//! centroiding run the same code. The frame-level SNR aggregate stays in
+//! `rp`, where it reports through rp's error type. See
+//! `docs/services/rp.md` (Module Structure) and
+//! `docs/plans/archive/image-evaluation-tools.md` for the layout
+//! rationale.
+
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod background;
+pub mod fwhm;
+pub mod hfr;
+pub mod pixel;
+pub mod snr;
+pub mod stars;
+pub mod stats;
+
+pub use background::{estimate_background, sigma_clipped_stats, BackgroundStats};
+pub use fwhm::{fit_2d_gaussian, GaussianFit2D};
+pub use hfr::{aggregate_hfr, star_hfr};
+pub use pixel::Pixel;
+pub use snr::per_star_snr;
+pub use stars::{detect_stars, DetectionParams, Star};
+pub use stats::{compute_stats, compute_stats_f64, FloatImageStats, ImageStats};
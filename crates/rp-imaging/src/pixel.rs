@@ -1,12 +1,17 @@
 //! `Pixel` trait — the abstraction analysis algorithms are generic over.
 //!
 //! Cameras emit either `u16` (every consumer/prosumer astro camera) or `i32`
-//! (future scientific sCMOS HDR modes — see [`super::cache::CachedPixels`]).
-//! Each analysis algorithm is written once over `T: Pixel` and monomorphized
-//! for both types.
+//! (future scientific sCMOS HDR modes — see rp's `CachedPixels`);
+//! calibrated masters and stacks read from disk are `f32`. Each analysis
+//! algorithm is written once over `T: Pixel` and monomorphized per type.
+//!
+//! Only `f32` has undefined pixels: NaN, the FITS float convention. Its
+//! `to_f64` keeps the NaN so the algorithms can skip it (they test
+//! `is_finite`); integer pixels are always finite.
 
-/// Pixel value held in [`super::cache::CachedPixels`]. Implementations exist
-/// for `u16` (the primary path) and `i32` (the scientific-camera hatch).
+/// Pixel value held in rp's `CachedPixels`. Implementations exist
+/// for `u16` (the primary path), `i32` (the scientific-camera hatch) and
+/// `f32` (float frames).
 ///
 /// `to_f64` covers the bulk of analysis arithmetic (means, sigma-clipping,
 /// centroids, HFR). `to_u32` is for camera-`max_adu` saturation comparison.
@@ -44,6 +49,20 @@ impl Pixel for i32 {
     }
 }
 
+impl Pixel for f32 {
+    #[inline]
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+
+    /// Truncates; negatives and NaN become `0`, values past `u32::MAX`
+    /// saturate.
+    #[inline]
+    fn to_u32(self) -> u32 {
+        self as u32
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -70,4 +89,14 @@ mod tests {
         assert_eq!(<i32 as Pixel>::to_u32(-1), 0);
         assert_eq!(<i32 as Pixel>::to_u32(i32::MIN), 0);
     }
+
+    #[test]
+    fn f32_pixel_conversions() {
+        assert_eq!(<f32 as Pixel>::to_f64(1.5), 1.5);
+        assert!(<f32 as Pixel>::to_f64(f32::NAN).is_nan());
+        assert_eq!(<f32 as Pixel>::to_u32(65_535.9), 65_535);
+        assert_eq!(<f32 as Pixel>::to_u32(-3.0), 0);
+        assert_eq!(<f32 as Pixel>::to_u32(f32::NAN), 0);
+        assert_eq!(<f32 as Pixel>::to_u32(1e12), u32::MAX);
+    }
 }
@@ -0,0 +1,62 @@
+//! Per-star signal-to-noise via the CCD-equation approximation.
+//!
+//! ```text
+//! noise = sqrt(signal + n_pixels · σ_bg²)
+//! snr   = signal / noise
+//! ```
+//!
+//! where `signal` is the background-subtracted total flux of the
+//! component, `n_pixels` is the area of the component, and `σ_bg` is the
+//! sigma-clipped background standard deviation. This is the standard CCD
+//! equation with the dark-current and read-noise terms collapsed into the
+//! background variance, and the gain implicitly set to 1 ADU/electron —
+//! good enough for relative quality screening across frames from the
+//! *same* camera, **not** an absolute photometric SNR.
+//!
+//! The frame-level aggregate (`compute_snr`) stays in rp, which reports
+//! its failures through rp's error type.
+
+use crate::stars::Star;
+
+/// Per-star SNR triple: `(signal, noise, snr)`. Returns `None` when the
+/// noise term is zero (no signal and a perfectly flat background — only
+/// happens on synthetic test inputs).
+#[must_use]
+pub fn per_star_snr(star: &Star, background_stddev: f64) -> Option<(f64, f64, f64)> {
+    let signal = star.total_flux;
+    let n = star.pixels.len() as f64;
+    let variance = signal.max(0.0) + n * background_stddev * background_stddev;
+    if variance <= 0.0 {
+        return None;
+    }
+    let noise = variance.sqrt();
+    Some((signal, noise, signal / noise))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_star_snr_matches_ccd_equation() {
+        let star = Star {
+            centroid_x: 0.0,
+            centroid_y: 0.0,
+            total_flux: 10_000.0,
+            peak: 5_000.0,
+            pixels: (0..25).map(|i| (i / 5, i % 5)).collect(),
+            bounding_box: (0, 0, 4, 4),
+            saturated_pixel_count: 0,
+        };
+        let bg_stddev = 10.0;
+        let (signal, noise, snr) = per_star_snr(&star, bg_stddev).unwrap();
+        // signal = 10_000; noise = sqrt(10_000 + 25 · 100) = sqrt(12_500) ≈ 111.8
+        assert_eq!(signal, 10_000.0);
+        assert!(
+            (noise - 111.803_398_874_989_5).abs() < 1e-6,
+            "noise = {noise}"
+        );
+        assert!((snr - 89.4427).abs() < 0.01, "snr = {snr}");
+    }
+}
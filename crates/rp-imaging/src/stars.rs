@@ -20,8 +20,8 @@ use std::collections::VecDeque;
 use ndarray::{Array2, ArrayView2};
 use ndarray_ndimage::{gaussian_filter, BorderMode};
 
-use super::background::BackgroundStats;
-use super::pixel::Pixel;
+use crate::background::BackgroundStats;
+use crate::pixel::Pixel;
 
 /// A detected star.
 #[derive(Debug, Clone)]
@@ -79,8 +79,16 @@ pub fn detect_stars<T: Pixel>(
         return Vec::new();
     }
 
-    // Smooth a f64 copy of the input.
-    let f64_data: Array2<f64> = view.mapv(super::pixel::Pixel::to_f64);
+    // Smooth a f64 copy of the input. Undefined (NaN) pixels of a float
+    // frame read as sky, so the kernel doesn't spread them.
+    let f64_data: Array2<f64> = view.mapv(|p| {
+        let v = p.to_f64();
+        if v.is_finite() {
+            v
+        } else {
+            background.mean
+        }
+    });
     let smoothed = if params.smoothing_sigma > 0.0 && rows > 4 && cols > 4 {
         gaussian_filter(&f64_data, params.smoothing_sigma, 0, BorderMode::Reflect, 4)
     } else {
@@ -528,4 +536,30 @@ mod tests {
         let comps = connected_components_4(mask.view());
         assert_eq!(comps.len(), 2);
     }
+
+    #[test]
+    fn nan_pixels_beside_a_star_do_not_spread() {
+        // A float frame normalised to [0, 1], with undefined pixels both
+        // in the sky and on the star's flank.
+        let mut arr = make_gaussian_i32(64, 64, 32.5, 32.5, 1.5, 20_000.0, 1000.0)
+            .mapv(|v| v as f32 / 65_535.0);
+        arr[[10, 10]] = f32::NAN;
+        arr[[32, 35]] = f32::NAN;
+        let bg = BackgroundStats {
+            mean: 1000.0 / 65_535.0,
+            stddev: 5.0 / 65_535.0,
+            median: 1000.0 / 65_535.0,
+            n_pixels: 4094,
+        };
+        let stars = detect_stars(arr.view(), &bg, &default_params(5, 200));
+        assert_eq!(stars.len(), 1);
+        let s = &stars[0];
+        assert!(s.total_flux.is_finite() && s.total_flux > 0.0);
+        assert!(s.peak.is_finite());
+        assert!(
+            (s.centroid_x - 32.5).abs() < 0.5,
+            "centroid_x = {}",
+            s.centroid_x
+        );
+    }
 }
@@ -2,6 +2,9 @@
 //!
 //! Custom implementation on stdlib iterators + `select_nth_unstable` for
 //! median (iterative O(n) quickselect, safe for arbitrarily large images).
+//! Integer frames go through [`compute_stats`] in whole ADU; floating-point
+//! frames (e.g. a normalized [0, 1] master) through [`compute_stats_f64`],
+//! which keeps their fractional values.
 
 /// Pixel-level statistics for an image.
 #[derive(Debug, Clone)]
@@ -75,6 +78,57 @@ pub fn compute_stats(pixels: &mut [i32]) -> Option<ImageStats> {
     })
 }
 
+/// Pixel-level statistics for a floating-point image, in the frame's own
+/// units: unclamped and unrounded, since a normalized or calibrated
+/// frame's values are meaningful below 1 and below 0.
+#[derive(Debug, Clone)]
+pub struct FloatImageStats {
+    pub median: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub pixel_count: u64,
+}
+
+/// The [`compute_stats`] counterpart for floating-point pixels. Same
+/// in-place partition, so the caller's buffer is left permuted. Callers
+/// drop undefined (NaN) pixels first; any that remain order by
+/// [`f64::total_cmp`].
+///
+/// Returns `None` if the pixel slice is empty.
+pub fn compute_stats_f64(pixels: &mut [f64]) -> Option<FloatImageStats> {
+    if pixels.is_empty() {
+        return None;
+    }
+
+    let pixel_count = pixels.len() as u64;
+    let mut min = f64::INFINITY;
+    let mut max = f64::NEG_INFINITY;
+    let mut sum = 0.0_f64;
+    for &p in pixels.iter() {
+        min = min.min(p);
+        max = max.max(p);
+        sum += p;
+    }
+
+    let mid = pixels.len() / 2;
+    let (_, &mut upper, _) = pixels.select_nth_unstable_by(mid, f64::total_cmp);
+    let median = if pixels.len().is_multiple_of(2) {
+        let (_, &mut lower, _) = pixels[..mid].select_nth_unstable_by(mid - 1, f64::total_cmp);
+        f64::midpoint(lower, upper)
+    } else {
+        upper
+    };
+
+    Some(FloatImageStats {
+        median,
+        mean: sum / pixel_count as f64,
+        min,
+        max,
+        pixel_count,
+    })
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -163,4 +217,24 @@ mod tests {
             stats.mean_adu
         );
     }
+
+    #[test]
+    fn compute_stats_f64_keeps_fractional_values() {
+        let mut pixels = vec![0.75, 0.25, 0.5, 1.0];
+        let stats = compute_stats_f64(&mut pixels).unwrap();
+        assert!((stats.median - 0.625).abs() < 1e-12);
+        assert!((stats.mean - 0.625).abs() < 1e-12);
+        assert!((stats.min - 0.25).abs() < 1e-12);
+        assert!((stats.max - 1.0).abs() < 1e-12);
+        assert_eq!(stats.pixel_count, 4);
+    }
+
+    #[test]
+    fn compute_stats_f64_keeps_negatives_and_handles_empty() {
+        let mut pixels = vec![-0.5, 0.0, 0.5];
+        let stats = compute_stats_f64(&mut pixels).unwrap();
+        assert!((stats.min + 0.5).abs() < 1e-12);
+        assert!(stats.median.abs() < 1e-12);
+        assert!(compute_stats_f64(&mut []).is_none());
+    }
 }
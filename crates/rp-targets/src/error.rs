@@ -71,6 +71,15 @@ pub enum TargetStoreError {
         reason: String,
     },
 
+    /// A target's [`crate::MosaicSpec`] is out of range: a grid axis
+    /// outside `1..=`[`crate::MAX_MOSAIC_PANELS_PER_AXIS`], a 1×1 grid,
+    /// or an overlap outside `[0, 50)` percent.
+    #[error("invalid mosaic: {reason}")]
+    InvalidMosaic {
+        /// Human-readable description of which field failed and why.
+        reason: String,
+    },
+
     /// The blocking task running a redb operation panicked or was cancelled.
     #[error("target store blocking task join error: {0}")]
     Join(String),
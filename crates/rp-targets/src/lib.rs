@@ -24,8 +24,9 @@ pub use error::TargetStoreError;
 pub use memory::InMemoryTargetStore;
 pub use migrate::CURRENT_SCHEMA_VERSION;
 pub use model::{
-    validate_goals, AcquisitionGoal, GradingThresholds, SchedulingConstraints, Target, TargetSlug,
-    TargetSlugError, WriteStamp, OPERATOR_WRITER,
+    validate_goals, validate_mosaic, AcquisitionGoal, GradingThresholds, MoonAvoidance,
+    MosaicPanel, MosaicSpec, SchedulingConstraints, Target, TargetSlug, TargetSlugError,
+    WriteStamp, MAX_MOSAIC_PANELS_PER_AXIS, OPERATOR_WRITER,
 };
 pub use redb_store::RedbTargetStore;
 // The plan value types live in `rp-vocabulary` (ADR-019); re-export the ones
@@ -44,7 +45,8 @@ pub trait TargetStore: Send + Sync {
     /// Writes `target`, creating it or overwriting an existing row with the
     /// same slug in place — never a duplicate row. Preserves the existing
     /// row's `created_at` and `created_by` on overwrite. Validates
-    /// `target.goals` per [`validate_goals`] before writing.
+    /// `target.goals` per [`validate_goals`] and `target.mosaic` per
+    /// [`validate_mosaic`] before writing.
     async fn upsert_target(&self, target: Target) -> Result<(), TargetStoreError>;
 
     /// Returns the target for `slug`, including its goals, or `None` if
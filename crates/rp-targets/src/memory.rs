@@ -7,7 +7,9 @@ use std::sync::Mutex;
 use async_trait::async_trait;
 
 use crate::error::TargetStoreError;
-use crate::model::{validate_goals, AcquisitionGoal, Target, TargetSlug, WriteStamp};
+use crate::model::{
+    validate_goals, validate_mosaic, AcquisitionGoal, Target, TargetSlug, WriteStamp,
+};
 use crate::TargetStore;
 
 /// In-memory [`TargetStore`] test double: a `BTreeMap` behind a `Mutex`,
@@ -32,6 +34,9 @@ impl InMemoryTargetStore {
 impl TargetStore for InMemoryTargetStore {
     async fn upsert_target(&self, mut target: Target) -> Result<(), TargetStoreError> {
         validate_goals(&target.goals)?;
+        if let Some(mosaic) = &target.mosaic {
+            validate_mosaic(mosaic)?;
+        }
         let mut targets = self
             .targets
             .lock()
@@ -105,6 +110,7 @@ mod tests {
             magnitude: Some(5.7),
             size_arcmin: Some(62.0),
             position_angle_degrees: None,
+            mosaic: None,
             priority: 0,
             active: true,
             goals: Vec::new(),
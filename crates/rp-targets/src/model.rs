@@ -143,7 +143,7 @@ pub enum TargetSlugError {
 /// `(filter, binning, exposure_duration)` triple is the quota key from the
 /// filename scheme — frame type is always `Light` for goals, and gain is
 /// a fixed per-setup camera setting rather than a sub-spec dimension.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AcquisitionGoal {
     /// Filter name, e.g. `"Ha"`, `"L"`, `"R"`.
     pub filter: String,
@@ -155,6 +155,28 @@ pub struct AcquisitionGoal {
     pub exposure_duration: Duration,
     /// Number of good frames desired for this sub-spec.
     pub desired_count: u32,
+    /// Filter-aware Moon avoidance for this sub-spec. `None` (the
+    /// default, and every pre-existing row) means only the target-level
+    /// [`SchedulingConstraints`] apply. Omitted from the serialized form
+    /// when `None`, so rows and wire payloads without it are unchanged.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moon_avoidance: Option<MoonAvoidance>,
+}
+
+/// The Lorentzian Moon-avoidance curve other schedulers (ACP, NINA's
+/// Target Scheduler) key per filter: the separation a goal needs from
+/// the Moon is `distance_degrees` at full Moon and falls off as
+/// `distance / (1 + (days_from_full / width_days)²)` as the Moon ages
+/// away from full. A broadband filter wants a large distance and a wide
+/// curve; a narrowband filter a small distance and a narrow one. Storage
+/// only — evaluated by rp's planner against `rp-ephemeris`' Moon age.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MoonAvoidance {
+    /// Required Moon separation at full Moon, in degrees (`0`-`180`].
+    pub distance_degrees: f64,
+    /// Half-width of the curve, in days from full Moon: the required
+    /// separation has halved `width_days` either side of full. Positive.
+    pub width_days: f64,
 }
 
 /// Per-target scheduling constraints. Each `None` field falls back to the
@@ -198,6 +220,95 @@ pub struct GradingThresholds {
     pub min_snr: Option<f64>,
 }
 
+/// Largest panel count accepted along either axis of a [`MosaicSpec`].
+/// Far beyond any mosaic a single rig finishes in a season, and small
+/// enough that a typo (`rows: 30`) is caught at the write rather than
+/// turning one target into hundreds of planner candidates.
+pub const MAX_MOSAIC_PANELS_PER_AXIS: u32 = 10;
+
+/// A multi-panel framing of a [`Target`]: a `columns` × `rows` grid of
+/// panels centered on the target's `coord`, adjacent panels overlapping
+/// by `overlap_percent` of the field. The grid is laid out in the frame
+/// of the target's effective position angle, so rotating the framing
+/// rotates the whole mosaic with it.
+///
+/// Storage only. Panel *identity* lives here ([`MosaicPanel::slug`] —
+/// it is the on-disk `{target}` token, so it must be stable); panel
+/// *coordinates* depend on the imaging train's field of view and are
+/// derived by rp's planner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MosaicSpec {
+    /// Panels across the frame's width, `1`-[`MAX_MOSAIC_PANELS_PER_AXIS`].
+    pub columns: u32,
+    /// Panels along the frame's height, `1`-[`MAX_MOSAIC_PANELS_PER_AXIS`].
+    pub rows: u32,
+    /// Overlap between adjacent panels as a percentage of the field of
+    /// view along that axis, `[0, 50)`.
+    pub overlap_percent: f64,
+}
+
+impl MosaicSpec {
+    /// Every panel of the grid, row-major: row 1 left to right, then
+    /// row 2, and so on.
+    pub fn panels(&self) -> impl Iterator<Item = MosaicPanel> + '_ {
+        (1..=self.rows)
+            .flat_map(move |row| (1..=self.columns).map(move |column| MosaicPanel { row, column }))
+    }
+
+    /// Whether `panel` lies inside this grid.
+    #[must_use]
+    pub const fn contains(&self, panel: MosaicPanel) -> bool {
+        panel.row >= 1
+            && panel.row <= self.rows
+            && panel.column >= 1
+            && panel.column <= self.columns
+    }
+}
+
+/// One panel of a [`MosaicSpec`] grid, 1-based. Row 1 is the top of the
+/// frame (toward the position angle), column 1 its left edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MosaicPanel {
+    pub row: u32,
+    pub column: u32,
+}
+
+impl MosaicPanel {
+    /// The panel's identity: `parent` suffixed with `-r{row}c{column}`,
+    /// e.g. `m31-r1c2`. Each panel's frames land under this slug, which
+    /// is what keeps their progress separate from the parent's and from
+    /// each other's. Infallible — a valid slug plus `[a-z0-9-]` is still
+    /// a valid slug.
+    #[must_use]
+    pub fn slug(&self, parent: &TargetSlug) -> TargetSlug {
+        TargetSlug(format!("{}-r{}c{}", parent.as_str(), self.row, self.column))
+    }
+
+    /// The inverse of [`MosaicPanel::slug`]: the parent slug and panel a
+    /// `-r{row}c{column}`-suffixed slug names. Only the shape is checked
+    /// — whether the parent is a mosaic with that panel is for the store
+    /// to answer.
+    #[must_use]
+    pub fn split_slug(slug: &TargetSlug) -> Option<(TargetSlug, Self)> {
+        let (parent, suffix) = slug.as_str().rsplit_once("-r")?;
+        let (row, column) = suffix.split_once('c')?;
+        // Exactly the digits `slug` writes: no sign, no leading zero.
+        let index = |digits: &str| {
+            let canonical = digits.bytes().all(|b| b.is_ascii_digit()) && !digits.starts_with('0');
+            if canonical {
+                digits.parse::<u32>().ok()
+            } else {
+                None
+            }
+        };
+        let panel = Self {
+            row: index(row)?,
+            column: index(column)?,
+        };
+        Some((TargetSlug::new(parent).ok()?, panel))
+    }
+}
+
 /// A planned pointing plus its acquisition goals — one row in the target
 /// store.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -237,6 +348,14 @@ pub struct Target {
     /// no schema-version bump).
     #[serde(default)]
     pub position_angle_degrees: Option<f64>,
+    /// Multi-panel framing (`docs/services/rp.md` § Target Store →
+    /// Mosaics). `None` is a single-frame target. When present, the
+    /// planner images the panels rather than `coord` itself, each under
+    /// its own [`MosaicPanel::slug`], and every panel carries this
+    /// row's `goals`. Omitted from the serialized form when `None`, so
+    /// pre-existing rows are unchanged (no schema-version bump).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mosaic: Option<MosaicSpec>,
 
     /// Scheduling priority; higher values are preferred by the planner.
     pub priority: i32,
@@ -301,8 +420,10 @@ pub struct WriteStamp {
 
 /// Validates a goal set for [`crate::TargetStore::upsert_target`] and
 /// [`crate::TargetStore::set_goals`]: no two goals may share the same
-/// `(filter, binning, exposure_duration)` key, and no goal may have a zero
-/// `desired_count` or zero `exposure_duration`.
+/// `(filter, binning, exposure_duration)` key, no goal may have a zero
+/// `desired_count` or zero `exposure_duration`, and a goal's
+/// [`MoonAvoidance`] must have a `distance_degrees` in `(0, 180]` and a
+/// positive, finite `width_days`.
 ///
 /// # Errors
 ///
@@ -325,6 +446,19 @@ pub fn validate_goals(goals: &[AcquisitionGoal]) -> Result<(), TargetStoreError>
                 ),
             });
         }
+        if let Some(avoidance) = goal.moon_avoidance {
+            if !(avoidance.distance_degrees > 0.0 && avoidance.distance_degrees <= 180.0)
+                || !(avoidance.width_days.is_finite() && avoidance.width_days > 0.0)
+            {
+                return Err(TargetStoreError::InvalidGoals {
+                    reason: format!(
+                        "goal for filter {:?} at {} has moon_avoidance distance {} / width {}; \
+                         distance must be in (0, 180] and width positive",
+                        goal.filter, goal.binning, avoidance.distance_degrees, avoidance.width_days
+                    ),
+                });
+            }
+        }
     }
 
     for (i, a) in goals.iter().enumerate() {
@@ -346,6 +480,40 @@ pub fn validate_goals(goals: &[AcquisitionGoal]) -> Result<(), TargetStoreError>
     Ok(())
 }
 
+/// Validates a [`MosaicSpec`] for [`crate::TargetStore::upsert_target`]:
+/// `columns` and `rows` in `1..=`[`MAX_MOSAIC_PANELS_PER_AXIS`] with at
+/// least two panels in total (a 1×1 grid is a plain target — leave
+/// `mosaic` unset), and `overlap_percent` finite in `[0, 50)`.
+///
+/// # Errors
+///
+/// Returns [`TargetStoreError::InvalidMosaic`] naming the offending field.
+pub fn validate_mosaic(mosaic: &MosaicSpec) -> Result<(), TargetStoreError> {
+    let axis = 1..=MAX_MOSAIC_PANELS_PER_AXIS;
+    if !axis.contains(&mosaic.columns) || !axis.contains(&mosaic.rows) {
+        return Err(TargetStoreError::InvalidMosaic {
+            reason: format!(
+                "mosaic grid {}x{} is out of range; columns and rows must each be 1-{}",
+                mosaic.columns, mosaic.rows, MAX_MOSAIC_PANELS_PER_AXIS
+            ),
+        });
+    }
+    if mosaic.columns * mosaic.rows < 2 {
+        return Err(TargetStoreError::InvalidMosaic {
+            reason: "a 1x1 mosaic is a single frame; omit mosaic instead".to_string(),
+        });
+    }
+    if !(mosaic.overlap_percent.is_finite() && (0.0..50.0).contains(&mosaic.overlap_percent)) {
+        return Err(TargetStoreError::InvalidMosaic {
+            reason: format!(
+                "mosaic overlap_percent {} is out of range; must be at least 0 and below 50",
+                mosaic.overlap_percent
+            ),
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -448,6 +616,7 @@ mod tests {
             binning: Binning { x, y },
             exposure_duration: Duration::from_secs(secs),
             desired_count,
+            moon_avoidance: None,
         }
     }
 
@@ -478,6 +647,35 @@ mod tests {
         assert!(matches!(err, TargetStoreError::InvalidGoals { .. }));
     }
 
+    #[test]
+    fn validate_goals_rejects_a_degenerate_moon_avoidance_curve() {
+        let mut g = goal("Ha", 1, 1, 300, 20);
+        g.moon_avoidance = Some(MoonAvoidance {
+            distance_degrees: 60.0,
+            width_days: 0.0,
+        });
+        let err = validate_goals(&[g.clone()]).unwrap_err();
+        assert!(matches!(err, TargetStoreError::InvalidGoals { .. }));
+
+        g.moon_avoidance = Some(MoonAvoidance {
+            distance_degrees: 200.0,
+            width_days: 7.0,
+        });
+        assert!(validate_goals(&[g]).is_err());
+    }
+
+    // A goal without Moon avoidance serializes exactly as it did before
+    // the field existed, and a pre-existing goal row reads back as
+    // `None` — no schema-version step.
+    #[test]
+    fn goal_without_moon_avoidance_keeps_its_serialized_shape() {
+        let g = goal("Ha", 1, 1, 300, 20);
+        let v = serde_json::to_value(&g).unwrap();
+        assert!(v.get("moon_avoidance").is_none(), "{v}");
+        let back: AcquisitionGoal = serde_json::from_value(v).unwrap();
+        assert_eq!(back.moon_avoidance, None);
+    }
+
     // A row serialized before the writer-identity fields existed must
     // deserialize as operator-owned — this is the whole redb migration
     // story for the additive change (no schema-version step).
@@ -514,4 +712,122 @@ mod tests {
         let target: Target = serde_json::from_value(old_row).unwrap();
         assert_eq!(target.position_angle_degrees, None);
     }
+
+    #[test]
+    fn mosaic_panels_enumerate_row_major_with_stable_slugs() {
+        let spec = MosaicSpec {
+            columns: 3,
+            rows: 2,
+            overlap_percent: 10.0,
+        };
+        let parent = TargetSlug::new("m31").unwrap();
+        let slugs: Vec<String> = spec
+            .panels()
+            .map(|p| p.slug(&parent).as_str().to_string())
+            .collect();
+        assert_eq!(
+            slugs,
+            ["m31-r1c1", "m31-r1c2", "m31-r1c3", "m31-r2c1", "m31-r2c2", "m31-r2c3"]
+        );
+        // A panel slug is itself a canonical slug token.
+        for slug in &slugs {
+            assert_eq!(TargetSlug::new(slug).unwrap().as_str(), slug);
+        }
+    }
+
+    #[test]
+    fn panel_slugs_split_back_into_parent_and_panel() {
+        let spec = MosaicSpec {
+            columns: 10,
+            rows: 2,
+            overlap_percent: 10.0,
+        };
+        let parent = TargetSlug::new("sh2-129").unwrap();
+        for panel in spec.panels() {
+            let (got_parent, got_panel) = MosaicPanel::split_slug(&panel.slug(&parent)).unwrap();
+            assert_eq!(got_parent, parent);
+            assert_eq!(got_panel, panel);
+            assert!(spec.contains(got_panel));
+        }
+        assert!(!spec.contains(MosaicPanel { row: 3, column: 1 }));
+        for not_a_panel in ["m31", "m31-r1", "m31-r01c2", "m31-rc2", "m31-r1c", "-r1c2"] {
+            let slug = TargetSlug::new(not_a_panel).unwrap();
+            assert!(MosaicPanel::split_slug(&slug).is_none(), "{not_a_panel}");
+        }
+    }
+
+    #[test]
+    fn validate_mosaic_accepts_a_sane_grid() {
+        validate_mosaic(&MosaicSpec {
+            columns: 2,
+            rows: 3,
+            overlap_percent: 15.0,
+        })
+        .unwrap();
+        validate_mosaic(&MosaicSpec {
+            columns: 2,
+            rows: 1,
+            overlap_percent: 0.0,
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn validate_mosaic_rejects_degenerate_grids_and_overlaps() {
+        let base = MosaicSpec {
+            columns: 2,
+            rows: 3,
+            overlap_percent: 15.0,
+        };
+        for bad in [
+            MosaicSpec { columns: 0, ..base },
+            MosaicSpec {
+                rows: MAX_MOSAIC_PANELS_PER_AXIS + 1,
+                ..base
+            },
+            MosaicSpec {
+                columns: 1,
+                rows: 1,
+                ..base
+            },
+            MosaicSpec {
+                overlap_percent: 50.0,
+                ..base
+            },
+            MosaicSpec {
+                overlap_percent: -1.0,
+                ..base
+            },
+            MosaicSpec {
+                overlap_percent: f64::NAN,
+                ..base
+            },
+        ] {
+            let err = validate_mosaic(&bad).unwrap_err();
+            assert!(
+                matches!(err, TargetStoreError::InvalidMosaic { .. }),
+                "{bad:?}"
+            );
+        }
+    }
+
+    // Same additive-field story: a pre-mosaic row reads back as a
+    // single-frame target, and a single-frame target serializes without
+    // the key.
+    #[test]
+    fn pre_mosaic_row_deserializes_as_a_single_frame() {
+        let old_row = serde_json::json!({
+            "slug": "m31",
+            "display_name": "M 31",
+            "coord": { "ra_hours": 0.7123, "dec_degrees": 41.2688 },
+            "priority": 0,
+            "active": true,
+            "created_at": "2026-07-22T00:00:00Z",
+            "updated_at": "2026-07-22T00:00:00Z"
+        });
+        let target: Target = serde_json::from_value(old_row).unwrap();
+        assert_eq!(target.mosaic, None);
+        let v = serde_json::to_value(&target).unwrap();
+        assert!(v.get("mosaic").is_none(), "{v}");
+    }
 }
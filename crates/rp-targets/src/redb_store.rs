@@ -10,7 +10,9 @@ use redb::{Database, ReadableDatabase, ReadableTable, TableDefinition};
 
 use crate::error::TargetStoreError;
 use crate::migrate::{check_schema_version, CURRENT_SCHEMA_VERSION};
-use crate::model::{validate_goals, AcquisitionGoal, Target, TargetSlug, WriteStamp};
+use crate::model::{
+    validate_goals, validate_mosaic, AcquisitionGoal, Target, TargetSlug, WriteStamp,
+};
 use crate::TargetStore;
 
 const TARGETS_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("targets");
@@ -116,6 +118,9 @@ fn list_targets_sync(db: &Database) -> Result<Vec<Target>, TargetStoreError> {
 
 fn upsert_target_sync(db: &Database, mut target: Target) -> Result<(), TargetStoreError> {
     validate_goals(&target.goals)?;
+    if let Some(mosaic) = &target.mosaic {
+        validate_mosaic(mosaic)?;
+    }
     let write_txn = db.begin_write()?;
     {
         let mut table = write_txn.open_table(TARGETS_TABLE)?;
@@ -236,6 +241,7 @@ mod tests {
             magnitude: Some(4.0),
             size_arcmin: Some(120.0),
             position_angle_degrees: None,
+            mosaic: None,
             priority: 0,
             active: false,
             goals: Vec::new(),
@@ -358,6 +364,7 @@ mod tests {
             binning: crate::Binning { x: 1, y: 1 },
             exposure_duration: std::time::Duration::from_mins(5),
             desired_count: 20,
+            moon_avoidance: None,
         }];
         store
             .set_goals(&slug, goals.clone(), sample_stamp())
@@ -61,7 +61,7 @@ for pkgdir in services/*/pkg; do
         # Services with no defaultable config gate on the config file existing
         # instead of crash-looping on a fresh install.
         case "$svc" in
-            sky-survey-camera|plate-solver|calibrator-flats|session-runner|polar-align)
+            sky-survey-camera|plate-solver|calibrator-flats|calibrator-darks|session-runner|polar-align|frame-grader|native-guider)
                 grep -q "^ConditionPathExists=/var/lib/rusty-photon/\.config/rusty-photon/$svc\.json\$" "$unit" \
                     || err "$svc: no-default-config service must gate on ConditionPathExists=<XDG config path>"
                 ;;
@@ -423,9 +423,12 @@ win_port_of() {
         planetarium-bridge) echo 11126 ;;
         phd2-guider) echo 11130 ;;
         plate-solver) echo 11131 ;;
+        native-guider) echo 11132 ;;
         calibrator-flats) echo 11170 ;;
         session-runner) echo 11171 ;;
         polar-align) echo 11172 ;;
+        calibrator-darks) echo 11173 ;;
+        frame-grader) echo 11174 ;;
         *) echo "" ;;
     esac
 }
@@ -469,11 +472,11 @@ if [ -f "$pkg_wxs" ]; then
             || err "$svc: firewall exception port must be $port"
         # Demand-start on exactly the no-defaultable-config services (the
         # ConditionPathExists= translation); everything else auto-starts on
-        # install. session-runner is one of the five gated services:
+        # install. session-runner is one of the eight gated services:
         # workflows_dir/state_dir are required config fields with no usable
         # defaults, mirroring its Linux ConditionPathExists= unit.
         case "$svc" in
-            sky-survey-camera | plate-solver | calibrator-flats | session-runner | polar-align)
+            sky-survey-camera | plate-solver | calibrator-flats | calibrator-darks | session-runner | polar-align | frame-grader | native-guider)
                 grep -q 'Start="demand"' "$frag" \
                     || err "$svc: gated service must install with Start=\"demand\""
                 grep -q 'Start="install"' "$frag" \
@@ -130,9 +130,12 @@ port_of() {
         planetarium-bridge) echo 11126 ;;
         phd2-guider) echo 11130 ;;
         plate-solver) echo 11131 ;;
+        native-guider) echo 11132 ;;
         calibrator-flats) echo 11170 ;;
         session-runner) echo 11171 ;;
         polar-align) echo 11172 ;;
+        calibrator-darks) echo 11173 ;;
+        frame-grader) echo 11174 ;;
         *) echo "" ;;
     esac
 }
@@ -163,7 +166,7 @@ is_gated() {
     # `brew services start`, so the gate is not starting them (a start
     # without a config exits and keep_alive respawn-loops by design).
     case "$1" in
-        sky-survey-camera|plate-solver|calibrator-flats|session-runner|polar-align) return 0 ;;
+        sky-survey-camera|plate-solver|calibrator-flats|calibrator-darks|session-runner|polar-align|frame-grader|native-guider) return 0 ;;
         *) return 1 ;;
     esac
 }
@@ -68,14 +68,16 @@ $ports = @{
     'dsd-fp2' = 11119; 'ui-htmx' = 11120; 'qhy-camera' = 11121
     'zwo-camera' = 11122; 'pa-scops-oag' = 11123; 'zwo-focuser' = 11124
     'planetarium-bridge' = 11126
-    'phd2-guider' = 11130; 'plate-solver' = 11131; 'calibrator-flats' = 11170
-    'session-runner' = 11171; 'polar-align' = 11172
+    'phd2-guider' = 11130; 'plate-solver' = 11131; 'native-guider' = 11132
+    'calibrator-flats' = 11170
+    'session-runner' = 11171; 'polar-align' = 11172; 'calibrator-darks' = 11173
+    'frame-grader' = 11174
 }
 $allServices = $ports.Keys | Sort-Object
 # session-runner is gated like the Linux-gated three: its workflows_dir/
 # state_dir are required config fields with no usable defaults.
 $gated = @('sky-survey-camera', 'plate-solver', 'calibrator-flats', 'session-runner',
-    'polar-align')
+    'polar-align', 'calibrator-darks', 'frame-grader', 'native-guider')
 $serial = @('ppba-driver', 'qhy-focuser', 'pa-falcon-rotator', 'pa-scops-oag',
     'dsd-fp2', 'star-adventurer-gti')
 $active = @('sentinel', 'ui-htmx', 'filemonitor', 'rp',
@@ -146,9 +146,12 @@ port_of() {
         planetarium-bridge) echo 11126 ;;
         phd2-guider) echo 11130 ;;
         plate-solver) echo 11131 ;;
+        native-guider) echo 11132 ;;
         calibrator-flats) echo 11170 ;;
         session-runner) echo 11171 ;;
         polar-align) echo 11172 ;;
+        calibrator-darks) echo 11173 ;;
+        frame-grader) echo 11174 ;;
         *) echo "" ;;
     esac
 }
@@ -176,7 +179,7 @@ probe_path() {
 is_gated() {
     # No defaultable config → unit gated on ConditionPathExists (see plan).
     case "$1" in
-        sky-survey-camera|plate-solver|calibrator-flats|session-runner|polar-align) return 0 ;;
+        sky-survey-camera|plate-solver|calibrator-flats|calibrator-darks|session-runner|polar-align|frame-grader|native-guider) return 0 ;;
         *) return 1 ;;
     esac
 }
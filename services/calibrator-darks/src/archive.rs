@@ -0,0 +1,295 @@
+//! Frame-archive scan: what the dark library must cover and what it
+//! already holds.
+//!
+//! rp writes an exposure-document sidecar (`<image>.json`) next to every
+//! FITS file it captures (docs/services/rp.md § Exposure Document). The
+//! scan reads those sidecars straight off the shared filesystem — no MCP
+//! round-trip per frame — and keeps only the core fields that key a dark
+//! to its light: camera, cooler rung, gain, offset, binning and exposure.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Duration;
+
+use rp_vocabulary::{Binning, FrameType};
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::error::{CalibratorDarksError, Result};
+
+/// The readout settings a bias must match: everything a dark matches
+/// except the exposure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Readout {
+    pub gain: i32,
+    pub offset: i32,
+    pub binning: Binning,
+}
+
+/// One (gain, offset, binning, exposure) combination a dark must match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DarkCombo {
+    pub readout: Readout,
+    pub duration: Duration,
+}
+
+/// The subset of rp's `ExposureDocument` the scan needs. Unknown fields
+/// are ignored; `file_path` is required so stray JSON files that are not
+/// exposure documents fail to parse and are skipped.
+#[derive(Debug, Deserialize)]
+struct Sidecar {
+    #[serde(rename = "file_path")]
+    _file_path: String,
+    #[serde(default)]
+    camera_id: Option<String>,
+    #[serde(default, with = "humantime_serde")]
+    duration: Option<Duration>,
+    #[serde(default)]
+    cooler_setpoint_c: Option<i32>,
+    #[serde(default)]
+    gain: Option<i32>,
+    #[serde(default)]
+    offset: Option<i32>,
+    #[serde(default)]
+    binning: Option<Binning>,
+    #[serde(default)]
+    frame_type: Option<FrameType>,
+}
+
+/// What the archive holds for one camera.
+#[derive(Debug, Default)]
+pub struct ArchiveSummary {
+    /// Every combination seen on a `Light` frame, in a stable order.
+    pub combos: Vec<DarkCombo>,
+    /// Existing darks per (rung, combination).
+    pub darks: HashMap<(i32, DarkCombo), u32>,
+    /// Existing biases per (rung, readout).
+    pub biases: HashMap<(i32, Readout), u32>,
+}
+
+impl ArchiveSummary {
+    #[must_use]
+    pub fn darks_at(&self, rung_c: i32, combo: &DarkCombo) -> u32 {
+        self.darks.get(&(rung_c, *combo)).copied().unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn biases_at(&self, rung_c: i32, readout: &Readout) -> u32 {
+        self.biases.get(&(rung_c, *readout)).copied().unwrap_or(0)
+    }
+
+    /// The distinct readouts across `combos`, in first-seen order — the
+    /// bias sets to build.
+    #[must_use]
+    pub fn readouts(&self) -> Vec<Readout> {
+        let mut seen = HashSet::new();
+        self.combos
+            .iter()
+            .map(|combo| combo.readout)
+            .filter(|readout| seen.insert(*readout))
+            .collect()
+    }
+
+    fn record(&mut self, sidecar: Sidecar) {
+        let (Some(gain), Some(offset), Some(binning)) =
+            (sidecar.gain, sidecar.offset, sidecar.binning)
+        else {
+            return;
+        };
+        let readout = Readout {
+            gain,
+            offset,
+            binning,
+        };
+        match (
+            sidecar.frame_type,
+            sidecar.duration,
+            sidecar.cooler_setpoint_c,
+        ) {
+            (Some(FrameType::Light), Some(duration), _) => {
+                let combo = DarkCombo { readout, duration };
+                if !self.combos.contains(&combo) {
+                    self.combos.push(combo);
+                }
+            }
+            (Some(FrameType::Dark), Some(duration), Some(rung)) => {
+                *self
+                    .darks
+                    .entry((rung, DarkCombo { readout, duration }))
+                    .or_default() += 1;
+            }
+            (Some(FrameType::Bias), _, Some(rung)) => {
+                *self.biases.entry((rung, readout)).or_default() += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn sort(&mut self) {
+        self.combos.sort_by_key(|c| {
+            (
+                c.readout.gain,
+                c.readout.offset,
+                c.readout.binning.x,
+                c.readout.binning.y,
+                c.duration,
+            )
+        });
+    }
+}
+
+/// Scan `directory` recursively for `camera_id`'s exposure documents.
+///
+/// Frames recorded before rp persisted gain/offset/binning, or whose
+/// camera could not report them, carry no usable key and are skipped;
+/// so are darks and biases captured while rp was not regulating the
+/// camera at a rung (no `cooler_setpoint_c`). Unreadable or unparsable
+/// files are skipped too — only an unreadable `directory` itself fails.
+pub fn scan(directory: &Path, camera_id: &str) -> Result<ArchiveSummary> {
+    let mut summary = ArchiveSummary::default();
+    let entries = std::fs::read_dir(directory).map_err(|e| {
+        CalibratorDarksError::Archive(format!(
+            "failed to read archive directory '{}': {}",
+            directory.display(),
+            e
+        ))
+    })?;
+    let mut pending: Vec<_> = entries.flatten().map(|entry| entry.path()).collect();
+
+    while let Some(path) = pending.pop() {
+        if path.is_dir() {
+            match std::fs::read_dir(&path) {
+                Ok(entries) => pending.extend(entries.flatten().map(|entry| entry.path())),
+                Err(e) => {
+                    debug!(path = %path.display(), error = %e, "skipping unreadable directory")
+                }
+            }
+            continue;
+        }
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let sidecar = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Sidecar>(&contents).ok());
+        match sidecar {
+            Some(sidecar) if sidecar.camera_id.as_deref() == Some(camera_id) => {
+                summary.record(sidecar);
+            }
+            Some(_) => {}
+            None => debug!(path = %path.display(), "skipping non-document JSON file"),
+        }
+    }
+
+    summary.sort();
+    debug!(
+        combos = summary.combos.len(),
+        dark_sets = summary.darks.len(),
+        bias_sets = summary.biases.len(),
+        "archive scanned"
+    );
+    Ok(summary)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn write_doc(dir: &Path, name: &str, doc: serde_json::Value) {
+        std::fs::write(dir.join(format!("{name}.json")), doc.to_string()).unwrap();
+    }
+
+    fn doc(frame_type: &str, duration: &str, rung: Option<i32>) -> serde_json::Value {
+        let mut doc = serde_json::json!({
+            "id": "x",
+            "captured_at": "2026-10-16T00:00:00Z",
+            "file_path": "/data/x.fits",
+            "width": 100,
+            "height": 100,
+            "camera_id": "main-cam",
+            "duration": duration,
+            "gain": 100,
+            "offset": 10,
+            "binning": "1x1",
+            "frame_type": frame_type,
+        });
+        if let Some(rung) = rung {
+            doc["cooler_setpoint_c"] = serde_json::json!(rung);
+        }
+        doc
+    }
+
+    fn readout() -> Readout {
+        Readout {
+            gain: 100,
+            offset: 10,
+            binning: Binning { x: 1, y: 1 },
+        }
+    }
+
+    #[test]
+    fn lights_supply_distinct_sorted_combinations() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("M31/Light");
+        std::fs::create_dir_all(&nested).unwrap();
+        write_doc(&nested, "a", doc("Light", "300s", Some(-10)));
+        write_doc(&nested, "b", doc("Light", "300s", Some(-10)));
+        write_doc(dir.path(), "c", doc("Light", "60s", None));
+
+        let summary = scan(dir.path(), "main-cam").unwrap();
+        let durations: Vec<_> = summary.combos.iter().map(|c| c.duration).collect();
+        assert_eq!(
+            durations,
+            vec![Duration::from_secs(60), Duration::from_secs(300)]
+        );
+        assert_eq!(summary.readouts(), vec![readout()]);
+    }
+
+    #[test]
+    fn existing_darks_and_biases_are_counted_per_rung() {
+        let dir = tempfile::tempdir().unwrap();
+        write_doc(dir.path(), "d1", doc("Dark", "300s", Some(-10)));
+        write_doc(dir.path(), "d2", doc("Dark", "300s", Some(-10)));
+        write_doc(dir.path(), "d3", doc("Dark", "300s", Some(0)));
+        write_doc(dir.path(), "b1", doc("Bias", "1ms", Some(-10)));
+        // Not regulated at a rung: no library to count it toward.
+        write_doc(dir.path(), "d4", doc("Dark", "300s", None));
+
+        let summary = scan(dir.path(), "main-cam").unwrap();
+        let combo = DarkCombo {
+            readout: readout(),
+            duration: Duration::from_secs(300),
+        };
+        assert_eq!(summary.darks_at(-10, &combo), 2);
+        assert_eq!(summary.darks_at(0, &combo), 1);
+        assert_eq!(summary.darks_at(5, &combo), 0);
+        assert_eq!(summary.biases_at(-10, &readout()), 1);
+        assert_eq!(summary.biases_at(0, &readout()), 0);
+        assert!(summary.combos.is_empty());
+    }
+
+    #[test]
+    fn other_cameras_unkeyed_frames_and_stray_json_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut other = doc("Light", "300s", Some(-10));
+        other["camera_id"] = serde_json::json!("guide-cam");
+        write_doc(dir.path(), "other", other);
+        let mut legacy = doc("Light", "120s", Some(-10));
+        legacy.as_object_mut().unwrap().remove("gain");
+        write_doc(dir.path(), "legacy", legacy);
+        std::fs::write(dir.path().join("stray.json"), r#"{"not": "a document"}"#).unwrap();
+        std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+        std::fs::write(dir.path().join("image.fits"), "SIMPLE").unwrap();
+
+        let summary = scan(dir.path(), "main-cam").unwrap();
+        assert!(summary.combos.is_empty());
+        assert!(summary.darks.is_empty());
+    }
+
+    #[test]
+    fn a_missing_archive_directory_is_an_error() {
+        let err = scan(Path::new("/nonexistent/rusty-photon/images"), "main-cam").unwrap_err();
+        assert!(err.to_string().contains("failed to read archive directory"));
+    }
+}
@@ -0,0 +1,280 @@
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Deserialize;
+
+pub use rusty_photon_server_config::ServerConfig;
+
+use crate::error::{CalibratorDarksError, Result};
+
+/// Dark-library plan passed via the orchestrator plugin config. This is
+/// also the service's config file, so it carries the HTTP `server` block.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DarkPlan {
+    /// The HTTP server for `/invoke` and `/health`. Plan files without a
+    /// `server` block keep loading via the default.
+    #[serde(default = "default_server")]
+    pub server: ServerConfig,
+    pub camera_id: String,
+    /// CoverCalibrator whose cover is closed for the run. Absent, `null`,
+    /// or `""` means the rig has no motorized cover: the operator caps
+    /// the telescope before invoking, and the workflow never touches a
+    /// cover.
+    #[serde(default)]
+    pub calibrator_id: Option<String>,
+    /// rp's image directory (its `session.data_directory`). Scanned
+    /// recursively for exposure-document sidecars: light frames supply
+    /// the (gain, offset, binning, exposure) combinations to cover, and
+    /// existing darks/biases are counted against the targets below.
+    pub archive_directory: PathBuf,
+    /// Darks wanted per rung and combination (default 20).
+    #[serde(default = "default_dark_count")]
+    pub dark_count: u32,
+    /// Biases wanted per rung and (gain, offset, binning) (default 50).
+    #[serde(default = "default_bias_count")]
+    pub bias_count: u32,
+    /// Restrict the run to these rungs (°C) of the camera's
+    /// `cooler_targets_c` ladder. Absent means every rung; entries that
+    /// are not on the ladder are ignored with a warning.
+    #[serde(default)]
+    pub rungs: Option<Vec<i32>>,
+    /// Upper bound reported to rp in the `/invoke` acknowledgment
+    /// (humantime, default `"10h"` — a full cloudy night). rp treats a
+    /// run past it as failed; an interrupted library resumes where it
+    /// left off on the next invocation.
+    #[serde(default = "default_max_duration", with = "humantime_serde")]
+    pub max_duration: Duration,
+    /// HTTP Basic credentials presented to `rp` — MCP calls and the
+    /// completion POST alike. The D6 observatory credential; doctor
+    /// `--fix` wires it (ADR-017).
+    #[serde(default)]
+    pub service_auth: Option<rp_mcp_client::ClientAuthConfig>,
+    /// PEM CA path used to trust a TLS-enabled `rp`. Per the ADR-017
+    /// policy, `service_auth` is only sent when this is set and the URL
+    /// is https.
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+}
+
+impl DarkPlan {
+    /// The cover to close, with the no-cover spellings (absent, `null`,
+    /// `""`) normalized to `None`.
+    #[must_use]
+    pub fn calibrator(&self) -> Option<&str> {
+        self.calibrator_id.as_deref().filter(|id| !id.is_empty())
+    }
+
+    #[must_use]
+    pub const fn rp_auth(&self) -> Option<&rp_mcp_client::ClientAuthConfig> {
+        self.service_auth.as_ref()
+    }
+
+    pub fn rp_ca(&self) -> Option<&std::path::Path> {
+        self.ca_cert.as_deref().map(std::path::Path::new)
+    }
+}
+
+/// calibrator-darks' default `server` block when the plan file omits it:
+/// port 11173 on all interfaces, plain HTTP.
+pub(crate) fn default_server() -> ServerConfig {
+    ServerConfig::new(11173)
+}
+
+/// CLI overrides layered over the file config after load: `--port` and
+/// `--bind-address` pin `server.port` / `server.bind_address` over whatever
+/// the file (or the `default_server()` fallback) supplied.
+#[derive(Debug, Clone, Default)]
+pub struct CliOverrides {
+    /// `--port` → `server.port`.
+    pub port: Option<u16>,
+    /// `--bind-address` → `server.bind_address`.
+    pub bind_address: Option<IpAddr>,
+}
+
+impl CliOverrides {
+    /// Apply the overrides onto `plan` in place.
+    pub const fn apply(&self, plan: &mut DarkPlan) {
+        if let Some(port) = self.port {
+            plan.server.port = port;
+        }
+        if let Some(bind_address) = self.bind_address {
+            plan.server.bind_address = bind_address;
+        }
+    }
+}
+
+const fn default_dark_count() -> u32 {
+    20
+}
+
+const fn default_bias_count() -> u32 {
+    50
+}
+
+const fn default_max_duration() -> Duration {
+    Duration::from_hours(10)
+}
+
+pub fn load_config(path: &Path) -> Result<DarkPlan> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        CalibratorDarksError::Config(format!(
+            "failed to read config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    serde_json::from_str(&contents).map_err(|e| {
+        CalibratorDarksError::Config(format!(
+            "failed to parse config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_dark_plan_with_defaults() {
+        let json = r#"{
+            "camera_id": "main-cam",
+            "archive_directory": "/var/lib/rusty-photon/images"
+        }"#;
+        let plan: DarkPlan = serde_json::from_str(json).unwrap();
+        assert_eq!(plan.camera_id, "main-cam");
+        assert_eq!(plan.calibrator(), None);
+        assert_eq!(
+            plan.archive_directory,
+            PathBuf::from("/var/lib/rusty-photon/images")
+        );
+        assert_eq!(plan.dark_count, 20);
+        assert_eq!(plan.bias_count, 50);
+        assert!(plan.rungs.is_none());
+        assert_eq!(plan.max_duration, Duration::from_hours(10));
+        // A plan file without a `server` block keeps loading via the default.
+        assert_eq!(plan.server.port, 11173);
+        assert_eq!(plan.server.bind_address.to_string(), "0.0.0.0");
+        assert!(plan.server.tls.is_none());
+        assert!(plan.server.auth.is_none());
+    }
+
+    #[test]
+    fn deserialize_dark_plan_with_overrides() {
+        let json = r#"{
+            "server": { "port": 12000, "bind_address": "127.0.0.1" },
+            "camera_id": "main-cam",
+            "calibrator_id": "flat-panel",
+            "archive_directory": "/data",
+            "dark_count": 30,
+            "bias_count": 100,
+            "rungs": [-10, 0],
+            "max_duration": "6h"
+        }"#;
+        let plan: DarkPlan = serde_json::from_str(json).unwrap();
+        assert_eq!(plan.server.socket_addr().to_string(), "127.0.0.1:12000");
+        assert_eq!(plan.calibrator(), Some("flat-panel"));
+        assert_eq!(plan.dark_count, 30);
+        assert_eq!(plan.bias_count, 100);
+        assert_eq!(plan.rungs, Some(vec![-10, 0]));
+        assert_eq!(plan.max_duration, Duration::from_hours(6));
+    }
+
+    #[test]
+    fn calibrator_absent_null_and_empty_all_mean_no_cover() {
+        for cover_field in ["", r#""calibrator_id": null,"#, r#""calibrator_id": "","#] {
+            let json = format!(
+                r#"{{
+                    "camera_id": "main-cam",
+                    {cover_field}
+                    "archive_directory": "/data"
+                }}"#
+            );
+            let plan: DarkPlan = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                plan.calibrator(),
+                None,
+                "spelling {cover_field:?} must mean no cover"
+            );
+        }
+    }
+
+    #[test]
+    fn cli_overrides_pin_port_and_bind_address() {
+        let json = r#"{"camera_id": "main-cam", "archive_directory": "/data"}"#;
+        let mut plan: DarkPlan = serde_json::from_str(json).unwrap();
+        let overrides = CliOverrides {
+            port: Some(12345),
+            bind_address: Some("127.0.0.1".parse().unwrap()),
+        };
+        overrides.apply(&mut plan);
+        assert_eq!(plan.server.socket_addr().to_string(), "127.0.0.1:12345");
+    }
+
+    #[test]
+    fn load_config_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        std::fs::write(
+            &path,
+            r#"{"camera_id": "main-cam", "archive_directory": "/data"}"#,
+        )
+        .unwrap();
+
+        let plan = load_config(&path).unwrap();
+        assert_eq!(plan.camera_id, "main-cam");
+    }
+
+    #[test]
+    fn load_config_missing_file() {
+        let err = load_config(Path::new("/nonexistent/calibrator-darks/plan.json")).unwrap_err();
+        assert!(err.to_string().contains("failed to read config file"));
+    }
+
+    #[test]
+    fn load_config_invalid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plan.json");
+        std::fs::write(&path, "not valid json").unwrap();
+
+        let err = load_config(&path).unwrap_err();
+        assert!(err.to_string().contains("failed to parse config file"));
+    }
+
+    #[test]
+    fn dark_plan_rejects_unknown_field() {
+        let json = r#"{
+            "camera_id": "main-cam",
+            "archive_directory": "/data",
+            "filters": []
+        }"#;
+        let err = serde_json::from_str::<DarkPlan>(json).unwrap_err();
+        assert!(err.to_string().contains("filters"), "{err}");
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod doctor_toml_parity {
+    use rusty_photon_server_config::doctor_toml::{parse, ServerClass};
+
+    use super::default_server;
+
+    /// `pkg/doctor.toml` is this service's catalog entry for
+    /// `rusty-photon-doctor` and must match the config defaults
+    /// (docs/services/doctor.md §The derived catalog).
+    #[test]
+    fn pkg_doctor_toml_matches_config_defaults() {
+        let meta = parse(include_str!("../pkg/doctor.toml")).unwrap();
+        assert_eq!(meta.port, default_server().port);
+        assert_eq!(meta.class, ServerClass::Core);
+        assert!(
+            meta.config_gated,
+            "calibrator-darks has no sensible default config"
+        );
+    }
+}
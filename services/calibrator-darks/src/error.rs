@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, CalibratorDarksError>;
+
+#[derive(Debug, Error)]
+pub enum CalibratorDarksError {
+    #[error("config error: {0}")]
+    Config(String),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("MCP tool call failed: {0}")]
+    ToolCall(String),
+
+    #[error("archive error: {0}")]
+    Archive(String),
+
+    #[error("workflow error: {0}")]
+    Workflow(String),
+
+    #[error("server error: {0}")]
+    Server(String),
+}
@@ -0,0 +1,183 @@
+//! MCP client for calling rp's built-in tools, built on the standard
+//! `rp-mcp-client` crate (ADR-017): CA-pinned TLS and the observatory
+//! credential over verified HTTPS only.
+
+use std::path::Path;
+use std::time::Duration;
+
+use rp_mcp_client::{ClientAuthConfig, RpMcpClient};
+use rp_vocabulary::{Binning, FrameType};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::{CalibratorDarksError, Result};
+
+/// MCP client for one `rp` session.
+pub struct McpClient {
+    inner: RpMcpClient,
+}
+
+/// Result from the `get_camera_info` tool.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CameraInfo {
+    pub bin_x: u8,
+    pub bin_y: u8,
+    #[serde(with = "humantime_serde")]
+    pub exposure_min: Duration,
+    /// `null` when the camera cannot report it.
+    #[serde(default)]
+    pub gain: Option<i32>,
+    /// `null` when the camera cannot report it.
+    #[serde(default)]
+    pub offset: Option<i32>,
+    /// The camera's dark-library ladder, ascending.
+    #[serde(default)]
+    pub cooler_targets_c: Vec<i32>,
+}
+
+/// Result from the `get_cover_state` tool.
+#[derive(Debug, Clone, Deserialize)]
+struct CoverStateResult {
+    cover_state: String,
+}
+
+/// Result from the `cool_camera` tool.
+#[derive(Debug, Clone, Deserialize)]
+struct CoolCameraResult {
+    stabilized: bool,
+}
+
+impl McpClient {
+    /// Connect to an MCP server at the given URL, presenting
+    /// `service_auth` per the ADR-017 credential policy.
+    pub async fn new(
+        mcp_url: &str,
+        service_auth: Option<&ClientAuthConfig>,
+        ca_cert: Option<&Path>,
+    ) -> Result<Self> {
+        debug!(url = %mcp_url, "connecting MCP client");
+        let inner = RpMcpClient::connect(mcp_url, service_auth, ca_cert)
+            .await
+            .map_err(|e| CalibratorDarksError::ToolCall(format!("MCP connect: {e}")))?;
+        Ok(Self { inner })
+    }
+
+    /// Capture one calibration frame. `frame_type` routes it to rp's
+    /// calibration directories and stamps it on the exposure document,
+    /// which is how the next archive scan counts it.
+    pub async fn capture(
+        &self,
+        camera_id: &str,
+        duration: Duration,
+        frame_type: FrameType,
+    ) -> Result<()> {
+        let _: Value = self
+            .call_tool(
+                "capture",
+                serde_json::json!({
+                    "camera_id": camera_id,
+                    "duration": humantime::format_duration(duration).to_string(),
+                    "frame_type": frame_type,
+                }),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_camera_info(&self, camera_id: &str) -> Result<CameraInfo> {
+        self.call_tool(
+            "get_camera_info",
+            serde_json::json!({"camera_id": camera_id}),
+        )
+        .await
+    }
+
+    /// Apply any of gain, offset and binning in one call; `None`
+    /// leaves that setting as it is.
+    pub async fn set_camera_settings(
+        &self,
+        camera_id: &str,
+        gain: Option<i32>,
+        offset: Option<i32>,
+        binning: Option<Binning>,
+    ) -> Result<()> {
+        let mut args = serde_json::json!({"camera_id": camera_id});
+        if let Some(gain) = gain {
+            args["gain"] = serde_json::json!(gain);
+        }
+        if let Some(offset) = offset {
+            args["offset"] = serde_json::json!(offset);
+        }
+        if let Some(binning) = binning {
+            args["binning"] = serde_json::json!(binning);
+        }
+        let _: Value = self.call_tool("set_camera_settings", args).await?;
+        Ok(())
+    }
+
+    /// Cool the camera to one rung of its ladder; `true` once the sensor
+    /// settled there, `false` when the rung proved unreachable.
+    pub async fn cool_camera(&self, camera_id: &str, target_c: i32) -> Result<bool> {
+        let result: CoolCameraResult = self
+            .call_tool(
+                "cool_camera",
+                serde_json::json!({"camera_id": camera_id, "target_c": target_c}),
+            )
+            .await?;
+        Ok(result.stabilized)
+    }
+
+    /// Read the cover's state without actuating anything. Returns the
+    /// state name as rp reports it (`NotPresent` | `Closed` | `Moving` |
+    /// `Open` | `Unknown` | `Error`).
+    pub async fn get_cover_state(&self, calibrator_id: &str) -> Result<String> {
+        let result: CoverStateResult = self
+            .call_tool(
+                "get_cover_state",
+                serde_json::json!({"calibrator_id": calibrator_id}),
+            )
+            .await?;
+        Ok(result.cover_state)
+    }
+
+    pub async fn close_cover(&self, calibrator_id: &str) -> Result<()> {
+        let _: Value = self
+            .call_tool(
+                "close_cover",
+                serde_json::json!({"calibrator_id": calibrator_id}),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub async fn open_cover(&self, calibrator_id: &str) -> Result<()> {
+        let _: Value = self
+            .call_tool(
+                "open_cover",
+                serde_json::json!({"calibrator_id": calibrator_id}),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Generic helper: call tool, check for errors, deserialize result.
+    async fn call_tool<T: serde::de::DeserializeOwned>(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+    ) -> Result<T> {
+        debug!(tool = %tool_name, "calling MCP tool");
+
+        let args = arguments.as_object().cloned().unwrap_or_default();
+        let value = self
+            .inner
+            .call_tool(tool_name, args)
+            .await
+            .map_err(|e| CalibratorDarksError::ToolCall(format!("{tool_name}: {e}")))?;
+
+        serde_json::from_value(value).map_err(|e| {
+            CalibratorDarksError::ToolCall(format!("{tool_name}: failed to parse result: {e}"))
+        })
+    }
+}
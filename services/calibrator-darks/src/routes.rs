@@ -0,0 +1,160 @@
+//! HTTP routes: POST /invoke for orchestrator invocation.
+
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::Value;
+use tracing::{debug, info, warn};
+
+use crate::config::DarkPlan;
+use crate::mcp_client::McpClient;
+use crate::workflow;
+
+pub fn build_router(plan: DarkPlan) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/invoke", post(invoke_handler))
+        .with_state(plan)
+}
+
+async fn health() -> &'static str {
+    "calibrator-darks healthy"
+}
+
+async fn invoke_handler(
+    axum::extract::State(plan): axum::extract::State<DarkPlan>,
+    Json(body): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let workflow_id = body
+        .get("workflow_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let mcp_server_url = body
+        .get("mcp_server_url")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    debug!(
+        workflow_id = %workflow_id,
+        mcp_server_url = %mcp_server_url,
+        "received invocation"
+    );
+
+    // Spawn the workflow in the background so we can acknowledge immediately
+    let wf_id = workflow_id;
+    let mcp_url = mcp_server_url;
+    let plan_clone = plan.clone();
+
+    tokio::spawn(async move {
+        let mcp = match McpClient::new(&mcp_url, plan_clone.rp_auth(), plan_clone.rp_ca()).await {
+            Ok(c) => c,
+            Err(e) => {
+                warn!(workflow_id = %wf_id, error = %e, "failed to connect MCP client");
+                post_failure(&mcp_url, &wf_id, &e.to_string(), &plan_clone).await;
+                return;
+            }
+        };
+
+        match workflow::run(&mcp, &plan_clone).await {
+            Ok(result) => {
+                info!(
+                    workflow_id = %wf_id,
+                    total_frames = result.total_frames,
+                    "dark library completed"
+                );
+                post_completion(&mcp_url, &wf_id, &result, &plan_clone).await;
+            }
+            Err(e) => {
+                warn!(workflow_id = %wf_id, error = %e, "dark library failed");
+                post_failure(&mcp_url, &wf_id, &e.to_string(), &plan_clone).await;
+            }
+        }
+    });
+
+    // Acknowledge with timing estimate. The work depends on an archive
+    // scan and cooldowns that have not happened yet, so both figures
+    // come from the plan's configured bound.
+    let estimated = plan.max_duration / 2;
+    let max = plan.max_duration;
+
+    let ack = serde_json::json!({
+        "estimated_duration": humantime::format_duration(estimated).to_string(),
+        "max_duration": humantime::format_duration(max).to_string(),
+    });
+
+    (StatusCode::OK, Json(ack))
+}
+
+async fn post_completion(
+    mcp_server_url: &str,
+    workflow_id: &str,
+    result: &workflow::WorkflowResult,
+    plan: &DarkPlan,
+) {
+    let base_url = mcp_server_url.trim_end_matches("/mcp");
+    let url = format!("{base_url}/api/plugins/{workflow_id}/complete");
+
+    let rungs: Vec<Value> = result
+        .rungs
+        .iter()
+        .map(|r| {
+            serde_json::json!({
+                "rung_c": r.rung_c,
+                "status": r.status.as_str(),
+                "darks_captured": r.darks_captured,
+                "biases_captured": r.biases_captured,
+                "sets_already_complete": r.sets_complete,
+            })
+        })
+        .collect();
+
+    let body = serde_json::json!({
+        "status": "complete",
+        "result": {
+            "reason": "dark_library_complete",
+            "rungs": rungs,
+            "total_frames": result.total_frames,
+        }
+    });
+
+    post_to_rp(&url, &body, plan).await;
+}
+
+async fn post_failure(mcp_server_url: &str, workflow_id: &str, error: &str, plan: &DarkPlan) {
+    let base_url = mcp_server_url.trim_end_matches("/mcp");
+    let url = format!("{base_url}/api/plugins/{workflow_id}/complete");
+
+    let body = serde_json::json!({
+        "status": "error",
+        "result": {
+            "reason": "dark_library_failed",
+            "error": error,
+        }
+    });
+
+    post_to_rp(&url, &body, plan).await;
+}
+
+/// POST a completion body to `rp`, trusting and authenticating per the
+/// ADR-017 policy — the same legs the MCP client uses.
+async fn post_to_rp(url: &str, body: &Value, plan: &DarkPlan) {
+    let client = match rusty_photon_tls::client::build_reqwest_client(plan.rp_ca()) {
+        Ok(client) => client,
+        Err(e) => {
+            warn!(%url, error = %e, "cannot build HTTP client for the completion post");
+            return;
+        }
+    };
+    let auth_header = rp_mcp_client::basic_authorization(url, plan.rp_auth(), plan.rp_ca())
+        .unwrap_or_else(|e| {
+            warn!(%url, error = %e, "cannot build the completion Authorization header");
+            None
+        });
+    let mut request = client.post(url).json(body);
+    if let Some(header) = auth_header {
+        request = request.header(reqwest::header::AUTHORIZATION, header);
+    }
+    let _ = request.send().await;
+}
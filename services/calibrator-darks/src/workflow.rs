@@ -0,0 +1,524 @@
+//! Dark-library workflow: per rung, cool, then fill every missing
+//! dark and bias set the frame archive calls for.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rp_vocabulary::{Binning, FrameType};
+use tracing::{debug, info, warn};
+
+use crate::archive::{self, ArchiveSummary, DarkCombo, Readout};
+use crate::config::DarkPlan;
+use crate::error::{CalibratorDarksError, Result};
+use crate::mcp_client::McpClient;
+
+/// What the per-rung loop needs from rp: cool to a rung, apply a
+/// readout, capture a calibration frame. Wrapped in a trait so the loop
+/// can be tested in isolation with `mockall`.
+#[async_trait]
+#[cfg_attr(test, mockall::automock)]
+trait DarkRig: Send + Sync {
+    async fn cool_to(&self, camera_id: &str, rung_c: i32) -> Result<bool>;
+    async fn apply_readout(&self, camera_id: &str, readout: Readout) -> Result<()>;
+    async fn capture_frame(
+        &self,
+        camera_id: &str,
+        duration: Duration,
+        frame_type: FrameType,
+    ) -> Result<()>;
+}
+
+#[async_trait]
+impl DarkRig for McpClient {
+    async fn cool_to(&self, camera_id: &str, rung_c: i32) -> Result<bool> {
+        self.cool_camera(camera_id, rung_c).await
+    }
+
+    async fn apply_readout(&self, camera_id: &str, readout: Readout) -> Result<()> {
+        self.set_camera_settings(
+            camera_id,
+            Some(readout.gain),
+            Some(readout.offset),
+            Some(readout.binning),
+        )
+        .await
+    }
+
+    async fn capture_frame(
+        &self,
+        camera_id: &str,
+        duration: Duration,
+        frame_type: FrameType,
+    ) -> Result<()> {
+        self.capture(camera_id, duration, frame_type).await
+    }
+}
+
+/// The frames still missing at one rung.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RungPlan {
+    pub rung_c: i32,
+    /// Dark sets to fill, with the number of frames each still needs.
+    pub darks: Vec<(DarkCombo, u32)>,
+    /// Bias sets to fill, with the number of frames each still needs.
+    pub biases: Vec<(Readout, u32)>,
+    /// Dark and bias sets already complete on disk.
+    pub sets_complete: u32,
+}
+
+/// Result of the dark-library workflow.
+#[derive(Debug, Default)]
+pub struct WorkflowResult {
+    pub rungs: Vec<RungResult>,
+    pub total_frames: u32,
+}
+
+/// What happened at a rung.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RungStatus {
+    /// Cooled to the rung and filled its missing sets.
+    Captured,
+    /// Nothing was missing; the rung was not visited.
+    AlreadyComplete,
+    /// The cooler could not settle at the rung; it was skipped.
+    Unreachable,
+}
+
+impl RungStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Captured => "captured",
+            Self::AlreadyComplete => "already_complete",
+            Self::Unreachable => "unreachable",
+        }
+    }
+}
+
+/// Result for a single rung.
+#[derive(Debug)]
+pub struct RungResult {
+    pub rung_c: i32,
+    pub status: RungStatus,
+    pub darks_captured: u32,
+    pub biases_captured: u32,
+    pub sets_complete: u32,
+}
+
+impl RungResult {
+    const fn skipped(plan: &RungPlan, status: RungStatus) -> Self {
+        Self {
+            rung_c: plan.rung_c,
+            status,
+            darks_captured: 0,
+            biases_captured: 0,
+            sets_complete: plan.sets_complete,
+        }
+    }
+}
+
+/// Run the full dark-library workflow.
+///
+/// 1. Query the camera's ladder and current settings
+/// 2. Scan the archive and work out what each rung is missing; stop
+///    here, touching nothing, when the library is already complete
+/// 3. Record the cover's initial state and close it (when configured)
+/// 4. Per rung: cool, then capture the missing darks and biases
+/// 5. Restore the camera settings and the cover's initial state
+///    (always, even on error): reopen only what started open
+pub async fn run(mcp: &McpClient, plan: &DarkPlan) -> Result<WorkflowResult> {
+    // 1. Get camera info
+    let camera_info = mcp.get_camera_info(&plan.camera_id).await?;
+    let rungs = select_rungs(&camera_info.cooler_targets_c, plan.rungs.as_deref());
+    if rungs.is_empty() {
+        return Err(CalibratorDarksError::Workflow(format!(
+            "camera {} has no cooler_targets_c rungs to build a dark library for",
+            plan.camera_id
+        )));
+    }
+
+    // 2. Scan the archive off the async runtime — it can hold years of
+    // sidecars.
+    let directory = plan.archive_directory.clone();
+    let camera_id = plan.camera_id.clone();
+    let summary = tokio::task::spawn_blocking(move || archive::scan(&directory, &camera_id))
+        .await
+        .map_err(|e| CalibratorDarksError::Workflow(format!("archive scan task: {e}")))??;
+    let plans = plan_rungs(&summary, &rungs, plan.dark_count, plan.bias_count);
+
+    info!(
+        rungs = ?rungs,
+        combos = summary.combos.len(),
+        rungs_to_fill = plans
+            .iter()
+            .filter(|p| !p.darks.is_empty() || !p.biases.is_empty())
+            .count(),
+        "starting dark library capture"
+    );
+    if plans
+        .iter()
+        .all(|p| p.darks.is_empty() && p.biases.is_empty())
+    {
+        info!("dark library already complete, nothing to capture");
+        return Ok(WorkflowResult {
+            rungs: plans
+                .iter()
+                .map(|p| RungResult::skipped(p, RungStatus::AlreadyComplete))
+                .collect(),
+            total_frames: 0,
+        });
+    }
+
+    // 3. Shut out the light. A failed read aborts here — nothing has
+    // moved yet.
+    let initial_cover = match plan.calibrator() {
+        Some(calibrator_id) => {
+            let state = mcp.get_cover_state(calibrator_id).await?;
+            debug!(initial_cover = %state, "recorded initial cover state");
+            mcp.close_cover(calibrator_id).await?;
+            Some(state)
+        }
+        None => None,
+    };
+
+    // 4. Capture (with cleanup guard)
+    let result = run_rungs(mcp, &plan.camera_id, &plans, camera_info.exposure_min).await;
+
+    // 5. Always clean up: the camera's readout as we found it, and the
+    // cover reopened only if it started open — one that started closed,
+    // or whose initial reading was anomalous, stays closed.
+    if let Err(e) = mcp
+        .set_camera_settings(
+            &plan.camera_id,
+            camera_info.gain,
+            camera_info.offset,
+            Some(Binning {
+                x: camera_info.bin_x,
+                y: camera_info.bin_y,
+            }),
+        )
+        .await
+    {
+        warn!(error = %e, "failed to restore camera settings during cleanup");
+    }
+    if let (Some(calibrator_id), Some(initial_cover)) = (plan.calibrator(), initial_cover) {
+        if initial_cover == "Open" {
+            if let Err(e) = mcp.open_cover(calibrator_id).await {
+                warn!(error = %e, "failed to open cover during cleanup");
+            }
+        } else {
+            debug!(initial_cover = %initial_cover, "leaving cover closed");
+        }
+    }
+
+    result
+}
+
+/// The ladder rungs to visit: all of them, or those the plan names.
+/// Named rungs that are not on the ladder are dropped with a warning.
+fn select_rungs(ladder: &[i32], wanted: Option<&[i32]>) -> Vec<i32> {
+    let Some(wanted) = wanted else {
+        return ladder.to_vec();
+    };
+    for rung in wanted.iter().filter(|rung| !ladder.contains(rung)) {
+        warn!(rung_c = rung, ladder = ?ladder, "rung is not on the camera's ladder, ignoring");
+    }
+    ladder
+        .iter()
+        .copied()
+        .filter(|rung| wanted.contains(rung))
+        .collect()
+}
+
+/// Work out, per rung, which dark and bias sets fall short of the
+/// wanted counts. Sets already at or above the count are skipped.
+fn plan_rungs(
+    summary: &ArchiveSummary,
+    rungs: &[i32],
+    dark_count: u32,
+    bias_count: u32,
+) -> Vec<RungPlan> {
+    let readouts = summary.readouts();
+    rungs
+        .iter()
+        .map(|&rung_c| {
+            let mut sets_complete = 0;
+            let darks = summary
+                .combos
+                .iter()
+                .filter_map(|combo| {
+                    let missing = dark_count.saturating_sub(summary.darks_at(rung_c, combo));
+                    if missing == 0 {
+                        sets_complete += 1;
+                        None
+                    } else {
+                        Some((*combo, missing))
+                    }
+                })
+                .collect();
+            let biases = readouts
+                .iter()
+                .filter_map(|readout| {
+                    let missing = bias_count.saturating_sub(summary.biases_at(rung_c, readout));
+                    if missing == 0 {
+                        sets_complete += 1;
+                        None
+                    } else {
+                        Some((*readout, missing))
+                    }
+                })
+                .collect();
+            RungPlan {
+                rung_c,
+                darks,
+                biases,
+                sets_complete,
+            }
+        })
+        .collect()
+}
+
+/// Visit each rung with work to do: cool, then fill its dark sets and
+/// its bias sets (at the camera's shortest exposure). A rung that
+/// cannot be reached is reported unreachable and skipped; a tool
+/// failure aborts the run — frames already captured stay on disk, so
+/// the next invocation resumes where this one stopped.
+async fn run_rungs<R: DarkRig + ?Sized>(
+    rig: &R,
+    camera_id: &str,
+    plans: &[RungPlan],
+    bias_duration: Duration,
+) -> Result<WorkflowResult> {
+    let mut result = WorkflowResult::default();
+
+    for plan in plans {
+        if plan.darks.is_empty() && plan.biases.is_empty() {
+            debug!(rung_c = plan.rung_c, "rung already complete");
+            result
+                .rungs
+                .push(RungResult::skipped(plan, RungStatus::AlreadyComplete));
+            continue;
+        }
+
+        info!(rung_c = plan.rung_c, "cooling to rung");
+        if !rig.cool_to(camera_id, plan.rung_c).await? {
+            warn!(rung_c = plan.rung_c, "rung unreachable, skipping");
+            result
+                .rungs
+                .push(RungResult::skipped(plan, RungStatus::Unreachable));
+            continue;
+        }
+
+        let mut darks_captured = 0;
+        for (combo, missing) in &plan.darks {
+            debug!(
+                rung_c = plan.rung_c,
+                readout = ?combo.readout,
+                duration = %humantime::format_duration(combo.duration),
+                missing = missing,
+                "capturing darks"
+            );
+            rig.apply_readout(camera_id, combo.readout).await?;
+            for _ in 0..*missing {
+                rig.capture_frame(camera_id, combo.duration, FrameType::Dark)
+                    .await?;
+                darks_captured += 1;
+            }
+        }
+
+        let mut biases_captured = 0;
+        for (readout, missing) in &plan.biases {
+            debug!(rung_c = plan.rung_c, readout = ?readout, missing = missing, "capturing biases");
+            rig.apply_readout(camera_id, *readout).await?;
+            for _ in 0..*missing {
+                rig.capture_frame(camera_id, bias_duration, FrameType::Bias)
+                    .await?;
+                biases_captured += 1;
+            }
+        }
+
+        info!(
+            rung_c = plan.rung_c,
+            darks_captured = darks_captured,
+            biases_captured = biases_captured,
+            "rung complete"
+        );
+        result.total_frames += darks_captured + biases_captured;
+        result.rungs.push(RungResult {
+            rung_c: plan.rung_c,
+            status: RungStatus::Captured,
+            darks_captured,
+            biases_captured,
+            sets_complete: plan.sets_complete,
+        });
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::{plan_rungs, run_rungs, select_rungs, MockDarkRig, RungPlan, RungStatus};
+    use crate::archive::{ArchiveSummary, DarkCombo, Readout};
+    use crate::error::CalibratorDarksError;
+    use mockall::predicate::{always, eq};
+    use rp_vocabulary::{Binning, FrameType};
+    use std::time::Duration;
+
+    const BIAS: Duration = Duration::from_micros(10);
+
+    fn readout(gain: i32) -> Readout {
+        Readout {
+            gain,
+            offset: 10,
+            binning: Binning { x: 1, y: 1 },
+        }
+    }
+
+    fn combo(gain: i32, secs: u64) -> DarkCombo {
+        DarkCombo {
+            readout: readout(gain),
+            duration: Duration::from_secs(secs),
+        }
+    }
+
+    #[test]
+    fn select_rungs_defaults_to_the_whole_ladder() {
+        assert_eq!(select_rungs(&[-20, -10, 0], None), vec![-20, -10, 0]);
+    }
+
+    #[test]
+    fn select_rungs_keeps_ladder_order_and_drops_unknown_rungs() {
+        assert_eq!(
+            select_rungs(&[-20, -10, 0], Some(&[0, 5, -20])),
+            vec![-20, 0]
+        );
+    }
+
+    #[test]
+    fn plan_rungs_fills_only_the_shortfall() {
+        let mut summary = ArchiveSummary {
+            combos: vec![combo(100, 300), combo(100, 60)],
+            ..ArchiveSummary::default()
+        };
+        summary.darks.insert((-10, combo(100, 300)), 20);
+        summary.darks.insert((-10, combo(100, 60)), 5);
+        summary.biases.insert((-10, readout(100)), 60);
+
+        let plans = plan_rungs(&summary, &[-10, 0], 20, 50);
+        assert_eq!(
+            plans,
+            vec![
+                RungPlan {
+                    rung_c: -10,
+                    darks: vec![(combo(100, 60), 15)],
+                    biases: vec![],
+                    sets_complete: 2,
+                },
+                RungPlan {
+                    rung_c: 0,
+                    darks: vec![(combo(100, 300), 20), (combo(100, 60), 20)],
+                    biases: vec![(readout(100), 50)],
+                    sets_complete: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_rungs_without_lights_has_nothing_to_do() {
+        let plans = plan_rungs(&ArchiveSummary::default(), &[-10], 20, 50);
+        assert!(plans[0].darks.is_empty());
+        assert!(plans[0].biases.is_empty());
+    }
+
+    #[tokio::test]
+    async fn run_rungs_cools_then_captures_darks_and_biases() {
+        let plans = vec![RungPlan {
+            rung_c: -10,
+            darks: vec![(combo(100, 300), 2)],
+            biases: vec![(readout(100), 3)],
+            sets_complete: 1,
+        }];
+        let mut rig = MockDarkRig::new();
+        rig.expect_cool_to()
+            .with(eq("cam"), eq(-10))
+            .times(1)
+            .returning(|_, _| Ok(true));
+        rig.expect_apply_readout()
+            .with(eq("cam"), eq(readout(100)))
+            .times(2)
+            .returning(|_, _| Ok(()));
+        rig.expect_capture_frame()
+            .with(eq("cam"), eq(Duration::from_secs(300)), eq(FrameType::Dark))
+            .times(2)
+            .returning(|_, _, _| Ok(()));
+        rig.expect_capture_frame()
+            .with(eq("cam"), eq(BIAS), eq(FrameType::Bias))
+            .times(3)
+            .returning(|_, _, _| Ok(()));
+
+        let result = run_rungs(&rig, "cam", &plans, BIAS).await.unwrap();
+        assert_eq!(result.total_frames, 5);
+        assert_eq!(result.rungs.len(), 1);
+        assert_eq!(result.rungs[0].status, RungStatus::Captured);
+        assert_eq!(result.rungs[0].darks_captured, 2);
+        assert_eq!(result.rungs[0].biases_captured, 3);
+        assert_eq!(result.rungs[0].sets_complete, 1);
+    }
+
+    #[tokio::test]
+    async fn run_rungs_skips_complete_and_unreachable_rungs() {
+        let plans = vec![
+            RungPlan {
+                rung_c: -20,
+                darks: vec![(combo(100, 300), 2)],
+                biases: vec![],
+                sets_complete: 0,
+            },
+            RungPlan {
+                rung_c: -10,
+                darks: vec![],
+                biases: vec![],
+                sets_complete: 2,
+            },
+        ];
+        let mut rig = MockDarkRig::new();
+        // Only the rung with work is cooled to; it proves unreachable,
+        // so nothing is captured.
+        rig.expect_cool_to()
+            .with(eq("cam"), eq(-20))
+            .times(1)
+            .returning(|_, _| Ok(false));
+        rig.expect_apply_readout().never();
+        rig.expect_capture_frame().never();
+
+        let result = run_rungs(&rig, "cam", &plans, BIAS).await.unwrap();
+        assert_eq!(result.total_frames, 0);
+        assert_eq!(result.rungs.len(), 2);
+        assert_eq!(result.rungs[0].status, RungStatus::Unreachable);
+        assert_eq!(result.rungs[1].status, RungStatus::AlreadyComplete);
+        assert_eq!(result.rungs[1].sets_complete, 2);
+    }
+
+    #[tokio::test]
+    async fn run_rungs_aborts_on_a_capture_failure() {
+        let plans = vec![RungPlan {
+            rung_c: -10,
+            darks: vec![(combo(100, 300), 5)],
+            biases: vec![],
+            sets_complete: 0,
+        }];
+        let mut rig = MockDarkRig::new();
+        rig.expect_cool_to().returning(|_, _| Ok(true));
+        rig.expect_apply_readout().returning(|_, _| Ok(()));
+        rig.expect_capture_frame()
+            .with(always(), always(), always())
+            .times(1)
+            .returning(|_, _, _| Err(CalibratorDarksError::ToolCall("capture: boom".into())));
+
+        let err = run_rungs(&rig, "cam", &plans, BIAS).await.unwrap_err();
+        assert!(err.to_string().contains("boom"));
+    }
+}
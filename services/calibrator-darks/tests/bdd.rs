@@ -0,0 +1,47 @@
+//! BDD test entry point for the calibrator-darks service.
+//!
+//! The dark-library scenarios spawn three processes — `OmniSim`, rp, and
+//! calibrator-darks — and drive the workflow end-to-end via rp's REST API.
+//! The smoke scenarios spawn calibrator-darks alone with a temp config.
+
+#![allow(clippy::expect_used, clippy::panic)]
+
+#[path = "bdd/world.rs"]
+mod world;
+
+#[path = "bdd/steps/mod.rs"]
+mod steps;
+
+bdd_infra::bdd_main! {
+    use cucumber::World as _;
+    use world::CalibratorDarksWorld;
+
+    CalibratorDarksWorld::cucumber()
+        .before(|_feature, _rule, _scenario, _world| {
+            Box::pin(async move {
+                // OmniSim is a per-process singleton: reset the devices
+                // so one scenario's cooler and readout settings do not
+                // leak into the next. See calibrator-flats' BDD entry
+                // point for why a reset failure is fatal.
+                if let Err(errors) =
+                    bdd_infra::rp_harness::OmniSimHandle::reset_all_devices().await
+                {
+                    panic!("OmniSim device reset failed: {}", errors.join("; "));
+                }
+            })
+        })
+        .after(|_feature, _rule, _scenario, _finished, maybe_world| {
+            Box::pin(async move {
+                if let Some(world) = maybe_world {
+                    if let Some(handle) = world.calibrator_darks.as_mut() {
+                        handle.stop().await;
+                    }
+                    if let Some(rp) = world.rp.as_mut() {
+                        rp.stop().await;
+                    }
+                }
+            })
+        })
+        .run_and_exit("tests/features")
+        .await;
+}
@@ -0,0 +1,8 @@
+//! TLS + HTTP Basic Auth smoke steps, expanded from the shared macro. The
+//! service-specific parts (config template, launch) live in the
+//! `TlsAuthSmokeWorld` impl in `world.rs`. The scenario spawns only
+//! calibrator-darks itself, with a temp config.
+
+use crate::world::CalibratorDarksWorld;
+
+bdd_infra::tls_auth_smoke_steps!(CalibratorDarksWorld);
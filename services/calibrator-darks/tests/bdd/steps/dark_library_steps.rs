@@ -0,0 +1,335 @@
+//! BDD step definitions for the end-to-end dark-library workflow.
+//!
+//! The scenarios spawn three processes: `OmniSim` (Alpaca simulator), rp
+//! (equipment gateway + session orchestrator), and calibrator-darks (the
+//! orchestrator plugin being tested), sharing one temp directory as rp's
+//! frame archive. The scenario seeds light-frame sidecars there; the
+//! workflow reads them to decide which darks each rung needs and rp writes
+//! the captured darks and biases back beside them.
+
+use std::path::Path;
+use std::time::Duration;
+
+use bdd_infra::rp_harness::{
+    start_rp, write_temp_config_file, McpTestClient, OmniSimHandle, WebhookReceiver,
+};
+use bdd_infra::ServiceHandle;
+use cucumber::{given, then, when};
+use serde_json::Value;
+
+use crate::world::CalibratorDarksWorld;
+
+// ---------------------------------------------------------------------------
+// Given steps
+// ---------------------------------------------------------------------------
+
+#[given("a running Alpaca simulator")]
+async fn running_alpaca_simulator(world: &mut CalibratorDarksWorld) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+}
+
+#[given(expr = "a test webhook receiver subscribed to {string}")]
+async fn webhook_receiver_subscribed_to(world: &mut CalibratorDarksWorld, event_type: String) {
+    if world.webhook_receiver.is_none() {
+        let events = world.received_events.clone();
+        world.webhook_receiver = Some(
+            WebhookReceiver::start(events, Duration::from_secs(5), Duration::from_secs(10)).await,
+        );
+    }
+    let url = world
+        .webhook_receiver
+        .as_ref()
+        .expect("webhook receiver not started")
+        .url
+        .clone();
+    match world
+        .plugin_configs
+        .iter_mut()
+        .find(|p| p.get("name").and_then(Value::as_str) == Some("test-event-plugin"))
+    {
+        Some(plugin) => {
+            if let Some(subscribed) = plugin["subscribes_to"].as_array_mut() {
+                subscribed.push(serde_json::json!(event_type));
+            }
+        }
+        None => world.plugin_configs.push(serde_json::json!({
+            "name": "test-event-plugin",
+            "type": "event",
+            "webhook_url": url,
+            "subscribes_to": [event_type]
+        })),
+    }
+}
+
+#[given(expr = "the calibrator-darks service is configured for {int} darks and {int} biases")]
+fn configure_counts(world: &mut CalibratorDarksWorld, darks: u32, biases: u32) {
+    world.dark_count = darks;
+    world.bias_count = biases;
+}
+
+#[given(expr = "the plan is restricted to the {int} °C rung")]
+fn restrict_rungs(world: &mut CalibratorDarksWorld, rung: i32) {
+    world.rungs = Some(vec![rung]);
+}
+
+#[given(
+    expr = "rp is running with a camera with cooler targets {string} and the calibrator-darks orchestrator"
+)]
+async fn rp_running_with_camera_and_calibrator_darks(
+    world: &mut CalibratorDarksWorld,
+    targets: String,
+) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+    let cooler_targets_c = targets
+        .split(',')
+        .map(|t| t.trim().parse().expect("cooler target must be an integer"))
+        .collect();
+    world.cameras.push(bdd_infra::rp_harness::CameraConfig {
+        id: "main-cam".to_string(),
+        alpaca_url: world.omnisim_url(),
+        device_number: 0,
+        cooler_targets_c,
+    });
+
+    let plan = world.build_plan();
+    let plan_path = write_temp_config_file("calibrator-darks-config", &plan).await;
+    let handle = ServiceHandle::start(env!("CARGO_PKG_NAME"), &plan_path).await;
+    world.plugin_configs.push(serde_json::json!({
+        "name": "calibrator-darks",
+        "type": "orchestrator",
+        "invoke_url": format!("{}/invoke", handle.base_url),
+        "requires_tools": []
+    }));
+    world.calibrator_darks = Some(handle);
+
+    let config = world.build_rp_config();
+    world.rp = Some(start_rp(&config).await);
+    assert!(
+        world.wait_for_rp_healthy().await,
+        "rp did not become healthy within timeout"
+    );
+}
+
+#[given(expr = "the archive holds a {int} ms light frame at the camera's current readout")]
+async fn archive_holds_light(world: &mut CalibratorDarksWorld, millis: u64) {
+    let readout = current_readout(world).await;
+    world.light_millis = Some(millis);
+    let archive = world.archive().path().to_path_buf();
+    write_sidecar(&archive, "light-0", "Light", millis, None, &readout);
+}
+
+#[given(
+    expr = "the archive already holds {int} darks and {int} biases for it at each of {string} °C"
+)]
+async fn archive_holds_calibration(
+    world: &mut CalibratorDarksWorld,
+    darks: u32,
+    biases: u32,
+    rungs: String,
+) {
+    let readout = current_readout(world).await;
+    let millis = world
+        .light_millis
+        .expect("seed a light frame before its darks");
+    let archive = world.archive().path().to_path_buf();
+    for rung in rungs
+        .split(',')
+        .map(|r| r.trim().parse::<i32>().expect("rung must be an integer"))
+    {
+        for i in 0..darks {
+            let name = format!("dark-{rung}-{i}");
+            write_sidecar(&archive, &name, "Dark", millis, Some(rung), &readout);
+        }
+        for i in 0..biases {
+            let name = format!("bias-{rung}-{i}");
+            write_sidecar(&archive, &name, "Bias", 1, Some(rung), &readout);
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when("a session is started via the REST API")]
+async fn start_session(world: &mut CalibratorDarksWorld) {
+    let url = format!("{}/api/session/start", world.rp_url());
+    let resp = reqwest::Client::new()
+        .post(&url)
+        .json(&serde_json::json!({}))
+        .send()
+        .await
+        .expect("failed to POST /api/session/start");
+    assert!(
+        resp.status().is_success(),
+        "session start failed with {}",
+        resp.status()
+    );
+}
+
+#[when("the calibrator-darks orchestrator runs to completion")]
+async fn orchestrator_runs_to_completion(world: &mut CalibratorDarksWorld) {
+    // Per rung: a cooldown pass (bounded by the fast profile's 30 s
+    // backstop) plus the darks and biases, each a sub-second exposure.
+    // Allow 180 s for a two-rung ladder.
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/session/status", world.rp_url());
+    for _ in 0..720 {
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        if let Ok(resp) = client.get(&url).send().await {
+            if let Ok(body) = resp.json::<Value>().await {
+                if body.get("status").and_then(Value::as_str) == Some("idle") {
+                    return;
+                }
+            }
+        }
+    }
+    panic!(
+        "calibrator-darks orchestrator did not complete within 180s \
+         (expected session to return to idle)"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the archive should hold {int} {string} frames at {int} °C")]
+fn archive_should_hold(
+    world: &mut CalibratorDarksWorld,
+    expected: usize,
+    frame_type: String,
+    rung: i32,
+) {
+    let archive = world.archive().path().to_path_buf();
+    let found = sidecars(&archive)
+        .into_iter()
+        .filter(|doc| {
+            doc["frame_type"].as_str() == Some(frame_type.as_str())
+                && doc["cooler_setpoint_c"].as_i64() == Some(i64::from(rung))
+        })
+        .count();
+    assert_eq!(
+        found, expected,
+        "expected {expected} {frame_type} frame(s) at {rung} °C in the archive, found {found}"
+    );
+}
+
+#[then(expr = "the test webhook receiver should have received a {string} event for {int} °C")]
+async fn received_cooler_event_for(
+    world: &mut CalibratorDarksWorld,
+    event_type: String,
+    rung: i32,
+) {
+    let events = world.wait_for_events(&event_type, 1).await;
+    assert!(
+        events
+            .iter()
+            .any(|e| e.payload["target_c"].as_i64() == Some(i64::from(rung))),
+        "no '{event_type}' event with target_c {rung}; got {:?}",
+        events.iter().map(|e| &e.payload).collect::<Vec<_>>()
+    );
+}
+
+#[then(expr = "the session status should be {string}")]
+async fn session_status_is(world: &mut CalibratorDarksWorld, expected: String) {
+    let url = format!("{}/api/session/status", world.rp_url());
+    let body: Value = reqwest::Client::new()
+        .get(&url)
+        .send()
+        .await
+        .expect("failed to GET /api/session/status")
+        .json()
+        .await
+        .expect("failed to parse session status");
+    assert_eq!(
+        body.get("status").and_then(Value::as_str),
+        Some(expected.as_str())
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// The camera's live (gain, offset, binning) via rp's `get_camera_info` —
+/// the readout the seeded frames carry, so `set_camera_settings` is
+/// handed values the simulator accepts.
+async fn current_readout(world: &CalibratorDarksWorld) -> Value {
+    let client = McpTestClient::connect(&format!("{}/mcp", world.rp_url()))
+        .await
+        .expect("failed to connect MCP test client");
+    let info = client
+        .call_tool(
+            "get_camera_info",
+            serde_json::json!({"camera_id": "main-cam"}),
+        )
+        .await
+        .expect("get_camera_info failed");
+    assert!(
+        info["gain"].is_i64() && info["offset"].is_i64(),
+        "the simulator camera must report gain and offset: {info}"
+    );
+    serde_json::json!({
+        "gain": info["gain"],
+        "offset": info["offset"],
+        "binning": format!("{}x{}", info["bin_x"], info["bin_y"]),
+    })
+}
+
+/// Write an exposure-document sidecar with the fields the archive scan
+/// keys on.
+fn write_sidecar(
+    archive: &Path,
+    name: &str,
+    frame_type: &str,
+    millis: u64,
+    rung: Option<i32>,
+    readout: &Value,
+) {
+    let mut doc = serde_json::json!({
+        "id": name,
+        "captured_at": "2026-10-16T00:00:00Z",
+        "file_path": archive.join(format!("{name}.fits")).to_string_lossy(),
+        "width": 100,
+        "height": 100,
+        "camera_id": "main-cam",
+        "duration": format!("{millis}ms"),
+        "gain": readout["gain"],
+        "offset": readout["offset"],
+        "binning": readout["binning"],
+        "frame_type": frame_type,
+    });
+    if let Some(rung) = rung {
+        doc["cooler_setpoint_c"] = serde_json::json!(rung);
+    }
+    std::fs::write(archive.join(format!("{name}.json")), doc.to_string())
+        .expect("failed to write a seeded sidecar");
+}
+
+/// Every exposure document under `dir`, recursively.
+fn sidecars(dir: &Path) -> Vec<Value> {
+    let mut docs = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return docs;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            docs.extend(sidecars(&path));
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            if let Some(doc) = std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|s| serde_json::from_str::<Value>(&s).ok())
+                .filter(|doc| doc.get("file_path").is_some())
+            {
+                docs.push(doc);
+            }
+        }
+    }
+    docs
+}
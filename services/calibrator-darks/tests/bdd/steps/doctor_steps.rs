@@ -0,0 +1,6 @@
+//! Doctor-subcommand smoke steps — all shared, generated against the
+//! `DoctorSmokeWorld` impl in `world.rs`.
+
+use crate::world::CalibratorDarksWorld;
+
+bdd_infra::doctor_smoke_steps!(CalibratorDarksWorld);
@@ -0,0 +1,5 @@
+//! BDD step definitions for the calibrator-darks service.
+
+pub mod auth_steps;
+pub mod dark_library_steps;
+pub mod doctor_steps;
@@ -0,0 +1,179 @@
+//! BDD test world for the calibrator-darks service: the three external
+//! processes of the dark-library scenarios (`OmniSim`, rp,
+//! calibrator-darks), the frame archive they share, plus the shared TLS +
+//! auth and doctor smoke state.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use bdd_infra::rp_harness::{
+    CameraConfig, CoolingOverrides, ReceivedEvent, RpConfigBuilder, WebhookReceiver,
+};
+use bdd_infra::tls_auth::{TlsAuthSmokeWorld, TlsAuthState};
+use bdd_infra::ServiceHandle;
+use cucumber::World;
+use serde_json::Value;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+#[derive(Default, World, derive_more::Debug)]
+#[debug("CalibratorDarksWorld {{ .. }}")]
+pub struct CalibratorDarksWorld {
+    // --- Infrastructure handles ---
+    pub omnisim: Option<bdd_infra::rp_harness::OmniSimHandle>,
+    pub rp: Option<ServiceHandle>,
+    pub calibrator_darks: Option<ServiceHandle>,
+    pub webhook_receiver: Option<WebhookReceiver>,
+
+    // --- rp config building ---
+    pub cameras: Vec<CameraConfig>,
+    pub plugin_configs: Vec<Value>,
+
+    // --- Webhook state ---
+    pub received_events: Arc<RwLock<Vec<ReceivedEvent>>>,
+
+    // --- Dark-library plan ---
+    /// rp's `session.data_directory` and the plan's `archive_directory`:
+    /// rp writes the darks where calibrator-darks scans for them.
+    pub archive: Option<TempDir>,
+    /// Exposure of the seeded light frame, which the seeded darks match.
+    pub light_millis: Option<u64>,
+    pub dark_count: u32,
+    pub bias_count: u32,
+    /// The plan's `rungs` restriction; `None` visits the whole ladder.
+    pub rungs: Option<Vec<i32>>,
+
+    /// State for the shared TLS + auth smoke steps.
+    pub tls_auth: TlsAuthState,
+
+    /// Doctor-subcommand smoke state (staged config file + run output)
+    pub doctor_smoke: bdd_infra::doctor_smoke::DoctorSmokeState,
+}
+
+impl bdd_infra::doctor_smoke::DoctorSmokeWorld for CalibratorDarksWorld {
+    fn doctor_smoke(&mut self) -> &mut bdd_infra::doctor_smoke::DoctorSmokeState {
+        &mut self.doctor_smoke
+    }
+
+    fn valid_config(&self) -> serde_json::Value {
+        // The tls-auth smoke's base config plus a plain `server` block.
+        let mut config = TlsAuthSmokeWorld::base_test_config(self);
+        config["server"] = serde_json::json!({ "port": 0 });
+        config
+    }
+}
+
+impl CalibratorDarksWorld {
+    pub fn omnisim_url(&self) -> String {
+        self.omnisim
+            .as_ref()
+            .expect("OmniSim must be started before accessing its URL")
+            .base_url
+            .clone()
+    }
+
+    pub fn rp_url(&self) -> String {
+        self.rp
+            .as_ref()
+            .map(|h| h.base_url.clone())
+            .expect("rp must be started before accessing its URL")
+    }
+
+    /// The shared frame archive, created on first use.
+    pub fn archive(&mut self) -> &TempDir {
+        self.archive
+            .get_or_insert_with(|| TempDir::new().expect("failed to create the archive dir"))
+    }
+
+    /// rp's config: the accumulated camera and plugins, the archive as
+    /// its data directory, and cooling tuned for test speed.
+    pub fn build_rp_config(&mut self) -> Value {
+        let archive = self.archive().path().to_string_lossy().into_owned();
+        let mut builder = RpConfigBuilder::new();
+        for camera in &self.cameras {
+            builder.add_camera(camera.clone());
+        }
+        for plugin in &self.plugin_configs {
+            builder.add_plugin(plugin.clone());
+        }
+        builder
+            .with_data_directory(archive)
+            .with_cooling(CoolingOverrides::fast());
+        builder.build()
+    }
+
+    /// The calibrator-darks plan: no cover (the simulated rig is capped),
+    /// the archive, and the scenario's counts and rung restriction.
+    pub fn build_plan(&mut self) -> Value {
+        let archive = self.archive().path().to_string_lossy().into_owned();
+        let mut plan = serde_json::json!({
+            "camera_id": "main-cam",
+            "archive_directory": archive,
+            "dark_count": self.dark_count,
+            "bias_count": self.bias_count,
+            // Port 0 for the same reason as calibrator-flats' BDD plan:
+            // concurrent suites must not fight over the default port.
+            "server": {
+                "port": 0,
+                "bind_address": "127.0.0.1"
+            }
+        });
+        if let Some(rungs) = &self.rungs {
+            plan["rungs"] = serde_json::json!(rungs);
+        }
+        plan
+    }
+
+    /// Wait for rp's `/health` endpoint to return 200.
+    pub async fn wait_for_rp_healthy(&self) -> bool {
+        bdd_infra::rp_harness::wait_for_rp_healthy(&self.rp_url()).await
+    }
+
+    /// Every received event of `event_type`, waiting up to 10 s for at
+    /// least `count` of them.
+    pub async fn wait_for_events(&self, event_type: &str, count: usize) -> Vec<ReceivedEvent> {
+        let mut matching = Vec::new();
+        for _ in 0..40 {
+            matching = self
+                .received_events
+                .read()
+                .await
+                .iter()
+                .filter(|e| e.event_type == event_type)
+                .cloned()
+                .collect();
+            if matching.len() >= count {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+        matching
+    }
+}
+
+impl TlsAuthSmokeWorld for CalibratorDarksWorld {
+    const PROBE_PATH: &'static str = "/health";
+
+    fn tls_auth(&mut self) -> &mut TlsAuthState {
+        &mut self.tls_auth
+    }
+
+    fn base_test_config(&self) -> serde_json::Value {
+        // Never invoked — the smoke scenario only probes `/health`, so
+        // the archive directory need not exist.
+        serde_json::json!({
+            "camera_id": "main-cam",
+            "archive_directory": "/nonexistent/rusty-photon/images"
+        })
+    }
+
+    async fn start_with_tls_auth(&mut self, config: serde_json::Value) {
+        let handle = bdd_infra::tls_auth::spawn_service_handle(
+            &mut self.tls_auth,
+            env!("CARGO_PKG_NAME"),
+            &config,
+        )
+        .await;
+        self.calibrator_darks = Some(handle);
+    }
+}
@@ -0,0 +1,53 @@
+@serial
+Feature: Calibrator dark-library workflow (end-to-end)
+  The calibrator-darks orchestrator is a real service that connects to rp
+  as an MCP client. It scans rp's frame archive for the light-frame
+  readouts and exposures it must match, then walks the camera's
+  cooler_targets_c ladder: per rung it calls cool_camera and captures the
+  darks and biases that rung is still missing. A set already on disk is
+  not captured again.
+
+  These tests start all three processes (OmniSim, rp, calibrator-darks),
+  sharing one temp directory as rp's archive, with cooling tuned for test
+  speed. The simulator cooler reaches both -10 °C and 5 °C.
+
+  Background:
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to "cooler_stabilized"
+    And the calibrator-darks service is configured for 2 darks and 2 biases
+
+  Scenario: Each rung of the ladder gets its own darks and biases
+    Given rp is running with a camera with cooler targets "-10, 5" and the calibrator-darks orchestrator
+    And the archive holds a 200 ms light frame at the camera's current readout
+    When a session is started via the REST API
+    And the calibrator-darks orchestrator runs to completion
+    Then the session status should be "idle"
+    And the test webhook receiver should have received a "cooler_stabilized" event for -10 °C
+    And the test webhook receiver should have received a "cooler_stabilized" event for 5 °C
+    And the archive should hold 2 "Dark" frames at -10 °C
+    And the archive should hold 2 "Bias" frames at -10 °C
+    And the archive should hold 2 "Dark" frames at 5 °C
+    And the archive should hold 2 "Bias" frames at 5 °C
+
+  Scenario: A plan restricted to one rung leaves the others alone
+    Given the plan is restricted to the 5 °C rung
+    And rp is running with a camera with cooler targets "-10, 5" and the calibrator-darks orchestrator
+    And the archive holds a 200 ms light frame at the camera's current readout
+    When a session is started via the REST API
+    And the calibrator-darks orchestrator runs to completion
+    Then the session status should be "idle"
+    And the archive should hold 2 "Dark" frames at 5 °C
+    And the archive should hold 0 "Dark" frames at -10 °C
+    And the archive should hold 0 "Bias" frames at -10 °C
+
+  Scenario: A library already complete captures nothing
+    Given rp is running with a camera with cooler targets "-10, 5" and the calibrator-darks orchestrator
+    And the archive holds a 200 ms light frame at the camera's current readout
+    And the archive already holds 2 darks and 2 biases for it at each of "-10, 5" °C
+    When a session is started via the REST API
+    And the calibrator-darks orchestrator runs to completion
+    Then the session status should be "idle"
+    And the archive should hold 2 "Dark" frames at -10 °C
+    And the archive should hold 2 "Bias" frames at -10 °C
+    And the archive should hold 2 "Dark" frames at 5 °C
+    And the archive should hold 2 "Bias" frames at 5 °C
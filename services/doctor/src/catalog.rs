@@ -24,8 +24,9 @@ pub struct CatalogEntry {
     pub default_port: u16,
     /// The service hard-requires a hand-written config and never
     /// self-creates one (docs/packaging.md's "config-gated" services:
-    /// `calibrator-flats`, `plate-solver`, `polar-align`,
-    /// `session-runner`, `sky-survey-camera`). A
+    /// `calibrator-darks`, `calibrator-flats`, `frame-grader`,
+    /// `native-guider`, `plate-solver`, `polar-align`, `session-runner`,
+    /// `sky-survey-camera`). A
     /// `FileAbsent` scan is expected and unremarkable for these — the unit
     /// cannot start without an operator writing the file first, so it never
     /// serves plain HTTP the way a self-defaulting service would
@@ -55,6 +56,10 @@ impl CatalogEntry {
 
 /// The embedded `pkg/doctor.toml` files, alphabetical by service.
 static RAW: &[(&str, &str)] = &[
+    (
+        "calibrator-darks",
+        include_str!("../../calibrator-darks/pkg/doctor.toml"),
+    ),
     (
         "calibrator-flats",
         include_str!("../../calibrator-flats/pkg/doctor.toml"),
@@ -64,6 +69,14 @@ static RAW: &[(&str, &str)] = &[
         "filemonitor",
         include_str!("../../filemonitor/pkg/doctor.toml"),
     ),
+    (
+        "frame-grader",
+        include_str!("../../frame-grader/pkg/doctor.toml"),
+    ),
+    (
+        "native-guider",
+        include_str!("../../native-guider/pkg/doctor.toml"),
+    ),
     (
         "pa-falcon-rotator",
         include_str!("../../pa-falcon-rotator/pkg/doctor.toml"),
@@ -246,7 +259,7 @@ mod tests {
         assert_eq!(entry("qhy-focuser").unwrap().default_port, 11113);
     }
 
-    /// The five services with no sensible default config (docs/packaging.md
+    /// The eight services with no sensible default config (docs/packaging.md
     /// §Installing) declare `config_gated`; nothing else does. Drift here
     /// means `tls.absent`/`auth.absent` either wrongly nags a hard-gated
     /// service or wrongly stays silent about a self-defaulting one whose
@@ -254,7 +267,10 @@ mod tests {
     #[test]
     fn test_config_gated_matches_the_known_set() {
         const GATED: &[&str] = &[
+            "calibrator-darks",
             "calibrator-flats",
+            "frame-grader",
+            "native-guider",
             "plate-solver",
             "polar-align",
             "session-runner",
@@ -411,17 +411,18 @@ pub fn mint_credential(config_dir: &Path) -> Result<String, String> {
 /// `service_auth` / `ca_cert` field pair, and where in its config that
 /// pair lives. `prefix` is a JSON-pointer prefix — empty for the
 /// top-level shape (sentinel's probe client, the session-runner /
-/// calibrator-flats / polar-align MCP clients — ADR-017), `"/rp"` for
-/// planetarium-bridge, whose client block nests under its `rp` key
+/// calibrator-flats / calibrator-darks / polar-align / frame-grader MCP
+/// clients — ADR-017), `"/rp"` for planetarium-bridge, whose client block nests under its `rp` key
 /// (planetarium-bridge.md § Configuration).
 struct ClientWiring {
     service: &'static str,
     /// `false` for services with only a `ca_cert` setting — no
     /// `service_auth`, because they carry no
     /// shared-observatory-credential client role. `rp`'s outbound
-    /// Alpaca / plate-solver / guider clients trust the observatory CA
-    /// the same way, but device credentials are per-device `auth`
-    /// blocks, not the D6 shared credential (issue #609).
+    /// Alpaca / plate-solver / guider clients and native-guider's
+    /// camera / mount clients trust the observatory CA the same way,
+    /// but device credentials are per-device `auth` blocks, not the D6
+    /// shared credential (issue #609).
     wire_auth: bool,
     prefix: &'static str,
 }
@@ -442,11 +443,21 @@ const CLIENT_WIRING: &[ClientWiring] = &[
         wire_auth: true,
         prefix: "",
     },
+    ClientWiring {
+        service: "calibrator-darks",
+        wire_auth: true,
+        prefix: "",
+    },
     ClientWiring {
         service: "polar-align",
         wire_auth: true,
         prefix: "",
     },
+    ClientWiring {
+        service: "frame-grader",
+        wire_auth: true,
+        prefix: "",
+    },
     ClientWiring {
         service: "planetarium-bridge",
         wire_auth: true,
@@ -457,6 +468,11 @@ const CLIENT_WIRING: &[ClientWiring] = &[
         wire_auth: false,
         prefix: "",
     },
+    ClientWiring {
+        service: "native-guider",
+        wire_auth: false,
+        prefix: "",
+    },
 ];
 
 /// The client-block wiring `--fix` distributes into each client service's
@@ -0,0 +1,326 @@
+//! Rolling per-target/filter baselines and the relative verdict.
+//!
+//! Absolute thresholds (`max_hfr_pixels`, `min_star_count`, …) live in
+//! rp's target config and are applied by rp itself when it reads the
+//! grading section. What they cannot express is "worse than this
+//! target's frames have been tonight": thin cloud halves the star count
+//! of a rich field and still leaves hundreds, dew swells HFR by a third
+//! and still stays under a generous absolute limit. A [`Baselines`]
+//! store keeps the last `window` frames of every (target, filter,
+//! exposure) and judges each new frame against their medians.
+//!
+//! Every frame joins its baseline, rejected or not. Medians shrug off a
+//! passing cloud, and a lasting change (the moon rising) is adopted
+//! once it fills half the window instead of rejecting the rest of the
+//! night. The store lives in memory: a restart re-learns each baseline
+//! over its first `min_frames` frames.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::config::BaselineConfig;
+
+/// What one light frame measured.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameMetrics {
+    /// Median star HFR in pixels; `None` when no stars were detected.
+    pub hfr: Option<f64>,
+    /// Median star FWHM in pixels; `None` when no PSF fit converged.
+    pub fwhm: Option<f64>,
+    pub star_count: u32,
+    /// Median PSF eccentricity; `None` when no PSF fit converged.
+    pub eccentricity: Option<f64>,
+    /// Median per-star SNR; `None` when no stars were detected.
+    pub snr: Option<f64>,
+    /// Sigma-clipped sky background level (ADU).
+    pub background: f64,
+    /// Sigma-clipped sky background noise (ADU).
+    pub background_stddev: f64,
+}
+
+/// Which frames share a baseline. Exposure is part of the key because
+/// star count, SNR and background all scale with it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BaselineKey {
+    pub target: String,
+    pub filter: Option<String>,
+    pub exposure: Option<Duration>,
+}
+
+/// The medians a frame was judged against.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Reference {
+    /// Frames the medians were taken over.
+    pub frames: usize,
+    pub hfr: Option<f64>,
+    pub star_count: f64,
+    pub snr: Option<f64>,
+    pub background: f64,
+}
+
+/// A metric that degraded past its baseline ratio.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reason {
+    Hfr,
+    StarCount,
+    Snr,
+    Background,
+}
+
+/// The relative verdict on one frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Verdict {
+    /// `None` while the baseline is still below `min_frames`.
+    pub reference: Option<Reference>,
+    /// Every metric that degraded past its ratio; empty means accepted.
+    pub reasons: Vec<Reason>,
+    /// Stars or SNR were lost while HFR held: a transparency loss
+    /// (cloud, haze) rather than focus drift or dew, which swell stars.
+    pub cloud_suspected: bool,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn rejected(&self) -> bool {
+        !self.reasons.is_empty()
+    }
+}
+
+/// Every baseline this process has seen.
+#[derive(Debug, Default)]
+pub struct Baselines {
+    frames: HashMap<BaselineKey, VecDeque<FrameMetrics>>,
+}
+
+impl Baselines {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Judge `metrics` against its baseline as it stood, then add the
+    /// frame to it.
+    pub fn judge(
+        &mut self,
+        key: BaselineKey,
+        metrics: &FrameMetrics,
+        config: &BaselineConfig,
+    ) -> Verdict {
+        let history = self.frames.entry(key).or_default();
+        let verdict = match reference(history, config.min_frames) {
+            Some(reference) => compare(metrics, reference, config),
+            None => Verdict {
+                reference: None,
+                reasons: Vec::new(),
+                cloud_suspected: false,
+            },
+        };
+        history.push_back(metrics.clone());
+        while history.len() > config.window {
+            history.pop_front();
+        }
+        verdict
+    }
+}
+
+fn reference(history: &VecDeque<FrameMetrics>, min_frames: usize) -> Option<Reference> {
+    if history.len() < min_frames {
+        return None;
+    }
+    Some(Reference {
+        frames: history.len(),
+        hfr: median(history.iter().filter_map(|m| m.hfr)),
+        star_count: median(history.iter().map(|m| f64::from(m.star_count))).unwrap_or(0.0),
+        snr: median(history.iter().filter_map(|m| m.snr)),
+        background: median(history.iter().map(|m| m.background)).unwrap_or(0.0),
+    })
+}
+
+fn compare(metrics: &FrameMetrics, reference: Reference, config: &BaselineConfig) -> Verdict {
+    let mut reasons = Vec::new();
+    if let (Some(hfr), Some(base)) = (metrics.hfr, reference.hfr) {
+        if base > 0.0 && hfr > base * config.max_hfr_ratio {
+            reasons.push(Reason::Hfr);
+        }
+    }
+    if f64::from(metrics.star_count) < reference.star_count * config.min_star_count_ratio {
+        reasons.push(Reason::StarCount);
+    }
+    if let (Some(snr), Some(base)) = (metrics.snr, reference.snr) {
+        if snr < base * config.min_snr_ratio {
+            reasons.push(Reason::Snr);
+        }
+    }
+    if reference.background > 0.0
+        && metrics.background > reference.background * config.max_background_ratio
+    {
+        reasons.push(Reason::Background);
+    }
+    let lost_light = reasons.contains(&Reason::StarCount) || reasons.contains(&Reason::Snr);
+    let cloud_suspected = lost_light && !reasons.contains(&Reason::Hfr);
+    Verdict {
+        reference: Some(reference),
+        reasons,
+        cloud_suspected,
+    }
+}
+
+pub(crate) fn median(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let mut values: Vec<f64> = values.filter(|v| v.is_finite()).collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    } else {
+        Some(values[mid])
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn key(filter: &str) -> BaselineKey {
+        BaselineKey {
+            target: "m31".to_string(),
+            filter: Some(filter.to_string()),
+            exposure: Some(Duration::from_secs(300)),
+        }
+    }
+
+    fn good() -> FrameMetrics {
+        FrameMetrics {
+            hfr: Some(2.0),
+            fwhm: Some(3.1),
+            star_count: 400,
+            eccentricity: Some(0.4),
+            snr: Some(40.0),
+            background: 1000.0,
+            background_stddev: 12.0,
+        }
+    }
+
+    fn config() -> BaselineConfig {
+        BaselineConfig {
+            window: 6,
+            min_frames: 3,
+            ..BaselineConfig::default()
+        }
+    }
+
+    fn warmed(filter: &str) -> Baselines {
+        let mut baselines = Baselines::new();
+        for _ in 0..3 {
+            baselines.judge(key(filter), &good(), &config());
+        }
+        baselines
+    }
+
+    #[test]
+    fn frames_before_min_frames_are_not_judged() {
+        let mut baselines = Baselines::new();
+        let awful = FrameMetrics {
+            star_count: 3,
+            ..good()
+        };
+        for _ in 0..3 {
+            let verdict = baselines.judge(key("Ha"), &awful, &config());
+            assert_eq!(verdict.reference, None);
+            assert!(!verdict.rejected());
+        }
+        // The fourth frame has a baseline to be judged against.
+        let verdict = baselines.judge(key("Ha"), &good(), &config());
+        assert_eq!(verdict.reference.unwrap().frames, 3);
+    }
+
+    #[test]
+    fn a_frame_like_its_baseline_is_accepted() {
+        let verdict = warmed("L").judge(key("L"), &good(), &config());
+        assert!(!verdict.rejected());
+        assert!(!verdict.cloud_suspected);
+        assert_eq!(
+            verdict.reference,
+            Some(Reference {
+                frames: 3,
+                hfr: Some(2.0),
+                star_count: 400.0,
+                snr: Some(40.0),
+                background: 1000.0,
+            })
+        );
+    }
+
+    #[test]
+    fn swollen_stars_are_rejected_without_suspecting_cloud() {
+        // Dew: stars swell and the faint ones drown.
+        let dewed = FrameMetrics {
+            hfr: Some(2.8),
+            star_count: 200,
+            ..good()
+        };
+        let verdict = warmed("L").judge(key("L"), &dewed, &config());
+        assert_eq!(verdict.reasons, vec![Reason::Hfr, Reason::StarCount]);
+        assert!(!verdict.cloud_suspected);
+    }
+
+    #[test]
+    fn lost_stars_at_steady_hfr_suspect_cloud() {
+        let thin_cloud = FrameMetrics {
+            star_count: 180,
+            snr: Some(22.0),
+            background: 1700.0,
+            ..good()
+        };
+        let verdict = warmed("L").judge(key("L"), &thin_cloud, &config());
+        assert!(verdict.rejected());
+        assert_eq!(
+            verdict.reasons,
+            vec![Reason::StarCount, Reason::Snr, Reason::Background]
+        );
+        assert!(verdict.cloud_suspected);
+    }
+
+    #[test]
+    fn baselines_are_kept_per_filter() {
+        let mut baselines = warmed("L");
+        // A narrowband frame sees a fraction of the broadband stars; it
+        // starts a baseline of its own instead of failing against L.
+        let ha = FrameMetrics {
+            star_count: 90,
+            background: 300.0,
+            ..good()
+        };
+        let verdict = baselines.judge(key("Ha"), &ha, &config());
+        assert_eq!(verdict.reference, None);
+    }
+
+    #[test]
+    fn a_lasting_change_is_adopted_within_the_window() {
+        let mut baselines = warmed("L");
+        let moonlit = FrameMetrics {
+            background: 2000.0,
+            ..good()
+        };
+        let verdicts: Vec<bool> = (0..6)
+            .map(|_| baselines.judge(key("L"), &moonlit, &config()).rejected())
+            .collect();
+        // Rejected while the old sky holds the median, accepted once
+        // the new one does.
+        assert_eq!(verdicts, vec![true, true, true, false, false, false]);
+    }
+
+    #[test]
+    fn median_ignores_non_finite_values_and_averages_even_counts() {
+        assert_eq!(median([3.0, 1.0, 2.0].into_iter()), Some(2.0));
+        assert_eq!(median([4.0, 1.0, 3.0, 2.0].into_iter()), Some(2.5));
+        assert_eq!(median([f64::NAN, 5.0].into_iter()), Some(5.0));
+        assert_eq!(median(std::iter::empty()), None);
+    }
+}
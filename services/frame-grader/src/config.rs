@@ -0,0 +1,398 @@
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+pub use rusty_photon_server_config::ServerConfig;
+
+use crate::error::{FrameGraderError, Result};
+
+/// frame-grader's config file: where `rp` is, how to detect stars on
+/// this rig's frames, and how far a frame may fall behind its baseline.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GraderConfig {
+    /// The HTTP server for `/webhook` and `/health`. Config files
+    /// without a `server` block keep loading via the default.
+    #[serde(default = "default_server")]
+    pub server: ServerConfig,
+    /// rp's MCP endpoint. The analysis tools are called here; the
+    /// document fetch and the completion POST go to the same host with
+    /// `/mcp` stripped.
+    #[serde(default = "default_mcp_server_url")]
+    pub mcp_server_url: String,
+    /// HTTP Basic credentials presented to `rp` — MCP calls, the
+    /// document fetch and the completion POST alike. The D6 observatory
+    /// credential; doctor `--fix` wires it (ADR-017).
+    #[serde(default)]
+    pub service_auth: Option<rp_mcp_client::ClientAuthConfig>,
+    /// PEM CA path used to trust a TLS-enabled `rp`. Per the ADR-017
+    /// policy, `service_auth` is only sent when this is set and the URL
+    /// is https.
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    /// Minimum star component area in pixels, passed to `measure_stars`
+    /// and `compute_snr`. No default: it depends on the rig's sampling.
+    pub min_area: usize,
+    /// Maximum star component area in pixels. No default, as `min_area`.
+    pub max_area: usize,
+    /// Detection threshold in background sigmas (default 5.0, the
+    /// tools' own default).
+    #[serde(default = "default_threshold_sigma")]
+    pub threshold_sigma: f64,
+    /// The rolling baseline each light frame is judged against.
+    #[serde(default)]
+    pub baseline: BaselineConfig,
+    /// `max_duration` reported in every webhook acknowledgment
+    /// (humantime, default `"1m"`); the estimate is half of it. Grading
+    /// reads the frame twice through rp's image cache, so a minute is
+    /// generous even for a large sensor.
+    #[serde(default = "default_max_duration", with = "humantime_serde")]
+    pub max_duration: Duration,
+}
+
+/// Rolling-baseline settings. A light frame is compared with the median
+/// of the last `window` frames sharing its target, filter and exposure;
+/// each ratio bounds how far one metric may degrade against it.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BaselineConfig {
+    /// Frames kept per baseline (default 20).
+    #[serde(default = "default_window")]
+    pub window: usize,
+    /// Frames a baseline needs before it judges anything (default 5).
+    /// Until then frames are graded on absolute metrics only.
+    #[serde(default = "default_min_frames")]
+    pub min_frames: usize,
+    /// Reject when HFR exceeds the baseline by this factor (default
+    /// 1.25) — focus drift, dew on the optics, seeing collapse.
+    #[serde(default = "default_max_hfr_ratio")]
+    pub max_hfr_ratio: f64,
+    /// Reject when the star count falls below this fraction of the
+    /// baseline (default 0.6) — cloud, dew, haze.
+    #[serde(default = "default_min_star_count_ratio")]
+    pub min_star_count_ratio: f64,
+    /// Reject when the median star SNR falls below this fraction of the
+    /// baseline (default 0.7) — thin cloud, rising sky brightness.
+    #[serde(default = "default_min_snr_ratio")]
+    pub min_snr_ratio: f64,
+    /// Reject when the sky background exceeds the baseline by this
+    /// factor (default 1.5) — moonlit or light-polluted cloud.
+    #[serde(default = "default_max_background_ratio")]
+    pub max_background_ratio: f64,
+}
+
+impl Default for BaselineConfig {
+    fn default() -> Self {
+        Self {
+            window: default_window(),
+            min_frames: default_min_frames(),
+            max_hfr_ratio: default_max_hfr_ratio(),
+            min_star_count_ratio: default_min_star_count_ratio(),
+            min_snr_ratio: default_min_snr_ratio(),
+            max_background_ratio: default_max_background_ratio(),
+        }
+    }
+}
+
+impl GraderConfig {
+    #[must_use]
+    pub const fn rp_auth(&self) -> Option<&rp_mcp_client::ClientAuthConfig> {
+        self.service_auth.as_ref()
+    }
+
+    pub fn rp_ca(&self) -> Option<&Path> {
+        self.ca_cert.as_deref().map(Path::new)
+    }
+
+    /// rp's REST base URL: `mcp_server_url` without its `/mcp` suffix.
+    #[must_use]
+    pub fn rp_base_url(&self) -> &str {
+        self.mcp_server_url
+            .trim_end_matches('/')
+            .trim_end_matches("/mcp")
+    }
+
+    /// Reject settings that would make every frame pass or every frame
+    /// fail, naming the offending field.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| Err(FrameGraderError::Config(msg.to_string()));
+        if self.min_area == 0 || self.min_area > self.max_area {
+            return invalid("min_area must be >= 1 and <= max_area");
+        }
+        if !self.threshold_sigma.is_finite() || self.threshold_sigma <= 0.0 {
+            return invalid("threshold_sigma must be > 0");
+        }
+        let b = &self.baseline;
+        if b.min_frames == 0 || b.window < b.min_frames {
+            return invalid("baseline.min_frames must be >= 1 and <= baseline.window");
+        }
+        let positive = |v: f64| v.is_finite() && v > 0.0;
+        if !positive(b.max_hfr_ratio) || b.max_hfr_ratio < 1.0 {
+            return invalid("baseline.max_hfr_ratio must be >= 1");
+        }
+        if !positive(b.max_background_ratio) || b.max_background_ratio < 1.0 {
+            return invalid("baseline.max_background_ratio must be >= 1");
+        }
+        if !positive(b.min_star_count_ratio) || b.min_star_count_ratio > 1.0 {
+            return invalid("baseline.min_star_count_ratio must be in (0, 1]");
+        }
+        if !positive(b.min_snr_ratio) || b.min_snr_ratio > 1.0 {
+            return invalid("baseline.min_snr_ratio must be in (0, 1]");
+        }
+        Ok(())
+    }
+}
+
+/// frame-grader's default `server` block when the config omits it:
+/// port 11174 on all interfaces, plain HTTP.
+pub(crate) fn default_server() -> ServerConfig {
+    ServerConfig::new(11174)
+}
+
+fn default_mcp_server_url() -> String {
+    "http://127.0.0.1:11115/mcp".to_string()
+}
+
+/// CLI overrides layered over the file config after load: `--port` and
+/// `--bind-address` pin `server.port` / `server.bind_address` over whatever
+/// the file (or the `default_server()` fallback) supplied.
+#[derive(Debug, Clone, Default)]
+pub struct CliOverrides {
+    /// `--port` → `server.port`.
+    pub port: Option<u16>,
+    /// `--bind-address` → `server.bind_address`.
+    pub bind_address: Option<IpAddr>,
+}
+
+impl CliOverrides {
+    /// Apply the overrides onto `config` in place.
+    pub const fn apply(&self, config: &mut GraderConfig) {
+        if let Some(port) = self.port {
+            config.server.port = port;
+        }
+        if let Some(bind_address) = self.bind_address {
+            config.server.bind_address = bind_address;
+        }
+    }
+}
+
+const fn default_threshold_sigma() -> f64 {
+    5.0
+}
+
+const fn default_window() -> usize {
+    20
+}
+
+const fn default_min_frames() -> usize {
+    5
+}
+
+const fn default_max_hfr_ratio() -> f64 {
+    1.25
+}
+
+const fn default_min_star_count_ratio() -> f64 {
+    0.6
+}
+
+const fn default_min_snr_ratio() -> f64 {
+    0.7
+}
+
+const fn default_max_background_ratio() -> f64 {
+    1.5
+}
+
+const fn default_max_duration() -> Duration {
+    Duration::from_mins(1)
+}
+
+pub fn load_config(path: &Path) -> Result<GraderConfig> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        FrameGraderError::Config(format!(
+            "failed to read config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let config: GraderConfig = serde_json::from_str(&contents).map_err(|e| {
+        FrameGraderError::Config(format!(
+            "failed to parse config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"{"min_area": 5, "max_area": 400}"#;
+
+    #[test]
+    fn deserialize_config_with_defaults() {
+        let config: GraderConfig = serde_json::from_str(MINIMAL).unwrap();
+        assert_eq!(config.mcp_server_url, "http://127.0.0.1:11115/mcp");
+        assert_eq!(config.rp_base_url(), "http://127.0.0.1:11115");
+        assert_eq!((config.min_area, config.max_area), (5, 400));
+        assert!((config.threshold_sigma - 5.0).abs() < f64::EPSILON);
+        assert_eq!(config.baseline, BaselineConfig::default());
+        assert_eq!(config.baseline.window, 20);
+        assert_eq!(config.baseline.min_frames, 5);
+        assert_eq!(config.max_duration, Duration::from_mins(1));
+        assert!(config.service_auth.is_none());
+        assert!(config.rp_ca().is_none());
+        // A config without a `server` block keeps loading via the default.
+        assert_eq!(config.server.port, 11174);
+        assert_eq!(config.server.bind_address.to_string(), "0.0.0.0");
+        assert!(config.server.tls.is_none());
+        assert!(config.server.auth.is_none());
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn deserialize_config_with_overrides() {
+        let json = r#"{
+            "server": { "port": 12000, "bind_address": "127.0.0.1" },
+            "mcp_server_url": "https://rp.local:11115/mcp/",
+            "ca_cert": "/etc/rusty-photon/ca.pem",
+            "min_area": 9,
+            "max_area": 900,
+            "threshold_sigma": 4.0,
+            "baseline": { "window": 10, "min_frames": 3, "max_hfr_ratio": 1.4 },
+            "max_duration": "2m"
+        }"#;
+        let config: GraderConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.server.socket_addr().to_string(), "127.0.0.1:12000");
+        assert_eq!(config.rp_base_url(), "https://rp.local:11115");
+        assert_eq!(config.rp_ca(), Some(Path::new("/etc/rusty-photon/ca.pem")));
+        assert_eq!(config.baseline.window, 10);
+        assert_eq!(config.baseline.min_frames, 3);
+        assert!((config.baseline.max_hfr_ratio - 1.4).abs() < f64::EPSILON);
+        // Unset baseline fields keep their defaults.
+        assert!((config.baseline.min_snr_ratio - 0.7).abs() < f64::EPSILON);
+        assert_eq!(config.max_duration, Duration::from_mins(2));
+    }
+
+    #[test]
+    fn detection_areas_are_required() {
+        let err = serde_json::from_str::<GraderConfig>(r#"{"min_area": 5}"#).unwrap_err();
+        assert!(err.to_string().contains("max_area"), "{err}");
+    }
+
+    #[test]
+    fn validate_names_the_offending_setting() {
+        let cases = [
+            (r#"{"min_area": 50, "max_area": 40}"#, "min_area"),
+            (
+                r#"{"min_area": 5, "max_area": 400, "threshold_sigma": 0}"#,
+                "threshold_sigma",
+            ),
+            (
+                r#"{"min_area": 5, "max_area": 400, "baseline": {"window": 3}}"#,
+                "baseline.min_frames",
+            ),
+            (
+                r#"{"min_area": 5, "max_area": 400, "baseline": {"max_hfr_ratio": 0.9}}"#,
+                "baseline.max_hfr_ratio",
+            ),
+            (
+                r#"{"min_area": 5, "max_area": 400, "baseline": {"min_star_count_ratio": 1.5}}"#,
+                "baseline.min_star_count_ratio",
+            ),
+            (
+                r#"{"min_area": 5, "max_area": 400, "baseline": {"min_snr_ratio": 0}}"#,
+                "baseline.min_snr_ratio",
+            ),
+            (
+                r#"{"min_area": 5, "max_area": 400, "baseline": {"max_background_ratio": 0.5}}"#,
+                "baseline.max_background_ratio",
+            ),
+        ];
+        for (json, field) in cases {
+            let config: GraderConfig = serde_json::from_str(json).unwrap();
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{json}: {err}");
+        }
+    }
+
+    #[test]
+    fn cli_overrides_pin_port_and_bind_address() {
+        let mut config: GraderConfig = serde_json::from_str(MINIMAL).unwrap();
+        let overrides = CliOverrides {
+            port: Some(12345),
+            bind_address: Some("127.0.0.1".parse().unwrap()),
+        };
+        overrides.apply(&mut config);
+        assert_eq!(config.server.socket_addr().to_string(), "127.0.0.1:12345");
+    }
+
+    #[test]
+    fn load_config_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frame-grader.json");
+        std::fs::write(&path, MINIMAL).unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.min_area, 5);
+    }
+
+    #[test]
+    fn load_config_runs_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frame-grader.json");
+        std::fs::write(&path, r#"{"min_area": 0, "max_area": 400}"#).unwrap();
+        let err = load_config(&path).unwrap_err();
+        assert!(err.to_string().contains("min_area"), "{err}");
+    }
+
+    #[test]
+    fn load_config_missing_file() {
+        let err = load_config(Path::new("/nonexistent/frame-grader.json")).unwrap_err();
+        assert!(err.to_string().contains("failed to read config file"));
+    }
+
+    #[test]
+    fn load_config_invalid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("frame-grader.json");
+        std::fs::write(&path, "not valid json").unwrap();
+        let err = load_config(&path).unwrap_err();
+        assert!(err.to_string().contains("failed to parse config file"));
+    }
+
+    #[test]
+    fn config_rejects_unknown_field() {
+        let json = r#"{"min_area": 5, "max_area": 400, "max_hfr": 3.0}"#;
+        let err = serde_json::from_str::<GraderConfig>(json).unwrap_err();
+        assert!(err.to_string().contains("max_hfr"), "{err}");
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod doctor_toml_parity {
+    use rusty_photon_server_config::doctor_toml::{parse, ServerClass};
+
+    use super::default_server;
+
+    /// `pkg/doctor.toml` is this service's catalog entry for
+    /// `rusty-photon-doctor` and must match the config defaults
+    /// (docs/services/doctor.md §The derived catalog).
+    #[test]
+    fn pkg_doctor_toml_matches_config_defaults() {
+        let meta = parse(include_str!("../pkg/doctor.toml")).unwrap();
+        assert_eq!(meta.port, default_server().port);
+        assert_eq!(meta.class, ServerClass::Core);
+        assert!(
+            meta.config_gated,
+            "frame-grader needs the rig's star detection areas"
+        );
+    }
+}
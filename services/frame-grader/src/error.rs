@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, FrameGraderError>;
+
+#[derive(Debug, Error)]
+pub enum FrameGraderError {
+    #[error("config error: {0}")]
+    Config(String),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("MCP tool call failed: {0}")]
+    ToolCall(String),
+
+    #[error("rp request failed: {0}")]
+    Rp(String),
+
+    #[error("server error: {0}")]
+    Server(String),
+}
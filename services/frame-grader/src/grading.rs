@@ -0,0 +1,180 @@
+//! Grading one exposure: fetch its document, run rp's analysis tools on
+//! a light frame, judge the metrics against the frame's baseline, and
+//! shape the `grading` section rp stores on the document.
+//!
+//! rp's progress scan reads `hfr`, `star_count`, `eccentricity` and
+//! `snr` from the section against the target's absolute thresholds,
+//! plus `rejected` — the baseline verdict — on its own (rp.md
+//! § Grading Thresholds). The remaining keys are for the operator.
+
+use serde_json::Value;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::baseline::{median, Baselines, FrameMetrics, Verdict};
+use crate::config::GraderConfig;
+use crate::error::Result;
+use crate::mcp_client::{BackgroundResult, Detection, McpClient, MeasureStarsResult, SnrResult};
+use crate::rp_api;
+
+/// Grade `document_id`. `Ok(None)` when the frame is not a target's
+/// light frame and so carries no grading section.
+pub async fn grade(
+    config: &GraderConfig,
+    baselines: &Mutex<Baselines>,
+    document_id: &str,
+) -> Result<Option<Value>> {
+    let document = rp_api::fetch_document(config, document_id).await?;
+    let Some(key) = document.baseline_key() else {
+        debug!(document_id, "not a target light frame; not graded");
+        return Ok(None);
+    };
+
+    let mcp = McpClient::new(&config.mcp_server_url, config.rp_auth(), config.rp_ca()).await?;
+    let detection = Detection {
+        threshold_sigma: config.threshold_sigma,
+        min_area: config.min_area,
+        max_area: config.max_area,
+    };
+    let stars = mcp.measure_stars(document_id, detection).await?;
+    let snr = mcp.compute_snr(document_id, detection).await?;
+    let background = mcp.estimate_background(document_id).await?;
+    let metrics = frame_metrics(&stars, &snr, &background);
+
+    let verdict = baselines
+        .lock()
+        .await
+        .judge(key, &metrics, &config.baseline);
+    debug!(
+        document_id,
+        rejected = verdict.rejected(),
+        reasons = ?verdict.reasons,
+        "baseline verdict"
+    );
+    Ok(Some(grading_section(&metrics, &verdict)))
+}
+
+#[must_use]
+pub fn frame_metrics(
+    stars: &MeasureStarsResult,
+    snr: &SnrResult,
+    background: &BackgroundResult,
+) -> FrameMetrics {
+    FrameMetrics {
+        hfr: stars.median_hfr,
+        fwhm: stars.median_fwhm,
+        star_count: stars.star_count,
+        eccentricity: median(stars.stars.iter().filter_map(|s| s.eccentricity)),
+        snr: snr.snr,
+        background: background.median,
+        background_stddev: background.stddev,
+    }
+}
+
+/// The `grading` section for one graded frame.
+#[must_use]
+pub fn grading_section(metrics: &FrameMetrics, verdict: &Verdict) -> Value {
+    let mut section = serde_json::json!({
+        "hfr": metrics.hfr,
+        "fwhm": metrics.fwhm,
+        "star_count": metrics.star_count,
+        "eccentricity": metrics.eccentricity,
+        "snr": metrics.snr,
+        "background": metrics.background,
+        "background_stddev": metrics.background_stddev,
+        "cloud_suspected": verdict.cloud_suspected,
+        "rejected": verdict.rejected(),
+        "reasons": verdict.reasons,
+    });
+    if let Some(reference) = &verdict.reference {
+        section["baseline"] = serde_json::json!(reference);
+    }
+    section
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::baseline::{Reason, Reference};
+
+    fn measured() -> (MeasureStarsResult, SnrResult, BackgroundResult) {
+        let stars = serde_json::from_value(serde_json::json!({
+            "stars": [
+                {"x": 1.0, "y": 1.0, "hfr": 2.0, "fwhm": 3.0, "eccentricity": 0.3, "flux": 1.0},
+                {"x": 9.0, "y": 9.0, "hfr": 2.2, "fwhm": null, "eccentricity": null, "flux": 1.0},
+                {"x": 5.0, "y": 5.0, "hfr": 2.4, "fwhm": 3.4, "eccentricity": 0.5, "flux": 1.0}
+            ],
+            "star_count": 3,
+            "median_fwhm": 3.2,
+            "median_hfr": 2.2,
+            "background_mean": 1010.0,
+            "background_stddev": 15.0
+        }))
+        .unwrap();
+        let snr = serde_json::from_value(serde_json::json!({
+            "snr": 41.5, "signal": 9000.0, "noise": 216.8, "star_count": 3,
+            "background_mean": 1010.0, "background_stddev": 15.0
+        }))
+        .unwrap();
+        let background = serde_json::from_value(serde_json::json!({
+            "mean": 1004.0, "stddev": 12.5, "median": 1001.0, "pixel_count": 64
+        }))
+        .unwrap();
+        (stars, snr, background)
+    }
+
+    #[test]
+    fn metrics_come_from_the_three_tools() {
+        let (stars, snr, background) = measured();
+        let metrics = frame_metrics(&stars, &snr, &background);
+        assert_eq!(metrics.hfr, Some(2.2));
+        assert_eq!(metrics.fwhm, Some(3.2));
+        assert_eq!(metrics.star_count, 3);
+        // Median over the stars whose fit converged.
+        assert_eq!(metrics.eccentricity, Some(0.4));
+        assert_eq!(metrics.snr, Some(41.5));
+        assert_eq!(metrics.background, 1001.0);
+        assert_eq!(metrics.background_stddev, 12.5);
+    }
+
+    #[test]
+    fn section_carries_metrics_and_verdict() {
+        let (stars, snr, background) = measured();
+        let metrics = frame_metrics(&stars, &snr, &background);
+        let verdict = Verdict {
+            reference: Some(Reference {
+                frames: 8,
+                hfr: Some(2.1),
+                star_count: 6.0,
+                snr: Some(60.0),
+                background: 990.0,
+            }),
+            reasons: vec![Reason::StarCount, Reason::Snr],
+            cloud_suspected: true,
+        };
+        let section = grading_section(&metrics, &verdict);
+        assert_eq!(section["hfr"], 2.2);
+        assert_eq!(section["star_count"], 3);
+        assert_eq!(section["eccentricity"], 0.4);
+        assert_eq!(section["snr"], 41.5);
+        assert_eq!(section["rejected"], true);
+        assert_eq!(section["cloud_suspected"], true);
+        assert_eq!(section["reasons"], serde_json::json!(["star_count", "snr"]));
+        assert_eq!(section["baseline"]["frames"], 8);
+    }
+
+    #[test]
+    fn section_before_a_baseline_exists_is_not_rejected() {
+        let (stars, snr, background) = measured();
+        let metrics = frame_metrics(&stars, &snr, &background);
+        let verdict = Verdict {
+            reference: None,
+            reasons: Vec::new(),
+            cloud_suspected: false,
+        };
+        let section = grading_section(&metrics, &verdict);
+        assert_eq!(section["rejected"], false);
+        assert!(section.get("baseline").is_none());
+    }
+}
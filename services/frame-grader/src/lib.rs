@@ -0,0 +1,119 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod baseline;
+pub mod config;
+pub mod doctor;
+pub mod error;
+pub mod grading;
+pub mod mcp_client;
+pub mod routes;
+pub mod rp_api;
+
+use std::future::Future;
+use std::net::SocketAddr;
+
+use tracing::{debug, info};
+
+use crate::config::GraderConfig;
+use crate::error::Result;
+
+/// Builder for the frame-grader server.
+pub struct ServerBuilder {
+    config: Option<GraderConfig>,
+}
+
+impl ServerBuilder {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { config: None }
+    }
+
+    #[must_use]
+    pub fn with_config(mut self, config: GraderConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    pub async fn build(self) -> Result<BoundServer> {
+        let config = self.config.ok_or_else(|| {
+            crate::error::FrameGraderError::Config(
+                "ServerBuilder::build: config is required \u{2014} call .with_config(...) first"
+                    .to_string(),
+            )
+        })?;
+        let server = config.server.clone();
+
+        let router = routes::build_router(config);
+
+        // Layer HTTP Basic Auth when configured (server.auth).
+        let router = match &server.auth {
+            Some(auth) => {
+                if server.tls.is_none() {
+                    tracing::warn!(
+                        "Authentication is enabled but TLS is not. Credentials will be \
+                         transmitted in cleartext. Consider enabling TLS (see `doctor --fix`)."
+                    );
+                }
+                rp_auth::layer(router, auth)
+            }
+            None => router,
+        };
+
+        let listener = tokio::net::TcpListener::bind(server.socket_addr()).await?;
+        let local_addr = listener.local_addr()?;
+
+        // This println is parsed by BDD tests to discover the bound port.
+        // Console mode only: stdout is a dead handle under the Windows SCM,
+        // and the only stdout consumer (bdd-infra's port parser) never runs
+        // services with --service.
+        if !rusty_photon_service_lifecycle::is_scm_service() {
+            println!("Bound frame-grader server bound_addr={local_addr}");
+        }
+        info!("frame-grader service bound on {}", local_addr);
+
+        Ok(BoundServer {
+            listener,
+            router,
+            local_addr,
+            tls: server.tls,
+        })
+    }
+}
+
+impl Default for ServerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fully bound frame-grader server ready to accept connections.
+pub struct BoundServer {
+    listener: tokio::net::TcpListener,
+    router: axum::Router,
+    local_addr: SocketAddr,
+    tls: Option<rusty_photon_tls::config::TlsConfig>,
+}
+
+impl BoundServer {
+    pub const fn listen_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub async fn start(self, shutdown: impl Future<Output = ()> + Send + 'static) -> Result<()> {
+        info!("frame-grader service started on {}", self.local_addr);
+
+        match self.tls {
+            Some(ref tls) => {
+                rusty_photon_tls::server::serve_tls(self.listener, self.router, tls, shutdown)
+                    .await
+                    .map_err(|e| crate::error::FrameGraderError::Server(e.to_string()))?;
+            }
+            None => axum::serve(self.listener, self.router)
+                .with_graceful_shutdown(shutdown)
+                .await
+                .map_err(|e| crate::error::FrameGraderError::Server(e.to_string()))?,
+        }
+
+        debug!("frame-grader service shut down");
+        Ok(())
+    }
+}
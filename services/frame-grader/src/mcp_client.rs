@@ -0,0 +1,137 @@
+//! MCP client for calling rp's built-in analysis tools, built on the
+//! standard `rp-mcp-client` crate (ADR-017): CA-pinned TLS and the
+//! observatory credential over verified HTTPS only.
+
+use std::path::Path;
+
+use rp_mcp_client::{ClientAuthConfig, RpMcpClient};
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::debug;
+
+use crate::error::{FrameGraderError, Result};
+
+/// MCP client for one `rp`.
+pub struct McpClient {
+    inner: RpMcpClient,
+}
+
+/// Star detection settings shared by `measure_stars` and `compute_snr`.
+#[derive(Debug, Clone, Copy)]
+pub struct Detection {
+    pub threshold_sigma: f64,
+    pub min_area: usize,
+    pub max_area: usize,
+}
+
+/// One star from the `measure_stars` tool; only the fields grading uses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeasuredStar {
+    /// `null` when the star's Gaussian fit failed.
+    #[serde(default)]
+    pub eccentricity: Option<f64>,
+}
+
+/// Result from the `measure_stars` tool.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MeasureStarsResult {
+    #[serde(default)]
+    pub stars: Vec<MeasuredStar>,
+    pub star_count: u32,
+    /// `null` when no stars were detected.
+    #[serde(default)]
+    pub median_hfr: Option<f64>,
+    /// `null` when no Gaussian fit converged.
+    #[serde(default)]
+    pub median_fwhm: Option<f64>,
+}
+
+/// Result from the `compute_snr` tool.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SnrResult {
+    /// Median per-star SNR; `null` when no stars were detected.
+    #[serde(default)]
+    pub snr: Option<f64>,
+}
+
+/// Result from the `estimate_background` tool (sigma-clipped).
+#[derive(Debug, Clone, Deserialize)]
+pub struct BackgroundResult {
+    pub mean: f64,
+    pub stddev: f64,
+    pub median: f64,
+}
+
+impl McpClient {
+    /// Connect to an MCP server at the given URL, presenting
+    /// `service_auth` per the ADR-017 credential policy.
+    pub async fn new(
+        mcp_url: &str,
+        service_auth: Option<&ClientAuthConfig>,
+        ca_cert: Option<&Path>,
+    ) -> Result<Self> {
+        debug!(url = %mcp_url, "connecting MCP client");
+        let inner = RpMcpClient::connect(mcp_url, service_auth, ca_cert)
+            .await
+            .map_err(|e| FrameGraderError::ToolCall(format!("MCP connect: {e}")))?;
+        Ok(Self { inner })
+    }
+
+    pub async fn measure_stars(
+        &self,
+        document_id: &str,
+        detection: Detection,
+    ) -> Result<MeasureStarsResult> {
+        self.call_tool(
+            "measure_stars",
+            serde_json::json!({
+                "document_id": document_id,
+                "threshold_sigma": detection.threshold_sigma,
+                "min_area": detection.min_area,
+                "max_area": detection.max_area,
+            }),
+        )
+        .await
+    }
+
+    pub async fn compute_snr(&self, document_id: &str, detection: Detection) -> Result<SnrResult> {
+        self.call_tool(
+            "compute_snr",
+            serde_json::json!({
+                "document_id": document_id,
+                "threshold_sigma": detection.threshold_sigma,
+                "min_area": detection.min_area,
+                "max_area": detection.max_area,
+            }),
+        )
+        .await
+    }
+
+    pub async fn estimate_background(&self, document_id: &str) -> Result<BackgroundResult> {
+        self.call_tool(
+            "estimate_background",
+            serde_json::json!({"document_id": document_id}),
+        )
+        .await
+    }
+
+    /// Generic helper: call tool, check for errors, deserialize result.
+    async fn call_tool<T: serde::de::DeserializeOwned>(
+        &self,
+        tool_name: &str,
+        arguments: Value,
+    ) -> Result<T> {
+        debug!(tool = %tool_name, "calling MCP tool");
+
+        let args = arguments.as_object().cloned().unwrap_or_default();
+        let value = self
+            .inner
+            .call_tool(tool_name, args)
+            .await
+            .map_err(|e| FrameGraderError::ToolCall(format!("{tool_name}: {e}")))?;
+
+        serde_json::from_value(value).map_err(|e| {
+            FrameGraderError::ToolCall(format!("{tool_name}: failed to parse result: {e}"))
+        })
+    }
+}
@@ -0,0 +1,110 @@
+//! HTTP routes: POST /webhook for rp's event deliveries.
+
+use std::sync::Arc;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::Value;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::baseline::Baselines;
+use crate::config::GraderConfig;
+use crate::{grading, rp_api};
+
+/// Router state: the config plus every baseline learned since start.
+#[derive(Clone)]
+struct GraderState {
+    config: Arc<GraderConfig>,
+    baselines: Arc<Mutex<Baselines>>,
+}
+
+pub fn build_router(config: GraderConfig) -> Router {
+    let state = GraderState {
+        config: Arc::new(config),
+        baselines: Arc::new(Mutex::new(Baselines::new())),
+    };
+    Router::new()
+        .route("/health", get(health))
+        .route("/webhook", post(webhook_handler))
+        .with_state(state)
+}
+
+async fn health() -> &'static str {
+    "frame-grader healthy"
+}
+
+/// Acknowledge the delivery at once and grade in the background. Every
+/// acknowledged event is completed — with a `grading` section for a
+/// graded light frame, without one otherwise — so a barrier on this
+/// plugin never waits out `max_duration` over a frame it skipped.
+async fn webhook_handler(
+    State(state): State<GraderState>,
+    Json(body): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let event_id = body
+        .get("event_id")
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let event = body.get("event").and_then(|v| v.as_str()).unwrap_or("");
+    let document_id = body
+        .pointer("/payload/document_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+
+    debug!(event_id = %event_id, event, ?document_id, "received event");
+
+    let config = Arc::clone(&state.config);
+    match (event, document_id) {
+        ("exposure_complete", Some(document_id)) => {
+            tokio::spawn(async move {
+                let completion = match grading::grade(&config, &state.baselines, &document_id).await
+                {
+                    Ok(Some(section)) => {
+                        info!(
+                            document_id = %document_id,
+                            rejected = %section["rejected"],
+                            "frame graded"
+                        );
+                        serde_json::json!({
+                            "status": "complete",
+                            "document_id": document_id,
+                            "sections": { "grading": section },
+                        })
+                    }
+                    Ok(None) => serde_json::json!({"status": "complete"}),
+                    Err(e) => {
+                        warn!(document_id = %document_id, error = %e, "grading failed");
+                        serde_json::json!({
+                            "status": "error",
+                            "result": { "error": e.to_string() },
+                        })
+                    }
+                };
+                rp_api::post_completion(&config, &event_id, &completion).await;
+            });
+        }
+        _ => {
+            // Subscribed to something that is not a frame: nothing to
+            // grade, but the acknowledgment still owes a completion.
+            tokio::spawn(async move {
+                rp_api::post_completion(
+                    &config,
+                    &event_id,
+                    &serde_json::json!({"status": "complete"}),
+                )
+                .await;
+            });
+        }
+    }
+
+    let max = state.config.max_duration;
+    let ack = serde_json::json!({
+        "estimated_duration": humantime::format_duration(max / 2).to_string(),
+        "max_duration": humantime::format_duration(max).to_string(),
+    });
+    (StatusCode::OK, Json(ack))
+}
@@ -0,0 +1,144 @@
+//! rp's REST endpoints the grader uses besides MCP: the exposure
+//! document fetch and the event completion POST. Both trust and
+//! authenticate per the ADR-017 policy — the same legs the MCP client
+//! uses.
+
+use std::time::Duration;
+
+use rp_vocabulary::FrameType;
+use serde::Deserialize;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::baseline::BaselineKey;
+use crate::config::GraderConfig;
+use crate::error::{FrameGraderError, Result};
+
+/// The exposure-document fields grading needs; everything else in the
+/// document is ignored.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FrameDocument {
+    #[serde(default)]
+    pub frame_type: Option<FrameType>,
+    #[serde(default)]
+    pub target: Option<DocumentTarget>,
+    #[serde(default)]
+    pub filter: Option<String>,
+    #[serde(default, with = "humantime_serde")]
+    pub duration: Option<Duration>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DocumentTarget {
+    pub slug: String,
+}
+
+impl FrameDocument {
+    /// The baseline this frame is judged against, or `None` when it is
+    /// not graded at all: only `Light` frames of a target are.
+    #[must_use]
+    pub fn baseline_key(&self) -> Option<BaselineKey> {
+        if self.frame_type != Some(FrameType::Light) {
+            return None;
+        }
+        let target = self.target.as_ref()?;
+        Some(BaselineKey {
+            target: target.slug.clone(),
+            filter: self.filter.clone(),
+            exposure: self.duration,
+        })
+    }
+}
+
+fn request(
+    config: &GraderConfig,
+    method: reqwest::Method,
+    url: &str,
+) -> Result<reqwest::RequestBuilder> {
+    let client = rusty_photon_tls::client::build_reqwest_client(config.rp_ca())
+        .map_err(|e| FrameGraderError::Rp(format!("cannot build HTTP client: {e}")))?;
+    let auth_header = rp_mcp_client::basic_authorization(url, config.rp_auth(), config.rp_ca())
+        .map_err(|e| FrameGraderError::Rp(format!("cannot build Authorization header: {e}")))?;
+    let mut request = client.request(method, url);
+    if let Some(header) = auth_header {
+        request = request.header(reqwest::header::AUTHORIZATION, header);
+    }
+    Ok(request)
+}
+
+/// `GET /api/documents/{id}`.
+pub async fn fetch_document(config: &GraderConfig, document_id: &str) -> Result<FrameDocument> {
+    let url = format!("{}/api/documents/{document_id}", config.rp_base_url());
+    let response = request(config, reqwest::Method::GET, &url)?
+        .send()
+        .await
+        .map_err(|e| FrameGraderError::Rp(format!("GET {url}: {e}")))?;
+    if !response.status().is_success() {
+        return Err(FrameGraderError::Rp(format!(
+            "GET {url}: {}",
+            response.status()
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| FrameGraderError::Rp(format!("GET {url}: invalid document: {e}")))
+}
+
+/// `POST /api/plugins/{event_id}/complete`. Failures are logged, not
+/// returned: there is no one left to tell.
+pub async fn post_completion(config: &GraderConfig, event_id: &str, body: &Value) {
+    let url = format!("{}/api/plugins/{event_id}/complete", config.rp_base_url());
+    let response = match request(config, reqwest::Method::POST, &url) {
+        Ok(request) => request.json(body).send().await,
+        Err(e) => {
+            warn!(%url, error = %e, "cannot post the completion");
+            return;
+        }
+    };
+    match response {
+        Ok(r) if !r.status().is_success() => {
+            warn!(%url, status = %r.status(), "rp refused the completion");
+        }
+        Ok(_) => {}
+        Err(e) => warn!(%url, error = %e, "completion post failed"),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_target_lights_have_a_baseline() {
+        let light: FrameDocument = serde_json::from_value(serde_json::json!({
+            "id": "doc-1",
+            "file_path": "/data/m31/doc.fits",
+            "frame_type": "Light",
+            "target": {"slug": "m31", "display_name": "Andromeda"},
+            "filter": "Ha",
+            "duration": "5m",
+            "sections": {}
+        }))
+        .unwrap();
+        assert_eq!(
+            light.baseline_key(),
+            Some(BaselineKey {
+                target: "m31".to_string(),
+                filter: Some("Ha".to_string()),
+                exposure: Some(Duration::from_secs(300)),
+            })
+        );
+
+        let flat: FrameDocument = serde_json::from_value(
+            serde_json::json!({"frame_type": "Flat", "target": {"slug": "flat"}}),
+        )
+        .unwrap();
+        assert_eq!(flat.baseline_key(), None);
+
+        // A bare `capture` (no frame_type) belongs to no target.
+        let untyped: FrameDocument = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(untyped.baseline_key(), None);
+    }
+}
@@ -0,0 +1,50 @@
+//! BDD test entry point for the frame-grader service.
+//!
+//! The grading scenarios spawn three processes — `OmniSim`, rp, and
+//! frame-grader — and follow one frame from rp's `exposure_complete`
+//! delivery to the stored `grading` section. The baseline judgment
+//! itself is covered by the unit tests in `src/grading.rs` and
+//! `src/baseline.rs`. The smoke scenarios spawn frame-grader alone with
+//! a temp config.
+
+#![allow(clippy::expect_used, clippy::panic)]
+
+#[path = "bdd/world.rs"]
+mod world;
+
+#[path = "bdd/steps/mod.rs"]
+mod steps;
+
+bdd_infra::bdd_main! {
+    use cucumber::World as _;
+    use world::FrameGraderWorld;
+
+    FrameGraderWorld::cucumber()
+        .before(|_feature, _rule, _scenario, _world| {
+            Box::pin(async move {
+                // OmniSim is a per-process singleton: reset the devices
+                // so one scenario's camera settings do not leak into the
+                // next. See calibrator-flats' BDD entry point for why a
+                // reset failure is fatal.
+                if let Err(errors) =
+                    bdd_infra::rp_harness::OmniSimHandle::reset_all_devices().await
+                {
+                    panic!("OmniSim device reset failed: {}", errors.join("; "));
+                }
+            })
+        })
+        .after(|_feature, _rule, _scenario, _finished, maybe_world| {
+            Box::pin(async move {
+                if let Some(world) = maybe_world {
+                    if let Some(handle) = world.frame_grader.as_mut() {
+                        handle.stop().await;
+                    }
+                    if let Some(rp) = world.rp.as_mut() {
+                        rp.stop().await;
+                    }
+                }
+            })
+        })
+        .run_and_exit("tests/features")
+        .await;
+}
@@ -0,0 +1,276 @@
+//! BDD step definitions for the end-to-end grading flow.
+//!
+//! The scenarios spawn three processes: `OmniSim` (Alpaca simulator), rp
+//! (equipment gateway, capture, analysis tools), and frame-grader (the
+//! event plugin being tested). rp delivers `exposure_complete` to
+//! frame-grader's `/webhook`; frame-grader grades the frame through rp's
+//! MCP tools and posts its completion back, and rp announces each
+//! section it stores with `document_updated` — which a test webhook
+//! receiver watches.
+
+use std::time::Duration;
+
+use bdd_infra::rp_harness::{
+    start_rp, write_temp_config_file, McpTestClient, OmniSimHandle, WebhookReceiver,
+};
+use bdd_infra::ServiceHandle;
+use cucumber::{given, then, when};
+use serde_json::Value;
+
+use crate::world::FrameGraderWorld;
+
+// ---------------------------------------------------------------------------
+// Given steps
+// ---------------------------------------------------------------------------
+
+#[given("a running Alpaca simulator")]
+async fn running_alpaca_simulator(world: &mut FrameGraderWorld) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+}
+
+#[given(expr = "a test webhook receiver subscribed to {string}")]
+async fn webhook_receiver_subscribed_to(world: &mut FrameGraderWorld, event_type: String) {
+    if world.webhook_receiver.is_none() {
+        let events = world.received_events.clone();
+        world.webhook_receiver = Some(
+            WebhookReceiver::start(events, Duration::from_secs(5), Duration::from_secs(10)).await,
+        );
+    }
+    let url = world
+        .webhook_receiver
+        .as_ref()
+        .expect("webhook receiver not started")
+        .url
+        .clone();
+    match world
+        .plugin_configs
+        .iter_mut()
+        .find(|p| p.get("name").and_then(Value::as_str) == Some("test-event-plugin"))
+    {
+        Some(plugin) => {
+            if let Some(subscribed) = plugin["subscribes_to"].as_array_mut() {
+                subscribed.push(serde_json::json!(event_type));
+            }
+        }
+        None => world.plugin_configs.push(serde_json::json!({
+            "name": "test-event-plugin",
+            "type": "event",
+            "webhook_url": url,
+            "subscribes_to": [event_type]
+        })),
+    }
+}
+
+/// rp starts first on a free port of its own; frame-grader then starts
+/// on the port its registration already names, pointed at rp's MCP
+/// endpoint. Nothing is delivered to it before the first capture.
+#[given("rp is running with a camera and the frame-grader event plugin")]
+async fn rp_running_with_camera_and_frame_grader(world: &mut FrameGraderWorld) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+    world.cameras.push(bdd_infra::rp_harness::CameraConfig {
+        id: "main-cam".to_string(),
+        alpaca_url: world.omnisim_url(),
+        device_number: 0,
+        cooler_targets_c: Vec::new(),
+    });
+
+    let port = pick_free_port();
+    world.frame_grader_port = Some(port);
+    world.plugin_configs.push(serde_json::json!({
+        "name": "frame-grader",
+        "type": "event",
+        "webhook_url": format!("http://127.0.0.1:{port}/webhook"),
+        "subscribes_to": ["exposure_complete"]
+    }));
+
+    let config = world.build_rp_config();
+    world.rp = Some(start_rp(&config).await);
+    assert!(
+        world.wait_for_rp_healthy().await,
+        "rp did not become healthy within timeout"
+    );
+
+    let grader_config = world.build_grader_config();
+    let config_path = write_temp_config_file("frame-grader-config", &grader_config).await;
+    world.frame_grader = Some(ServiceHandle::start(env!("CARGO_PKG_NAME"), &config_path).await);
+}
+
+#[given(
+    expr = "rp's target store holds a target named {string} at ra_hours {float} dec_degrees {float}"
+)]
+async fn target_store_holds(
+    world: &mut FrameGraderWorld,
+    display_name: String,
+    ra_hours: f64,
+    dec_degrees: f64,
+) {
+    mcp(world)
+        .await
+        .call_tool(
+            "add_target",
+            serde_json::json!({
+                "display_name": display_name,
+                "ra_hours": ra_hours,
+                "dec_degrees": dec_degrees
+            }),
+        )
+        .await
+        .expect("add_target should succeed in scenario setup");
+}
+
+// ---------------------------------------------------------------------------
+// When steps
+// ---------------------------------------------------------------------------
+
+#[when(expr = "a {string} frame of target {string} is captured for {int} ms")]
+async fn capture_light(world: &mut FrameGraderWorld, frame_type: String, target: String, ms: u64) {
+    let args = serde_json::json!({
+        "camera_id": "main-cam",
+        "duration": format!("{ms}ms"),
+        "target": target,
+        "frame_type": frame_type,
+    });
+    capture(world, frame_type, args).await;
+}
+
+#[when(expr = "a {string} frame is captured for {int} ms")]
+async fn capture_calibration(world: &mut FrameGraderWorld, frame_type: String, ms: u64) {
+    let args = serde_json::json!({
+        "camera_id": "main-cam",
+        "duration": format!("{ms}ms"),
+        "frame_type": frame_type,
+    });
+    capture(world, frame_type, args).await;
+}
+
+// ---------------------------------------------------------------------------
+// Then steps
+// ---------------------------------------------------------------------------
+
+/// rp emits `document_updated` only after the section is persisted and
+/// only from the completion handler, so the event proves both the
+/// grading write and the completion.
+#[then(expr = "frame-grader should complete the {string} frame with a grading section")]
+async fn completes_with_grading(world: &mut FrameGraderWorld, frame_type: String) {
+    let document_id = captured(world, &frame_type);
+    let event = world
+        .wait_for_event("document_updated", |p| {
+            p["document_id"].as_str() == Some(document_id.as_str())
+                && p["section_name"] == "grading"
+        })
+        .await;
+    assert!(
+        event.is_some(),
+        "no grading section was written for {document_id} within 60s"
+    );
+    world.last_document = Some(fetch_document(world, &document_id).await);
+}
+
+#[then("the grading section should carry the frame's metrics")]
+fn grading_carries_metrics(world: &mut FrameGraderWorld) {
+    let grading = grading_section(world);
+    assert!(grading["star_count"].is_u64(), "{grading}");
+    assert!(grading["background"].is_f64(), "{grading}");
+    assert!(grading["background_stddev"].is_f64(), "{grading}");
+    for nullable in ["hfr", "fwhm", "eccentricity", "snr"] {
+        assert!(
+            grading[nullable].is_null() || grading[nullable].is_f64(),
+            "{nullable} should be a number or null: {grading}"
+        );
+    }
+}
+
+#[then("the grading section should not reject the frame before a baseline exists")]
+fn grading_not_rejected(world: &mut FrameGraderWorld) {
+    let grading = grading_section(world);
+    assert_eq!(grading["rejected"], false, "{grading}");
+    assert_eq!(grading["reasons"], serde_json::json!([]), "{grading}");
+    assert!(grading.get("baseline").is_none(), "{grading}");
+}
+
+/// Runs after the light frame's grading lands: the calibration frame
+/// was delivered first and needs no analysis, so its completion is long
+/// posted by then.
+#[then(expr = "the {string} frame should carry no grading section")]
+async fn no_grading_for(world: &mut FrameGraderWorld, frame_type: String) {
+    let document_id = captured(world, &frame_type);
+    let doc = fetch_document(world, &document_id).await;
+    assert!(
+        doc.pointer("/sections/grading").is_none(),
+        "a {frame_type} frame was graded: {doc}"
+    );
+    assert!(
+        !world.received_events.read().await.iter().any(|e| {
+            e.event_type == "document_updated"
+                && e.payload["document_id"].as_str() == Some(document_id.as_str())
+        }),
+        "a section was written for the {frame_type} frame"
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Helpers
+// ---------------------------------------------------------------------------
+
+/// Probe the OS for a free `127.0.0.1` port, as the `OmniSim` launcher
+/// does. Another process can grab it before frame-grader binds; that
+/// lost race surfaces as frame-grader failing to start.
+fn pick_free_port() -> u16 {
+    std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.local_addr())
+        .map_or_else(
+            |e| panic!("failed to probe a free port for frame-grader: {e}"),
+            |addr| addr.port(),
+        )
+}
+
+async fn mcp(world: &FrameGraderWorld) -> McpTestClient {
+    McpTestClient::connect(&format!("{}/mcp", world.rp_url()))
+        .await
+        .expect("failed to connect MCP test client")
+}
+
+async fn capture(world: &mut FrameGraderWorld, frame_type: String, args: Value) {
+    let result = mcp(world)
+        .await
+        .call_tool("capture", args)
+        .await
+        .unwrap_or_else(|e| panic!("capture failed: {e}"));
+    let document_id = result["document_id"]
+        .as_str()
+        .unwrap_or_else(|| panic!("capture returned no document_id: {result}"));
+    world.captured.insert(frame_type, document_id.to_string());
+}
+
+fn captured(world: &FrameGraderWorld, frame_type: &str) -> String {
+    world
+        .captured
+        .get(frame_type)
+        .cloned()
+        .unwrap_or_else(|| panic!("no {frame_type} frame was captured"))
+}
+
+async fn fetch_document(world: &FrameGraderWorld, document_id: &str) -> Value {
+    let url = format!("{}/api/documents/{document_id}", world.rp_url());
+    reqwest::Client::new()
+        .get(&url)
+        .send()
+        .await
+        .unwrap_or_else(|e| panic!("GET {url} failed: {e}"))
+        .json()
+        .await
+        .unwrap_or_else(|e| panic!("GET {url} returned an invalid document: {e}"))
+}
+
+fn grading_section(world: &FrameGraderWorld) -> &Value {
+    let doc = world
+        .last_document
+        .as_ref()
+        .expect("wait for the grading section before asserting on it");
+    doc.pointer("/sections/grading")
+        .unwrap_or_else(|| panic!("no grading section in {doc}"))
+}
@@ -0,0 +1,5 @@
+//! BDD step definitions for the frame-grader service.
+
+pub mod auth_steps;
+pub mod doctor_steps;
+pub mod grading_steps;
@@ -0,0 +1,178 @@
+//! BDD test world for the frame-grader service: the three external
+//! processes of the grading scenarios (`OmniSim`, rp, frame-grader), the
+//! frame archive rp writes to, plus the shared TLS + auth and doctor
+//! smoke state.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bdd_infra::rp_harness::{CameraConfig, ReceivedEvent, RpConfigBuilder, WebhookReceiver};
+use bdd_infra::tls_auth::{TlsAuthSmokeWorld, TlsAuthState};
+use bdd_infra::ServiceHandle;
+use cucumber::World;
+use serde_json::Value;
+use tempfile::TempDir;
+use tokio::sync::RwLock;
+
+#[derive(Default, World, derive_more::Debug)]
+#[debug("FrameGraderWorld {{ .. }}")]
+pub struct FrameGraderWorld {
+    // --- Infrastructure handles ---
+    pub omnisim: Option<bdd_infra::rp_harness::OmniSimHandle>,
+    pub rp: Option<ServiceHandle>,
+    pub frame_grader: Option<ServiceHandle>,
+    pub webhook_receiver: Option<WebhookReceiver>,
+
+    // --- rp config building ---
+    pub cameras: Vec<CameraConfig>,
+    pub plugin_configs: Vec<Value>,
+
+    // --- Webhook state ---
+    pub received_events: Arc<RwLock<Vec<ReceivedEvent>>>,
+
+    // --- Grading flow ---
+    /// rp's `session.data_directory`, so captured frames land in a temp
+    /// directory the scenario owns.
+    pub archive: Option<TempDir>,
+    /// frame-grader's port, reserved before rp starts: rp's plugin
+    /// registration names the webhook URL, and frame-grader in turn
+    /// needs rp's URL, so one side has to be known up front.
+    pub frame_grader_port: Option<u16>,
+    /// `document_id` of each capture, keyed by its `frame_type`.
+    pub captured: HashMap<String, String>,
+    /// The last document fetched from `GET /api/documents/{id}`.
+    pub last_document: Option<Value>,
+
+    /// State for the shared TLS + auth smoke steps.
+    pub tls_auth: TlsAuthState,
+
+    /// Doctor-subcommand smoke state (staged config file + run output)
+    pub doctor_smoke: bdd_infra::doctor_smoke::DoctorSmokeState,
+}
+
+impl bdd_infra::doctor_smoke::DoctorSmokeWorld for FrameGraderWorld {
+    fn doctor_smoke(&mut self) -> &mut bdd_infra::doctor_smoke::DoctorSmokeState {
+        &mut self.doctor_smoke
+    }
+
+    fn valid_config(&self) -> serde_json::Value {
+        // The tls-auth smoke's base config plus a plain `server` block.
+        let mut config = TlsAuthSmokeWorld::base_test_config(self);
+        config["server"] = serde_json::json!({ "port": 0 });
+        config
+    }
+}
+
+impl FrameGraderWorld {
+    pub fn omnisim_url(&self) -> String {
+        self.omnisim
+            .as_ref()
+            .expect("OmniSim must be started before accessing its URL")
+            .base_url
+            .clone()
+    }
+
+    pub fn rp_url(&self) -> String {
+        self.rp
+            .as_ref()
+            .map(|h| h.base_url.clone())
+            .expect("rp must be started before accessing its URL")
+    }
+
+    /// The frame archive, created on first use.
+    pub fn archive(&mut self) -> &TempDir {
+        self.archive
+            .get_or_insert_with(|| TempDir::new().expect("failed to create the archive dir"))
+    }
+
+    /// rp's config: the accumulated camera and plugins, the archive as
+    /// its data directory, and a site so a `Light` capture can render
+    /// its night date.
+    pub fn build_rp_config(&mut self) -> Value {
+        let archive = self.archive().path().to_string_lossy().into_owned();
+        let mut builder = RpConfigBuilder::new();
+        for camera in &self.cameras {
+            builder.add_camera(camera.clone());
+        }
+        for plugin in &self.plugin_configs {
+            builder.add_plugin(plugin.clone());
+        }
+        builder
+            .with_data_directory(archive)
+            .with_site(47.6062, -122.3321);
+        builder.build()
+    }
+
+    /// frame-grader's config: rp's MCP endpoint, detection areas wide
+    /// enough for the simulator's frames, and the reserved port.
+    pub fn build_grader_config(&self) -> Value {
+        let port = self
+            .frame_grader_port
+            .expect("reserve frame-grader's port before building its config");
+        serde_json::json!({
+            "mcp_server_url": format!("{}/mcp", self.rp_url()),
+            "min_area": 5,
+            "max_area": 400,
+            "server": {
+                "port": port,
+                "bind_address": "127.0.0.1"
+            }
+        })
+    }
+
+    /// Wait for rp's `/health` endpoint to return 200.
+    pub async fn wait_for_rp_healthy(&self) -> bool {
+        bdd_infra::rp_harness::wait_for_rp_healthy(&self.rp_url()).await
+    }
+
+    /// The first received event of `event_type` whose payload matches
+    /// `predicate`, waiting up to 60 s: grading reads the frame three
+    /// times through rp's analysis tools before it completes.
+    pub async fn wait_for_event(
+        &self,
+        event_type: &str,
+        predicate: impl Fn(&Value) -> bool,
+    ) -> Option<ReceivedEvent> {
+        for _ in 0..240 {
+            if let Some(event) = self
+                .received_events
+                .read()
+                .await
+                .iter()
+                .find(|e| e.event_type == event_type && predicate(&e.payload))
+            {
+                return Some(event.clone());
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+        None
+    }
+}
+
+impl TlsAuthSmokeWorld for FrameGraderWorld {
+    const PROBE_PATH: &'static str = "/health";
+
+    fn tls_auth(&mut self) -> &mut TlsAuthState {
+        &mut self.tls_auth
+    }
+
+    fn base_test_config(&self) -> serde_json::Value {
+        // rp is never contacted — the smoke scenario only probes
+        // `/health`, so nothing need answer at the default MCP URL.
+        serde_json::json!({
+            "min_area": 5,
+            "max_area": 400
+        })
+    }
+
+    async fn start_with_tls_auth(&mut self, config: serde_json::Value) {
+        let handle = bdd_infra::tls_auth::spawn_service_handle(
+            &mut self.tls_auth,
+            env!("CARGO_PKG_NAME"),
+            &config,
+        )
+        .await;
+        self.frame_grader = Some(handle);
+    }
+}
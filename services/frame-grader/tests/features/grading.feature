@@ -0,0 +1,33 @@
+@serial
+Feature: Grading a frame end to end
+  frame-grader is an event plugin subscribed to `exposure_complete`. rp
+  delivers the event to its `/webhook`; frame-grader acknowledges at once,
+  fetches the exposure document, and — for a target's `Light` frame —
+  measures it through rp's `measure_stars`, `compute_snr` and
+  `estimate_background` tools, judges it against the rolling baseline,
+  and posts its completion with a `grading` section. rp stores the
+  section on the document and announces it with `document_updated`.
+  Every other frame is completed without a section.
+
+  A first frame has no baseline to be judged against, so it is graded
+  on its metrics alone and never rejected. The baseline comparison is
+  covered by the unit tests in src/baseline.rs.
+
+  Background:
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to "document_updated"
+    And rp is running with a camera and the frame-grader event plugin
+
+  Scenario: A light frame is graded and the grading section is stored
+    Given rp's target store holds a target named "M33" at ra_hours 1.4642 dec_degrees 30.6602
+    When a "Light" frame of target "m33" is captured for 1000 ms
+    Then frame-grader should complete the "Light" frame with a grading section
+    And the grading section should carry the frame's metrics
+    And the grading section should not reject the frame before a baseline exists
+
+  Scenario: A calibration frame is completed without a grading section
+    Given rp's target store holds a target named "M33" at ra_hours 1.4642 dec_degrees 30.6602
+    When a "Dark" frame is captured for 1000 ms
+    And a "Light" frame of target "m33" is captured for 1000 ms
+    Then frame-grader should complete the "Light" frame with a grading section
+    And the "Dark" frame should carry no grading section
@@ -0,0 +1,274 @@
+//! Per-axis guide algorithms: how much of a measured error to correct.
+//!
+//! Both follow PHD2's algorithms of the same names, so a profile tuned
+//! there carries over. Inputs and outputs are pixels along the axis;
+//! the guide loop turns the output into a pulse via the calibration
+//! rate.
+
+use std::collections::VecDeque;
+
+use crate::config::AlgorithmConfig;
+
+pub trait GuideAlgorithm: Send {
+    /// The correction, in pixels, for an error of `input` pixels.
+    fn result(&mut self, input: f64) -> f64;
+
+    /// Forget the history; called whenever the lock position jumps.
+    fn reset(&mut self);
+}
+
+impl AlgorithmConfig {
+    #[must_use]
+    pub fn build(&self) -> Box<dyn GuideAlgorithm> {
+        match *self {
+            Self::Hysteresis {
+                hysteresis,
+                aggression,
+                min_move_px,
+            } => Box::new(Hysteresis {
+                hysteresis,
+                aggression,
+                min_move_px,
+                last_move: 0.0,
+            }),
+            Self::ResistSwitch {
+                aggression,
+                min_move_px,
+                fast_switch,
+            } => Box::new(ResistSwitch::new(aggression, min_move_px, fast_switch)),
+        }
+    }
+}
+
+/// Blend the error with the previous correction, then scale it.
+#[derive(Debug)]
+struct Hysteresis {
+    hysteresis: f64,
+    aggression: f64,
+    min_move_px: f64,
+    last_move: f64,
+}
+
+impl GuideAlgorithm for Hysteresis {
+    fn result(&mut self, input: f64) -> f64 {
+        let blended = (1.0 - self.hysteresis).mul_add(input, self.hysteresis * self.last_move);
+        let correction = if input.abs() < self.min_move_px {
+            0.0
+        } else {
+            blended * self.aggression
+        };
+        self.last_move = correction;
+        correction
+    }
+
+    fn reset(&mut self) {
+        self.last_move = 0.0;
+    }
+}
+
+/// Recent errors [`ResistSwitch`] weighs before committing to a side.
+const HISTORY_LEN: usize = 10;
+
+/// Only correct toward the side the recent errors agree on, and only
+/// switch sides once the error on the other side is growing.
+#[derive(Debug)]
+struct ResistSwitch {
+    aggression: f64,
+    min_move_px: f64,
+    fast_switch: bool,
+    /// Always `HISTORY_LEN` long, oldest first; zero-filled on reset.
+    history: VecDeque<f64>,
+    /// `1.0`, `-1.0`, or `0.0` before a side is chosen.
+    current_side: f64,
+}
+
+fn sign(v: f64) -> f64 {
+    if v > 0.0 {
+        1.0
+    } else if v < 0.0 {
+        -1.0
+    } else {
+        0.0
+    }
+}
+
+fn mean<'a>(values: impl Iterator<Item = &'a f64>) -> f64 {
+    let (sum, n) = values.fold((0.0, 0u32), |(sum, n), v| (sum + v, n + 1));
+    if n == 0 {
+        0.0
+    } else {
+        sum / f64::from(n)
+    }
+}
+
+impl ResistSwitch {
+    fn new(aggression: f64, min_move_px: f64, fast_switch: bool) -> Self {
+        Self {
+            aggression,
+            min_move_px,
+            fast_switch,
+            history: std::iter::repeat_n(0.0, HISTORY_LEN).collect(),
+            current_side: 0.0,
+        }
+    }
+
+    fn decide(&mut self, input: f64) -> f64 {
+        if input.abs() < self.min_move_px {
+            return 0.0;
+        }
+
+        // A large error against the current side switches at once: the
+        // history is rewritten as if the last three errors had all been
+        // this one, which the checks below then accept.
+        if self.fast_switch
+            && sign(input) != self.current_side
+            && input.abs() > 3.0 * self.min_move_px
+        {
+            self.current_side = 0.0;
+            for (i, v) in self.history.iter_mut().enumerate() {
+                *v = if i < HISTORY_LEN - 3 { 0.0 } else { input };
+            }
+        }
+
+        let votes: f64 = self
+            .history
+            .iter()
+            .filter(|v| v.abs() > self.min_move_px)
+            .map(|&v| sign(v))
+            .sum();
+
+        if self.current_side == 0.0 || self.current_side == -sign(votes) {
+            if votes.abs() < 3.0 {
+                return 0.0;
+            }
+            // Switch only if the error is growing: the newest three
+            // samples must outweigh the oldest three.
+            let oldest = mean(self.history.iter().take(3));
+            let newest = mean(self.history.iter().rev().take(3));
+            if newest.abs() <= oldest.abs() {
+                return 0.0;
+            }
+            self.current_side = sign(votes);
+        }
+
+        if self.current_side != sign(input) {
+            return 0.0;
+        }
+        input * self.aggression
+    }
+}
+
+impl GuideAlgorithm for ResistSwitch {
+    fn result(&mut self, input: f64) -> f64 {
+        self.history.pop_front();
+        self.history.push_back(input);
+        self.decide(input)
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.aggression, self.min_move_px, self.fast_switch);
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} !~ {b}");
+    }
+
+    fn hysteresis() -> Box<dyn GuideAlgorithm> {
+        AlgorithmConfig::Hysteresis {
+            hysteresis: 0.1,
+            aggression: 0.7,
+            min_move_px: 0.15,
+        }
+        .build()
+    }
+
+    fn resist_switch(fast_switch: bool) -> Box<dyn GuideAlgorithm> {
+        AlgorithmConfig::ResistSwitch {
+            aggression: 1.0,
+            min_move_px: 0.2,
+            fast_switch,
+        }
+        .build()
+    }
+
+    #[test]
+    fn hysteresis_blends_with_the_previous_correction() {
+        let mut algo = hysteresis();
+        approx(algo.result(1.0), 0.9 * 0.7);
+        // 0.9 · 1.0 + 0.1 · 0.63, scaled by 0.7.
+        approx(algo.result(1.0), (0.9 + 0.1 * 0.63) * 0.7);
+    }
+
+    #[test]
+    fn hysteresis_ignores_errors_below_min_move() {
+        let mut algo = hysteresis();
+        approx(algo.result(0.1), 0.0);
+        approx(algo.result(-0.14), 0.0);
+        // The skipped step zeroed the history.
+        approx(algo.result(1.0), 0.9 * 0.7);
+    }
+
+    #[test]
+    fn hysteresis_reset_forgets_the_last_move() {
+        let mut algo = hysteresis();
+        algo.result(2.0);
+        algo.reset();
+        approx(algo.result(1.0), 0.9 * 0.7);
+    }
+
+    #[test]
+    fn resist_switch_waits_for_three_votes() {
+        let mut algo = resist_switch(false);
+        approx(algo.result(0.5), 0.0);
+        approx(algo.result(0.6), 0.0);
+        // Third vote, and the newest three outweigh the zeroed oldest.
+        approx(algo.result(0.7), 0.7);
+        approx(algo.result(0.8), 0.8);
+    }
+
+    #[test]
+    fn resist_switch_refuses_the_other_side_on_a_single_error() {
+        let mut algo = resist_switch(false);
+        for v in [0.5, 0.6, 0.7, 0.8] {
+            algo.result(v);
+        }
+        approx(algo.result(-0.5), 0.0);
+        approx(algo.result(0.6), 0.6);
+    }
+
+    #[test]
+    fn fast_switch_follows_a_large_reversal() {
+        let mut algo = resist_switch(true);
+        for v in [0.3, 0.3, 0.3, 0.4] {
+            algo.result(v);
+        }
+        // A 0.7 px reversal (> 3 · 0.2) is followed at once.
+        approx(algo.result(-0.7), -0.7);
+        approx(algo.result(-0.8), -0.8);
+    }
+
+    #[test]
+    fn resist_switch_ignores_errors_below_min_move() {
+        let mut algo = resist_switch(true);
+        for v in [0.5, 0.6, 0.7, 0.8] {
+            algo.result(v);
+        }
+        approx(algo.result(0.1), 0.0);
+    }
+
+    #[test]
+    fn resist_switch_reset_forgets_the_side() {
+        let mut algo = resist_switch(false);
+        for v in [0.5, 0.6, 0.7, 0.8] {
+            algo.result(v);
+        }
+        algo.reset();
+        approx(algo.result(0.9), 0.0);
+    }
+}
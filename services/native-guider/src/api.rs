@@ -0,0 +1,555 @@
+//! Axum router and HTTP handlers for the guider service endpoints.
+//!
+//! Routes, bodies, and responses are the contract `phd2-guider serve`
+//! froze (`docs/services/phd2-guider.md` § "HTTP Service Mode"), so
+//! `rp-guider` drives either backend unchanged.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::config::{AlgorithmConfig, BacklashConfig, DecGuideMode};
+use crate::error::ServiceError;
+use crate::guider::{
+    AppState, AxisUpdate, CalibrationTarget, GuiderOps, SettingsUpdate, StatsSnapshot, Tuning,
+};
+
+pub fn build_router(ops: Arc<GuiderOps>) -> Router {
+    Router::new()
+        .route("/api/v1/guiding/start", post(start_guiding))
+        .route("/api/v1/guiding/stop", post(stop_guiding))
+        .route("/api/v1/guiding/pause", post(pause_guiding))
+        .route("/api/v1/guiding/resume", post(resume_guiding))
+        .route("/api/v1/dither", post(dither))
+        .route("/api/v1/guiding/stats", get(stats))
+        .route("/api/v1/guiding/metrics", get(metrics))
+        .route("/api/v1/equipment", get(equipment))
+        .route("/api/v1/calibration/clear", post(clear_calibration))
+        .route("/api/v1/star/reselect", post(reselect_star))
+        .route(
+            "/api/v1/guiding/settings",
+            get(settings).post(update_settings),
+        )
+        .route("/health", get(health))
+        .with_state(ops)
+}
+
+/// Partial settle override; omitted fields fall back to the config
+/// `settling` block, field by field.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SettleBody {
+    #[serde(default)]
+    pixels: Option<f64>,
+    #[serde(default, with = "humantime_serde::option")]
+    time: Option<Duration>,
+    #[serde(default, with = "humantime_serde::option")]
+    timeout: Option<Duration>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StartBody {
+    #[serde(default)]
+    recalibrate: bool,
+    #[serde(default)]
+    settle: Option<SettleBody>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DitherBody {
+    amount_px: f64,
+    #[serde(default)]
+    ra_only: bool,
+    #[serde(default)]
+    settle: Option<SettleBody>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PauseBody {
+    #[serde(default)]
+    full: bool,
+}
+
+/// One axis of a settings update. `min_move_px` and `aggressiveness`
+/// are the shared names for the algorithm's `min_move_px` and
+/// `aggression`; `params` takes any parameter by its config name.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AxisSettingsBody {
+    #[serde(default)]
+    min_move_px: Option<f64>,
+    #[serde(default)]
+    aggressiveness: Option<f64>,
+    #[serde(default)]
+    params: BTreeMap<String, f64>,
+}
+
+impl From<AxisSettingsBody> for AxisUpdate {
+    fn from(body: AxisSettingsBody) -> Self {
+        Self {
+            min_move_px: body.min_move_px,
+            aggressiveness: body.aggressiveness,
+            params: body.params,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SettingsBody {
+    #[serde(default)]
+    dec_guide_mode: Option<DecGuideMode>,
+    #[serde(default)]
+    ra: Option<AxisSettingsBody>,
+    #[serde(default)]
+    dec: Option<AxisSettingsBody>,
+    #[serde(default)]
+    multi_star: Option<bool>,
+    #[serde(default)]
+    backlash_compensation: Option<BacklashConfig>,
+}
+
+/// Response shape shared by `guiding/start` and `dither`.
+#[derive(Debug, Serialize)]
+struct SettledResponse {
+    state: &'static str,
+    rms_ra_px: Option<f64>,
+    rms_dec_px: Option<f64>,
+    total_rms_px: Option<f64>,
+    sample_count: u32,
+}
+
+impl SettledResponse {
+    const fn from_snapshot(snapshot: StatsSnapshot) -> Self {
+        Self {
+            state: "guiding",
+            rms_ra_px: snapshot.rms_ra_px,
+            rms_dec_px: snapshot.rms_dec_px,
+            total_rms_px: snapshot.total_rms_px,
+            sample_count: snapshot.sample_count,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct StatsResponse {
+    app_state: String,
+    guiding: bool,
+    rms_ra_px: Option<f64>,
+    rms_dec_px: Option<f64>,
+    total_rms_px: Option<f64>,
+    snr: Option<f64>,
+    star_mass: Option<f64>,
+    sample_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct StateResponse {
+    state: &'static str,
+}
+
+/// Parse a request body whose fields are all optional: an empty (or
+/// whitespace-only) body is `{}`; a malformed one is
+/// `invalid_request`. Explicit `Bytes` parsing (rather than the `Json`
+/// extractor) keeps the error inside the structured envelope and makes
+/// the empty body valid.
+fn parse_optional_body<T: Default + serde::de::DeserializeOwned>(
+    bytes: &axum::body::Bytes,
+) -> Result<T, ServiceError> {
+    if bytes.iter().all(u8::is_ascii_whitespace) {
+        return Ok(T::default());
+    }
+    serde_json::from_slice(bytes)
+        .map_err(|e| ServiceError::InvalidRequest(format!("malformed body: {e}")))
+}
+
+fn parse_required_body<T: serde::de::DeserializeOwned>(
+    bytes: &axum::body::Bytes,
+) -> Result<T, ServiceError> {
+    serde_json::from_slice(bytes)
+        .map_err(|e| ServiceError::InvalidRequest(format!("malformed body: {e}")))
+}
+
+/// Merge a per-request settle override onto the config defaults,
+/// rejecting a non-positive or non-finite threshold before anything
+/// reaches the guide loop (same bar as `amount_px`).
+fn resolve_settle(
+    ops: &GuiderOps,
+    settle: Option<SettleBody>,
+) -> Result<crate::config::SettleParams, ServiceError> {
+    let settle = settle.unwrap_or_default();
+    if let Some(pixels) = settle.pixels {
+        if !pixels.is_finite() || pixels <= 0.0 {
+            return Err(ServiceError::InvalidRequest(format!(
+                "settle.pixels must be a positive number of pixels, got {pixels}"
+            )));
+        }
+    }
+    Ok(ops.resolve_settle(settle.pixels, settle.time, settle.timeout))
+}
+
+async fn start_guiding(
+    State(ops): State<Arc<GuiderOps>>,
+    bytes: axum::body::Bytes,
+) -> Result<Json<SettledResponse>, ServiceError> {
+    let body: StartBody = parse_optional_body(&bytes)?;
+    let settle = resolve_settle(&ops, body.settle)?;
+    let snapshot = ops.start_guiding(settle, body.recalibrate).await?;
+    Ok(Json(SettledResponse::from_snapshot(snapshot)))
+}
+
+async fn stop_guiding(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<StateResponse>, ServiceError> {
+    ops.stop().await?;
+    Ok(Json(StateResponse { state: "stopped" }))
+}
+
+async fn pause_guiding(
+    State(ops): State<Arc<GuiderOps>>,
+    bytes: axum::body::Bytes,
+) -> Result<Json<StateResponse>, ServiceError> {
+    let body: PauseBody = parse_optional_body(&bytes)?;
+    ops.pause(body.full).await?;
+    Ok(Json(StateResponse { state: "paused" }))
+}
+
+async fn resume_guiding(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<StateResponse>, ServiceError> {
+    ops.resume().await?;
+    Ok(Json(StateResponse { state: "resumed" }))
+}
+
+async fn dither(
+    State(ops): State<Arc<GuiderOps>>,
+    bytes: axum::body::Bytes,
+) -> Result<Json<SettledResponse>, ServiceError> {
+    let body: DitherBody = parse_required_body(&bytes)?;
+    if !body.amount_px.is_finite() || body.amount_px <= 0.0 {
+        return Err(ServiceError::InvalidRequest(format!(
+            "amount_px must be a positive number of pixels, got {}",
+            body.amount_px
+        )));
+    }
+    let settle = resolve_settle(&ops, body.settle)?;
+    let snapshot = ops.dither(body.amount_px, body.ra_only, settle).await?;
+    Ok(Json(SettledResponse::from_snapshot(snapshot)))
+}
+
+async fn stats(State(ops): State<Arc<GuiderOps>>) -> Result<Json<StatsResponse>, ServiceError> {
+    let stats = ops.stats().await?;
+    Ok(Json(StatsResponse {
+        app_state: stats.app_state.to_string(),
+        guiding: stats.app_state == AppState::Guiding,
+        rms_ra_px: stats.snapshot.rms_ra_px,
+        rms_dec_px: stats.snapshot.rms_dec_px,
+        total_rms_px: stats.snapshot.total_rms_px,
+        snr: stats.snapshot.snr,
+        star_mass: stats.snapshot.star_mass,
+        sample_count: stats.snapshot.sample_count,
+    }))
+}
+
+/// Which calibration to clear; the serde names are the wire contract
+/// (`"mount"` default, `"ao"`, `"both"`).
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ClearCalibrationBody {
+    #[serde(default)]
+    which: ClearTarget,
+}
+
+#[derive(Debug, Default, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum ClearTarget {
+    #[default]
+    Mount,
+    Ao,
+    Both,
+}
+
+impl From<ClearTarget> for CalibrationTarget {
+    fn from(t: ClearTarget) -> Self {
+        match t {
+            ClearTarget::Mount => Self::Mount,
+            ClearTarget::Ao => Self::Ao,
+            ClearTarget::Both => Self::Both,
+        }
+    }
+}
+
+async fn metrics(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<serde_json::Value>, ServiceError> {
+    let metrics = ops.metrics().await?;
+    Ok(Json(serde_json::json!({
+        "guiding": metrics.guiding,
+        "frames": metrics.frames,
+    })))
+}
+
+/// Every slot is present, `null` when the guider does not drive that
+/// device — the same shape `phd2-guider` serves.
+async fn equipment(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<serde_json::Value>, ServiceError> {
+    let equipment = ops.equipment().await?;
+    let slot = |d: &crate::equipment::DeviceStatus| serde_json::json!({ "name": d.name, "connected": d.connected });
+    Ok(Json(serde_json::json!({
+        "camera": slot(&equipment.camera),
+        "mount": slot(&equipment.mount),
+        "aux_mount": null,
+        "ao": null,
+        "rotator": null,
+    })))
+}
+
+async fn clear_calibration(
+    State(ops): State<Arc<GuiderOps>>,
+    bytes: axum::body::Bytes,
+) -> Result<Json<StateResponse>, ServiceError> {
+    let body: ClearCalibrationBody = parse_optional_body(&bytes)?;
+    ops.clear_calibration(body.which.into()).await?;
+    Ok(Json(StateResponse { state: "cleared" }))
+}
+
+async fn reselect_star(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<StateResponse>, ServiceError> {
+    ops.reselect_star().await?;
+    Ok(Json(StateResponse { state: "selected" }))
+}
+
+fn axis_json(algorithm: &AlgorithmConfig) -> serde_json::Value {
+    let params = algorithm.params();
+    serde_json::json!({
+        "min_move_px": params.get("min_move_px"),
+        "aggressiveness": params.get("aggression"),
+        "params": params,
+    })
+}
+
+/// The settings wire shape `phd2-guider` serves. This guider guides on
+/// one star, so `multi_star` is always `false`.
+fn settings_json(tuning: &Tuning) -> serde_json::Value {
+    serde_json::json!({
+        "dec_guide_mode": tuning.dec_guide_mode,
+        "ra": axis_json(&tuning.ra),
+        "dec": axis_json(&tuning.dec),
+        "multi_star": false,
+        "backlash_compensation": tuning.backlash,
+    })
+}
+
+async fn settings(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<serde_json::Value>, ServiceError> {
+    let tuning = ops.settings().await?;
+    Ok(Json(settings_json(&tuning)))
+}
+
+async fn update_settings(
+    State(ops): State<Arc<GuiderOps>>,
+    bytes: axum::body::Bytes,
+) -> Result<Json<serde_json::Value>, ServiceError> {
+    let body: SettingsBody = parse_optional_body(&bytes)?;
+    if body.multi_star == Some(true) {
+        return Err(ServiceError::InvalidRequest(
+            "multi_star: native-guider guides on a single star".to_string(),
+        ));
+    }
+    let tuning = ops
+        .update_settings(SettingsUpdate {
+            dec_guide_mode: body.dec_guide_mode,
+            ra: body.ra.map(Into::into).unwrap_or_default(),
+            dec: body.dec.map(Into::into).unwrap_or_default(),
+            backlash: body.backlash_compensation,
+        })
+        .await?;
+    Ok(Json(settings_json(&tuning)))
+}
+
+/// The 503 means alive-but-degraded (equipment off is the normal
+/// daytime state); `message` is the opaque explanation sentinel displays
+/// on its dashboard without interpreting (docs/services/sentinel.md
+/// §Service Health Supervision).
+async fn health(State(ops): State<Arc<GuiderOps>>) -> impl IntoResponse {
+    let missing = ops.disconnected().await;
+    if missing.is_empty() {
+        (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({
+                "status": "unavailable",
+                "message": format!(
+                    "not connected: {}; reconnecting automatically",
+                    missing.join(", ")
+                ),
+            })),
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn test_ops() -> GuiderOps {
+        crate::testing::SimRig::new().ops()
+    }
+
+    #[test]
+    fn a_start_body_defaults_to_no_recalibrate_and_no_settle_override() {
+        let body: StartBody = serde_json::from_str("{}").unwrap();
+        assert!(!body.recalibrate);
+        assert!(body.settle.is_none());
+    }
+
+    #[test]
+    fn a_whitespace_only_body_parses_as_the_default() {
+        let bytes = axum::body::Bytes::from_static(b" \n\t ");
+        let body: StartBody = parse_optional_body(&bytes).unwrap();
+        assert!(!body.recalibrate);
+        assert!(body.settle.is_none());
+    }
+
+    #[test]
+    fn a_non_positive_settle_threshold_is_rejected_before_any_rpc() {
+        let ops = test_ops();
+        for pixels in [0.0, -1.5, f64::NAN, f64::INFINITY] {
+            let settle = Some(SettleBody {
+                pixels: Some(pixels),
+                time: None,
+                timeout: None,
+            });
+            let err = resolve_settle(&ops, settle).unwrap_err();
+            assert_eq!(err.code(), crate::error::ErrorCode::InvalidRequest);
+        }
+    }
+
+    #[test]
+    fn settle_durations_parse_as_humantime_strings() {
+        let body: StartBody =
+            serde_json::from_str(r#"{"settle": {"pixels": 2.0, "time": "5s", "timeout": "30s"}}"#)
+                .unwrap();
+        let settle = body.settle.unwrap();
+        assert_eq!(settle.pixels, Some(2.0));
+        assert_eq!(settle.time, Some(Duration::from_secs(5)));
+        assert_eq!(settle.timeout, Some(Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn a_clear_calibration_body_maps_every_target() {
+        for (body, expected) in [
+            ("{}", CalibrationTarget::Mount),
+            (r#"{"which": "mount"}"#, CalibrationTarget::Mount),
+            (r#"{"which": "ao"}"#, CalibrationTarget::Ao),
+            (r#"{"which": "both"}"#, CalibrationTarget::Both),
+        ] {
+            let parsed: ClearCalibrationBody = serde_json::from_str(body).unwrap();
+            assert_eq!(
+                CalibrationTarget::from(parsed.which),
+                expected,
+                "body {body}"
+            );
+        }
+        assert!(serde_json::from_str::<ClearCalibrationBody>(r#"{"which": "camera"}"#).is_err());
+    }
+
+    #[test]
+    fn a_dither_body_requires_amount_px() {
+        let err = serde_json::from_str::<DitherBody>(r#"{"ra_only": true}"#).unwrap_err();
+        assert!(err.to_string().contains("amount_px"));
+    }
+
+    #[test]
+    fn unknown_body_fields_are_rejected() {
+        let err = serde_json::from_str::<StartBody>(r#"{"recalibrate": false, "pixels": 1.0}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("pixels"));
+    }
+
+    #[test]
+    fn a_settings_body_parses_every_field() {
+        let body: SettingsBody = serde_json::from_str(
+            r#"{
+                "dec_guide_mode": "North",
+                "ra": { "aggressiveness": 0.6 },
+                "dec": { "min_move_px": 0.3, "params": { "fast_switch": 0 } },
+                "multi_star": false,
+                "backlash_compensation": { "enabled": true, "pulse": "450ms" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(body.dec_guide_mode, Some(DecGuideMode::North));
+        assert_eq!(body.ra.unwrap().aggressiveness, Some(0.6));
+        let dec = body.dec.unwrap();
+        assert_eq!(dec.min_move_px, Some(0.3));
+        assert_eq!(dec.params["fast_switch"], 0.0);
+        assert_eq!(
+            body.backlash_compensation.unwrap().pulse,
+            Duration::from_millis(450)
+        );
+        assert!(serde_json::from_str::<SettingsBody>(r#"{"dec_guide_mode": "auto"}"#).is_err());
+    }
+
+    #[test]
+    fn the_settings_wire_shape_matches_phd2_guiders() {
+        let tuning = Tuning {
+            dec_guide_mode: DecGuideMode::Auto,
+            ra: AlgorithmConfig::Hysteresis {
+                hysteresis: 0.1,
+                aggression: 0.7,
+                min_move_px: 0.15,
+            },
+            dec: AlgorithmConfig::ResistSwitch {
+                aggression: 1.0,
+                min_move_px: 0.2,
+                fast_switch: true,
+            },
+            backlash: BacklashConfig::default(),
+        };
+        let json = settings_json(&tuning);
+        assert_eq!(json["dec_guide_mode"], "Auto");
+        assert_eq!(json["ra"]["min_move_px"], 0.15);
+        assert_eq!(json["ra"]["aggressiveness"], 0.7);
+        assert_eq!(json["ra"]["params"]["hysteresis"], 0.1);
+        assert_eq!(json["dec"]["params"]["fast_switch"], 1.0);
+        assert_eq!(json["multi_star"], false);
+        assert_eq!(
+            json["backlash_compensation"],
+            serde_json::json!({ "enabled": false, "pulse": "0s" })
+        );
+    }
+
+    #[test]
+    fn the_settled_response_serializes_null_rms_when_unsampled() {
+        let response = SettledResponse::from_snapshot(StatsSnapshot {
+            rms_ra_px: None,
+            rms_dec_px: None,
+            total_rms_px: None,
+            snr: None,
+            star_mass: None,
+            sample_count: 0,
+        });
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["state"], "guiding");
+        assert_eq!(json["rms_ra_px"], serde_json::Value::Null);
+        assert_eq!(json["sample_count"], 0);
+    }
+}
@@ -0,0 +1,346 @@
+//! Calibration: how a guide pulse on each mount axis moves the star on
+//! the sensor.
+//!
+//! The star is walked West in equal pulses until it has travelled
+//! `distance_px`, then brought back East; Dec backlash is taken up with
+//! North pulses, and the star is walked North and brought back South.
+//! Each walk yields an angle on the sensor and a rate in pixels per
+//! second of pulse. The guide loop uses the pair to resolve a camera-
+//! frame error into RA and Dec components and each component into a
+//! pulse.
+//!
+//! Each step is tracked from the star's previous position, so one step
+//! must move the star less than `detection.search_radius_px`.
+
+use tokio::time::Duration;
+use tracing::{debug, info};
+
+use crate::config::CalibrationConfig;
+use crate::equipment::GuideDirection;
+use crate::error::ServiceError;
+use crate::guider::Rig;
+use crate::star::StarMeasurement;
+
+/// Travel that shows the North backlash has been taken up.
+const BACKLASH_CLEARED_PX: f64 = 3.0;
+
+/// The star's displacement over one calibration walk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AxisMove {
+    dx: f64,
+    dy: f64,
+    pulsed: Duration,
+}
+
+/// A finished calibration. Angles are the direction, on the sensor, the
+/// star moves under West (RA) and North (Dec) pulses; rates are pixels
+/// per second of pulse.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    pub ra_angle: f64,
+    pub ra_rate: f64,
+    pub dec_angle: f64,
+    pub dec_rate: f64,
+}
+
+impl Calibration {
+    fn from_moves(ra: AxisMove, dec: AxisMove) -> Self {
+        Self {
+            ra_angle: ra.dy.atan2(ra.dx),
+            ra_rate: ra.dx.hypot(ra.dy) / ra.pulsed.as_secs_f64(),
+            dec_angle: dec.dy.atan2(dec.dx),
+            dec_rate: dec.dx.hypot(dec.dy) / dec.pulsed.as_secs_f64(),
+        }
+    }
+
+    /// How far the two axes are from perpendicular, in degrees.
+    #[must_use]
+    pub fn orthogonality_error_deg(&self) -> f64 {
+        let between = (self.dec_angle - self.ra_angle)
+            .to_degrees()
+            .rem_euclid(180.0);
+        (between - 90.0).abs()
+    }
+
+    /// Resolve a camera-frame offset into pixels along the RA (West)
+    /// and Dec (North) axes.
+    #[must_use]
+    pub fn to_mount(&self, dx: f64, dy: f64) -> (f64, f64) {
+        let (ra_sin, ra_cos) = self.ra_angle.sin_cos();
+        let (dec_sin, dec_cos) = self.dec_angle.sin_cos();
+        let det = (self.dec_angle - self.ra_angle).sin();
+        (
+            dx.mul_add(dec_sin, -dy * dec_cos) / det,
+            ra_cos.mul_add(dy, -ra_sin * dx) / det,
+        )
+    }
+
+    /// The inverse of [`Self::to_mount`].
+    #[must_use]
+    pub fn to_camera(&self, ra: f64, dec: f64) -> (f64, f64) {
+        let (ra_sin, ra_cos) = self.ra_angle.sin_cos();
+        let (dec_sin, dec_cos) = self.dec_angle.sin_cos();
+        (
+            ra.mul_add(ra_cos, dec * dec_cos),
+            ra.mul_add(ra_sin, dec * dec_sin),
+        )
+    }
+
+    /// The pulse that moves the star `ra` pixels back along the RA
+    /// axis: East for a star displaced West, and vice versa.
+    #[must_use]
+    pub fn ra_pulse(&self, ra: f64, max_pulse: Duration) -> Option<(GuideDirection, Duration)> {
+        let direction = if ra > 0.0 {
+            GuideDirection::East
+        } else {
+            GuideDirection::West
+        };
+        pulse_for(ra, self.ra_rate, max_pulse).map(|d| (direction, d))
+    }
+
+    /// The Dec counterpart of [`Self::ra_pulse`].
+    #[must_use]
+    pub fn dec_pulse(&self, dec: f64, max_pulse: Duration) -> Option<(GuideDirection, Duration)> {
+        let direction = if dec > 0.0 {
+            GuideDirection::South
+        } else {
+            GuideDirection::North
+        };
+        pulse_for(dec, self.dec_rate, max_pulse).map(|d| (direction, d))
+    }
+}
+
+/// `None` for a correction that rounds to a zero-length pulse.
+fn pulse_for(pixels: f64, rate: f64, max_pulse: Duration) -> Option<Duration> {
+    let ms = (pixels.abs() / rate * 1000.0).round();
+    if !ms.is_finite() || ms < 1.0 {
+        return None;
+    }
+    let max_ms = max_pulse.as_millis() as f64;
+    Some(Duration::from_millis(ms.min(max_ms) as u64))
+}
+
+/// Run a full calibration from `start`, the selected guide star.
+pub(crate) async fn calibrate(
+    rig: &Rig,
+    start: StarMeasurement,
+    config: &CalibrationConfig,
+) -> Result<Calibration, ServiceError> {
+    info!(x = start.x, y = start.y, "calibration starting");
+    let (ra, steps, at) = walk(rig, GuideDirection::West, start, config).await?;
+    let back = walk_back(rig, GuideDirection::East, at, steps, config).await?;
+    let dec_start = clear_backlash(rig, back, config).await?;
+    let (dec, steps, at) = walk(rig, GuideDirection::North, dec_start, config).await?;
+    walk_back(rig, GuideDirection::South, at, steps, config).await?;
+
+    let calibration = Calibration::from_moves(ra, dec);
+    let error = calibration.orthogonality_error_deg();
+    if error > config.max_orthogonality_error_deg {
+        return Err(ServiceError::GuideFailed(format!(
+            "calibration rejected: RA and Dec axes are {error:.1}° from perpendicular (limit {:.1}°); check for Dec backlash or a mount that did not move",
+            config.max_orthogonality_error_deg
+        )));
+    }
+    info!(
+        ra_angle_deg = calibration.ra_angle.to_degrees(),
+        ra_rate = calibration.ra_rate,
+        dec_angle_deg = calibration.dec_angle.to_degrees(),
+        dec_rate = calibration.dec_rate,
+        orthogonality_error_deg = error,
+        "calibration complete"
+    );
+    Ok(calibration)
+}
+
+/// One pulse, then find the star again near where it was.
+async fn step(
+    rig: &Rig,
+    direction: GuideDirection,
+    from: StarMeasurement,
+    config: &CalibrationConfig,
+) -> Result<StarMeasurement, ServiceError> {
+    rig.pulse(direction, config.step).await?;
+    rig.locate(from.x, from.y).await?.ok_or_else(|| {
+        ServiceError::GuideFailed(format!(
+            "star lost during {direction:?} calibration near ({:.1}, {:.1})",
+            from.x, from.y
+        ))
+    })
+}
+
+/// Pulse `direction` until the star is `distance_px` from `start`.
+/// Returns the move, the number of pulses, and where the star ended up.
+async fn walk(
+    rig: &Rig,
+    direction: GuideDirection,
+    start: StarMeasurement,
+    config: &CalibrationConfig,
+) -> Result<(AxisMove, u32, StarMeasurement), ServiceError> {
+    let mut at = start;
+    for steps in 1..=config.max_steps {
+        at = step(rig, direction, at, config).await?;
+        let travelled = at.distance_to(start.x, start.y);
+        debug!(?direction, steps, travelled, "calibration step");
+        if travelled >= config.distance_px {
+            let axis = AxisMove {
+                dx: at.x - start.x,
+                dy: at.y - start.y,
+                pulsed: config.step * steps,
+            };
+            return Ok((axis, steps, at));
+        }
+    }
+    Err(ServiceError::GuideFailed(format!(
+        "{direction:?} calibration failed: the star moved {:.1} px in {} steps of {} (needed {:.1} px)",
+        at.distance_to(start.x, start.y),
+        config.max_steps,
+        humantime::format_duration(config.step),
+        config.distance_px
+    )))
+}
+
+/// Undo a walk with the same number of pulses the other way.
+async fn walk_back(
+    rig: &Rig,
+    direction: GuideDirection,
+    from: StarMeasurement,
+    steps: u32,
+    config: &CalibrationConfig,
+) -> Result<StarMeasurement, ServiceError> {
+    let mut at = from;
+    for _ in 0..steps {
+        at = step(rig, direction, at, config).await?;
+    }
+    Ok(at)
+}
+
+/// Pulse North until the star starts to move, so the Dec walk measures
+/// the drive rather than the gear slack.
+async fn clear_backlash(
+    rig: &Rig,
+    start: StarMeasurement,
+    config: &CalibrationConfig,
+) -> Result<StarMeasurement, ServiceError> {
+    let mut at = start;
+    for _ in 0..config.max_steps {
+        at = step(rig, GuideDirection::North, at, config).await?;
+        if at.distance_to(start.x, start.y) >= BACKLASH_CLEARED_PX {
+            return Ok(at);
+        }
+    }
+    Err(ServiceError::GuideFailed(format!(
+        "Dec backlash not cleared: the star did not move {BACKLASH_CLEARED_PX} px North in {} steps",
+        config.max_steps
+    )))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::testing::SimRig;
+
+    fn approx(a: f64, b: f64, tol: f64) {
+        assert!((a - b).abs() < tol, "{a} !~ {b}");
+    }
+
+    fn calibration(ra_deg: f64, dec_deg: f64) -> Calibration {
+        Calibration {
+            ra_angle: ra_deg.to_radians(),
+            ra_rate: 10.0,
+            dec_angle: dec_deg.to_radians(),
+            dec_rate: 5.0,
+        }
+    }
+
+    #[test]
+    fn mount_and_camera_frames_round_trip() {
+        let cal = calibration(30.0, 115.0);
+        let (ra, dec) = cal.to_mount(3.0, -4.0);
+        let (dx, dy) = cal.to_camera(ra, dec);
+        approx(dx, 3.0, 1e-9);
+        approx(dy, -4.0, 1e-9);
+    }
+
+    #[test]
+    fn an_offset_along_an_axis_resolves_onto_that_axis_only() {
+        let cal = calibration(30.0, 120.0);
+        let (dx, dy) = (
+            2.0 * 30f64.to_radians().cos(),
+            2.0 * 30f64.to_radians().sin(),
+        );
+        let (ra, dec) = cal.to_mount(dx, dy);
+        approx(ra, 2.0, 1e-9);
+        approx(dec, 0.0, 1e-9);
+    }
+
+    #[test]
+    fn orthogonality_error_ignores_axis_handedness() {
+        approx(calibration(0.0, 90.0).orthogonality_error_deg(), 0.0, 1e-9);
+        approx(calibration(0.0, -90.0).orthogonality_error_deg(), 0.0, 1e-9);
+        approx(
+            calibration(10.0, 80.0).orthogonality_error_deg(),
+            20.0,
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn pulses_oppose_the_error_and_are_clipped() {
+        let cal = calibration(0.0, 90.0);
+        let max = Duration::from_millis(2500);
+        assert_eq!(
+            cal.ra_pulse(2.0, max),
+            Some((GuideDirection::East, Duration::from_millis(200)))
+        );
+        assert_eq!(
+            cal.ra_pulse(-2.0, max),
+            Some((GuideDirection::West, Duration::from_millis(200)))
+        );
+        assert_eq!(
+            cal.dec_pulse(1.0, max),
+            Some((GuideDirection::South, Duration::from_millis(200)))
+        );
+        assert_eq!(
+            cal.dec_pulse(-100.0, max),
+            Some((GuideDirection::North, max))
+        );
+        assert_eq!(cal.ra_pulse(0.0, max), None);
+    }
+
+    fn config() -> CalibrationConfig {
+        CalibrationConfig {
+            step: Duration::from_millis(500),
+            distance_px: 15.0,
+            max_steps: 20,
+            max_orthogonality_error_deg: 30.0,
+        }
+    }
+
+    #[tokio::test]
+    async fn calibration_recovers_the_simulated_axes() {
+        let sim = SimRig::new();
+        let rig = sim.rig();
+        let start = rig.select().await.unwrap();
+        let cal = calibrate(&rig, start, &config()).await.unwrap();
+        approx(cal.ra_angle.to_degrees(), SimRig::RA_ANGLE_DEG, 2.0);
+        approx(cal.dec_angle.to_degrees(), SimRig::RA_ANGLE_DEG + 90.0, 2.0);
+        approx(cal.ra_rate, SimRig::RATE_PX_PER_SEC, 0.5);
+        approx(cal.dec_rate, SimRig::RATE_PX_PER_SEC, 0.5);
+        // The walks are undone: only the one backlash step North is left.
+        let (x, y) = sim.star();
+        let one_step = SimRig::RATE_PX_PER_SEC * config().step.as_secs_f64();
+        approx((x - start.x).hypot(y - start.y), one_step, 0.5);
+    }
+
+    #[tokio::test]
+    async fn a_mount_that_does_not_move_fails_calibration() {
+        let sim = SimRig::new();
+        sim.set_frozen(true);
+        let rig = sim.rig();
+        let start = rig.select().await.unwrap();
+        let err = calibrate(&rig, start, &config()).await.unwrap_err();
+        assert!(matches!(err, ServiceError::GuideFailed(_)), "{err:?}");
+        assert!(err.to_string().contains("West calibration failed"), "{err}");
+    }
+}
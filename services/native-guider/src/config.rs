@@ -0,0 +1,824 @@
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::Duration;
+
+use rp_auth::config::ClientAuthConfig;
+use serde::{Deserialize, Serialize};
+
+pub use rusty_photon_server_config::ServerConfig;
+
+use crate::error::{NativeGuiderError, Result};
+
+/// native-guider's config file: the guide camera and mount on their
+/// Alpaca servers, how to find the guide star, and how hard to correct.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GuiderConfig {
+    /// The HTTP server for the guiding API and `/health`. Config files
+    /// without a `server` block keep loading via the default.
+    #[serde(default = "default_server")]
+    pub server: ServerConfig,
+    /// The guide camera. No default: it is the rig's own device.
+    pub camera: DeviceConfig,
+    /// The mount that receives the guide pulses. No default, as `camera`.
+    pub mount: DeviceConfig,
+    /// PEM CA path used to trust TLS-enabled Alpaca servers. Per the
+    /// ADR-017 policy, device `auth` is only useful over https.
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    /// Guide exposure length (humantime, default `"2s"`).
+    #[serde(default = "default_exposure", with = "humantime_serde")]
+    pub exposure: Duration,
+    /// Guide-star detection and tracking.
+    #[serde(default)]
+    pub detection: DetectionConfig,
+    /// The calibration run that learns how pulses move the star.
+    #[serde(default)]
+    pub calibration: CalibrationConfig,
+    /// RA guide algorithm (default `hysteresis`).
+    #[serde(default = "default_ra_algorithm")]
+    pub ra: AlgorithmConfig,
+    /// Dec guide algorithm (default `resist_switch`).
+    #[serde(default = "default_dec_algorithm")]
+    pub dec: AlgorithmConfig,
+    /// Which Dec corrections are sent (default `Auto`, both ways).
+    #[serde(default)]
+    pub dec_guide_mode: DecGuideMode,
+    /// Extra Dec pulse after the axis reverses (default off).
+    #[serde(default)]
+    pub backlash_compensation: BacklashConfig,
+    /// Longest single guide pulse on either axis (humantime, default
+    /// `"2500ms"`). A larger correction is clipped, not split.
+    #[serde(default = "default_max_pulse", with = "humantime_serde")]
+    pub max_pulse: Duration,
+    /// Default settle criteria, overridable per request.
+    #[serde(default)]
+    pub settling: SettleParams,
+    /// How long `guiding/stop` waits for the guide loop to wind down
+    /// (humantime, default `"10s"`).
+    #[serde(default = "default_stop_timeout", with = "humantime_serde")]
+    pub stop_timeout: Duration,
+    /// Interval between attempts to reach the camera and mount while
+    /// either is unreachable (humantime, default `"5s"`).
+    #[serde(default = "default_reconnect_interval", with = "humantime_serde")]
+    pub reconnect_interval: Duration,
+}
+
+/// One Alpaca device: its server URL and its index among that server's
+/// devices of the same type.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceConfig {
+    pub alpaca_url: String,
+    #[serde(default)]
+    pub device_number: u32,
+    /// HTTP Basic credentials for the Alpaca server.
+    #[serde(default)]
+    pub auth: Option<ClientAuthConfig>,
+}
+
+/// Guide-star detection, passed to rp-imaging's star detector.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DetectionConfig {
+    /// Detection threshold in background sigmas (default 5.0).
+    #[serde(default = "default_threshold_sigma")]
+    pub threshold_sigma: f64,
+    /// Minimum star component area in pixels (default 3).
+    #[serde(default = "default_min_area")]
+    pub min_area: usize,
+    /// Maximum star component area in pixels (default 400).
+    #[serde(default = "default_max_area")]
+    pub max_area: usize,
+    /// Lowest SNR a star may have to be selected (default 10).
+    #[serde(default = "default_min_snr")]
+    pub min_snr: f64,
+    /// Half-width of the box the star is searched for on each frame,
+    /// around its last position (default 15 px). Also the margin a
+    /// selected star must keep from the frame edges.
+    #[serde(default = "default_search_radius_px")]
+    pub search_radius_px: f64,
+    /// ADU at which the guide camera saturates. Stars with clipped
+    /// pixels are never selected; unset skips the check.
+    #[serde(default)]
+    pub saturation_adu: Option<u32>,
+}
+
+impl Default for DetectionConfig {
+    fn default() -> Self {
+        Self {
+            threshold_sigma: default_threshold_sigma(),
+            min_area: default_min_area(),
+            max_area: default_max_area(),
+            min_snr: default_min_snr(),
+            search_radius_px: default_search_radius_px(),
+            saturation_adu: None,
+        }
+    }
+}
+
+/// Calibration: the star is walked West then North in equal pulses
+/// until it has moved `distance_px` on each axis, and brought back.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CalibrationConfig {
+    /// Length of each calibration pulse (humantime, default `"1s"`).
+    #[serde(default = "default_calibration_step", with = "humantime_serde")]
+    pub step: Duration,
+    /// Star travel required on each axis (default 25 px).
+    #[serde(default = "default_calibration_distance_px")]
+    pub distance_px: f64,
+    /// Pulses per axis before calibration gives up (default 60).
+    #[serde(default = "default_calibration_max_steps")]
+    pub max_steps: u32,
+    /// How far the RA and Dec axes may be from perpendicular on the
+    /// sensor before the calibration is rejected (default 30°).
+    #[serde(default = "default_max_orthogonality_error_deg")]
+    pub max_orthogonality_error_deg: f64,
+}
+
+impl Default for CalibrationConfig {
+    fn default() -> Self {
+        Self {
+            step: default_calibration_step(),
+            distance_px: default_calibration_distance_px(),
+            max_steps: default_calibration_max_steps(),
+            max_orthogonality_error_deg: default_max_orthogonality_error_deg(),
+        }
+    }
+}
+
+/// One axis' guide algorithm, tagged by `algorithm`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "algorithm", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AlgorithmConfig {
+    /// Blend each correction with the previous one; the RA default.
+    Hysteresis {
+        /// Weight of the previous correction, in `[0, 1)` (default 0.1).
+        #[serde(default = "default_hysteresis")]
+        hysteresis: f64,
+        /// Fraction of the blended error corrected (default 0.7).
+        #[serde(default = "default_ra_aggression")]
+        aggression: f64,
+        /// Errors below this are left alone (default 0.15 px).
+        #[serde(default = "default_ra_min_move_px")]
+        min_move_px: f64,
+    },
+    /// Only correct toward the side the recent errors agree on; the
+    /// Dec default, since Dec drift is one-sided and backlash punishes
+    /// reversals.
+    ResistSwitch {
+        /// Fraction of the error corrected (default 1.0).
+        #[serde(default = "default_dec_aggression")]
+        aggression: f64,
+        /// Errors below this are left alone (default 0.2 px).
+        #[serde(default = "default_dec_min_move_px")]
+        min_move_px: f64,
+        /// Switch sides at once on a large opposite error (default true).
+        #[serde(default = "default_fast_switch")]
+        fast_switch: bool,
+    },
+}
+
+impl AlgorithmConfig {
+    /// The algorithm's numeric parameters by their config names; a
+    /// flag reads as `1.0` / `0.0`.
+    #[must_use]
+    pub fn params(&self) -> BTreeMap<&'static str, f64> {
+        match *self {
+            Self::Hysteresis {
+                hysteresis,
+                aggression,
+                min_move_px,
+            } => BTreeMap::from([
+                ("hysteresis", hysteresis),
+                ("aggression", aggression),
+                ("min_move_px", min_move_px),
+            ]),
+            Self::ResistSwitch {
+                aggression,
+                min_move_px,
+                fast_switch,
+            } => BTreeMap::from([
+                ("aggression", aggression),
+                ("min_move_px", min_move_px),
+                ("fast_switch", if fast_switch { 1.0 } else { 0.0 }),
+            ]),
+        }
+    }
+
+    /// Set one parameter by its config name. Fails, naming the
+    /// parameter, when the algorithm has no such parameter or a flag
+    /// is given something other than `0` or `1`; range checks are
+    /// [`Self::validate`]'s.
+    pub fn set_param(&mut self, name: &str, value: f64) -> std::result::Result<(), String> {
+        match (self, name) {
+            (Self::Hysteresis { hysteresis, .. }, "hysteresis") => *hysteresis = value,
+            (
+                Self::Hysteresis { aggression, .. } | Self::ResistSwitch { aggression, .. },
+                "aggression",
+            ) => *aggression = value,
+            (
+                Self::Hysteresis { min_move_px, .. } | Self::ResistSwitch { min_move_px, .. },
+                "min_move_px",
+            ) => *min_move_px = value,
+            (Self::ResistSwitch { fast_switch, .. }, "fast_switch") => {
+                *fast_switch = if value == 0.0 {
+                    false
+                } else if value == 1.0 {
+                    true
+                } else {
+                    return Err(format!("fast_switch must be 0 or 1, got {value}"));
+                };
+            }
+            (algorithm, name) => {
+                return Err(format!(
+                    "the {} algorithm has no parameter {name}",
+                    algorithm.name()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// The `algorithm` tag.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Hysteresis { .. } => "hysteresis",
+            Self::ResistSwitch { .. } => "resist_switch",
+        }
+    }
+
+    pub(crate) fn validate(&self, axis: &str) -> Result<()> {
+        let invalid = |field: &str, rule: &str| {
+            Err(NativeGuiderError::Config(format!(
+                "{axis}.{field} must be {rule}"
+            )))
+        };
+        let (aggression, min_move_px) = match *self {
+            Self::Hysteresis {
+                hysteresis,
+                aggression,
+                min_move_px,
+            } => {
+                if !(0.0..1.0).contains(&hysteresis) {
+                    return invalid("hysteresis", "in [0, 1)");
+                }
+                (aggression, min_move_px)
+            }
+            Self::ResistSwitch {
+                aggression,
+                min_move_px,
+                ..
+            } => (aggression, min_move_px),
+        };
+        if !aggression.is_finite() || aggression <= 0.0 || aggression > 2.0 {
+            return invalid("aggression", "in (0, 2]");
+        }
+        if !min_move_px.is_finite() || min_move_px < 0.0 {
+            return invalid("min_move_px", ">= 0");
+        }
+        Ok(())
+    }
+}
+
+/// Which Dec corrections the guide loop sends, in PHD2's spelling. A
+/// one-sided mode keeps a mount with Dec backlash from ever reversing
+/// the axis; the mount must then drift toward the guided side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum DecGuideMode {
+    Off,
+    #[default]
+    Auto,
+    North,
+    South,
+}
+
+/// Dec backlash compensation: `pulse` is added to the first Dec
+/// correction after the axis reverses, to take up the gear slack.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BacklashConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Length of the extra pulse (humantime, default `"0s"`).
+    #[serde(default, with = "humantime_serde")]
+    pub pulse: Duration,
+}
+
+impl BacklashConfig {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.enabled && self.pulse.is_zero() {
+            return Err(NativeGuiderError::Config(
+                "backlash_compensation.pulse must be > 0 when enabled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Settle criteria: the star must stay within `pixels` of the lock
+/// position for `time`, and must do so before `timeout`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SettleParams {
+    #[serde(default = "default_settle_pixels")]
+    pub pixels: f64,
+    #[serde(default = "default_settle_time", with = "humantime_serde")]
+    pub time: Duration,
+    #[serde(default = "default_settle_timeout", with = "humantime_serde")]
+    pub timeout: Duration,
+}
+
+impl Default for SettleParams {
+    fn default() -> Self {
+        Self {
+            pixels: default_settle_pixels(),
+            time: default_settle_time(),
+            timeout: default_settle_timeout(),
+        }
+    }
+}
+
+impl GuiderConfig {
+    pub fn ca_cert_path(&self) -> Option<&Path> {
+        self.ca_cert.as_deref().map(Path::new)
+    }
+
+    /// Reject settings the guide loop cannot run with, naming the
+    /// offending field.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |msg: &str| Err(NativeGuiderError::Config(msg.to_string()));
+        if self.exposure.is_zero() {
+            return invalid("exposure must be > 0");
+        }
+        let d = &self.detection;
+        if d.min_area == 0 || d.min_area > d.max_area {
+            return invalid("detection.min_area must be >= 1 and <= detection.max_area");
+        }
+        if !d.threshold_sigma.is_finite() || d.threshold_sigma <= 0.0 {
+            return invalid("detection.threshold_sigma must be > 0");
+        }
+        if !d.min_snr.is_finite() || d.min_snr < 0.0 {
+            return invalid("detection.min_snr must be >= 0");
+        }
+        if !d.search_radius_px.is_finite() || d.search_radius_px < 2.0 {
+            return invalid("detection.search_radius_px must be >= 2");
+        }
+        let c = &self.calibration;
+        if c.step.is_zero() {
+            return invalid("calibration.step must be > 0");
+        }
+        if !c.distance_px.is_finite() || c.distance_px <= 0.0 {
+            return invalid("calibration.distance_px must be > 0");
+        }
+        if c.max_steps == 0 {
+            return invalid("calibration.max_steps must be >= 1");
+        }
+        if !(c.max_orthogonality_error_deg > 0.0 && c.max_orthogonality_error_deg < 90.0) {
+            return invalid("calibration.max_orthogonality_error_deg must be in (0, 90)");
+        }
+        self.ra.validate("ra")?;
+        self.dec.validate("dec")?;
+        self.backlash_compensation.validate()?;
+        if self.max_pulse.is_zero() {
+            return invalid("max_pulse must be > 0");
+        }
+        if !self.settling.pixels.is_finite() || self.settling.pixels <= 0.0 {
+            return invalid("settling.pixels must be > 0");
+        }
+        Ok(())
+    }
+}
+
+/// native-guider's default `server` block when the config omits it:
+/// port 11132 on all interfaces, plain HTTP.
+pub(crate) fn default_server() -> ServerConfig {
+    ServerConfig::new(11132)
+}
+
+/// CLI overrides layered over the file config after load: `--port` and
+/// `--bind-address` pin `server.port` / `server.bind_address` over whatever
+/// the file (or the `default_server()` fallback) supplied.
+#[derive(Debug, Clone, Default)]
+pub struct CliOverrides {
+    /// `--port` → `server.port`.
+    pub port: Option<u16>,
+    /// `--bind-address` → `server.bind_address`.
+    pub bind_address: Option<IpAddr>,
+}
+
+impl CliOverrides {
+    /// Apply the overrides onto `config` in place.
+    pub const fn apply(&self, config: &mut GuiderConfig) {
+        if let Some(port) = self.port {
+            config.server.port = port;
+        }
+        if let Some(bind_address) = self.bind_address {
+            config.server.bind_address = bind_address;
+        }
+    }
+}
+
+const fn default_exposure() -> Duration {
+    Duration::from_secs(2)
+}
+
+const fn default_threshold_sigma() -> f64 {
+    5.0
+}
+
+const fn default_min_area() -> usize {
+    3
+}
+
+const fn default_max_area() -> usize {
+    400
+}
+
+const fn default_min_snr() -> f64 {
+    10.0
+}
+
+const fn default_search_radius_px() -> f64 {
+    15.0
+}
+
+const fn default_calibration_step() -> Duration {
+    Duration::from_secs(1)
+}
+
+const fn default_calibration_distance_px() -> f64 {
+    25.0
+}
+
+const fn default_calibration_max_steps() -> u32 {
+    60
+}
+
+const fn default_max_orthogonality_error_deg() -> f64 {
+    30.0
+}
+
+const fn default_ra_algorithm() -> AlgorithmConfig {
+    AlgorithmConfig::Hysteresis {
+        hysteresis: default_hysteresis(),
+        aggression: default_ra_aggression(),
+        min_move_px: default_ra_min_move_px(),
+    }
+}
+
+const fn default_dec_algorithm() -> AlgorithmConfig {
+    AlgorithmConfig::ResistSwitch {
+        aggression: default_dec_aggression(),
+        min_move_px: default_dec_min_move_px(),
+        fast_switch: default_fast_switch(),
+    }
+}
+
+const fn default_hysteresis() -> f64 {
+    0.1
+}
+
+const fn default_ra_aggression() -> f64 {
+    0.7
+}
+
+const fn default_ra_min_move_px() -> f64 {
+    0.15
+}
+
+const fn default_dec_aggression() -> f64 {
+    1.0
+}
+
+const fn default_dec_min_move_px() -> f64 {
+    0.2
+}
+
+const fn default_fast_switch() -> bool {
+    true
+}
+
+const fn default_max_pulse() -> Duration {
+    Duration::from_millis(2500)
+}
+
+/// PHD2's own default settle threshold, so a rig moving between the
+/// two guiders settles the same way.
+const fn default_settle_pixels() -> f64 {
+    1.5
+}
+
+const fn default_settle_time() -> Duration {
+    Duration::from_secs(10)
+}
+
+const fn default_settle_timeout() -> Duration {
+    Duration::from_mins(1)
+}
+
+const fn default_stop_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+const fn default_reconnect_interval() -> Duration {
+    Duration::from_secs(5)
+}
+
+pub fn load_config(path: &Path) -> Result<GuiderConfig> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        NativeGuiderError::Config(format!(
+            "failed to read config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let config: GuiderConfig = serde_json::from_str(&contents).map_err(|e| {
+        NativeGuiderError::Config(format!(
+            "failed to parse config file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    config.validate()?;
+    Ok(config)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const MINIMAL: &str = r#"{
+        "camera": { "alpaca_url": "http://127.0.0.1:11111" },
+        "mount": { "alpaca_url": "http://127.0.0.1:11112" }
+    }"#;
+
+    fn with(extra: &str) -> String {
+        format!(
+            r#"{{
+                "camera": {{ "alpaca_url": "http://127.0.0.1:11111" }},
+                "mount": {{ "alpaca_url": "http://127.0.0.1:11112" }},
+                {extra}
+            }}"#
+        )
+    }
+
+    #[test]
+    fn deserialize_config_with_defaults() {
+        let config: GuiderConfig = serde_json::from_str(MINIMAL).unwrap();
+        assert_eq!(config.camera.device_number, 0);
+        assert!(config.camera.auth.is_none());
+        assert!(config.ca_cert_path().is_none());
+        assert_eq!(config.exposure, Duration::from_secs(2));
+        assert_eq!(config.detection, DetectionConfig::default());
+        assert_eq!(config.calibration, CalibrationConfig::default());
+        assert_eq!(config.ra, default_ra_algorithm());
+        assert_eq!(config.dec, default_dec_algorithm());
+        assert_eq!(config.dec_guide_mode, DecGuideMode::Auto);
+        assert!(!config.backlash_compensation.enabled);
+        assert_eq!(config.max_pulse, Duration::from_millis(2500));
+        assert_eq!(config.settling, SettleParams::default());
+        assert_eq!(config.stop_timeout, Duration::from_secs(10));
+        assert_eq!(config.reconnect_interval, Duration::from_secs(5));
+        // A config without a `server` block keeps loading via the default.
+        assert_eq!(config.server.port, 11132);
+        assert_eq!(config.server.bind_address.to_string(), "0.0.0.0");
+        assert!(config.server.tls.is_none());
+        assert!(config.server.auth.is_none());
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn deserialize_config_with_overrides() {
+        let json = r#"{
+            "server": { "port": 12000, "bind_address": "127.0.0.1" },
+            "camera": {
+                "alpaca_url": "https://guidecam.local:11111",
+                "device_number": 1,
+                "auth": { "username": "observatory", "password": "secret" }
+            },
+            "mount": { "alpaca_url": "http://127.0.0.1:11112", "device_number": 2 },
+            "ca_cert": "/etc/rusty-photon/ca.pem",
+            "exposure": "1500ms",
+            "detection": { "min_snr": 6.0, "saturation_adu": 65000 },
+            "calibration": { "step": "750ms", "distance_px": 20.0 },
+            "ra": { "algorithm": "resist_switch", "aggression": 0.8 },
+            "dec": { "algorithm": "hysteresis", "hysteresis": 0.2 },
+            "dec_guide_mode": "North",
+            "backlash_compensation": { "enabled": true, "pulse": "450ms" },
+            "settling": { "pixels": 0.8, "time": "5s" }
+        }"#;
+        let config: GuiderConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(config.server.socket_addr().to_string(), "127.0.0.1:12000");
+        assert_eq!(config.camera.device_number, 1);
+        assert_eq!(config.camera.auth.as_ref().unwrap().username, "observatory");
+        assert_eq!(config.mount.device_number, 2);
+        assert_eq!(
+            config.ca_cert_path(),
+            Some(Path::new("/etc/rusty-photon/ca.pem"))
+        );
+        assert_eq!(config.exposure, Duration::from_millis(1500));
+        assert!((config.detection.min_snr - 6.0).abs() < f64::EPSILON);
+        assert_eq!(config.detection.saturation_adu, Some(65000));
+        // Unset detection fields keep their defaults.
+        assert_eq!(config.detection.max_area, 400);
+        assert_eq!(config.calibration.step, Duration::from_millis(750));
+        assert_eq!(config.calibration.max_steps, 60);
+        assert_eq!(
+            config.ra,
+            AlgorithmConfig::ResistSwitch {
+                aggression: 0.8,
+                min_move_px: 0.2,
+                fast_switch: true,
+            }
+        );
+        assert_eq!(
+            config.dec,
+            AlgorithmConfig::Hysteresis {
+                hysteresis: 0.2,
+                aggression: 0.7,
+                min_move_px: 0.15,
+            }
+        );
+        assert_eq!(config.dec_guide_mode, DecGuideMode::North);
+        assert_eq!(
+            config.backlash_compensation,
+            BacklashConfig {
+                enabled: true,
+                pulse: Duration::from_millis(450),
+            }
+        );
+        assert_eq!(config.settling.time, Duration::from_secs(5));
+        assert_eq!(config.settling.timeout, Duration::from_mins(1));
+        config.validate().unwrap();
+    }
+
+    #[test]
+    fn camera_and_mount_are_required() {
+        let err = serde_json::from_str::<GuiderConfig>(
+            r#"{"camera": {"alpaca_url": "http://127.0.0.1:11111"}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("mount"), "{err}");
+    }
+
+    #[test]
+    fn an_unknown_algorithm_is_rejected() {
+        let err = serde_json::from_str::<GuiderConfig>(&with(r#""ra": {"algorithm": "lowpass"}"#))
+            .unwrap_err();
+        assert!(err.to_string().contains("lowpass"), "{err}");
+    }
+
+    #[test]
+    fn validate_names_the_offending_setting() {
+        let cases = [
+            (r#""exposure": "0s""#, "exposure"),
+            (
+                r#""detection": {"min_area": 50, "max_area": 40}"#,
+                "detection.min_area",
+            ),
+            (
+                r#""detection": {"threshold_sigma": 0}"#,
+                "detection.threshold_sigma",
+            ),
+            (r#""detection": {"min_snr": -1}"#, "detection.min_snr"),
+            (
+                r#""detection": {"search_radius_px": 1}"#,
+                "detection.search_radius_px",
+            ),
+            (r#""calibration": {"step": "0s"}"#, "calibration.step"),
+            (
+                r#""calibration": {"distance_px": 0}"#,
+                "calibration.distance_px",
+            ),
+            (
+                r#""calibration": {"max_steps": 0}"#,
+                "calibration.max_steps",
+            ),
+            (
+                r#""calibration": {"max_orthogonality_error_deg": 90}"#,
+                "calibration.max_orthogonality_error_deg",
+            ),
+            (
+                r#""ra": {"algorithm": "hysteresis", "hysteresis": 1.0}"#,
+                "ra.hysteresis",
+            ),
+            (
+                r#""dec": {"algorithm": "resist_switch", "aggression": 0}"#,
+                "dec.aggression",
+            ),
+            (
+                r#""dec": {"algorithm": "resist_switch", "min_move_px": -0.1}"#,
+                "dec.min_move_px",
+            ),
+            (
+                r#""backlash_compensation": {"enabled": true}"#,
+                "backlash_compensation.pulse",
+            ),
+            (r#""max_pulse": "0s""#, "max_pulse"),
+            (r#""settling": {"pixels": 0}"#, "settling.pixels"),
+        ];
+        for (extra, field) in cases {
+            let json = with(extra);
+            let config: GuiderConfig = serde_json::from_str(&json).unwrap();
+            let err = config.validate().unwrap_err();
+            assert!(err.to_string().contains(field), "{extra}: {err}");
+        }
+    }
+
+    #[test]
+    fn algorithm_parameters_are_set_by_their_config_names() {
+        let mut ra = default_ra_algorithm();
+        ra.set_param("hysteresis", 0.3).unwrap();
+        ra.set_param("min_move_px", 0.25).unwrap();
+        assert_eq!(ra.params()["hysteresis"], 0.3);
+        assert_eq!(ra.params()["min_move_px"], 0.25);
+        let err = ra.set_param("fast_switch", 0.0).unwrap_err();
+        assert!(
+            err.contains("hysteresis algorithm has no parameter fast_switch"),
+            "{err}"
+        );
+
+        let mut dec = default_dec_algorithm();
+        assert_eq!(dec.params()["fast_switch"], 1.0);
+        dec.set_param("fast_switch", 0.0).unwrap();
+        assert_eq!(dec.params()["fast_switch"], 0.0);
+        assert!(dec.set_param("fast_switch", 0.5).is_err());
+    }
+
+    #[test]
+    fn cli_overrides_pin_port_and_bind_address() {
+        let mut config: GuiderConfig = serde_json::from_str(MINIMAL).unwrap();
+        let overrides = CliOverrides {
+            port: Some(12345),
+            bind_address: Some("127.0.0.1".parse().unwrap()),
+        };
+        overrides.apply(&mut config);
+        assert_eq!(config.server.socket_addr().to_string(), "127.0.0.1:12345");
+    }
+
+    #[test]
+    fn load_config_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("native-guider.json");
+        std::fs::write(&path, MINIMAL).unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.camera.alpaca_url, "http://127.0.0.1:11111");
+    }
+
+    #[test]
+    fn load_config_runs_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("native-guider.json");
+        std::fs::write(&path, with(r#""exposure": "0s""#)).unwrap();
+        let err = load_config(&path).unwrap_err();
+        assert!(err.to_string().contains("exposure"), "{err}");
+    }
+
+    #[test]
+    fn load_config_missing_file() {
+        let err = load_config(Path::new("/nonexistent/native-guider.json")).unwrap_err();
+        assert!(err.to_string().contains("failed to read config file"));
+    }
+
+    #[test]
+    fn load_config_invalid_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("native-guider.json");
+        std::fs::write(&path, "not valid json").unwrap();
+        let err = load_config(&path).unwrap_err();
+        assert!(err.to_string().contains("failed to parse config file"));
+    }
+
+    #[test]
+    fn config_rejects_unknown_field() {
+        let err = serde_json::from_str::<GuiderConfig>(&with(r#""aggression": 0.5"#)).unwrap_err();
+        assert!(err.to_string().contains("aggression"), "{err}");
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod doctor_toml_parity {
+    use rusty_photon_server_config::doctor_toml::{parse, ServerClass};
+
+    use super::default_server;
+
+    /// `pkg/doctor.toml` is this service's catalog entry for
+    /// `rusty-photon-doctor` and must match the config defaults
+    /// (docs/services/doctor.md §The derived catalog).
+    #[test]
+    fn pkg_doctor_toml_matches_config_defaults() {
+        let meta = parse(include_str!("../pkg/doctor.toml")).unwrap();
+        assert_eq!(meta.port, default_server().port);
+        assert_eq!(meta.class, ServerClass::Core);
+        assert!(
+            meta.config_gated,
+            "native-guider needs the rig's guide camera and mount"
+        );
+    }
+}
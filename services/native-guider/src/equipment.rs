@@ -0,0 +1,410 @@
+//! The guide camera and mount behind narrow traits, and their ASCOM
+//! Alpaca implementations.
+//!
+//! The guide loop only ever exposes a frame and pulses the mount, so
+//! it sees two small `#[async_trait]` traits instead of the full
+//! `ascom_alpaca` device APIs — which keeps the synthetic rigs in the
+//! unit tests tiny. Errors are `String`s; the caller decides whether a
+//! failure is unreachable equipment or a guiding failure.
+//!
+//! Both Alpaca devices are resolved lazily on their server (by index
+//! among the devices of their type, like every other Alpaca client in
+//! the workspace) and connected by [`GuideCamera::connect`] /
+//! [`GuideMount::connect`]. The guide camera is this service's own; the
+//! mount is usually shared with `rp`, for which `Connected = true` is
+//! idempotent.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use ascom_alpaca::api::camera::CameraState;
+use ascom_alpaca::api::{Camera, Telescope, TypedDevice};
+use ascom_alpaca::Client;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use ndarray::Array2;
+use rp_auth::config::ClientAuthConfig;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+use crate::config::DeviceConfig;
+
+/// Deadline for resolving a device on its Alpaca server.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Per-request connect and read timeouts, as in rp's Alpaca client.
+const ALPACA_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const ALPACA_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long past the exposure a frame may take to read out.
+const READOUT_GRACE: Duration = Duration::from_secs(30);
+
+/// How long past its duration a pulse may keep `IsPulseGuiding` set.
+const PULSE_GRACE: Duration = Duration::from_secs(5);
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// A guide pulse direction, in the mount's frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GuideDirection {
+    North,
+    South,
+    East,
+    West,
+}
+
+impl From<GuideDirection> for ascom_alpaca::api::telescope::GuideDirection {
+    fn from(direction: GuideDirection) -> Self {
+        match direction {
+            GuideDirection::North => Self::North,
+            GuideDirection::South => Self::South,
+            GuideDirection::East => Self::East,
+            GuideDirection::West => Self::West,
+        }
+    }
+}
+
+/// One equipment slot of `GET /api/v1/equipment`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceStatus {
+    pub name: String,
+    pub connected: bool,
+}
+
+#[async_trait]
+pub trait GuideCamera: Send + Sync {
+    /// Resolve the camera on its server and connect it.
+    async fn connect(&self) -> Result<(), String>;
+
+    /// Name and live connection state.
+    async fn status(&self) -> DeviceStatus;
+
+    /// Expose one frame and download it, indexed `[x, y]`.
+    async fn capture(&self, exposure: Duration) -> Result<Array2<i32>, String>;
+}
+
+#[async_trait]
+pub trait GuideMount: Send + Sync {
+    /// Resolve the mount on its server, connect it, and check that it
+    /// can pulse guide.
+    async fn connect(&self) -> Result<(), String>;
+
+    /// Name and live connection state.
+    async fn status(&self) -> DeviceStatus;
+
+    /// Issue one guide pulse and return once the mount has finished it.
+    async fn pulse(&self, direction: GuideDirection, duration: Duration) -> Result<(), String>;
+}
+
+/// Build an Alpaca client with per-request timeouts, optional HTTP
+/// Basic Auth and optional CA trust — rp's `build_alpaca_client`.
+fn build_alpaca_client(
+    url: &str,
+    auth: Option<&ClientAuthConfig>,
+    ca_cert_path: Option<&std::path::Path>,
+) -> Result<Client, Box<dyn std::error::Error + Send + Sync>> {
+    let mut builder = rusty_photon_tls::client::client_builder(ca_cert_path)?
+        .user_agent("rusty-photon-native-guider")
+        .connect_timeout(ALPACA_CONNECT_TIMEOUT)
+        .read_timeout(ALPACA_READ_TIMEOUT);
+    if let Some(a) = auth {
+        let encoded = BASE64.encode(format!("{}:{}", a.username, a.password));
+        // Keeps the credential out of the client's `Debug` output.
+        let mut header_value: reqwest::header::HeaderValue = format!("Basic {encoded}").parse()?;
+        header_value.set_sensitive(true);
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("authorization", header_value);
+        builder = builder.default_headers(headers);
+    }
+    Ok(Client::new_with_client(url, builder.build()?)?)
+}
+
+/// List the server's devices, bounded by [`DISCOVERY_TIMEOUT`].
+async fn discover(client: &Client) -> Result<Vec<TypedDevice>, String> {
+    match tokio::time::timeout(DISCOVERY_TIMEOUT, client.get_devices()).await {
+        Ok(Ok(devices)) => Ok(devices.into_iter().collect()),
+        Ok(Err(e)) => Err(e.to_string()),
+        Err(_) => Err(format!(
+            "device discovery timed out after {DISCOVERY_TIMEOUT:?}"
+        )),
+    }
+}
+
+/// A device handle plus the name it reported when it was resolved.
+struct Resolved<T: ?Sized> {
+    device: Arc<T>,
+    name: String,
+}
+
+pub struct AlpacaGuideCamera {
+    client: Client,
+    label: String,
+    device_number: u32,
+    resolved: RwLock<Option<Resolved<dyn Camera>>>,
+}
+
+impl AlpacaGuideCamera {
+    pub fn from_config(
+        config: &DeviceConfig,
+        ca_cert_path: Option<&std::path::Path>,
+    ) -> Result<Self, String> {
+        let client = build_alpaca_client(&config.alpaca_url, config.auth.as_ref(), ca_cert_path)
+            .map_err(|e| format!("camera client: {e}"))?;
+        Ok(Self {
+            client,
+            label: format!("camera {} at {}", config.device_number, config.alpaca_url),
+            device_number: config.device_number,
+            resolved: RwLock::new(None),
+        })
+    }
+
+    async fn device(&self) -> Result<Arc<dyn Camera>, String> {
+        self.resolved
+            .read()
+            .await
+            .as_ref()
+            .map(|r| Arc::clone(&r.device))
+            .ok_or_else(|| format!("{} is not connected", self.label))
+    }
+}
+
+#[async_trait]
+impl GuideCamera for AlpacaGuideCamera {
+    async fn connect(&self) -> Result<(), String> {
+        let camera = discover(&self.client)
+            .await?
+            .into_iter()
+            .filter_map(|d| match d {
+                TypedDevice::Camera(c) => Some(c),
+                _ => None,
+            })
+            .nth(self.device_number as usize)
+            .ok_or_else(|| format!("{} not found", self.label))?;
+        camera
+            .set_connected(true)
+            .await
+            .map_err(|e| format!("failed to connect {}: {e}", self.label))?;
+        let name = camera.name().await.unwrap_or_else(|_| self.label.clone());
+        *self.resolved.write().await = Some(Resolved {
+            device: camera,
+            name,
+        });
+        Ok(())
+    }
+
+    async fn status(&self) -> DeviceStatus {
+        let guard = self.resolved.read().await;
+        match guard.as_ref() {
+            Some(r) => DeviceStatus {
+                name: r.name.clone(),
+                connected: r.device.connected().await.unwrap_or(false),
+            },
+            None => DeviceStatus {
+                name: self.label.clone(),
+                connected: false,
+            },
+        }
+    }
+
+    async fn capture(&self, exposure: Duration) -> Result<Array2<i32>, String> {
+        let camera = self.device().await?;
+        camera
+            .start_exposure(exposure, true)
+            .await
+            .map_err(|e| format!("failed to start exposure: {e}"))?;
+
+        // A failed exposure leaves `ImageReady` false forever, so the
+        // `Error` state is terminal and the deadline backstops a camera
+        // wedged in `Exposing` (the same guard as rp's capture).
+        let deadline = Instant::now() + exposure + READOUT_GRACE;
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            match camera.image_ready().await {
+                Ok(true) => break,
+                Ok(false) => {
+                    if let Ok(CameraState::Error) = camera.camera_state().await {
+                        return Err("exposure failed: camera reported error state".to_string());
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!(
+                            "timeout waiting for image_ready after {:?}",
+                            exposure + READOUT_GRACE
+                        ));
+                    }
+                }
+                Err(e) => return Err(format!("error checking image ready: {e}")),
+            }
+        }
+
+        let image = camera
+            .image_array()
+            .await
+            .map_err(|e| format!("failed to download image array: {e}"))?;
+        let (width, height, planes) = image.dim();
+        if planes != 1 {
+            return Err(format!(
+                "guide frames must be monochrome, got {planes} planes"
+            ));
+        }
+        let pixels: Vec<i32> = image.iter().copied().collect();
+        Array2::from_shape_vec((width, height), pixels)
+            .map_err(|e| format!("guide frame shape mismatch: {e}"))
+    }
+}
+
+pub struct AlpacaGuideMount {
+    client: Client,
+    label: String,
+    device_number: u32,
+    resolved: RwLock<Option<Resolved<dyn Telescope>>>,
+}
+
+impl AlpacaGuideMount {
+    pub fn from_config(
+        config: &DeviceConfig,
+        ca_cert_path: Option<&std::path::Path>,
+    ) -> Result<Self, String> {
+        let client = build_alpaca_client(&config.alpaca_url, config.auth.as_ref(), ca_cert_path)
+            .map_err(|e| format!("mount client: {e}"))?;
+        Ok(Self {
+            client,
+            label: format!("mount {} at {}", config.device_number, config.alpaca_url),
+            device_number: config.device_number,
+            resolved: RwLock::new(None),
+        })
+    }
+
+    async fn device(&self) -> Result<Arc<dyn Telescope>, String> {
+        self.resolved
+            .read()
+            .await
+            .as_ref()
+            .map(|r| Arc::clone(&r.device))
+            .ok_or_else(|| format!("{} is not connected", self.label))
+    }
+}
+
+#[async_trait]
+impl GuideMount for AlpacaGuideMount {
+    async fn connect(&self) -> Result<(), String> {
+        let telescope = discover(&self.client)
+            .await?
+            .into_iter()
+            .filter_map(|d| match d {
+                TypedDevice::Telescope(t) => Some(t),
+                _ => None,
+            })
+            .nth(self.device_number as usize)
+            .ok_or_else(|| format!("{} not found", self.label))?;
+        telescope
+            .set_connected(true)
+            .await
+            .map_err(|e| format!("failed to connect {}: {e}", self.label))?;
+        if !telescope
+            .can_pulse_guide()
+            .await
+            .map_err(|e| format!("failed to read CanPulseGuide on {}: {e}", self.label))?
+        {
+            return Err(format!("{} cannot pulse guide", self.label));
+        }
+        let name = telescope
+            .name()
+            .await
+            .unwrap_or_else(|_| self.label.clone());
+        *self.resolved.write().await = Some(Resolved {
+            device: telescope,
+            name,
+        });
+        Ok(())
+    }
+
+    async fn status(&self) -> DeviceStatus {
+        let guard = self.resolved.read().await;
+        match guard.as_ref() {
+            Some(r) => DeviceStatus {
+                name: r.name.clone(),
+                connected: r.device.connected().await.unwrap_or(false),
+            },
+            None => DeviceStatus {
+                name: self.label.clone(),
+                connected: false,
+            },
+        }
+    }
+
+    async fn pulse(&self, direction: GuideDirection, duration: Duration) -> Result<(), String> {
+        let telescope = self.device().await?;
+        telescope
+            .pulse_guide(direction.into(), duration)
+            .await
+            .map_err(|e| format!("pulse guide {direction:?} failed: {e}"))?;
+        // Alpaca `PulseGuide` may return before the pulse ends; the
+        // next frame must not start until the mount has finished.
+        tokio::time::sleep(duration).await;
+        let deadline = Instant::now() + PULSE_GRACE;
+        loop {
+            match telescope.is_pulse_guiding().await {
+                Ok(false) => return Ok(()),
+                Ok(true) if Instant::now() >= deadline => {
+                    return Err(format!(
+                        "mount still pulse guiding {PULSE_GRACE:?} after a {duration:?} pulse"
+                    ));
+                }
+                Ok(true) => tokio::time::sleep(POLL_INTERVAL).await,
+                Err(e) => return Err(format!("error checking IsPulseGuiding: {e}")),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn device(url: &str) -> DeviceConfig {
+        DeviceConfig {
+            alpaca_url: url.into(),
+            device_number: 0,
+            auth: None,
+        }
+    }
+
+    #[test]
+    fn build_alpaca_client_with_auth() {
+        let auth = ClientAuthConfig {
+            username: "u".into(),
+            password: "p".into(),
+        };
+        build_alpaca_client("http://127.0.0.1/", Some(&auth), None).unwrap();
+    }
+
+    #[test]
+    fn build_alpaca_client_rejects_invalid_url() {
+        build_alpaca_client("not a url", None, None).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn an_unresolved_camera_reports_its_label_disconnected() {
+        let camera = AlpacaGuideCamera::from_config(&device("http://127.0.0.1:1"), None).unwrap();
+        let status = camera.status().await;
+        assert_eq!(status.name, "camera 0 at http://127.0.0.1:1");
+        assert!(!status.connected);
+        let err = camera.capture(Duration::from_secs(1)).await.unwrap_err();
+        assert!(err.contains("not connected"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn connecting_to_a_dead_server_fails_without_hanging() {
+        // Port 1 is reserved; nothing answers, and discovery is bounded.
+        let mount = AlpacaGuideMount::from_config(&device("http://127.0.0.1:1"), None).unwrap();
+        mount.connect().await.unwrap_err();
+        assert!(!mount.status().await.connected);
+        let err = mount
+            .pulse(GuideDirection::West, Duration::from_millis(10))
+            .await
+            .unwrap_err();
+        assert!(err.contains("not connected"), "{err}");
+    }
+}
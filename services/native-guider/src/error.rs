@@ -0,0 +1,188 @@
+//! Startup errors, and the HTTP error taxonomy of the guiding API.
+//!
+//! The wire codes and statuses are the ones `phd2-guider serve` froze
+//! (`docs/services/phd2-guider.md` § "Error envelope") so `rp-guider`
+//! reads both backends the same way.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, NativeGuiderError>;
+
+#[derive(Debug, Error)]
+pub enum NativeGuiderError {
+    #[error("config error: {0}")]
+    Config(String),
+
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("server error: {0}")]
+    Server(String),
+}
+
+#[derive(Debug, Error)]
+pub enum ServiceError {
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("cannot dither: not guiding (state: {0})")]
+    NotGuiding(String),
+
+    #[error("guiding failed: {0}")]
+    GuideFailed(String),
+
+    #[error("settle timed out: not settled within the {0} backstop")]
+    SettleTimeout(String),
+
+    #[error("stop timed out: the guide loop did not stop within {0}")]
+    StopTimeout(String),
+
+    #[error("guide equipment unreachable: {0}")]
+    EquipmentUnreachable(String),
+
+    #[error("internal: {0}")]
+    Internal(String),
+}
+
+/// Wire-format error codes. `EquipmentUnreachable` keeps the frozen
+/// `phd2_unreachable` name: to `rp` it means the same thing — the
+/// guiding backend cannot reach what it drives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    InvalidRequest,
+    NotGuiding,
+    GuideFailed,
+    SettleTimeout,
+    StopTimeout,
+    #[serde(rename = "phd2_unreachable")]
+    EquipmentUnreachable,
+    Internal,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorResponse {
+    pub error: ErrorCode,
+    pub message: String,
+    #[serde(skip_serializing_if = "serde_json::Value::is_null")]
+    pub details: serde_json::Value,
+}
+
+impl ServiceError {
+    /// Map this error to its wire-format code.
+    #[must_use]
+    pub const fn code(&self) -> ErrorCode {
+        match self {
+            Self::InvalidRequest(_) => ErrorCode::InvalidRequest,
+            Self::NotGuiding(_) => ErrorCode::NotGuiding,
+            Self::GuideFailed(_) => ErrorCode::GuideFailed,
+            Self::SettleTimeout(_) => ErrorCode::SettleTimeout,
+            Self::StopTimeout(_) => ErrorCode::StopTimeout,
+            Self::EquipmentUnreachable(_) => ErrorCode::EquipmentUnreachable,
+            Self::Internal(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// HTTP status the service returns for this error.
+    #[must_use]
+    pub const fn status(&self) -> StatusCode {
+        match self {
+            Self::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            Self::NotGuiding(_) => StatusCode::CONFLICT,
+            Self::GuideFailed(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            Self::SettleTimeout(_) | Self::StopTimeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            Self::EquipmentUnreachable(_) => StatusCode::BAD_GATEWAY,
+            Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ServiceError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let body = ErrorResponse {
+            error: self.code(),
+            message: self.to_string(),
+            details: serde_json::Value::Null,
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_variant_maps_to_its_frozen_code_and_status() {
+        let cases: Vec<(ServiceError, ErrorCode, StatusCode)> = vec![
+            (
+                ServiceError::InvalidRequest("x".into()),
+                ErrorCode::InvalidRequest,
+                StatusCode::BAD_REQUEST,
+            ),
+            (
+                ServiceError::NotGuiding("Stopped".into()),
+                ErrorCode::NotGuiding,
+                StatusCode::CONFLICT,
+            ),
+            (
+                ServiceError::GuideFailed("star lost".into()),
+                ErrorCode::GuideFailed,
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                ServiceError::SettleTimeout("70s".into()),
+                ErrorCode::SettleTimeout,
+                StatusCode::GATEWAY_TIMEOUT,
+            ),
+            (
+                ServiceError::StopTimeout("10s".into()),
+                ErrorCode::StopTimeout,
+                StatusCode::GATEWAY_TIMEOUT,
+            ),
+            (
+                ServiceError::EquipmentUnreachable("refused".into()),
+                ErrorCode::EquipmentUnreachable,
+                StatusCode::BAD_GATEWAY,
+            ),
+            (
+                ServiceError::Internal("bug".into()),
+                ErrorCode::Internal,
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+        ];
+        for (err, code, status) in cases {
+            assert_eq!(err.code(), code);
+            assert_eq!(err.status(), status);
+        }
+    }
+
+    #[test]
+    fn unreachable_equipment_keeps_the_frozen_wire_code() {
+        let json = serde_json::to_string(&ErrorCode::EquipmentUnreachable).unwrap();
+        assert_eq!(json, "\"phd2_unreachable\"");
+        let json = serde_json::to_string(&ErrorCode::NotGuiding).unwrap();
+        assert_eq!(json, "\"not_guiding\"");
+    }
+
+    #[test]
+    fn the_envelope_omits_null_details() {
+        let body = ErrorResponse {
+            error: ErrorCode::GuideFailed,
+            message: "guiding failed: star lost".into(),
+            details: serde_json::Value::Null,
+        };
+        let json = serde_json::to_value(&body).unwrap();
+        assert_eq!(json["error"], "guide_failed");
+        assert_eq!(json["message"], "guiding failed: star lost");
+        assert!(json.get("details").is_none());
+    }
+}
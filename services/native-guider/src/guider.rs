@@ -0,0 +1,1385 @@
+//! The guide engine behind the HTTP API: star selection, calibration,
+//! the guide loop, settle-blocking guide and dither, the confirmed
+//! stop, the rolling RMS window, and the guide algorithm settings.
+//!
+//! Behavior contract: `docs/services/native-guider.md` § "Guiding". The
+//! mutating operations serialize behind a single-flight mutex
+//! (overlapping requests queue, not error); the read-only snapshot
+//! paths bypass it. The guide loop runs as its own task and takes its
+//! orders — lock position, pause, settle, reselect, retune, stop —
+//! through a shared [`LoopControl`] it reads once per frame.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+
+use ndarray::Array2;
+use rand::RngExt;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::algorithms::GuideAlgorithm;
+use crate::calibration::{calibrate, Calibration};
+use crate::config::{
+    AlgorithmConfig, BacklashConfig, CalibrationConfig, DecGuideMode, DetectionConfig,
+    GuiderConfig, SettleParams,
+};
+use crate::equipment::{DeviceStatus, GuideCamera, GuideDirection, GuideMount};
+use crate::error::{NativeGuiderError, ServiceError};
+use crate::settle::{SettleProgress, SettleTracker};
+use crate::star::{find_star_near, select_guide_star, StarMeasurement};
+
+/// Rolling RMS window size, in guide steps.
+const RMS_WINDOW: usize = 50;
+
+/// Per-frame metrics ring size, in frames.
+const METRICS_WINDOW: usize = 50;
+
+/// Wall-clock grace added to the request's settle timeout for the
+/// backstop. The guide loop enforces the settle timeout itself; the
+/// backstop only catches a loop wedged in a hung exposure or pulse.
+const SETTLE_GRACE: Duration = Duration::from_secs(10);
+
+/// How long star reselection may take beyond one exposure.
+const RESELECT_GRACE: Duration = Duration::from_secs(30);
+
+/// How often a fully paused loop checks for resume or stop.
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+
+/// Back-off after a failed guide frame, so a disconnected camera does
+/// not spin the loop.
+const CAPTURE_RETRY: Duration = Duration::from_secs(1);
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The guide camera and mount plus the frame settings every
+/// measurement uses. Shared by calibration and the guide loop.
+pub(crate) struct Rig {
+    camera: Arc<dyn GuideCamera>,
+    mount: Arc<dyn GuideMount>,
+    exposure: Duration,
+    detection: DetectionConfig,
+}
+
+impl Rig {
+    pub(crate) fn new(
+        camera: Arc<dyn GuideCamera>,
+        mount: Arc<dyn GuideMount>,
+        exposure: Duration,
+        detection: DetectionConfig,
+    ) -> Self {
+        Self {
+            camera,
+            mount,
+            exposure,
+            detection,
+        }
+    }
+
+    async fn capture(&self) -> Result<Array2<i32>, ServiceError> {
+        self.camera
+            .capture(self.exposure)
+            .await
+            .map_err(ServiceError::EquipmentUnreachable)
+    }
+
+    pub(crate) async fn pulse(
+        &self,
+        direction: GuideDirection,
+        duration: Duration,
+    ) -> Result<(), ServiceError> {
+        self.mount
+            .pulse(direction, duration)
+            .await
+            .map_err(ServiceError::EquipmentUnreachable)
+    }
+
+    /// Capture a frame and pick a guide star on it.
+    pub(crate) async fn select(&self) -> Result<StarMeasurement, ServiceError> {
+        let frame = self.capture().await?;
+        self.select_in(frame).await
+    }
+
+    /// Full-frame selection runs on the blocking pool: on a large guide
+    /// sensor it is the one measurement too slow for the runtime thread.
+    async fn select_in(&self, frame: Array2<i32>) -> Result<StarMeasurement, ServiceError> {
+        let detection = self.detection;
+        tokio::task::spawn_blocking(move || select_guide_star(frame.view(), &detection))
+            .await
+            .map_err(|e| ServiceError::Internal(format!("star selection task failed: {e}")))?
+            .ok_or_else(|| {
+                ServiceError::GuideFailed(format!(
+                    "no guide star found: no unsaturated star with SNR >= {} clear of the frame edges",
+                    self.detection.min_snr
+                ))
+            })
+    }
+
+    /// Capture a frame and find the star near `(x, y)`; `None` when it
+    /// is lost.
+    pub(crate) async fn locate(
+        &self,
+        x: f64,
+        y: f64,
+    ) -> Result<Option<StarMeasurement>, ServiceError> {
+        let frame = self.capture().await?;
+        Ok(find_star_near(frame.view(), x, y, &self.detection))
+    }
+}
+
+/// The guider's state, reported with PHD2's names so `rp` reads both
+/// backends alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppState {
+    Stopped,
+    Selected,
+    Calibrating,
+    Guiding,
+    LostLock,
+    Paused,
+}
+
+impl fmt::Display for AppState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Stopped => "Stopped",
+            Self::Selected => "Selected",
+            Self::Calibrating => "Calibrating",
+            Self::Guiding => "Guiding",
+            Self::LostLock => "LostLock",
+            Self::Paused => "Paused",
+        })
+    }
+}
+
+/// One retained guide step: RA and Dec error in pixels along the axes.
+type StepSample = (Option<f64>, Option<f64>);
+
+#[derive(Debug, Default)]
+struct StatsWindow {
+    steps: VecDeque<StepSample>,
+    last_snr: Option<f64>,
+    last_star_mass: Option<f64>,
+}
+
+impl StatsWindow {
+    fn push(&mut self, ra: Option<f64>, dec: Option<f64>, snr: Option<f64>, mass: Option<f64>) {
+        if self.steps.len() == RMS_WINDOW {
+            self.steps.pop_front();
+        }
+        self.steps.push_back((ra, dec));
+        self.last_snr = snr;
+        self.last_star_mass = mass;
+    }
+
+    fn snapshot(&self) -> StatsSnapshot {
+        let (mut ra_sum_sq, mut ra_n, mut dec_sum_sq, mut dec_n) = (0.0f64, 0u32, 0.0f64, 0u32);
+        for (ra, dec) in &self.steps {
+            if let Some(v) = ra {
+                ra_sum_sq += v * v;
+                ra_n += 1;
+            }
+            if let Some(v) = dec {
+                dec_sum_sq += v * v;
+                dec_n += 1;
+            }
+        }
+        let rms = |sum_sq: f64, n: u32| (n > 0).then(|| (sum_sq / f64::from(n)).sqrt());
+        let rms_ra_px = rms(ra_sum_sq, ra_n);
+        let rms_dec_px = rms(dec_sum_sq, dec_n);
+        let total_rms_px = match (rms_ra_px, rms_dec_px) {
+            (Some(ra), Some(dec)) => Some(ra.hypot(dec)),
+            _ => None,
+        };
+        StatsSnapshot {
+            rms_ra_px,
+            rms_dec_px,
+            total_rms_px,
+            snr: self.last_snr,
+            star_mass: self.last_star_mass,
+            sample_count: self.steps.len() as u32,
+        }
+    }
+}
+
+/// Point-in-time view of the rolling window, embedded in guide,
+/// dither, and stats responses.
+#[derive(Debug, Clone, Copy)]
+pub struct StatsSnapshot {
+    pub rms_ra_px: Option<f64>,
+    pub rms_dec_px: Option<f64>,
+    pub total_rms_px: Option<f64>,
+    pub snr: Option<f64>,
+    pub star_mass: Option<f64>,
+    pub sample_count: u32,
+}
+
+/// Stats endpoint payload: the window snapshot plus the guider state.
+#[derive(Debug)]
+pub struct GuidingStats {
+    pub app_state: AppState,
+    pub snapshot: StatsSnapshot,
+}
+
+/// One entry of the per-frame metrics ring behind
+/// `GET /api/v1/guiding/metrics`: a measured frame's star metrics, or a
+/// lost-star marker (`star_lost: true`, no star metrics).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct FrameMetrics {
+    pub frame: u64,
+    pub hfd: Option<f64>,
+    pub snr: Option<f64>,
+    pub star_mass: Option<f64>,
+    pub star_lost: bool,
+}
+
+#[derive(Debug)]
+pub struct GuidingMetrics {
+    pub guiding: bool,
+    pub frames: Vec<FrameMetrics>,
+}
+
+/// Equipment endpoint payload. The guider drives no AO, rotator, or
+/// auxiliary mount, so those slots are always empty.
+#[derive(Debug)]
+pub struct Equipment {
+    pub camera: DeviceStatus,
+    pub mount: DeviceStatus,
+}
+
+/// The settings the guide loop corrects with, changeable at runtime
+/// through `POST /api/v1/guiding/settings`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tuning {
+    pub dec_guide_mode: DecGuideMode,
+    pub ra: AlgorithmConfig,
+    pub dec: AlgorithmConfig,
+    pub backlash: BacklashConfig,
+}
+
+/// One axis of a settings update; every field optional.
+#[derive(Debug, Default)]
+pub struct AxisUpdate {
+    pub min_move_px: Option<f64>,
+    pub aggressiveness: Option<f64>,
+    /// Algorithm parameters by their config names.
+    pub params: BTreeMap<String, f64>,
+}
+
+impl AxisUpdate {
+    /// Apply onto `algorithm` and re-check it, naming the axis in any
+    /// error.
+    fn apply(&self, axis: &str, algorithm: &mut AlgorithmConfig) -> Result<(), ServiceError> {
+        let fields = [
+            ("min_move_px", self.min_move_px),
+            ("aggression", self.aggressiveness),
+        ];
+        let named = fields
+            .into_iter()
+            .filter_map(|(name, value)| value.map(|v| (name, v)))
+            .chain(self.params.iter().map(|(name, &v)| (name.as_str(), v)));
+        for (name, value) in named {
+            algorithm
+                .set_param(name, value)
+                .map_err(|e| ServiceError::InvalidRequest(format!("{axis}: {e}")))?;
+        }
+        algorithm.validate(axis).map_err(invalid_setting)
+    }
+}
+
+/// A setting the config loader would refuse, refused at runtime too.
+fn invalid_setting(error: NativeGuiderError) -> ServiceError {
+    match error {
+        NativeGuiderError::Config(message) => ServiceError::InvalidRequest(message),
+        other => ServiceError::Internal(other.to_string()),
+    }
+}
+
+/// Partial settings update, checked whole before any of it applies.
+#[derive(Debug, Default)]
+pub struct SettingsUpdate {
+    pub dec_guide_mode: Option<DecGuideMode>,
+    pub ra: AxisUpdate,
+    pub dec: AxisUpdate,
+    pub backlash: Option<BacklashConfig>,
+}
+
+/// Which stored calibration to clear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationTarget {
+    Mount,
+    Ao,
+    Both,
+}
+
+/// What the loop reports into, read by the snapshot endpoints.
+#[derive(Debug)]
+struct Telemetry {
+    state: Mutex<AppState>,
+    stats: Mutex<StatsWindow>,
+    /// Newest at the back; cleared with the RMS window on each start.
+    metrics: Mutex<VecDeque<FrameMetrics>>,
+}
+
+impl Telemetry {
+    fn state(&self) -> AppState {
+        *lock(&self.state)
+    }
+
+    fn set_state(&self, state: AppState) {
+        *lock(&self.state) = state;
+    }
+
+    fn reset(&self) {
+        *lock(&self.stats) = StatsWindow::default();
+        lock(&self.metrics).clear();
+    }
+
+    fn push_metrics(&self, entry: FrameMetrics) {
+        let mut ring = lock(&self.metrics);
+        if ring.len() == METRICS_WINDOW {
+            ring.pop_front();
+        }
+        ring.push_back(entry);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseMode {
+    /// Keep measuring, stop correcting.
+    Partial,
+    /// Stop capturing altogether.
+    Full,
+}
+
+type Reply = oneshot::Sender<Result<(), ServiceError>>;
+
+/// The orders a running loop picks up at the top of each frame.
+struct LoopControl {
+    /// Where the star should be, in camera pixels.
+    lock: (f64, f64),
+    /// Set when `lock` jumps (dither, reselect), so the algorithms drop
+    /// history measured against the old position.
+    lock_moved: bool,
+    pause: Option<PauseMode>,
+    settle: Option<(SettleTracker, Reply)>,
+    reselect: Option<Reply>,
+    /// New settings for the loop to switch to.
+    retune: Option<Tuning>,
+    stop: bool,
+}
+
+struct RunningLoop {
+    control: Arc<Mutex<LoopControl>>,
+    task: JoinHandle<()>,
+}
+
+/// Settings fixed at startup that the operations need.
+struct GuideSettings {
+    calibration: CalibrationConfig,
+    max_pulse: Duration,
+    default_settle: SettleParams,
+    stop_timeout: Duration,
+}
+
+pub struct GuiderOps {
+    rig: Arc<Rig>,
+    settings: GuideSettings,
+    /// Single-flight lock for mutating operations.
+    op_lock: tokio::sync::Mutex<()>,
+    telemetry: Arc<Telemetry>,
+    /// Kept across guiding sessions until cleared or a recalibrating
+    /// start replaces it.
+    calibration: Mutex<Option<Calibration>>,
+    /// Starts from the config; each guide start and settings update
+    /// hands the loop a copy.
+    tuning: Mutex<Tuning>,
+    running: Mutex<Option<RunningLoop>>,
+}
+
+impl GuiderOps {
+    pub fn new(
+        camera: Arc<dyn GuideCamera>,
+        mount: Arc<dyn GuideMount>,
+        config: &GuiderConfig,
+    ) -> Self {
+        Self {
+            rig: Arc::new(Rig::new(camera, mount, config.exposure, config.detection)),
+            settings: GuideSettings {
+                calibration: config.calibration,
+                max_pulse: config.max_pulse,
+                default_settle: config.settling,
+                stop_timeout: config.stop_timeout,
+            },
+            op_lock: tokio::sync::Mutex::new(()),
+            telemetry: Arc::new(Telemetry {
+                state: Mutex::new(AppState::Stopped),
+                stats: Mutex::new(StatsWindow::default()),
+                metrics: Mutex::new(VecDeque::with_capacity(METRICS_WINDOW)),
+            }),
+            calibration: Mutex::new(None),
+            tuning: Mutex::new(Tuning {
+                dec_guide_mode: config.dec_guide_mode,
+                ra: config.ra,
+                dec: config.dec,
+                backlash: config.backlash_compensation,
+            }),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Merge a partial per-request settle override onto the config
+    /// defaults, field by field.
+    pub fn resolve_settle(
+        &self,
+        pixels: Option<f64>,
+        time: Option<Duration>,
+        timeout: Option<Duration>,
+    ) -> SettleParams {
+        let defaults = self.settings.default_settle;
+        SettleParams {
+            pixels: pixels.unwrap_or(defaults.pixels),
+            time: time.unwrap_or(defaults.time),
+            timeout: timeout.unwrap_or(defaults.timeout),
+        }
+    }
+
+    /// Keep (re)connecting whichever device is not connected, for the
+    /// life of the process: the guider starts in daylight with the
+    /// equipment off, and a device can drop mid-night.
+    pub fn spawn_connect_retry(self: &Arc<Self>, interval: Duration) {
+        let ops = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                if !ops.rig.camera.status().await.connected {
+                    match ops.rig.camera.connect().await {
+                        Ok(()) => info!("guide camera connected"),
+                        Err(e) => debug!("guide camera not reachable yet: {e}"),
+                    }
+                }
+                if !ops.rig.mount.status().await.connected {
+                    match ops.rig.mount.connect().await {
+                        Ok(()) => info!("mount connected"),
+                        Err(e) => debug!("mount not reachable yet: {e}"),
+                    }
+                }
+                tokio::time::sleep(interval).await;
+            }
+        });
+    }
+
+    /// The devices not currently connected, by label — empty when the
+    /// guider is ready.
+    pub async fn disconnected(&self) -> Vec<String> {
+        let mut missing = Vec::new();
+        for status in [
+            self.rig.camera.status().await,
+            self.rig.mount.status().await,
+        ] {
+            if !status.connected {
+                missing.push(status.name);
+            }
+        }
+        missing
+    }
+
+    /// Select a star, calibrate if needed, start the guide loop, and
+    /// block until the star settles. While already guiding without
+    /// `recalibrate`, this only waits for the star to settle again.
+    pub async fn start_guiding(
+        &self,
+        settle: SettleParams,
+        recalibrate: bool,
+    ) -> Result<StatsSnapshot, ServiceError> {
+        let _op = self.op_lock.lock().await;
+        if !recalibrate {
+            if let Some(rx) = self.arm_settle(settle) {
+                debug!("guide requested while guiding; waiting for settle");
+                self.wait_for_settle(rx, &settle).await?;
+                return Ok(self.stats_snapshot());
+            }
+        }
+        self.halt().await?;
+        self.telemetry.reset();
+        debug!(
+            pixels = settle.pixels,
+            time = ?settle.time,
+            timeout = ?settle.timeout,
+            recalibrate,
+            "starting guiding"
+        );
+        let result = self.begin(settle, recalibrate).await;
+        if result.is_err() {
+            self.telemetry.set_state(AppState::Stopped);
+        }
+        let rx = result?;
+        self.wait_for_settle(rx, &settle).await?;
+        Ok(self.stats_snapshot())
+    }
+
+    /// Everything up to a running loop: select, calibrate, spawn.
+    async fn begin(
+        &self,
+        settle: SettleParams,
+        recalibrate: bool,
+    ) -> Result<oneshot::Receiver<Result<(), ServiceError>>, ServiceError> {
+        let mut star = self.rig.select().await?;
+        self.telemetry.set_state(AppState::Selected);
+        info!(
+            x = star.x,
+            y = star.y,
+            snr = star.snr,
+            "guide star selected"
+        );
+
+        let stored = if recalibrate {
+            None
+        } else {
+            *lock(&self.calibration)
+        };
+        let calibration = if let Some(calibration) = stored {
+            calibration
+        } else {
+            self.telemetry.set_state(AppState::Calibrating);
+            let calibration = calibrate(&self.rig, star, &self.settings.calibration).await?;
+            *lock(&self.calibration) = Some(calibration);
+            // The walks leave the star near, not exactly at, its start.
+            star = self.rig.locate(star.x, star.y).await?.ok_or_else(|| {
+                ServiceError::GuideFailed("guide star lost after calibration".to_string())
+            })?;
+            calibration
+        };
+
+        let (tx, rx) = oneshot::channel();
+        let control = Arc::new(Mutex::new(LoopControl {
+            lock: (star.x, star.y),
+            lock_moved: false,
+            pause: None,
+            settle: Some((SettleTracker::new(settle, Instant::now()), tx)),
+            reselect: None,
+            retune: None,
+            stop: false,
+        }));
+        let tuning = *lock(&self.tuning);
+        let guide_loop = GuideLoop {
+            rig: Arc::clone(&self.rig),
+            telemetry: Arc::clone(&self.telemetry),
+            control: Arc::clone(&control),
+            calibration,
+            ra: tuning.ra.build(),
+            dec: tuning.dec.build(),
+            dec_output: DecOutput::new(tuning.dec_guide_mode, tuning.backlash),
+            max_pulse: self.settings.max_pulse,
+            star: (star.x, star.y),
+            frame: 0,
+        };
+        self.telemetry.set_state(AppState::Guiding);
+        let task = tokio::spawn(guide_loop.run());
+        *lock(&self.running) = Some(RunningLoop { control, task });
+        Ok(rx)
+    }
+
+    /// Dither and block until the star settles at the new lock
+    /// position. Rejected with `not_guiding` unless the state is
+    /// Guiding.
+    pub async fn dither(
+        &self,
+        amount_px: f64,
+        ra_only: bool,
+        settle: SettleParams,
+    ) -> Result<StatsSnapshot, ServiceError> {
+        let _op = self.op_lock.lock().await;
+        let state = self.telemetry.state();
+        let calibration = *lock(&self.calibration);
+        let (Some(calibration), AppState::Guiding) = (calibration, state) else {
+            return Err(ServiceError::NotGuiding(state.to_string()));
+        };
+        let (ra, dec) = {
+            let mut rng = rand::rng();
+            let ra = rng.random_range(-1.0..=1.0) * amount_px;
+            let dec = if ra_only {
+                0.0
+            } else {
+                rng.random_range(-1.0..=1.0) * amount_px
+            };
+            (ra, dec)
+        };
+        let (dx, dy) = calibration.to_camera(ra, dec);
+        debug!(amount_px, ra_only, ra, dec, "dithering");
+        let rx = {
+            let running = lock(&self.running);
+            let Some(running) = running.as_ref() else {
+                return Err(ServiceError::NotGuiding(state.to_string()));
+            };
+            let mut control = lock(&running.control);
+            control.lock.0 += dx;
+            control.lock.1 += dy;
+            control.lock_moved = true;
+            let (tx, rx) = oneshot::channel();
+            control.settle = Some((SettleTracker::new(settle, Instant::now()), tx));
+            rx
+        };
+        self.wait_for_settle(rx, &settle).await?;
+        Ok(self.stats_snapshot())
+    }
+
+    /// Stop the guide loop and wait for it to exit. Idempotent: an
+    /// already-stopped guider succeeds immediately.
+    pub async fn stop(&self) -> Result<(), ServiceError> {
+        let _op = self.op_lock.lock().await;
+        if lock(&self.running).is_none() {
+            debug!("stop requested while already stopped");
+            self.telemetry.set_state(AppState::Stopped);
+            return Ok(());
+        }
+        self.halt().await
+    }
+
+    /// Pause corrections (`full`: capture too). A no-op when not guiding.
+    pub async fn pause(&self, full: bool) -> Result<(), ServiceError> {
+        let _op = self.op_lock.lock().await;
+        let running = lock(&self.running);
+        if let Some(running) = running.as_ref() {
+            lock(&running.control).pause = Some(if full {
+                PauseMode::Full
+            } else {
+                PauseMode::Partial
+            });
+            self.telemetry.set_state(AppState::Paused);
+        }
+        Ok(())
+    }
+
+    pub async fn resume(&self) -> Result<(), ServiceError> {
+        let _op = self.op_lock.lock().await;
+        let running = lock(&self.running);
+        if let Some(running) = running.as_ref() {
+            let mut control = lock(&running.control);
+            if control.pause.take().is_some() {
+                self.telemetry.set_state(AppState::Guiding);
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn stats(&self) -> Result<GuidingStats, ServiceError> {
+        Ok(GuidingStats {
+            app_state: self.telemetry.state(),
+            snapshot: self.stats_snapshot(),
+        })
+    }
+
+    /// The per-frame metrics ring plus the guiding flag — read-only, no
+    /// mutating mutex (mirrors `stats`).
+    pub async fn metrics(&self) -> Result<GuidingMetrics, ServiceError> {
+        let frames = lock(&self.telemetry.metrics).iter().cloned().collect();
+        Ok(GuidingMetrics {
+            guiding: self.telemetry.state() == AppState::Guiding,
+            frames,
+        })
+    }
+
+    pub async fn equipment(&self) -> Result<Equipment, ServiceError> {
+        Ok(Equipment {
+            camera: self.rig.camera.status().await,
+            mount: self.rig.mount.status().await,
+        })
+    }
+
+    /// Drop the stored mount calibration; the next guide start
+    /// recalibrates. There is no AO calibration to clear.
+    pub async fn clear_calibration(&self, which: CalibrationTarget) -> Result<(), ServiceError> {
+        let _op = self.op_lock.lock().await;
+        if matches!(which, CalibrationTarget::Mount | CalibrationTarget::Both) {
+            *lock(&self.calibration) = None;
+            info!("calibration cleared");
+        }
+        Ok(())
+    }
+
+    /// Pick a new guide star on the loop's next frame and lock onto it.
+    pub async fn reselect_star(&self) -> Result<(), ServiceError> {
+        let _op = self.op_lock.lock().await;
+        let rx = {
+            let running = lock(&self.running);
+            let Some(running) = running.as_ref() else {
+                return Err(ServiceError::GuideFailed(
+                    "no guide loop running to select a star on; start guiding first".to_string(),
+                ));
+            };
+            let (tx, rx) = oneshot::channel();
+            lock(&running.control).reselect = Some(tx);
+            rx
+        };
+        match tokio::time::timeout(self.rig.exposure + RESELECT_GRACE, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(ServiceError::GuideFailed(
+                "guide loop stopped before selecting a star".to_string(),
+            )),
+            Err(_) => Err(ServiceError::GuideFailed(
+                "no guide frame to select a star on (is guiding fully paused?)".to_string(),
+            )),
+        }
+    }
+
+    /// The current guide algorithm settings — read-only, no mutating
+    /// mutex (mirrors `stats`).
+    pub async fn settings(&self) -> Result<Tuning, ServiceError> {
+        Ok(*lock(&self.tuning))
+    }
+
+    /// Apply a partial settings update. The whole update is checked
+    /// before any of it applies; a running loop switches over on its
+    /// next frame.
+    pub async fn update_settings(&self, update: SettingsUpdate) -> Result<Tuning, ServiceError> {
+        let _op = self.op_lock.lock().await;
+        let mut tuning = *lock(&self.tuning);
+        update.ra.apply("ra", &mut tuning.ra)?;
+        update.dec.apply("dec", &mut tuning.dec)?;
+        if let Some(backlash) = update.backlash {
+            backlash.validate().map_err(invalid_setting)?;
+            tuning.backlash = backlash;
+        }
+        if let Some(mode) = update.dec_guide_mode {
+            tuning.dec_guide_mode = mode;
+        }
+        *lock(&self.tuning) = tuning;
+        if let Some(running) = lock(&self.running).as_ref() {
+            lock(&running.control).retune = Some(tuning);
+        }
+        info!(?tuning, "guide settings updated");
+        Ok(tuning)
+    }
+
+    fn stats_snapshot(&self) -> StatsSnapshot {
+        lock(&self.telemetry.stats).snapshot()
+    }
+
+    /// Hand a running loop a new settle to track; `None` when no loop
+    /// is running.
+    fn arm_settle(
+        &self,
+        settle: SettleParams,
+    ) -> Option<oneshot::Receiver<Result<(), ServiceError>>> {
+        let running = lock(&self.running);
+        let running = running.as_ref()?;
+        let mut control = lock(&running.control);
+        if control.stop {
+            return None;
+        }
+        let (tx, rx) = oneshot::channel();
+        control.settle = Some((SettleTracker::new(settle, Instant::now()), tx));
+        Some(rx)
+    }
+
+    /// Order the loop to stop and wait for it. On timeout the loop is
+    /// kept (still ordered to stop) so a later stop can wait again.
+    async fn halt(&self) -> Result<(), ServiceError> {
+        let Some(running) = lock(&self.running).take() else {
+            return Ok(());
+        };
+        lock(&running.control).stop = true;
+        let mut task = running.task;
+        match tokio::time::timeout(self.settings.stop_timeout, &mut task).await {
+            Ok(joined) => {
+                if let Err(e) = joined {
+                    warn!("guide loop ended abnormally: {e}");
+                }
+                self.telemetry.set_state(AppState::Stopped);
+                info!("guiding stopped");
+                Ok(())
+            }
+            Err(_) => {
+                warn!(
+                    "guide loop did not stop within {:?}",
+                    self.settings.stop_timeout
+                );
+                *lock(&self.running) = Some(RunningLoop {
+                    control: running.control,
+                    task,
+                });
+                Err(ServiceError::StopTimeout(
+                    humantime::format_duration(self.settings.stop_timeout).to_string(),
+                ))
+            }
+        }
+    }
+
+    async fn wait_for_settle(
+        &self,
+        rx: oneshot::Receiver<Result<(), ServiceError>>,
+        settle: &SettleParams,
+    ) -> Result<(), ServiceError> {
+        let backstop = settle.timeout + SETTLE_GRACE;
+        match tokio::time::timeout(backstop, rx).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err(ServiceError::GuideFailed(
+                "guiding stopped before the star settled".to_string(),
+            )),
+            Err(_) => {
+                warn!("no settle result within the {backstop:?} backstop");
+                Err(ServiceError::SettleTimeout(
+                    humantime::format_duration(backstop).to_string(),
+                ))
+            }
+        }
+    }
+}
+
+/// Shapes the Dec algorithm's pulse: drops the directions the Dec
+/// guide mode forbids, and pads the first pulse after a reversal with
+/// the backlash compensation.
+#[derive(Debug)]
+struct DecOutput {
+    mode: DecGuideMode,
+    backlash: BacklashConfig,
+    /// Direction of the last Dec pulse sent; survives retunes, since
+    /// the gear slack it tracks does.
+    last: Option<GuideDirection>,
+}
+
+impl DecOutput {
+    const fn new(mode: DecGuideMode, backlash: BacklashConfig) -> Self {
+        Self {
+            mode,
+            backlash,
+            last: None,
+        }
+    }
+
+    fn shape(
+        &mut self,
+        pulse: Option<(GuideDirection, Duration)>,
+    ) -> Option<(GuideDirection, Duration)> {
+        let (direction, duration) = pulse?;
+        let allowed = match self.mode {
+            DecGuideMode::Off => false,
+            DecGuideMode::Auto => true,
+            DecGuideMode::North => direction == GuideDirection::North,
+            DecGuideMode::South => direction == GuideDirection::South,
+        };
+        if !allowed {
+            return None;
+        }
+        let reversed = self.last.is_some_and(|last| last != direction);
+        self.last = Some(direction);
+        if reversed && self.backlash.enabled {
+            Some((direction, duration.saturating_add(self.backlash.pulse)))
+        } else {
+            Some((direction, duration))
+        }
+    }
+}
+
+/// The running guide loop: one frame, one measurement, at most one
+/// pulse per axis, repeated until told to stop.
+struct GuideLoop {
+    rig: Arc<Rig>,
+    telemetry: Arc<Telemetry>,
+    control: Arc<Mutex<LoopControl>>,
+    calibration: Calibration,
+    ra: Box<dyn GuideAlgorithm>,
+    dec: Box<dyn GuideAlgorithm>,
+    dec_output: DecOutput,
+    max_pulse: Duration,
+    /// The star's last measured position; the next search centre.
+    star: (f64, f64),
+    frame: u64,
+}
+
+impl GuideLoop {
+    async fn run(mut self) {
+        loop {
+            let (stop, pause) = {
+                let control = lock(&self.control);
+                (control.stop, control.pause)
+            };
+            if stop {
+                break;
+            }
+            if pause == Some(PauseMode::Full) {
+                tokio::time::sleep(PAUSE_POLL).await;
+                continue;
+            }
+            let frame = match self.rig.capture().await {
+                Ok(frame) => frame,
+                Err(e) => {
+                    warn!("guide frame failed: {e}");
+                    self.fail_settle(e);
+                    tokio::time::sleep(CAPTURE_RETRY).await;
+                    continue;
+                }
+            };
+            self.frame += 1;
+            let reselect = lock(&self.control).reselect.take();
+            match reselect {
+                Some(reply) => self.reselect(frame, reply).await,
+                None => self.step(&frame).await,
+            }
+        }
+        self.fail_settle(ServiceError::GuideFailed(
+            "guiding stopped before the star settled".to_string(),
+        ));
+    }
+
+    async fn reselect(&mut self, frame: Array2<i32>, reply: Reply) {
+        let result = self.rig.select_in(frame).await.map(|star| {
+            info!(
+                x = star.x,
+                y = star.y,
+                snr = star.snr,
+                "guide star reselected"
+            );
+            self.star = (star.x, star.y);
+            let mut control = lock(&self.control);
+            control.lock = (star.x, star.y);
+            control.lock_moved = true;
+        });
+        let _ = reply.send(result);
+    }
+
+    async fn step(&mut self, frame: &Array2<i32>) {
+        let (target, paused, moved, retune) = {
+            let mut control = lock(&self.control);
+            let moved = std::mem::take(&mut control.lock_moved);
+            (
+                control.lock,
+                control.pause.is_some(),
+                moved,
+                control.retune.take(),
+            )
+        };
+        if let Some(tuning) = retune {
+            self.ra = tuning.ra.build();
+            self.dec = tuning.dec.build();
+            self.dec_output.mode = tuning.dec_guide_mode;
+            self.dec_output.backlash = tuning.backlash;
+        } else if moved {
+            self.ra.reset();
+            self.dec.reset();
+        }
+
+        let Some(star) =
+            find_star_near(frame.view(), self.star.0, self.star.1, &self.rig.detection)
+        else {
+            debug!(frame = self.frame, "guide star lost");
+            self.telemetry.set_state(AppState::LostLock);
+            self.telemetry.push_metrics(FrameMetrics {
+                frame: self.frame,
+                hfd: None,
+                snr: None,
+                star_mass: None,
+                star_lost: true,
+            });
+            self.observe_settle(None);
+            return;
+        };
+        self.star = (star.x, star.y);
+        self.telemetry.set_state(if paused {
+            AppState::Paused
+        } else {
+            AppState::Guiding
+        });
+
+        let (dx, dy) = (star.x - target.0, star.y - target.1);
+        let (ra_error, dec_error) = self.calibration.to_mount(dx, dy);
+        lock(&self.telemetry.stats).push(
+            Some(ra_error),
+            Some(dec_error),
+            Some(star.snr),
+            Some(star.mass),
+        );
+        self.telemetry.push_metrics(FrameMetrics {
+            frame: self.frame,
+            hfd: star.hfd,
+            snr: Some(star.snr),
+            star_mass: Some(star.mass),
+            star_lost: false,
+        });
+        self.observe_settle(Some(dx.hypot(dy)));
+        if paused {
+            return;
+        }
+
+        let ra_pulse = self
+            .calibration
+            .ra_pulse(self.ra.result(ra_error), self.max_pulse);
+        let dec_pulse = self.dec_output.shape(
+            self.calibration
+                .dec_pulse(self.dec.result(dec_error), self.max_pulse),
+        );
+        debug!(
+            frame = self.frame,
+            ra_error,
+            dec_error,
+            ?ra_pulse,
+            ?dec_pulse,
+            "guide step"
+        );
+        for (direction, duration) in [ra_pulse, dec_pulse].into_iter().flatten() {
+            if let Err(e) = self.rig.pulse(direction, duration).await {
+                warn!("guide pulse failed: {e}");
+                self.fail_settle(e);
+                break;
+            }
+        }
+    }
+
+    fn observe_settle(&self, distance: Option<f64>) {
+        let mut control = lock(&self.control);
+        let Some((tracker, _)) = control.settle.as_mut() else {
+            return;
+        };
+        let outcome = match tracker.observe(distance, Instant::now()) {
+            SettleProgress::Settling => return,
+            SettleProgress::Settled => Ok(()),
+            SettleProgress::TimedOut => Err(ServiceError::GuideFailed(format!(
+                "the star did not settle within {}",
+                humantime::format_duration(tracker.timeout())
+            ))),
+        };
+        if let Some((_, reply)) = control.settle.take() {
+            let _ = reply.send(outcome);
+        }
+    }
+
+    fn fail_settle(&self, error: ServiceError) {
+        if let Some((_, reply)) = lock(&self.control).settle.take() {
+            let _ = reply.send(Err(error));
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::testing::SimRig;
+
+    fn approx(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-9, "{a} !~ {b}");
+    }
+
+    #[test]
+    fn the_rms_window_computes_per_axis_and_total_rms() {
+        let mut window = StatsWindow::default();
+        window.push(Some(0.3), Some(-0.4), Some(25.1), Some(5340.0));
+        window.push(Some(-0.3), Some(0.4), Some(26.0), None);
+        let snap = window.snapshot();
+        approx(snap.rms_ra_px.unwrap(), 0.3);
+        approx(snap.rms_dec_px.unwrap(), 0.4);
+        approx(snap.total_rms_px.unwrap(), 0.5);
+        assert_eq!(snap.sample_count, 2);
+        approx(snap.snr.unwrap(), 26.0);
+        assert_eq!(snap.star_mass, None);
+    }
+
+    #[test]
+    fn the_window_is_capped_at_fifty_steps() {
+        let mut window = StatsWindow::default();
+        for _ in 0..RMS_WINDOW {
+            window.push(Some(1.0), Some(1.0), None, None);
+        }
+        window.push(Some(0.0), Some(0.0), None, None);
+        let snap = window.snapshot();
+        assert_eq!(snap.sample_count, RMS_WINDOW as u32);
+        approx(snap.rms_ra_px.unwrap(), (49.0f64 / 50.0).sqrt());
+    }
+
+    #[test]
+    fn settle_overrides_merge_field_by_field_onto_the_defaults() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        let merged = ops.resolve_settle(Some(2.0), None, Some(Duration::from_secs(30)));
+        approx(merged.pixels, 2.0);
+        assert_eq!(merged.time, Duration::ZERO);
+        assert_eq!(merged.timeout, Duration::from_secs(30));
+    }
+
+    fn settle() -> SettleParams {
+        SettleParams {
+            pixels: 1.5,
+            time: Duration::from_millis(50),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    #[tokio::test]
+    async fn start_calibrates_guides_and_settles() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        let snapshot = ops.start_guiding(settle(), false).await.unwrap();
+        assert!(snapshot.sample_count >= 1);
+        assert!(lock(&ops.calibration).is_some());
+        let stats = ops.stats().await.unwrap();
+        assert_eq!(stats.app_state, AppState::Guiding);
+        ops.stop().await.unwrap();
+        assert_eq!(ops.stats().await.unwrap().app_state, AppState::Stopped);
+    }
+
+    #[tokio::test]
+    async fn the_loop_corrects_drift() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        let pulses_before = sim.pulse_count();
+        sim.set_drift(0.4, -0.3);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        ops.stop().await.unwrap();
+        assert!(sim.pulse_count() > pulses_before, "no corrections sent");
+        let snapshot = ops.stats().await.unwrap().snapshot;
+        assert!(
+            snapshot.total_rms_px.unwrap() < 2.0,
+            "drift not held: {snapshot:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn dither_moves_the_lock_and_settles_again() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        let before = sim.star();
+        ops.dither(5.0, false, settle()).await.unwrap();
+        let after = sim.star();
+        // The loop walked the star to the dithered lock position.
+        let lock_pos = lock(&lock(&ops.running).as_ref().unwrap().control).lock;
+        assert!((after.0 - lock_pos.0).hypot(after.1 - lock_pos.1) <= 1.5);
+        assert!((after.0 - before.0).hypot(after.1 - before.1) <= 5.0 * 2f64.sqrt() + 1.5);
+        ops.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn dither_is_rejected_unless_guiding() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        let err = ops.dither(3.0, false, settle()).await.unwrap_err();
+        assert!(
+            matches!(err, ServiceError::NotGuiding(ref s) if s == "Stopped"),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_second_start_reuses_the_calibration() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        ops.stop().await.unwrap();
+        let pulses = sim.pulse_count();
+        ops.start_guiding(settle(), false).await.unwrap();
+        ops.stop().await.unwrap();
+        // No calibration walk: at most a few guide corrections.
+        assert!(sim.pulse_count() - pulses < 10);
+    }
+
+    #[tokio::test]
+    async fn clearing_the_calibration_forces_a_new_one() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        ops.stop().await.unwrap();
+        ops.clear_calibration(CalibrationTarget::Ao).await.unwrap();
+        assert!(lock(&ops.calibration).is_some());
+        ops.clear_calibration(CalibrationTarget::Mount)
+            .await
+            .unwrap();
+        assert!(lock(&ops.calibration).is_none());
+    }
+
+    #[tokio::test]
+    async fn an_empty_sky_fails_the_start() {
+        let sim = SimRig::new();
+        sim.set_star_visible(false);
+        let ops = sim.ops();
+        let err = ops.start_guiding(settle(), false).await.unwrap_err();
+        assert!(matches!(err, ServiceError::GuideFailed(_)), "{err:?}");
+        assert_eq!(ops.stats().await.unwrap().app_state, AppState::Stopped);
+    }
+
+    #[tokio::test]
+    async fn a_dead_camera_is_reported_as_unreachable() {
+        let sim = SimRig::new();
+        sim.set_camera_failing(true);
+        let ops = sim.ops();
+        let err = ops.start_guiding(settle(), false).await.unwrap_err();
+        assert!(
+            matches!(err, ServiceError::EquipmentUnreachable(_)),
+            "{err:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn a_lost_star_is_reported_and_recovered() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        sim.set_star_visible(false);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(ops.stats().await.unwrap().app_state, AppState::LostLock);
+        assert!(ops
+            .metrics()
+            .await
+            .unwrap()
+            .frames
+            .iter()
+            .any(|f| f.star_lost));
+        sim.set_star_visible(true);
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(ops.stats().await.unwrap().app_state, AppState::Guiding);
+        ops.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn pause_and_resume_report_their_state() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        ops.pause(true).await.unwrap();
+        assert_eq!(ops.stats().await.unwrap().app_state, AppState::Paused);
+        ops.resume().await.unwrap();
+        assert_eq!(ops.stats().await.unwrap().app_state, AppState::Guiding);
+        ops.stop().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn reselect_needs_a_running_loop() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        let err = ops.reselect_star().await.unwrap_err();
+        assert!(matches!(err, ServiceError::GuideFailed(_)), "{err:?}");
+        ops.start_guiding(settle(), false).await.unwrap();
+        ops.reselect_star().await.unwrap();
+        ops.stop().await.unwrap();
+    }
+
+    fn backlash(pulse_ms: u64) -> BacklashConfig {
+        BacklashConfig {
+            enabled: true,
+            pulse: Duration::from_millis(pulse_ms),
+        }
+    }
+
+    #[test]
+    fn a_one_sided_dec_mode_drops_the_other_direction() {
+        let pulse = |direction| Some((direction, Duration::from_millis(100)));
+        let mut north = DecOutput::new(DecGuideMode::North, BacklashConfig::default());
+        assert!(north.shape(pulse(GuideDirection::South)).is_none());
+        assert_eq!(
+            north.shape(pulse(GuideDirection::North)),
+            pulse(GuideDirection::North)
+        );
+        let mut off = DecOutput::new(DecGuideMode::Off, BacklashConfig::default());
+        assert!(off.shape(pulse(GuideDirection::North)).is_none());
+    }
+
+    #[test]
+    fn backlash_compensation_pads_only_the_first_pulse_after_a_reversal() {
+        let mut dec = DecOutput::new(DecGuideMode::Auto, backlash(300));
+        let pulse = |direction| Some((direction, Duration::from_millis(100)));
+        // The first pulse has nothing to reverse from.
+        assert_eq!(
+            dec.shape(pulse(GuideDirection::North)),
+            pulse(GuideDirection::North)
+        );
+        assert_eq!(
+            dec.shape(pulse(GuideDirection::South)),
+            Some((GuideDirection::South, Duration::from_millis(400)))
+        );
+        assert_eq!(
+            dec.shape(pulse(GuideDirection::South)),
+            pulse(GuideDirection::South)
+        );
+    }
+
+    #[tokio::test]
+    async fn a_settings_update_applies_by_config_name() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        let tuning = ops
+            .update_settings(SettingsUpdate {
+                dec_guide_mode: Some(DecGuideMode::South),
+                dec: AxisUpdate {
+                    min_move_px: Some(0.4),
+                    params: BTreeMap::from([("fast_switch".to_string(), 0.0)]),
+                    ..AxisUpdate::default()
+                },
+                backlash: Some(backlash(250)),
+                ..SettingsUpdate::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(tuning.dec_guide_mode, DecGuideMode::South);
+        assert_eq!(tuning.dec.params()["min_move_px"], 0.4);
+        assert_eq!(tuning.dec.params()["fast_switch"], 0.0);
+        assert_eq!(ops.settings().await.unwrap(), tuning);
+    }
+
+    #[tokio::test]
+    async fn an_invalid_settings_update_changes_nothing() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        let before = ops.settings().await.unwrap();
+        let err = ops
+            .update_settings(SettingsUpdate {
+                dec_guide_mode: Some(DecGuideMode::Off),
+                ra: AxisUpdate {
+                    params: BTreeMap::from([("fast_switch".to_string(), 0.0)]),
+                    ..AxisUpdate::default()
+                },
+                ..SettingsUpdate::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, ServiceError::InvalidRequest(ref m) if m.contains("fast_switch")),
+            "{err:?}"
+        );
+        let err = ops
+            .update_settings(SettingsUpdate {
+                backlash: Some(backlash(0)),
+                ..SettingsUpdate::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ServiceError::InvalidRequest(_)), "{err:?}");
+        assert_eq!(ops.settings().await.unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn dec_guide_mode_off_stops_dec_corrections_while_guiding() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.start_guiding(settle(), false).await.unwrap();
+        ops.update_settings(SettingsUpdate {
+            dec_guide_mode: Some(DecGuideMode::Off),
+            ..SettingsUpdate::default()
+        })
+        .await
+        .unwrap();
+        // Drift along the Dec axis only: with Dec guiding off, nothing
+        // corrects it.
+        let dec = (SimRig::RA_ANGLE_DEG + 90.0).to_radians();
+        sim.set_drift(0.3 * dec.cos(), 0.3 * dec.sin());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        ops.stop().await.unwrap();
+        let snapshot = ops.stats().await.unwrap().snapshot;
+        assert!(
+            snapshot.rms_dec_px.unwrap() > 2.0,
+            "Dec drift was corrected: {snapshot:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_is_idempotent() {
+        let sim = SimRig::new();
+        let ops = sim.ops();
+        ops.stop().await.unwrap();
+        ops.stop().await.unwrap();
+    }
+}
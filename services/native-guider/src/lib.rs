@@ -0,0 +1,171 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod algorithms;
+pub mod api;
+pub mod calibration;
+pub mod config;
+pub mod doctor;
+pub mod equipment;
+pub mod error;
+pub mod guider;
+pub mod settle;
+pub mod star;
+
+#[cfg(test)]
+mod testing;
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use tracing::{debug, info};
+
+use crate::config::GuiderConfig;
+use crate::equipment::{AlpacaGuideCamera, AlpacaGuideMount, GuideCamera, GuideMount};
+use crate::error::{NativeGuiderError, Result};
+use crate::guider::GuiderOps;
+
+/// Two-phase server builder: `build()` binds the listener (so the bound
+/// port is known up-front), then `start()` serves.
+pub struct ServerBuilder {
+    config: Option<GuiderConfig>,
+    camera: Option<Arc<dyn GuideCamera>>,
+    mount: Option<Arc<dyn GuideMount>>,
+}
+
+impl ServerBuilder {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            config: None,
+            camera: None,
+            mount: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_config(mut self, config: GuiderConfig) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Override the guide camera (tests inject a simulated one;
+    /// production builds an Alpaca client from `config.camera`).
+    #[must_use]
+    pub fn with_camera(mut self, camera: Arc<dyn GuideCamera>) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// Override the mount, as [`Self::with_camera`].
+    #[must_use]
+    pub fn with_mount(mut self, mount: Arc<dyn GuideMount>) -> Self {
+        self.mount = Some(mount);
+        self
+    }
+
+    pub async fn build(self) -> Result<BoundServer> {
+        let config = self.config.ok_or_else(|| {
+            NativeGuiderError::Config(
+                "ServerBuilder::build: config is required \u{2014} call .with_config(...) first"
+                    .to_string(),
+            )
+        })?;
+
+        let camera: Arc<dyn GuideCamera> = match self.camera {
+            Some(camera) => camera,
+            None => Arc::new(
+                AlpacaGuideCamera::from_config(&config.camera, config.ca_cert_path())
+                    .map_err(NativeGuiderError::Config)?,
+            ),
+        };
+        let mount: Arc<dyn GuideMount> = match self.mount {
+            Some(mount) => mount,
+            None => Arc::new(
+                AlpacaGuideMount::from_config(&config.mount, config.ca_cert_path())
+                    .map_err(NativeGuiderError::Config)?,
+            ),
+        };
+        let ops = Arc::new(GuiderOps::new(camera, mount, &config));
+
+        // Bind before spawning the connect-retry task: a bind failure a
+        // caller chooses to handle must not leak it.
+        let server = config.server.clone();
+        let listener = tokio::net::TcpListener::bind(server.socket_addr()).await?;
+        let local_addr = listener.local_addr()?;
+
+        // Equipment that is off at startup is not fatal: the retry task
+        // connects it whenever it appears, and reconnects it after a drop.
+        ops.spawn_connect_retry(config.reconnect_interval);
+
+        let router = api::build_router(ops);
+
+        // Layer HTTP Basic Auth when configured (server.auth).
+        let router = match &server.auth {
+            Some(auth) => {
+                if server.tls.is_none() {
+                    tracing::warn!(
+                        "Authentication is enabled but TLS is not. Credentials will be \
+                         transmitted in cleartext. Consider enabling TLS (see `doctor --fix`)."
+                    );
+                }
+                rp_auth::layer(router, auth)
+            }
+            None => router,
+        };
+
+        // This println is parsed by BDD tests to discover the bound port.
+        // Console mode only: stdout is a dead handle under the Windows SCM,
+        // and the only stdout consumer (bdd-infra's port parser) never runs
+        // services with --service.
+        if !rusty_photon_service_lifecycle::is_scm_service() {
+            println!("Bound native-guider server bound_addr={local_addr}");
+        }
+        info!("native-guider service bound on {}", local_addr);
+
+        Ok(BoundServer {
+            listener,
+            router,
+            local_addr,
+            tls: server.tls,
+        })
+    }
+}
+
+impl Default for ServerBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A fully bound native-guider server ready to accept connections.
+pub struct BoundServer {
+    listener: tokio::net::TcpListener,
+    router: axum::Router,
+    local_addr: SocketAddr,
+    tls: Option<rusty_photon_tls::config::TlsConfig>,
+}
+
+impl BoundServer {
+    pub const fn listen_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub async fn start(self, shutdown: impl Future<Output = ()> + Send + 'static) -> Result<()> {
+        info!("native-guider service started on {}", self.local_addr);
+
+        match self.tls {
+            Some(ref tls) => {
+                rusty_photon_tls::server::serve_tls(self.listener, self.router, tls, shutdown)
+                    .await
+                    .map_err(|e| NativeGuiderError::Server(e.to_string()))?;
+            }
+            None => axum::serve(self.listener, self.router)
+                .with_graceful_shutdown(shutdown)
+                .await
+                .map_err(|e| NativeGuiderError::Server(e.to_string()))?,
+        }
+
+        debug!("native-guider service shut down");
+        Ok(())
+    }
+}
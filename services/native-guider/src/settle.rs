@@ -0,0 +1,134 @@
+//! Settle detection: has the star stayed within `pixels` of the lock
+//! position for `time`, before `timeout` runs out?
+
+use tokio::time::{Duration, Instant};
+
+use crate::config::SettleParams;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettleProgress {
+    Settling,
+    Settled,
+    TimedOut,
+}
+
+#[derive(Debug)]
+pub struct SettleTracker {
+    params: SettleParams,
+    started: Instant,
+    /// When the current in-range streak began.
+    in_range_since: Option<Instant>,
+}
+
+impl SettleTracker {
+    #[must_use]
+    pub const fn new(params: SettleParams, now: Instant) -> Self {
+        Self {
+            params,
+            started: now,
+            in_range_since: None,
+        }
+    }
+
+    /// Feed one frame: the star's distance from the lock position, or
+    /// `None` when the star was not found. A lost star breaks the streak.
+    pub fn observe(&mut self, distance: Option<f64>, now: Instant) -> SettleProgress {
+        match distance {
+            Some(d) if d <= self.params.pixels => {
+                let since = *self.in_range_since.get_or_insert(now);
+                if now.saturating_duration_since(since) >= self.params.time {
+                    return SettleProgress::Settled;
+                }
+            }
+            _ => self.in_range_since = None,
+        }
+        if now.saturating_duration_since(self.started) >= self.params.timeout {
+            SettleProgress::TimedOut
+        } else {
+            SettleProgress::Settling
+        }
+    }
+
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        self.params.timeout
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn params(time_secs: u64) -> SettleParams {
+        SettleParams {
+            pixels: 1.5,
+            time: Duration::from_secs(time_secs),
+            timeout: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn settles_once_in_range_for_the_settle_time() {
+        let t0 = Instant::now();
+        let mut tracker = SettleTracker::new(params(10), t0);
+        assert_eq!(tracker.observe(Some(0.5), t0), SettleProgress::Settling);
+        let t1 = t0 + Duration::from_secs(5);
+        assert_eq!(tracker.observe(Some(1.0), t1), SettleProgress::Settling);
+        let t2 = t0 + Duration::from_secs(10);
+        assert_eq!(tracker.observe(Some(1.4), t2), SettleProgress::Settled);
+    }
+
+    #[test]
+    fn leaving_the_range_restarts_the_streak() {
+        let t0 = Instant::now();
+        let mut tracker = SettleTracker::new(params(10), t0);
+        tracker.observe(Some(0.5), t0);
+        tracker.observe(Some(3.0), t0 + Duration::from_secs(5));
+        let t1 = t0 + Duration::from_secs(6);
+        assert_eq!(tracker.observe(Some(0.5), t1), SettleProgress::Settling);
+        assert_eq!(
+            tracker.observe(Some(0.5), t0 + Duration::from_secs(12)),
+            SettleProgress::Settling
+        );
+        assert_eq!(
+            tracker.observe(Some(0.5), t0 + Duration::from_secs(16)),
+            SettleProgress::Settled
+        );
+    }
+
+    #[test]
+    fn a_lost_star_breaks_the_streak() {
+        let t0 = Instant::now();
+        let mut tracker = SettleTracker::new(params(10), t0);
+        tracker.observe(Some(0.5), t0);
+        tracker.observe(None, t0 + Duration::from_secs(5));
+        assert_eq!(
+            tracker.observe(Some(0.5), t0 + Duration::from_secs(10)),
+            SettleProgress::Settling
+        );
+    }
+
+    #[test]
+    fn zero_settle_time_settles_on_the_first_frame_in_range() {
+        let t0 = Instant::now();
+        let mut tracker = SettleTracker::new(params(0), t0);
+        assert_eq!(tracker.observe(Some(2.0), t0), SettleProgress::Settling);
+        assert_eq!(tracker.observe(Some(1.0), t0), SettleProgress::Settled);
+    }
+
+    #[test]
+    fn times_out_when_never_settled() {
+        let t0 = Instant::now();
+        let mut tracker = SettleTracker::new(params(10), t0);
+        assert_eq!(
+            tracker.observe(Some(5.0), t0 + Duration::from_secs(59)),
+            SettleProgress::Settling
+        );
+        assert_eq!(
+            tracker.observe(Some(5.0), t0 + Duration::from_secs(60)),
+            SettleProgress::TimedOut
+        );
+        assert_eq!(tracker.timeout(), Duration::from_secs(60));
+    }
+}
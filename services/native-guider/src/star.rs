@@ -0,0 +1,216 @@
+//! Guide-star selection and per-frame centroiding, on rp's star
+//! detection kernels (`rp-imaging`).
+//!
+//! Selection runs once over the full frame and picks the highest-SNR
+//! unsaturated star clear of the edges. Every later frame is only
+//! searched in a box around the star's last position — the box keeps
+//! a neighbouring star from being mistaken for the guide star and keeps
+//! the per-frame cost independent of the sensor size.
+
+use ndarray::{s, ArrayView2};
+use rp_imaging::{
+    detect_stars, estimate_background, per_star_snr, star_hfr, BackgroundStats, DetectionParams,
+    Star,
+};
+
+use crate::config::DetectionConfig;
+
+/// Gaussian smoothing before thresholding; rp's tools use the same.
+const SMOOTHING_SIGMA: f64 = 1.0;
+
+/// One measurement of the guide star on one frame, in full-frame
+/// pixel coordinates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StarMeasurement {
+    pub x: f64,
+    pub y: f64,
+    pub snr: f64,
+    /// Background-subtracted total flux.
+    pub mass: f64,
+    /// Half-flux diameter, when the profile could be measured.
+    pub hfd: Option<f64>,
+}
+
+impl StarMeasurement {
+    /// Distance from `(x, y)` in pixels.
+    #[must_use]
+    pub fn distance_to(&self, x: f64, y: f64) -> f64 {
+        (self.x - x).hypot(self.y - y)
+    }
+}
+
+const fn detection_params(config: &DetectionConfig) -> DetectionParams {
+    DetectionParams {
+        threshold_sigma: config.threshold_sigma,
+        smoothing_sigma: SMOOTHING_SIGMA,
+        min_area: config.min_area,
+        max_area: config.max_area,
+        max_adu: config.saturation_adu,
+    }
+}
+
+fn measure(
+    view: ArrayView2<'_, i32>,
+    star: &Star,
+    background: &BackgroundStats,
+    origin: (usize, usize),
+) -> StarMeasurement {
+    StarMeasurement {
+        x: star.centroid_x + origin.0 as f64,
+        y: star.centroid_y + origin.1 as f64,
+        snr: per_star_snr(star, background.stddev).map_or(0.0, |(_, _, snr)| snr),
+        mass: star.total_flux,
+        hfd: star_hfr(view, star, background.mean).map(|hfr| 2.0 * hfr),
+    }
+}
+
+/// Pick the guide star on a full frame: the highest-SNR star with no
+/// clipped pixels, at least `min_snr`, and at least the search radius
+/// from every edge. `None` when no star qualifies.
+#[must_use]
+pub fn select_guide_star(
+    frame: ArrayView2<'_, i32>,
+    config: &DetectionConfig,
+) -> Option<StarMeasurement> {
+    let background = estimate_background(frame)?;
+    let (width, height) = frame.dim();
+    let margin = config.search_radius_px;
+    let inside = |m: &StarMeasurement| {
+        m.x >= margin
+            && m.y >= margin
+            && m.x <= width as f64 - 1.0 - margin
+            && m.y <= height as f64 - 1.0 - margin
+    };
+    detect_stars(frame, &background, &detection_params(config))
+        .iter()
+        .filter(|star| star.saturated_pixel_count == 0)
+        .map(|star| measure(frame, star, &background, (0, 0)))
+        .filter(|m| m.snr >= config.min_snr && inside(m))
+        .max_by(|a, b| a.snr.total_cmp(&b.snr))
+}
+
+/// Find the guide star near `(x, y)`: the detected star closest to it
+/// within the search box. `None` means the star is lost.
+#[must_use]
+pub fn find_star_near(
+    frame: ArrayView2<'_, i32>,
+    x: f64,
+    y: f64,
+    config: &DetectionConfig,
+) -> Option<StarMeasurement> {
+    let (width, height) = frame.dim();
+    let radius = config.search_radius_px;
+    let bound = |centre: f64, len: usize| {
+        let lo = (centre - radius).floor().max(0.0) as usize;
+        let hi = ((centre + radius).ceil().max(0.0) as usize + 1).min(len);
+        (lo.min(hi), hi)
+    };
+    let (x0, x1) = bound(x, width);
+    let (y0, y1) = bound(y, height);
+    if x1 <= x0 || y1 <= y0 {
+        return None;
+    }
+    let window = frame.slice(s![x0..x1, y0..y1]);
+    let background = estimate_background(window)?;
+    detect_stars(window, &background, &detection_params(config))
+        .iter()
+        .map(|star| measure(window, star, &background, (x0, y0)))
+        .filter(|m| m.distance_to(x, y) <= radius)
+        .min_by(|a, b| a.distance_to(x, y).total_cmp(&b.distance_to(x, y)))
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+pub(crate) mod tests {
+    use super::*;
+    use ndarray::Array2;
+
+    /// A `width × height` frame: flat sky at 1000 ADU with a small
+    /// deterministic ripple, plus one Gaussian per `(x, y, peak)`.
+    pub(crate) fn synthetic_frame(
+        width: usize,
+        height: usize,
+        stars: &[(f64, f64, f64)],
+    ) -> Array2<i32> {
+        Array2::from_shape_fn((width, height), |(x, y)| {
+            let ripple = ((x * 7 + y * 13) % 11) as f64 - 5.0;
+            let flux: f64 = stars
+                .iter()
+                .map(|&(sx, sy, peak)| {
+                    let r2 = (x as f64 - sx).powi(2) + (y as f64 - sy).powi(2);
+                    peak * (-r2 / (2.0 * 1.5 * 1.5)).exp()
+                })
+                .sum();
+            (1000.0 + ripple + flux).round() as i32
+        })
+    }
+
+    fn config() -> DetectionConfig {
+        DetectionConfig {
+            min_snr: 5.0,
+            ..DetectionConfig::default()
+        }
+    }
+
+    #[test]
+    fn selection_prefers_the_brightest_star_clear_of_the_edges() {
+        // The brightest star sits 5 px from the left edge, inside the
+        // 15 px margin, so the runner-up is chosen.
+        let frame = synthetic_frame(
+            120,
+            100,
+            &[
+                (5.0, 50.0, 20000.0),
+                (60.0, 40.0, 8000.0),
+                (90.0, 70.0, 3000.0),
+            ],
+        );
+        let star = select_guide_star(frame.view(), &config()).unwrap();
+        assert!((star.x - 60.0).abs() < 0.2, "x = {}", star.x);
+        assert!((star.y - 40.0).abs() < 0.2, "y = {}", star.y);
+        assert!(star.snr > 5.0);
+        assert!(star.mass > 0.0);
+        let hfd = star.hfd.unwrap();
+        assert!(hfd > 1.0 && hfd < 6.0, "hfd = {hfd}");
+    }
+
+    #[test]
+    fn selection_skips_saturated_stars() {
+        let frame = synthetic_frame(120, 100, &[(60.0, 40.0, 20000.0), (80.0, 60.0, 4000.0)]);
+        let config = DetectionConfig {
+            saturation_adu: Some(15000),
+            ..config()
+        };
+        let star = select_guide_star(frame.view(), &config).unwrap();
+        assert!((star.x - 80.0).abs() < 0.2, "x = {}", star.x);
+    }
+
+    #[test]
+    fn an_empty_frame_has_no_guide_star() {
+        let frame = synthetic_frame(120, 100, &[]);
+        assert!(select_guide_star(frame.view(), &config()).is_none());
+    }
+
+    #[test]
+    fn the_star_is_tracked_in_full_frame_coordinates() {
+        let frame = synthetic_frame(120, 100, &[(63.4, 41.7, 6000.0), (90.0, 70.0, 9000.0)]);
+        let star = find_star_near(frame.view(), 60.0, 40.0, &config()).unwrap();
+        assert!((star.x - 63.4).abs() < 0.1, "x = {}", star.x);
+        assert!((star.y - 41.7).abs() < 0.1, "y = {}", star.y);
+    }
+
+    #[test]
+    fn a_star_outside_the_search_box_is_lost() {
+        let frame = synthetic_frame(120, 100, &[(90.0, 70.0, 9000.0)]);
+        assert!(find_star_near(frame.view(), 40.0, 40.0, &config()).is_none());
+    }
+
+    #[test]
+    fn the_search_box_is_clipped_at_the_frame_edge() {
+        // Searching from a corner: the box is cut off on two sides.
+        let frame = synthetic_frame(120, 100, &[(8.0, 91.0, 4000.0)]);
+        let star = find_star_near(frame.view(), 2.0, 98.0, &config()).unwrap();
+        assert!((star.x - 8.0).abs() < 0.3, "x = {}", star.x);
+        assert!((star.y - 91.0).abs() < 0.3, "y = {}", star.y);
+    }
+}
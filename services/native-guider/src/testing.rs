@@ -0,0 +1,194 @@
+//! A simulated guide rig for unit tests: one star on a synthetic frame
+//! that moves under guide pulses along fixed, known axes.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ndarray::Array2;
+
+use crate::config::{CalibrationConfig, DetectionConfig, GuiderConfig, SettleParams};
+use crate::equipment::{DeviceStatus, GuideCamera, GuideDirection, GuideMount};
+use crate::guider::{GuiderOps, Rig};
+use crate::star::tests::synthetic_frame;
+
+const WIDTH: usize = 160;
+const HEIGHT: usize = 120;
+
+/// Stands in for the exposure, so the guide loop yields to the runtime.
+const FRAME_TIME: Duration = Duration::from_millis(2);
+
+#[derive(Debug)]
+struct SimState {
+    star: (f64, f64),
+    /// Added to the star position before every frame.
+    drift: (f64, f64),
+    visible: bool,
+    frozen: bool,
+    camera_failing: bool,
+    pulses: usize,
+}
+
+#[derive(Clone)]
+pub(crate) struct SimRig {
+    state: Arc<Mutex<SimState>>,
+}
+
+impl SimRig {
+    /// Direction a West pulse moves the star, from the +x axis.
+    pub(crate) const RA_ANGLE_DEG: f64 = 30.0;
+    pub(crate) const RATE_PX_PER_SEC: f64 = 10.0;
+
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(SimState {
+                star: (80.0, 60.0),
+                drift: (0.0, 0.0),
+                visible: true,
+                frozen: false,
+                camera_failing: false,
+                pulses: 0,
+            })),
+        }
+    }
+
+    fn with<T>(&self, f: impl FnOnce(&mut SimState) -> T) -> T {
+        f(&mut self.state.lock().unwrap())
+    }
+
+    pub(crate) fn star(&self) -> (f64, f64) {
+        self.with(|s| s.star)
+    }
+
+    pub(crate) fn pulse_count(&self) -> usize {
+        self.with(|s| s.pulses)
+    }
+
+    pub(crate) fn set_drift(&self, dx: f64, dy: f64) {
+        self.with(|s| s.drift = (dx, dy));
+    }
+
+    pub(crate) fn set_star_visible(&self, visible: bool) {
+        self.with(|s| s.visible = visible);
+    }
+
+    /// A mount that accepts pulses but never moves.
+    pub(crate) fn set_frozen(&self, frozen: bool) {
+        self.with(|s| s.frozen = frozen);
+    }
+
+    pub(crate) fn set_camera_failing(&self, failing: bool) {
+        self.with(|s| s.camera_failing = failing);
+    }
+
+    fn config() -> GuiderConfig {
+        serde_json::from_value(serde_json::json!({
+            "camera": { "alpaca_url": "http://127.0.0.1:1" },
+            "mount": { "alpaca_url": "http://127.0.0.1:1" },
+        }))
+        .map(|mut config: GuiderConfig| {
+            config.exposure = FRAME_TIME;
+            config.detection = DetectionConfig {
+                min_snr: 5.0,
+                ..DetectionConfig::default()
+            };
+            config.calibration = CalibrationConfig {
+                step: Duration::from_millis(500),
+                distance_px: 15.0,
+                max_steps: 20,
+                max_orthogonality_error_deg: 30.0,
+            };
+            config.settling = SettleParams {
+                pixels: 1.5,
+                time: Duration::ZERO,
+                timeout: Duration::from_secs(10),
+            };
+            config
+        })
+        .unwrap()
+    }
+
+    pub(crate) fn rig(&self) -> Rig {
+        let config = Self::config();
+        Rig::new(
+            Arc::new(self.clone()),
+            Arc::new(self.clone()),
+            config.exposure,
+            config.detection,
+        )
+    }
+
+    pub(crate) fn ops(&self) -> GuiderOps {
+        GuiderOps::new(
+            Arc::new(self.clone()),
+            Arc::new(self.clone()),
+            &Self::config(),
+        )
+    }
+}
+
+#[async_trait]
+impl GuideCamera for SimRig {
+    async fn connect(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn status(&self) -> DeviceStatus {
+        DeviceStatus {
+            name: "sim camera".to_string(),
+            connected: true,
+        }
+    }
+
+    async fn capture(&self, exposure: Duration) -> Result<Array2<i32>, String> {
+        tokio::time::sleep(exposure).await;
+        let (star, visible) = self.with(|s| {
+            if s.camera_failing {
+                return Err("camera unplugged".to_string());
+            }
+            s.star.0 += s.drift.0;
+            s.star.1 += s.drift.1;
+            Ok((s.star, s.visible))
+        })?;
+        let stars = if visible {
+            vec![(star.0, star.1, 8000.0)]
+        } else {
+            vec![]
+        };
+        Ok(synthetic_frame(WIDTH, HEIGHT, &stars))
+    }
+}
+
+#[async_trait]
+impl GuideMount for SimRig {
+    async fn connect(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn status(&self) -> DeviceStatus {
+        DeviceStatus {
+            name: "sim mount".to_string(),
+            connected: true,
+        }
+    }
+
+    async fn pulse(&self, direction: GuideDirection, duration: Duration) -> Result<(), String> {
+        let ra = Self::RA_ANGLE_DEG.to_radians();
+        let dec = ra + std::f64::consts::FRAC_PI_2;
+        let (angle, sense) = match direction {
+            GuideDirection::West => (ra, 1.0),
+            GuideDirection::East => (ra, -1.0),
+            GuideDirection::North => (dec, 1.0),
+            GuideDirection::South => (dec, -1.0),
+        };
+        let distance = sense * Self::RATE_PX_PER_SEC * duration.as_secs_f64();
+        self.with(|s| {
+            s.pulses += 1;
+            if !s.frozen {
+                s.star.0 += distance * angle.cos();
+                s.star.1 += distance * angle.sin();
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! BDD test entry point for the native-guider service.
+//!
+//! The guiding scenarios spawn `OmniSim` (the mount), sky-survey-camera
+//! (the guide camera, following the mount over the `SkyView` stub's
+//! star field), and native-guider, and drive guiding end to end through
+//! the HTTP contract rp's guider client speaks. The unit tests in
+//! `src/guider.rs` cover the same flows against a simulated rig.
+
+#![allow(clippy::expect_used, clippy::panic)]
+
+#[path = "bdd/world.rs"]
+mod world;
+
+#[path = "bdd/steps/mod.rs"]
+mod steps;
+
+bdd_infra::bdd_main! {
+    use cucumber::World as _;
+    use world::NativeGuiderWorld;
+
+    NativeGuiderWorld::cucumber()
+        .before(|_feature, _rule, _scenario, _world| {
+            Box::pin(async move {
+                // Scenarios sync and pulse the shared OmniSim telescope;
+                // reset it so one scenario's pointing never leaks into
+                // the next (see calibrator-flats' hook for the ordering
+                // and loud-failure rationale).
+                if let Err(errors) =
+                    bdd_infra::rp_harness::OmniSimHandle::reset_all_devices().await
+                {
+                    panic!("OmniSim device reset failed: {}", errors.join("; "));
+                }
+            })
+        })
+        .after(|_feature, _rule, _scenario, _finished, maybe_world| {
+            Box::pin(async move {
+                if let Some(world) = maybe_world {
+                    if let Some(handle) = world.native_guider.as_mut() {
+                        handle.stop().await;
+                    }
+                    if let Some(handle) = world.guide_camera.as_mut() {
+                        handle.stop().await;
+                    }
+                }
+            })
+        })
+        .run_and_exit("tests/features")
+        .await;
+}
@@ -0,0 +1,8 @@
+//! TLS + HTTP Basic Auth smoke steps, expanded from the shared macro. The
+//! service-specific parts (config template, launch) live in the
+//! `TlsAuthSmokeWorld` impl in `world.rs`. The scenario's first Given
+//! puts the guide camera and mount on `OmniSim`, so `/health` answers 200.
+
+use crate::world::NativeGuiderWorld;
+
+bdd_infra::tls_auth_smoke_steps!(NativeGuiderWorld);
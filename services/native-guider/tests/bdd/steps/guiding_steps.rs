@@ -0,0 +1,271 @@
+//! Step definitions for `tests/features/guiding.feature`.
+
+use cucumber::{given, then, when};
+
+use bdd_infra::rp_harness::OmniSimHandle;
+use bdd_infra::sky_survey_camera_harness::{
+    start_sky_survey_camera, SkySurveyCameraConfigBuilder, SkyViewStub, TelescopeFollow,
+};
+
+use crate::world::NativeGuiderWorld;
+
+/// Where the scenarios point the mount: well clear of the pole, so the
+/// RA axis moves the star at a useful rate.
+const TARGET_RA_HOURS: f64 = 10.0;
+const TARGET_DEC_DEG: f64 = 20.0;
+
+// ---------------------------------------------------------------------------
+// Givens
+// ---------------------------------------------------------------------------
+
+async fn ensure_omnisim(world: &mut NativeGuiderWorld) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+}
+
+#[given("a guide camera and mount on the Alpaca simulator")]
+async fn rig_on_omnisim(world: &mut NativeGuiderWorld) {
+    ensure_omnisim(world).await;
+}
+
+#[given("a sky-survey guide camera following the simulated mount over a star field")]
+async fn sky_survey_guide_camera(world: &mut NativeGuiderWorld) {
+    ensure_omnisim(world).await;
+    // Tracking holds the star still between pulses; the sync puts the
+    // mount on the star field without a real-speed slew.
+    OmniSimHandle::set_telescope_tracking(true)
+        .await
+        .expect("enable telescope tracking");
+    OmniSimHandle::sync_telescope_to(TARGET_RA_HOURS, TARGET_DEC_DEG)
+        .await
+        .expect("sync telescope");
+
+    let stub = SkyViewStub::start_star_field().await;
+    // 400 mm at 3.76 µm is ~1.94"/px: a guide pulse moves the star a
+    // few pixels, and stars sit ~60 px apart on the stub's grid.
+    let mut cfg = SkySurveyCameraConfigBuilder::new(stub.url.clone())
+        .with_sensor(320, 240)
+        .with_initial_pointing(TARGET_RA_HOURS * 15.0, TARGET_DEC_DEG)
+        .with_follow(TelescopeFollow {
+            alpaca_url: world.omnisim_url(),
+            device_number: 0,
+            offset_ra_arcsec: 0.0,
+            offset_dec_arcsec: 0.0,
+            request_timeout: std::time::Duration::from_secs(2),
+        })
+        .build();
+    cfg.focal_length_mm = 400.0;
+
+    let (handle, cache) = start_sky_survey_camera(&cfg).await;
+    world.sky_view_stub = Some(stub);
+    world.guide_camera = Some(handle);
+    world.guide_camera_cache = Some(cache);
+}
+
+#[given("native-guider is running")]
+async fn native_guider_running(world: &mut NativeGuiderWorld) {
+    world.start_native_guider().await;
+}
+
+#[given("native-guider is guiding with a fresh calibration")]
+async fn native_guider_guiding(world: &mut NativeGuiderWorld) {
+    start_guiding_recalibrated(world).await;
+    let response = world.last_response();
+    assert_eq!(
+        response.status, 200,
+        "guiding did not start; body: {}",
+        response.body
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Whens
+// ---------------------------------------------------------------------------
+
+async fn post(world: &mut NativeGuiderWorld, path: &str, body: serde_json::Value) {
+    let url = format!("{}{}", world.service_url(), path);
+    let response = NativeGuiderWorld::http_client()
+        .post(&url)
+        .json(&body)
+        .send()
+        .await
+        .expect("HTTP request failed");
+    world.record_response(response).await;
+}
+
+async fn get(world: &mut NativeGuiderWorld, path: &str) {
+    let url = format!("{}{}", world.service_url(), path);
+    let response = NativeGuiderWorld::http_client()
+        .get(&url)
+        .send()
+        .await
+        .expect("HTTP request failed");
+    world.record_response(response).await;
+}
+
+#[when("the client starts guiding with a fresh calibration")]
+async fn start_guiding_recalibrated(world: &mut NativeGuiderWorld) {
+    post(
+        world,
+        "/api/v1/guiding/start",
+        serde_json::json!({ "recalibrate": true }),
+    )
+    .await;
+}
+
+#[when("the client starts guiding")]
+async fn start_guiding(world: &mut NativeGuiderWorld) {
+    post(world, "/api/v1/guiding/start", serde_json::json!({})).await;
+}
+
+#[when(expr = "the client dithers by {float} pixels")]
+async fn dither(world: &mut NativeGuiderWorld, amount_px: f64) {
+    post(
+        world,
+        "/api/v1/dither",
+        serde_json::json!({ "amount_px": amount_px }),
+    )
+    .await;
+}
+
+#[when("the client stops guiding")]
+async fn stop_guiding(world: &mut NativeGuiderWorld) {
+    post(world, "/api/v1/guiding/stop", serde_json::json!({})).await;
+}
+
+#[when("the client requests the guiding stats")]
+async fn request_stats(world: &mut NativeGuiderWorld) {
+    get(world, "/api/v1/guiding/stats").await;
+}
+
+#[when("the client requests the guider equipment")]
+async fn request_equipment(world: &mut NativeGuiderWorld) {
+    get(world, "/api/v1/equipment").await;
+}
+
+#[when("the client requests the guiding settings")]
+async fn request_settings(world: &mut NativeGuiderWorld) {
+    get(world, "/api/v1/guiding/settings").await;
+}
+
+#[when(
+    expr = "the client sets the Dec guide mode to {string} with backlash compensation of {string}"
+)]
+async fn set_dec_mode_and_backlash(world: &mut NativeGuiderWorld, mode: String, pulse: String) {
+    post(
+        world,
+        "/api/v1/guiding/settings",
+        serde_json::json!({
+            "dec_guide_mode": mode,
+            "backlash_compensation": { "enabled": true, "pulse": pulse },
+        }),
+    )
+    .await;
+}
+
+#[when("the client turns multi-star guiding on")]
+async fn set_multi_star(world: &mut NativeGuiderWorld) {
+    post(
+        world,
+        "/api/v1/guiding/settings",
+        serde_json::json!({ "multi_star": true }),
+    )
+    .await;
+}
+
+// ---------------------------------------------------------------------------
+// Thens
+// ---------------------------------------------------------------------------
+
+#[then(expr = "the response status should be {int}")]
+async fn response_status(world: &mut NativeGuiderWorld, status: u16) {
+    let response = world.last_response();
+    assert_eq!(
+        response.status, status,
+        "unexpected status; body: {}",
+        response.body
+    );
+}
+
+#[then(expr = "the response field {string} should be {string}")]
+async fn response_field_string(world: &mut NativeGuiderWorld, field: String, expected: String) {
+    let body = &world.last_response().body;
+    assert_eq!(
+        body[&field].as_str(),
+        Some(expected.as_str()),
+        "field {field} in {body}"
+    );
+}
+
+#[then(expr = "the response field {string} should be true")]
+async fn response_field_true(world: &mut NativeGuiderWorld, field: String) {
+    let body = &world.last_response().body;
+    assert_eq!(
+        body[&field].as_bool(),
+        Some(true),
+        "field {field} in {body}"
+    );
+}
+
+#[then(expr = "the response field {string} should be false")]
+async fn response_field_false(world: &mut NativeGuiderWorld, field: String) {
+    let body = &world.last_response().body;
+    assert_eq!(
+        body[&field].as_bool(),
+        Some(false),
+        "field {field} in {body}"
+    );
+}
+
+#[then(expr = "the backlash compensation should be enabled with a pulse of {string}")]
+async fn backlash_enabled(world: &mut NativeGuiderWorld, pulse: String) {
+    let body = &world.last_response().body;
+    let backlash = &body["backlash_compensation"];
+    assert_eq!(backlash["enabled"].as_bool(), Some(true), "in {body}");
+    assert_eq!(
+        backlash["pulse"].as_str(),
+        Some(pulse.as_str()),
+        "in {body}"
+    );
+}
+
+#[then(expr = "the response field {string} should be below {float}")]
+async fn response_field_below(world: &mut NativeGuiderWorld, field: String, limit: f64) {
+    let body = &world.last_response().body;
+    let value = body[&field]
+        .as_f64()
+        .unwrap_or_else(|| panic!("field {field} is not a number in {body}"));
+    assert!(value < limit, "field {field} = {value}, not below {limit}");
+}
+
+#[then(expr = "the response field {string} should be at least {float}")]
+async fn response_field_at_least(world: &mut NativeGuiderWorld, field: String, floor: f64) {
+    let body = &world.last_response().body;
+    let value = body[&field]
+        .as_f64()
+        .unwrap_or_else(|| panic!("field {field} is not a number in {body}"));
+    assert!(value >= floor, "field {field} = {value}, below {floor}");
+}
+
+#[then(expr = "the response error should be {string}")]
+async fn response_error(world: &mut NativeGuiderWorld, code: String) {
+    let body = &world.last_response().body;
+    assert_eq!(body["error"].as_str(), Some(code.as_str()), "in {body}");
+}
+
+#[then(expr = "the equipment {string} slot should be connected")]
+async fn equipment_slot_connected(world: &mut NativeGuiderWorld, slot: String) {
+    let body = &world.last_response().body;
+    assert_eq!(
+        body[&slot]["connected"].as_bool(),
+        Some(true),
+        "slot {slot} in {body}"
+    );
+}
+
+#[then(expr = "the equipment {string} slot should be null")]
+async fn equipment_slot_null(world: &mut NativeGuiderWorld, slot: String) {
+    let body = &world.last_response().body;
+    assert!(body[&slot].is_null(), "slot {slot} in {body}");
+}
@@ -0,0 +1,5 @@
+//! BDD step definitions for the native-guider service.
+
+pub mod auth_steps;
+pub mod doctor_steps;
+pub mod guiding_steps;
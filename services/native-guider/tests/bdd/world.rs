@@ -0,0 +1,176 @@
+//! BDD test world for the native-guider service: the simulated rig
+//! (`OmniSim`'s telescope, and either `OmniSim`'s camera or a
+//! sky-survey-camera following the telescope), the service handle, the
+//! last HTTP response, and the shared TLS + auth and doctor smoke state.
+
+use std::time::Duration;
+
+use bdd_infra::rp_harness::{write_temp_config_file, OmniSimHandle};
+use bdd_infra::sky_survey_camera_harness::SkyViewStub;
+use bdd_infra::tls_auth::{TlsAuthSmokeWorld, TlsAuthState};
+use bdd_infra::ServiceHandle;
+use cucumber::World;
+use serde_json::Value;
+
+#[derive(Default, World, derive_more::Debug)]
+#[debug("NativeGuiderWorld {{ .. }}")]
+pub struct NativeGuiderWorld {
+    pub omnisim: Option<OmniSimHandle>,
+    /// In-process `SkyView` stub serving the star field the guide
+    /// camera images.
+    pub sky_view_stub: Option<SkyViewStub>,
+    /// sky-survey-camera as the guide camera, when a scenario uses one
+    /// instead of `OmniSim`'s camera. **Must stay declared above
+    /// `guide_camera_cache`** — Rust drops struct fields top-down, so the
+    /// camera process stops before its cache directory is removed.
+    pub guide_camera: Option<ServiceHandle>,
+    pub guide_camera_cache: Option<tempfile::TempDir>,
+    pub native_guider: Option<ServiceHandle>,
+
+    /// Result of the most recent HTTP request (status + body).
+    pub last_response: Option<HttpResponse>,
+
+    /// State for the shared TLS + auth smoke steps.
+    pub tls_auth: TlsAuthState,
+
+    /// Doctor-subcommand smoke state (staged config file + run output)
+    pub doctor_smoke: bdd_infra::doctor_smoke::DoctorSmokeState,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub body: Value,
+}
+
+impl bdd_infra::doctor_smoke::DoctorSmokeWorld for NativeGuiderWorld {
+    fn doctor_smoke(&mut self) -> &mut bdd_infra::doctor_smoke::DoctorSmokeState {
+        &mut self.doctor_smoke
+    }
+
+    fn valid_config(&self) -> serde_json::Value {
+        // Not `base_test_config()`: that shape reads the running
+        // OmniSim's URL, and the doctor smoke never starts one (doctor
+        // only parses the config, it never dials the devices).
+        serde_json::json!({
+            "server": { "port": 0 },
+            "camera": { "alpaca_url": "http://127.0.0.1:32323", "device_number": 0 },
+            "mount": { "alpaca_url": "http://127.0.0.1:32323", "device_number": 0 }
+        })
+    }
+}
+
+impl TlsAuthSmokeWorld for NativeGuiderWorld {
+    /// `/health` answers 200 only once both devices are connected, so
+    /// the probe also proves the service reached its equipment.
+    const PROBE_PATH: &'static str = "/health";
+
+    fn tls_auth(&mut self) -> &mut TlsAuthState {
+        &mut self.tls_auth
+    }
+
+    fn base_test_config(&self) -> serde_json::Value {
+        self.guider_config()
+    }
+
+    async fn start_with_tls_auth(&mut self, config: serde_json::Value) {
+        let handle = bdd_infra::tls_auth::spawn_service_handle(
+            &mut self.tls_auth,
+            env!("CARGO_PKG_NAME"),
+            &config,
+        )
+        .await;
+        self.native_guider = Some(handle);
+    }
+}
+
+impl NativeGuiderWorld {
+    pub fn omnisim_url(&self) -> String {
+        self.omnisim
+            .as_ref()
+            .expect("OmniSim must be started before accessing its URL")
+            .base_url
+            .clone()
+    }
+
+    /// The guide camera's Alpaca server: sky-survey-camera when the
+    /// scenario started one, otherwise `OmniSim`'s camera simulator.
+    pub fn camera_url(&self) -> String {
+        self.guide_camera
+            .as_ref()
+            .map_or_else(|| self.omnisim_url(), |h| h.base_url.clone())
+    }
+
+    /// native-guider's config for the scenario's rig, tuned so a
+    /// calibration and a few guide frames fit comfortably inside a
+    /// scenario: short exposures and calibration pulses, a short
+    /// reconnect interval, and a few seconds of settle time.
+    pub fn guider_config(&self) -> Value {
+        serde_json::json!({
+            "camera": { "alpaca_url": self.camera_url(), "device_number": 0 },
+            "mount": { "alpaca_url": self.omnisim_url(), "device_number": 0 },
+            "exposure": "200ms",
+            "calibration": { "step": "1s", "distance_px": 12.0, "max_steps": 20 },
+            "settling": { "pixels": 1.5, "time": "2s", "timeout": "60s" },
+            "reconnect_interval": "500ms"
+        })
+    }
+
+    /// Service base URL (e.g., `http://127.0.0.1:37113`).
+    pub fn service_url(&self) -> String {
+        self.native_guider
+            .as_ref()
+            .map(|h| h.base_url.clone())
+            .expect("native-guider not started — Given step missing?")
+    }
+
+    /// Spawn native-guider with [`Self::guider_config`] and wait until
+    /// `/health` reports both devices connected.
+    pub async fn start_native_guider(&mut self) {
+        let path = write_temp_config_file("native-guider-bdd-config", &self.guider_config()).await;
+        let handle = ServiceHandle::start(env!("CARGO_PKG_NAME"), &path).await;
+        let health = format!("{}/health", handle.base_url);
+        self.native_guider = Some(handle);
+        for _ in 0..60 {
+            if let Ok(resp) = Self::http_client().get(&health).send().await {
+                if resp.status().as_u16() == 200 {
+                    return;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+        panic!("native-guider did not report its equipment connected: {health}");
+    }
+
+    pub async fn record_response(&mut self, response: reqwest::Response) {
+        let status = response.status().as_u16();
+        let text = response.text().await.expect("read response body");
+        let body = if text.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_str(&text)
+                .unwrap_or_else(|e| panic!("non-JSON response body ({e}): {text}"))
+        };
+        self.last_response = Some(HttpResponse { status, body });
+    }
+
+    pub const fn last_response(&self) -> &HttpResponse {
+        self.last_response
+            .as_ref()
+            .expect("no HTTP request made yet — When step missing?")
+    }
+
+    /// Shared client. The timeout covers a full calibration plus the
+    /// settle: `guiding/start` blocks until the star settles.
+    pub fn http_client() -> reqwest::Client {
+        static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+        CLIENT
+            .get_or_init(|| {
+                reqwest::Client::builder()
+                    .timeout(Duration::from_secs(180))
+                    .build()
+                    .expect("build reqwest client")
+            })
+            .clone()
+    }
+}
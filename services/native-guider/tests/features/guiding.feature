@@ -0,0 +1,72 @@
+@serial
+Feature: Native guiding against the simulator
+
+  native-guider serves the guider HTTP contract rp's guider client is
+  written against (the phd2-guider contract), but guides on its own:
+  it exposes the guide camera over Alpaca, finds a guide star with
+  rp's star detection, learns the mount's axes with a calibration run,
+  and corrects with PulseGuide on the Alpaca mount.
+
+  These scenarios guide OmniSim's telescope. The guide camera is
+  sky-survey-camera following that telescope over the SkyView stub's
+  synthetic star field, so every guide pulse moves the stars on the
+  next frame. Tracking is on and the mount never drifts, so after a
+  calibration the star settles at the lock position.
+
+  Background:
+    Given a sky-survey guide camera following the simulated mount over a star field
+    And native-guider is running
+
+  Scenario: The guider reports its camera and mount and no other devices
+    When the client requests the guider equipment
+    Then the response status should be 200
+    And the equipment "camera" slot should be connected
+    And the equipment "mount" slot should be connected
+    And the equipment "ao" slot should be null
+    And the equipment "rotator" slot should be null
+
+  Scenario: A Dec profile switch reads back in the phd2-guider settings shape
+    When the client sets the Dec guide mode to "North" with backlash compensation of "300ms"
+    Then the response status should be 200
+    When the client requests the guiding settings
+    Then the response field "dec_guide_mode" should be "North"
+    And the response field "multi_star" should be false
+    And the backlash compensation should be enabled with a pulse of "300ms"
+
+  Scenario: Multi-star guiding is rejected
+    When the client turns multi-star guiding on
+    Then the response status should be 400
+    And the response error should be "invalid_request"
+
+  Scenario: Dithering before guiding starts is rejected
+    When the client dithers by 3.0 pixels
+    Then the response status should be 409
+    And the response error should be "not_guiding"
+
+  Scenario: Starting guiding calibrates, locks on a star, and settles
+    When the client starts guiding with a fresh calibration
+    Then the response status should be 200
+    And the response field "state" should be "guiding"
+    And the response field "total_rms_px" should be below 1.5
+    When the client requests the guiding stats
+    Then the response field "app_state" should be "Guiding"
+    And the response field "guiding" should be true
+    And the response field "snr" should be at least 10.0
+
+  Scenario: A dither moves the lock position and guiding settles on it
+    Given native-guider is guiding with a fresh calibration
+    When the client dithers by 3.0 pixels
+    Then the response status should be 200
+    And the response field "state" should be "guiding"
+
+  Scenario: A second start reuses the calibration
+    Given native-guider is guiding with a fresh calibration
+    When the client stops guiding
+    Then the response field "state" should be "stopped"
+    When the client starts guiding
+    Then the response status should be 200
+    And the response field "state" should be "guiding"
+    When the client stops guiding
+    Then the response status should be 200
+    When the client requests the guiding stats
+    Then the response field "app_state" should be "Stopped"
@@ -32,6 +32,9 @@
 //!   `MOCK_PHD2_ROTATOR` - "connected" populates `get_current_equipment`'s
 //!     rotator slot ({"name": "Mock Rotator", "connected": true});
 //!     unset/anything else reports null (no rotator in the profile)
+//!   `MOCK_PHD2_SHUTDOWN_EXIT_CODE` - when set, a `shutdown` RPC exits
+//!     the process at once with this status and no response: a crash,
+//!     for `serve`'s process supervision scenarios
 //!
 //! Command line argument takes precedence over environment variable for port.
 //! Default port is 4400 (same as PHD2).
@@ -338,7 +341,8 @@ fn handle_request(
         "get_connected" => serde_json::json!(false),
         "set_connected" => serde_json::json!(0),
         "get_profiles" => serde_json::json!([
-            {"id": 1, "name": "Mock Profile"}
+            {"id": 1, "name": "Mock Profile"},
+            {"id": 2, "name": "Mock Narrowband"}
         ]),
         "get_profile" => serde_json::json!({"id": 1, "name": "Mock Profile"}),
         "set_profile" => serde_json::json!(0),
@@ -404,9 +408,27 @@ fn handle_request(
             emit_settle_sequence(writer.clone());
             serde_json::json!(0)
         }
-        "get_algo_param_names" => serde_json::json!(["Aggressiveness", "MinMove"]),
-        "get_algo_param" => serde_json::json!(0.5),
+        // PHD2's defaults: Hysteresis on RA, Resist Switch on Dec.
+        "get_algo_param_names" => {
+            if params["axis"] == "dec" {
+                serde_json::json!(["algorithmName", "minMove", "aggression", "fastSwitch"])
+            } else {
+                serde_json::json!(["algorithmName", "minMove", "hysteresis", "aggression"])
+            }
+        }
+        "get_algo_param" => match params["name"].as_str() {
+            Some("algorithmName") if params["axis"] == "dec" => {
+                serde_json::json!("ResistSwitch")
+            }
+            Some("algorithmName") => serde_json::json!("Hysteresis"),
+            Some("minMove") => serde_json::json!(0.15),
+            Some("hysteresis") => serde_json::json!(0.1),
+            Some("fastSwitch") => serde_json::json!(1.0),
+            _ => serde_json::json!(0.7),
+        },
         "set_algo_param" => serde_json::json!(0),
+        "get_dec_guide_mode" => serde_json::json!("Auto"),
+        "set_dec_guide_mode" => serde_json::json!(0),
         "get_ccd_temperature" => serde_json::json!(20.0),
         "get_cooler_status" => serde_json::json!({
             "temperature": 20.0,
@@ -423,6 +445,13 @@ fn handle_request(
         "save_image" => serde_json::json!("/tmp/mock_image.fits"),
         "capture_single_frame" => serde_json::json!(0),
         "shutdown" => {
+            if let Some(code) = std::env::var("MOCK_PHD2_SHUTDOWN_EXIT_CODE")
+                .ok()
+                .and_then(|c| c.parse().ok())
+            {
+                eprintln!("Shutdown requested - crashing with status {code}");
+                std::process::exit(code);
+            }
             if ignore_shutdown {
                 eprintln!("Shutdown requested but ignored (shutdown_fails mode)");
                 // Return success but don't actually shut down
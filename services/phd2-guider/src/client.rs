@@ -26,8 +26,8 @@ use crate::events::{AppState, Phd2Event};
 use crate::io::{ConnectionFactory, TcpConnectionFactory};
 use crate::rpc::RpcRequest;
 use crate::types::{
-    CalibrationData, CalibrationTarget, CoolerStatus, Equipment, GuideAxis, Profile, Rect,
-    StarImage,
+    CalibrationData, CalibrationTarget, CoolerStatus, DecGuideMode, Equipment, GuideAxis, Profile,
+    Rect, StarImage,
 };
 
 /// PHD2 client for communicating with PHD2 via JSON RPC
@@ -823,6 +823,26 @@ impl Phd2Client {
         Ok(())
     }
 
+    /// Get which Dec corrections PHD2 sends
+    pub async fn get_dec_guide_mode(&self) -> Result<DecGuideMode> {
+        debug!("Getting Dec guide mode");
+        let result = self.send_request("get_dec_guide_mode", None).await?;
+        let mode: DecGuideMode = serde_json::from_value(result)?;
+        Ok(mode)
+    }
+
+    /// Set which Dec corrections PHD2 sends
+    ///
+    /// # Arguments
+    /// * `mode` - `Off`, `Auto`, `North` or `South`
+    pub async fn set_dec_guide_mode(&self, mode: DecGuideMode) -> Result<()> {
+        debug!("Setting Dec guide mode to {}", mode);
+        let params = serde_json::json!({ "mode": mode });
+        self.send_request("set_dec_guide_mode", Some(params))
+            .await?;
+        Ok(())
+    }
+
     // ========================================================================
     // Camera Cooling Methods
     // ========================================================================
@@ -2275,6 +2295,34 @@ mod mock_tests {
         assert!(messages[0].contains("\"value\":0.3"));
     }
 
+    #[tokio::test]
+    async fn test_get_dec_guide_mode() {
+        let (client, _sent) = create_test_client_with_responses(vec![
+            Some(version_event()),
+            Some(rpc_response(1, r#""North""#)),
+        ]);
+
+        client.connect().await.unwrap();
+        let mode = client.get_dec_guide_mode().await.unwrap();
+
+        assert_eq!(mode, DecGuideMode::North);
+    }
+
+    #[tokio::test]
+    async fn test_set_dec_guide_mode() {
+        let (client, sent) = create_test_client_with_responses(vec![
+            Some(version_event()),
+            Some(rpc_response(1, "0")),
+        ]);
+
+        client.connect().await.unwrap();
+        client.set_dec_guide_mode(DecGuideMode::Off).await.unwrap();
+
+        let messages = sent.lock().unwrap();
+        assert!(messages[0].contains("set_dec_guide_mode"));
+        assert!(messages[0].contains("\"mode\":\"Off\""));
+    }
+
     // ============================================================================
     // Camera cooling tests
     // ============================================================================
@@ -18,6 +18,10 @@ pub struct Config {
     /// the `Stopped` state (`serve` mode only).
     #[serde(default = "default_stop_timeout", with = "humantime_serde")]
     pub stop_timeout: Duration,
+    /// How `serve` watches and restarts the PHD2 process it owns
+    /// (`serve` mode with `phd2.auto_start` only).
+    #[serde(default)]
+    pub supervision: SupervisionConfig,
     #[serde(default)]
     pub phd2: Phd2Config,
     #[serde(default)]
@@ -29,6 +33,7 @@ impl Default for Config {
         Self {
             server: default_server(),
             stop_timeout: default_stop_timeout(),
+            supervision: SupervisionConfig::default(),
             phd2: Phd2Config::default(),
             settling: SettleParams::default(),
         }
@@ -63,6 +68,10 @@ pub struct Phd2Config {
     pub auto_start: bool,
     #[serde(default)]
     pub auto_connect_equipment: bool,
+    /// Equipment profile (by name) selected after PHD2 starts; `None`
+    /// keeps whichever profile PHD2 opens with
+    #[serde(default)]
+    pub profile: Option<String>,
     #[serde(default)]
     pub reconnect: ReconnectConfig,
     /// Environment variables to set when spawning the PHD2 process
@@ -70,6 +79,45 @@ pub struct Phd2Config {
     pub spawn_env: std::collections::HashMap<String, String>,
 }
 
+/// PHD2 process supervision under `serve`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SupervisionConfig {
+    /// Wait before the first restart after a crash; doubles per
+    /// consecutive crash
+    #[serde(default = "default_restart_backoff", with = "humantime_serde")]
+    pub restart_backoff: Duration,
+    /// Ceiling of the doubling restart backoff
+    #[serde(default = "default_max_restart_backoff", with = "humantime_serde")]
+    pub max_restart_backoff: Duration,
+    /// How long PHD2 may go without an event or an RPC answer before it
+    /// is declared hung and restarted
+    #[serde(default = "default_hang_timeout", with = "humantime_serde")]
+    pub hang_timeout: Duration,
+}
+
+impl Default for SupervisionConfig {
+    fn default() -> Self {
+        Self {
+            restart_backoff: default_restart_backoff(),
+            max_restart_backoff: default_max_restart_backoff(),
+            hang_timeout: default_hang_timeout(),
+        }
+    }
+}
+
+const fn default_restart_backoff() -> Duration {
+    Duration::from_secs(5)
+}
+
+const fn default_max_restart_backoff() -> Duration {
+    Duration::from_mins(5)
+}
+
+const fn default_hang_timeout() -> Duration {
+    Duration::from_mins(1)
+}
+
 /// Configuration for automatic reconnection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -113,6 +161,7 @@ impl Default for Phd2Config {
             command_timeout: default_command_timeout(),
             auto_start: false,
             auto_connect_equipment: false,
+            profile: None,
             reconnect: ReconnectConfig::default(),
             spawn_env: std::collections::HashMap::new(),
         }
@@ -222,6 +271,7 @@ mod tests {
         assert_eq!(config.command_timeout, Duration::from_secs(30));
         assert!(!config.auto_start);
         assert!(!config.auto_connect_equipment);
+        assert!(config.profile.is_none());
         assert!(config.reconnect.enabled);
         assert_eq!(config.reconnect.interval, Duration::from_secs(5));
         assert!(config.reconnect.max_retries.is_none());
@@ -287,6 +337,25 @@ mod tests {
         assert_eq!(config.phd2.port, 14400);
     }
 
+    #[test]
+    fn the_supervision_fields_parse_from_json() {
+        let config: Config = serde_json::from_str(
+            r#"{
+                "supervision": { "restart_backoff": "1s", "hang_timeout": "20s" },
+                "phd2": { "auto_start": true, "profile": "Rig A" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.supervision.restart_backoff, Duration::from_secs(1));
+        assert_eq!(
+            config.supervision.max_restart_backoff,
+            Duration::from_mins(5)
+        );
+        assert_eq!(config.supervision.hang_timeout, Duration::from_secs(20));
+        assert!(config.phd2.auto_start);
+        assert_eq!(config.phd2.profile.as_deref(), Some("Rig A"));
+    }
+
     #[test]
     fn a_misspelled_config_key_fails_at_config_load() {
         let err = serde_json::from_str::<Config>(r#"{"stop_timout": "5s"}"#).unwrap_err();
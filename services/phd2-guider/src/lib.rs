@@ -65,6 +65,6 @@ pub use process::{get_default_phd2_path, Phd2ProcessManager};
 pub use rpc::{RpcErrorObject, RpcRequest, RpcResponse};
 pub use service::{BoundServer, ServerBuilder};
 pub use types::{
-    CalibrationData, CalibrationTarget, CoolerStatus, Equipment, EquipmentDevice, GuideAxis,
-    Profile, Rect, StarImage,
+    CalibrationData, CalibrationTarget, CoolerStatus, DecGuideMode, Equipment, EquipmentDevice,
+    GuideAxis, Profile, Rect, StarImage,
 };
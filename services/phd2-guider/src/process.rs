@@ -351,6 +351,23 @@ impl Phd2ProcessManager {
         let process = self.process.lock().await;
         process.is_some()
     }
+
+    /// Exit code of the managed PHD2 process, if it has exited
+    ///
+    /// An exited process is released, so a following `start_phd2` spawns
+    /// a fresh one. Returns `Ok(None)` while it runs, and when no process
+    /// is managed (PHD2 was already running when `start_phd2` was called).
+    pub async fn managed_exit_status(&self) -> Result<Option<i32>> {
+        let mut process = self.process.lock().await;
+        let Some(ref mut child) = *process else {
+            return Ok(None);
+        };
+        let status = child.try_wait().await?;
+        if status.is_some() {
+            *process = None;
+        }
+        Ok(status)
+    }
 }
 
 #[cfg(test)]
@@ -785,6 +802,58 @@ mod tests {
         assert_eq!(spawner.get_spawn_count(), 1);
     }
 
+    // ============================================================================
+    // managed_exit_status tests
+    // ============================================================================
+
+    #[tokio::test]
+    async fn test_managed_exit_status_without_managed_process() {
+        let spawner = Arc::new(MockProcessSpawner::new());
+        let factory = Arc::new(MockConnectionFactory::new());
+        let manager = Phd2ProcessManager::with_spawner(create_test_config(), spawner, factory);
+
+        assert_eq!(manager.managed_exit_status().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_managed_exit_status_while_running() {
+        let spawner = Arc::new(MockProcessSpawner::new());
+        spawner.add_spawn_success();
+        let factory = Arc::new(MockConnectionFactory::new());
+        factory.set_can_connect(false);
+        factory.set_can_connect(true);
+        let manager = Phd2ProcessManager::with_spawner(create_test_config(), spawner, factory);
+
+        manager.start_phd2().await.unwrap();
+        assert_eq!(manager.managed_exit_status().await.unwrap(), None);
+        assert!(manager.has_managed_process().await);
+    }
+
+    #[tokio::test]
+    async fn test_managed_exit_status_releases_an_exited_process() {
+        let spawner = Arc::new(MockProcessSpawner::new());
+        // Ready before the exit is noticed: the port answered, then the
+        // process died (a crash after startup).
+        spawner.add_process_exits_immediately(139);
+        spawner.add_spawn_success();
+        let factory = Arc::new(MockConnectionFactory::new());
+        factory.set_can_connect(false);
+        factory.set_can_connect(true);
+        factory.set_can_connect(false);
+        factory.set_can_connect(true);
+        let manager =
+            Phd2ProcessManager::with_spawner(create_test_config(), spawner.clone(), factory);
+
+        manager.start_phd2().await.unwrap();
+        assert_eq!(manager.managed_exit_status().await.unwrap(), Some(139));
+        assert!(!manager.has_managed_process().await);
+
+        // Released, so the restart spawns a fresh process.
+        manager.start_phd2().await.unwrap();
+        assert_eq!(spawner.get_spawn_count(), 2);
+        assert!(manager.has_managed_process().await);
+    }
+
     // ============================================================================
     // stop_phd2 tests
     // ============================================================================
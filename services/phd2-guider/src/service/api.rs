@@ -17,7 +17,9 @@ use axum::{
 use serde::{Deserialize, Serialize};
 
 use super::error::ServiceError;
-use super::guider::{GuiderOps, StatsSnapshot};
+use super::guider::{AxisUpdate, GuiderOps, GuidingSettings, SettingsUpdate, StatsSnapshot};
+use super::supervisor::SupervisionStatus;
+use crate::types::DecGuideMode;
 
 pub fn build_router(ops: Arc<GuiderOps>) -> Router {
     Router::new()
@@ -28,6 +30,10 @@ pub fn build_router(ops: Arc<GuiderOps>) -> Router {
         .route("/api/v1/dither", post(dither))
         .route("/api/v1/guiding/stats", get(stats))
         .route("/api/v1/guiding/metrics", get(metrics))
+        .route(
+            "/api/v1/guiding/settings",
+            get(settings).post(update_settings),
+        )
         .route("/api/v1/equipment", get(equipment))
         .route("/api/v1/calibration/clear", post(clear_calibration))
         .route("/api/v1/star/reselect", post(reselect_star))
@@ -74,6 +80,36 @@ struct PauseBody {
     full: bool,
 }
 
+/// One axis of a settings update; every field optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AxisSettingsBody {
+    #[serde(default)]
+    min_move_px: Option<f64>,
+    #[serde(default)]
+    aggressiveness: Option<f64>,
+    #[serde(default)]
+    params: std::collections::BTreeMap<String, f64>,
+}
+
+/// Partial settings update. `multi_star` and `backlash_compensation`
+/// are part of the contract but not reachable through PHD2's event
+/// server, so this service rejects them rather than ignore them.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SettingsBody {
+    #[serde(default)]
+    dec_guide_mode: Option<DecGuideMode>,
+    #[serde(default)]
+    ra: AxisSettingsBody,
+    #[serde(default)]
+    dec: AxisSettingsBody,
+    #[serde(default)]
+    multi_star: Option<bool>,
+    #[serde(default)]
+    backlash_compensation: Option<serde_json::Value>,
+}
+
 /// Response shape shared by `guiding/start` and `dither`.
 #[derive(Debug, Serialize)]
 struct SettledResponse {
@@ -216,6 +252,76 @@ async fn stats(State(ops): State<Arc<GuiderOps>>) -> Result<Json<StatsResponse>,
     }))
 }
 
+/// Check one axis of a settings update and convert it, rejecting a
+/// negative min-move or a non-positive aggressiveness before anything
+/// reaches PHD2 (same bar as `amount_px`).
+fn axis_update(axis: &str, body: AxisSettingsBody) -> Result<AxisUpdate, ServiceError> {
+    if let Some(v) = body.min_move_px {
+        if !v.is_finite() || v < 0.0 {
+            return Err(ServiceError::InvalidRequest(format!(
+                "{axis}.min_move_px must be a non-negative number of pixels, got {v}"
+            )));
+        }
+    }
+    if let Some(v) = body.aggressiveness {
+        if !v.is_finite() || v <= 0.0 {
+            return Err(ServiceError::InvalidRequest(format!(
+                "{axis}.aggressiveness must be positive, got {v}"
+            )));
+        }
+    }
+    if let Some((name, v)) = body.params.iter().find(|(_, v)| !v.is_finite()) {
+        return Err(ServiceError::InvalidRequest(format!(
+            "{axis}.params.{name} must be a finite number, got {v}"
+        )));
+    }
+    Ok(AxisUpdate {
+        min_move_px: body.min_move_px,
+        aggressiveness: body.aggressiveness,
+        params: body.params,
+    })
+}
+
+/// Serialized by hand so the two settings PHD2 does not expose stay
+/// on the wire as `null`, as the unconfigured equipment slots do.
+fn settings_json(settings: GuidingSettings) -> serde_json::Value {
+    serde_json::json!({
+        "dec_guide_mode": settings.dec_guide_mode,
+        "ra": settings.ra,
+        "dec": settings.dec,
+        "multi_star": null,
+        "backlash_compensation": null,
+    })
+}
+
+async fn settings(
+    State(ops): State<Arc<GuiderOps>>,
+) -> Result<Json<serde_json::Value>, ServiceError> {
+    let settings = ops.settings().await?;
+    Ok(Json(settings_json(settings)))
+}
+
+async fn update_settings(
+    State(ops): State<Arc<GuiderOps>>,
+    bytes: axum::body::Bytes,
+) -> Result<Json<serde_json::Value>, ServiceError> {
+    let body: SettingsBody = parse_optional_body(&bytes)?;
+    if body.multi_star.is_some() || body.backlash_compensation.is_some() {
+        return Err(ServiceError::InvalidRequest(
+            "multi_star and backlash_compensation are not reachable through PHD2's event \
+             server; set them in the PHD2 profile (Brain dialog)"
+                .to_string(),
+        ));
+    }
+    let update = SettingsUpdate {
+        dec_guide_mode: body.dec_guide_mode,
+        ra: axis_update("ra", body.ra)?,
+        dec: axis_update("dec", body.dec)?,
+    };
+    let settings = ops.update_settings(update).await?;
+    Ok(Json(settings_json(settings)))
+}
+
 /// Which calibration to clear; the serde names are the wire contract
 /// (`"mount"` default, `"ao"`, `"both"`).
 #[derive(Debug, Default, Deserialize)]
@@ -290,26 +396,56 @@ async fn reselect_star(
     Ok(Json(StateResponse { state: "selected" }))
 }
 
+/// `/health`'s `phd2_process` object: the restart count and the last
+/// crash, when `serve` owns the PHD2 process.
+fn supervision_json(status: &SupervisionStatus) -> serde_json::Value {
+    serde_json::json!({
+        "restart_count": status.restart_count,
+        "last_crash": status.last_crash.as_ref().map(|crash| serde_json::json!({
+            "reason": crash.reason,
+            "at": humantime::format_rfc3339_seconds(crash.at).to_string(),
+        })),
+    })
+}
+
 /// The 503 means alive-but-degraded (PHD2 off is the normal daytime
 /// state); `message` is the opaque explanation sentinel displays on its
 /// dashboard without interpreting (docs/services/sentinel.md §Service
 /// Health Supervision).
 async fn health(State(ops): State<Arc<GuiderOps>>) -> impl IntoResponse {
-    if ops.is_connected().await {
-        (StatusCode::OK, Json(serde_json::json!({ "status": "ok" })))
+    let supervision = ops.supervision();
+    let (status, mut body) = if ops.is_connected().await {
+        (StatusCode::OK, serde_json::json!({ "status": "ok" }))
     } else {
+        let message = match &supervision {
+            Some(SupervisionStatus {
+                restarting: true,
+                last_crash: Some(crash),
+                ..
+            }) => format!(
+                "PHD2 at {} is down and this service is restarting it: {}",
+                ops.phd2_addr(),
+                crash.reason
+            ),
+            Some(_) => format!(
+                "no connection to PHD2 at {}; this service starts PHD2 and restarts it when it goes down",
+                ops.phd2_addr()
+            ),
+            None => format!(
+                "no connection to PHD2 at {}; reconnecting automatically — \
+                 start PHD2 with its event server enabled (Tools → Enable Server)",
+                ops.phd2_addr()
+            ),
+        };
         (
             StatusCode::SERVICE_UNAVAILABLE,
-            Json(serde_json::json!({
-                "status": "unavailable",
-                "message": format!(
-                    "no connection to PHD2 at {}; reconnecting automatically — \
-                     start PHD2 with its event server enabled (Tools → Enable Server)",
-                    ops.phd2_addr()
-                ),
-            })),
+            serde_json::json!({ "status": "unavailable", "message": message }),
         )
+    };
+    if let Some(supervision) = &supervision {
+        body["phd2_process"] = supervision_json(supervision);
     }
+    (status, Json(body))
 }
 
 #[cfg(test)]
@@ -404,6 +540,109 @@ mod tests {
         assert!(err.to_string().contains("pixels"));
     }
 
+    #[test]
+    fn a_settings_body_parses_every_field() {
+        let body: SettingsBody = serde_json::from_str(
+            r#"{"dec_guide_mode": "North",
+                "ra": {"min_move_px": 0.2, "params": {"hysteresis": 0.1}},
+                "dec": {"aggressiveness": 0.9}}"#,
+        )
+        .unwrap();
+        assert_eq!(body.dec_guide_mode, Some(DecGuideMode::North));
+        assert_eq!(body.ra.min_move_px, Some(0.2));
+        assert_eq!(body.ra.params.get("hysteresis"), Some(&0.1));
+        assert_eq!(body.dec.aggressiveness, Some(0.9));
+        assert!(body.multi_star.is_none());
+    }
+
+    #[test]
+    fn out_of_range_axis_settings_are_rejected() {
+        for body in [
+            AxisSettingsBody {
+                min_move_px: Some(-0.1),
+                ..AxisSettingsBody::default()
+            },
+            AxisSettingsBody {
+                aggressiveness: Some(0.0),
+                ..AxisSettingsBody::default()
+            },
+            AxisSettingsBody {
+                params: [("minMove".to_string(), f64::NAN)].into(),
+                ..AxisSettingsBody::default()
+            },
+        ] {
+            let err = axis_update("dec", body).unwrap_err();
+            assert_eq!(err.code(), super::super::error::ErrorCode::InvalidRequest);
+        }
+    }
+
+    #[test]
+    fn the_settings_wire_shape_keeps_the_unexposed_settings_null() {
+        let axis = |min_move: f64| super::super::guider::AxisSettings {
+            min_move_px: Some(min_move),
+            aggressiveness: None,
+            params: [("minMove".to_string(), min_move)].into(),
+        };
+        let json = settings_json(GuidingSettings {
+            dec_guide_mode: DecGuideMode::Auto,
+            ra: axis(0.15),
+            dec: axis(0.2),
+        });
+        assert_eq!(json["dec_guide_mode"], "Auto");
+        assert_eq!(json["ra"]["min_move_px"], 0.15);
+        assert_eq!(json["dec"]["params"]["minMove"], 0.2);
+        assert_eq!(json["dec"]["aggressiveness"], serde_json::Value::Null);
+        assert_eq!(json["multi_star"], serde_json::Value::Null);
+        assert_eq!(json["backlash_compensation"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn the_health_process_object_carries_the_last_crash() {
+        let json = supervision_json(&SupervisionStatus::default());
+        assert_eq!(json["restart_count"], 0);
+        assert_eq!(json["last_crash"], serde_json::Value::Null);
+
+        let json = supervision_json(&SupervisionStatus {
+            restart_count: 2,
+            last_crash: Some(super::super::supervisor::Crash {
+                reason: "PHD2 exited with status 139".to_string(),
+                at: std::time::UNIX_EPOCH + Duration::from_secs(1_800_000_000),
+            }),
+            restarting: false,
+        });
+        assert_eq!(json["restart_count"], 2);
+        assert_eq!(json["last_crash"]["reason"], "PHD2 exited with status 139");
+        assert_eq!(json["last_crash"]["at"], "2027-01-15T08:00:00Z");
+    }
+
+    #[tokio::test]
+    async fn health_reports_the_process_only_under_supervision() {
+        let ops = Arc::new(test_ops());
+        let response = health(State(Arc::clone(&ops))).await.into_response();
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body = axum::body::to_bytes(response.into_body(), 4096)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(json.get("phd2_process").is_none(), "in {json}");
+
+        ops.update_supervision(|status| {
+            status.restarting = true;
+            status.last_crash = Some(super::super::supervisor::Crash {
+                reason: "PHD2 exited with status 139".to_string(),
+                at: std::time::SystemTime::now(),
+            });
+        });
+        let response = health(State(ops)).await.into_response();
+        let body = axum::body::to_bytes(response.into_body(), 4096)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["phd2_process"]["restart_count"], 0);
+        let message = json["message"].as_str().unwrap();
+        assert!(message.contains("PHD2 exited with status 139"), "{message}");
+    }
+
     #[test]
     fn the_settled_response_serializes_null_rms_when_unsampled() {
         let response = SettledResponse::from_snapshot(StatsSnapshot {
@@ -1,12 +1,13 @@
 //! Guiding operations behind the HTTP API: settle-blocking guide and
-//! dither, the confirmed stop, and the rolling RMS window.
+//! dither, the confirmed stop, the rolling RMS window, and the guide
+//! algorithm settings.
 //!
 //! Behavior contract: `docs/services/phd2-guider.md` § "HTTP Service
 //! Mode". The mutating operations serialize behind a single-flight
 //! mutex (overlapping requests queue, not error); the read-only
 //! snapshot paths bypass it.
 
-use std::collections::VecDeque;
+use std::collections::{BTreeMap, VecDeque};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -16,8 +17,10 @@ use tracing::{debug, info, warn};
 use crate::client::Phd2Client;
 use crate::config::SettleParams;
 use crate::events::{AppState, Phd2Event};
+use crate::types::{DecGuideMode, GuideAxis};
 
 use super::error::ServiceError;
+use super::supervisor::SupervisionStatus;
 
 /// Rolling RMS window size, in guide steps.
 const RMS_WINDOW: usize = 50;
@@ -128,6 +131,112 @@ pub struct GuidingMetrics {
     pub frames: Vec<FrameMetrics>,
 }
 
+/// The string-valued parameter PHD2 lists alongside the numeric ones;
+/// it names the algorithm and is not tunable through `set_algo_param`.
+const ALGORITHM_NAME_PARAM: &str = "algorithmName";
+
+/// PHD2's names for the min-move parameter, matched case-insensitively.
+const MIN_MOVE_PARAMS: &[&str] = &["minMove"];
+
+/// PHD2's names for the aggressiveness parameter: Hysteresis and Resist
+/// Switch call it `aggression`, Lowpass2 `aggressiveness`.
+const AGGRESSIVENESS_PARAMS: &[&str] = &["aggression", "aggressiveness"];
+
+/// One axis' algorithm parameters under PHD2's names, with the two
+/// most algorithms share resolved out of them (`None` when the axis'
+/// algorithm has no such parameter).
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AxisSettings {
+    pub min_move_px: Option<f64>,
+    pub aggressiveness: Option<f64>,
+    pub params: BTreeMap<String, f64>,
+}
+
+impl AxisSettings {
+    fn from_params(params: BTreeMap<String, f64>) -> Self {
+        let lookup = |candidates: &[&str]| {
+            find_param(params.keys(), candidates).and_then(|name| params.get(name).copied())
+        };
+        Self {
+            min_move_px: lookup(MIN_MOVE_PARAMS),
+            aggressiveness: lookup(AGGRESSIVENESS_PARAMS),
+            params,
+        }
+    }
+}
+
+/// Settings endpoint payload: what PHD2 reports over its event server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GuidingSettings {
+    pub dec_guide_mode: DecGuideMode,
+    pub ra: AxisSettings,
+    pub dec: AxisSettings,
+}
+
+/// A partial update of one axis. `params` are written verbatim under
+/// PHD2's names; `min_move_px` / `aggressiveness` are resolved to the
+/// axis algorithm's own name for them.
+#[derive(Debug, Clone, Default)]
+pub struct AxisUpdate {
+    pub min_move_px: Option<f64>,
+    pub aggressiveness: Option<f64>,
+    pub params: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SettingsUpdate {
+    pub dec_guide_mode: Option<DecGuideMode>,
+    pub ra: AxisUpdate,
+    pub dec: AxisUpdate,
+}
+
+fn find_param<'a>(
+    names: impl IntoIterator<Item = &'a String>,
+    candidates: &[&str],
+) -> Option<&'a str> {
+    names
+        .into_iter()
+        .find(|name| candidates.iter().any(|c| name.eq_ignore_ascii_case(c)))
+        .map(String::as_str)
+}
+
+/// Resolve an axis update against the parameter names the axis'
+/// algorithm has, into the `set_algo_param` writes it needs. Fails
+/// without writing anything when a name is unknown to the algorithm.
+fn plan_axis_writes(
+    axis: GuideAxis,
+    names: &[String],
+    update: AxisUpdate,
+) -> Result<Vec<(String, f64)>, ServiceError> {
+    let mut writes = Vec::new();
+    for (name, value) in update.params {
+        if name == ALGORITHM_NAME_PARAM || !names.contains(&name) {
+            return Err(ServiceError::InvalidRequest(format!(
+                "the {axis} guide algorithm has no parameter '{name}' (it has: {})",
+                names.join(", ")
+            )));
+        }
+        writes.push((name, value));
+    }
+    for (value, candidates, label) in [
+        (update.min_move_px, MIN_MOVE_PARAMS, "min-move"),
+        (
+            update.aggressiveness,
+            AGGRESSIVENESS_PARAMS,
+            "aggressiveness",
+        ),
+    ] {
+        let Some(value) = value else { continue };
+        let Some(name) = find_param(names, candidates) else {
+            return Err(ServiceError::InvalidRequest(format!(
+                "the {axis} guide algorithm has no {label} parameter"
+            )));
+        };
+        writes.push((name.to_string(), value));
+    }
+    Ok(writes)
+}
+
 pub struct GuiderOps {
     client: Arc<Phd2Client>,
     /// Single-flight lock for mutating operations.
@@ -138,6 +247,9 @@ pub struct GuiderOps {
     metrics: std::sync::Mutex<std::collections::VecDeque<FrameMetrics>>,
     default_settle: SettleParams,
     stop_timeout: Duration,
+    /// PHD2 process supervision state; `None` unless `serve` owns the
+    /// PHD2 process (`phd2.auto_start`).
+    supervision: std::sync::Mutex<Option<SupervisionStatus>>,
 }
 
 impl GuiderOps {
@@ -155,9 +267,27 @@ impl GuiderOps {
             )),
             default_settle,
             stop_timeout,
+            supervision: std::sync::Mutex::new(None),
         }
     }
 
+    /// The PHD2 process supervision state, when `serve` owns PHD2.
+    pub fn supervision(&self) -> Option<SupervisionStatus> {
+        self.supervision
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Update the supervision state, starting it on first use.
+    pub(crate) fn update_supervision(&self, update: impl FnOnce(&mut SupervisionStatus)) {
+        let mut supervision = self
+            .supervision
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        update(supervision.get_or_insert_with(SupervisionStatus::default));
+    }
+
     fn push_metrics(&self, entry: FrameMetrics) {
         let mut ring = self
             .metrics
@@ -444,6 +574,81 @@ impl GuiderOps {
             .map_err(ServiceError::from)
     }
 
+    /// PHD2's Dec guide mode and both axes' algorithm parameters —
+    /// read-only, no mutating mutex (mirrors `stats`).
+    pub async fn settings(&self) -> Result<GuidingSettings, ServiceError> {
+        let dec_guide_mode = self
+            .client
+            .get_dec_guide_mode()
+            .await
+            .map_err(ServiceError::from)?;
+        Ok(GuidingSettings {
+            dec_guide_mode,
+            ra: self.axis_settings(GuideAxis::Ra).await?,
+            dec: self.axis_settings(GuideAxis::Dec).await?,
+        })
+    }
+
+    /// Apply a partial settings update and return the settings PHD2
+    /// reports afterwards. Every name is checked against the axis'
+    /// algorithm before the first write, so a bad request changes
+    /// nothing.
+    pub async fn update_settings(
+        &self,
+        update: SettingsUpdate,
+    ) -> Result<GuidingSettings, ServiceError> {
+        let _op = self.op_lock.lock().await;
+        let mut writes = Vec::new();
+        for (axis, axis_update) in [(GuideAxis::Ra, update.ra), (GuideAxis::Dec, update.dec)] {
+            let names = self
+                .client
+                .get_algo_param_names(axis)
+                .await
+                .map_err(ServiceError::from)?;
+            writes.extend(
+                plan_axis_writes(axis, &names, axis_update)?
+                    .into_iter()
+                    .map(|(name, value)| (axis, name, value)),
+            );
+        }
+        if let Some(mode) = update.dec_guide_mode {
+            debug!(%mode, "setting Dec guide mode");
+            self.client
+                .set_dec_guide_mode(mode)
+                .await
+                .map_err(ServiceError::from)?;
+        }
+        for (axis, name, value) in writes {
+            debug!(%axis, name, value, "setting guide algorithm parameter");
+            self.client
+                .set_algo_param(axis, &name, value)
+                .await
+                .map_err(ServiceError::from)?;
+        }
+        self.settings().await
+    }
+
+    async fn axis_settings(&self, axis: GuideAxis) -> Result<AxisSettings, ServiceError> {
+        let names = self
+            .client
+            .get_algo_param_names(axis)
+            .await
+            .map_err(ServiceError::from)?;
+        let mut params = BTreeMap::new();
+        for name in names {
+            if name == ALGORITHM_NAME_PARAM {
+                continue;
+            }
+            let value = self
+                .client
+                .get_algo_param(axis, &name)
+                .await
+                .map_err(ServiceError::from)?;
+            params.insert(name, value);
+        }
+        Ok(AxisSettings::from_params(params))
+    }
+
     fn stats_snapshot(&self) -> StatsSnapshot {
         self.stats
             .lock()
@@ -548,6 +753,73 @@ mod tests {
         approx(snap.rms_ra_px.unwrap(), (49.0f64 / 50.0).sqrt());
     }
 
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn shared_parameters_resolve_to_each_algorithms_own_name() {
+        let params: BTreeMap<String, f64> = [
+            ("minMove".to_string(), 0.2),
+            ("aggressiveness".to_string(), 0.6),
+        ]
+        .into();
+        let settings = AxisSettings::from_params(params);
+        assert_eq!(settings.min_move_px, Some(0.2));
+        assert_eq!(settings.aggressiveness, Some(0.6));
+
+        let settings = AxisSettings::from_params([("fastSwitch".to_string(), 1.0)].into());
+        assert_eq!(settings.min_move_px, None);
+        assert_eq!(settings.aggressiveness, None);
+    }
+
+    #[test]
+    fn an_axis_update_plans_writes_under_phd2_names() {
+        let update = AxisUpdate {
+            min_move_px: Some(0.3),
+            aggressiveness: Some(0.8),
+            params: [("fastSwitch".to_string(), 0.0)].into(),
+        };
+        let writes = plan_axis_writes(
+            GuideAxis::Dec,
+            &names(&["minMove", "aggression", "fastSwitch"]),
+            update,
+        )
+        .unwrap();
+        assert_eq!(
+            writes,
+            vec![
+                ("fastSwitch".to_string(), 0.0),
+                ("minMove".to_string(), 0.3),
+                ("aggression".to_string(), 0.8),
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unknown_parameter_fails_the_plan() {
+        let update = AxisUpdate {
+            params: [("hysteresis".to_string(), 0.1)].into(),
+            ..AxisUpdate::default()
+        };
+        let err = plan_axis_writes(GuideAxis::Dec, &names(&["minMove", "aggression"]), update)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("no parameter 'hysteresis'"),
+            "{err}"
+        );
+
+        let update = AxisUpdate {
+            aggressiveness: Some(0.5),
+            ..AxisUpdate::default()
+        };
+        let err = plan_axis_writes(GuideAxis::Ra, &names(&["algorithmName"]), update).unwrap_err();
+        assert!(
+            err.to_string().contains("no aggressiveness parameter"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn settle_overrides_merge_field_by_field_onto_the_defaults() {
         let client = Arc::new(Phd2Client::new(crate::config::Phd2Config::default()));
@@ -6,9 +6,11 @@
 pub mod api;
 pub mod error;
 pub mod guider;
+pub mod supervisor;
 
 pub use error::{ErrorCode, ErrorResponse, ServiceError};
 pub use guider::GuiderOps;
+pub use supervisor::{Phd2Supervisor, SupervisionStatus};
 
 use std::future::Future;
 use std::net::SocketAddr;
@@ -18,6 +20,7 @@ use tokio::net::TcpListener;
 
 use crate::client::Phd2Client;
 use crate::config::Config;
+use crate::process::Phd2ProcessManager;
 
 /// Two-phase server builder. `build()` binds the TCP listener (so the
 /// bound port is known up-front), then `start()` serves. Mirrors
@@ -63,7 +66,7 @@ impl ServerBuilder {
             .unwrap_or_else(|| Arc::new(Phd2Client::new(config.phd2.clone())));
 
         let ops = Arc::new(GuiderOps::new(
-            client,
+            Arc::clone(&client),
             config.settling.clone(),
             config.stop_timeout,
         ));
@@ -75,10 +78,23 @@ impl ServerBuilder {
         let local_addr = listener.local_addr()?;
 
         ops.spawn_event_pump();
-        // A failed initial connect is not fatal: PHD2 may start later.
-        // The retry task establishes the first connection; the
-        // client's auto-reconnect owns recovery after that.
-        ops.spawn_connect_retry(config.phd2.reconnect.interval);
+        if config.phd2.auto_start {
+            // serve owns the PHD2 process: the supervisor starts it,
+            // connects, and restarts it when it exits or hangs.
+            Phd2Supervisor::new(
+                Arc::clone(&ops),
+                client,
+                Phd2ProcessManager::new(config.phd2.clone()),
+                config.phd2.clone(),
+                config.supervision.clone(),
+            )
+            .spawn();
+        } else {
+            // A failed initial connect is not fatal: PHD2 may start later.
+            // The retry task establishes the first connection; the
+            // client's auto-reconnect owns recovery after that.
+            ops.spawn_connect_retry(config.phd2.reconnect.interval);
+        }
 
         let router = api::build_router(ops);
 
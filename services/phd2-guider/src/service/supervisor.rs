@@ -0,0 +1,296 @@
+//! PHD2 process supervision under `serve` (`phd2.auto_start`): start
+//! PHD2 with the configured profile and equipment, watch it through the
+//! event socket, and restart it with backoff when it exits or hangs.
+//!
+//! Behavior contract: `docs/services/phd2-guider.md` § "PHD2 process
+//! supervision". The supervisor owns the client's connection while it
+//! runs — auto-reconnect is off, so a restart is never raced by a
+//! reconnect onto the dying process.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+use tokio::sync::broadcast;
+use tracing::{debug, info, warn};
+
+use crate::client::Phd2Client;
+use crate::config::{Phd2Config, SupervisionConfig};
+use crate::events::{AppState, Phd2Event};
+use crate::process::Phd2ProcessManager;
+
+use super::guider::GuiderOps;
+
+/// How often the watch checks the process and pings PHD2.
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Why PHD2 last went down, as reported on `/health`.
+#[derive(Debug, Clone)]
+pub struct Crash {
+    pub reason: String,
+    pub at: SystemTime,
+}
+
+/// Supervision state reported on `/health`.
+#[derive(Debug, Clone, Default)]
+pub struct SupervisionStatus {
+    /// PHD2 restarts after a crash, hang, or failed start since `serve`
+    /// started.
+    pub restart_count: u32,
+    pub last_crash: Option<Crash>,
+    /// A crash was recorded and PHD2 has not been started again yet.
+    pub restarting: bool,
+}
+
+/// The next restart backoff: doubled, capped at `max`.
+fn next_backoff(current: Duration, max: Duration) -> Duration {
+    current.saturating_mul(2).min(max)
+}
+
+/// Whether the guide loop was running — the state a restart restores.
+const fn was_guiding(state: AppState) -> bool {
+    matches!(
+        state,
+        AppState::Guiding | AppState::Calibrating | AppState::LostLock
+    )
+}
+
+/// Whether the event came over PHD2's event socket, as opposed to the
+/// connection-state notices the client raises itself.
+const fn from_phd2(event: &Phd2Event) -> bool {
+    !matches!(
+        event,
+        Phd2Event::ConnectionLost { .. }
+            | Phd2Event::Reconnecting { .. }
+            | Phd2Event::Reconnected
+            | Phd2Event::ReconnectFailed { .. }
+    )
+}
+
+pub struct Phd2Supervisor {
+    ops: Arc<GuiderOps>,
+    client: Arc<Phd2Client>,
+    manager: Phd2ProcessManager,
+    phd2: Phd2Config,
+    config: SupervisionConfig,
+}
+
+impl Phd2Supervisor {
+    pub const fn new(
+        ops: Arc<GuiderOps>,
+        client: Arc<Phd2Client>,
+        manager: Phd2ProcessManager,
+        phd2: Phd2Config,
+        config: SupervisionConfig,
+    ) -> Self {
+        Self {
+            ops,
+            client,
+            manager,
+            phd2,
+            config,
+        }
+    }
+
+    /// Start supervising. `/health` reports the supervision state from
+    /// the moment this returns.
+    pub fn spawn(self) {
+        self.client.set_auto_reconnect_enabled(false);
+        self.ops.update_supervision(|_| {});
+        tokio::spawn(async move { self.run().await });
+    }
+
+    async fn run(&self) {
+        let mut backoff = self.config.restart_backoff;
+        let mut restore_guiding = false;
+        loop {
+            if let Err(reason) = self.launch(restore_guiding).await {
+                warn!("PHD2 did not start: {reason}; retrying in {backoff:?}");
+                self.record_crash(format!("start failed: {reason}"));
+                // A process that spawned but never answered is killed,
+                // so the retry spawns afresh.
+                let _ = self.manager.stop_phd2(None).await;
+                tokio::time::sleep(backoff).await;
+                backoff = next_backoff(backoff, self.config.max_restart_backoff);
+                continue;
+            }
+            self.ops.update_supervision(|status| {
+                if status.restarting {
+                    status.restarting = false;
+                    status.restart_count += 1;
+                }
+            });
+
+            let up_since = Instant::now();
+            let (reason, guiding) = self.watch().await;
+            warn!("PHD2 went down: {reason}; restarting in {backoff:?}");
+            self.record_crash(reason);
+            // Kills a hung process; an exited one is already released.
+            let _ = self.manager.stop_phd2(None).await;
+            let _ = self.client.disconnect().await;
+
+            // A PHD2 that stayed up longer than the backoff ceiling was
+            // healthy; its crash starts the backoff over.
+            if up_since.elapsed() >= self.config.max_restart_backoff {
+                backoff = self.config.restart_backoff;
+            }
+            tokio::time::sleep(backoff).await;
+            backoff = next_backoff(backoff, self.config.max_restart_backoff);
+            restore_guiding = guiding;
+        }
+    }
+
+    fn record_crash(&self, reason: String) {
+        self.ops.update_supervision(|status| {
+            status.restarting = true;
+            status.last_crash = Some(Crash {
+                reason,
+                at: SystemTime::now(),
+            });
+        });
+    }
+
+    /// Start PHD2 (or adopt one already running), connect, select the
+    /// profile and connect equipment. With `restore_guiding`, the guide
+    /// loop the crash interrupted is restarted in the background.
+    async fn launch(&self, restore_guiding: bool) -> Result<(), String> {
+        self.manager.start_phd2().await.map_err(|e| e.to_string())?;
+        if !self.client.is_connected().await {
+            self.client.connect().await.map_err(|e| e.to_string())?;
+        }
+        info!("PHD2 is up at {}", self.client.phd2_addr());
+
+        // Equipment failures leave PHD2 running: a restart cannot plug
+        // a camera back in, and rp's guiding requests report the cause.
+        if let Some(name) = &self.phd2.profile {
+            if let Err(e) = self.select_profile(name).await {
+                warn!("could not select PHD2 profile '{name}': {e}");
+            }
+        }
+        if self.phd2.auto_connect_equipment {
+            if let Err(e) = self.client.connect_equipment().await {
+                warn!("could not connect PHD2 equipment: {e}");
+            }
+        }
+
+        if restore_guiding {
+            // Without recalibrate, PHD2 guides on the calibration it
+            // restored with the profile, and calibrates first when it
+            // has none.
+            let ops = Arc::clone(&self.ops);
+            tokio::spawn(async move {
+                info!("restarting the guide loop the PHD2 crash interrupted");
+                let settle = ops.resolve_settle(None, None, None);
+                if let Err(e) = ops.start_guiding(settle, false).await {
+                    warn!("could not restart guiding after the PHD2 restart: {e}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    async fn select_profile(&self, name: &str) -> crate::Result<()> {
+        if self.client.get_current_profile().await?.name == name {
+            return Ok(());
+        }
+        let profiles = self.client.get_profiles().await?;
+        let profile = profiles.iter().find(|p| p.name == name).ok_or_else(|| {
+            crate::error::Phd2Error::InvalidState(format!("PHD2 has no profile named '{name}'"))
+        })?;
+        self.client.set_profile(profile.id).await
+    }
+
+    /// Watch a running PHD2 until it exits or stops answering; returns
+    /// the reason and whether it was guiding when last heard from.
+    async fn watch(&self) -> (String, bool) {
+        let mut events = self.client.subscribe();
+        let mut ticker = tokio::time::interval(WATCH_INTERVAL);
+        let mut last_heard = Instant::now();
+        let mut guiding = false;
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) if from_phd2(&event) => {
+                        last_heard = Instant::now();
+                        // Between pings, the guide loop's own events
+                        // keep the restore state current.
+                        match event {
+                            Phd2Event::StartGuiding | Phd2Event::GuideStep(_) => guiding = true,
+                            Phd2Event::GuidingStopped
+                            | Phd2Event::LoopingExposuresStopped
+                            | Phd2Event::Paused => guiding = false,
+                            _ => {}
+                        }
+                    }
+                    Ok(Phd2Event::ConnectionLost { reason }) => {
+                        debug!("PHD2 event socket closed: {reason}");
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return ("the PHD2 client shut down".to_string(), guiding);
+                    }
+                },
+                _ = ticker.tick() => {
+                    match self.manager.managed_exit_status().await {
+                        Ok(Some(code)) => {
+                            return (format!("PHD2 exited with status {code}"), guiding);
+                        }
+                        Ok(None) => {}
+                        Err(e) => debug!("could not check the PHD2 process: {e}"),
+                    }
+                    let ping = tokio::time::timeout(
+                        self.config.hang_timeout,
+                        self.client.get_app_state(),
+                    );
+                    if let Ok(Ok(state)) = ping.await {
+                        last_heard = Instant::now();
+                        guiding = was_guiding(state);
+                    }
+                    if last_heard.elapsed() >= self.config.hang_timeout {
+                        let reason = format!(
+                            "PHD2 did not answer on its event server for {}",
+                            humantime::format_duration(self.config.hang_timeout)
+                        );
+                        return (reason, guiding);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_backoff_doubles_up_to_the_ceiling() {
+        let max = Duration::from_secs(60);
+        let mut backoff = Duration::from_secs(5);
+        let mut seen = Vec::new();
+        for _ in 0..6 {
+            seen.push(backoff.as_secs());
+            backoff = next_backoff(backoff, max);
+        }
+        assert_eq!(seen, [5, 10, 20, 40, 60, 60]);
+    }
+
+    #[test]
+    fn only_an_active_guide_loop_is_restored() {
+        assert!(was_guiding(AppState::Guiding));
+        assert!(was_guiding(AppState::Calibrating));
+        assert!(was_guiding(AppState::LostLock));
+        assert!(!was_guiding(AppState::Looping));
+        assert!(!was_guiding(AppState::Paused));
+        assert!(!was_guiding(AppState::Stopped));
+    }
+
+    #[test]
+    fn client_connection_notices_are_not_proof_of_life() {
+        assert!(from_phd2(&Phd2Event::GuidingStopped));
+        assert!(!from_phd2(&Phd2Event::ConnectionLost {
+            reason: "eof".to_string()
+        }));
+        assert!(!from_phd2(&Phd2Event::Reconnected));
+    }
+}
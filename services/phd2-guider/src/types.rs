@@ -121,6 +121,20 @@ impl GuideAxis {
     }
 }
 
+/// Which Dec corrections PHD2 sends (`get_dec_guide_mode` /
+/// `set_dec_guide_mode`). The serde names are PHD2's own spellings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, derive_more::Display)]
+pub enum DecGuideMode {
+    /// No Dec corrections
+    Off,
+    /// Corrections in both directions
+    Auto,
+    /// North corrections only
+    North,
+    /// South corrections only
+    South,
+}
+
 /// Camera cooler status from PHD2
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CoolerStatus {
@@ -207,6 +221,17 @@ mod tests {
         assert_eq!(json["height"], 50);
     }
 
+    #[test]
+    fn test_dec_guide_mode_uses_phd2_spellings() {
+        assert_eq!(
+            serde_json::to_value(DecGuideMode::North).unwrap(),
+            serde_json::json!("North")
+        );
+        let mode: DecGuideMode = serde_json::from_str(r#""Off""#).unwrap();
+        assert_eq!(mode, DecGuideMode::Off);
+        assert!(serde_json::from_str::<DecGuideMode>(r#""north""#).is_err());
+    }
+
     #[test]
     fn test_profile_parsing() {
         let json = r#"{"id":1,"name":"Default Equipment"}"#;
@@ -30,8 +30,10 @@ bdd_infra::bdd_main! {
                     if let Some(handle) = world.service_handle.as_mut() {
                         handle.stop().await;
                     }
-                    // Drop kills the mock PHD2 child.
+                    // Drop kills the mock PHD2 child, and shuts down a
+                    // supervised one now that serve cannot restart it.
                     world.mock.take();
+                    world.supervised_phd2.take();
                 }
             })
         })
@@ -176,6 +176,48 @@ async fn reselect_star(world: &mut GuiderWorld) {
     post(world, "/api/v1/star/reselect", serde_json::json!({})).await;
 }
 
+#[when("the client requests the guiding settings")]
+async fn request_settings(world: &mut GuiderWorld) {
+    get(world, "/api/v1/guiding/settings").await;
+}
+
+#[when(expr = "the client sets the Dec guide mode to {string} and the Dec min move to {float}")]
+async fn set_dec_mode_and_min_move(world: &mut GuiderWorld, mode: String, min_move: f64) {
+    post(
+        world,
+        "/api/v1/guiding/settings",
+        serde_json::json!({ "dec_guide_mode": mode, "dec": { "min_move_px": min_move } }),
+    )
+    .await;
+}
+
+#[when(
+    expr = "the client sets the Dec guide mode to {string} and the RA parameter {string} to {float}"
+)]
+async fn set_dec_mode_and_ra_param(
+    world: &mut GuiderWorld,
+    mode: String,
+    name: String,
+    value: f64,
+) {
+    post(
+        world,
+        "/api/v1/guiding/settings",
+        serde_json::json!({ "dec_guide_mode": mode, "ra": { "params": { name: value } } }),
+    )
+    .await;
+}
+
+#[when("the client turns multi-star guiding on")]
+async fn set_multi_star(world: &mut GuiderWorld) {
+    post(
+        world,
+        "/api/v1/guiding/settings",
+        serde_json::json!({ "multi_star": true }),
+    )
+    .await;
+}
+
 #[when("the client probes the service health")]
 async fn probe_health(world: &mut GuiderWorld) {
     get(world, "/health").await;
@@ -241,6 +283,31 @@ async fn response_field_true(world: &mut GuiderWorld, field: String) {
     );
 }
 
+#[then(expr = "the response field {string} should be null")]
+async fn response_field_null(world: &mut GuiderWorld, field: String) {
+    let body = &world.last_response().body;
+    assert!(body[&field].is_null(), "field {field} in {body}");
+}
+
+#[then(expr = "the {string} settings should report min_move_px {float} and aggressiveness {float}")]
+async fn axis_settings(world: &mut GuiderWorld, axis: String, min_move: f64, aggressiveness: f64) {
+    let axis = &world.last_response().body[&axis];
+    approx(axis["min_move_px"].as_f64().expect("min_move_px"), min_move);
+    approx(
+        axis["aggressiveness"].as_f64().expect("aggressiveness"),
+        aggressiveness,
+    );
+}
+
+#[then(expr = "the {string} settings should report parameter {string} as {float}")]
+async fn axis_param(world: &mut GuiderWorld, axis: String, name: String, value: f64) {
+    let axis = &world.last_response().body[&axis];
+    let actual = axis["params"][&name]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no parameter {name} in {axis}"));
+    approx(actual, value);
+}
+
 #[then(expr = "the response error should be {string}")]
 async fn response_error(world: &mut GuiderWorld, code: String) {
     let body = &world.last_response().body;
@@ -393,6 +460,29 @@ async fn mock_received_dither(
     assert_settle(&rpc["params"], pixels, time, timeout);
 }
 
+#[then(expr = "the mock PHD2 should have received a set_dec_guide_mode request for {string}")]
+async fn mock_received_dec_mode(world: &mut GuiderWorld, mode: String) {
+    settle_log(world).await;
+    let rpcs = world.logged_rpcs_named("set_dec_guide_mode");
+    let rpc = rpcs.last().expect("no set_dec_guide_mode RPC in log");
+    assert_eq!(rpc["params"]["mode"].as_str(), Some(mode.as_str()), "{rpc}");
+}
+
+#[then(
+    expr = "the mock PHD2 should have received a set_algo_param request for {word} {string} at {float}"
+)]
+async fn mock_received_algo_param(world: &mut GuiderWorld, axis: String, name: String, value: f64) {
+    settle_log(world).await;
+    let rpcs = world.logged_rpcs_named("set_algo_param");
+    let rpc = rpcs
+        .iter()
+        .find(|rpc| {
+            rpc["params"]["axis"] == axis.as_str() && rpc["params"]["name"] == name.as_str()
+        })
+        .unwrap_or_else(|| panic!("no set_algo_param for {axis} {name} in log: {rpcs:?}"));
+    approx(rpc["params"]["value"].as_f64().expect("value"), value);
+}
+
 #[then("the mock PHD2 should have received a full pause request")]
 async fn mock_received_full_pause(world: &mut GuiderWorld) {
     settle_log(world).await;
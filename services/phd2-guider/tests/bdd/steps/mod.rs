@@ -1,3 +1,4 @@
 pub mod auth_steps;
 pub mod doctor_steps;
 pub mod http_steps;
+pub mod supervision_steps;
@@ -0,0 +1,96 @@
+//! Step definitions for `tests/features/process_supervision.feature`.
+
+use cucumber::{given, then, when};
+use std::time::Duration;
+
+use crate::world::GuiderWorld;
+
+/// Restart budget for a scenario: crash detection (one 2 s watch tick),
+/// the 200 ms backoff, and the mock's startup, with room to spare.
+const RESTART_WITHIN: Duration = Duration::from_secs(20);
+
+#[given("the guider service is supervising PHD2")]
+async fn service_supervising(world: &mut GuiderWorld) {
+    world.start_supervising_service().await;
+}
+
+#[when("the supervised PHD2 crashes")]
+async fn supervised_phd2_crashes(world: &mut GuiderWorld) {
+    world
+        .supervised_phd2
+        .as_ref()
+        .expect("no supervised PHD2 — Given step missing?")
+        .crash();
+}
+
+#[then(expr = "the PHD2 restart count should be {int}")]
+async fn restart_count(world: &mut GuiderWorld, count: u64) {
+    let body = &world.last_response().body;
+    assert_eq!(
+        body["phd2_process"]["restart_count"].as_u64(),
+        Some(count),
+        "in {body}"
+    );
+}
+
+#[then(expr = "the health probe should report {int} PHD2 restart(s)")]
+async fn health_reports_restarts(world: &mut GuiderWorld, count: u64) {
+    world
+        .wait_for_health(RESTART_WITHIN, |body| {
+            body["status"] == "ok" && body["phd2_process"]["restart_count"].as_u64() == Some(count)
+        })
+        .await;
+}
+
+#[then(expr = "the last PHD2 crash reason should mention {string}")]
+async fn last_crash_reason(world: &mut GuiderWorld, fragment: String) {
+    let body = &world.last_response().body;
+    let reason = body["phd2_process"]["last_crash"]["reason"]
+        .as_str()
+        .unwrap_or_else(|| panic!("no last crash reason in {body}"));
+    assert!(reason.contains(&fragment), "reason: {reason}");
+    assert!(
+        body["phd2_process"]["last_crash"]["at"].is_string(),
+        "in {body}"
+    );
+}
+
+#[then(expr = "the mock PHD2 should have received a set_profile request for profile {int}")]
+async fn received_set_profile(world: &mut GuiderWorld, id: i64) {
+    let requests = world.logged_rpcs_named("set_profile");
+    assert!(
+        requests.iter().any(|rpc| rpc["params"]["id"] == id),
+        "set_profile requests: {requests:?}"
+    );
+}
+
+#[then(expr = "the mock PHD2 should have received {int} set_profile requests")]
+async fn received_set_profile_count(world: &mut GuiderWorld, count: usize) {
+    let requests = world.logged_rpcs_named("set_profile");
+    assert_eq!(requests.len(), count, "set_profile requests: {requests:?}");
+}
+
+#[then("the mock PHD2 should have received a request to connect the equipment")]
+async fn received_connect_equipment(world: &mut GuiderWorld) {
+    let requests = world.logged_rpcs_named("set_connected");
+    assert!(
+        requests.iter().any(|rpc| rpc["params"] == true),
+        "set_connected requests: {requests:?}"
+    );
+}
+
+#[then(expr = "the mock PHD2 should eventually have received {int} guide requests")]
+async fn eventually_received_guides(world: &mut GuiderWorld, count: usize) {
+    let deadline = tokio::time::Instant::now() + RESTART_WITHIN;
+    loop {
+        let guides = world.logged_rpcs_named("guide").len();
+        if guides >= count {
+            return;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "{guides} guide requests after {RESTART_WITHIN:?}, expected {count}"
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
@@ -22,6 +22,10 @@ pub struct GuiderWorld {
     /// The mock PHD2 child. Killed on drop.
     pub mock: Option<MockPhd2Handle>,
 
+    /// The mock PHD2 that `serve` spawned itself (`phd2.auto_start`).
+    /// Shut down on drop, after the `after` hook has stopped `serve`.
+    pub supervised_phd2: Option<SupervisedPhd2>,
+
     /// Per-scenario temp dir holding the config file and the RPC log.
     pub temp_dir: Option<TempDir>,
 
@@ -121,6 +125,41 @@ impl Drop for MockPhd2Handle {
     }
 }
 
+/// A mock PHD2 spawned by the service under test rather than by the
+/// scenario, so it is reached by port: drop sends it `shutdown`.
+#[derive(Debug)]
+pub struct SupervisedPhd2 {
+    pub port: u16,
+}
+
+impl SupervisedPhd2 {
+    /// Send `shutdown` over the mock's event socket. The supervised mock
+    /// runs with `MOCK_PHD2_SHUTDOWN_EXIT_CODE`, so it exits at once with
+    /// that status — a crash, as far as `serve` can tell.
+    pub fn crash(&self) {
+        use std::io::{Read, Write};
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], self.port));
+        let Ok(mut stream) = std::net::TcpStream::connect_timeout(&addr, Duration::from_secs(2))
+        else {
+            return;
+        };
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(2)));
+        // Wait for the Version event: the mock drops a connection whose
+        // peer is gone before it has written it.
+        let mut reader = BufReader::new(stream.try_clone().expect("clone mock PHD2 stream"));
+        let _ = reader.read_line(&mut String::new());
+        let _ = stream.write_all(b"{\"jsonrpc\":\"2.0\",\"method\":\"shutdown\",\"id\":1}\n");
+        // The mock exits without answering; EOF means it is gone.
+        let _ = reader.read_to_end(&mut Vec::new());
+    }
+}
+
+impl Drop for SupervisedPhd2 {
+    fn drop(&mut self) {
+        self.crash();
+    }
+}
+
 impl GuiderWorld {
     /// Locate the in-tree `mock_phd2` binary the way
     /// `tests/test_integration.rs` does: explicit `MOCK_PHD2_BINARY`
@@ -276,6 +315,89 @@ impl GuiderWorld {
         }
     }
 
+    /// Start `phd2-guider serve` owning PHD2 (`phd2.auto_start`), with
+    /// `mock_phd2` as the PHD2 executable on a free port, the "Mock
+    /// Narrowband" profile, and short restart backoffs. Waits until
+    /// `/health` reports the supervised PHD2 connected.
+    pub async fn start_supervising_service(&mut self) {
+        let dir = self.temp_dir_path();
+        let rpc_log = dir.join("rpc_log.jsonl");
+        self.rpc_log_path = Some(rpc_log.clone());
+        let phd2_port = std::net::TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("reserve a port for the supervised mock PHD2")
+            .port();
+
+        let config = serde_json::json!({
+            "server": { "bind_address": "127.0.0.1", "port": 0 },
+            "supervision": {
+                "restart_backoff": "200ms",
+                "max_restart_backoff": "1s",
+                "hang_timeout": "10s"
+            },
+            "phd2": {
+                "host": "127.0.0.1",
+                "port": phd2_port,
+                "executable_path": Self::mock_phd2_path()
+                    .canonicalize()
+                    .expect("resolve the mock_phd2 path"),
+                "connection_timeout": "10s",
+                "command_timeout": "5s",
+                "auto_start": true,
+                "auto_connect_equipment": true,
+                "profile": "Mock Narrowband",
+                "spawn_env": {
+                    "MOCK_PHD2_PORT": phd2_port.to_string(),
+                    "MOCK_PHD2_RPC_LOG": rpc_log,
+                    "MOCK_PHD2_SHUTDOWN_EXIT_CODE": "3"
+                }
+            },
+            "settling": { "pixels": 0.5, "time": "10s", "timeout": "60s" }
+        });
+        let config_path = dir.join("config.json");
+        std::fs::write(&config_path, config.to_string()).expect("write config");
+        let config_str = config_path.to_string_lossy().into_owned();
+
+        let handle =
+            ServiceHandle::start_with_args("phd2-guider", &["--config", &config_str, "serve"])
+                .await;
+        self.service_handle = Some(handle);
+        self.supervised_phd2 = Some(SupervisedPhd2 { port: phd2_port });
+
+        self.wait_for_health(Duration::from_secs(20), |body| body["status"] == "ok")
+            .await;
+    }
+
+    /// Poll `/health` until `accept` takes its JSON body, recording the
+    /// accepted response; fails the scenario after `within`.
+    pub async fn wait_for_health(
+        &mut self,
+        within: Duration,
+        accept: impl Fn(&serde_json::Value) -> bool,
+    ) {
+        let url = format!("{}/health", self.service_url());
+        let deadline = tokio::time::Instant::now() + within;
+        let mut last = serde_json::Value::Null;
+        loop {
+            let probe = Self::http_client()
+                .get(&url)
+                .timeout(Duration::from_secs(2))
+                .send();
+            if let Ok(response) = probe.await {
+                self.record_response(response).await;
+                last = self.last_response().body.clone();
+                if accept(&last) {
+                    return;
+                }
+            }
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "/health did not reach the expected state within {within:?}; last body: {last}"
+            );
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
     /// Record an HTTP response into the world.
     pub async fn record_response(&mut self, response: reqwest::Response) {
         let status = response.status().as_u16();
@@ -19,6 +19,10 @@ Feature: Guider HTTP service contract
   them, so the per-frame metrics window always holds three entries —
   the star-lost one flagged, never contributing an HFD.
 
+  The mock guides RA with Hysteresis (minMove 0.15, hysteresis 0.1,
+  aggression 0.7) and Dec with Resist Switch (minMove 0.15, aggression
+  0.7, fastSwitch 1.0) in the Auto Dec guide mode.
+
   Scenario: Starting guiding blocks until PHD2 settles and reports the guiding RMS
     Given a mock PHD2 that settles successfully
     And the guider service is running
@@ -192,6 +196,41 @@ Feature: Guider HTTP service contract
     Then the response status should be 502
     And the response error should be "phd2_unreachable"
 
+  Scenario: Guiding settings report PHD2's Dec guide mode and algorithm parameters
+    Given a mock PHD2 that settles successfully
+    And the guider service is running
+    When the client requests the guiding settings
+    Then the response status should be 200
+    And the response field "dec_guide_mode" should be "Auto"
+    And the "ra" settings should report min_move_px 0.15 and aggressiveness 0.7
+    And the "dec" settings should report parameter "fastSwitch" as 1.0
+    And the response field "multi_star" should be null
+    And the response field "backlash_compensation" should be null
+
+  Scenario: A settings update forwards the Dec guide mode and parameters under PHD2's names
+    Given a mock PHD2 that settles successfully
+    And the guider service is running
+    When the client sets the Dec guide mode to "North" and the Dec min move to 0.3
+    Then the response status should be 200
+    And the mock PHD2 should have received a set_dec_guide_mode request for "North"
+    And the mock PHD2 should have received a set_algo_param request for dec "minMove" at 0.3
+
+  Scenario: A parameter the axis' algorithm does not have changes nothing
+    Given a mock PHD2 that settles successfully
+    And the guider service is running
+    When the client sets the Dec guide mode to "Off" and the RA parameter "fastSwitch" to 0.0
+    Then the response status should be 400
+    And the response error should be "invalid_request" mentioning "fastSwitch"
+    And the mock PHD2 should not have received a "set_dec_guide_mode" request
+    And the mock PHD2 should not have received a "set_algo_param" request
+
+  Scenario: Multi-star guiding cannot be set through PHD2's event server
+    Given a mock PHD2 that settles successfully
+    And the guider service is running
+    When the client turns multi-star guiding on
+    Then the response status should be 400
+    And the response error should be "invalid_request" mentioning "PHD2 profile"
+
   Scenario: Health reports ok while PHD2 is connected
     Given a mock PHD2 that settles successfully
     And the guider service is running
@@ -0,0 +1,34 @@
+Feature: PHD2 process supervision under serve
+
+  With phd2.auto_start, phd2-guider serve owns the PHD2 process: it
+  starts PHD2, selects the configured profile, connects the equipment,
+  and restarts PHD2 with backoff when it exits or stops answering on
+  its event server — restarting the guide loop when one was running.
+  The health probe reports the restart count and the last crash so
+  sentinel's service-health dashboard shows why guiding paused.
+
+  The scenarios run mock_phd2 as the PHD2 executable, configured for
+  the "Mock Narrowband" profile (id 2). A crash is a shutdown request
+  the mock answers by exiting at once with status 3.
+
+  Scenario: serve starts PHD2 with the configured profile and equipment
+    Given the guider service is supervising PHD2
+    When the client probes the service health
+    Then the response status should be 200
+    And the PHD2 restart count should be 0
+    And the mock PHD2 should have received a set_profile request for profile 2
+    And the mock PHD2 should have received a request to connect the equipment
+
+  Scenario: A crashed PHD2 is restarted and the crash reported on the health probe
+    Given the guider service is supervising PHD2
+    When the supervised PHD2 crashes
+    Then the health probe should report 1 PHD2 restart
+    And the last PHD2 crash reason should mention "exited with status 3"
+    And the mock PHD2 should have received 2 set_profile requests
+
+  Scenario: A crash while guiding restarts the guide loop
+    Given the guider service is supervising PHD2
+    And the client starts guiding
+    When the supervised PHD2 crashes
+    Then the health probe should report 1 PHD2 restart
+    And the mock PHD2 should eventually have received 2 guide requests
@@ -9,6 +9,7 @@
 use std::path::PathBuf;
 use std::time::Duration;
 
+use rp_ephemeris::HorizonProfile;
 use rp_mcp_client::ClientAuthConfig;
 pub use rusty_photon_server_config::AlpacaServerConfig;
 use serde::{Deserialize, Serialize};
@@ -48,7 +49,7 @@ impl Default for Config {
 /// The startup-default observing site. A client site push (`SkySafari` sends
 /// its GPS-derived coordinates after connect) overrides these live; a
 /// restart reverts to them.
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, schemars::JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct SiteConfig {
     /// WGS84 latitude in degrees, north positive.
@@ -58,6 +59,12 @@ pub struct SiteConfig {
     /// Elevation in meters. Reported via `SiteElevation` only.
     #[serde(default)]
     pub site_elevation_m: f64,
+    /// Horizon file (N.I.N.A. `.hrz`, Stellarium, APCC / CSV — the formats
+    /// `rp_ephemeris::HorizonProfile::parse` reads), normally the same file
+    /// rp's `site.horizon.file` names. Raises the reported-position altitude
+    /// floor per azimuth; `None` keeps the floor flat.
+    #[serde(default)]
+    pub site_horizon_file: Option<PathBuf>,
 }
 
 impl Default for SiteConfig {
@@ -66,10 +73,30 @@ impl Default for SiteConfig {
             site_latitude_deg: LatitudeDeg(33.0),
             site_longitude_deg: LongitudeDeg(-117.0),
             site_elevation_m: 0.0,
+            site_horizon_file: None,
         }
     }
 }
 
+impl SiteConfig {
+    /// Read and parse `site_horizon_file` — the empty (flat) profile when
+    /// unset. A file is I/O rather than a range, so it is checked here, once
+    /// at startup, instead of at deserialization.
+    ///
+    /// # Errors
+    ///
+    /// A message naming the field if the file cannot be read or parsed.
+    pub fn horizon_profile(&self) -> Result<HorizonProfile, String> {
+        let Some(path) = self.site_horizon_file.as_ref() else {
+            return Ok(HorizonProfile::default());
+        };
+        std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| HorizonProfile::parse(&text).map_err(|e| e.to_string()))
+            .map_err(|e| format!("site.site_horizon_file {}: {e}", path.display()))
+    }
+}
+
 /// Latitude in degrees, `[-90, 90]`.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
 #[serde(into = "f64", try_from = "f64")]
@@ -387,6 +414,7 @@ mod tests {
         assert_eq!(config.site.site_latitude_deg.degrees(), 33.0);
         assert_eq!(config.site.site_longitude_deg.degrees(), -117.0);
         assert_eq!(config.site.site_elevation_m, 0.0);
+        assert!(config.site.site_horizon_file.is_none());
         assert_eq!(
             config.rp.mcp_server_url.as_str(),
             "http://127.0.0.1:11115/mcp"
@@ -458,6 +486,23 @@ mod tests {
         assert!(err.contains("report_altitude_floor_deg"), "{err}");
     }
 
+    #[test]
+    fn horizon_file_loads_and_a_missing_one_names_the_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("site.hrz");
+        std::fs::write(&path, "0 15\n180 30\n").unwrap();
+        let mut site = SiteConfig {
+            site_horizon_file: Some(path),
+            ..SiteConfig::default()
+        };
+        assert_eq!(site.horizon_profile().unwrap().points().len(), 2);
+
+        site.site_horizon_file = Some(dir.path().join("missing.hrz"));
+        let err = site.horizon_profile().unwrap_err();
+        assert!(err.contains("site_horizon_file"), "{err}");
+        assert!(SiteConfig::default().horizon_profile().unwrap().is_empty());
+    }
+
     #[test]
     fn out_of_range_latitude_is_rejected_naming_the_field() {
         let json = r#"{
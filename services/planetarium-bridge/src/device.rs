@@ -5,7 +5,8 @@
 //! one import and sets the virtual pointing. Slew verbs are simulated
 //! motion — `Slewing` reads true for the convergence window while the
 //! reported position interpolates — and never import. What
-//! `RightAscension`/`Declination` return is subject to the altitude floor:
+//! `RightAscension`/`Declination` return is subject to the altitude floor
+//! (raised per azimuth by the site's horizon profile, when configured):
 //! below it, the report snaps to the zenith idle point (RA = LST,
 //! Dec = site latitude), the P3a wedge defense.
 
@@ -20,7 +21,7 @@ use ascom_alpaca::api::Device;
 use ascom_alpaca::{ASCOMError, ASCOMResult};
 use async_trait::async_trait;
 use chrono::{SecondsFormat, Utc};
-use rp_ephemeris::{Ephemeris, ErfarsEphemeris, IcrsCoord, Site};
+use rp_ephemeris::{Ephemeris, ErfarsEphemeris, HorizonProfile, IcrsCoord, Site};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
@@ -68,6 +69,10 @@ pub struct BridgeTelescope {
     slew_duration: Duration,
     /// `None` disables the reported-position policy.
     floor_deg: Option<f64>,
+    /// Per-azimuth obstructions raising `floor_deg`; empty = a flat floor.
+    /// Loaded once from `site.site_horizon_file` and kept across client
+    /// site pushes (the push moves the coordinates, not the trees).
+    horizon: HorizonProfile,
     importer: Importer,
     /// Peer address of the most recent Alpaca request — the import
     /// provenance's `client` field (single-client in practice; P3a).
@@ -86,6 +91,7 @@ impl BridgeTelescope {
             site_config.site_latitude_deg.degrees(),
             site_config.site_longitude_deg.degrees(),
         )?;
+        let horizon = site_config.horizon_profile()?;
         // Start pointed at the zenith idle point — the "parked overhead"
         // resting pose the reported-position policy snaps to anyway.
         let initial_ra = ephemeris.sidereal_time(&site, Utc::now()).lst_hours;
@@ -107,6 +113,7 @@ impl BridgeTelescope {
             floor_deg: device
                 .report_altitude_floor_deg
                 .map(super::config::FloorDeg::degrees),
+            horizon,
             importer,
             last_client,
         })
@@ -139,7 +146,10 @@ impl BridgeTelescope {
             },
             now,
         ) {
-            Ok(alt_az) if alt_az.altitude_degrees < floor => {
+            Ok(alt_az)
+                if alt_az.altitude_degrees
+                    < self.horizon.floor_at(alt_az.azimuth_degrees, floor) =>
+            {
                 let lst = self.ephemeris.sidereal_time(&site, now).lst_hours;
                 (lst, site.latitude_degrees)
             }
@@ -5,6 +5,7 @@
 //! `docs/plans/archive/plate-solver.md` §"HTTP contract" and the
 //! behavior contract is in `docs/services/plate-solver.md`.
 
+use crate::config::SolverKind;
 use crate::error::AppError;
 use crate::runner::{AstapRunner, RunnerError, SolveOutcome, SolveRequest, WcsMatrix};
 use axum::{extract::State, response::IntoResponse, routing::get, routing::post, Json, Router};
@@ -23,9 +24,14 @@ pub struct AppState {
     pub semaphore: Arc<Semaphore>,
     pub default_solve_timeout: Duration,
     pub max_solve_timeout: Duration,
-    /// Paths re-validated by `/health` on every probe.
-    pub astap_binary_path: PathBuf,
-    pub astap_db_directory: PathBuf,
+    /// Names the solver in error and `/health` messages.
+    pub solver: SolverKind,
+    /// Paths re-validated by `/health` on every probe: the active
+    /// solver's binary, plus ASTAP's star database or `solve-field`'s
+    /// config (whichever applies; the other is `None`).
+    pub binary_path: PathBuf,
+    pub astap_db_directory: Option<PathBuf>,
+    pub solve_field_config: Option<PathBuf>,
 }
 
 pub fn build_router(state: AppState) -> Router {
@@ -139,18 +145,19 @@ async fn solve(
         timeout,
     };
 
+    let solver = state.solver.display_name();
     match state.runner.solve(request).await {
         Ok(outcome) => Ok(Json(SolveResponseBody::from(outcome))),
         Err(RunnerError::ExitStatus {
             status,
             stderr_tail,
         }) => Err(AppError::SolveFailed {
-            message: format!("ASTAP exited with code {status}"),
+            message: format!("{solver} exited with code {status}"),
             exit_code: Some(status),
             stderr_tail: Some(stderr_tail),
         }),
         Err(RunnerError::NoWcs) => Err(AppError::SolveFailed {
-            message: "ASTAP did not write a .wcs sidecar (exit was clean)".to_string(),
+            message: format!("{solver} did not write a .wcs sidecar (exit was clean)"),
             exit_code: None,
             stderr_tail: None,
         }),
@@ -175,41 +182,42 @@ struct HealthBody {
     message: Option<String>,
 }
 
-/// Cheap readiness probe: stats both runtime dependencies. Returns 200
-/// with `{"status": "ok"}` when both pass; 503 otherwise — a degraded
-/// answer, since a missing binary or database is not curable by a
-/// service restart.
+/// Cheap readiness probe: stats the active solver's runtime
+/// dependencies. Returns 200 with `{"status": "ok"}` when all pass; 503
+/// otherwise — a degraded answer, since a missing binary or index data
+/// is not curable by a service restart.
 async fn health(State(state): State<AppState>) -> impl IntoResponse {
-    // SECURITY: `state.astap_binary_path` and `state.astap_db_directory`
-    // come from operator-supplied config, validated at startup. Health
-    // re-validates them against the same rules. The wrapper isn't
-    // multi-tenant; "user-controlled path" alerts on these fields are
-    // by-design and dismissed.
-    if !path_is_executable_file(&state.astap_binary_path) {
-        return (
-            axum::http::StatusCode::SERVICE_UNAVAILABLE,
-            Json(HealthBody {
-                status: "binary_unavailable",
-                message: Some(format!(
-                    "ASTAP binary missing or not executable: {}",
-                    state.astap_binary_path.display()
-                )),
-            }),
-        )
-            .into_response();
+    // SECURITY: the probed paths come from operator-supplied config,
+    // validated at startup. Health re-validates them against the same
+    // rules. The wrapper isn't multi-tenant; "user-controlled path"
+    // alerts on these fields are by-design and dismissed.
+    let solver = state.solver.display_name();
+    if !path_is_executable_file(&state.binary_path) {
+        return unavailable(
+            "binary_unavailable",
+            format!(
+                "{solver} binary missing or not executable: {}",
+                state.binary_path.display()
+            ),
+        );
     }
-    if !state.astap_db_directory.is_dir() {
-        return (
-            axum::http::StatusCode::SERVICE_UNAVAILABLE,
-            Json(HealthBody {
-                status: "db_unavailable",
-                message: Some(format!(
-                    "ASTAP star database directory missing: {}",
-                    state.astap_db_directory.display()
-                )),
-            }),
-        )
-            .into_response();
+    if let Some(db) = &state.astap_db_directory {
+        if !db.is_dir() {
+            return unavailable(
+                "db_unavailable",
+                format!("ASTAP star database directory missing: {}", db.display()),
+            );
+        }
+    }
+    // solve-field reaches its index files through this config, so a
+    // missing one is the same condition as ASTAP's missing database.
+    if let Some(config) = &state.solve_field_config {
+        if !config.is_file() {
+            return unavailable(
+                "db_unavailable",
+                format!("solve-field index config missing: {}", config.display()),
+            );
+        }
     }
     (
         axum::http::StatusCode::OK,
@@ -221,6 +229,17 @@ async fn health(State(state): State<AppState>) -> impl IntoResponse {
         .into_response()
 }
 
+fn unavailable(status: &'static str, message: String) -> axum::response::Response {
+    (
+        axum::http::StatusCode::SERVICE_UNAVAILABLE,
+        Json(HealthBody {
+            status,
+            message: Some(message),
+        }),
+    )
+        .into_response()
+}
+
 fn path_is_executable_file(path: &std::path::Path) -> bool {
     // SECURITY: `path` is operator-supplied via config; see comment in
     // `health()`. CodeQL's "user-controlled data in path expression"
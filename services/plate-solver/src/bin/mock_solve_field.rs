@@ -0,0 +1,136 @@
+//! `mock_solve_field` — test double mimicking astrometry.net's
+//! `solve-field` surface, as `SolveFieldRunner` drives it.
+//!
+//! Behavior is selected via `MOCK_SOLVE_FIELD_MODE`:
+//!
+//! | Mode | Behavior |
+//! |------|----------|
+//! | `normal` (default) | Write a canned astrometry.net-style `.wcs` to the `--wcs <path>` argument, exit 0 |
+//! | `exit_failure` | Print to stderr, exit 1 (no `.wcs`) |
+//! | `no_wcs` | Exit 0 without writing any `.wcs` — `solve-field`'s "did not solve" outcome |
+//! | `hang` | Sleep indefinitely; respond to the platform's graceful signal cleanly |
+//!
+//! `MOCK_SOLVE_FIELD_ARGV_OUT=<path>` (any mode) appends the received argv
+//! to the file at `<path>`, one arg per line, with a trailing blank line as
+//! record separator — the `MOCK_ASTAP_ARGV_OUT` counterpart.
+//!
+//! Pattern mirrors `src/bin/mock_astap.rs`.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Cards of the canned `.wcs` for `MOCK_SOLVE_FIELD_MODE=normal`. Shape
+/// mirrors real `solve-field --crpix-center` output: a header-only FITS
+/// primary HDU (`NAXIS = 0`) carrying the solution as CRPIX + CD matrix
+/// only — no CDELT/CROTA2 — plus the suite's HISTORY banner. The matrix
+/// matches `mock_astap`'s (1.05″/px rotated 12.3°, RA-flipped parity) so
+/// both runners' tests assert the same center and scale.
+const CANNED_WCS_CARDS: [&str; 17] = [
+    "SIMPLE  =                    T",
+    "BITPIX  =                    8",
+    "NAXIS   =                    0",
+    "WCSAXES =                    2",
+    "CTYPE1  = 'RA---TAN'",
+    "CTYPE2  = 'DEC--TAN'",
+    "EQUINOX =               2000.0",
+    "CRVAL1  =              10.6848",
+    "CRVAL2  =              41.2690",
+    "CRPIX1  =                512.0",
+    "CRPIX2  =                384.0",
+    "CD1_1   =         -0.000284972",
+    "CD1_2   =         -0.000062134",
+    "CD2_1   =         -0.000062134",
+    "CD2_2   =          0.000284972",
+    "HISTORY Created by the Astrometry.net suite.",
+    "END",
+];
+
+fn main() -> std::process::ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+
+    if let Ok(out_path) = std::env::var("MOCK_SOLVE_FIELD_ARGV_OUT") {
+        let _ = write_argv(&out_path, &args);
+    }
+
+    let mode = std::env::var("MOCK_SOLVE_FIELD_MODE").unwrap_or_else(|_| "normal".to_string());
+
+    match mode.as_str() {
+        "normal" => run_normal(&args),
+        "exit_failure" => run_exit_failure(),
+        "no_wcs" => run_no_wcs(),
+        "hang" => run_hang(),
+        other => {
+            eprintln!("mock_solve_field: unknown MOCK_SOLVE_FIELD_MODE: {other}");
+            std::process::ExitCode::from(2)
+        }
+    }
+}
+
+fn write_argv(path: &str, args: &[String]) -> std::io::Result<()> {
+    let mut f = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    for a in args {
+        writeln!(f, "{a}")?;
+    }
+    writeln!(f)?;
+    Ok(())
+}
+
+fn wcs_path_from_argv(args: &[String]) -> Option<PathBuf> {
+    let mut iter = args.iter();
+    while let Some(a) = iter.next() {
+        if a == "--wcs" {
+            return iter.next().map(PathBuf::from);
+        }
+    }
+    None
+}
+
+/// The canned cards, each padded to 80 bytes, padded to one 2880-byte
+/// FITS block.
+fn canned_wcs() -> String {
+    let mut content = String::with_capacity(2880);
+    for c in CANNED_WCS_CARDS {
+        content.push_str(&format!("{c:<80}"));
+    }
+    while content.len() < 2880 {
+        content.push(' ');
+    }
+    content
+}
+
+fn run_normal(args: &[String]) -> std::process::ExitCode {
+    let Some(wcs_path) = wcs_path_from_argv(args) else {
+        eprintln!("mock_solve_field: --wcs <path> required in `normal` mode");
+        return std::process::ExitCode::from(2);
+    };
+    if let Err(e) = std::fs::write(&wcs_path, canned_wcs()) {
+        eprintln!(
+            "mock_solve_field: failed to write {}: {e}",
+            wcs_path.display()
+        );
+        return std::process::ExitCode::from(2);
+    }
+    std::process::ExitCode::SUCCESS
+}
+
+fn run_exit_failure() -> std::process::ExitCode {
+    eprintln!("mock_solve_field: simulated solve failure (exit 1)");
+    std::process::ExitCode::from(1)
+}
+
+const fn run_no_wcs() -> std::process::ExitCode {
+    // solve-field exits 0 when it gives up without a match; the wrapper
+    // must surface NoWcs.
+    std::process::ExitCode::SUCCESS
+}
+
+fn run_hang() -> std::process::ExitCode {
+    // Default Unix SIGTERM / Windows CTRL_BREAK_EVENT handling exits,
+    // which is what this mode needs.
+    loop {
+        std::thread::sleep(std::time::Duration::from_mins(1));
+    }
+}
@@ -18,9 +18,26 @@ pub struct Config {
     #[serde(default = "default_server")]
     pub server: ServerConfig,
 
-    pub astap_binary_path: PathBuf,
+    /// Which solver every request runs through.
+    #[serde(default)]
+    pub solver: SolverKind,
+
+    /// Required when `solver` is `astap`.
+    #[serde(default)]
+    pub astap_binary_path: Option<PathBuf>,
+
+    /// Required when `solver` is `astap`.
+    #[serde(default)]
+    pub astap_db_directory: Option<PathBuf>,
+
+    /// Required when `solver` is `solve_field`.
+    #[serde(default)]
+    pub solve_field_binary_path: Option<PathBuf>,
 
-    pub astap_db_directory: PathBuf,
+    /// `astrometry.cfg` naming the index files, passed as `--config`.
+    /// `None` leaves `solve-field` on its compiled-in default.
+    #[serde(default)]
+    pub solve_field_config: Option<PathBuf>,
 
     #[serde(default = "default_max_concurrency")]
     pub max_concurrency: usize,
@@ -38,6 +55,42 @@ pub struct Config {
     /// `env::set_var` races.
     #[serde(default)]
     pub astap_extra_env: HashMap<String, String>,
+
+    /// Environment variables set on every spawned `solve-field` child;
+    /// the `astap_extra_env` counterpart (BDD and integration tests
+    /// drive `mock_solve_field`'s `MOCK_SOLVE_FIELD_MODE` through it).
+    #[serde(default)]
+    pub solve_field_extra_env: HashMap<String, String>,
+}
+
+/// The external solver the wrapper drives.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverKind {
+    /// ASTAP CLI (`AstapCliRunner`).
+    #[default]
+    Astap,
+    /// astrometry.net `solve-field` (`SolveFieldRunner`).
+    SolveField,
+}
+
+impl SolverKind {
+    /// Operator-facing name used in solve errors and `/health` messages.
+    #[must_use]
+    pub const fn display_name(self) -> &'static str {
+        match self {
+            Self::Astap => "ASTAP",
+            Self::SolveField => "solve-field",
+        }
+    }
+
+    /// The `solver` config value that selects this kind.
+    const fn config_name(self) -> &'static str {
+        match self {
+            Self::Astap => "astap",
+            Self::SolveField => "solve_field",
+        }
+    }
 }
 
 /// plate-solver's default `server` block when the config file omits it:
@@ -68,10 +121,23 @@ pub enum ConfigError {
     Parse(#[from] serde_json::Error),
 
     #[error(
-        "invalid `astap_binary_path`: {message} (path: {path}). \
+        "`{field}` is required when `solver` is \"{solver}\". \
+         See services/plate-solver/README.md for install instructions."
+    )]
+    MissingSolverField {
+        field: &'static str,
+        solver: &'static str,
+    },
+
+    #[error(
+        "invalid `{field}`: {message} (path: {path}). \
          See services/plate-solver/README.md for install instructions."
     )]
-    InvalidBinaryPath { path: String, message: String },
+    InvalidBinaryPath {
+        field: &'static str,
+        path: String,
+        message: String,
+    },
 
     #[error(
         "invalid `astap_db_directory`: {message} (path: {path}). \
@@ -79,6 +145,12 @@ pub enum ConfigError {
     )]
     InvalidDbDirectory { path: String, message: String },
 
+    #[error(
+        "invalid `solve_field_config`: {message} (path: {path}). \
+         See services/plate-solver/README.md for install instructions."
+    )]
+    InvalidSolveFieldConfig { path: String, message: String },
+
     #[error("`default_solve_timeout` ({default:?}) exceeds `max_solve_timeout` ({max:?})")]
     TimeoutOrder { default: Duration, max: Duration },
 
@@ -97,8 +169,22 @@ impl Config {
     /// Validate the parsed config. Caller exits non-zero on failure so
     /// Sentinel surfaces the misconfiguration rather than masking it.
     pub fn validate(&self) -> Result<(), ConfigError> {
-        validate_binary_path(&self.astap_binary_path)?;
-        validate_db_directory(&self.astap_db_directory)?;
+        match self.solver {
+            SolverKind::Astap => {
+                let binary = self.required("astap_binary_path", &self.astap_binary_path)?;
+                validate_binary_path("astap_binary_path", binary)?;
+                let db = self.required("astap_db_directory", &self.astap_db_directory)?;
+                validate_db_directory(db)?;
+            }
+            SolverKind::SolveField => {
+                let binary =
+                    self.required("solve_field_binary_path", &self.solve_field_binary_path)?;
+                validate_binary_path("solve_field_binary_path", binary)?;
+                if let Some(config) = &self.solve_field_config {
+                    validate_solve_field_config(config)?;
+                }
+            }
+        }
         if self.default_solve_timeout > self.max_solve_timeout {
             return Err(ConfigError::TimeoutOrder {
                 default: self.default_solve_timeout,
@@ -110,15 +196,37 @@ impl Config {
         }
         Ok(())
     }
+
+    /// The active solver's binary: the path `/health` re-probes.
+    #[must_use]
+    pub fn solver_binary_path(&self) -> Option<&Path> {
+        match self.solver {
+            SolverKind::Astap => self.astap_binary_path.as_deref(),
+            SolverKind::SolveField => self.solve_field_binary_path.as_deref(),
+        }
+    }
+
+    fn required<'a>(
+        &self,
+        field: &'static str,
+        value: &'a Option<PathBuf>,
+    ) -> Result<&'a Path, ConfigError> {
+        value.as_deref().ok_or(ConfigError::MissingSolverField {
+            field,
+            solver: self.solver.config_name(),
+        })
+    }
 }
 
-fn validate_binary_path(path: &Path) -> Result<(), ConfigError> {
+fn validate_binary_path(field: &'static str, path: &Path) -> Result<(), ConfigError> {
     let meta = std::fs::metadata(path).map_err(|e| ConfigError::InvalidBinaryPath {
+        field,
         path: path.display().to_string(),
         message: format!("stat failed: {e}"),
     })?;
     if !meta.is_file() {
         return Err(ConfigError::InvalidBinaryPath {
+            field,
             path: path.display().to_string(),
             message: "not a regular file".into(),
         });
@@ -130,6 +238,7 @@ fn validate_binary_path(path: &Path) -> Result<(), ConfigError> {
         // Any execute bit set (user, group, or other).
         if mode & 0o111 == 0 {
             return Err(ConfigError::InvalidBinaryPath {
+                field,
                 path: path.display().to_string(),
                 message: "not executable (no execute bit set)".into(),
             });
@@ -152,6 +261,20 @@ fn validate_db_directory(path: &Path) -> Result<(), ConfigError> {
     Ok(())
 }
 
+fn validate_solve_field_config(path: &Path) -> Result<(), ConfigError> {
+    let meta = std::fs::metadata(path).map_err(|e| ConfigError::InvalidSolveFieldConfig {
+        path: path.display().to_string(),
+        message: format!("stat failed: {e}"),
+    })?;
+    if !meta.is_file() {
+        return Err(ConfigError::InvalidSolveFieldConfig {
+            path: path.display().to_string(),
+            message: "not a regular file".into(),
+        });
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -191,6 +314,33 @@ mod tests {
         p
     }
 
+    /// A defaults-everywhere ASTAP config; tests override one field.
+    fn astap_config(binary: PathBuf, db: PathBuf) -> Config {
+        Config {
+            server: default_server(),
+            solver: SolverKind::Astap,
+            astap_binary_path: Some(binary),
+            astap_db_directory: Some(db),
+            solve_field_binary_path: None,
+            solve_field_config: None,
+            max_concurrency: default_max_concurrency(),
+            default_solve_timeout: default_solve_timeout(),
+            max_solve_timeout: default_max_solve_timeout(),
+            astap_extra_env: Default::default(),
+            solve_field_extra_env: Default::default(),
+        }
+    }
+
+    fn solve_field_only(binary: PathBuf) -> Config {
+        Config {
+            solver: SolverKind::SolveField,
+            astap_binary_path: None,
+            astap_db_directory: None,
+            solve_field_binary_path: Some(binary),
+            ..astap_config(PathBuf::new(), PathBuf::new())
+        }
+    }
+
     #[test]
     fn happy_path_loads_and_validates() {
         let dir = TempDir::new().unwrap();
@@ -217,7 +367,7 @@ mod tests {
     }
 
     #[test]
-    fn missing_binary_path_field_fails_to_parse() {
+    fn missing_binary_path_field_fails_validation() {
         let dir = TempDir::new().unwrap();
         let db = fake_db_dir(&dir);
         let body = serde_json::json!({
@@ -225,23 +375,24 @@ mod tests {
         })
         .to_string();
         let cfg_path = write_config(&dir, &body);
-        let err = load_config(&cfg_path).unwrap_err();
-        assert!(matches!(err, ConfigError::Parse(_)));
+        let cfg = load_config(&cfg_path).unwrap();
+        assert_eq!(cfg.solver, SolverKind::Astap);
+        let err = cfg.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::MissingSolverField {
+                field: "astap_binary_path",
+                solver: "astap"
+            }
+        ));
+        assert!(err.to_string().contains("README"));
     }
 
     #[test]
     fn nonexistent_binary_path_fails_validation() {
         let dir = TempDir::new().unwrap();
         let db = fake_db_dir(&dir);
-        let cfg = Config {
-            server: default_server(),
-            astap_binary_path: "/absolutely/does/not/exist/astap_cli".into(),
-            astap_db_directory: db,
-            max_concurrency: default_max_concurrency(),
-            default_solve_timeout: default_solve_timeout(),
-            max_solve_timeout: default_max_solve_timeout(),
-            astap_extra_env: Default::default(),
-        };
+        let cfg = astap_config("/absolutely/does/not/exist/astap_cli".into(), db);
         let err = cfg.validate().unwrap_err();
         let msg = err.to_string();
         assert!(matches!(err, ConfigError::InvalidBinaryPath { .. }));
@@ -253,15 +404,7 @@ mod tests {
     fn nonexistent_db_directory_fails_validation() {
         let dir = TempDir::new().unwrap();
         let bin = fake_binary(&dir, "astap_cli");
-        let cfg = Config {
-            server: default_server(),
-            astap_binary_path: bin,
-            astap_db_directory: "/absolutely/does/not/exist/d05".into(),
-            max_concurrency: default_max_concurrency(),
-            default_solve_timeout: default_solve_timeout(),
-            max_solve_timeout: default_max_solve_timeout(),
-            astap_extra_env: Default::default(),
-        };
+        let cfg = astap_config(bin, "/absolutely/does/not/exist/d05".into());
         let err = cfg.validate().unwrap_err();
         assert!(matches!(err, ConfigError::InvalidDbDirectory { .. }));
         assert!(err.to_string().contains("astap_db_directory"));
@@ -273,13 +416,9 @@ mod tests {
         let bin = fake_binary(&dir, "astap_cli");
         let db = fake_db_dir(&dir);
         let cfg = Config {
-            server: default_server(),
-            astap_binary_path: bin,
-            astap_db_directory: db,
-            max_concurrency: 1,
             default_solve_timeout: Duration::from_mins(1),
             max_solve_timeout: Duration::from_secs(30),
-            astap_extra_env: Default::default(),
+            ..astap_config(bin, db)
         };
         let err = cfg.validate().unwrap_err();
         assert!(matches!(err, ConfigError::TimeoutOrder { .. }));
@@ -291,13 +430,8 @@ mod tests {
         let bin = fake_binary(&dir, "astap_cli");
         let db = fake_db_dir(&dir);
         let cfg = Config {
-            server: default_server(),
-            astap_binary_path: bin,
-            astap_db_directory: db,
             max_concurrency: 0,
-            default_solve_timeout: default_solve_timeout(),
-            max_solve_timeout: default_max_solve_timeout(),
-            astap_extra_env: Default::default(),
+            ..astap_config(bin, db)
         };
         assert!(matches!(
             cfg.validate().unwrap_err(),
@@ -315,19 +449,84 @@ mod tests {
         use std::os::unix::fs::PermissionsExt;
         fs::set_permissions(&bin, fs::Permissions::from_mode(0o644)).unwrap();
         let db = fake_db_dir(&dir);
-        let cfg = Config {
-            server: default_server(),
-            astap_binary_path: bin,
-            astap_db_directory: db,
-            max_concurrency: 1,
-            default_solve_timeout: default_solve_timeout(),
-            max_solve_timeout: default_max_solve_timeout(),
-            astap_extra_env: Default::default(),
-        };
+        let cfg = astap_config(bin, db);
         let err = cfg.validate().unwrap_err();
         assert!(matches!(err, ConfigError::InvalidBinaryPath { .. }));
         assert!(err.to_string().contains("not executable"));
     }
+
+    #[test]
+    fn solve_field_config_loads_without_astap_fields() {
+        let dir = TempDir::new().unwrap();
+        let bin = fake_binary(&dir, "solve-field");
+        let cfg_file = dir.path().join("astrometry.cfg");
+        fs::write(&cfg_file, b"add_path /usr/share/astrometry\n").unwrap();
+        let body = serde_json::json!({
+            "solver": "solve_field",
+            "solve_field_binary_path": bin.to_string_lossy(),
+            "solve_field_config": cfg_file.to_string_lossy(),
+        })
+        .to_string();
+        let cfg = load_config(write_config(&dir, &body)).unwrap();
+        cfg.validate().unwrap();
+        assert_eq!(cfg.solver, SolverKind::SolveField);
+        assert_eq!(cfg.solver_binary_path(), Some(bin.as_path()));
+    }
+
+    #[test]
+    fn solve_field_without_its_binary_fails_validation() {
+        let dir = TempDir::new().unwrap();
+        let bin = fake_binary(&dir, "astap_cli");
+        let db = fake_db_dir(&dir);
+        // ASTAP paths do not stand in for the selected solver's binary.
+        let cfg = Config {
+            solver: SolverKind::SolveField,
+            ..astap_config(bin, db)
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::MissingSolverField {
+                field: "solve_field_binary_path",
+                solver: "solve_field"
+            }
+        ));
+    }
+
+    #[test]
+    fn nonexistent_solve_field_binary_names_its_field() {
+        let cfg = solve_field_only("/absolutely/does/not/exist/solve-field".into());
+        let err = cfg.validate().unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::InvalidBinaryPath {
+                field: "solve_field_binary_path",
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("solve_field_binary_path"));
+    }
+
+    #[test]
+    fn solve_field_config_must_be_a_file() {
+        let dir = TempDir::new().unwrap();
+        let bin = fake_binary(&dir, "solve-field");
+        let cfg = Config {
+            solve_field_config: Some(dir.path().to_path_buf()),
+            ..solve_field_only(bin)
+        };
+        let err = cfg.validate().unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidSolveFieldConfig { .. }));
+        assert!(err.to_string().contains("not a regular file"));
+    }
+
+    #[test]
+    fn unknown_solver_fails_to_parse() {
+        let dir = TempDir::new().unwrap();
+        let body = serde_json::json!({ "solver": "pinpoint" }).to_string();
+        let err = load_config(write_config(&dir, &body)).unwrap_err();
+        assert!(matches!(err, ConfigError::Parse(_)));
+    }
 }
 
 #[cfg(test)]
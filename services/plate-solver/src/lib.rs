@@ -1,5 +1,6 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
-//! plate-solver — rp-managed service wrapping the ASTAP CLI.
+//! plate-solver — rp-managed service wrapping the ASTAP CLI or
+//! astrometry.net's `solve-field`.
 //!
 //! See `docs/services/plate-solver.md` for the design contract and
 //! `docs/plans/archive/plate-solver.md` for sequencing.
@@ -12,13 +13,15 @@ pub mod runner;
 pub mod supervision;
 
 pub use api::AppState;
-pub use config::{load_config, Config, ConfigError};
+pub use config::{load_config, Config, ConfigError, SolverKind};
 pub use error::{AppError, ErrorCode, ErrorResponse};
 pub use runner::astap::AstapCliRunner;
+pub use runner::solve_field::SolveFieldRunner;
 pub use runner::{AstapRunner, RunnerError, SolveOutcome, SolveRequest, WcsMatrix};
 
 use std::future::Future;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::net::TcpListener;
 use tokio::sync::Semaphore;
@@ -49,7 +52,8 @@ impl ServerBuilder {
     }
 
     /// Override the runner (tests inject mocks; production uses
-    /// `AstapCliRunner` constructed from config).
+    /// `AstapCliRunner` or `SolveFieldRunner` constructed from config,
+    /// per its `solver` field).
     pub fn with_runner(mut self, runner: Arc<dyn AstapRunner>) -> Self {
         self.runner = Some(runner);
         self
@@ -65,15 +69,30 @@ impl ServerBuilder {
             .validate()
             .map_err(|e| std::io::Error::other(e.to_string()))?;
 
-        let runner = self.runner.unwrap_or_else(|| {
-            let mut runner = AstapCliRunner::new(
-                config.astap_binary_path.clone(),
-                config.astap_db_directory.clone(),
-            );
-            for (k, v) in &config.astap_extra_env {
-                runner = runner.with_env(k, v);
+        // `validate()` guaranteed the selected solver's paths are set.
+        let binary_path = config
+            .solver_binary_path()
+            .map(PathBuf::from)
+            .unwrap_or_default();
+        let runner: Arc<dyn AstapRunner> = self.runner.unwrap_or_else(|| match config.solver {
+            SolverKind::Astap => {
+                let mut runner = AstapCliRunner::new(
+                    binary_path.clone(),
+                    config.astap_db_directory.clone().unwrap_or_default(),
+                );
+                for (k, v) in &config.astap_extra_env {
+                    runner = runner.with_env(k, v);
+                }
+                Arc::new(runner)
+            }
+            SolverKind::SolveField => {
+                let mut runner =
+                    SolveFieldRunner::new(binary_path.clone(), config.solve_field_config.clone());
+                for (k, v) in &config.solve_field_extra_env {
+                    runner = runner.with_env(k, v);
+                }
+                Arc::new(runner)
             }
-            Arc::new(runner)
         });
 
         let semaphore = Arc::new(Semaphore::new(config.max_concurrency));
@@ -83,8 +102,16 @@ impl ServerBuilder {
             semaphore,
             default_solve_timeout: config.default_solve_timeout,
             max_solve_timeout: config.max_solve_timeout,
-            astap_binary_path: config.astap_binary_path.clone(),
-            astap_db_directory: config.astap_db_directory.clone(),
+            solver: config.solver,
+            binary_path,
+            astap_db_directory: match config.solver {
+                SolverKind::Astap => config.astap_db_directory.clone(),
+                SolverKind::SolveField => None,
+            },
+            solve_field_config: match config.solver {
+                SolverKind::Astap => None,
+                SolverKind::SolveField => config.solve_field_config.clone(),
+            },
         };
 
         let router = api::build_router(state);
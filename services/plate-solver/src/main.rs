@@ -30,7 +30,7 @@ struct Cli {
     /// Path to the JSON config file. Defaults to the platform
     /// config directory (e.g. `~/.config/rusty-photon/plate-solver.json`
     /// on Linux). There is no built-in default config: the file must
-    /// exist (`astap_binary_path` / `astap_db_directory` are mandatory).
+    /// exist (the selected solver's binary path has no default).
     #[arg(short, long)]
     config: Option<PathBuf>,
 
@@ -6,16 +6,17 @@
 //! end-to-end behavior (real `mock_astap` child + supervision arms) lives
 //! in `tests/supervision_integration.rs`.
 
-use super::wcs::read_wcs_sidecar;
-use super::{AstapRunner, RunnerError, SolveOutcome, SolveRequest};
-use crate::supervision::{spawn_with_deadline, SpawnOutcome};
+use super::{run_to_wcs_sidecar, AstapRunner, RunnerError, SolveOutcome, SolveRequest};
 use async_trait::async_trait;
 use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use tokio::process::Command;
 
 #[cfg(windows)]
-const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+pub(super) const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+
+/// `solver` string when ASTAP's sidecar carries no banner card.
+const ASTAP_BANNER: &str = "astap-cli";
 
 /// Wraps `astap_cli` invocations.
 pub struct AstapCliRunner {
@@ -92,30 +93,9 @@ impl AstapCliRunner {
 #[async_trait]
 impl AstapRunner for AstapCliRunner {
     async fn solve(&self, request: SolveRequest) -> Result<SolveOutcome, RunnerError> {
-        let timeout = request.timeout;
-        let fits_path = request.fits_path.clone();
         let cmd = self.build_command(&request);
-        let outcome = spawn_with_deadline(cmd, timeout).await?;
-        match outcome {
-            SpawnOutcome::Exited {
-                status,
-                stderr_tail,
-            } => {
-                if !status.success() {
-                    return Err(RunnerError::ExitStatus {
-                        status: status.code().unwrap_or(-1),
-                        stderr_tail,
-                    });
-                }
-                let wcs_path = wcs_sidecar_path(&fits_path);
-                if !wcs_path.exists() {
-                    return Err(RunnerError::NoWcs);
-                }
-                read_wcs_sidecar(&wcs_path).map_err(|e| RunnerError::MalformedWcs(e.to_string()))
-            }
-            SpawnOutcome::TimedOutTerminated => Err(RunnerError::TimedOutTerminated),
-            SpawnOutcome::TimedOutKilled => Err(RunnerError::TimedOutKilled),
-        }
+        let wcs_path = wcs_sidecar_path(&request.fits_path);
+        run_to_wcs_sidecar(cmd, request.timeout, &wcs_path, ASTAP_BANNER).await
     }
 }
 
@@ -126,8 +106,8 @@ impl AstapRunner for AstapCliRunner {
 /// like `.fits.fz` are **not supported** here — `with_extension`
 /// would yield `something.fits.wcs` rather than `something.wcs`.
 /// Real fixtures using `.fits.fz` haven't surfaced; revisit if they
-/// do.
-fn wcs_sidecar_path(fits_path: &Path) -> PathBuf {
+/// do. `SolveFieldRunner` names the same path via `--wcs`.
+pub(super) fn wcs_sidecar_path(fits_path: &Path) -> PathBuf {
     fits_path.with_extension("wcs")
 }
 
@@ -1,15 +1,21 @@
 //! Subprocess-runner abstraction.
 //!
 //! `AstapRunner` is the trait the HTTP handler calls. The real
-//! implementation is in `astap.rs`; tests use `mockall`'s generated mock.
+//! implementations are in `astap.rs` (ASTAP CLI) and `solve_field.rs`
+//! (astrometry.net `solve-field`), selected by the `solver` config
+//! field; tests use `mockall`'s generated mock.
 
+use self::wcs::read_wcs_sidecar_or;
+use crate::supervision::{spawn_with_deadline, SpawnOutcome};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use thiserror::Error;
+use tokio::process::Command;
 
 pub mod astap;
+pub mod solve_field;
 pub mod wcs;
 
 /// Inputs to one solve attempt. Hint fields use decimal degrees on the wire
@@ -57,10 +63,10 @@ pub struct WcsMatrix {
 
 #[derive(Debug, Error)]
 pub enum RunnerError {
-    #[error("ASTAP exited with code {status}: {stderr_tail}")]
+    #[error("solver exited with code {status}: {stderr_tail}")]
     ExitStatus { status: i32, stderr_tail: String },
 
-    #[error("ASTAP did not write a .wcs sidecar (exit was clean)")]
+    #[error("solver did not write a .wcs sidecar (exit was clean)")]
     NoWcs,
 
     #[error("malformed .wcs: {0}")]
@@ -81,3 +87,36 @@ pub enum RunnerError {
 pub trait AstapRunner: Send + Sync {
     async fn solve(&self, request: SolveRequest) -> Result<SolveOutcome, RunnerError>;
 }
+
+/// Run a solver child under the wall-clock deadline and parse the `.wcs`
+/// it leaves at `wcs_path`. Both runners funnel through here — they
+/// differ only in argv — so the exit-status / missing-sidecar /
+/// malformed-sidecar mapping is identical whichever solver is configured.
+/// `default_banner` is the `solver` string when the sidecar names none.
+pub(crate) async fn run_to_wcs_sidecar(
+    cmd: Command,
+    timeout: Duration,
+    wcs_path: &Path,
+    default_banner: &str,
+) -> Result<SolveOutcome, RunnerError> {
+    match spawn_with_deadline(cmd, timeout).await? {
+        SpawnOutcome::Exited {
+            status,
+            stderr_tail,
+        } => {
+            if !status.success() {
+                return Err(RunnerError::ExitStatus {
+                    status: status.code().unwrap_or(-1),
+                    stderr_tail,
+                });
+            }
+            if !wcs_path.exists() {
+                return Err(RunnerError::NoWcs);
+            }
+            read_wcs_sidecar_or(wcs_path, default_banner)
+                .map_err(|e| RunnerError::MalformedWcs(e.to_string()))
+        }
+        SpawnOutcome::TimedOutTerminated => Err(RunnerError::TimedOutTerminated),
+        SpawnOutcome::TimedOutKilled => Err(RunnerError::TimedOutKilled),
+    }
+}
@@ -0,0 +1,263 @@
+//! `SolveFieldRunner`, the astrometry.net implementation of the
+//! `AstapRunner` trait: builds a `solve-field` `Command`, spawns it under
+//! the supervision module, and parses the `.wcs` it writes.
+//!
+//! `solve-field` scatters half a dozen outputs next to its input by
+//! default (`.axy`, `.corr`, `.match`, `.rdls`, `.solved`, `.new`,
+//! plots). The runner keeps only the one the HTTP contract needs: the
+//! `.wcs`, pinned to the same path ASTAP uses via `--wcs`. The rest are
+//! switched off (`none`) or sent to a temp file, so rp's image
+//! directory stays as clean under either solver. The `.corr` match
+//! table is dropped deliberately: the contract has no match-quality
+//! fields for it to feed (docs/services/plate-solver.md).
+//!
+//! The argv-mapping behavior is unit-tested in this file. The
+//! spawn-based end-to-end behavior (real `mock_solve_field` child)
+//! lives in `tests/runner_integration.rs`.
+
+use super::astap::wcs_sidecar_path;
+use super::{run_to_wcs_sidecar, AstapRunner, RunnerError, SolveOutcome, SolveRequest};
+use async_trait::async_trait;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::process::Command;
+
+/// `solver` string when the sidecar carries no banner card.
+const SOLVE_FIELD_BANNER: &str = "solve-field";
+
+/// Bounds of the `--scale-low` / `--scale-high` band, as multiples of
+/// `fov_hint_deg`. The hint is the image *height* (ASTAP's `-fov`
+/// convention) while `solve-field`'s `degwidth` unit is the *width*;
+/// without the aspect ratio the band spans either orientation up to
+/// 2:1, which still prunes the index search far below a blind solve.
+const SCALE_LOW_FACTOR: f64 = 0.5;
+const SCALE_HIGH_FACTOR: f64 = 2.0;
+
+/// Wraps `solve-field` invocations.
+pub struct SolveFieldRunner {
+    binary_path: PathBuf,
+    config_path: Option<PathBuf>,
+    extra_env: Vec<(String, String)>,
+}
+
+impl SolveFieldRunner {
+    /// `config_path` is passed as `--config`: the `astrometry.cfg` that
+    /// names the index files. `None` leaves `solve-field` on its
+    /// compiled-in default.
+    #[must_use]
+    pub const fn new(binary_path: PathBuf, config_path: Option<PathBuf>) -> Self {
+        Self {
+            binary_path,
+            config_path,
+            extra_env: Vec::new(),
+        }
+    }
+
+    /// Add an environment variable to set on every spawned
+    /// `solve-field` child. Same role as `AstapCliRunner::with_env`;
+    /// integration tests drive `mock_solve_field`'s
+    /// `MOCK_SOLVE_FIELD_MODE` through it.
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.extra_env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Build the `Command` argv from a `SolveRequest` without spawning.
+    /// Pure function; exercised by argv-mapping unit tests.
+    #[must_use]
+    pub fn build_command(&self, req: &SolveRequest) -> Command {
+        let mut cmd = Command::new(&self.binary_path);
+        cmd.arg(&req.fits_path);
+        cmd.args(["--overwrite", "--no-plots", "--temp-axy"]);
+        // CRPIX at the image center makes CRVAL the center's RA/Dec,
+        // which is what `ra_center` / `dec_center` promise.
+        cmd.arg("--crpix-center");
+        cmd.arg("--wcs").arg(wcs_sidecar_path(&req.fits_path));
+        for output in [
+            "--new-fits",
+            "--index-xyls",
+            "--rdls",
+            "--match",
+            "--corr",
+            "--solved",
+        ] {
+            cmd.arg(output).arg("none");
+        }
+        if let Some(config) = &self.config_path {
+            cmd.arg("--config").arg(config);
+        }
+        // solve-field's own CPU budget, so it gives up on its own before
+        // the wrapper's deadline has to signal it. Whole seconds, at
+        // least one.
+        cmd.arg("--cpulimit")
+            .arg(req.timeout.as_secs().max(1).to_string());
+
+        // `--ra`/`--dec` only work as a pair, and `--radius` only
+        // narrows a search around them; a lone half of the pair (or a
+        // radius without a center) produces no flag rather than a
+        // solve-field usage error.
+        if let (Some(ra_deg), Some(dec_deg)) = (req.ra_hint, req.dec_hint) {
+            // Wire format and solve-field both use decimal degrees.
+            cmd.arg("--ra").arg(format!("{ra_deg:.10}"));
+            cmd.arg("--dec").arg(format!("{dec_deg:.10}"));
+            if let Some(r) = req.search_radius_deg {
+                cmd.arg("--radius").arg(format!("{r:.10}"));
+            }
+        }
+        if let Some(fov) = req.fov_hint_deg {
+            cmd.args(["--scale-units", "degwidth"]);
+            cmd.arg("--scale-low")
+                .arg(format!("{:.10}", fov * SCALE_LOW_FACTOR));
+            cmd.arg("--scale-high")
+                .arg(format!("{:.10}", fov * SCALE_HIGH_FACTOR));
+        }
+
+        cmd.stdout(Stdio::null());
+        cmd.stderr(Stdio::piped());
+
+        for (k, v) in &self.extra_env {
+            cmd.env(k, v);
+        }
+
+        #[cfg(windows)]
+        {
+            cmd.creation_flags(super::astap::CREATE_NEW_PROCESS_GROUP);
+        }
+
+        cmd
+    }
+}
+
+#[async_trait]
+impl AstapRunner for SolveFieldRunner {
+    async fn solve(&self, request: SolveRequest) -> Result<SolveOutcome, RunnerError> {
+        let cmd = self.build_command(&request);
+        let wcs_path = wcs_sidecar_path(&request.fits_path);
+        run_to_wcs_sidecar(cmd, request.timeout, &wcs_path, SOLVE_FIELD_BANNER).await
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn req() -> SolveRequest {
+        SolveRequest {
+            fits_path: PathBuf::from("/data/lights/m31.fits"),
+            ra_hint: None,
+            dec_hint: None,
+            fov_hint_deg: None,
+            search_radius_deg: None,
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    fn argv(cmd: &Command) -> Vec<String> {
+        cmd.as_std()
+            .get_args()
+            .map(|s| s.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    fn flag_value(argv: &[String], flag: &str) -> Option<f64> {
+        let idx = argv.iter().position(|a| a == flag)?;
+        argv[idx + 1].parse().ok()
+    }
+
+    fn runner() -> SolveFieldRunner {
+        SolveFieldRunner::new(PathBuf::from("/usr/bin/solve-field"), None)
+    }
+
+    #[test]
+    fn no_hints_produces_minimal_argv() {
+        let cmd = runner().build_command(&req());
+        assert_eq!(
+            argv(&cmd),
+            vec![
+                "/data/lights/m31.fits",
+                "--overwrite",
+                "--no-plots",
+                "--temp-axy",
+                "--crpix-center",
+                "--wcs",
+                "/data/lights/m31.wcs",
+                "--new-fits",
+                "none",
+                "--index-xyls",
+                "none",
+                "--rdls",
+                "none",
+                "--match",
+                "none",
+                "--corr",
+                "none",
+                "--solved",
+                "none",
+                "--cpulimit",
+                "30",
+            ]
+        );
+    }
+
+    #[test]
+    fn config_path_passes_as_config_flag() {
+        let runner = SolveFieldRunner::new(
+            PathBuf::from("/usr/bin/solve-field"),
+            Some(PathBuf::from("/etc/astrometry.cfg")),
+        );
+        let argv = argv(&runner.build_command(&req()));
+        let idx = argv.iter().position(|a| a == "--config").unwrap();
+        assert_eq!(argv[idx + 1], "/etc/astrometry.cfg");
+    }
+
+    #[test]
+    fn cpulimit_rounds_down_to_whole_seconds_but_never_zero() {
+        let mut r = req();
+        r.timeout = Duration::from_millis(2500);
+        assert_eq!(
+            flag_value(&argv(&runner().build_command(&r)), "--cpulimit"),
+            Some(2.0)
+        );
+        r.timeout = Duration::from_millis(200);
+        assert_eq!(
+            flag_value(&argv(&runner().build_command(&r)), "--cpulimit"),
+            Some(1.0)
+        );
+    }
+
+    #[test]
+    fn pointing_hints_pass_through_in_degrees() {
+        let mut r = req();
+        r.ra_hint = Some(10.6848);
+        r.dec_hint = Some(41.2690);
+        r.search_radius_deg = Some(5.0);
+        let argv = argv(&runner().build_command(&r));
+        assert!((flag_value(&argv, "--ra").unwrap() - 10.6848).abs() < 1e-9);
+        assert!((flag_value(&argv, "--dec").unwrap() - 41.2690).abs() < 1e-9);
+        assert!((flag_value(&argv, "--radius").unwrap() - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn half_a_pointing_hint_produces_no_flags() {
+        let mut r = req();
+        r.ra_hint = Some(10.6848);
+        r.search_radius_deg = Some(5.0);
+        let argv = argv(&runner().build_command(&r));
+        assert!(!argv.contains(&"--ra".to_string()));
+        assert!(!argv.contains(&"--dec".to_string()));
+        assert!(!argv.contains(&"--radius".to_string()));
+    }
+
+    #[test]
+    fn fov_hint_maps_to_a_degwidth_scale_band() {
+        let mut r = req();
+        r.fov_hint_deg = Some(1.5);
+        let argv = argv(&runner().build_command(&r));
+        let idx = argv.iter().position(|a| a == "--scale-units").unwrap();
+        assert_eq!(argv[idx + 1], "degwidth");
+        assert!((flag_value(&argv, "--scale-low").unwrap() - 0.75).abs() < 1e-9);
+        assert!((flag_value(&argv, "--scale-high").unwrap() - 3.0).abs() < 1e-9);
+    }
+}
@@ -1,10 +1,11 @@
-//! ASTAP `.wcs` sidecar parser.
+//! `.wcs` sidecar parser.
 //!
 //! ASTAP writes a `.wcs` sidecar next to each successfully solved FITS,
-//! containing the World Coordinate System keywords as a FITS primary
-//! HDU header (no data block — `NAXIS = 0` or all `NAXISn = 0`). Parsing
-//! goes through [`fitsrs`] for the FITS-card layer and
-//! [`wcs::WCSParams`] for the keyword-to-field mapping. The wrapper
+//! and `solve-field` writes one wherever `--wcs` points it; both contain
+//! the World Coordinate System keywords as a FITS primary HDU header (no
+//! data block — `NAXIS = 0` or all `NAXISn = 0`). Parsing goes through
+//! [`fitsrs`] for the FITS-card layer and [`wcs::WCSParams`] for the
+//! keyword-to-field mapping. The wrapper
 //! accepts either CDELT/CROTA or CD-matrix WCS conventions for the
 //! pixel-scale and rotation response fields; CRVAL1/CRVAL2 are always
 //! required.
@@ -32,6 +33,15 @@ use wcs::WCSParams;
 
 const FITS_BLOCK: usize = 2880;
 
+/// Banner reported when the sidecar names no solver and the caller
+/// supplied no other default.
+const DEFAULT_BANNER: &str = "astap-cli";
+
+/// Upper-cased markers that identify a solver's HISTORY / COMMENT card:
+/// ASTAP's version comment, and astrometry.net's "Created by the
+/// Astrometry.net suite." history line.
+const BANNER_MARKERS: [&str; 2] = ["ASTAP", "ASTROMETRY.NET"];
+
 #[derive(Debug, Error)]
 pub enum WcsParseError {
     #[error("io: {0}")]
@@ -52,12 +62,30 @@ pub enum WcsParseError {
 /// HISTORY / COMMENT cards, and — when the sidecar carries a complete
 /// CRPIX + CD set — the full WCS linear mapping.
 pub fn read_wcs_sidecar(path: &Path) -> Result<SolveOutcome, WcsParseError> {
+    read_wcs_sidecar_or(path, DEFAULT_BANNER)
+}
+
+/// [`read_wcs_sidecar`] with the banner to report when the sidecar
+/// carries none.
+pub fn read_wcs_sidecar_or(
+    path: &Path,
+    default_banner: &str,
+) -> Result<SolveOutcome, WcsParseError> {
     let bytes = std::fs::read(path)?;
-    parse_wcs_bytes(&bytes)
+    parse_wcs_bytes_or(&bytes, default_banner)
 }
 
 /// Pure-function variant for unit testing.
 pub fn parse_wcs_bytes(bytes: &[u8]) -> Result<SolveOutcome, WcsParseError> {
+    parse_wcs_bytes_or(bytes, DEFAULT_BANNER)
+}
+
+/// [`parse_wcs_bytes`] with the banner to report when the sidecar
+/// carries none.
+pub fn parse_wcs_bytes_or(
+    bytes: &[u8],
+    default_banner: &str,
+) -> Result<SolveOutcome, WcsParseError> {
     let normalized = pad_to_fits_block(bytes);
     let mut hdu_list = Fits::from_reader(Cursor::new(&*normalized));
     let hdu = hdu_list
@@ -102,7 +130,7 @@ pub fn parse_wcs_bytes(bytes: &[u8]) -> Result<SolveOutcome, WcsParseError> {
     WCSParams::deserialize(header.into_deserializer())
         .map_err(|e| WcsParseError::Malformed(format!("WCS deserialization failed: {e}")))?;
 
-    let solver = find_banner(header).unwrap_or_else(|| "astap-cli".to_string());
+    let solver = find_banner(header).unwrap_or_else(|| default_banner.to_string());
 
     Ok(SolveOutcome {
         ra_center: crval1,
@@ -207,8 +235,8 @@ fn pad_to_fits_block(bytes: &[u8]) -> std::borrow::Cow<'_, [u8]> {
     std::borrow::Cow::Owned(padded)
 }
 
-/// Walk the header's cards looking for a HISTORY or COMMENT mentioning
-/// "ASTAP". The banner is informational; the HTTP contract falls back
+/// Walk the header's cards looking for a HISTORY or COMMENT naming a
+/// solver (see [`BANNER_MARKERS`]). The banner is informational; the HTTP contract falls back
 /// to a default when none is found.
 fn find_banner<X>(header: &Header<X>) -> Option<String>
 where
@@ -219,7 +247,8 @@ where
             Card::History(s) | Card::Comment(s) => s.as_str(),
             _ => continue,
         };
-        if text.to_ascii_uppercase().contains("ASTAP") {
+        let upper = text.to_ascii_uppercase();
+        if BANNER_MARKERS.iter().any(|m| upper.contains(m)) {
             return Some(text.trim().to_string());
         }
     }
@@ -766,6 +795,44 @@ mod tests {
         assert_eq!(out.solver, "astap-cli");
     }
 
+    #[test]
+    fn astrometry_net_history_becomes_solver_banner() {
+        // solve-field's sidecar: CD matrix only, no CDELT/CROTA2, and a
+        // HISTORY card naming the suite.
+        let bytes = build_wcs(&[
+            ("CRVAL1", "10.6848"),
+            ("CRVAL2", "41.2690"),
+            ("CRPIX1", "512.0"),
+            ("CRPIX2", "384.0"),
+            ("CD1_1", "-0.000284972"),
+            ("CD1_2", "-0.000062134"),
+            ("CD2_1", "-0.000062134"),
+            ("CD2_2", "0.000284972"),
+        ]);
+        let mut spliced = bytes;
+        let end_idx = (0..spliced.len())
+            .step_by(80)
+            .find(|i| &spliced[*i..*i + 8] == b"END     ")
+            .expect("END card not found");
+        let history = format!("{:<80}", "HISTORY Created by the Astrometry.net suite.");
+        spliced.splice(end_idx..end_idx, history.into_bytes());
+        let out = parse_wcs_bytes_or(&spliced, "solve-field").unwrap();
+        assert_eq!(out.solver, "Created by the Astrometry.net suite.");
+        assert!((out.pixel_scale_arcsec - 1.05).abs() < 1e-2);
+        assert!(out.wcs_matrix.is_some());
+    }
+
+    #[test]
+    fn caller_default_banner_applies_when_none_found() {
+        let bytes = build_wcs(&[
+            ("CRVAL1", "10.6848"),
+            ("CRVAL2", "41.2690"),
+            ("CDELT1", "-0.000291667"),
+        ]);
+        let out = parse_wcs_bytes_or(&bytes, "solve-field").unwrap();
+        assert_eq!(out.solver, "solve-field");
+    }
+
     #[test]
     fn non_finite_crval1_returns_non_numeric() {
         // Float overflow → infinity. coerce_float's finite guard catches
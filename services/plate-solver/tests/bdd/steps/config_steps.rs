@@ -78,6 +78,23 @@ async fn given_config_with_mock_astap(world: &mut PlateSolverWorld) {
         .insert("astap_binary_path".into(), bin.to_string_lossy().into());
 }
 
+#[given(expr = "a config selecting solver {string}")]
+async fn given_config_selecting_solver(world: &mut PlateSolverWorld, solver: String) {
+    world.pending_config.insert("solver".into(), solver.into());
+}
+
+#[given("a valid solve_field_binary_path")]
+async fn given_valid_solve_field_binary_path(world: &mut PlateSolverWorld) {
+    // Startup validation and /health only stat the binary, so any
+    // executable stands in; no solve is issued.
+    let dir = world.temp_dir_path();
+    let bin = copy_mock_astap(&dir);
+    world.pending_config.insert(
+        "solve_field_binary_path".into(),
+        bin.to_string_lossy().into(),
+    );
+}
+
 #[when("the wrapper starts")]
 async fn when_wrapper_starts(world: &mut PlateSolverWorld) {
     // Inject a server block with port: 0 so concurrent test runs don't
@@ -6,7 +6,8 @@ Feature: Configuration validation at startup
   rather than masking it with a silent retry.
 
   Required fields exit on absence — `astap_binary_path` and
-  `astap_db_directory` have no implicit defaults. The validation rules
+  `astap_db_directory` (or, with `solver` set to `solve_field`,
+  `solve_field_binary_path`) have no implicit defaults. The validation rules
   are listed in `docs/services/plate-solver.md` §"Configuration
   Validation at Startup".
 
@@ -51,3 +52,19 @@ Feature: Configuration validation at startup
     When the wrapper starts
     Then the wrapper prints bound_addr to stdout
     And the wrapper /health returns 200
+
+  Scenario: solve_field solver without solve_field_binary_path rejects the config
+    Given a config selecting solver "solve_field"
+    And a valid astap_binary_path
+    And a valid astap_db_directory
+    When the wrapper starts
+    Then the wrapper exits non-zero
+    And the wrapper stderr names "solve_field_binary_path"
+    And the wrapper stderr references the README
+
+  Scenario: Valid solve_field config is accepted without ASTAP paths
+    Given a config selecting solver "solve_field"
+    And a valid solve_field_binary_path
+    When the wrapper starts
+    Then the wrapper prints bound_addr to stdout
+    And the wrapper /health returns 200
@@ -1,19 +1,20 @@
-//! End-to-end integration tests for `AstapCliRunner::solve()`.
+//! End-to-end integration tests for `AstapCliRunner::solve()` and
+//! `SolveFieldRunner::solve()`.
 //!
 //! Each test drives the full solve pipeline (`build_command` → spawn under
-//! supervision → parse `.wcs`) against `mock_astap` configured for a
-//! specific failure mode. These exercise the `solve()` orchestrator's
+//! supervision → parse `.wcs`) against `mock_astap` or `mock_solve_field`
+//! configured for a specific failure mode. These exercise the `solve()` orchestrator's
 //! branches that unit tests can't reach without spawning a subprocess
 //! (`ExitStatus`, `NoWcs`, `MalformedWcs`, success).
 //!
-//! `MOCK_ASTAP_MODE` is set per-test on the spawned child via the
-//! `AstapCliRunner::with_env` builder, not via `std::env::set_var`,
-//! so concurrent tests in the same process don't race.
+//! `MOCK_ASTAP_MODE` / `MOCK_SOLVE_FIELD_MODE` is set per-test on the
+//! spawned child via the runners' `with_env` builders, not via
+//! `std::env::set_var`, so concurrent tests in the same process don't race.
 
 #![allow(clippy::unwrap_used, clippy::expect_used, clippy::panic)]
 
 use plate_solver::runner::wcs::read_wcs_sidecar;
-use plate_solver::{AstapCliRunner, AstapRunner, RunnerError, SolveRequest};
+use plate_solver::{AstapCliRunner, AstapRunner, RunnerError, SolveFieldRunner, SolveRequest};
 use std::path::PathBuf;
 use std::time::Duration;
 use tempfile::TempDir;
@@ -38,6 +39,25 @@ fn mock_astap_path() -> PathBuf {
     )
 }
 
+fn mock_solve_field_path() -> PathBuf {
+    if let Ok(p) = std::env::var("MOCK_SOLVE_FIELD_BINARY") {
+        let path = PathBuf::from(p);
+        if path.exists() {
+            return path;
+        }
+    }
+    if let Some(p) = option_env!("CARGO_BIN_EXE_mock_solve_field") {
+        let path = PathBuf::from(p);
+        if path.exists() {
+            return path;
+        }
+    }
+    panic!(
+        "mock_solve_field binary not found. Tried MOCK_SOLVE_FIELD_BINARY env \
+         var, then CARGO_BIN_EXE_mock_solve_field. Run `cargo build --tests -p plate-solver`."
+    )
+}
+
 fn runner(mode: &str) -> (AstapCliRunner, TempDir) {
     let dir = TempDir::new().expect("tempdir");
     let runner = AstapCliRunner::new(mock_astap_path(), dir.path().to_path_buf())
@@ -45,6 +65,13 @@ fn runner(mode: &str) -> (AstapCliRunner, TempDir) {
     (runner, dir)
 }
 
+fn solve_field_runner(mode: &str) -> (SolveFieldRunner, TempDir) {
+    let dir = TempDir::new().expect("tempdir");
+    let runner = SolveFieldRunner::new(mock_solve_field_path(), None)
+        .with_env("MOCK_SOLVE_FIELD_MODE", mode);
+    (runner, dir)
+}
+
 const fn req(fits_path: PathBuf) -> SolveRequest {
     SolveRequest {
         fits_path,
@@ -138,3 +165,99 @@ async fn malformed_wcs_maps_to_malformed_wcs_error() {
         other => panic!("expected MalformedWcs, got {other:?}"),
     }
 }
+
+#[tokio::test]
+async fn solve_field_happy_path_returns_solve_outcome() {
+    let (runner, dir) = solve_field_runner("normal");
+    let fits = dir.path().join("test.fits");
+    fs::write(&fits, b"placeholder").await.unwrap();
+
+    let outcome = runner.solve(req(fits.clone())).await.unwrap();
+
+    // CD-matrix-only sidecar: scale and rotation derive from the matrix
+    // and land on the same numbers mock_astap's CDELT/CROTA2 give.
+    assert!((outcome.ra_center - 10.6848).abs() < 1e-6);
+    assert!((outcome.dec_center - 41.2690).abs() < 1e-6);
+    assert!((outcome.pixel_scale_arcsec - 1.05).abs() < 1e-2);
+    assert!(
+        outcome.solver.contains("Astrometry.net"),
+        "{}",
+        outcome.solver
+    );
+    let matrix = outcome.wcs_matrix.unwrap();
+    assert!((matrix.crpix1 - 512.0).abs() < 1e-9);
+    assert!((matrix.cd2_2 - 0.000284972).abs() < 1e-12);
+
+    // The runner pinned the sidecar to ASTAP's path via --wcs.
+    assert!(fits.with_extension("wcs").exists());
+}
+
+#[tokio::test]
+async fn solve_field_argv_reaches_the_child() {
+    let (runner, dir) = solve_field_runner("normal");
+    let argv_out = dir.path().join("argv.txt");
+    let runner = runner.with_env("MOCK_SOLVE_FIELD_ARGV_OUT", argv_out.to_string_lossy());
+    let fits = dir.path().join("test.fits");
+    fs::write(&fits, b"placeholder").await.unwrap();
+
+    let mut request = req(fits);
+    request.ra_hint = Some(10.6848);
+    request.dec_hint = Some(41.2690);
+    request.fov_hint_deg = Some(1.5);
+    runner.solve(request).await.unwrap();
+
+    let argv = fs::read_to_string(&argv_out).await.unwrap();
+    for flag in ["--ra", "--dec", "--scale-low", "--scale-high", "--wcs"] {
+        assert!(
+            argv.lines().any(|l| l == flag),
+            "{flag} missing from {argv}"
+        );
+    }
+}
+
+#[tokio::test]
+async fn solve_field_exit_failure_maps_to_exit_status_error() {
+    let (runner, dir) = solve_field_runner("exit_failure");
+    let fits = dir.path().join("test.fits");
+    fs::write(&fits, b"placeholder").await.unwrap();
+
+    let err = runner.solve(req(fits)).await.unwrap_err();
+    match err {
+        RunnerError::ExitStatus {
+            status,
+            stderr_tail,
+        } => {
+            assert_eq!(status, 1);
+            assert!(stderr_tail.contains("simulated solve failure"));
+        }
+        other => panic!("expected ExitStatus, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn solve_field_unsolved_maps_to_no_wcs_error() {
+    let (runner, dir) = solve_field_runner("no_wcs");
+    let fits = dir.path().join("test.fits");
+    fs::write(&fits, b"placeholder").await.unwrap();
+
+    let err = runner.solve(req(fits)).await.unwrap_err();
+    assert!(
+        matches!(err, RunnerError::NoWcs),
+        "expected NoWcs, got {err:?}"
+    );
+}
+
+#[tokio::test]
+async fn solve_field_hang_times_out() {
+    let (runner, dir) = solve_field_runner("hang");
+    let fits = dir.path().join("test.fits");
+    fs::write(&fits, b"placeholder").await.unwrap();
+
+    let mut request = req(fits);
+    request.timeout = Duration::from_millis(300);
+    let err = runner.solve(request).await.unwrap_err();
+    assert!(
+        matches!(err, RunnerError::TimedOutTerminated),
+        "expected TimedOutTerminated, got {err:?}"
+    );
+}
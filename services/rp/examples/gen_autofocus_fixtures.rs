@@ -209,6 +209,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             usize::try_from(WIDTH)?,
             usize::try_from(HEIGHT)?,
             &doc_id,
+            &[],
+            rp::config::FitsCompression::None,
         )
         .await?;
         println!("  {}  HFR={:.3} px  → {}", name, hfr, path.display());
@@ -0,0 +1,341 @@
+//! Background Solving (rp.md § Background Solving): every `Light`
+//! frame is plate-solved off the capture path once its
+//! `exposure_complete` fires. The solution lands on the exposure
+//! document as a `plate_solve` section carrying the FITS WCS keywords,
+//! and a solved center that strays from the frame's target by more
+//! than `drift_threshold_arcsec` publishes `pointing_drift` — an
+//! **event, never an action**: the orchestrator decides whether to
+//! re-center.
+//!
+//! The listener only forwards document ids into a bounded queue; one
+//! worker drains it, so solves run one at a time (the plate-solver
+//! wrapper is single-flight by default) and a burst of short frames
+//! drops the overflow instead of stacking solves behind each other.
+
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use crate::config::BackgroundSolveConfig;
+use crate::mcp::internals::{DoPlateSolveInput, DoPlateSolveOutput};
+use crate::mcp::McpHandler;
+use crate::persistence::ExposureDocument;
+
+/// Frames waiting for the worker. Deep enough to ride out one slow
+/// solve behind a run of short subs; anything past it is skipped.
+const QUEUE_DEPTH: usize = 16;
+
+/// The frame's target in solver units, when the document resolved one
+/// against the target store.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetCoord {
+    pub slug: String,
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+}
+
+/// What a background solve of one document needs from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameHints {
+    /// Doubles as the pointing hint and the drift reference.
+    pub target: Option<TargetCoord>,
+    /// The frame's vertical FOV, ASTAP's `-fov` convention.
+    pub fov_hint_deg: Option<f64>,
+}
+
+/// `None` for anything but a `Light` frame: calibration frames have
+/// no sky to solve, and untyped captures are focus and centering
+/// frames whose callers solve them when they need to.
+#[must_use]
+pub fn frame_hints(doc: &ExposureDocument) -> Option<FrameHints> {
+    if doc.frame_type != Some(rp_vocabulary::FrameType::Light) {
+        return None;
+    }
+    let target = doc.target.as_ref().and_then(|t| {
+        Some(TargetCoord {
+            slug: t.slug.clone(),
+            ra_deg: t.ra_hours? * 15.0,
+            dec_deg: t.dec_degrees?,
+        })
+    });
+    Some(FrameHints {
+        target,
+        fov_hint_deg: doc.optics.as_ref().map(|o| o.fov_height_deg),
+    })
+}
+
+/// The FITS WCS header keywords of a solution, ready to merge into a
+/// header. `None` when the wrapper returned no CD matrix — CRVAL alone
+/// is not a usable WCS. The projection is TAN, which is what the
+/// solver's linear mapping describes.
+#[must_use]
+pub fn wcs_keywords(out: &DoPlateSolveOutput) -> Option<Value> {
+    let m = out.wcs_matrix?;
+    Some(serde_json::json!({
+        "CTYPE1": "RA---TAN",
+        "CTYPE2": "DEC--TAN",
+        "CRVAL1": out.ra_center,
+        "CRVAL2": out.dec_center,
+        "CRPIX1": m.crpix1,
+        "CRPIX2": m.crpix2,
+        "CD1_1": m.cd1_1,
+        "CD1_2": m.cd1_2,
+        "CD2_1": m.cd2_1,
+        "CD2_2": m.cd2_2,
+    }))
+}
+
+/// The `plate_solve` section for a solved frame. `drift` is `null`
+/// when the frame had no target to measure against.
+#[must_use]
+pub fn solved_section(
+    out: &DoPlateSolveOutput,
+    target: Option<&TargetCoord>,
+    threshold_arcsec: f64,
+) -> Value {
+    let drift = target.map(|t| {
+        let drift_arcsec =
+            crate::imaging::haversine_arcsec(out.ra_center, out.dec_center, t.ra_deg, t.dec_deg);
+        serde_json::json!({
+            "target": t.slug,
+            "target_ra_deg": t.ra_deg,
+            "target_dec_deg": t.dec_deg,
+            "drift_arcsec": drift_arcsec,
+            "threshold_arcsec": threshold_arcsec,
+            "exceeded": drift_arcsec > threshold_arcsec,
+        })
+    });
+    serde_json::json!({
+        "status": "solved",
+        "ra_center": out.ra_center,
+        "dec_center": out.dec_center,
+        "pixel_scale_arcsec": out.pixel_scale_arcsec,
+        "rotation_deg": out.rotation_deg,
+        "solver": out.solver,
+        "wcs_keywords": wcs_keywords(out),
+        "drift": drift,
+    })
+}
+
+/// Spawn the listener and its worker. Both end when the event bus
+/// closes.
+pub fn spawn(handler: McpHandler, config: BackgroundSolveConfig) -> tokio::task::JoinHandle<()> {
+    let mut bus_rx = handler.event_bus.subscribe();
+    let (tx, mut rx) = mpsc::channel::<String>(QUEUE_DEPTH);
+
+    tokio::spawn(async move {
+        debug!("background solver started");
+        while let Some(document_id) = rx.recv().await {
+            solve_one(&handler, config, &document_id).await;
+        }
+    });
+
+    tokio::spawn(async move {
+        loop {
+            match bus_rx.recv().await {
+                Ok(envelope) if envelope.event == "exposure_complete" => {
+                    let Some(document_id) = envelope.payload["document_id"].as_str() else {
+                        continue;
+                    };
+                    if tx.try_send(document_id.to_string()).is_err() {
+                        debug!(document_id, "background solve queue full; frame skipped");
+                    }
+                }
+                Ok(_) => {}
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                    debug!("background solver lagged {n} events");
+                }
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
+
+/// Solve one document and record the outcome. Every failure is
+/// logged and recorded, never raised: a frame that does not solve is
+/// still a good frame.
+async fn solve_one(handler: &McpHandler, config: BackgroundSolveConfig, document_id: &str) {
+    let Some(doc) = handler.image_cache.resolve_document(document_id).await else {
+        debug!(document_id, "background solve: document not found");
+        return;
+    };
+    let Some(hints) = frame_hints(&doc) else {
+        return;
+    };
+
+    let input = DoPlateSolveInput {
+        document_id: Some(document_id),
+        image_path: None,
+        pointing_hint: hints.target.as_ref().map(|t| (t.ra_deg, t.dec_deg)),
+        use_mount_hints: false,
+        fov_hint_deg: hints.fov_hint_deg,
+        search_radius_deg: None,
+        timeout: config.timeout,
+    };
+    let threshold = config.drift_threshold_arcsec.value();
+    let section = match handler.do_plate_solve(input).await {
+        Ok(out) => {
+            let section = solved_section(&out, hints.target.as_ref(), threshold);
+            if section["drift"]["exceeded"].as_bool() == Some(true) {
+                debug!(document_id, drift = %section["drift"]["drift_arcsec"], "pointing drift");
+                handler.event_bus.emit(
+                    "pointing_drift",
+                    serde_json::json!({
+                        "document_id": document_id,
+                        "camera_id": doc.camera_id,
+                        "target": section["drift"]["target"],
+                        "drift_arcsec": section["drift"]["drift_arcsec"],
+                        "threshold_arcsec": threshold,
+                        "ra_center": out.ra_center,
+                        "dec_center": out.dec_center,
+                    }),
+                );
+            }
+            section
+        }
+        Err(e) => {
+            debug!(document_id, error = %e, "background solve failed");
+            serde_json::json!({ "status": "failed", "error": e })
+        }
+    };
+    if let Err(e) = handler
+        .image_cache
+        .put_section(document_id, "plate_solve", section)
+        .await
+    {
+        debug!(document_id, error = %e, "failed to persist plate_solve section");
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::persistence::{ExposureTarget, Optics};
+
+    fn doc(frame_type: Option<rp_vocabulary::FrameType>) -> ExposureDocument {
+        serde_json::from_value(serde_json::json!({
+            "id": "abc",
+            "captured_at": "2026-10-16T00:00:00Z",
+            "file_path": "/data/abc.fits",
+            "width": 100,
+            "height": 100,
+            "frame_type": frame_type,
+        }))
+        .unwrap()
+    }
+
+    fn m31() -> ExposureTarget {
+        ExposureTarget {
+            slug: "m31".to_string(),
+            display_name: Some("M31".to_string()),
+            ra_hours: Some(0.712_3),
+            dec_degrees: Some(41.269),
+            panel: None,
+        }
+    }
+
+    fn solution(ra: f64, dec: f64, matrix: bool) -> DoPlateSolveOutput {
+        DoPlateSolveOutput {
+            ra_center: ra,
+            dec_center: dec,
+            pixel_scale_arcsec: 1.05,
+            rotation_deg: 12.0,
+            solver: "astap-2026.05.03".to_string(),
+            wcs_matrix: matrix.then_some(rp_plate_solver::WcsMatrix {
+                crpix1: 2048.5,
+                crpix2: 1536.5,
+                cd1_1: -2.9e-4,
+                cd1_2: 1.0e-6,
+                cd2_1: 1.0e-6,
+                cd2_2: 2.9e-4,
+            }),
+        }
+    }
+
+    #[test]
+    fn only_light_frames_are_solved() {
+        use rp_vocabulary::FrameType;
+        for frame_type in [
+            None,
+            Some(FrameType::Dark),
+            Some(FrameType::Flat),
+            Some(FrameType::Bias),
+        ] {
+            assert!(frame_hints(&doc(frame_type)).is_none(), "{frame_type:?}");
+        }
+        assert!(frame_hints(&doc(Some(FrameType::Light))).is_some());
+    }
+
+    #[test]
+    fn hints_come_from_the_target_and_optics() {
+        let mut light = doc(Some(rp_vocabulary::FrameType::Light));
+        light.target = Some(m31());
+        light.optics = Some(Optics {
+            focal_length_mm: 530.0,
+            pixel_size_x_um: 3.76,
+            pixel_size_y_um: 3.76,
+            sensor_width_px: 6248,
+            sensor_height_px: 4176,
+            pixel_scale_x_arcsec_per_pixel: 1.46,
+            pixel_scale_y_arcsec_per_pixel: 1.46,
+            fov_width_deg: 2.54,
+            fov_height_deg: 1.7,
+        });
+        let hints = frame_hints(&light).unwrap();
+        let target = hints.target.unwrap();
+        assert_eq!(target.slug, "m31");
+        assert!((target.ra_deg - 10.684_5).abs() < 1e-9);
+        assert!((target.dec_deg - 41.269).abs() < 1e-9);
+        assert_eq!(hints.fov_hint_deg, Some(1.7));
+    }
+
+    #[test]
+    fn a_reserved_slug_without_coordinates_is_no_target() {
+        let mut light = doc(Some(rp_vocabulary::FrameType::Light));
+        light.target = Some(ExposureTarget {
+            slug: "m31".to_string(),
+            display_name: None,
+            ra_hours: None,
+            dec_degrees: None,
+            panel: None,
+        });
+        assert_eq!(frame_hints(&light).unwrap().target, None);
+    }
+
+    #[test]
+    fn wcs_keywords_need_the_cd_matrix() {
+        let kw = wcs_keywords(&solution(10.68, 41.27, true)).unwrap();
+        assert_eq!(kw["CTYPE1"], "RA---TAN");
+        assert_eq!(kw["CRVAL1"], 10.68);
+        assert_eq!(kw["CRVAL2"], 41.27);
+        assert_eq!(kw["CRPIX1"], 2048.5);
+        assert_eq!(kw["CD2_2"], 2.9e-4);
+        assert!(wcs_keywords(&solution(10.68, 41.27, false)).is_none());
+    }
+
+    #[test]
+    fn drift_is_measured_against_the_target() {
+        let target = TargetCoord {
+            slug: "m31".to_string(),
+            ra_deg: 10.0,
+            dec_deg: 0.0,
+        };
+        // 0.05° = 180″ off along the equator.
+        let section = solved_section(&solution(10.05, 0.0, true), Some(&target), 60.0);
+        assert_eq!(section["status"], "solved");
+        let drift = section["drift"]["drift_arcsec"].as_f64().unwrap();
+        assert!((drift - 180.0).abs() < 0.01, "{drift}");
+        assert_eq!(section["drift"]["exceeded"], true);
+
+        let section = solved_section(&solution(10.01, 0.0, true), Some(&target), 60.0);
+        assert_eq!(section["drift"]["exceeded"], false);
+    }
+
+    #[test]
+    fn no_target_means_no_drift() {
+        let section = solved_section(&solution(10.0, 0.0, false), None, 60.0);
+        assert!(section["drift"].is_null());
+        assert!(section["wcs_keywords"].is_null());
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use rusty_photon_config::actions::FieldError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::EquipmentConfig;
+
+/// The dew-heater controller (rp.md § Dew Management). Absent → rp
+/// never touches a heater; the PPBA's own auto-dew, if enabled, keeps
+/// running on its own.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DewConfig {
+    /// The `equipment.observing_conditions[].id` that supplies ambient
+    /// `Temperature` and `DewPoint`.
+    pub observing_conditions_id: String,
+    /// Control-loop cadence. Defaults to 30 seconds. Accepts a
+    /// humantime string.
+    #[serde(default = "default_poll_interval", with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub poll_interval: Duration,
+    pub heaters: Vec<DewHeaterConfig>,
+}
+
+/// One heater: a PWM (analog) switch port and the PI loop driving it.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DewHeaterConfig {
+    /// Names the heater in events and exposure documents.
+    pub id: String,
+    /// The `equipment.switches[].id` carrying the heater output.
+    pub switch_id: String,
+    /// The output's port number on that switch.
+    pub index: usize,
+    /// How far above the dew point to hold the optic, °C. Defaults to 3.
+    #[serde(default = "default_margin_c")]
+    pub margin_c: f64,
+    /// Proportional gain, % duty per °C short of the target. Defaults
+    /// to 10.
+    #[serde(default = "default_kp")]
+    pub kp: f64,
+    /// Integral gain, % duty per °C·minute of accumulated shortfall.
+    /// Defaults to 2.
+    #[serde(default = "default_ki")]
+    pub ki: f64,
+    /// Duty floor, percent. Defaults to 0.
+    #[serde(default)]
+    pub min_duty_pct: f64,
+    /// Duty ceiling, percent. Defaults to 100.
+    #[serde(default = "default_max_duty_pct")]
+    pub max_duty_pct: f64,
+    /// Optional switch port reporting the optic's own temperature in
+    /// °C (a read-only analog port, e.g. a heater strap's probe). With
+    /// it a PI loop regulates the optic; without it the duty follows
+    /// the ambient temperature − dew point spread, feed-forward.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature_sensor: Option<DewSensorConfig>,
+}
+
+/// A switch port read as a temperature, °C.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DewSensorConfig {
+    pub switch_id: String,
+    pub index: usize,
+}
+
+const fn default_poll_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_margin_c() -> f64 {
+    3.0
+}
+
+const fn default_kp() -> f64 {
+    10.0
+}
+
+const fn default_ki() -> f64 {
+    2.0
+}
+
+const fn default_max_duty_pct() -> f64 {
+    100.0
+}
+
+impl DewConfig {
+    /// Domain rules `serde` cannot express: the referenced devices
+    /// exist, every number is in range, and no port is driven twice.
+    #[must_use]
+    pub fn field_errors(&self, equipment: &EquipmentConfig) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if !equipment
+            .observing_conditions
+            .iter()
+            .any(|oc| oc.id == self.observing_conditions_id)
+        {
+            errors.push(FieldError {
+                path: "dew.observing_conditions_id".to_string(),
+                msg: format!(
+                    "no equipment.observing_conditions entry with id '{}'",
+                    self.observing_conditions_id
+                ),
+            });
+        }
+        if self.poll_interval.is_zero() {
+            errors.push(FieldError {
+                path: "dew.poll_interval".to_string(),
+                msg: "must be greater than zero".to_string(),
+            });
+        }
+        let has_switch = |id: &str| equipment.switches.iter().any(|s| s.id == id);
+        let mut ids = HashSet::new();
+        let mut ports = HashSet::new();
+        for (index, heater) in self.heaters.iter().enumerate() {
+            let at = |field: &str, msg: String| FieldError {
+                path: format!("dew.heaters.{index}.{field}"),
+                msg: format!("{msg} (heater '{}')", heater.id),
+            };
+            if !ids.insert(heater.id.as_str()) {
+                errors.push(at("id", "duplicate heater id".to_string()));
+            }
+            if !has_switch(&heater.switch_id) {
+                errors.push(at(
+                    "switch_id",
+                    format!("no equipment.switches entry with id '{}'", heater.switch_id),
+                ));
+            }
+            if !ports.insert((heater.switch_id.as_str(), heater.index)) {
+                errors.push(at(
+                    "index",
+                    format!(
+                        "port {} of switch '{}' is already driven by another heater",
+                        heater.index, heater.switch_id
+                    ),
+                ));
+            }
+            for (field, value) in [
+                ("margin_c", heater.margin_c),
+                ("kp", heater.kp),
+                ("ki", heater.ki),
+            ] {
+                if !(value.is_finite() && value >= 0.0) {
+                    errors.push(at(
+                        field,
+                        format!("must be a non-negative number; got {value}"),
+                    ));
+                }
+            }
+            if !(0.0..=100.0).contains(&heater.min_duty_pct) {
+                errors.push(at(
+                    "min_duty_pct",
+                    format!("must be in [0, 100]; got {}", heater.min_duty_pct),
+                ));
+            }
+            if !(0.0..=100.0).contains(&heater.max_duty_pct) {
+                errors.push(at(
+                    "max_duty_pct",
+                    format!("must be in [0, 100]; got {}", heater.max_duty_pct),
+                ));
+            } else if heater.max_duty_pct < heater.min_duty_pct {
+                errors.push(at(
+                    "max_duty_pct",
+                    format!(
+                        "must not be below min_duty_pct ({}); got {}",
+                        heater.min_duty_pct, heater.max_duty_pct
+                    ),
+                ));
+            }
+            if let Some(sensor) = &heater.temperature_sensor {
+                if !has_switch(&sensor.switch_id) {
+                    errors.push(at(
+                        "temperature_sensor.switch_id",
+                        format!("no equipment.switches entry with id '{}'", sensor.switch_id),
+                    ));
+                }
+            }
+        }
+        errors
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::{load_config, validate_config};
+
+    const EQUIPMENT: &str = r#"{
+        "switches": [{"id": "ppba", "alpaca_url": "http://127.0.0.1:11130"}],
+        "observing_conditions": [{"id": "weather", "alpaca_url": "http://127.0.0.1:11131"}]
+    }"#;
+
+    fn config_with_dew(dew: &str) -> crate::config::Config {
+        let json = format!(
+            r#"{{
+                "session": {{"data_directory": "/tmp/rp-test"}},
+                "equipment": {EQUIPMENT},
+                "dew": {dew}
+            }}"#
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn dew_heater_applies_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            format!(
+                r#"{{
+                    "session": {{"data_directory": "/tmp/rp-test"}},
+                    "equipment": {EQUIPMENT},
+                    "dew": {{
+                        "observing_conditions_id": "weather",
+                        "heaters": [{{"id": "main", "switch_id": "ppba", "index": 2}}]
+                    }}
+                }}"#
+            ),
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let dew = config.dew.unwrap();
+        assert_eq!(dew.poll_interval, Duration::from_secs(30));
+        let h = &dew.heaters[0];
+        assert_eq!(h.index, 2);
+        assert_eq!(h.margin_c, 3.0);
+        assert_eq!(h.kp, 10.0);
+        assert_eq!(h.ki, 2.0);
+        assert_eq!(h.min_duty_pct, 0.0);
+        assert_eq!(h.max_duty_pct, 100.0);
+        assert!(h.temperature_sensor.is_none());
+    }
+
+    #[test]
+    fn dew_field_errors_name_unknown_devices_and_bad_numbers() {
+        let config = config_with_dew(
+            r#"{
+                "observing_conditions_id": "nope",
+                "poll_interval": "0s",
+                "heaters": [
+                    {"id": "main", "switch_id": "ppba", "index": 2, "kp": -1.0,
+                     "min_duty_pct": 60, "max_duty_pct": 40},
+                    {"id": "main", "switch_id": "ppba", "index": 2,
+                     "temperature_sensor": {"switch_id": "upb", "index": 7}},
+                    {"id": "guide", "switch_id": "upb", "index": 0}
+                ]
+            }"#,
+        );
+        let paths: Vec<String> = validate_config(&config)
+            .into_iter()
+            .map(|e| e.path)
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                "dew.observing_conditions_id",
+                "dew.poll_interval",
+                "dew.heaters.0.kp",
+                "dew.heaters.0.max_duty_pct",
+                "dew.heaters.1.id",
+                "dew.heaters.1.index",
+                "dew.heaters.1.temperature_sensor.switch_id",
+                "dew.heaters.2.switch_id",
+            ]
+        );
+    }
+
+    #[test]
+    fn dew_block_omitted_is_none() {
+        let config: crate::config::Config =
+            serde_json::from_str(crate::config::test_support::MINIMAL_CONFIG_JSON).unwrap();
+        assert!(config.dew.is_none());
+    }
+}
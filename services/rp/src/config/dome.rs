@@ -1,3 +1,6 @@
+use std::time::Duration;
+
+use rusty_photon_config::actions::FieldError;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -15,11 +18,137 @@ pub struct DomeConfig {
     /// Optional HTTP Basic Auth credentials for connecting to auth-enabled Alpaca services
     #[serde(default)]
     pub auth: Option<rp_auth::config::ClientAuthConfig>,
+    /// Optional slaving geometry and policy. Present → `set_dome_slaving`
+    /// may make this dome follow the mount (rp.md § Dome Slaving); absent
+    /// → the dome is driven only by explicit tool calls (a roll-off roof
+    /// never needs it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slaving: Option<DomeSlavingConfig>,
+}
+
+/// How a slaved dome follows the mount. Offsets are meters in a local
+/// east/north/up frame centered on the dome sphere; the slit azimuth is
+/// computed by `rp_ephemeris::DomeGeometry`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DomeSlavingConfig {
+    /// Dome radius, meters.
+    pub dome_radius_m: f64,
+    /// RA/Dec axis intersection relative to the dome center, meters
+    /// east. Defaults to 0.
+    #[serde(default)]
+    pub mount_offset_east_m: f64,
+    /// Same, meters north. Defaults to 0.
+    #[serde(default)]
+    pub mount_offset_north_m: f64,
+    /// Same, meters up. Defaults to 0.
+    #[serde(default)]
+    pub mount_offset_up_m: f64,
+    /// Distance from the RA axis to the optical axis along the Dec axis
+    /// (the GEM offset), meters. Defaults to 0.
+    #[serde(default)]
+    pub gem_offset_m: f64,
+    /// How far the shutter azimuth may drift from the computed slit
+    /// azimuth before the dome is moved, degrees. Defaults to 3.
+    #[serde(default = "default_tolerance_deg")]
+    pub tolerance_deg: f64,
+    /// How often the follower re-reads the mount. Defaults to 2 seconds.
+    /// Accepts a humantime string.
+    #[serde(default = "default_poll_interval", with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub poll_interval: Duration,
+    /// Longest an exposure is held waiting for the slit to catch up
+    /// before `capture` fails. Defaults to 3 minutes. Accepts a
+    /// humantime string.
+    #[serde(default = "default_catch_up_timeout", with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub catch_up_timeout: Duration,
+}
+
+const fn default_tolerance_deg() -> f64 {
+    3.0
+}
+
+const fn default_poll_interval() -> Duration {
+    Duration::from_secs(2)
+}
+
+const fn default_catch_up_timeout() -> Duration {
+    Duration::from_mins(3)
+}
+
+impl DomeConfig {
+    /// Range-validate the dome as field-level errors (empty = valid),
+    /// with `index` naming its position in `equipment.domes`. Only the
+    /// slaving block carries ranges.
+    #[must_use]
+    pub fn field_errors(&self, index: usize) -> Vec<FieldError> {
+        let Some(s) = &self.slaving else {
+            return Vec::new();
+        };
+        let path = |field: &str| format!("equipment.domes.{index}.slaving.{field}");
+        let mut errors = Vec::new();
+        if !(s.dome_radius_m.is_finite() && s.dome_radius_m > 0.0) {
+            errors.push(FieldError {
+                path: path("dome_radius_m"),
+                msg: format!(
+                    "must be a positive number of meters; got {} (dome '{}')",
+                    s.dome_radius_m, self.id
+                ),
+            });
+        }
+        for (field, value) in [
+            ("mount_offset_east_m", s.mount_offset_east_m),
+            ("mount_offset_north_m", s.mount_offset_north_m),
+            ("mount_offset_up_m", s.mount_offset_up_m),
+            ("gem_offset_m", s.gem_offset_m),
+        ] {
+            if !value.is_finite() {
+                errors.push(FieldError {
+                    path: path(field),
+                    msg: format!("must be a finite number; got {value} (dome '{}')", self.id),
+                });
+            }
+        }
+        let reach = s
+            .mount_offset_up_m
+            .hypot(s.mount_offset_east_m.hypot(s.mount_offset_north_m))
+            + s.gem_offset_m.abs();
+        if reach.is_finite() && s.dome_radius_m > 0.0 && reach >= s.dome_radius_m {
+            errors.push(FieldError {
+                path: path("dome_radius_m"),
+                msg: format!(
+                    "the mount offsets place the optical axis {reach} m from the center, \
+                     outside a {} m dome (dome '{}')",
+                    s.dome_radius_m, self.id
+                ),
+            });
+        }
+        if !(s.tolerance_deg > 0.0 && s.tolerance_deg <= 180.0) {
+            errors.push(FieldError {
+                path: path("tolerance_deg"),
+                msg: format!(
+                    "must be in (0, 180]; got {} (dome '{}')",
+                    s.tolerance_deg, self.id
+                ),
+            });
+        }
+        if s.poll_interval.is_zero() {
+            errors.push(FieldError {
+                path: path("poll_interval"),
+                msg: format!("must be greater than zero (dome '{}')", self.id),
+            });
+        }
+        errors
+    }
 }
 
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
+    use std::time::Duration;
+
+    use super::*;
     use crate::config::load_config;
 
     #[test]
@@ -47,6 +176,69 @@ mod tests {
         assert_eq!(d.device_number, 0);
         assert!(d.name.is_none());
         assert!(d.auth.is_none());
+        assert!(d.slaving.is_none());
+    }
+
+    #[test]
+    fn dome_slaving_block_applies_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "session": {"data_directory": "/tmp/rp-test"},
+                "equipment": {
+                    "domes": [
+                        {
+                            "id": "dome",
+                            "alpaca_url": "http://127.0.0.1:11140",
+                            "slaving": {"dome_radius_m": 1.5, "gem_offset_m": 0.3}
+                        }
+                    ]
+                },
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let s = config.equipment.domes[0].slaving.as_ref().unwrap();
+        assert!((s.dome_radius_m - 1.5).abs() < f64::EPSILON);
+        assert!((s.gem_offset_m - 0.3).abs() < f64::EPSILON);
+        assert!(s.mount_offset_east_m.abs() < f64::EPSILON);
+        assert!((s.tolerance_deg - 3.0).abs() < f64::EPSILON);
+        assert_eq!(s.poll_interval, Duration::from_secs(2));
+        assert_eq!(s.catch_up_timeout, Duration::from_mins(3));
+    }
+
+    #[test]
+    fn dome_slaving_field_errors_name_each_bad_field() {
+        let dome = DomeConfig {
+            id: "dome".to_string(),
+            name: None,
+            alpaca_url: "http://127.0.0.1:11140".to_string(),
+            device_number: 0,
+            auth: None,
+            slaving: Some(DomeSlavingConfig {
+                dome_radius_m: 1.0,
+                mount_offset_east_m: 0.8,
+                mount_offset_north_m: 0.0,
+                mount_offset_up_m: 0.0,
+                gem_offset_m: 0.3,
+                tolerance_deg: 0.0,
+                poll_interval: Duration::ZERO,
+                catch_up_timeout: Duration::from_mins(3),
+            }),
+        };
+        let paths: Vec<String> = dome.field_errors(2).into_iter().map(|e| e.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "equipment.domes.2.slaving.dome_radius_m",
+                "equipment.domes.2.slaving.tolerance_deg",
+                "equipment.domes.2.slaving.poll_interval",
+            ]
+        );
     }
 
     #[test]
@@ -8,6 +8,8 @@ pub struct FilterWheelConfig {
     pub alpaca_url: String,
     #[serde(default)]
     pub device_number: u32,
+    /// Filter names by wheel slot. Optical trains key their
+    /// `focus_offsets` by these names.
     #[serde(default)]
     pub filters: Vec<String>,
     /// Optional HTTP Basic Auth credentials for connecting to auth-enabled Alpaca services
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Per-rig meridian-flip policy for the `perform_meridian_flip` compound
+/// tool (rp.md § `perform_meridian_flip` Contract). Nested under the
+/// singular mount because the flip is a property of the mount's
+/// mechanics: a German equatorial mount can track a limited distance
+/// past the meridian before the counterweight side must swap. Omitted
+/// block → both defaults apply.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MeridianFlipConfig {
+    /// How far past the meridian, in hour angle, the pointing must be
+    /// before the flip re-slews. Mounts that refuse a pier-side change
+    /// until the target is clearly west of the meridian need a few
+    /// minutes here. Defaults to 5 minutes. Accepts a humantime string,
+    /// read as sidereal time (hour angle).
+    #[serde(default = "default_hour_angle_offset", with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub hour_angle_offset: Duration,
+    /// Longest the flip will wait for the pointing to reach
+    /// `hour_angle_offset`. A flip requested further east than this
+    /// fails before any motion rather than blocking the mount for an
+    /// hour. Defaults to 30 minutes. Accepts a humantime string.
+    #[serde(default = "default_max_wait", with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub max_wait: Duration,
+}
+
+impl Default for MeridianFlipConfig {
+    fn default() -> Self {
+        Self {
+            hour_angle_offset: default_hour_angle_offset(),
+            max_wait: default_max_wait(),
+        }
+    }
+}
+
+const fn default_hour_angle_offset() -> Duration {
+    Duration::from_mins(5)
+}
+
+const fn default_max_wait() -> Duration {
+    Duration::from_mins(30)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::config::load_config;
+
+    #[test]
+    fn meridian_flip_block_omitted_uses_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "session": {"data_directory": "/tmp/rp-test"},
+                "equipment": {
+                    "mount": {"alpaca_url": "http://localhost:11122"}
+                },
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let m = config.equipment.mount.as_ref().unwrap();
+        assert_eq!(m.meridian_flip.hour_angle_offset, Duration::from_mins(5));
+        assert_eq!(m.meridian_flip.max_wait, Duration::from_mins(30));
+    }
+
+    #[test]
+    fn meridian_flip_block_overrides_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "session": {"data_directory": "/tmp/rp-test"},
+                "equipment": {
+                    "mount": {
+                        "alpaca_url": "http://localhost:11122",
+                        "meridian_flip": {"hour_angle_offset": "10m", "max_wait": "1h"}
+                    }
+                },
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let m = config.equipment.mount.as_ref().unwrap();
+        assert_eq!(m.meridian_flip.hour_angle_offset, Duration::from_mins(10));
+        assert_eq!(m.meridian_flip.max_wait, Duration::from_hours(1));
+    }
+}
@@ -6,7 +6,7 @@
 //! configs [`camera`], [`focuser`], [`mount`], [`filter_wheel`],
 //! [`cover_calibrator`], the [`optical_train`] light-path lists, and
 //! the mount-scoped [`guiding`] service block), [`imaging`],
-//! [`plate_solver`], [`server`].
+//! [`plate_solver`], [`dew`], [`server`].
 //! The submodules' public types are re-exported here so existing
 //! `crate::config::CameraConfig` callsites keep working unchanged.
 
@@ -14,12 +14,14 @@ pub mod camera;
 pub mod centering;
 pub mod cooling;
 pub mod cover_calibrator;
+pub mod dew;
 pub mod dome;
 pub mod equipment;
 pub mod filter_wheel;
 pub mod focuser;
 pub mod guiding;
 pub mod imaging;
+pub mod meridian_flip;
 pub mod mount;
 pub mod naming_template;
 pub mod observing_conditions;
@@ -38,23 +40,25 @@ pub use camera::CameraConfig;
 pub use centering::CenteringConfig;
 pub use cooling::CoolingConfig;
 pub use cover_calibrator::CoverCalibratorConfig;
+pub use dew::{DewConfig, DewHeaterConfig, DewSensorConfig};
 pub use dome::DomeConfig;
 pub use equipment::EquipmentConfig;
 pub use filter_wheel::FilterWheelConfig;
 pub use focuser::FocuserConfig;
 pub use guiding::{FocusWatchConfig, GuiderDefaults, GuidingConfig};
 pub use imaging::ImagingConfig;
+pub use meridian_flip::MeridianFlipConfig;
 pub use mount::MountConfig;
 pub use observing_conditions::ObservingConditionsConfig;
 pub use optical_train::{
     FocalLengthMm, OpticalTrainConfig, PositionAngleDegrees, TrainAutoFocusConfig, TrainPurpose,
 };
-pub use plate_solver::PlateSolverConfig;
+pub use plate_solver::{BackgroundSolveConfig, PlateSolverConfig};
 pub use rotator::RotatorConfig;
 pub use safety::SafetyConfig;
 pub use safety_monitor::SafetyMonitorConfig;
 pub use server::{AdvertisedUrl, ServerConfig};
-pub use session::SessionConfig;
+pub use session::{FitsCompression, FrameEncoding, ImageFormat, SessionConfig, XisfCompression};
 pub use site::SiteConfig;
 pub use switch::SwitchConfig;
 pub use target_store::{TargetStoreConfig, TargetStoreConfigWire};
@@ -112,6 +116,10 @@ pub struct Config {
     /// `equipment.cameras[].cooler_targets_c`.
     #[serde(default)]
     pub cooling: CoolingConfig,
+    /// Optional dew-heater controller (rp.md § Dew Management). When
+    /// `None`, rp drives no heater.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dew: Option<DewConfig>,
     /// Optional plate-solver service. When `None`, the `plate_solve`
     /// MCP tool returns `plate solver not configured`. Mirrors the
     /// `Option<MountConfig>` pattern — the service is optional
@@ -270,6 +278,12 @@ pub fn validate_config(config: &Config) -> Vec<FieldError> {
     for (index, cam) in config.equipment.cameras.iter().enumerate() {
         errors.extend(cam.field_errors(index));
     }
+    for (index, dome) in config.equipment.domes.iter().enumerate() {
+        errors.extend(dome.field_errors(index));
+    }
+    if let Some(dew) = config.dew.as_ref() {
+        errors.extend(dew.field_errors(&config.equipment));
+    }
     // The optical-train graph rules (roster existence, terminal camera,
     // order consistency, the one-guiding-train rule) live with the
     // derived model so validation and derivation cannot drift apart.
@@ -907,6 +921,7 @@ mod tests {
         config.site = Some(crate::config::SiteConfig {
             latitude_degrees: 91.0,
             longitude_degrees: 181.0,
+            horizon: None,
         });
         config.equipment.cameras = vec![
             serde_json::from_value(serde_json::json!({
@@ -1037,6 +1052,7 @@ mod tests {
             "session": {
                 "data_directory": "/data/lights",
                 "session_state_file": "/data/session_state.json",
+                "focus_model_file": "/data/focus_model.json",
                 "file_naming_pattern": "{target}_{filter}"
             },
             "site": { "latitude_degrees": 47.6062, "longitude_degrees": -122.3321 },
@@ -1133,7 +1149,8 @@ mod tests {
                 "warm_target_c": 10.0
             },
             "plate_solver": { "url": "http://localhost:11131", "timeout": "1m", "default_search_radius_deg": 3.0,
-                               "auth": { "username": "observatory", "password": "secret" } },
+                               "auth": { "username": "observatory", "password": "secret" },
+                               "background": { "drift_threshold_arcsec": 90.0, "timeout": "20s" } },
             "server": {
                 "port": 11115,
                 "bind_address": "127.0.0.1",
@@ -1159,9 +1176,12 @@ mod tests {
             ("/cooling/max_cooldown", "20m"),
             ("/cooling/warmup_step_interval", "2m"),
             ("/plate_solver/timeout", "1m"),
+            ("/plate_solver/background/timeout", "20s"),
             ("/equipment/mount/guiding/timeout", "1m 30s"),
             ("/equipment/mount/guiding/settle_time", "10s"),
             ("/equipment/mount/guiding/settle_timeout", "1m"),
+            ("/equipment/mount/meridian_flip/hour_angle_offset", "5m"),
+            ("/equipment/mount/meridian_flip/max_wait", "30m"),
         ] {
             assert_eq!(
                 value.pointer(pointer).and_then(Value::as_str),
@@ -86,6 +86,10 @@ pub struct MountConfig {
     /// "guider not configured".
     #[serde(default)]
     pub guiding: Option<super::guiding::GuidingConfig>,
+    /// Meridian-flip policy for `perform_meridian_flip` (hour-angle
+    /// offset and wait ceiling). Omitted → defaults.
+    #[serde(default)]
+    pub meridian_flip: super::meridian_flip::MeridianFlipConfig,
     /// Optional HTTP Basic Auth credentials for connecting to auth-enabled Alpaca services
     #[serde(default)]
     pub auth: Option<rp_auth::config::ClientAuthConfig>,
@@ -1284,8 +1284,13 @@ mod tests {
         let session = crate::config::session::SessionConfig {
             data_directory: "/tmp/x".to_string(),
             session_state_file: String::new(),
+            focus_model_file: String::new(),
             file_naming_pattern: Some(DEFAULT_PATTERN.to_string()),
             directory_pattern: None,
+            fits_keywords: Default::default(),
+            fits_compression: Default::default(),
+            image_format: Default::default(),
+            xisf_compression: Default::default(),
         };
         let templates = NamingTemplates::from_session_config(&session)
             .unwrap()
@@ -1348,8 +1353,13 @@ mod tests {
         super::super::session::SessionConfig {
             data_directory: "/tmp/rp-test".to_string(),
             session_state_file: String::new(),
+            focus_model_file: String::new(),
             file_naming_pattern: file_naming_pattern.map(str::to_string),
             directory_pattern: directory_pattern.map(str::to_string),
+            fits_keywords: Default::default(),
+            fits_compression: Default::default(),
+            image_format: Default::default(),
+            xisf_compression: Default::default(),
         }
     }
 
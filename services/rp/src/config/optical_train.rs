@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use schemars::JsonSchema;
@@ -253,6 +254,15 @@ pub struct OpticalTrainConfig {
     /// parameter per call.
     #[serde(default)]
     pub auto_focus: Option<TrainAutoFocusConfig>,
+    /// Per-filter focus offsets in focuser steps, keyed by the names in
+    /// the train's filter wheel's `filters` list. `set_filter` moves the
+    /// train's terminal focuser by the difference between the new and the
+    /// old filter's offsets; only differences matter, so the reference
+    /// filter conventionally sits at 0. Requires exactly one filter wheel
+    /// and a focuser in `devices`. Empty (the default) → filter changes
+    /// never move the focuser. `measure_filter_offsets` writes it.
+    #[serde(default)]
+    pub focus_offsets: BTreeMap<String, i32>,
 }
 
 #[cfg(test)]
@@ -27,12 +27,67 @@ pub struct PlateSolverConfig {
     /// auth-enabled plate-solver service.
     #[serde(default)]
     pub auth: Option<rp_auth::config::ClientAuthConfig>,
+    /// Background solving of light frames on `exposure_complete`
+    /// (rp.md § Background Solving). Omitted → disabled.
+    #[serde(default)]
+    pub background: Option<BackgroundSolveConfig>,
 }
 
 const fn default_plate_solver_timeout() -> Duration {
     Duration::from_mins(1)
 }
 
+/// The `background` sub-block: solve every `Light` frame off the
+/// capture path and publish `pointing_drift` when the solved center
+/// strays from the frame's target. Every field is optional; the
+/// block's presence enables background solving.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BackgroundSolveConfig {
+    /// Separation between the solved center and the frame's target
+    /// above which `pointing_drift` fires. Default 60″.
+    #[serde(default)]
+    pub drift_threshold_arcsec: DriftThresholdArcsec,
+    /// Per-solve deadline forwarded to the wrapper's `timeout`.
+    /// Omitted → the wrapper's own `default_solve_timeout`.
+    #[serde(default, with = "humantime_serde::option")]
+    #[schemars(with = "Option<String>")]
+    pub timeout: Option<Duration>,
+}
+
+/// The background solver's drift threshold in arcseconds. Must be a
+/// finite number strictly above zero — at zero every solved frame
+/// would report drift. Defaults to 60.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "f64")]
+pub struct DriftThresholdArcsec(f64);
+
+impl DriftThresholdArcsec {
+    #[must_use]
+    pub const fn value(self) -> f64 {
+        self.0
+    }
+}
+
+impl Default for DriftThresholdArcsec {
+    fn default() -> Self {
+        Self(60.0)
+    }
+}
+
+impl TryFrom<f64> for DriftThresholdArcsec {
+    type Error = String;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        if !value.is_finite() || value <= 0.0 {
+            return Err(format!(
+                "background.drift_threshold_arcsec must be a finite number > 0, got {value}"
+            ));
+        }
+        Ok(Self(value))
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -75,6 +130,7 @@ mod tests {
         assert_eq!(ps.timeout, Duration::from_mins(1));
         assert!(ps.default_search_radius_deg.is_none());
         assert!(ps.auth.is_none());
+        assert!(ps.background.is_none());
     }
 
     #[test]
@@ -107,6 +163,56 @@ mod tests {
         assert_eq!(auth.password, "secret");
     }
 
+    #[test]
+    fn plate_solver_background_block_applies_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "session": {"data_directory": "/tmp/rp-test"},
+                "equipment": {},
+                "plate_solver": {"url": "http://127.0.0.1:11131", "background": {}},
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let bg = config.plate_solver.unwrap().background.unwrap();
+        assert!((bg.drift_threshold_arcsec.value() - 60.0).abs() < f64::EPSILON);
+        assert!(bg.timeout.is_none());
+    }
+
+    #[test]
+    fn plate_solver_background_rejects_non_positive_threshold() {
+        for threshold in ["0", "-5.0"] {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("config.json");
+            std::fs::write(
+                &path,
+                format!(
+                    r#"{{
+                        "session": {{"data_directory": "/tmp/rp-test"}},
+                        "equipment": {{}},
+                        "plate_solver": {{
+                            "url": "http://127.0.0.1:11131",
+                            "background": {{"drift_threshold_arcsec": {threshold}, "timeout": "20s"}}
+                        }},
+                        "server": {{ "port": 0 }}
+                    }}"#
+                ),
+            )
+            .unwrap();
+
+            let msg = load_config(&path).unwrap_err().to_string();
+            assert!(
+                msg.contains("drift_threshold_arcsec"),
+                "threshold {threshold} must be rejected by name, got: {msg}"
+            );
+        }
+    }
+
     #[test]
     fn plate_solver_rejects_unknown_field() {
         let dir = tempfile::tempdir().unwrap();
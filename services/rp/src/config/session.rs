@@ -1,5 +1,7 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use rp_fits::writer::{Keyword, KeywordValue};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +18,12 @@ pub struct SessionConfig {
     /// lives.
     #[serde(default)]
     pub session_state_file: String,
+    /// Where the focus model's auto-focus history lives (rp.md § Focus
+    /// Temperature Compensation). Empty (the default) resolves to
+    /// `<data_directory>/focus_model.json` — see
+    /// [`Self::focus_model_path`].
+    #[serde(default)]
+    pub focus_model_file: String,
     /// Optional template for capture filenames. `None` is the default and
     /// produces filenames of the form `<doc_uuid_8>.fits` plus a matching
     /// `.json` sidecar — fully self-identifying via the UUID-8 suffix that
@@ -43,6 +51,138 @@ pub struct SessionConfig {
     /// [`crate::config::naming_template::validate_directory_pattern`]).
     #[serde(default)]
     pub directory_pattern: Option<String>,
+    /// Extra FITS header cards stamped on every captured frame, keyed
+    /// by keyword (rp.md § Capture Tool Details → FITS header): site
+    /// constants like `OBSERVER`, or an override of a derived card such
+    /// as `TELESCOP`. Validated at load — see [`FitsKeywords`].
+    #[serde(default)]
+    pub fits_keywords: FitsKeywords,
+    /// On-disk encoding of captured frames (rp.md § Capture Tool
+    /// Details → Compression). `none` (the default) writes a plain
+    /// primary-HDU image; `rice` writes the same pixels losslessly
+    /// Rice tile-compressed, which `fpack`-aware tools read natively.
+    #[serde(default)]
+    pub fits_compression: FitsCompression,
+    /// File format of captured frames (rp.md § Capture Tool Details →
+    /// Image format): `fits` (the default) or `xisf`, `PixInsight`'s
+    /// native format. Both carry the same header keywords and `DOC_ID`.
+    #[serde(default)]
+    pub image_format: ImageFormat,
+    /// Data-block compression of `xisf` frames: `none` (the default),
+    /// `zlib` or `lz4`. `fits_compression` governs `fits` frames.
+    #[serde(default)]
+    pub xisf_compression: XisfCompression,
+}
+
+/// `session.fits_compression`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum FitsCompression {
+    #[default]
+    None,
+    Rice,
+}
+
+/// `session.image_format`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageFormat {
+    #[default]
+    Fits,
+    Xisf,
+}
+
+/// `session.xisf_compression`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum XisfCompression {
+    #[default]
+    None,
+    Zlib,
+    Lz4,
+}
+
+/// How `do_capture` encodes a frame on disk: `session.image_format`
+/// together with that format's compression setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameEncoding {
+    Fits(FitsCompression),
+    Xisf(XisfCompression),
+}
+
+impl Default for FrameEncoding {
+    fn default() -> Self {
+        Self::Fits(FitsCompression::None)
+    }
+}
+
+impl FrameEncoding {
+    /// File extension of frames in this encoding, without the dot.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Fits(_) => "fits",
+            Self::Xisf(_) => "xisf",
+        }
+    }
+}
+
+/// One `session.fits_keywords` value. The JSON type picks the FITS
+/// type: `true` is a logical, `12` an integer, `12.5` a real, `"x"` a
+/// string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum FitsKeywordValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl From<&FitsKeywordValue> for KeywordValue {
+    fn from(value: &FitsKeywordValue) -> Self {
+        match value {
+            FitsKeywordValue::Bool(b) => Self::Bool(*b),
+            FitsKeywordValue::Int(n) => Self::Int(*n),
+            FitsKeywordValue::Float(f) => Self::Float(*f),
+            FitsKeywordValue::Str(s) => Self::Str(s.clone()),
+        }
+    }
+}
+
+/// The operator's FITS keyword map, validated at load
+/// (parse-don't-validate): every entry must be a card
+/// [`Keyword::new`] accepts, and none may be `DOC_ID` — rp's lineage
+/// key is never operator-supplied. Serializes transparently as the
+/// map.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(try_from = "BTreeMap<String, FitsKeywordValue>")]
+pub struct FitsKeywords(BTreeMap<String, FitsKeywordValue>);
+
+impl FitsKeywords {
+    /// The map as header cards, in key order. Every entry was
+    /// validated at load, so none is dropped here.
+    #[must_use]
+    pub fn keywords(&self) -> Vec<Keyword> {
+        self.0
+            .iter()
+            .filter_map(|(key, value)| Keyword::new(key, value.into()).ok())
+            .collect()
+    }
+}
+
+impl TryFrom<BTreeMap<String, FitsKeywordValue>> for FitsKeywords {
+    type Error = String;
+
+    fn try_from(map: BTreeMap<String, FitsKeywordValue>) -> Result<Self, Self::Error> {
+        for (key, value) in &map {
+            if key.eq_ignore_ascii_case("DOC_ID") {
+                return Err("fits_keywords: DOC_ID is written by rp and cannot be set".to_string());
+            }
+            Keyword::new(key, value.into()).map_err(|e| format!("fits_keywords.{key}: {e}"))?;
+        }
+        Ok(Self(map))
+    }
 }
 
 impl SessionConfig {
@@ -57,13 +197,35 @@ impl SessionConfig {
             PathBuf::from(&self.session_state_file)
         }
     }
+
+    /// The resolved focus-model path: `focus_model_file` when set, else
+    /// `<data_directory>/focus_model.json`.
+    #[must_use]
+    pub fn focus_model_path(&self) -> PathBuf {
+        if self.focus_model_file.is_empty() {
+            PathBuf::from(&self.data_directory).join("focus_model.json")
+        } else {
+            PathBuf::from(&self.focus_model_file)
+        }
+    }
+
+    /// The encoding `image_format` and its compression option select.
+    #[must_use]
+    pub const fn frame_encoding(&self) -> FrameEncoding {
+        match self.image_format {
+            ImageFormat::Fits => FrameEncoding::Fits(self.fits_compression),
+            ImageFormat::Xisf => FrameEncoding::Xisf(self.xisf_compression),
+        }
+    }
 }
 
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
+    use super::{FitsCompression, FrameEncoding, XisfCompression};
     use crate::config::load_config;
     use crate::config::test_support::MINIMAL_CONFIG_JSON;
+    use rp_fits::writer::KeywordValue;
 
     #[test]
     fn file_naming_pattern_defaults_to_none() {
@@ -78,6 +240,161 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fits_keywords_default_to_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, MINIMAL_CONFIG_JSON).unwrap();
+
+        let config = load_config(&path).unwrap();
+        assert!(config.session.fits_keywords.keywords().is_empty());
+    }
+
+    #[test]
+    fn fits_keywords_map_json_types_to_fits_types() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(
+            &path,
+            r#"{
+                "session": {
+                    "data_directory": "/tmp/rp-test",
+                    "fits_keywords": {
+                        "observer": "Igor",
+                        "TELESCOP": "RC8",
+                        "APTDIA": 203,
+                        "SITEELEV": 120.5,
+                        "GUIDED": true
+                    }
+                },
+                "equipment": {},
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+
+        let config = load_config(&path).unwrap();
+        let cards: Vec<_> = config
+            .session
+            .fits_keywords
+            .keywords()
+            .into_iter()
+            .map(|k| (k.key().to_string(), k.value().clone()))
+            .collect();
+        assert_eq!(
+            cards,
+            [
+                ("APTDIA".to_string(), KeywordValue::Int(203)),
+                ("GUIDED".to_string(), KeywordValue::Bool(true)),
+                ("SITEELEV".to_string(), KeywordValue::Float(120.5)),
+                ("TELESCOP".to_string(), KeywordValue::Str("RC8".into())),
+                ("OBSERVER".to_string(), KeywordValue::Str("Igor".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_fits_keywords_fail_to_load() {
+        for (body, needle) in [
+            (r#"{ "DOC_ID": "x" }"#, "DOC_ID"),
+            (r#"{ "BITPIX": 16 }"#, "reserved"),
+            (r#"{ "TOOLONGKEY": 1 }"#, "TOOLONGKEY"),
+        ] {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("config.json");
+            std::fs::write(
+                &path,
+                format!(
+                    r#"{{
+                        "session": {{ "data_directory": "/tmp/rp-test", "fits_keywords": {body} }},
+                        "equipment": {{}},
+                        "server": {{ "port": 0 }}
+                    }}"#
+                ),
+            )
+            .unwrap();
+
+            let error = load_config(&path).unwrap_err().to_string();
+            assert!(error.contains(needle), "{body}: {error}");
+        }
+    }
+
+    #[test]
+    fn fits_compression_defaults_to_none_and_parses_rice() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, MINIMAL_CONFIG_JSON).unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.session.fits_compression, FitsCompression::None);
+
+        std::fs::write(
+            &path,
+            r#"{
+                "session": { "data_directory": "/tmp/rp-test", "fits_compression": "rice" },
+                "equipment": {},
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(config.session.fits_compression, FitsCompression::Rice);
+
+        std::fs::write(
+            &path,
+            r#"{
+                "session": { "data_directory": "/tmp/rp-test", "fits_compression": "gzip" },
+                "equipment": {},
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+        let error = load_config(&path).unwrap_err().to_string();
+        assert!(error.contains("gzip"), "{error}");
+    }
+
+    #[test]
+    fn image_format_selects_the_frame_encoding() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+        std::fs::write(&path, MINIMAL_CONFIG_JSON).unwrap();
+        let config = load_config(&path).unwrap();
+        assert_eq!(
+            config.session.frame_encoding(),
+            FrameEncoding::Fits(FitsCompression::None)
+        );
+
+        std::fs::write(
+            &path,
+            r#"{
+                "session": {
+                    "data_directory": "/tmp/rp-test",
+                    "image_format": "xisf",
+                    "xisf_compression": "lz4",
+                    "fits_compression": "rice"
+                },
+                "equipment": {},
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+        let config = load_config(&path).unwrap();
+        let encoding = config.session.frame_encoding();
+        assert_eq!(encoding, FrameEncoding::Xisf(XisfCompression::Lz4));
+        assert_eq!(encoding.extension(), "xisf");
+
+        std::fs::write(
+            &path,
+            r#"{
+                "session": { "data_directory": "/tmp/rp-test", "image_format": "tiff" },
+                "equipment": {},
+                "server": { "port": 0 }
+            }"#,
+        )
+        .unwrap();
+        let error = load_config(&path).unwrap_err().to_string();
+        assert!(error.contains("tiff"), "{error}");
+    }
+
     #[test]
     fn an_unknown_session_key_fails_loud() {
         let dir = tempfile::tempdir().unwrap();
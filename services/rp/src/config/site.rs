@@ -1,3 +1,4 @@
+use rp_ephemeris::{HorizonPoint, HorizonProfile};
 use rusty_photon_config::actions::FieldError;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -7,17 +8,46 @@ use serde::{Deserialize, Serialize};
 /// timezone is derived from these coordinates at startup via
 /// `rp-ephemeris`; elevation is intentionally omitted (see
 /// `docs/services/rp.md` §"Site Configuration").
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(deny_unknown_fields)]
 pub struct SiteConfig {
     pub latitude_degrees: f64,
     pub longitude_degrees: f64,
+    /// Per-azimuth obstruction profile (rp.md §"Site Configuration" →
+    /// Horizon profile). Omitted ⇒ a flat horizon: only the altitude
+    /// floors apply.
+    #[serde(default)]
+    pub horizon: Option<HorizonConfig>,
+}
+
+/// The site's horizon profile: either inline `points` or a horizon
+/// `file` in one of the formats `HorizonProfile::parse` reads
+/// (N.I.N.A. `.hrz`, Stellarium `horizon_list.txt`, APCC / CSV) —
+/// exactly one of the two.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HorizonConfig {
+    #[serde(default)]
+    pub points: Vec<HorizonPointConfig>,
+    /// Path to a horizon file, read once at startup.
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+/// One inline horizon point; azimuth from north through east.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HorizonPointConfig {
+    pub azimuth_degrees: f64,
+    pub altitude_degrees: f64,
 }
 
 impl SiteConfig {
     /// Range-validate the site as field-level errors (empty = valid).
     /// Shared by `load_config` (which aborts startup on the first error)
-    /// and the REST `PUT /api/config` validation.
+    /// and the REST `PUT /api/config` validation. A horizon `file` is
+    /// not read here — that happens once, at startup, in
+    /// [`SiteConfig::horizon_profile`].
     #[must_use]
     pub fn field_errors(&self) -> Vec<FieldError> {
         let mut errors = Vec::new();
@@ -33,8 +63,71 @@ impl SiteConfig {
                 msg: format!("must be in [-180, 180]; got {}", self.longitude_degrees),
             });
         }
+        if let Some(horizon) = &self.horizon {
+            errors.extend(horizon.field_errors());
+        }
+        errors
+    }
+
+    /// The site's horizon profile: the empty (flat) profile when no
+    /// `horizon` is configured.
+    ///
+    /// # Errors
+    ///
+    /// A message naming `site.horizon` if the file cannot be read or
+    /// parsed, or an inline point is out of range.
+    pub fn horizon_profile(&self) -> Result<HorizonProfile, String> {
+        self.horizon
+            .as_ref()
+            .map_or_else(|| Ok(HorizonProfile::default()), HorizonConfig::load)
+    }
+}
+
+impl HorizonConfig {
+    fn field_errors(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if self.points.is_empty() == self.file.is_none() {
+            errors.push(FieldError {
+                path: "site.horizon".to_string(),
+                msg: "set exactly one of `points` or `file`".to_string(),
+            });
+        }
+        for (index, p) in self.points.iter().enumerate() {
+            if !(0.0..=360.0).contains(&p.azimuth_degrees) {
+                errors.push(FieldError {
+                    path: format!("site.horizon.points.{index}.azimuth_degrees"),
+                    msg: format!("must be in [0, 360]; got {}", p.azimuth_degrees),
+                });
+            }
+            if !(-90.0..=90.0).contains(&p.altitude_degrees) {
+                errors.push(FieldError {
+                    path: format!("site.horizon.points.{index}.altitude_degrees"),
+                    msg: format!("must be in [-90, 90]; got {}", p.altitude_degrees),
+                });
+            }
+        }
         errors
     }
+
+    fn load(&self) -> Result<HorizonProfile, String> {
+        match &self.file {
+            Some(path) => {
+                let text = std::fs::read_to_string(path)
+                    .map_err(|e| format!("site.horizon.file {path:?}: {e}"))?;
+                HorizonProfile::parse(&text).map_err(|e| format!("site.horizon.file {path:?}: {e}"))
+            }
+            None => HorizonProfile::new(
+                self.points
+                    .iter()
+                    .map(|p| HorizonPoint {
+                        azimuth_degrees: p.azimuth_degrees,
+                        altitude_degrees: p.altitude_degrees,
+                    })
+                    .collect(),
+            )
+            .map_err(|e| format!("site.horizon.points: {e}")),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -122,6 +215,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inline_horizon_points_load_into_a_profile() {
+        let site: super::SiteConfig = serde_json::from_str(
+            r#"{
+                "latitude_degrees": 47.6,
+                "longitude_degrees": -122.3,
+                "horizon": {"points": [
+                    {"azimuth_degrees": 90.0, "altitude_degrees": 30.0},
+                    {"azimuth_degrees": 180.0, "altitude_degrees": 40.0}
+                ]}
+            }"#,
+        )
+        .unwrap();
+        assert!(site.field_errors().is_empty());
+        let profile = site.horizon_profile().unwrap();
+        assert!((profile.altitude_at(135.0) - 35.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn horizon_file_is_read_and_parsed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("site.hrz");
+        std::fs::write(&path, "0 10\n180 40\n").unwrap();
+        let site = super::SiteConfig {
+            latitude_degrees: 0.0,
+            longitude_degrees: 0.0,
+            horizon: Some(super::HorizonConfig {
+                points: Vec::new(),
+                file: Some(path.to_string_lossy().into_owned()),
+            }),
+        };
+        assert_eq!(site.horizon_profile().unwrap().points().len(), 2);
+    }
+
+    #[test]
+    fn horizon_needs_exactly_one_source_and_in_range_points() {
+        let site = super::SiteConfig {
+            latitude_degrees: 0.0,
+            longitude_degrees: 0.0,
+            horizon: Some(super::HorizonConfig {
+                points: vec![super::HorizonPointConfig {
+                    azimuth_degrees: 400.0,
+                    altitude_degrees: 10.0,
+                }],
+                file: Some("/nonexistent.hrz".to_string()),
+            }),
+        };
+        let paths: Vec<String> = site.field_errors().into_iter().map(|e| e.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "site.horizon".to_string(),
+                "site.horizon.points.0.azimuth_degrees".to_string()
+            ]
+        );
+    }
+
     #[test]
     fn site_config_rejects_unknown_fields() {
         let dir = tempfile::tempdir().unwrap();
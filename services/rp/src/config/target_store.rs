@@ -32,10 +32,12 @@ pub struct TargetStoreConfig {
     /// Applied by `add_target` when the caller supplies no `goals[]`
     /// (Decision 10 — rp-owned policy, not bridge/UI config).
     pub default_goals: Vec<rp_targets::AcquisitionGoal>,
-    /// Fallback scheduling constraints for a target whose own
-    /// `scheduling` is `None`. `get_next_target`'s altitude-gating
-    /// parity (Decision 9) reads `min_altitude_degrees` from here when
-    /// a store-backed target carries no per-target override.
+    /// Fallback scheduling constraints, field by field, for a target
+    /// whose own `scheduling` field is `None`. `get_next_target` and
+    /// `get_target_status` evaluate all four (altitude — Decision 9 —
+    /// plus the Moon and meridian-window constraints); a `None` here
+    /// disables the constraint, except the altitude floor, which falls
+    /// further back to `planner.min_altitude_degrees`.
     pub default_scheduling: rp_targets::SchedulingConstraints,
     /// Tunables for `add_target`'s `source` import form (rp.md § Target
     /// Store → Import form).
@@ -190,7 +192,8 @@ impl From<SchedulingWire> for rp_targets::SchedulingConstraints {
 ///
 /// Returns a human-readable message if a `default_goals` entry fails
 /// its `TryFrom<&GoalWire>` conversion into [`rp_targets::AcquisitionGoal`],
-/// an `import` field is not a finite positive number, or a supplied
+/// an `import` field is not a finite positive number, a supplied
+/// `default_scheduling` field is outside its domain, or a supplied
 /// `default_grading` threshold is not a finite non-negative number.
 pub fn parse_target_store_config(
     wire: &TargetStoreConfigWire,
@@ -216,6 +219,43 @@ pub fn parse_target_store_config(
             ));
         }
     }
+    // The same domains `add_target` enforces on a per-target
+    // `scheduling` override — the planner applies both identically.
+    let scheduling = &wire.default_scheduling;
+    for (field, value, lo, hi) in [
+        (
+            "min_altitude_degrees",
+            scheduling.min_altitude_degrees,
+            -90.0,
+            90.0,
+        ),
+        (
+            "min_moon_separation_degrees",
+            scheduling.min_moon_separation_degrees,
+            0.0,
+            180.0,
+        ),
+        (
+            "max_moon_illumination_fraction",
+            scheduling.max_moon_illumination_fraction,
+            0.0,
+            1.0,
+        ),
+        (
+            "meridian_window_hours",
+            scheduling.meridian_window_hours,
+            0.0,
+            12.0,
+        ),
+    ] {
+        if let Some(value) = value {
+            if !(lo..=hi).contains(&value) {
+                return Err(format!(
+                    "target_store.default_scheduling.{field} must be in [{lo}, {hi}], got {value}"
+                ));
+            }
+        }
+    }
     if let Some(grading) = &wire.default_grading {
         for (field, value) in [
             ("max_hfr_pixels", grading.max_hfr_pixels),
@@ -282,6 +322,19 @@ mod tests {
         assert_eq!(config.default_scheduling.min_altitude_degrees, Some(25.0));
     }
 
+    #[test]
+    fn default_scheduling_rejects_an_out_of_range_moon_fraction() {
+        let wire: TargetStoreConfigWire = serde_json::from_value(serde_json::json!({
+            "default_scheduling": { "max_moon_illumination_fraction": 1.5 }
+        }))
+        .unwrap();
+        let err = parse_target_store_config(&wire).unwrap_err();
+        assert!(
+            err.contains("target_store.default_scheduling.max_moon_illumination_fraction"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn wire_rejects_unknown_field() {
         let err =
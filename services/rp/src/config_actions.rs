@@ -10,9 +10,14 @@
 //! [`ApplyDisposition::Restart`]: every persisted change is classified
 //! `restart_required` and takes effect on the next rp start.
 
-use rusty_photon_config::actions::{ApplyDisposition, ConfigurableDriver, FieldError};
+use std::collections::BTreeMap;
+use std::path::Path;
 
-use crate::config::{validate_config, Config};
+use rusty_photon_config::actions::{
+    config_apply, ApplyDisposition, ApplyStatus, ConfigurableDriver, FieldError,
+};
+
+use crate::config::{load_config, validate_config, Config};
 
 /// Re-exported so routes and tests can name the redaction sentinel without
 /// reaching across crates.
@@ -86,6 +91,45 @@ impl ConfigurableDriver for RpConfigDriver {
     }
 }
 
+/// Write `offsets` as optical train `train_id`'s `focus_offsets` into the
+/// config file at `config_path` — `measure_filter_offsets`' write-back.
+/// Goes through [`config_apply`] exactly as `PUT /api/config` does, so the
+/// edited config is validated whole and persisted the same way; an
+/// `invalid` outcome comes back as the joined `path: msg` list and leaves
+/// the file untouched.
+pub fn persist_focus_offsets(
+    config_path: &Path,
+    train_id: &str,
+    offsets: &BTreeMap<String, i32>,
+) -> Result<(), String> {
+    let current = load_config(config_path).map_err(|e| e.to_string())?;
+    let mut submitted = current.clone();
+    let train = submitted
+        .equipment
+        .optical_trains
+        .iter_mut()
+        .find(|t| t.id == train_id)
+        .ok_or_else(|| {
+            format!(
+                "optical train '{train_id}' is not in '{}'",
+                config_path.display()
+            )
+        })?;
+    train.focus_offsets.clone_from(offsets);
+    let body = serde_json::to_string(&submitted).map_err(|e| e.to_string())?;
+    let response = config_apply::<RpConfigDriver>(config_path, &(), &current, &body)
+        .map_err(|e| e.to_string())?;
+    if response.status == ApplyStatus::Invalid {
+        let errors: Vec<String> = response
+            .errors
+            .iter()
+            .map(|e| format!("{}: {}", e.path, e.msg))
+            .collect();
+        return Err(format!("config rejected: {}", errors.join("; ")));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -230,4 +274,62 @@ mod tests {
         );
         assert!(resp.overrides.is_empty(), "rp has no CLI overrides");
     }
+
+    /// A config file with one train over a three-filter wheel.
+    fn write_train_config(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("rp.json");
+        let config = serde_json::json!({
+            "session": { "data_directory": dir.join("data") },
+            "equipment": {
+                "cameras": [{ "id": "main-cam", "alpaca_url": "http://localhost:11120" }],
+                "focusers": [{ "id": "eaf", "alpaca_url": "http://localhost:11121" }],
+                "filter_wheels": [{
+                    "id": "main-fw",
+                    "alpaca_url": "http://localhost:11122",
+                    "filters": ["L", "R", "Ha"]
+                }],
+                "optical_trains": [{
+                    "id": "main",
+                    "devices": ["eaf", "main-fw", "main-cam"]
+                }]
+            },
+            "server": { "port": 0 }
+        });
+        std::fs::write(&path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        path
+    }
+
+    #[test]
+    fn persist_focus_offsets_writes_the_trains_offsets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_train_config(dir.path());
+        let offsets: BTreeMap<String, i32> = [("L".to_string(), 0), ("Ha".to_string(), 37)]
+            .into_iter()
+            .collect();
+        persist_focus_offsets(&path, "main", &offsets).unwrap();
+        let reloaded = load_config(&path).unwrap();
+        assert_eq!(reloaded.equipment.optical_trains[0].focus_offsets, offsets);
+    }
+
+    #[test]
+    fn persist_focus_offsets_refuses_an_invalid_result_and_leaves_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_train_config(dir.path());
+        let before = std::fs::read_to_string(&path).unwrap();
+        let offsets: BTreeMap<String, i32> = [("OIII".to_string(), 12)].into_iter().collect();
+        let err = persist_focus_offsets(&path, "main", &offsets).unwrap_err();
+        assert!(
+            err.contains("equipment.optical_trains.0.focus_offsets.OIII"),
+            "{err}"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+    }
+
+    #[test]
+    fn persist_focus_offsets_errors_for_a_train_not_in_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_train_config(dir.path());
+        let err = persist_focus_offsets(&path, "guide", &BTreeMap::new()).unwrap_err();
+        assert!(err.contains("optical train 'guide'"), "{err}");
+    }
 }
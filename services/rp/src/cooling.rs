@@ -23,7 +23,7 @@ use tokio::time::Instant;
 use tracing::{debug, info, warn};
 
 use crate::config::CoolingConfig;
-use crate::equipment::EquipmentRegistry;
+use crate::equipment::SharedEquipment;
 use crate::events::EventBus;
 
 /// Warm-up ramp step (rp.md § Camera Cooling): +5 °C per
@@ -43,7 +43,7 @@ struct CameraCooling {
 }
 
 pub struct CoolingController {
-    equipment: Arc<EquipmentRegistry>,
+    equipment: SharedEquipment,
     event_bus: Arc<EventBus>,
     config: CoolingConfig,
     states: Mutex<HashMap<String, CameraCooling>>,
@@ -51,7 +51,7 @@ pub struct CoolingController {
 
 impl CoolingController {
     pub fn new(
-        equipment: Arc<EquipmentRegistry>,
+        equipment: SharedEquipment,
         event_bus: Arc<EventBus>,
         config: CoolingConfig,
     ) -> Self {
@@ -119,6 +119,49 @@ impl CoolingController {
         }
     }
 
+    /// The `cool_camera` tool (rp.md § Camera Cooling → Cooling to a
+    /// chosen rung): run the cooldown pass against the one-rung ladder
+    /// `[rung_c]` and wait for it to finish. The pass can only stabilize
+    /// at that rung or, finding tonight's floor above it, switch the
+    /// cooler off as `cooler_unreachable` — it never snaps to another
+    /// rung. Like session start it cancels any running cooling task for
+    /// the camera first, and the pass runs as the camera's task, so a
+    /// session stop arriving mid-wait takes it over with a warm-up.
+    ///
+    /// Returns `true` when the camera ended stabilized at `rung_c`.
+    pub async fn cool_to_rung(self: &Arc<Self>, camera_id: &str, rung_c: i32) -> bool {
+        self.abort_task(camera_id);
+        let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+        let ctrl = Arc::clone(self);
+        let id = camera_id.to_string();
+        let handle = tokio::spawn(async move {
+            ctrl.run_cooldown(&id, &[rung_c]).await;
+            let _ = done_tx.send(());
+        });
+        self.store_task(camera_id, handle);
+        // A dropped sender means the pass was aborted (a session stop or
+        // a newer cooling request superseded it).
+        if done_rx.await.is_err() {
+            return false;
+        }
+        self.rung_for(camera_id) == Some(rung_c)
+    }
+
+    /// The `max_cooldown` backstop bounding one cooldown pass — the
+    /// `total` `cool_camera` reports its progress against.
+    pub fn max_cooldown(&self) -> std::time::Duration {
+        self.config.max_cooldown
+    }
+
+    /// The configured ladder for `camera_id`, ascending; empty when the
+    /// camera is unknown or rp never cools it.
+    pub fn ladder_for(&self, camera_id: &str) -> Vec<i32> {
+        self.ladder_cameras()
+            .into_iter()
+            .find_map(|(id, ladder)| (id == camera_id).then_some(ladder))
+            .unwrap_or_default()
+    }
+
     /// Startup recovery: the camera driver, not rp, is the source of
     /// truth for cooler state. A cooler found on and regulating at a
     /// configured rung is re-adopted as-is (no re-selection — the rung
@@ -435,6 +478,7 @@ impl CoolingController {
     /// config load).
     fn ladder_cameras(&self) -> Vec<(String, Vec<i32>)> {
         self.equipment
+            .snapshot()
             .cameras
             .iter()
             .filter(|c| !c.config.cooler_targets_c.is_empty())
@@ -503,6 +547,7 @@ impl CoolingController {
 #[cfg(test)]
 pub(crate) mod test_support {
     use super::*;
+    use crate::equipment::EquipmentRegistry;
 
     use std::collections::HashMap;
     use std::time::Duration;
@@ -739,7 +784,11 @@ pub(crate) mod test_support {
         );
         let bus = Arc::new(EventBus::from_config(&[], None).unwrap());
         let rx = bus.subscribe();
-        let ctrl = Arc::new(CoolingController::new(Arc::new(registry), bus, config));
+        let ctrl = Arc::new(CoolingController::new(
+            SharedEquipment::new(registry),
+            bus,
+            config,
+        ));
         (ctrl, rx)
     }
 
@@ -760,6 +809,7 @@ mod tests {
     use super::test_support::*;
     use super::*;
     use crate::equipment::test_support::spawn_stub;
+    use crate::equipment::EquipmentRegistry;
 
     use std::time::Duration;
 
@@ -861,6 +911,53 @@ mod tests {
         );
     }
 
+    /// `cool_camera`'s pass: a chosen warm rung above the lowest is
+    /// commanded directly and held, not re-selected down the ladder.
+    #[tokio::test]
+    async fn cool_to_rung_stabilizes_at_the_requested_rung() {
+        let sim: Sim = Arc::new(Mutex::new(CoolerSim::new()));
+        let stub = spawn_stub(stub_router(sim.clone())).await;
+        let (ctrl, mut rx) = controller_for(&stub.url(), &[-10, 5]).await;
+
+        assert!(ctrl.cool_to_rung("main-cam", 5).await);
+
+        assert_eq!(ctrl.rung_for("main-cam"), Some(5));
+        assert_eq!(sim.lock().unwrap().setpoint_c, 5.0);
+        let events = drain(&mut rx);
+        let stabilized = events
+            .iter()
+            .find(|e| e.event == "cooler_stabilized")
+            .expect("cooler_stabilized must be emitted");
+        assert_eq!(stabilized.payload["target_c"], json!(5));
+    }
+
+    /// A requested rung below tonight's floor never snaps to another
+    /// rung — the pass ends uncooled and the call reports it.
+    #[tokio::test]
+    async fn cool_to_rung_below_the_floor_reports_unreachable() {
+        let sim: Sim = Arc::new(Mutex::new(CoolerSim::new()));
+        let stub = spawn_stub(stub_router(sim.clone())).await;
+        let (ctrl, mut rx) = controller_for(&stub.url(), &[-40, -10]).await;
+
+        assert!(!ctrl.cool_to_rung("main-cam", -40).await);
+
+        assert_eq!(ctrl.rung_for("main-cam"), None);
+        assert!(!sim.lock().unwrap().cooler_on);
+        assert!(drain(&mut rx)
+            .iter()
+            .any(|e| e.event == "cooler_unreachable"));
+    }
+
+    #[tokio::test]
+    async fn ladder_for_is_ascending_and_empty_for_unknown_cameras() {
+        let sim: Sim = Arc::new(Mutex::new(CoolerSim::new()));
+        let stub = spawn_stub(stub_router(sim)).await;
+        let (ctrl, _rx) = controller_for(&stub.url(), &[5, -10]).await;
+
+        assert_eq!(ctrl.ladder_for("main-cam"), vec![-10, 5]);
+        assert!(ctrl.ladder_for("no-such-cam").is_empty());
+    }
+
     #[tokio::test]
     async fn a_camera_without_the_capability_is_skipped() {
         let sim: Sim = Arc::new(Mutex::new(CoolerSim::new()));
@@ -1080,7 +1177,7 @@ mod tests {
         let bus = Arc::new(EventBus::from_config(&[], None).unwrap());
         let mut rx = bus.subscribe();
         let ctrl = Arc::new(CoolingController::new(
-            Arc::new(registry),
+            SharedEquipment::new(registry),
             bus,
             fast_config(),
         ));
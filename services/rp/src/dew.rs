@@ -0,0 +1,512 @@
+//! Dew-heater controller (rp.md § Dew Management).
+//!
+//! While a session is live, every `dew.poll_interval` the controller
+//! reads ambient `Temperature` and `DewPoint` from the configured
+//! ObservingConditions device and steps every heater. A heater with a
+//! temperature sensor runs a PI loop: the target is the dew point plus
+//! the heater's margin, the measured value is its sensor. A heater
+//! without one has nothing that responds to it, so it runs feed-forward
+//! on the ambient spread instead ([`feed_forward_duty`]). Either way the
+//! output is a duty cycle, clamped to the heater's
+//! `[min_duty_pct, max_duty_pct]` and written to its analog switch port
+//! on the port's own range and step grid. A write that changes the
+//! port's value emits `switch_changed`, as `set_switch` does; a step
+//! that re-writes the same value does not, so a steady heater stays
+//! quiet at every poll. A sensed heater pinned at its ceiling and still
+//! short of the target raises `dew_risk`, once per episode.
+//!
+//! [`SessionManager`](crate::session::SessionManager) starts the loop
+//! with the session and stops it — switching every heater it drove off
+//! — when the session ends; like the cooler, the heaters hold through a
+//! safety interruption. `do_capture` reads [`DewController::duties`] to
+//! stamp each exposure document.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+
+use tracing::{debug, warn};
+
+use crate::config::{DewConfig, DewHeaterConfig};
+use crate::equipment::SharedEquipment;
+use crate::events::EventBus;
+
+/// Per-heater loop state. `duty_pct` is the duty last written to the
+/// port (what `do_capture` records); `None` until the first write
+/// lands, and again after session end.
+#[derive(Default)]
+struct HeaterState {
+    /// Accumulated shortfall, °C·min.
+    integral: f64,
+    duty_pct: Option<f64>,
+    /// Inside a `dew_risk` episode: the event has fired and waits for
+    /// the margin to be held again before it may fire again.
+    at_risk: bool,
+}
+
+#[derive(Default)]
+struct DewState {
+    task: Option<tokio::task::JoinHandle<()>>,
+    heaters: HashMap<String, HeaterState>,
+}
+
+/// One ambient reading.
+#[derive(Debug, Clone, Copy)]
+struct Ambient {
+    temperature_c: f64,
+    dew_point_c: f64,
+}
+
+pub struct DewController {
+    equipment: SharedEquipment,
+    event_bus: Arc<EventBus>,
+    config: DewConfig,
+    state: Mutex<DewState>,
+}
+
+impl DewController {
+    pub fn new(equipment: SharedEquipment, event_bus: Arc<EventBus>, config: DewConfig) -> Self {
+        Self {
+            equipment,
+            event_bus,
+            config,
+            state: Mutex::new(DewState::default()),
+        }
+    }
+
+    /// Duty per heater id, percent, as last written — empty when the
+    /// loop is not running. Stamped on every exposure document.
+    pub fn duties(&self) -> BTreeMap<String, f64> {
+        self.lock_state()
+            .heaters
+            .iter()
+            .filter_map(|(id, heater)| Some((id.clone(), heater.duty_pct?)))
+            .collect()
+    }
+
+    /// Session start, resume, or recovery: start the loop. A no-op when
+    /// it is already running, so a resumed session keeps its integrals.
+    pub fn start(self: &Arc<Self>) {
+        let mut state = self.lock_state();
+        if state.task.as_ref().is_some_and(|t| !t.is_finished()) {
+            return;
+        }
+        let ctrl = Arc::clone(self);
+        state.task = Some(tokio::spawn(async move { ctrl.run().await }));
+        debug!(
+            heaters = self.config.heaters.len(),
+            "dew controller started"
+        );
+    }
+
+    /// Session end: stop the loop and switch off every heater it drove.
+    /// Heaters rp never wrote are untouched.
+    pub fn stop(self: &Arc<Self>) {
+        let driven: Vec<(DewHeaterConfig, f64)> = {
+            let mut state = self.lock_state();
+            if let Some(task) = state.task.take() {
+                task.abort();
+            }
+            let heaters = std::mem::take(&mut state.heaters);
+            self.config
+                .heaters
+                .iter()
+                .filter_map(|h| Some((h.clone(), heaters.get(&h.id)?.duty_pct?)))
+                .collect()
+        };
+        if driven.is_empty() {
+            return;
+        }
+        let ctrl = Arc::clone(self);
+        tokio::spawn(async move {
+            for (heater, duty_pct) in &driven {
+                match ctrl.write_duty(heater, 0.0, Some(*duty_pct)).await {
+                    Ok(_) => debug!(heater_id = %heater.id, "dew heater switched off"),
+                    Err(e) => {
+                        warn!(heater_id = %heater.id, error = %e, "failed to switch dew heater off")
+                    }
+                }
+            }
+        });
+    }
+
+    async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(self.config.poll_interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            self.tick().await;
+        }
+    }
+
+    /// One control step over every heater. An unreadable ambient
+    /// reading holds every duty where it is.
+    pub(crate) async fn tick(&self) {
+        let ambient = match self.read_ambient().await {
+            Ok(ambient) => ambient,
+            Err(e) => {
+                warn!(error = %e, "cannot read ambient conditions; dew heater duties held");
+                return;
+            }
+        };
+        let dt_min = self.config.poll_interval.as_secs_f64() / 60.0;
+        for heater in &self.config.heaters {
+            if let Err(e) = self.regulate(heater, ambient, dt_min).await {
+                warn!(heater_id = %heater.id, error = %e, "dew heater step failed; duty held");
+            }
+        }
+    }
+
+    async fn regulate(
+        &self,
+        heater: &DewHeaterConfig,
+        ambient: Ambient,
+        dt_min: f64,
+    ) -> Result<(), String> {
+        let Some(sensor) = &heater.temperature_sensor else {
+            return self.feed_forward(heater, ambient).await;
+        };
+        let temperature_c = self
+            .read_port_value(&sensor.switch_id, sensor.index)
+            .await
+            .map_err(|e| format!("temperature sensor: {e}"))?;
+        let target_c = ambient.dew_point_c + heater.margin_c;
+        let error_c = target_c - temperature_c;
+        if !error_c.is_finite() {
+            return Err(format!(
+                "non-finite reading (temperature {temperature_c}, dew point {})",
+                ambient.dew_point_c
+            ));
+        }
+        // The integral is committed only once the write lands, so a
+        // failing port does not wind it up.
+        let (mut integral, previous_pct) = self
+            .lock_state()
+            .heaters
+            .get(&heater.id)
+            .map_or((0.0, None), |h| (h.integral, h.duty_pct));
+        let duty_pct = pi_step(&mut integral, heater, error_c, dt_min);
+        let written_pct = self.write_duty(heater, duty_pct, previous_pct).await?;
+
+        let raise = {
+            let mut state = self.lock_state();
+            let entry = state.heaters.entry(heater.id.clone()).or_default();
+            entry.integral = integral;
+            entry.duty_pct = Some(written_pct);
+            let short = error_c > 0.0 && duty_pct >= heater.max_duty_pct;
+            let raise = short && !entry.at_risk;
+            if short {
+                entry.at_risk = true;
+            } else if error_c <= 0.0 {
+                entry.at_risk = false;
+            }
+            raise
+        };
+        debug!(heater_id = %heater.id, temperature_c, target_c, duty_pct = written_pct, "dew heater step");
+        if raise {
+            self.event_bus.emit(
+                "dew_risk",
+                serde_json::json!({
+                    "heater_id": heater.id,
+                    "switch_id": heater.switch_id,
+                    "index": heater.index,
+                    "temperature_c": temperature_c,
+                    "dew_point_c": ambient.dew_point_c,
+                    "target_c": target_c,
+                    "duty_pct": written_pct,
+                }),
+            );
+        }
+        Ok(())
+    }
+
+    /// Step for a heater without a temperature sensor. The ambient
+    /// temperature does not respond to the heater, so a PI loop on it
+    /// would only wind up, and a `dew_risk` on it would fire every damp
+    /// night with the optic itself possibly well clear — neither runs
+    /// here.
+    async fn feed_forward(&self, heater: &DewHeaterConfig, ambient: Ambient) -> Result<(), String> {
+        let spread_c = ambient.temperature_c - ambient.dew_point_c;
+        if !spread_c.is_finite() {
+            return Err(format!(
+                "non-finite reading (temperature {}, dew point {})",
+                ambient.temperature_c, ambient.dew_point_c
+            ));
+        }
+        let previous_pct = self
+            .lock_state()
+            .heaters
+            .get(&heater.id)
+            .and_then(|h| h.duty_pct);
+        let written_pct = self
+            .write_duty(heater, feed_forward_duty(heater, spread_c), previous_pct)
+            .await?;
+        self.lock_state()
+            .heaters
+            .entry(heater.id.clone())
+            .or_default()
+            .duty_pct = Some(written_pct);
+        debug!(heater_id = %heater.id, spread_c, duty_pct = written_pct, "dew heater feed-forward step");
+        Ok(())
+    }
+
+    async fn read_ambient(&self) -> Result<Ambient, String> {
+        let id = &self.config.observing_conditions_id;
+        let entry = self
+            .equipment
+            .find_observing_conditions(id)
+            .ok_or_else(|| format!("observing conditions not found: {id}"))?;
+        let oc = entry
+            .device
+            .ok_or_else(|| format!("observing conditions not connected: {id}"))?;
+        let temperature_c = oc
+            .temperature()
+            .await
+            .map_err(|e| format!("failed to read temperature: {e}"))?;
+        let dew_point_c = oc
+            .dew_point()
+            .await
+            .map_err(|e| format!("failed to read dew point: {e}"))?;
+        Ok(Ambient {
+            temperature_c,
+            dew_point_c,
+        })
+    }
+
+    async fn read_port_value(&self, switch_id: &str, index: usize) -> Result<f64, String> {
+        let switch = self.switch(switch_id)?;
+        switch
+            .get_switch_value(index)
+            .await
+            .map_err(|e| format!("failed to read switch '{switch_id}' port {index}: {e}"))
+    }
+
+    /// Write `duty_pct` to the heater's port, mapped onto the port's
+    /// range and snapped to its step. Returns the duty actually
+    /// written, after the snap; `previous_pct` is the duty last written,
+    /// and `switch_changed` is emitted only when the new one differs.
+    async fn write_duty(
+        &self,
+        heater: &DewHeaterConfig,
+        duty_pct: f64,
+        previous_pct: Option<f64>,
+    ) -> Result<f64, String> {
+        let switch = self.switch(&heater.switch_id)?;
+        let index = heater.index;
+        let read = |what: &str, e: ascom_alpaca::ASCOMError| {
+            format!(
+                "failed to read switch '{}' port {index} {what}: {e}",
+                heater.switch_id
+            )
+        };
+        let min = switch
+            .min_switch_value(index)
+            .await
+            .map_err(|e| read("min", e))?;
+        let max = switch
+            .max_switch_value(index)
+            .await
+            .map_err(|e| read("max", e))?;
+        let step = switch
+            .switch_step(index)
+            .await
+            .map_err(|e| read("step", e))?;
+        let value = port_value(duty_pct, min, max, step);
+        switch.set_switch_value(index, value).await.map_err(|e| {
+            format!(
+                "failed to set switch '{}' port {index}: {e}",
+                heater.switch_id
+            )
+        })?;
+        let written_pct = duty_of(value, min, max);
+        if previous_pct.is_none_or(|p| (p - written_pct).abs() > f64::EPSILON) {
+            self.emit_switch_changed(heater, &switch).await;
+        }
+        Ok(written_pct)
+    }
+
+    /// `switch_changed` for a heater write, in `set_switch`'s shape —
+    /// name, state and value read back from the port — plus the
+    /// `heater_id` that drove it. The write has already landed, so a
+    /// failed read-back only costs the event.
+    async fn emit_switch_changed(
+        &self,
+        heater: &DewHeaterConfig,
+        switch: &Arc<dyn ascom_alpaca::api::Switch>,
+    ) {
+        let index = heater.index;
+        let read_back = async {
+            Ok::<_, ascom_alpaca::ASCOMError>((
+                switch.get_switch_name(index).await?,
+                switch.get_switch(index).await?,
+                switch.get_switch_value(index).await?,
+            ))
+        };
+        match read_back.await {
+            Ok((name, state, value)) => self.event_bus.emit(
+                "switch_changed",
+                serde_json::json!({
+                    "switch_id": heater.switch_id,
+                    "index": index,
+                    "name": name,
+                    "state": state,
+                    "value": value,
+                    "heater_id": heater.id,
+                }),
+            ),
+            Err(e) => {
+                warn!(heater_id = %heater.id, error = %e, "failed to read back dew heater port; switch_changed not emitted")
+            }
+        }
+    }
+
+    fn switch(&self, switch_id: &str) -> Result<Arc<dyn ascom_alpaca::api::Switch>, String> {
+        self.equipment
+            .find_switch(switch_id)
+            .ok_or_else(|| format!("switch not found: {switch_id}"))?
+            .device
+            .ok_or_else(|| format!("switch not connected: {switch_id}"))
+    }
+
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, DewState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// One PI update; returns the duty, percent, clamped to the heater's
+/// limits. `integral` (°C·min) advances by `error_c × dt_min` only
+/// while the output is not pinned at the limit the error pushes toward
+/// — conditional integration, so a heater held at full power through a
+/// long damp spell does not wind up and overshoot once it clears.
+fn pi_step(integral: &mut f64, heater: &DewHeaterConfig, error_c: f64, dt_min: f64) -> f64 {
+    let candidate = *integral + error_c * dt_min;
+    let output = heater.kp * error_c + heater.ki * candidate;
+    let pinned_high = output > heater.max_duty_pct && error_c > 0.0;
+    let pinned_low = output < heater.min_duty_pct && error_c < 0.0;
+    if !(pinned_high || pinned_low) {
+        *integral = candidate;
+    }
+    (heater.kp * error_c + heater.ki * *integral).clamp(heater.min_duty_pct, heater.max_duty_pct)
+}
+
+/// Feed-forward duty, percent, for a heater without a temperature
+/// sensor: `min_duty_pct` while the ambient spread (temperature − dew
+/// point, °C) is at least `margin_c`, ramping linearly up to
+/// `max_duty_pct` as the spread closes to zero, and held there below
+/// it. A zero margin switches straight from floor to ceiling at zero
+/// spread.
+fn feed_forward_duty(heater: &DewHeaterConfig, spread_c: f64) -> f64 {
+    let closed = if heater.margin_c > 0.0 {
+        ((heater.margin_c - spread_c) / heater.margin_c).clamp(0.0, 1.0)
+    } else if spread_c > 0.0 {
+        0.0
+    } else {
+        1.0
+    };
+    heater.min_duty_pct + closed * (heater.max_duty_pct - heater.min_duty_pct)
+}
+
+/// The port value for `duty_pct` on a `[min, max]` port with `step`
+/// (0 = continuous): the nearest grid point, or the one below it when
+/// the nearest would overshoot `max`.
+fn port_value(duty_pct: f64, min: f64, max: f64, step: f64) -> f64 {
+    let raw = min + duty_pct / 100.0 * (max - min);
+    if step <= 0.0 {
+        return raw.clamp(min, max.max(min));
+    }
+    let mut steps = ((raw - min) / step).round();
+    if min + steps * step > max {
+        steps -= 1.0;
+    }
+    min + steps.max(0.0) * step
+}
+
+/// The duty, percent, a port value represents.
+fn duty_of(value: f64, min: f64, max: f64) -> f64 {
+    if max > min {
+        (value - min) / (max - min) * 100.0
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn heater() -> DewHeaterConfig {
+        DewHeaterConfig {
+            id: "main".to_string(),
+            switch_id: "ppba".to_string(),
+            index: 2,
+            margin_c: 3.0,
+            kp: 10.0,
+            ki: 2.0,
+            min_duty_pct: 0.0,
+            max_duty_pct: 100.0,
+            temperature_sensor: None,
+        }
+    }
+
+    #[test]
+    fn pi_step_adds_proportional_and_integral_terms() {
+        let mut integral = 0.0;
+        // 2 °C short for half a minute: P = 20, I = 2 × (2 × 0.5) = 2.
+        let duty = pi_step(&mut integral, &heater(), 2.0, 0.5);
+        assert!((duty - 22.0).abs() < 1e-9);
+        assert!((integral - 1.0).abs() < 1e-9);
+        let duty = pi_step(&mut integral, &heater(), 2.0, 0.5);
+        assert!((duty - 24.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pi_step_does_not_wind_up_while_pinned_at_the_ceiling() {
+        let mut integral = 0.0;
+        let duty = pi_step(&mut integral, &heater(), 12.0, 0.5);
+        assert!((duty - 100.0).abs() < 1e-9);
+        assert!(integral.abs() < 1e-9);
+    }
+
+    #[test]
+    fn pi_step_clamps_to_the_heater_limits() {
+        let h = DewHeaterConfig {
+            min_duty_pct: 10.0,
+            max_duty_pct: 60.0,
+            ..heater()
+        };
+        let mut integral = 0.0;
+        assert!((pi_step(&mut integral, &h, -5.0, 0.5) - 10.0).abs() < 1e-9);
+        assert!((pi_step(&mut integral, &h, 8.0, 0.5) - 60.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn feed_forward_duty_ramps_across_the_margin() {
+        let h = DewHeaterConfig {
+            min_duty_pct: 10.0,
+            max_duty_pct: 70.0,
+            ..heater()
+        };
+        assert!((feed_forward_duty(&h, 5.0) - 10.0).abs() < 1e-9);
+        assert!((feed_forward_duty(&h, 3.0) - 10.0).abs() < 1e-9);
+        assert!((feed_forward_duty(&h, 1.0) - 50.0).abs() < 1e-9);
+        assert!((feed_forward_duty(&h, -2.0) - 70.0).abs() < 1e-9);
+        let h = DewHeaterConfig {
+            margin_c: 0.0,
+            ..heater()
+        };
+        assert!(feed_forward_duty(&h, 0.5).abs() < 1e-9);
+        assert!((feed_forward_duty(&h, 0.0) - 100.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn port_value_maps_duty_onto_the_port_range_and_step() {
+        assert!((port_value(22.0, 0.0, 100.0, 5.0) - 20.0).abs() < 1e-9);
+        assert!((port_value(50.0, 0.0, 255.0, 1.0) - 128.0).abs() < 1e-9);
+        assert!((port_value(100.0, 0.0, 100.0, 30.0) - 90.0).abs() < 1e-9);
+        assert!((port_value(100.0, 0.0, 100.0, 40.0) - 80.0).abs() < 1e-9);
+        assert!((port_value(33.3, 0.0, 1.0, 0.0) - 0.333).abs() < 1e-9);
+        assert!((duty_of(128.0, 0.0, 256.0) - 50.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,415 @@
+//! Dome slaving (rp.md § Dome Slaving): an rp-internal follower that
+//! keeps one dome's slit in front of the mount's optical axis.
+//!
+//! While enabled (`set_dome_slaving`), a background task re-reads the
+//! mount every `poll_interval` — sidereal time, RA/Dec, side of pier —
+//! turns that into the slit azimuth through
+//! [`rp_ephemeris::DomeGeometry`], and commands `SlewToAzimuth`
+//! whenever the shutter has drifted more than `tolerance_deg` from it.
+//! A parked or non-tracking mount has nothing to follow and counts as
+//! aligned.
+//!
+//! `do_capture` calls [`DomeSlaving::wait_aligned`] before an
+//! imaging-train exposure: it pokes the follower for a fresh
+//! evaluation and holds the exposure until the slit has caught up, up
+//! to `catch_up_timeout`. Like the motion gate, the hold delays the
+//! exposure rather than trailing it across the dome wall.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ascom_alpaca::api::telescope::PierSide;
+use ascom_alpaca::api::{Dome, Telescope};
+use rp_ephemeris::{DomeGeometry, SideOfPier};
+use tokio::sync::{watch, Notify};
+use tracing::debug;
+
+use crate::config::dome::DomeSlavingConfig;
+use crate::equipment::SharedEquipment;
+use crate::events::EventBus;
+
+/// The follower's latest evaluation. `pokes_seen` is the poke count
+/// read when the evaluation *started*, so a waiter can insist on one
+/// that began after its own poke — not one already in flight against
+/// a mount that has since moved.
+#[derive(Debug, Clone, Copy, Default)]
+struct Alignment {
+    pokes_seen: u64,
+    aligned: bool,
+}
+
+/// Wake-up channel from waiters to the follower: a counter plus the
+/// `Notify` that cuts the poll sleep short.
+#[derive(Default)]
+struct Poke {
+    count: AtomicU64,
+    notify: Notify,
+}
+
+impl Poke {
+    /// Ask for a fresh evaluation; returns the poke's number.
+    fn poke(&self) -> u64 {
+        let n = self.count.fetch_add(1, Ordering::SeqCst) + 1;
+        self.notify.notify_one();
+        n
+    }
+}
+
+/// The running follower for the one slaved dome. Dropping it stops
+/// the task.
+struct Follower {
+    dome_id: String,
+    catch_up_timeout: Duration,
+    status: watch::Receiver<Alignment>,
+    poke: Arc<Poke>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for Follower {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub struct DomeSlaving {
+    equipment: SharedEquipment,
+    event_bus: Arc<EventBus>,
+    active: Mutex<Option<Follower>>,
+}
+
+impl DomeSlaving {
+    pub fn new(equipment: SharedEquipment, event_bus: Arc<EventBus>) -> Self {
+        Self {
+            equipment,
+            event_bus,
+            active: Mutex::new(None),
+        }
+    }
+
+    /// Start following the mount with `dome_id`, replacing any dome
+    /// already slaved. The caller has checked the dome carries a
+    /// `slaving` block; this checks both devices are connected.
+    ///
+    /// # Errors
+    ///
+    /// A message when the dome or the mount is not connected.
+    pub fn enable(
+        &self,
+        dome_id: &str,
+        config: &DomeSlavingConfig,
+        latitude_deg: f64,
+    ) -> Result<(), String> {
+        let dome = self
+            .equipment
+            .find_dome(dome_id)
+            .and_then(|d| d.device.clone())
+            .ok_or_else(|| format!("dome not connected: {dome_id}"))?;
+        let mount = self
+            .equipment
+            .find_mount()
+            .and_then(|m| m.device.clone())
+            .ok_or_else(|| "mount not connected".to_string())?;
+
+        let (tx, status) = watch::channel(Alignment::default());
+        let poke = Arc::new(Poke::default());
+        let follow = Follow {
+            dome_id: dome_id.to_string(),
+            dome,
+            mount,
+            geometry: DomeGeometry {
+                radius_m: config.dome_radius_m,
+                mount_offset_east_m: config.mount_offset_east_m,
+                mount_offset_north_m: config.mount_offset_north_m,
+                mount_offset_up_m: config.mount_offset_up_m,
+                gem_offset_m: config.gem_offset_m,
+            },
+            latitude_deg,
+            tolerance_deg: config.tolerance_deg,
+            poll_interval: config.poll_interval,
+            event_bus: self.event_bus.clone(),
+        };
+        let task = tokio::spawn(follow.run(tx, poke.clone()));
+        debug!(dome_id, "dome slaving enabled");
+        let previous = self.lock().replace(Follower {
+            dome_id: dome_id.to_string(),
+            catch_up_timeout: config.catch_up_timeout,
+            status,
+            poke,
+            task,
+        });
+        drop(previous);
+        Ok(())
+    }
+
+    /// Stop following. Returns the dome that was slaved, if any. The
+    /// dome stays wherever it is.
+    pub fn disable(&self) -> Option<String> {
+        let previous = self.lock().take();
+        previous.map(|f| {
+            debug!(dome_id = %f.dome_id, "dome slaving disabled");
+            f.dome_id.clone()
+        })
+    }
+
+    /// The currently slaved dome, if any.
+    pub fn slaved_dome(&self) -> Option<String> {
+        self.lock().as_ref().map(|f| f.dome_id.clone())
+    }
+
+    /// Hold until the slaved dome's slit is in front of the mount's
+    /// current pointing. Returns immediately when no dome is slaved;
+    /// a follower stopped mid-wait (slaving disabled) releases the
+    /// waiter too.
+    ///
+    /// # Errors
+    ///
+    /// A message naming the dome when the slit has not caught up
+    /// within the dome's `catch_up_timeout`.
+    pub async fn wait_aligned(&self) -> Result<(), String> {
+        let (mut status, poke, timeout, dome_id) = {
+            let guard = self.lock();
+            let Some(f) = guard.as_ref() else {
+                return Ok(());
+            };
+            (
+                f.status.clone(),
+                f.poke.clone(),
+                f.catch_up_timeout,
+                f.dome_id.clone(),
+            )
+        };
+        let ticket = poke.poke();
+        let fresh_and_aligned = status.wait_for(|a| a.pokes_seen >= ticket && a.aligned);
+        match tokio::time::timeout(timeout, fresh_and_aligned).await {
+            Ok(_) => Ok(()),
+            Err(_) => Err(format!(
+                "dome {dome_id}: slit did not catch up with the mount within {}",
+                humantime::format_duration(timeout)
+            )),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Option<Follower>> {
+        // A poisoned lock only means a panic elsewhere mid-update; the
+        // `Option` inside is still coherent.
+        self.active
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Everything the follower task owns.
+struct Follow {
+    dome_id: String,
+    dome: Arc<dyn Dome>,
+    mount: Arc<dyn Telescope>,
+    geometry: DomeGeometry,
+    latitude_deg: f64,
+    tolerance_deg: f64,
+    poll_interval: Duration,
+    event_bus: Arc<EventBus>,
+}
+
+/// What one evaluation asks the follower to do.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Action {
+    /// Within tolerance.
+    Aligned,
+    /// Out of tolerance, and the dome is already on its way to this
+    /// target.
+    Wait,
+    /// Out of tolerance: command a slew to the target.
+    Slew,
+}
+
+/// Decide from the slit azimuth the mount needs, the dome's reported
+/// azimuth, whether it is moving, and the last azimuth the follower
+/// commanded. A dome already slewing to (near enough) the current
+/// target is left alone; one slewing to a stale target is re-aimed.
+fn decide(
+    target: f64,
+    current: f64,
+    slewing: bool,
+    last_commanded: Option<f64>,
+    tolerance: f64,
+) -> Action {
+    if angular_distance(target, current) <= tolerance {
+        return Action::Aligned;
+    }
+    let heading_there = last_commanded.is_some_and(|c| angular_distance(c, target) <= tolerance);
+    if slewing && heading_there {
+        Action::Wait
+    } else {
+        Action::Slew
+    }
+}
+
+/// Shortest distance between two azimuths, degrees in `[0, 180]`.
+fn angular_distance(a: f64, b: f64) -> f64 {
+    180.0 - ((a - b).rem_euclid(360.0) - 180.0).abs()
+}
+
+impl Follow {
+    async fn run(self, tx: watch::Sender<Alignment>, poke: Arc<Poke>) {
+        let mut catching_up = false;
+        let mut last_commanded = None;
+        loop {
+            let pokes_seen = poke.count.load(Ordering::SeqCst);
+            let aligned = match self.evaluate(&mut last_commanded).await {
+                Ok(Some((Action::Aligned, _, current))) => {
+                    if catching_up {
+                        catching_up = false;
+                        debug!(dome_id = %self.dome_id, azimuth = current, "dome slit aligned");
+                        self.event_bus.emit(
+                            "dome_slit_aligned",
+                            serde_json::json!({
+                                "dome_id": self.dome_id,
+                                "azimuth": current,
+                            }),
+                        );
+                    }
+                    true
+                }
+                Ok(Some((_, target, current))) => {
+                    if !catching_up {
+                        catching_up = true;
+                        debug!(dome_id = %self.dome_id, target, current, "dome slit catching up");
+                        self.event_bus.emit(
+                            "dome_slit_catching_up",
+                            serde_json::json!({
+                                "dome_id": self.dome_id,
+                                "target_azimuth": target,
+                                "dome_azimuth": current,
+                            }),
+                        );
+                    }
+                    false
+                }
+                // Parked or not tracking: nothing to follow.
+                Ok(None) => true,
+                Err(e) => {
+                    debug!(dome_id = %self.dome_id, error = %e, "dome slaving evaluation failed");
+                    false
+                }
+            };
+            tx.send_replace(Alignment {
+                pokes_seen,
+                aligned,
+            });
+            tokio::select! {
+                () = tokio::time::sleep(self.poll_interval) => {}
+                () = poke.notify.notified() => {}
+            }
+        }
+    }
+
+    /// One evaluation: `None` when the mount has nothing to follow,
+    /// otherwise the action taken with the target and dome azimuths.
+    async fn evaluate(
+        &self,
+        last_commanded: &mut Option<f64>,
+    ) -> Result<Option<(Action, f64, f64)>, String> {
+        let parked = self
+            .mount
+            .at_park()
+            .await
+            .map_err(|e| format!("mount at_park: {e}"))?;
+        let tracking = self
+            .mount
+            .tracking()
+            .await
+            .map_err(|e| format!("mount tracking: {e}"))?;
+        if parked || !tracking {
+            return Ok(None);
+        }
+        let lst = self
+            .mount
+            .sidereal_time()
+            .await
+            .map_err(|e| format!("mount sidereal_time: {e}"))?;
+        let ra = self
+            .mount
+            .right_ascension()
+            .await
+            .map_err(|e| format!("mount right_ascension: {e}"))?;
+        let dec = self
+            .mount
+            .declination()
+            .await
+            .map_err(|e| format!("mount declination: {e}"))?;
+        // A mount that cannot report its pier side is treated as
+        // centered on the RA axis: the pier offset still applies.
+        let side = match self.mount.side_of_pier().await {
+            Ok(PierSide::East) => SideOfPier::East,
+            Ok(PierSide::West) => SideOfPier::West,
+            _ => SideOfPier::Unknown,
+        };
+        let hour_angle = crate::planner::decision::signed_hour_angle(lst, ra);
+        let target = self
+            .geometry
+            .slit_azimuth(self.latitude_deg, hour_angle, dec, side)
+            .ok_or_else(|| "slaving geometry places the mount outside the dome".to_string())?;
+
+        let current = self
+            .dome
+            .azimuth()
+            .await
+            .map_err(|e| format!("dome azimuth: {e}"))?;
+        let slewing = self
+            .dome
+            .slewing()
+            .await
+            .map_err(|e| format!("dome slewing: {e}"))?;
+        let action = decide(
+            target,
+            current,
+            slewing,
+            *last_commanded,
+            self.tolerance_deg,
+        );
+        if action == Action::Slew {
+            self.dome
+                .slew_to_azimuth(target)
+                .await
+                .map_err(|e| format!("dome slew_to_azimuth: {e}"))?;
+            *last_commanded = Some(target);
+        }
+        Ok(Some((action, target, current)))
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn angular_distance_wraps_through_north() {
+        assert!((angular_distance(359.0, 1.0) - 2.0).abs() < 1e-9);
+        assert!((angular_distance(1.0, 359.0) - 2.0).abs() < 1e-9);
+        assert!((angular_distance(90.0, 270.0) - 180.0).abs() < 1e-9);
+        assert!(angular_distance(42.0, 42.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn within_tolerance_is_aligned_even_while_slewing() {
+        assert_eq!(decide(180.0, 182.0, true, None, 3.0), Action::Aligned);
+    }
+
+    #[test]
+    fn an_idle_dome_out_of_tolerance_is_slewed() {
+        assert_eq!(decide(180.0, 90.0, false, Some(180.0), 3.0), Action::Slew);
+    }
+
+    #[test]
+    fn a_dome_already_heading_to_the_target_is_left_alone() {
+        assert_eq!(decide(180.0, 90.0, true, Some(181.0), 3.0), Action::Wait);
+    }
+
+    #[test]
+    fn a_dome_heading_to_a_stale_target_is_re_aimed() {
+        assert_eq!(decide(180.0, 90.0, true, Some(120.0), 3.0), Action::Slew);
+        assert_eq!(decide(180.0, 90.0, true, None, 3.0), Action::Slew);
+    }
+}
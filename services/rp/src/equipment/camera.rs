@@ -22,6 +22,7 @@ use crate::config;
 /// `max_adu`-driven cache-variant choice and the document's `optics`
 /// block degrade gracefully) rather than refusing to register the
 /// camera.
+#[derive(Clone)]
 pub struct CameraEntry {
     pub id: String,
     pub connected: bool,
@@ -8,6 +8,7 @@ use super::alpaca::{
 };
 use crate::config;
 
+#[derive(Clone)]
 pub struct CoverCalibratorEntry {
     pub id: String,
     pub connected: bool,
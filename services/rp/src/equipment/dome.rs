@@ -8,6 +8,7 @@ use super::alpaca::{
 };
 use crate::config;
 
+#[derive(Clone)]
 pub struct DomeEntry {
     pub id: String,
     pub connected: bool,
@@ -116,6 +117,7 @@ mod tests {
             alpaca_url: url.to_string(),
             device_number,
             auth: None,
+            slaving: None,
         }
     }
 
@@ -8,6 +8,7 @@ use super::alpaca::{
 };
 use crate::config;
 
+#[derive(Clone)]
 pub struct FilterWheelEntry {
     pub id: String,
     pub connected: bool,
@@ -0,0 +1,1119 @@
+//! Runtime equipment changes (rp.md § Runtime Equipment Changes).
+//!
+//! [`SharedEquipment`] is the handle every consumer of the registry
+//! holds. It publishes an immutable [`EquipmentRegistry`] snapshot behind
+//! an `Arc`; a runtime change — `connect_device`, `disconnect_device`,
+//! `reload_equipment` — clones the current snapshot, rebuilds the
+//! affected entries through the same per-kind connect routines startup
+//! uses (so a camera re-reads MaxADU, pixel size and sensor size, and a
+//! mount is re-checked against the configured site), and swaps the new
+//! snapshot in. Work already in flight keeps the entry or device handle
+//! it resolved and is never touched: a swap only changes what the
+//! *next* lookup sees. Changes are serialized so two overlapping
+//! reloads cannot publish over each other.
+//!
+//! Disconnecting releases rp's handle on the device; like the rest of
+//! rp it never sends `Connected = false` to the driver, which may be
+//! shared with other Alpaca clients.
+//!
+//! A camera handle a change drops from the registry may still be
+//! exposing for the work that holds it, so the registry keeps a weak
+//! reference to it: [`SharedEquipment::released_cameras`] hands the
+//! ones still alive to the safety and `abort_session` teardowns, which
+//! abort them along with the listed cameras.
+
+use std::fmt;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError, RwLock, Weak};
+
+use ascom_alpaca::api::Camera;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use super::{
+    camera, check_mount_site, cover_calibrator, dome, filter_wheel, focuser, mount,
+    observing_conditions, rotator, safety_monitor, switch, CameraEntry, CoverCalibratorEntry,
+    DomeEntry, EquipmentRegistry, EquipmentStatus, FilterWheelEntry, FocuserEntry, MountEntry,
+    ObservingConditionsEntry, RotatorEntry, SafetyMonitorEntry, SwitchEntry,
+};
+use crate::config;
+use crate::error::{Result, RpError};
+
+/// Equipment kind, keyed like the config file's `equipment` block and
+/// `GET /api/equipment`. `mount` is singular and takes no id.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceKind {
+    Cameras,
+    FilterWheels,
+    CoverCalibrators,
+    Focusers,
+    SafetyMonitors,
+    Switches,
+    Rotators,
+    ObservingConditions,
+    Domes,
+    Mount,
+}
+
+impl DeviceKind {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Cameras => "cameras",
+            Self::FilterWheels => "filter_wheels",
+            Self::CoverCalibrators => "cover_calibrators",
+            Self::Focusers => "focusers",
+            Self::SafetyMonitors => "safety_monitors",
+            Self::Switches => "switches",
+            Self::Rotators => "rotators",
+            Self::ObservingConditions => "observing_conditions",
+            Self::Domes => "domes",
+            Self::Mount => "mount",
+        }
+    }
+}
+
+impl fmt::Display for DeviceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// What a runtime change did to one entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeAction {
+    /// The entry was rebuilt from its config and connected afresh.
+    Connect,
+    /// rp released its handle on the device; the entry stays listed.
+    Disconnect,
+    /// The entry left the config file and was dropped from the registry.
+    Remove,
+}
+
+/// One entry's before/after, reported by the MCP tools and turned into
+/// `device_connected` / `device_disconnected` events.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceChange {
+    pub kind: DeviceKind,
+    /// `None` for the mount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub action: ChangeAction,
+    pub was_connected: bool,
+    pub connected: bool,
+    /// Why a [`ChangeAction::Connect`] left the device disconnected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DeviceChange {
+    /// The `device_disconnected` event's `reason` when this change took
+    /// a connected device away, `None` otherwise.
+    #[must_use]
+    pub const fn disconnect_reason(&self) -> Option<&'static str> {
+        if !self.was_connected || self.connected {
+            return None;
+        }
+        Some(match self.action {
+            ChangeAction::Connect => "connect_failed",
+            ChangeAction::Disconnect => "requested",
+            ChangeAction::Remove => "removed",
+        })
+    }
+
+    fn label(&self) -> String {
+        match &self.id {
+            Some(id) => format!("{} '{id}'", self.kind),
+            None => self.kind.to_string(),
+        }
+    }
+}
+
+/// Where runtime changes read the equipment from: the config file rp
+/// was started from (`PUT /api/config` persists to it), plus the CA and
+/// site rp is *running* with — both are restart-only settings, so a
+/// reconnect verifies TLS and cross-checks the mount against the same
+/// values startup used.
+#[derive(Debug, Clone)]
+pub struct EquipmentSource {
+    pub config_path: PathBuf,
+    pub ca_cert_path: Option<PathBuf>,
+    pub site: Option<config::SiteConfig>,
+}
+
+impl EquipmentSource {
+    /// Read and validate the persisted config's `equipment` block — the
+    /// same load path as startup, so an invalid file is refused whole.
+    pub fn load_equipment(&self) -> Result<config::EquipmentConfig> {
+        config::load_config(&self.config_path).map(|config| config.equipment)
+    }
+}
+
+/// Dispatch on a [`DeviceKind`]: the listed kinds run `$body` with
+/// `$entry` aliased to their entry type, the mount runs `$mount`.
+macro_rules! with_listed {
+    ($kind:expr, $entry:ident => $body:expr, mount => $mount:expr $(,)?) => {
+        match $kind {
+            DeviceKind::Cameras => {
+                type $entry = CameraEntry;
+                $body
+            }
+            DeviceKind::FilterWheels => {
+                type $entry = FilterWheelEntry;
+                $body
+            }
+            DeviceKind::CoverCalibrators => {
+                type $entry = CoverCalibratorEntry;
+                $body
+            }
+            DeviceKind::Focusers => {
+                type $entry = FocuserEntry;
+                $body
+            }
+            DeviceKind::SafetyMonitors => {
+                type $entry = SafetyMonitorEntry;
+                $body
+            }
+            DeviceKind::Switches => {
+                type $entry = SwitchEntry;
+                $body
+            }
+            DeviceKind::Rotators => {
+                type $entry = RotatorEntry;
+                $body
+            }
+            DeviceKind::ObservingConditions => {
+                type $entry = ObservingConditionsEntry;
+                $body
+            }
+            DeviceKind::Domes => {
+                type $entry = DomeEntry;
+                $body
+            }
+            DeviceKind::Mount => $mount,
+        }
+    };
+}
+
+/// The shared, swappable equipment registry.
+#[derive(Clone)]
+pub struct SharedEquipment {
+    current: Arc<RwLock<Arc<EquipmentRegistry>>>,
+    changes: Arc<tokio::sync::Mutex<()>>,
+    /// Camera handles a published change dropped, by camera id.
+    released_cameras: Arc<Mutex<Vec<(String, Weak<dyn Camera>)>>>,
+}
+
+impl From<EquipmentRegistry> for SharedEquipment {
+    fn from(registry: EquipmentRegistry) -> Self {
+        Self::new(registry)
+    }
+}
+
+impl SharedEquipment {
+    #[must_use]
+    pub fn new(registry: EquipmentRegistry) -> Self {
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(registry))),
+            changes: Arc::new(tokio::sync::Mutex::new(())),
+            released_cameras: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// The registry as of now. Cheap (an `Arc` clone); later runtime
+    /// changes do not alter a snapshot already taken.
+    #[must_use]
+    pub fn snapshot(&self) -> Arc<EquipmentRegistry> {
+        self.current
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    fn publish(&self, registry: EquipmentRegistry) {
+        self.track_released_cameras(&self.snapshot(), &registry);
+        *self.current.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(registry);
+    }
+
+    /// Keep a weak handle on every camera device `previous` holds and
+    /// `next` no longer does — disconnected, replaced by a reconnect, or
+    /// dropped by a reload.
+    fn track_released_cameras(&self, previous: &EquipmentRegistry, next: &EquipmentRegistry) {
+        let mut released = self
+            .released_cameras
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        released.retain(|(_, device)| device.strong_count() > 0);
+        for camera in &previous.cameras {
+            let Some(device) = &camera.device else {
+                continue;
+            };
+            let kept = next
+                .cameras
+                .iter()
+                .any(|c| c.device.as_ref().is_some_and(|d| Arc::ptr_eq(d, device)));
+            if !kept {
+                released.push((camera.id.clone(), Arc::downgrade(device)));
+            }
+        }
+    }
+
+    /// Camera devices the registry no longer lists but work already in
+    /// flight still holds — a capture that resolved its camera before a
+    /// `disconnect_device` may still be exposing. The registry keeps no
+    /// strong reference, so a handle drops out of this list as soon as
+    /// its last holder finishes.
+    #[must_use]
+    pub fn released_cameras(&self) -> Vec<(String, Arc<dyn Camera>)> {
+        let mut released = self
+            .released_cameras
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        released.retain(|(_, device)| device.strong_count() > 0);
+        released
+            .iter()
+            .filter_map(|(id, device)| Some((id.clone(), device.upgrade()?)))
+            .collect()
+    }
+
+    #[must_use]
+    pub fn status(&self) -> EquipmentStatus {
+        self.snapshot().status()
+    }
+
+    // The lookups return the entry by value: a caller holding one keeps
+    // its device handle for as long as it needs it, whatever runtime
+    // change lands meanwhile.
+
+    #[must_use]
+    pub fn find_camera(&self, id: &str) -> Option<CameraEntry> {
+        self.snapshot().find_camera(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_filter_wheel(&self, id: &str) -> Option<FilterWheelEntry> {
+        self.snapshot().find_filter_wheel(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_cover_calibrator(&self, id: &str) -> Option<CoverCalibratorEntry> {
+        self.snapshot().find_cover_calibrator(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_focuser(&self, id: &str) -> Option<FocuserEntry> {
+        self.snapshot().find_focuser(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_safety_monitor(&self, id: &str) -> Option<SafetyMonitorEntry> {
+        self.snapshot().find_safety_monitor(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_switch(&self, id: &str) -> Option<SwitchEntry> {
+        self.snapshot().find_switch(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_rotator(&self, id: &str) -> Option<RotatorEntry> {
+        self.snapshot().find_rotator(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_observing_conditions(&self, id: &str) -> Option<ObservingConditionsEntry> {
+        self.snapshot().find_observing_conditions(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_dome(&self, id: &str) -> Option<DomeEntry> {
+        self.snapshot().find_dome(id).cloned()
+    }
+
+    #[must_use]
+    pub fn find_mount(&self) -> Option<MountEntry> {
+        self.snapshot().find_mount().cloned()
+    }
+
+    /// Rebuild one entry from `equipment` (the persisted config) and
+    /// connect it, replacing the registry's entry of that id or — for an
+    /// id the registry doesn't list yet — hot-adding it. Errors only when
+    /// the config has no such device; a device that fails to connect is
+    /// reported through [`DeviceChange::error`] and left listed as
+    /// disconnected, as at startup.
+    pub async fn connect_device(
+        &self,
+        kind: DeviceKind,
+        id: Option<&str>,
+        equipment: &config::EquipmentConfig,
+        ca_cert_path: Option<&Path>,
+        site: Option<&config::SiteConfig>,
+    ) -> Result<DeviceChange> {
+        let _changes = self.changes.lock().await;
+        let mut next = (*self.snapshot()).clone();
+        let change = with_listed!(
+            kind,
+            E => connect_listed::<E>(&mut next, required_id(kind, id)?, equipment, ca_cert_path).await?,
+            mount => connect_mount_entry(&mut next, equipment, ca_cert_path, site).await?,
+        );
+        self.publish(next);
+        log_change(&change);
+        Ok(change)
+    }
+
+    /// Release rp's handle on one listed device. The entry stays in the
+    /// registry, disconnected, until a `connect_device` or a reload that
+    /// finds its config changed. Disconnecting a device that is already
+    /// disconnected is a no-op change, not an error.
+    pub async fn disconnect_device(
+        &self,
+        kind: DeviceKind,
+        id: Option<&str>,
+    ) -> Result<DeviceChange> {
+        let _changes = self.changes.lock().await;
+        let mut next = (*self.snapshot()).clone();
+        let change = with_listed!(
+            kind,
+            E => disconnect_listed::<E>(&mut next, required_id(kind, id)?)?,
+            mount => disconnect_mount_entry(&mut next)?,
+        );
+        self.publish(next);
+        log_change(&change);
+        Ok(change)
+    }
+
+    /// Bring the registry in line with `equipment`: entries that left the
+    /// config are dropped, new and changed ones are connected, unchanged
+    /// ones are left exactly as they are (a disconnected one stays
+    /// disconnected — `connect_device` retries it). Entries end up in
+    /// config order. Returns one change per entry touched.
+    pub async fn reload_equipment(
+        &self,
+        equipment: &config::EquipmentConfig,
+        ca_cert_path: Option<&Path>,
+        site: Option<&config::SiteConfig>,
+    ) -> Vec<DeviceChange> {
+        let _changes = self.changes.lock().await;
+        let mut next = (*self.snapshot()).clone();
+        let mut changes = Vec::new();
+        reload_listed::<CameraEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_listed::<FilterWheelEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_listed::<CoverCalibratorEntry>(&mut next, equipment, ca_cert_path, &mut changes)
+            .await;
+        reload_listed::<FocuserEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_listed::<SafetyMonitorEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_listed::<SwitchEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_listed::<RotatorEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_listed::<ObservingConditionsEntry>(&mut next, equipment, ca_cert_path, &mut changes)
+            .await;
+        reload_listed::<DomeEntry>(&mut next, equipment, ca_cert_path, &mut changes).await;
+        reload_mount(&mut next, equipment, ca_cert_path, site, &mut changes).await;
+        self.publish(next);
+        for change in &changes {
+            log_change(change);
+        }
+        changes
+    }
+}
+
+fn log_change(change: &DeviceChange) {
+    match (change.action, change.connected, &change.error) {
+        (ChangeAction::Connect, true, _) => info!("{} connected", change.label()),
+        (ChangeAction::Connect, false, Some(e)) => warn!("{}: {e}", change.label()),
+        (ChangeAction::Connect, false, None) => warn!("{} did not connect", change.label()),
+        (ChangeAction::Disconnect, ..) => info!("{} disconnected on request", change.label()),
+        (ChangeAction::Remove, ..) => info!("{} removed from the registry", change.label()),
+    }
+}
+
+fn required_id(kind: DeviceKind, id: Option<&str>) -> Result<&str> {
+    id.ok_or_else(|| RpError::EquipmentNotFound(format!("{kind} requires an id")))
+}
+
+/// The id-keyed entry kinds, seen uniformly by the change routines.
+trait Listed: Clone + Send + Sync + 'static {
+    type Config: Serialize + Sync;
+    const KIND: DeviceKind;
+
+    fn id(&self) -> &str;
+    fn connected(&self) -> bool;
+    fn config(&self) -> &Self::Config;
+    /// The entry with rp's device handle (and anything read through it)
+    /// dropped.
+    fn released(&self) -> Self;
+    fn config_id(config: &Self::Config) -> &str;
+    fn configs(equipment: &config::EquipmentConfig) -> &[Self::Config];
+    fn entries(registry: &mut EquipmentRegistry) -> &mut Vec<Self>;
+    fn connect<'a>(
+        config: &'a Self::Config,
+        ca_cert_path: Option<&'a Path>,
+    ) -> impl Future<Output = Self> + Send + 'a;
+}
+
+macro_rules! listed_entry {
+    ($entry:ty, $config:ty, $kind:ident, $field:ident, $connect:path $(, $read:ident)*) => {
+        impl Listed for $entry {
+            type Config = $config;
+            const KIND: DeviceKind = DeviceKind::$kind;
+
+            fn id(&self) -> &str {
+                &self.id
+            }
+            fn connected(&self) -> bool {
+                self.connected
+            }
+            fn config(&self) -> &Self::Config {
+                &self.config
+            }
+            fn released(&self) -> Self {
+                Self {
+                    connected: false,
+                    device: None,
+                    $($read: None,)*
+                    ..self.clone()
+                }
+            }
+            fn config_id(config: &Self::Config) -> &str {
+                &config.id
+            }
+            fn configs(equipment: &config::EquipmentConfig) -> &[Self::Config] {
+                &equipment.$field
+            }
+            fn entries(registry: &mut EquipmentRegistry) -> &mut Vec<Self> {
+                &mut registry.$field
+            }
+            fn connect<'a>(
+                config: &'a Self::Config,
+                ca_cert_path: Option<&'a Path>,
+            ) -> impl Future<Output = Self> + Send + 'a {
+                $connect(config, ca_cert_path)
+            }
+        }
+    };
+}
+
+listed_entry!(
+    CameraEntry,
+    config::CameraConfig,
+    Cameras,
+    cameras,
+    camera::connect_camera,
+    max_adu,
+    pixel_size_x_um,
+    pixel_size_y_um,
+    sensor_width_px,
+    sensor_height_px
+);
+listed_entry!(
+    FilterWheelEntry,
+    config::FilterWheelConfig,
+    FilterWheels,
+    filter_wheels,
+    filter_wheel::connect_filter_wheel
+);
+listed_entry!(
+    CoverCalibratorEntry,
+    config::CoverCalibratorConfig,
+    CoverCalibrators,
+    cover_calibrators,
+    cover_calibrator::connect_cover_calibrator
+);
+listed_entry!(
+    FocuserEntry,
+    config::FocuserConfig,
+    Focusers,
+    focusers,
+    focuser::connect_focuser
+);
+listed_entry!(
+    SafetyMonitorEntry,
+    config::SafetyMonitorConfig,
+    SafetyMonitors,
+    safety_monitors,
+    safety_monitor::connect_safety_monitor
+);
+listed_entry!(
+    SwitchEntry,
+    config::SwitchConfig,
+    Switches,
+    switches,
+    switch::connect_switch
+);
+listed_entry!(
+    RotatorEntry,
+    config::RotatorConfig,
+    Rotators,
+    rotators,
+    rotator::connect_rotator
+);
+listed_entry!(
+    ObservingConditionsEntry,
+    config::ObservingConditionsConfig,
+    ObservingConditions,
+    observing_conditions,
+    observing_conditions::connect_observing_conditions
+);
+listed_entry!(
+    DomeEntry,
+    config::DomeConfig,
+    Domes,
+    domes,
+    dome::connect_dome
+);
+
+/// Config equality for the reload diff. The device configs carry no
+/// `PartialEq` (several hold durations and secrets), so compare their
+/// serialized form; a config that won't serialize counts as changed.
+fn same_config<C: Serialize>(a: &C, b: &C) -> bool {
+    match (serde_json::to_value(a), serde_json::to_value(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn connect_error(kind: DeviceKind, id: Option<&str>) -> String {
+    let label = id.map_or_else(|| kind.to_string(), |id| format!("{kind} '{id}'"));
+    format!("{label} did not connect; rp's log has the cause")
+}
+
+/// Put `entry` in place of the entry with its id (or append it), then
+/// restore config order; entries the config no longer lists keep their
+/// relative order at the end.
+fn place<E: Listed>(entries: &mut Vec<E>, entry: E, configs: &[E::Config]) {
+    match entries.iter_mut().find(|e| e.id() == entry.id()) {
+        Some(slot) => *slot = entry,
+        None => entries.push(entry),
+    }
+    entries.sort_by_key(|e| {
+        configs
+            .iter()
+            .position(|c| E::config_id(c) == e.id())
+            .unwrap_or(usize::MAX)
+    });
+}
+
+async fn connect_listed<E: Listed>(
+    registry: &mut EquipmentRegistry,
+    id: &str,
+    equipment: &config::EquipmentConfig,
+    ca_cert_path: Option<&Path>,
+) -> Result<DeviceChange> {
+    let configs = E::configs(equipment);
+    let config = configs
+        .iter()
+        .find(|c| E::config_id(c) == id)
+        .ok_or_else(|| {
+            RpError::EquipmentNotFound(format!("{} '{id}' is not in the config file", E::KIND))
+        })?;
+    let entries = E::entries(registry);
+    let was_connected = entries.iter().any(|e| e.id() == id && e.connected());
+    let entry = E::connect(config, ca_cert_path).await;
+    let connected = entry.connected();
+    place(entries, entry, configs);
+    Ok(DeviceChange {
+        kind: E::KIND,
+        id: Some(id.to_string()),
+        action: ChangeAction::Connect,
+        was_connected,
+        connected,
+        error: (!connected).then(|| connect_error(E::KIND, Some(id))),
+    })
+}
+
+fn disconnect_listed<E: Listed>(
+    registry: &mut EquipmentRegistry,
+    id: &str,
+) -> Result<DeviceChange> {
+    let entry = E::entries(registry)
+        .iter_mut()
+        .find(|e| e.id() == id)
+        .ok_or_else(|| RpError::EquipmentNotFound(format!("{} '{id}'", E::KIND)))?;
+    let was_connected = entry.connected();
+    *entry = entry.released();
+    Ok(DeviceChange {
+        kind: E::KIND,
+        id: Some(id.to_string()),
+        action: ChangeAction::Disconnect,
+        was_connected,
+        connected: false,
+        error: None,
+    })
+}
+
+async fn reload_listed<E: Listed>(
+    registry: &mut EquipmentRegistry,
+    equipment: &config::EquipmentConfig,
+    ca_cert_path: Option<&Path>,
+    changes: &mut Vec<DeviceChange>,
+) {
+    let configs = E::configs(equipment);
+    let entries = E::entries(registry);
+    entries.retain(|entry| {
+        let listed = configs.iter().any(|c| E::config_id(c) == entry.id());
+        if !listed {
+            changes.push(DeviceChange {
+                kind: E::KIND,
+                id: Some(entry.id().to_string()),
+                action: ChangeAction::Remove,
+                was_connected: entry.connected(),
+                connected: false,
+                error: None,
+            });
+        }
+        listed
+    });
+    for config in configs {
+        let id = E::config_id(config);
+        let current = entries.iter().find(|e| e.id() == id);
+        if current.is_some_and(|e| same_config(e.config(), config)) {
+            debug!("{} '{id}' unchanged; leaving it as it is", E::KIND);
+            continue;
+        }
+        let was_connected = current.is_some_and(E::connected);
+        let entry = E::connect(config, ca_cert_path).await;
+        let connected = entry.connected();
+        place(entries, entry, configs);
+        changes.push(DeviceChange {
+            kind: E::KIND,
+            id: Some(id.to_string()),
+            action: ChangeAction::Connect,
+            was_connected,
+            connected,
+            error: (!connected).then(|| connect_error(E::KIND, Some(id))),
+        });
+    }
+}
+
+/// Connect the mount and run the startup site cross-check on it. A
+/// mount that disagrees with the configured site aborts startup; at
+/// runtime it is left released instead, with the mismatch as the error.
+async fn connect_checked_mount(
+    config: &config::MountConfig,
+    ca_cert_path: Option<&Path>,
+    site: Option<&config::SiteConfig>,
+) -> (MountEntry, Option<String>) {
+    let entry = mount::connect_mount(config, ca_cert_path).await;
+    if !entry.connected {
+        return (entry, Some(connect_error(DeviceKind::Mount, None)));
+    }
+    if let Some(site) = site {
+        if let Err(e) = check_mount_site(&entry, site).await {
+            let released = MountEntry {
+                connected: false,
+                device: None,
+                ..entry
+            };
+            return (released, Some(e.to_string()));
+        }
+    }
+    (entry, None)
+}
+
+async fn connect_mount_entry(
+    registry: &mut EquipmentRegistry,
+    equipment: &config::EquipmentConfig,
+    ca_cert_path: Option<&Path>,
+    site: Option<&config::SiteConfig>,
+) -> Result<DeviceChange> {
+    let config = equipment
+        .mount
+        .as_ref()
+        .ok_or_else(|| RpError::EquipmentNotFound("mount is not in the config file".to_string()))?;
+    let was_connected = registry.mount.as_ref().is_some_and(|m| m.connected);
+    let (entry, error) = connect_checked_mount(config, ca_cert_path, site).await;
+    let connected = entry.connected;
+    registry.mount = Some(entry);
+    Ok(DeviceChange {
+        kind: DeviceKind::Mount,
+        id: None,
+        action: ChangeAction::Connect,
+        was_connected,
+        connected,
+        error,
+    })
+}
+
+fn disconnect_mount_entry(registry: &mut EquipmentRegistry) -> Result<DeviceChange> {
+    let entry = registry
+        .mount
+        .as_mut()
+        .ok_or_else(|| RpError::EquipmentNotFound("no mount configured".to_string()))?;
+    let was_connected = entry.connected;
+    entry.connected = false;
+    entry.device = None;
+    Ok(DeviceChange {
+        kind: DeviceKind::Mount,
+        id: None,
+        action: ChangeAction::Disconnect,
+        was_connected,
+        connected: false,
+        error: None,
+    })
+}
+
+async fn reload_mount(
+    registry: &mut EquipmentRegistry,
+    equipment: &config::EquipmentConfig,
+    ca_cert_path: Option<&Path>,
+    site: Option<&config::SiteConfig>,
+    changes: &mut Vec<DeviceChange>,
+) {
+    let Some(config) = &equipment.mount else {
+        if let Some(removed) = registry.mount.take() {
+            changes.push(DeviceChange {
+                kind: DeviceKind::Mount,
+                id: None,
+                action: ChangeAction::Remove,
+                was_connected: removed.connected,
+                connected: false,
+                error: None,
+            });
+        }
+        return;
+    };
+    if registry
+        .mount
+        .as_ref()
+        .is_some_and(|m| same_config(&m.config, config))
+    {
+        debug!("mount unchanged; leaving it as it is");
+        return;
+    }
+    let was_connected = registry.mount.as_ref().is_some_and(|m| m.connected);
+    let (entry, error) = connect_checked_mount(config, ca_cert_path, site).await;
+    let connected = entry.connected;
+    registry.mount = Some(entry);
+    changes.push(DeviceChange {
+        kind: DeviceKind::Mount,
+        id: None,
+        action: ChangeAction::Connect,
+        was_connected,
+        connected,
+        error,
+    });
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::equipment::test_support::spawn_stub;
+
+    use axum::{
+        routing::{get, put},
+        Json, Router,
+    };
+
+    /// An Alpaca server advertising one device of `device_type` at
+    /// index 0 that accepts `Connected = true`.
+    fn ok_device_router(device_type: &'static str) -> Router {
+        let connected = format!("/api/v1/{}/0/connected", device_type.to_lowercase());
+        Router::new()
+            .route(
+                "/management/v1/configureddevices",
+                get(move || async move {
+                    Json(serde_json::json!({
+                        "Value": [{
+                            "DeviceName": format!("{device_type} 0"),
+                            "DeviceType": device_type,
+                            "DeviceNumber": 0,
+                            "UniqueID": format!("test-{device_type}-uid")
+                        }],
+                        "ErrorNumber": 0,
+                        "ErrorMessage": ""
+                    }))
+                }),
+            )
+            .route(
+                &connected,
+                put(|| async { Json(serde_json::json!({ "ErrorNumber": 0, "ErrorMessage": "" })) }),
+            )
+    }
+
+    fn mount_router_with_site(lat: f64, lon: f64) -> Router {
+        ok_device_router("Telescope")
+            .route(
+                "/api/v1/telescope/0/sitelatitude",
+                get(move || async move {
+                    Json(serde_json::json!({ "Value": lat, "ErrorNumber": 0, "ErrorMessage": "" }))
+                }),
+            )
+            .route(
+                "/api/v1/telescope/0/sitelongitude",
+                get(move || async move {
+                    Json(serde_json::json!({ "Value": lon, "ErrorNumber": 0, "ErrorMessage": "" }))
+                }),
+            )
+    }
+
+    fn focuser_config(id: &str, url: &str) -> config::FocuserConfig {
+        config::FocuserConfig {
+            id: id.to_string(),
+            alpaca_url: url.to_string(),
+            device_number: 0,
+            min_position: None,
+            max_position: None,
+            steps_per_sec: Default::default(),
+            auth: None,
+        }
+    }
+
+    fn mount_config(url: &str) -> config::MountConfig {
+        config::MountConfig {
+            alpaca_url: url.to_string(),
+            device_number: 0,
+            settle_after_slew: None,
+            slew_rate_arcsec_per_sec: Default::default(),
+            guiding: None,
+            meridian_flip: Default::default(),
+            auth: None,
+        }
+    }
+
+    fn site(lat: f64, lon: f64) -> config::SiteConfig {
+        config::SiteConfig {
+            latitude_degrees: lat,
+            longitude_degrees: lon,
+            horizon: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_device_hot_adds_a_device_the_registry_does_not_list() {
+        let stub = spawn_stub(ok_device_router("Focuser")).await;
+        let shared = SharedEquipment::new(EquipmentRegistry::default());
+        let equipment = config::EquipmentConfig {
+            focusers: vec![focuser_config("new-focuser", &stub.url())],
+            ..Default::default()
+        };
+
+        let change = shared
+            .connect_device(
+                DeviceKind::Focusers,
+                Some("new-focuser"),
+                &equipment,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(change.action, ChangeAction::Connect);
+        assert!(change.connected && !change.was_connected);
+        assert!(change.error.is_none());
+        let entry = shared.find_focuser("new-focuser").unwrap();
+        assert!(entry.connected && entry.device.is_some());
+    }
+
+    #[tokio::test]
+    async fn connect_device_refuses_a_device_missing_from_the_config() {
+        let shared = SharedEquipment::new(EquipmentRegistry::default());
+        let err = shared
+            .connect_device(
+                DeviceKind::Focusers,
+                Some("ghost"),
+                &config::EquipmentConfig::default(),
+                None,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, RpError::EquipmentNotFound(_)), "{err}");
+        assert!(err.to_string().contains("not in the config file"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn a_failed_connect_is_reported_and_left_listed_disconnected() {
+        let shared = SharedEquipment::new(EquipmentRegistry::default());
+        let equipment = config::EquipmentConfig {
+            focusers: vec![focuser_config("main-focuser", "not-a-url")],
+            ..Default::default()
+        };
+        let change = shared
+            .connect_device(
+                DeviceKind::Focusers,
+                Some("main-focuser"),
+                &equipment,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(!change.connected);
+        assert!(change.error.unwrap().contains("did not connect"));
+        assert!(!shared.find_focuser("main-focuser").unwrap().connected);
+    }
+
+    #[tokio::test]
+    async fn disconnect_device_leaves_a_held_entry_usable() {
+        let stub = spawn_stub(ok_device_router("Focuser")).await;
+        let equipment = config::EquipmentConfig {
+            focusers: vec![focuser_config("main-focuser", &stub.url())],
+            ..Default::default()
+        };
+        let shared = SharedEquipment::new(EquipmentRegistry::new(&equipment, None).await);
+        // An operation already in flight resolved the entry before the
+        // disconnect landed.
+        let in_flight = shared.find_focuser("main-focuser").unwrap();
+
+        let change = shared
+            .disconnect_device(DeviceKind::Focusers, Some("main-focuser"))
+            .await
+            .unwrap();
+
+        assert_eq!(change.disconnect_reason(), Some("requested"));
+        assert!(in_flight.connected && in_flight.device.is_some());
+        let now = shared.find_focuser("main-focuser").unwrap();
+        assert!(!now.connected && now.device.is_none());
+        assert_eq!(shared.status().focusers[0].id, "main-focuser");
+    }
+
+    #[tokio::test]
+    async fn a_released_camera_is_reported_while_in_flight_work_holds_it() {
+        let stub = spawn_stub(ok_device_router("Camera")).await;
+        let equipment = config::EquipmentConfig {
+            cameras: vec![config::CameraConfig {
+                id: "main-cam".to_string(),
+                name: "main-cam".to_string(),
+                alpaca_url: stub.url(),
+                device_type: String::new(),
+                device_number: 0,
+                cooler_targets_c: Vec::new(),
+                gain: None,
+                offset: None,
+                readout_time_estimate: None,
+                auth: None,
+            }],
+            ..Default::default()
+        };
+        let shared = SharedEquipment::new(EquipmentRegistry::new(&equipment, None).await);
+        // A capture resolved the camera before the disconnect landed.
+        let in_flight = shared.find_camera("main-cam").unwrap();
+        assert!(shared.released_cameras().is_empty());
+
+        shared
+            .disconnect_device(DeviceKind::Cameras, Some("main-cam"))
+            .await
+            .unwrap();
+
+        let released = shared.released_cameras();
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].0, "main-cam");
+        assert!(Arc::ptr_eq(
+            &released[0].1,
+            in_flight.device.as_ref().unwrap()
+        ));
+
+        // Once the capture lets go there is nothing left to abort.
+        drop(released);
+        drop(in_flight);
+        assert!(shared.released_cameras().is_empty());
+    }
+
+    #[tokio::test]
+    async fn disconnecting_an_unknown_device_is_an_error() {
+        let shared = SharedEquipment::new(EquipmentRegistry::default());
+        assert!(shared
+            .disconnect_device(DeviceKind::Cameras, Some("nope"))
+            .await
+            .is_err());
+        assert!(shared
+            .disconnect_device(DeviceKind::Mount, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn reload_drops_removed_adds_new_and_leaves_unchanged_entries_alone() {
+        let stub = spawn_stub(ok_device_router("Focuser")).await;
+        let before = config::EquipmentConfig {
+            focusers: vec![
+                focuser_config("kept", &stub.url()),
+                focuser_config("dropped", &stub.url()),
+            ],
+            ..Default::default()
+        };
+        let shared = SharedEquipment::new(EquipmentRegistry::new(&before, None).await);
+        let kept = shared.snapshot();
+
+        let after = config::EquipmentConfig {
+            focusers: vec![
+                focuser_config("added", &stub.url()),
+                focuser_config("kept", &stub.url()),
+            ],
+            ..Default::default()
+        };
+        let changes = shared.reload_equipment(&after, None, None).await;
+
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.id.clone().unwrap(), c.action))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("dropped".to_string(), ChangeAction::Remove),
+                ("added".to_string(), ChangeAction::Connect),
+            ]
+        );
+        assert_eq!(changes[0].disconnect_reason(), Some("removed"));
+        let ids: Vec<_> = shared.status().focusers.into_iter().map(|f| f.id).collect();
+        assert_eq!(ids, ["added", "kept"]);
+        // The unchanged entry is the same device handle, not a reconnect.
+        let old = kept.find_focuser("kept").unwrap().device.clone().unwrap();
+        let new = shared.find_focuser("kept").unwrap().device.unwrap();
+        assert!(Arc::ptr_eq(&old, &new));
+    }
+
+    #[tokio::test]
+    async fn reconnecting_the_mount_reruns_the_site_cross_check() {
+        let stub = spawn_stub(mount_router_with_site(48.6062, -122.3321)).await;
+        let equipment = config::EquipmentConfig {
+            mount: Some(mount_config(&stub.url())),
+            ..Default::default()
+        };
+        let shared = SharedEquipment::new(EquipmentRegistry::default());
+
+        let change = shared
+            .connect_device(
+                DeviceKind::Mount,
+                None,
+                &equipment,
+                None,
+                Some(&site(47.6062, -122.3321)),
+            )
+            .await
+            .unwrap();
+
+        assert!(!change.connected);
+        assert!(change.error.unwrap().contains("site mismatch"));
+        let mount = shared.find_mount().unwrap();
+        assert!(!mount.connected && mount.device.is_none());
+
+        let change = shared
+            .connect_device(
+                DeviceKind::Mount,
+                None,
+                &equipment,
+                None,
+                Some(&site(48.6062, -122.3321)),
+            )
+            .await
+            .unwrap();
+        assert!(change.connected, "{change:?}");
+    }
+
+    #[test]
+    fn device_kind_uses_the_equipment_config_keys() {
+        let kinds: DeviceKind = serde_json::from_value(serde_json::json!("filter_wheels")).unwrap();
+        assert_eq!(kinds, DeviceKind::FilterWheels);
+        assert_eq!(
+            DeviceKind::ObservingConditions.to_string(),
+            "observing_conditions"
+        );
+    }
+}
@@ -10,13 +10,18 @@
 //! basic-auth header, retry/backoff with `Permanent`/`Transient` outcomes)
 //! lives in [`alpaca`].
 //!
-//! `switch`, `rotator`, `observing_conditions`, and `dome` cover roster
-//! membership and connectivity status only (rp.md § Equipment Integration)
-//! — no MCP tool integration, unlike the other six kinds.
+//! This module only connects and rosters devices; the MCP tools that
+//! drive each kind live under `crate::mcp::built_in` (rp.md § Built-in
+//! Tools).
 //!
 //! The submodules' `*Entry` types and shared status types are
 //! re-exported here so existing `crate::equipment::CameraEntry` etc.
 //! callsites keep working unchanged.
+//!
+//! The running registry is shared through [`live::SharedEquipment`],
+//! which swaps in a rebuilt registry when a device is connected,
+//! disconnected or hot-added at runtime (rp.md § Runtime Equipment
+//! Changes).
 
 pub mod alpaca;
 pub mod camera;
@@ -24,6 +29,7 @@ pub mod cover_calibrator;
 pub mod dome;
 pub mod filter_wheel;
 pub mod focuser;
+pub mod live;
 pub mod mount;
 pub mod observing_conditions;
 pub mod rotator;
@@ -39,6 +45,7 @@ pub use cover_calibrator::CoverCalibratorEntry;
 pub use dome::DomeEntry;
 pub use filter_wheel::FilterWheelEntry;
 pub use focuser::FocuserEntry;
+pub use live::{ChangeAction, DeviceChange, DeviceKind, EquipmentSource, SharedEquipment};
 pub use mount::MountEntry;
 pub use observing_conditions::ObservingConditionsEntry;
 pub use rotator::RotatorEntry;
@@ -51,7 +58,7 @@ use tracing::debug;
 use crate::config;
 use crate::error::RpError;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct EquipmentRegistry {
     pub cameras: Vec<CameraEntry>,
     pub filter_wheels: Vec<FilterWheelEntry>,
@@ -337,56 +344,66 @@ impl EquipmentRegistry {
             debug!("no mount configured; skipping mount-side site validation");
             return Ok(());
         };
-        if !mount.connected {
-            debug!("mount not connected; skipping mount-side site validation");
+        check_mount_site(mount, site).await
+    }
+}
+
+/// The per-mount half of [`EquipmentRegistry::validate_site`], shared
+/// with the runtime reconnect path in [`live`] so a re-connected mount
+/// gets the same cross-check it got at startup.
+pub(crate) async fn check_mount_site(
+    mount: &MountEntry,
+    site: &config::SiteConfig,
+) -> crate::error::Result<()> {
+    if !mount.connected {
+        debug!("mount not connected; skipping mount-side site validation");
+        return Ok(());
+    }
+    let Some(t) = mount.device.as_ref() else {
+        debug!("mount entry has no device handle; skipping site validation");
+        return Ok(());
+    };
+
+    let mount_lat = match t.site_latitude().await {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(
+                error = %e,
+                "mount did not report SiteLatitude; skipping mount-side site validation"
+            );
             return Ok(());
         }
-        let Some(t) = mount.device.as_ref() else {
-            debug!("mount entry has no device handle; skipping site validation");
+    };
+    let mount_lon = match t.site_longitude().await {
+        Ok(v) => v,
+        Err(e) => {
+            debug!(
+                error = %e,
+                "mount did not report SiteLongitude; skipping mount-side site validation"
+            );
             return Ok(());
-        };
-
-        let mount_lat = match t.site_latitude().await {
-            Ok(v) => v,
-            Err(e) => {
-                debug!(
-                    error = %e,
-                    "mount did not report SiteLatitude; skipping mount-side site validation"
-                );
-                return Ok(());
-            }
-        };
-        let mount_lon = match t.site_longitude().await {
-            Ok(v) => v,
-            Err(e) => {
-                debug!(
-                    error = %e,
-                    "mount did not report SiteLongitude; skipping mount-side site validation"
-                );
-                return Ok(());
-            }
-        };
-
-        let lat_diff = (mount_lat - site.latitude_degrees).abs();
-        // Longitude is angular: 179.99° E and -179.99° E are the
-        // same meridian, not 360° apart. Take the modular distance
-        // around 360° so the antimeridian doesn't trigger a false
-        // mismatch.
-        let lon_raw = (mount_lon - site.longitude_degrees).abs();
-        let lon_diff = lon_raw.min(360.0 - lon_raw);
-        if lat_diff > SITE_MATCH_TOLERANCE_DEG || lon_diff > SITE_MATCH_TOLERANCE_DEG {
-            return Err(RpError::SiteMismatch {
-                config_lat: site.latitude_degrees,
-                config_lon: site.longitude_degrees,
-                mount_lat,
-                mount_lon,
-            });
         }
-        debug!(
-            site_lat = site.latitude_degrees,
-            site_lon = site.longitude_degrees,
-            "mount-side site validation: configured site agrees with mount"
-        );
-        Ok(())
+    };
+
+    let lat_diff = (mount_lat - site.latitude_degrees).abs();
+    // Longitude is angular: 179.99° E and -179.99° E are the
+    // same meridian, not 360° apart. Take the modular distance
+    // around 360° so the antimeridian doesn't trigger a false
+    // mismatch.
+    let lon_raw = (mount_lon - site.longitude_degrees).abs();
+    let lon_diff = lon_raw.min(360.0 - lon_raw);
+    if lat_diff > SITE_MATCH_TOLERANCE_DEG || lon_diff > SITE_MATCH_TOLERANCE_DEG {
+        return Err(RpError::SiteMismatch {
+            config_lat: site.latitude_degrees,
+            config_lon: site.longitude_degrees,
+            mount_lat,
+            mount_lon,
+        });
     }
+    debug!(
+        site_lat = site.latitude_degrees,
+        site_lon = site.longitude_degrees,
+        "mount-side site validation: configured site agrees with mount"
+    );
+    Ok(())
 }
@@ -11,6 +11,7 @@ use crate::config;
 /// Singular mount entry. Piggyback rigs share one mount across multiple
 /// optical trains, so `EquipmentRegistry.mount` is an `Option`, not a
 /// `Vec`. No `id` field — there is nothing to disambiguate.
+#[derive(Clone)]
 pub struct MountEntry {
     pub connected: bool,
     pub config: config::MountConfig,
@@ -116,6 +117,7 @@ mod tests {
             settle_after_slew: None,
             slew_rate_arcsec_per_sec: Default::default(),
             guiding: None,
+            meridian_flip: Default::default(),
             auth: None,
         }
     }
@@ -333,6 +335,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 47.6062,
             longitude_degrees: -122.3321,
+            horizon: None,
         };
         registry
             .validate_site(Some(&site))
@@ -347,6 +350,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 47.6062,
             longitude_degrees: -122.3321,
+            horizon: None,
         };
         // No `CanGetSiteLatitude`/`Longitude` in ASCOM — the read
         // attempt itself is the capability probe. NOT_IMPLEMENTED
@@ -364,6 +368,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 47.6062,
             longitude_degrees: -122.3321,
+            horizon: None,
         };
         registry.validate_site(Some(&site)).await.unwrap();
     }
@@ -376,6 +381,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 47.606,
             longitude_degrees: -122.332,
+            horizon: None,
         };
         registry.validate_site(Some(&site)).await.unwrap();
     }
@@ -388,6 +394,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 47.6062,
             longitude_degrees: -122.3321,
+            horizon: None,
         };
         let err = registry.validate_site(Some(&site)).await.unwrap_err();
         match err {
@@ -417,6 +424,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 0.0,
             longitude_degrees: 179.999,
+            horizon: None,
         };
         registry.validate_site(Some(&site)).await.unwrap();
     }
@@ -428,6 +436,7 @@ mod tests {
         let site = config::SiteConfig {
             latitude_degrees: 47.6062,
             longitude_degrees: -122.3321,
+            horizon: None,
         };
         let err = registry.validate_site(Some(&site)).await.unwrap_err();
         assert!(
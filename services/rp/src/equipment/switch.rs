@@ -8,6 +8,7 @@ use super::alpaca::{
 };
 use crate::config;
 
+#[derive(Clone)]
 pub struct SwitchEntry {
     pub id: String,
     pub connected: bool,
@@ -8,7 +8,7 @@
 //! invariants (the purpose enum, focal-length positivity) are already
 //! enforced in the config field types at deserialize.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use rusty_photon_config::actions::FieldError;
 
@@ -60,6 +60,11 @@ pub struct Train {
     /// (`optical_trains[].auto_focus`), carried through for the
     /// train-addressed `auto_focus` fallback and `refocus_train`.
     pub auto_focus: Option<crate::config::TrainAutoFocusConfig>,
+    /// Per-filter focus offsets in focuser steps
+    /// (`optical_trains[].focus_offsets`), as loaded. `set_filter`
+    /// applies the handler's runtime copy, which
+    /// `measure_filter_offsets` updates without a restart.
+    pub focus_offsets: BTreeMap<String, i32>,
 }
 
 impl Train {
@@ -71,6 +76,27 @@ impl Train {
         self.devices.last().map(|d| d.id.as_str())
     }
 
+    /// The field of view this train images, from its `focal_length_mm`
+    /// and the pixel size and sensor dimensions `camera` (its terminal
+    /// camera) reported at connect — the same derivation as the exposure
+    /// document's `optics` block. `None` when the train has no focal
+    /// length or the camera's geometry was not read (not connected, or
+    /// the driver did not report it).
+    #[must_use]
+    pub fn field_of_view(&self, camera: &super::camera::CameraEntry) -> Option<FieldOfView> {
+        let optics = crate::persistence::Optics::from_camera_geometry(
+            self.focal_length_mm?,
+            camera.pixel_size_x_um?,
+            camera.pixel_size_y_um?,
+            camera.sensor_width_px?,
+            camera.sensor_height_px?,
+        )?;
+        Some(FieldOfView {
+            width_deg: optics.fov_width_deg,
+            height_deg: optics.fov_height_deg,
+        })
+    }
+
     /// The focuser that focuses this train's camera: the last focuser
     /// in the list. `None` for a train without focusers.
     #[must_use]
@@ -83,6 +109,15 @@ impl Train {
     }
 }
 
+/// A train's angular field of view, degrees along the sensor's width
+/// and height — what mosaic panels are laid out in (rp.md § Target
+/// Store → Mosaics).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FieldOfView {
+    pub width_deg: f64,
+    pub height_deg: f64,
+}
+
 /// One step of a dependency-ordered auto-focus sequence (rp.md
 /// § Optical Trains, derivation rules): run `focuser_id`'s AF in the
 /// context of `train_id` — capturing (or, for the guiding train,
@@ -333,6 +368,60 @@ impl TrainModel {
                 });
             }
 
+            // Focus offsets are keyed by the names of the train's one
+            // filter wheel and move its terminal focuser — both must be
+            // there. Only checked on a structurally clean train: the
+            // wheel and focuser are read off the resolved devices.
+            if train_ok && !train.focus_offsets.is_empty() {
+                let wheels: Vec<&str> = devices
+                    .iter()
+                    .filter(|d| d.kind == TrainDeviceKind::FilterWheel)
+                    .map(|d| d.id.as_str())
+                    .collect();
+                match wheels.as_slice() {
+                    [wheel_id] => {
+                        let filters = equipment
+                            .filter_wheels
+                            .iter()
+                            .find(|fw| fw.id == *wheel_id)
+                            .map(|fw| fw.filters.as_slice())
+                            .unwrap_or_default();
+                        for name in train.focus_offsets.keys() {
+                            if !filters.contains(name) {
+                                errors.push(FieldError {
+                                    path: path(&format!(".focus_offsets.{name}")),
+                                    msg: format!(
+                                        "filter wheel '{wheel_id}' has no filter named \
+                                         '{name}' (train '{}')",
+                                        train.id
+                                    ),
+                                });
+                            }
+                        }
+                    }
+                    _ => {
+                        errors.push(FieldError {
+                            path: path(".focus_offsets"),
+                            msg: format!(
+                                "focus_offsets require exactly one filter wheel in devices; \
+                                 got {} (train '{}')",
+                                wheels.len(),
+                                train.id
+                            ),
+                        });
+                    }
+                }
+                if !devices.iter().any(|d| d.kind == TrainDeviceKind::Focuser) {
+                    errors.push(FieldError {
+                        path: path(".focus_offsets"),
+                        msg: format!(
+                            "focus_offsets require a focuser in devices (train '{}')",
+                            train.id
+                        ),
+                    });
+                }
+            }
+
             if train_ok {
                 trains.push(Train {
                     id: train.id.clone(),
@@ -345,6 +434,7 @@ impl TrainModel {
                         .map(super::super::config::optical_train::PositionAngleDegrees::value),
                     devices,
                     auto_focus: train.auto_focus.clone(),
+                    focus_offsets: train.focus_offsets.clone(),
                 });
             }
         }
@@ -666,6 +756,45 @@ mod tests {
         assert!(model.train("guide").unwrap().auto_focus.is_none());
     }
 
+    #[test]
+    fn focus_offsets_are_carried_when_keyed_by_the_wheels_filters() {
+        let mut config = reference_rig();
+        config.filter_wheels[0].filters = vec!["L".into(), "R".into(), "Ha".into()];
+        config.optical_trains[0].focus_offsets = [("L".to_string(), 0), ("Ha".to_string(), 42)]
+            .into_iter()
+            .collect();
+        let model = TrainModel::try_from_equipment(&config).unwrap();
+        let main = model.train("main").unwrap();
+        assert_eq!(main.focus_offsets.get("Ha"), Some(&42));
+        assert!(model.train("guide").unwrap().focus_offsets.is_empty());
+    }
+
+    #[test]
+    fn focus_offsets_reject_a_name_the_wheel_does_not_carry() {
+        let mut config = reference_rig();
+        config.filter_wheels[0].filters = vec!["L".into(), "R".into()];
+        config.optical_trains[0].focus_offsets = [("OIII".to_string(), 10)].into_iter().collect();
+        let errors = TrainModel::try_from_equipment(&config).unwrap_err();
+        assert_eq!(
+            paths(&errors),
+            vec!["equipment.optical_trains.0.focus_offsets.OIII"]
+        );
+        assert!(errors[0].msg.contains("has no filter named 'OIII'"));
+    }
+
+    #[test]
+    fn focus_offsets_require_a_wheel_in_the_train() {
+        let mut config = reference_rig();
+        // The guide train has focusers but no wheel.
+        config.optical_trains[1].focus_offsets = [("L".to_string(), 0)].into_iter().collect();
+        let errors = TrainModel::try_from_equipment(&config).unwrap_err();
+        assert_eq!(
+            paths(&errors),
+            vec!["equipment.optical_trains.1.focus_offsets"]
+        );
+        assert!(errors[0].msg.contains("exactly one filter wheel"));
+    }
+
     #[test]
     fn reference_rig_builds_and_answers_the_derivation_table() {
         let model = TrainModel::try_from_equipment(&reference_rig()).unwrap();
@@ -703,6 +832,33 @@ mod tests {
         assert!(!model.camera_in_imaging_train("unknown-cam"));
     }
 
+    #[test]
+    fn field_of_view_combines_focal_length_with_the_connected_sensor() {
+        let rig = reference_rig();
+        let model = TrainModel::try_from_equipment(&rig).unwrap();
+        let main = model.train("main").unwrap();
+        let mut camera = crate::equipment::camera::CameraEntry {
+            id: "main-cam".to_string(),
+            connected: true,
+            config: rig.cameras[0].clone(),
+            device: None,
+            max_adu: None,
+            pixel_size_x_um: Some(3.76),
+            pixel_size_y_um: Some(3.76),
+            sensor_width_px: Some(6248),
+            sensor_height_px: Some(4176),
+        };
+        let fov = main.field_of_view(&camera).unwrap();
+        // 206.265 × 3.76 / 360 ≈ 2.154"/px.
+        let scale = 206.265 * 3.76 / 360.0;
+        assert!((fov.width_deg - scale * 6248.0 / 3600.0).abs() < 1e-12);
+        assert!((fov.height_deg - scale * 4176.0 / 3600.0).abs() < 1e-12);
+
+        // A sensor the driver never reported has no field of view.
+        camera.sensor_width_px = None;
+        assert!(main.field_of_view(&camera).is_none());
+    }
+
     /// Requirement 1 of the plan: main AF first (shared EAF runs in the
     /// train where it is terminal), guide AF after.
     #[test]
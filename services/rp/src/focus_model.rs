@@ -0,0 +1,492 @@
+//! Focus temperature compensation (rp.md § Focus Temperature
+//! Compensation): the per-train history of auto-focus results and the
+//! steps-per-°C model fitted from it.
+//!
+//! Every successful `auto_focus` / `refocus_train` step records one
+//! [`FocusSample`] — the focuser temperature read at the start of the
+//! sweep, the fitted best position, and the filter in the train at the
+//! time. [`fit_model`] turns a train's samples into a slope with outlier
+//! rejection; the `focus_model` tool category reads it and moves the
+//! focuser. The store only remembers and fits — it never moves
+//! hardware on its own.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+/// Fewest samples (after outlier rejection) a fit accepts.
+pub const MIN_FIT_SAMPLES: usize = 4;
+
+/// Smallest temperature range (°C) the kept samples must span. Below
+/// it the slope is mostly the sweep's own scatter.
+pub const MIN_TEMPERATURE_SPAN_C: f64 = 2.0;
+
+/// Residual cutoff for outlier rejection, in robust standard
+/// deviations (1.4826 × the median absolute residual).
+const OUTLIER_SIGMA: f64 = 3.0;
+
+/// Scale from a median absolute deviation to a normal sigma.
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+/// The cutoff never drops below one focuser step — positions are
+/// integers, so a sub-step residual is rounding, not an outlier.
+const MIN_OUTLIER_CUTOFF_STEPS: f64 = 1.0;
+
+/// Samples kept per train; the oldest are dropped first. Optics,
+/// spacing and focusers change over a season, so an unbounded
+/// history would fit a rig that no longer exists.
+const MAX_SAMPLES_PER_TRAIN: usize = 200;
+
+/// One auto-focus result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusSample {
+    pub focuser_id: String,
+    pub temperature_c: f64,
+    pub position: i32,
+    /// Filter in the train's wheel during the sweep; `None` for a
+    /// train without a wheel, or when the wheel could not be read.
+    pub filter: Option<String>,
+    /// RFC 3339.
+    pub recorded_at: String,
+}
+
+/// What the next compensation is measured from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReferenceSource {
+    AutoFocus,
+    Compensation,
+}
+
+/// The last point at which the train was known to be in focus: the
+/// latest auto-focus, or the latest applied compensation if newer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusReference {
+    pub focuser_id: String,
+    pub temperature_c: f64,
+    pub position: i32,
+    pub source: ReferenceSource,
+    /// RFC 3339.
+    pub recorded_at: String,
+}
+
+/// One train's persisted history.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TrainFocusHistory {
+    #[serde(default)]
+    pub samples: Vec<FocusSample>,
+    #[serde(default)]
+    pub reference: Option<FocusReference>,
+}
+
+impl TrainFocusHistory {
+    /// The samples taken with `focuser_id` — a train whose terminal
+    /// focuser changed in config must not fit the old one's history.
+    #[must_use]
+    pub fn samples_for(&self, focuser_id: &str) -> Vec<FocusSample> {
+        self.samples
+            .iter()
+            .filter(|s| s.focuser_id == focuser_id)
+            .cloned()
+            .collect()
+    }
+}
+
+/// Per-filter statistics of the kept samples. The fit centres each
+/// filter's samples on its own means, so filters focusing at different
+/// positions share one slope without their offset reading as a
+/// temperature effect.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FilterGroup {
+    pub filter: Option<String>,
+    pub samples: usize,
+    pub mean_temperature_c: f64,
+    pub mean_position: f64,
+}
+
+/// A fitted model.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FocusModelFit {
+    /// Focuser steps per °C; positive when focus moves outward as it
+    /// warms.
+    pub slope_steps_per_c: f64,
+    pub samples_used: usize,
+    /// Indices (into the fitted sample slice) dropped as outliers.
+    pub outliers: Vec<usize>,
+    pub temperature_span_c: f64,
+    /// RMS of the kept samples' residuals, in steps.
+    pub residual_rms_steps: f64,
+    pub filters: Vec<FilterGroup>,
+}
+
+impl FocusModelFit {
+    /// Steps to move for a temperature change from `from_c` to `to_c`,
+    /// rounded to the nearest step.
+    #[must_use]
+    pub fn predicted_delta(&self, from_c: f64, to_c: f64) -> i32 {
+        (self.slope_steps_per_c * (to_c - from_c)).round() as i32
+    }
+}
+
+/// Fit a steps-per-°C slope to `samples`: a least-squares line pooled
+/// within filters, one pass of rejecting samples whose residual exceeds
+/// [`OUTLIER_SIGMA`] robust sigmas, then a refit on the rest. Errors
+/// name why no model is available.
+pub fn fit_model(samples: &[FocusSample]) -> Result<FocusModelFit, String> {
+    if samples.len() < MIN_FIT_SAMPLES {
+        return Err(format!(
+            "{} auto-focus sample(s) recorded, need at least {MIN_FIT_SAMPLES}",
+            samples.len()
+        ));
+    }
+    let all: Vec<usize> = (0..samples.len()).collect();
+    let (slope, _) = pooled_fit(samples, &all)?;
+    let first_pass = residuals(samples, &all, slope);
+    let mut abs: Vec<f64> = first_pass.iter().map(|r| r.abs()).collect();
+    let mad = median(&mut abs);
+    let cutoff = (OUTLIER_SIGMA * MAD_TO_SIGMA * mad).max(MIN_OUTLIER_CUTOFF_STEPS);
+    let (kept, outliers): (Vec<usize>, Vec<usize>) = all
+        .iter()
+        .copied()
+        .partition(|&i| first_pass[i].abs() <= cutoff);
+    if kept.len() < MIN_FIT_SAMPLES {
+        return Err(format!(
+            "{} of {} auto-focus samples survive outlier rejection, need at least \
+             {MIN_FIT_SAMPLES}",
+            kept.len(),
+            samples.len()
+        ));
+    }
+    let (slope, filters) = pooled_fit(samples, &kept)?;
+    let refit = residuals(samples, &kept, slope);
+    let kept_residuals: Vec<f64> = kept.iter().map(|&i| refit[i]).collect();
+    let residual_rms_steps =
+        (kept_residuals.iter().map(|r| r * r).sum::<f64>() / kept_residuals.len() as f64).sqrt();
+    let (min_t, max_t) = temperature_range(samples, &kept);
+    Ok(FocusModelFit {
+        slope_steps_per_c: slope,
+        samples_used: kept.len(),
+        outliers,
+        temperature_span_c: max_t - min_t,
+        residual_rms_steps,
+        filters,
+    })
+}
+
+/// The within-filter least-squares slope over `indices`, plus the
+/// per-filter means it centred on.
+fn pooled_fit(
+    samples: &[FocusSample],
+    indices: &[usize],
+) -> Result<(f64, Vec<FilterGroup>), String> {
+    let filters = filter_groups(samples, indices);
+    let mut sxx = 0.0;
+    let mut sxy = 0.0;
+    for &i in indices {
+        let s = &samples[i];
+        let Some(group) = filters.iter().find(|g| g.filter == s.filter) else {
+            continue;
+        };
+        let dt = s.temperature_c - group.mean_temperature_c;
+        sxx += dt * dt;
+        sxy += dt * (f64::from(s.position) - group.mean_position);
+    }
+    let (min_t, max_t) = temperature_range(samples, indices);
+    if max_t - min_t < MIN_TEMPERATURE_SPAN_C {
+        return Err(format!(
+            "auto-focus samples span {:.1} °C, need at least {MIN_TEMPERATURE_SPAN_C:.1} °C",
+            max_t - min_t
+        ));
+    }
+    // The overall span can be wide while every filter was only ever
+    // focused at one temperature; then there is no slope to learn.
+    if sxx <= f64::EPSILON {
+        return Err("no filter has auto-focus samples at more than one temperature".to_string());
+    }
+    Ok((sxy / sxx, filters))
+}
+
+fn temperature_range(samples: &[FocusSample], indices: &[usize]) -> (f64, f64) {
+    indices
+        .iter()
+        .map(|&i| samples[i].temperature_c)
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), t| {
+            (lo.min(t), hi.max(t))
+        })
+}
+
+fn filter_groups(samples: &[FocusSample], indices: &[usize]) -> Vec<FilterGroup> {
+    let mut sums: BTreeMap<Option<&str>, (usize, f64, f64)> = BTreeMap::new();
+    for &i in indices {
+        let s = &samples[i];
+        let entry = sums.entry(s.filter.as_deref()).or_default();
+        entry.0 += 1;
+        entry.1 += s.temperature_c;
+        entry.2 += f64::from(s.position);
+    }
+    sums.into_iter()
+        .map(|(filter, (n, t, p))| FilterGroup {
+            filter: filter.map(str::to_string),
+            samples: n,
+            mean_temperature_c: t / n as f64,
+            mean_position: p / n as f64,
+        })
+        .collect()
+}
+
+/// Residuals of every sample (indexed like `samples`) against the
+/// line of slope `slope` through the means of its filter within
+/// `indices`. Samples whose filter has no kept sample get 0.
+fn residuals(samples: &[FocusSample], indices: &[usize], slope: f64) -> Vec<f64> {
+    let filters = filter_groups(samples, indices);
+    samples
+        .iter()
+        .map(|s| {
+            filters
+                .iter()
+                .find(|g| g.filter == s.filter)
+                .map_or(0.0, |g| {
+                    f64::from(s.position)
+                        - (g.mean_position + slope * (s.temperature_c - g.mean_temperature_c))
+                })
+        })
+        .collect()
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// The persisted per-train histories, keyed by train id. Loaded once at
+/// startup and rewritten (atomically) after every change when a path is
+/// configured; [`Self::in_memory`] keeps them for the process only.
+pub struct FocusModelStore {
+    path: Option<PathBuf>,
+    trains: tokio::sync::Mutex<BTreeMap<String, TrainFocusHistory>>,
+}
+
+impl FocusModelStore {
+    /// A store that is never written to disk — tests, and handlers
+    /// built without a data directory.
+    #[must_use]
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            trains: tokio::sync::Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Open the store at `path`. A missing file is an empty history; an
+    /// unreadable or corrupt one is logged and also starts empty —
+    /// losing the model costs a few auto-focus runs, refusing to start
+    /// would cost the night.
+    #[must_use]
+    pub fn load(path: PathBuf) -> Self {
+        let trains = match std::fs::read(&path) {
+            Ok(bytes) => match serde_json::from_slice(&bytes) {
+                Ok(trains) => trains,
+                Err(e) => {
+                    warn!(path = %path.display(), error = %e,
+                          "cannot parse the focus model file; starting with no history");
+                    BTreeMap::new()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                debug!(path = %path.display(), "no focus model file; starting with no history");
+                BTreeMap::new()
+            }
+            Err(e) => {
+                warn!(path = %path.display(), error = %e,
+                      "cannot read the focus model file; starting with no history");
+                BTreeMap::new()
+            }
+        };
+        Self {
+            path: Some(path),
+            trains: tokio::sync::Mutex::new(trains),
+        }
+    }
+
+    /// A copy of `train_id`'s history (empty when nothing was recorded).
+    pub async fn history(&self, train_id: &str) -> TrainFocusHistory {
+        self.trains
+            .lock()
+            .await
+            .get(train_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Record an auto-focus result; it also becomes the train's
+    /// compensation reference.
+    pub async fn record_sample(&self, train_id: &str, sample: FocusSample) {
+        let mut trains = self.trains.lock().await;
+        let history = trains.entry(train_id.to_string()).or_default();
+        history.reference = Some(FocusReference {
+            focuser_id: sample.focuser_id.clone(),
+            temperature_c: sample.temperature_c,
+            position: sample.position,
+            source: ReferenceSource::AutoFocus,
+            recorded_at: sample.recorded_at.clone(),
+        });
+        history.samples.push(sample);
+        let excess = history.samples.len().saturating_sub(MAX_SAMPLES_PER_TRAIN);
+        history.samples.drain(..excess);
+        self.persist(&trains).await;
+    }
+
+    /// Record an applied compensation as the train's new reference.
+    pub async fn record_compensation(&self, train_id: &str, reference: FocusReference) {
+        let mut trains = self.trains.lock().await;
+        trains.entry(train_id.to_string()).or_default().reference = Some(reference);
+        self.persist(&trains).await;
+    }
+
+    /// Rewrite the file. Callers hold the lock across the write so
+    /// concurrent writers cannot land out of order. A failed write is
+    /// logged, never surfaced: the in-memory history stays correct.
+    async fn persist(&self, trains: &BTreeMap<String, TrainFocusHistory>) {
+        let Some(path) = self.path.clone() else {
+            return;
+        };
+        let body = match serde_json::to_vec_pretty(trains) {
+            Ok(body) => body,
+            Err(e) => {
+                warn!(error = %e, "cannot serialize the focus model; skipping the write");
+                return;
+            }
+        };
+        let write_path = path.clone();
+        let result =
+            tokio::task::spawn_blocking(move || rp_fits::atomic::write_atomic(&write_path, &body))
+                .await;
+        match result {
+            Ok(Ok(())) => debug!(path = %path.display(), "focus model persisted"),
+            Ok(Err(e)) => warn!(path = %path.display(), error = %e,
+                                "failed to write the focus model file; continuing"),
+            Err(e) => warn!(error = %e, "focus model write task failed; continuing"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn sample(temperature_c: f64, position: i32, filter: Option<&str>) -> FocusSample {
+        FocusSample {
+            focuser_id: "foc".to_string(),
+            temperature_c,
+            position,
+            filter: filter.map(str::to_string),
+            recorded_at: "2026-10-01T22:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn fits_the_slope_of_a_clean_line() {
+        let samples: Vec<_> = (0..6)
+            .map(|i| sample(f64::from(i), 10_000 - 30 * i, None))
+            .collect();
+        let fit = fit_model(&samples).unwrap();
+        assert!((fit.slope_steps_per_c + 30.0).abs() < 1e-9);
+        assert_eq!(fit.samples_used, 6);
+        assert!(fit.outliers.is_empty());
+        assert!((fit.temperature_span_c - 5.0).abs() < 1e-9);
+        assert_eq!(fit.predicted_delta(10.0, 8.0), 60);
+    }
+
+    #[test]
+    fn rejects_a_wild_sample_and_refits_without_it() {
+        let mut samples: Vec<_> = (0..6)
+            .map(|i| sample(f64::from(i), 10_000 + 20 * i, None))
+            .collect();
+        samples.push(sample(2.5, 12_000, None));
+        let fit = fit_model(&samples).unwrap();
+        assert_eq!(fit.outliers, vec![6]);
+        assert_eq!(fit.samples_used, 6);
+        assert!((fit.slope_steps_per_c - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn filter_offsets_do_not_read_as_temperature() {
+        // L focuses 500 steps inside Ha, and Ha was only ever focused
+        // warm: a single unpooled line would see a steep slope.
+        let samples = vec![
+            sample(0.0, 10_000, Some("L")),
+            sample(2.0, 10_020, Some("L")),
+            sample(4.0, 10_040, Some("L")),
+            sample(8.0, 10_580, Some("Ha")),
+            sample(10.0, 10_600, Some("Ha")),
+        ];
+        let fit = fit_model(&samples).unwrap();
+        assert!((fit.slope_steps_per_c - 10.0).abs() < 1e-9);
+        assert_eq!(fit.filters.len(), 2);
+    }
+
+    #[test]
+    fn too_few_samples_is_an_error() {
+        let samples = vec![sample(0.0, 100, None), sample(5.0, 150, None)];
+        let err = fit_model(&samples).unwrap_err();
+        assert!(err.contains("need at least 4"), "{err}");
+    }
+
+    #[test]
+    fn a_narrow_temperature_span_is_an_error() {
+        let samples: Vec<_> = (0..5)
+            .map(|i| sample(10.0 + 0.2 * f64::from(i), 100 + i, None))
+            .collect();
+        let err = fit_model(&samples).unwrap_err();
+        assert!(err.contains("need at least 2.0 °C"), "{err}");
+    }
+
+    #[test]
+    fn samples_for_skips_other_focusers() {
+        let mut other = sample(1.0, 1, None);
+        other.focuser_id = "old".to_string();
+        let history = TrainFocusHistory {
+            samples: vec![sample(0.0, 0, None), other],
+            reference: None,
+        };
+        assert_eq!(history.samples_for("foc").len(), 1);
+    }
+
+    #[tokio::test]
+    async fn history_round_trips_through_the_file_and_is_capped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("focus_model.json");
+        let store = FocusModelStore::load(path.clone());
+        for i in 0..(MAX_SAMPLES_PER_TRAIN as i32 + 3) {
+            store
+                .record_sample("main", sample(f64::from(i), i, None))
+                .await;
+        }
+        let reloaded = FocusModelStore::load(path).history("main").await;
+        assert_eq!(reloaded.samples.len(), MAX_SAMPLES_PER_TRAIN);
+        assert_eq!(reloaded.samples[0].position, 3);
+        let reference = reloaded.reference.unwrap();
+        assert_eq!(reference.source, ReferenceSource::AutoFocus);
+        assert_eq!(reference.position, MAX_SAMPLES_PER_TRAIN as i32 + 2);
+    }
+
+    #[tokio::test]
+    async fn a_corrupt_file_starts_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("focus_model.json");
+        std::fs::write(&path, b"not json").unwrap();
+        let store = FocusModelStore::load(path);
+        assert!(store.history("main").await.samples.is_empty());
+    }
+}
@@ -1,15 +1,13 @@
 //! Pure image-analysis kernels: single-purpose math over `ArrayView2`.
 //!
-//! Each submodule is generic over [`pixel::Pixel`] and free of I/O,
-//! async, and persistence concerns. Compositional analyzers live in
+//! The kernels live in the shared `rp-imaging` crate so the native
+//! guider centroids with the same code; they are re-exported here under
+//! their original module paths. [`snr`] stays in rp because
+//! `compute_snr` reports through [`crate::error::RpError`]. Compositional analyzers live in
 //! [`crate::imaging::tools`]; cache and FITS I/O live in
 //! [`crate::persistence`]. See `docs/services/rp.md` (Module Structure)
 //! and `docs/plans/archive/image-evaluation-tools.md` for the layout rationale.
 
-pub mod background;
-pub mod fwhm;
-pub mod hfr;
-pub mod pixel;
+pub use rp_imaging::{background, fwhm, hfr, pixel, stars, stats};
+
 pub mod snr;
-pub mod stars;
-pub mod stats;
@@ -1,23 +1,9 @@
 //! `compute_snr`: signal-to-noise summary based on per-star photometry.
 //!
 //! Composes background estimation → star detection → per-star SNR via the
-//! CCD-equation approximation, then aggregates with the median. Pure
-//! logic — no I/O, no MCP types.
-//!
-//! Per-star noise model:
-//!
-//! ```text
-//! noise = sqrt(signal + n_pixels · σ_bg²)
-//! snr   = signal / noise
-//! ```
-//!
-//! where `signal` is the background-subtracted total flux of the
-//! component, `n_pixels` is the area of the component, and `σ_bg` is the
-//! sigma-clipped background standard deviation. This is the standard CCD
-//! equation with the dark-current and read-noise terms collapsed into the
-//! background variance, and the gain implicitly set to 1 ADU/electron —
-//! good enough for relative quality screening across frames from the
-//! *same* camera, **not** an absolute photometric SNR.
+//! CCD-equation approximation ([`per_star_snr`], shared from
+//! `rp-imaging`), then aggregates with the median. Pure logic — no I/O,
+//! no MCP types.
 //!
 //! When no stars are detected, the aggregate SNR/signal/noise are all
 //! `None` (the JSON tool layer maps this to `null`). The caller decides
@@ -28,9 +14,11 @@ use serde::Serialize;
 
 use super::background::estimate_background;
 use super::pixel::Pixel;
-use super::stars::{detect_stars, DetectionParams, Star};
+use super::stars::{detect_stars, DetectionParams};
 use crate::error::{Result, RpError};
 
+pub use rp_imaging::snr::per_star_snr;
+
 /// Aggregate result of the `compute_snr` pipeline.
 #[derive(Debug, Clone, Serialize)]
 pub struct SnrResult {
@@ -46,21 +34,6 @@ pub struct SnrResult {
     pub background_stddev: f64,
 }
 
-/// Per-star SNR triple: `(signal, noise, snr)`. Returns `None` when the
-/// noise term is zero (no signal and a perfectly flat background — only
-/// happens on synthetic test inputs).
-#[must_use]
-pub fn per_star_snr(star: &Star, background_stddev: f64) -> Option<(f64, f64, f64)> {
-    let signal = star.total_flux;
-    let n = star.pixels.len() as f64;
-    let variance = signal.max(0.0) + n * background_stddev * background_stddev;
-    if variance <= 0.0 {
-        return None;
-    }
-    let noise = variance.sqrt();
-    Some((signal, noise, signal / noise))
-}
-
 /// Run the SNR pipeline. `max_adu` is for saturation flagging inside
 /// `detect_stars`; this tool does not gate on it.
 pub fn compute_snr<T: Pixel>(
@@ -148,28 +121,6 @@ mod tests {
         arr
     }
 
-    #[test]
-    fn per_star_snr_matches_ccd_equation() {
-        let star = Star {
-            centroid_x: 0.0,
-            centroid_y: 0.0,
-            total_flux: 10_000.0,
-            peak: 5_000.0,
-            pixels: (0..25).map(|i| (i / 5, i % 5)).collect(),
-            bounding_box: (0, 0, 4, 4),
-            saturated_pixel_count: 0,
-        };
-        let bg_stddev = 10.0;
-        let (signal, noise, snr) = per_star_snr(&star, bg_stddev).unwrap();
-        // signal = 10_000; noise = sqrt(10_000 + 25 · 100) = sqrt(12_500) ≈ 111.8
-        assert_eq!(signal, 10_000.0);
-        assert!(
-            (noise - 111.803_398_874_989_5).abs() < 1e-6,
-            "noise = {noise}"
-        );
-        assert!((snr - 89.4427).abs() < 0.01, "snr = {snr}");
-    }
-
     #[test]
     fn one_star_yields_finite_snr() {
         let arr = make_gaussian_with_dither(64, 64, 32.0, 32.0, 2.0, 20_000.0, 1000.0);
@@ -264,4 +215,16 @@ mod tests {
         assert!(v.get("background_stddev").is_some());
         assert!(v["snr"].is_null());
     }
+
+    #[test]
+    fn one_star_in_a_float_frame_yields_finite_snr() {
+        let mut arr = make_gaussian_with_dither(64, 64, 32.0, 32.0, 2.0, 20_000.0, 1000.0)
+            .mapv(|v| f32::from(v) / 65_535.0);
+        arr[[0, 0]] = f32::NAN;
+        let r = compute_snr(arr.view(), 5.0, 5, 4096, None).unwrap();
+        assert_eq!(r.star_count, 1);
+        let snr = r.snr.expect("snr should be Some");
+        assert!(snr > 0.0 && snr.is_finite(), "snr = {snr}");
+        assert!(r.background_mean.is_finite());
+    }
 }
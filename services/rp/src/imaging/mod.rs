@@ -35,7 +35,7 @@ pub use analysis::hfr::{aggregate_hfr, star_hfr};
 pub use analysis::pixel::Pixel;
 pub use analysis::snr::{compute_snr, per_star_snr, SnrResult};
 pub use analysis::stars::{detect_stars, DetectionParams, Star};
-pub use analysis::stats::{compute_stats, ImageStats};
+pub use analysis::stats::{compute_stats, compute_stats_f64, FloatImageStats, ImageStats};
 pub use tools::center_on_target::haversine_arcsec;
 pub use tools::measure_basic::{measure_basic, MeasureBasicResult};
 pub use tools::measure_stars::{
@@ -217,4 +217,19 @@ mod tests {
         assert_eq!(r.star_count, 1);
         assert_eq!(r.saturated_star_count, 1);
     }
+
+    #[test]
+    fn one_star_in_a_float_frame_with_nan_pixels() {
+        // A calibrated master: normalised floats, a few undefined pixels.
+        let mut arr = make_gaussian(64, 64, 32.5, 32.5, 1.5, 20_000.0, 1000.0)
+            .mapv(|v| f32::from(v) / 65_535.0);
+        arr[[3, 40]] = f32::NAN;
+        arr[[50, 7]] = f32::NAN;
+        let r = measure_basic(arr.view(), 5.0, 5, 4096, None).unwrap();
+        assert_eq!(r.star_count, 1);
+        let hfr = r.hfr.expect("hfr should be Some");
+        assert!(hfr.is_finite() && hfr > 0.0, "hfr = {hfr}");
+        assert!((r.background_mean - 1000.0 / 65_535.0).abs() < 1e-4);
+        assert_eq!(r.pixel_count, 64 * 64);
+    }
 }
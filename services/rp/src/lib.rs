@@ -1,11 +1,15 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod background_solve;
 pub mod config;
 pub mod config_actions;
 pub mod cooling;
+pub mod dew;
 pub mod doctor;
+pub mod dome_slaving;
 pub mod equipment;
 pub mod error;
 pub mod events;
+pub mod focus_model;
 pub mod guiding_watch;
 pub mod imaging;
 pub mod mcp;
@@ -28,13 +32,13 @@ use rusty_photon_tls::config::TlsConfig;
 use tokio_util::sync::CancellationToken;
 
 use crate::config::{AdvertisedUrl, Config};
-use crate::equipment::EquipmentRegistry;
+use crate::equipment::{EquipmentRegistry, EquipmentSource, SharedEquipment};
 use crate::error::Result;
 use crate::events::EventBus;
 use crate::mcp::McpHandler;
 use crate::persistence::ImageCache;
 use crate::routes::{build_router, AppState};
-use crate::safety::{AlpacaSafetyProbe, SafetyEnforcer};
+use crate::safety::{RegistrySafetyRoster, SafetyEnforcer};
 use crate::session::{SessionConfig, SessionManager};
 
 /// Builder for the rp server.
@@ -90,8 +94,7 @@ impl ServerBuilder {
         let bind_addr = config.server.socket_addr();
 
         debug!("initializing equipment registry");
-        let equipment =
-            Arc::new(EquipmentRegistry::new(&config.equipment, config.ca_cert_path()).await);
+        let equipment = EquipmentRegistry::new(&config.equipment, config.ca_cert_path()).await;
 
         // Validate the configured site against the mount's reported
         // SiteLatitude/SiteLongitude. A mismatch beyond 0.01° aborts
@@ -100,6 +103,15 @@ impl ServerBuilder {
         // lacks the property, or no mount is configured, this is a
         // debug-logged no-op.
         equipment.validate_site(config.site.as_ref()).await?;
+        // Shared from here on: the runtime connect/disconnect/reload
+        // tools swap rebuilt registries in behind this handle (rp.md
+        // § Runtime Equipment Changes).
+        let equipment = SharedEquipment::new(equipment);
+        let equipment_source = EquipmentSource {
+            config_path: config_path.clone(),
+            ca_cert_path: config.ca_cert_path().map(std::path::Path::to_path_buf),
+            site: config.site.clone(),
+        };
 
         debug!("initializing event bus");
         let event_bus = Arc::new(
@@ -125,12 +137,24 @@ impl ServerBuilder {
             config.cooling.clone(),
         ));
 
+        // The dew-heater controller (rp.md § Dew Management), present
+        // only with a `dew` block. Session transitions start and stop
+        // it; do_capture reads its duties per frame.
+        let dew = config.dew.clone().map(|dew_cfg| {
+            Arc::new(crate::dew::DewController::new(
+                equipment.clone(),
+                event_bus.clone(),
+                dew_cfg,
+            ))
+        });
+
         let session = Arc::new(
             SessionManager::new(event_bus.clone(), &config.plugins, config.ca_cert_path())
                 .map_err(crate::error::RpError::Config)?
                 .with_progress_store(planner_progress.clone())
                 .with_state_path(config.session.session_state_path())
-                .with_cooling(cooling.clone()),
+                .with_cooling(cooling.clone())
+                .with_dew(dew.clone()),
         );
 
         let session_config = SessionConfig {
@@ -156,6 +180,18 @@ impl ServerBuilder {
         } else {
             None
         };
+        // The horizon profile is read (and a horizon file parsed) once,
+        // here; a missing or malformed file fails startup rather than
+        // silently planning against a flat horizon.
+        let horizon = Arc::new(match config.site.as_ref() {
+            Some(site_cfg) => site_cfg
+                .horizon_profile()
+                .map_err(crate::error::RpError::Config)?,
+            None => rp_ephemeris::HorizonProfile::default(),
+        });
+        if !horizon.is_empty() {
+            tracing::info!("horizon profile: {} points", horizon.points().len());
+        }
 
         // Target store (rp.md § Target Store): the sole source of planner
         // targets (the legacy `targets[]` config array was retired).
@@ -314,6 +350,9 @@ impl ServerBuilder {
             );
         }
 
+        // The `/mcp` session registry, shared by `abort_session` and the
+        // safety enforcer (below) so both can cancel in-flight tool calls.
+        let mcp_sessions = Arc::new(LocalSessionManager::default());
         let mcp = McpHandler::new(
             equipment.clone(),
             event_bus.clone(),
@@ -322,15 +361,31 @@ impl ServerBuilder {
             site,
         )
         .with_planner_default_min_altitude(default_min_alt)
+        .with_horizon(horizon)
         .with_progress_store(planner_progress)
         .with_session_manager(session.clone())
+        .with_mcp_sessions(mcp_sessions.clone())
         .with_plate_solver(plate_solver_client, plate_solver_default_radius)
         .with_guider(guider_client.clone(), guider_defaults)
         .with_trains(trains)
         .with_centering_config(config.centering.clone())
         .with_cooling(cooling)
+        .with_dew(dew)
         .with_target_store(Some(target_store), target_store_config)
-        .with_naming_templates(naming_templates);
+        .with_naming_templates(naming_templates)
+        .with_fits_keywords(config.session.fits_keywords.keywords())
+        .with_frame_encoding(config.session.frame_encoding())
+        .with_equipment_source(equipment_source)
+        .with_focus_model(Arc::new(crate::focus_model::FocusModelStore::load(
+            config.session.focus_model_path(),
+        )));
+
+        // Background Solving (rp.md § Background Solving): spawned only
+        // when the operator configured `plate_solver.background`. Events
+        // only — the orchestrator owns any re-centering.
+        if let Some(bg_cfg) = config.plate_solver.as_ref().and_then(|ps| ps.background) {
+            crate::background_solve::spawn(mcp.clone(), bg_cfg);
+        }
 
         // Cancellation token for in-flight SSE streams
         // (`/api/events/subscribe`). Cloned into AppState so the handler can
@@ -342,10 +397,9 @@ impl ServerBuilder {
         // Safety enforcement (rp.md § Safety): the gate flag is read by the
         // `/mcp` middleware, the session registry is shared with the
         // enforcer so an unsafe transition can terminate every open MCP
-        // session. `None` when no safety monitors are configured — sessions
-        // then run ungated and no polling task is spawned.
+        // session. It runs even with no safety monitors configured — the
+        // gate then stays open — so one connected at runtime is enforced.
         let safety_ok = Arc::new(AtomicBool::new(true));
-        let mcp_sessions = Arc::new(LocalSessionManager::default());
         let safety = SafetyEnforcer::from_registry(
             equipment.clone(),
             event_bus.clone(),
@@ -560,8 +614,7 @@ pub struct BoundServer {
     /// shutdown can drain. A clone lives in `AppState` for the handler.
     sse_shutdown: CancellationToken,
     /// Safety polling loop, spawned by `start()` and cancelled on shutdown.
-    /// `None` when no safety monitors are configured.
-    safety: Option<SafetyEnforcer<AlpacaSafetyProbe>>,
+    safety: SafetyEnforcer<RegistrySafetyRoster>,
     /// Kept so `start()` can run startup recovery (rp.md § Recovery
     /// Behavior) once the server is about to serve.
     session: Arc<SessionManager>,
@@ -576,26 +629,19 @@ impl BoundServer {
     }
 
     pub async fn start(self, shutdown: impl Future<Output = ()> + Send + 'static) -> Result<()> {
-        // Safety before recovery (rp.md § Recovery Behavior): with
-        // monitors configured, complete one poll inline so the `/mcp`
-        // gate reflects reality — and unsafe conditions already secured
-        // the equipment — before any orchestrator is re-invoked. The
-        // loop then continues from that state. The lifecycle shutdown
-        // is chained below.
+        // Safety before recovery (rp.md § Recovery Behavior): complete
+        // one poll inline so the `/mcp` gate reflects reality — and
+        // unsafe conditions already secured the equipment — before any
+        // orchestrator is re-invoked. The loop then continues from that
+        // state. The lifecycle shutdown is chained below.
         let safety_cancel = CancellationToken::new();
-        let safety_task = match self.safety {
-            Some(enforcer) => {
-                let mut per_monitor = std::collections::HashMap::new();
-                let overall = enforcer.poll_once(&mut per_monitor, true).await;
-                let cancel = safety_cancel.clone();
-                Some(tokio::spawn(enforcer.run_from(
-                    cancel,
-                    per_monitor,
-                    overall,
-                )))
-            }
-            None => None,
-        };
+        let mut per_monitor = std::collections::HashMap::new();
+        let overall = self.safety.poll_once(&mut per_monitor, true).await;
+        let safety_task = tokio::spawn(self.safety.run_from(
+            safety_cancel.clone(),
+            per_monitor,
+            overall,
+        ));
 
         // Startup recovery (rp.md § Recovery Behavior): restore a
         // persisted session — re-invoking the orchestrator only under
@@ -635,9 +681,7 @@ impl BoundServer {
 
         // The safety loop was cancelled by `graceful`; join it so the
         // process doesn't exit mid-transition.
-        if let Some(task) = safety_task {
-            let _ = task.await;
-        }
+        let _ = safety_task.await;
 
         debug!("rp service shut down");
         Ok(())
@@ -1,8 +1,9 @@
-//! Auto-focus tool category: the `auto_focus` V-curve compound tool
-//! and the train-aware `refocus_train` expansion (rp.md § Optical
-//! Trains, §`auto_focus` Contract, §`refocus_train` Contract).
+//! Auto-focus tool category: the `auto_focus` V-curve compound tool,
+//! the train-aware `refocus_train` expansion, and
+//! `measure_filter_offsets` (rp.md § Optical Trains, §`auto_focus`
+//! Contract, §`refocus_train` Contract, § Filter Focus Offsets).
 
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::time::Duration;
 
 use rmcp::handler::server::wrapper::Parameters;
@@ -18,6 +19,7 @@ use super::super::internals::ResolvedParams;
 use super::super::progress::{ProgressEmitter, ProgressSink};
 use super::super::{tool_error, tool_success};
 use crate::config::{TrainAutoFocusConfig, TrainPurpose};
+use crate::equipment::trains::TrainDeviceKind;
 use crate::events::EventEnvelope;
 use crate::imaging;
 
@@ -80,6 +82,23 @@ pub struct RefocusTrainParams {
     pub reason: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(extend("required" = ["train_id"]))]
+pub struct MeasureFilterOffsetsParams {
+    /// The imaging train to measure. Needs exactly one filter wheel, a
+    /// focuser, and an `auto_focus` config block.
+    #[serde(default)]
+    pub train_id: Option<String>,
+    /// The filter the offsets are measured against (its offset is 0).
+    /// Default: the wheel's first filter.
+    #[serde(default)]
+    pub reference_filter: Option<String>,
+    /// Filters to measure, by name. Default: every filter on the wheel.
+    /// The reference is always measured, first.
+    #[serde(default)]
+    pub filters: Option<Vec<String>>,
+}
+
 /// One fully-resolved AF step of a `refocus_train` expansion: a
 /// capture-based V-curve run in an imaging train, or a PHD2-metric
 /// run in the guiding train.
@@ -137,6 +156,19 @@ impl McpHandler {
         self.refocus_train_inner(params, progress_sink).await
     }
 
+    #[tool(
+        description = "Measure an imaging train's per-filter focus offsets: switch its filter wheel to the reference filter and run auto_focus, then do the same for every other filter (set_filter's existing offsets pre-position the focuser), and take each filter's offset as its best focus position minus the reference's. The offsets are written to the train's focus_offsets in rp's config file through the same validate-and-persist path as PUT /api/config, and take effect for set_filter immediately. Filters not measured this run keep their offsets, re-based through a filter measured both this run and in the old set (the reference when it has one); with no such filter they cannot be placed and are dropped, listed in dropped_filters. The wheel is left on the last filter measured. Sweep parameters come from the train's auto_focus config block"
+    )]
+    pub(crate) async fn measure_filter_offsets(
+        &self,
+        Parameters(params): Parameters<MeasureFilterOffsetsParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let progress_sink = ProgressSink::from_request_context(&ctx);
+        self.measure_filter_offsets_inner(params, progress_sink)
+            .await
+    }
+
     /// Body of the `auto_focus` MCP tool, split out so unit tests can
     /// pass `None` for the progress sink without constructing a real
     /// rmcp `Peer` (its constructor is `pub(crate)` in rmcp 1.7).
@@ -529,12 +561,193 @@ impl McpHandler {
         }))
     }
 
+    /// Body of the `measure_filter_offsets` MCP tool — see
+    /// `auto_focus_inner` for why the split exists.
+    pub(crate) async fn measure_filter_offsets_inner(
+        &self,
+        params: MeasureFilterOffsetsParams,
+        progress_sink: Option<ProgressSink>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(train_id) = params.train_id else {
+            return Ok(tool_error!("missing required parameter: train_id"));
+        };
+
+        // Everything the run needs is resolved before the first filter
+        // switch: a call that cannot finish never moves hardware.
+        let Some(train) = self.trains.train(&train_id) else {
+            return Ok(tool_error!("train not found: {}", train_id));
+        };
+        if train.purpose == TrainPurpose::Guiding {
+            return Ok(tool_error!(
+                "measure_filter_offsets: train '{}' is the guiding train; offsets are \
+                 measured by capture through an imaging train",
+                train_id
+            ));
+        }
+        let wheels: Vec<&str> = train
+            .devices
+            .iter()
+            .filter(|d| d.kind == TrainDeviceKind::FilterWheel)
+            .map(|d| d.id.as_str())
+            .collect();
+        let wheel_id = match wheels.as_slice() {
+            [id] => (*id).to_string(),
+            [] => return Ok(tool_error!("train '{}' has no filter wheel", train_id)),
+            many => {
+                return Ok(tool_error!(
+                    "measure_filter_offsets: train '{}' has {} filter wheels; focus offsets \
+                     need exactly one",
+                    train_id,
+                    many.len()
+                ))
+            }
+        };
+        let Some(camera_id) = train.camera_id().map(str::to_string) else {
+            return Ok(tool_error!("train '{}' has no camera", train_id));
+        };
+        let Some(focuser_id) = train.terminal_focuser().map(str::to_string) else {
+            return Ok(tool_error!("train '{}' has no focuser", train_id));
+        };
+        let Some(block) = &train.auto_focus else {
+            return Ok(tool_error!(
+                "measure_filter_offsets: train '{}' has no auto_focus config block",
+                train_id
+            ));
+        };
+        let af_params = match af_params_from_block(&train_id, block) {
+            Ok(p) => p,
+            Err(e) => return Ok(tool_error!("measure_filter_offsets: {}", e)),
+        };
+        let Some(source) = &self.equipment_source else {
+            return Ok(tool_error!(
+                "measure_filter_offsets: equipment source not configured"
+            ));
+        };
+        let Some(wheel) = self.equipment.find_filter_wheel(&wheel_id) else {
+            return Ok(tool_error!("filter wheel not found: {}", wheel_id));
+        };
+        let names = wheel.config.filters;
+        let reference = match params.reference_filter {
+            Some(name) => name,
+            None => match names.first() {
+                Some(name) => name.clone(),
+                None => {
+                    return Ok(tool_error!(
+                        "measure_filter_offsets: filter wheel '{}' has no named filters",
+                        wheel_id
+                    ))
+                }
+            },
+        };
+        let requested = params.filters.unwrap_or_else(|| names.clone());
+        // Reference first, then the rest in the caller's order, once each.
+        let mut order = vec![reference.clone()];
+        for name in requested {
+            if !order.contains(&name) {
+                order.push(name);
+            }
+        }
+        if let Some(unknown) = order.iter().find(|name| !names.contains(name)) {
+            return Ok(tool_error!("filter not found: {}", unknown));
+        }
+        if order.len() < 2 {
+            return Ok(tool_error!(
+                "measure_filter_offsets: nothing to measure besides the reference filter '{}'",
+                reference
+            ));
+        }
+
+        let mut reference_position: Option<i32> = None;
+        let mut measurements = Vec::with_capacity(order.len());
+        let mut measured = BTreeMap::new();
+        for filter in &order {
+            debug!(train_id, filter = %filter, "measuring filter focus offset");
+            if let Err(e) = self.do_set_filter(&wheel_id, filter).await {
+                return Ok(tool_error!(
+                    "measure_filter_offsets: switching to '{}' failed: {}",
+                    filter,
+                    e
+                ));
+            }
+            let result = match self
+                .run_auto_focus_step(
+                    &camera_id,
+                    &focuser_id,
+                    af_params.clone(),
+                    progress_sink.clone(),
+                )
+                .await
+            {
+                Ok(result) => result,
+                Err(e) => {
+                    return Ok(tool_error!(
+                        "measure_filter_offsets: auto-focus through '{}' failed: {}",
+                        filter,
+                        e
+                    ))
+                }
+            };
+            let reference_position = *reference_position.get_or_insert(result.best_position);
+            let offset = result.best_position.saturating_sub(reference_position);
+            measured.insert(filter.clone(), offset);
+            measurements.push(serde_json::json!({
+                "filter": filter,
+                "best_position": result.best_position,
+                "best_hfr": result.best_hfr,
+                "offset": offset,
+            }));
+        }
+
+        let previous = self
+            .focus_offsets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&train_id)
+            .cloned()
+            .unwrap_or_default();
+        let (offsets, dropped_filters) = merge_focus_offsets(&previous, measured, &order);
+
+        let config_path = source.config_path.clone();
+        let (persist_train, persist_offsets) = (train_id.clone(), offsets.clone());
+        let persisted = tokio::task::spawn_blocking(move || {
+            crate::config_actions::persist_focus_offsets(
+                &config_path,
+                &persist_train,
+                &persist_offsets,
+            )
+        })
+        .await
+        .map_err(|e| format!("config write task join error: {e}"))
+        .and_then(|r| r);
+        if let Err(e) = persisted {
+            return Ok(tool_error!(
+                "measure_filter_offsets: measured {} but writing them to the config failed: {}",
+                serde_json::json!(offsets),
+                e
+            ));
+        }
+        self.focus_offsets
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(train_id.clone(), offsets.clone());
+
+        Ok(tool_success!({
+            "train_id": train_id,
+            "filter_wheel_id": wheel_id,
+            "reference_filter": reference,
+            "measurements": measurements,
+            "focus_offsets": offsets,
+            "dropped_filters": dropped_filters,
+        }))
+    }
+
     /// One full V-curve run for a resolved camera + focuser pair: the
     /// shared body of `auto_focus` and each `refocus_train` step.
     /// Resolves the devices, reads the starting position and
     /// temperature, emits the `focus_started` / `focus_complete` /
-    /// `focus_failed` triple, and drives the sweep through
-    /// [`AutoFocusAdapter`].
+    /// `focus_failed` triple, drives the sweep through
+    /// [`AutoFocusAdapter`], and records a success in the focus model
+    /// of the train the pair terminates.
     async fn run_auto_focus_step(
         &self,
         camera_id: &str,
@@ -627,6 +840,22 @@ impl McpHandler {
                         "samples_used": result.samples_used,
                     }),
                 ));
+                // The model is per train; a camera + focuser pair that
+                // is not some train's terminal pair has none to feed.
+                let train_id = self
+                    .trains
+                    .train_for_camera(camera_id)
+                    .filter(|t| t.terminal_focuser() == Some(focuser_id))
+                    .map(|t| t.id.clone());
+                if let Some(train_id) = train_id {
+                    self.record_focus_sample(
+                        &train_id,
+                        focuser_id,
+                        starting_temperature_c,
+                        result.best_position,
+                    )
+                    .await;
+                }
                 Ok(result)
             }
             Err(e) => {
@@ -922,6 +1151,13 @@ impl McpHandler {
                         "method": "phd2_hfd",
                     }),
                 ));
+                self.record_focus_sample(
+                    train_id,
+                    focuser_id,
+                    outcome.temperature_c,
+                    outcome.best_position,
+                )
+                .await;
                 Ok(outcome)
             }
             Err(e) => {
@@ -1042,6 +1278,38 @@ fn af_params_from_block(
     })
 }
 
+/// A `measure_filter_offsets` run's new offset set: `measured` (relative
+/// to this run's reference) plus every filter of `previous` the run
+/// skipped. Offsets are relative, so a skipped filter's distance from
+/// any filter measured in both runs still holds; it is re-based through
+/// the first such filter in measuring `order` — the reference, when the
+/// old set had one. With no filter in common the skipped filters cannot
+/// be placed and are returned as dropped instead.
+pub(crate) fn merge_focus_offsets(
+    previous: &BTreeMap<String, i32>,
+    mut measured: BTreeMap<String, i32>,
+    order: &[String],
+) -> (BTreeMap<String, i32>, Vec<String>) {
+    let skipped = previous
+        .iter()
+        .filter(|(name, _)| !measured.contains_key(*name));
+    let anchor = order
+        .iter()
+        .find_map(|name| Some((*previous.get(name)?, *measured.get(name)?)));
+    let Some((old_anchor, new_anchor)) = anchor else {
+        let dropped = skipped.map(|(name, _)| name.clone()).collect();
+        return (measured, dropped);
+    };
+    let rebased: Vec<(String, i32)> = skipped
+        .map(|(name, offset)| {
+            let offset = offset.saturating_sub(old_anchor).saturating_add(new_anchor);
+            (name.clone(), offset)
+        })
+        .collect();
+    measured.extend(rebased);
+    (measured, Vec::new())
+}
+
 /// The metric-sweep geometry from the guiding train's `auto_focus`
 /// block — what a `refocus_train` metric step runs with.
 fn guide_sweep_from_block(block: &TrainAutoFocusConfig) -> GuideSweepParams {
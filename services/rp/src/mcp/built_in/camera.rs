@@ -9,7 +9,7 @@ use serde::Deserialize;
 use tracing::debug;
 
 use super::super::handler::McpHandler;
-use super::super::progress::{ProgressEmitter, ProgressSink};
+use super::super::progress::{ProgressEmitter, ProgressSink, PROGRESS_INTERVAL};
 use super::super::{resolve_device, tool_error, tool_success};
 
 #[derive(Debug, Deserialize, JsonSchema)]
@@ -48,6 +48,29 @@ pub struct CameraIdParams {
     pub camera_id: String,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetCameraSettingsParams {
+    pub camera_id: String,
+    /// Sensor gain, in the camera's own units (ASCOM `Gain`).
+    #[serde(default)]
+    pub gain: Option<i32>,
+    /// Sensor offset / pedestal, in the camera's own units (ASCOM
+    /// `Offset`).
+    #[serde(default)]
+    pub offset: Option<i32>,
+    /// Binning as `"AxB"`, e.g. `"2x2"`.
+    #[serde(default)]
+    pub binning: Option<rp_vocabulary::Binning>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CoolCameraParams {
+    pub camera_id: String,
+    /// The rung to regulate at, °C — one of the camera's
+    /// `cooler_targets_c`.
+    pub target_c: i32,
+}
+
 #[tool_router(router = tool_router_camera, vis = "pub")]
 impl McpHandler {
     #[tool(
@@ -105,7 +128,11 @@ impl McpHandler {
         }
     }
 
-    #[tool(description = "Read camera capabilities: max_adu, exposure limits, sensor dimensions")]
+    #[tool(
+        description = "Read camera capabilities: max_adu, exposure limits, sensor dimensions, \
+                       current binning / gain / offset, and the cooler_targets_c dark-library \
+                       ladder"
+    )]
     pub(crate) async fn get_camera_info(
         &self,
         Parameters(params): Parameters<CameraIdParams>,
@@ -154,6 +181,16 @@ impl McpHandler {
             }
         };
 
+        // Gain and offset are operator-mutable like binning, so they are
+        // read live; `null` when the camera does not implement them.
+        let gain = cam.gain().await.ok();
+        let offset = cam.offset().await.ok();
+
+        // The dark-library ladder (rp.md § Camera Cooling), ascending —
+        // the rungs `cool_camera` accepts.
+        let mut cooler_targets_c = cam_entry.config.cooler_targets_c.clone();
+        cooler_targets_c.sort_unstable();
+
         Ok(tool_success!({
             "camera_id": params.camera_id,
             "max_adu": max_adu,
@@ -163,6 +200,130 @@ impl McpHandler {
             "bin_y": bin_y,
             "exposure_min": humantime::format_duration(exposure_min).to_string(),
             "exposure_max": humantime::format_duration(exposure_max).to_string(),
+            "gain": gain,
+            "offset": offset,
+            "cooler_targets_c": cooler_targets_c,
+        }))
+    }
+
+    #[tool(
+        description = "Set the camera's readout settings: any of gain, offset and binning \
+                       (\"AxB\"). Returns the values read back afterwards (null where the \
+                       camera does not report one)"
+    )]
+    pub(crate) async fn set_camera_settings(
+        &self,
+        Parameters(params): Parameters<SetCameraSettingsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        if params.gain.is_none() && params.offset.is_none() && params.binning.is_none() {
+            return Ok(tool_error!(
+                "set_camera_settings: pass at least one of gain, offset or binning"
+            ));
+        }
+        let (_, cam) = resolve_device!(self, find_camera, &params.camera_id, "camera");
+
+        if let Some(gain) = params.gain {
+            if let Err(e) = cam.set_gain(gain).await {
+                return Ok(tool_error!("failed to set gain to {}: {}", gain, e));
+            }
+        }
+        if let Some(offset) = params.offset {
+            if let Err(e) = cam.set_offset(offset).await {
+                return Ok(tool_error!("failed to set offset to {}: {}", offset, e));
+            }
+        }
+        if let Some(binning) = params.binning {
+            if let Err(e) = cam.set_bin_x(binning.x).await {
+                return Ok(tool_error!("failed to set binning to {}: {}", binning, e));
+            }
+            if let Err(e) = cam.set_bin_y(binning.y).await {
+                return Ok(tool_error!("failed to set binning to {}: {}", binning, e));
+            }
+        }
+
+        let binning = cam.bin().await.ok().map(|bin| rp_vocabulary::Binning {
+            x: bin[0] as u8,
+            y: bin[1] as u8,
+        });
+        let gain = cam.gain().await.ok();
+        let offset = cam.offset().await.ok();
+        debug!(camera_id = %params.camera_id, ?gain, ?offset, ?binning, "camera settings applied");
+        Ok(tool_success!({
+            "camera_id": params.camera_id,
+            "gain": gain,
+            "offset": offset,
+            "binning": binning,
+        }))
+    }
+
+    #[tool(
+        description = "Cool the camera to one rung of its cooler_targets_c dark-library ladder \
+                       and wait until the cooler has stabilized there or proven it unreachable \
+                       tonight (then switched off). Returns stabilized: true/false. Long-running: \
+                       bounded by cooling.max_cooldown"
+    )]
+    pub(crate) async fn cool_camera(
+        &self,
+        Parameters(params): Parameters<CoolCameraParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        // A pass can run for the whole `max_cooldown` (default well past
+        // rmcp's 300 s session keep-alive), so it ticks progress like
+        // every other long wait — see `mcp::progress`.
+        let sink = ProgressSink::from_request_context(&ctx);
+        let emitter = sink.as_ref().map(ProgressSink::as_emitter);
+        self.cool_camera_inner(params, emitter).await
+    }
+
+    /// Body of the `cool_camera` MCP tool, split out so unit tests can
+    /// pass a counting emitter without constructing a real rmcp `Peer`.
+    pub(crate) async fn cool_camera_inner(
+        &self,
+        params: CoolCameraParams,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(cooling) = self.cooling.clone() else {
+            return Ok(tool_error!("camera cooling is not available"));
+        };
+        let _ = resolve_device!(self, find_camera, &params.camera_id, "camera");
+        let ladder = cooling.ladder_for(&params.camera_id);
+        if !ladder.contains(&params.target_c) {
+            return Ok(tool_error!(
+                "{} °C is not a cooler_targets_c rung of camera {} (ladder: {:?})",
+                params.target_c,
+                params.camera_id,
+                ladder
+            ));
+        }
+
+        let pass = cooling.cool_to_rung(&params.camera_id, params.target_c);
+        let stabilized = match progress {
+            None => pass.await,
+            Some(sink) => {
+                tokio::pin!(pass);
+                let total_secs = cooling.max_cooldown().as_secs_f64();
+                let started_at = tokio::time::Instant::now();
+                let mut ticks =
+                    tokio::time::interval_at(started_at + PROGRESS_INTERVAL, PROGRESS_INTERVAL);
+                loop {
+                    tokio::select! {
+                        stabilized = &mut pass => break stabilized,
+                        _ = ticks.tick() => {
+                            sink.emit(
+                                started_at.elapsed().as_secs_f64(),
+                                Some(total_secs),
+                                Some(format!("cooling to {} °C", params.target_c)),
+                            )
+                            .await;
+                        }
+                    }
+                }
+            }
+        };
+        Ok(tool_success!({
+            "camera_id": params.camera_id,
+            "target_c": params.target_c,
+            "stabilized": stabilized,
         }))
     }
 }
@@ -109,14 +109,54 @@ impl McpHandler {
             return Ok(tool_error!("{}", e));
         }
 
+        let cot_params = imaging::tools::center_on_target::CenterOnTargetParams {
+            ra,
+            dec,
+            duration,
+            tolerance_arcsec,
+            max_attempts,
+        };
+        match self
+            .do_center_on_target(&camera_id, cot_params, progress_sink)
+            .await
+        {
+            Ok(result) => {
+                let iterations =
+                    serde_json::to_value(&result.iterations).unwrap_or(serde_json::Value::Null);
+                Ok(tool_success!({
+                    "final_error_arcsec": result.final_error_arcsec,
+                    "attempts": result.attempts,
+                    "final_ra": result.final_ra,
+                    "final_dec": result.final_dec,
+                    "iterations": iterations,
+                }))
+            }
+            Err(e) => Ok(tool_error!("{}", e)),
+        }
+    }
+}
+
+impl McpHandler {
+    /// The centering loop under its `centering_started` /
+    /// `centering_complete` / `centering_failed` triple, with one
+    /// `centering_iteration` event per pass. Shared by the
+    /// `center_on_target` tool and `perform_meridian_flip`'s re-center
+    /// leg; callers resolve the camera and mount first so their own
+    /// addressing errors surface before the triple opens.
+    pub(crate) async fn do_center_on_target(
+        &self,
+        camera_id: &str,
+        cot_params: imaging::tools::center_on_target::CenterOnTargetParams,
+        progress_sink: Option<ProgressSink>,
+    ) -> Result<imaging::tools::center_on_target::CenterOnTargetResult, String> {
         let operation_id = uuid::Uuid::new_v4().to_string();
         let started_at = chrono::Utc::now();
         // Advisory outer-loop deadline (§2.5): per-iteration slews/captures
         // carry their own deadlines; this sizes the whole loop for the
         // Sentinel watchdog. rp does not enforce it.
         let (predicted_ms, max_ms) = super::super::internals::centering_deadlines(
-            max_attempts,
-            duration,
+            cot_params.max_attempts,
+            cot_params.duration,
             self.centering.solve_time_estimate,
             self.centering.slew_overhead_estimate,
         );
@@ -127,23 +167,15 @@ impl McpHandler {
                 started_at,
                 serde_json::json!({
                     "camera_id": camera_id,
-                    "ra": ra,
-                    "dec": dec,
-                    "tolerance_arcsec": tolerance_arcsec,
-                    "max_attempts": max_attempts,
+                    "ra": cot_params.ra,
+                    "dec": cot_params.dec,
+                    "tolerance_arcsec": cot_params.tolerance_arcsec,
+                    "max_attempts": cot_params.max_attempts,
                 }),
             )
             .with_deadlines(predicted_ms, max_ms),
         );
 
-        let cot_params = imaging::tools::center_on_target::CenterOnTargetParams {
-            ra,
-            dec,
-            duration,
-            tolerance_arcsec,
-            max_attempts,
-        };
-
         // Store the per-request sink on the adapter so every
         // inner `do_capture` and `do_slew_blocking` call emits
         // progress through the same `progressToken`. See
@@ -151,12 +183,12 @@ impl McpHandler {
         // this guards against.
         let adapter = CenterOnTargetAdapter {
             handler: self,
-            camera_id: camera_id.clone(),
+            camera_id: camera_id.to_string(),
             progress: progress_sink,
         };
 
         let event_bus = self.event_bus.clone();
-        let camera_id_for_event = camera_id.clone();
+        let camera_id_for_event = camera_id.to_string();
         let emit_iteration = move |rec: &imaging::tools::center_on_target::IterationRecord| {
             let action = serde_json::to_value(rec.action).unwrap_or(serde_json::Value::Null);
             event_bus.emit(
@@ -195,25 +227,18 @@ impl McpHandler {
                             "final_dec": result.final_dec,
                         }),
                     ));
-                let iterations =
-                    serde_json::to_value(&result.iterations).unwrap_or(serde_json::Value::Null);
-                Ok(tool_success!({
-                    "final_error_arcsec": result.final_error_arcsec,
-                    "attempts": result.attempts,
-                    "final_ra": result.final_ra,
-                    "final_dec": result.final_dec,
-                    "iterations": iterations,
-                }))
+                Ok(result)
             }
             Err(e) => {
+                let msg = e.to_string();
                 self.event_bus
                     .emit_operation(crate::events::EventEnvelope::failed(
                         "centering",
                         &operation_id,
                         started_at,
-                        &e.to_string(),
+                        &msg,
                     ));
-                Ok(tool_error!("{}", e))
+                Err(msg)
             }
         }
     }
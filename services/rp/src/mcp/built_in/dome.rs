@@ -0,0 +1,369 @@
+//! Dome tool category: `open_shutter`, `close_shutter`, `park_dome`,
+//! `slew_dome_to_azimuth`, `get_dome_state`, `set_dome_slaving`
+//! (rp.md § Dome Tool Details).
+//!
+//! The motion tools block polling the device until it reports the
+//! requested state, under a fixed ceiling: shutters and roll-off roofs
+//! take minutes, not seconds. `park_dome` and `slew_dome_to_azimuth`
+//! refuse while the dome is slaved — the follower would immediately
+//! drive it back to the mount's pointing. The five-minute ceiling
+//! matches rmcp's session keep-alive, so every motion wait ticks
+//! progress (`mcp::progress`).
+
+use std::time::Duration;
+
+use ascom_alpaca::api::dome::ShutterState;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_router, RoleServer};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tracing::debug;
+
+use super::super::handler::McpHandler;
+use super::super::progress::{ProgressEmitter, ProgressSink, PROGRESS_INTERVAL};
+use super::super::{resolve_device, tool_error, tool_success};
+
+/// Ceiling on every dome motion poll. A roll-off roof or a full dome
+/// rotation on a slow drive runs a few minutes; five covers both
+/// without leaving a stuck shutter blocking a workflow all night.
+const DOME_MOTION_DEADLINE: Duration = Duration::from_mins(5);
+
+/// How often the motion tools re-read the device.
+const DOME_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DomeIdParams {
+    pub dome_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SlewDomeParams {
+    pub dome_id: String,
+    /// Target azimuth in degrees, `0.0 <= azimuth < 360.0`, north
+    /// through east.
+    pub azimuth: f64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetDomeSlavingParams {
+    pub dome_id: String,
+    /// `true` starts following the mount; `false` stops.
+    pub enabled: bool,
+}
+
+#[tool_router(router = tool_router_dome, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "Read the dome state without actuating anything: shutter status (Open | Closed | Opening | Closing | Error), azimuth, slewing, at_park, and whether rp is slaving it to the mount"
+    )]
+    pub(crate) async fn get_dome_state(
+        &self,
+        Parameters(params): Parameters<DomeIdParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, dome) = resolve_device!(self, find_dome, &params.dome_id, "dome");
+        let shutter = match dome.shutter_status().await {
+            Ok(s) => format!("{s:?}"),
+            Err(e) => return Ok(tool_error!("failed to read shutter status: {}", e)),
+        };
+        // Azimuth and park state are optional capabilities (a roll-off
+        // roof has neither); report them as null rather than failing.
+        let azimuth = dome.azimuth().await.ok();
+        let slewing = dome.slewing().await.ok();
+        let at_park = dome.at_park().await.ok();
+        let slaved = self.dome_slaving.slaved_dome().as_deref() == Some(params.dome_id.as_str());
+        Ok(tool_success!({
+            "shutter_status": shutter,
+            "azimuth": azimuth,
+            "slewing": slewing,
+            "at_park": at_park,
+            "slaved": slaved,
+        }))
+    }
+
+    #[tool(description = "Open the dome shutter or roll-off roof (blocks until open)")]
+    pub(crate) async fn open_shutter(
+        &self,
+        Parameters(params): Parameters<DomeIdParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let sink = ProgressSink::from_request_context(&ctx);
+        self.open_shutter_inner(params, sink.as_ref().map(ProgressSink::as_emitter))
+            .await
+    }
+
+    /// Body of the `open_shutter` MCP tool, split out so unit tests can
+    /// pass a counting emitter without constructing a real rmcp `Peer`.
+    pub(crate) async fn open_shutter_inner(
+        &self,
+        params: DomeIdParams,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, dome) = resolve_device!(self, find_dome, &params.dome_id, "dome");
+
+        debug!(dome_id = %params.dome_id, "opening shutter");
+        if let Err(e) = dome.open_shutter().await {
+            return Ok(tool_error!("failed to open shutter: {}", e));
+        }
+        match wait_for_shutter(dome.as_ref(), ShutterState::Open, progress).await {
+            Ok(()) => {
+                debug!(dome_id = %params.dome_id, "shutter open");
+                Ok(tool_success!({"status": "open"}))
+            }
+            Err(e) => Ok(tool_error!("open_shutter: {}", e)),
+        }
+    }
+
+    #[tool(description = "Close the dome shutter or roll-off roof (blocks until closed)")]
+    pub(crate) async fn close_shutter(
+        &self,
+        Parameters(params): Parameters<DomeIdParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let sink = ProgressSink::from_request_context(&ctx);
+        self.close_shutter_inner(params, sink.as_ref().map(ProgressSink::as_emitter))
+            .await
+    }
+
+    /// Body of the `close_shutter` MCP tool; see [`Self::open_shutter_inner`].
+    pub(crate) async fn close_shutter_inner(
+        &self,
+        params: DomeIdParams,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, dome) = resolve_device!(self, find_dome, &params.dome_id, "dome");
+
+        debug!(dome_id = %params.dome_id, "closing shutter");
+        if let Err(e) = dome.close_shutter().await {
+            return Ok(tool_error!("failed to close shutter: {}", e));
+        }
+        match wait_for_shutter(dome.as_ref(), ShutterState::Closed, progress).await {
+            Ok(()) => {
+                debug!(dome_id = %params.dome_id, "shutter closed");
+                Ok(tool_success!({"status": "closed"}))
+            }
+            Err(e) => Ok(tool_error!("close_shutter: {}", e)),
+        }
+    }
+
+    #[tool(
+        description = "Park the dome (blocks until at_park). Refused while the dome is slaved to the mount — call set_dome_slaving with enabled=false first"
+    )]
+    pub(crate) async fn park_dome(
+        &self,
+        Parameters(params): Parameters<DomeIdParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let sink = ProgressSink::from_request_context(&ctx);
+        self.park_dome_inner(params, sink.as_ref().map(ProgressSink::as_emitter))
+            .await
+    }
+
+    /// Body of the `park_dome` MCP tool; see [`Self::open_shutter_inner`].
+    pub(crate) async fn park_dome_inner(
+        &self,
+        params: DomeIdParams,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, dome) = resolve_device!(self, find_dome, &params.dome_id, "dome");
+        if let Some(msg) = self.refuse_while_slaved("park_dome", &params.dome_id) {
+            return Ok(tool_error!("{}", msg));
+        }
+
+        debug!(dome_id = %params.dome_id, "parking dome");
+        if let Err(e) = dome.park().await {
+            return Ok(tool_error!("failed to park dome: {}", e));
+        }
+        let mut ticker = MotionProgress::new(progress, "parking dome");
+        let deadline = tokio::time::Instant::now() + DOME_MOTION_DEADLINE;
+        loop {
+            tokio::time::sleep(DOME_POLL_INTERVAL).await;
+            ticker.tick().await;
+            match dome.at_park().await {
+                Ok(true) => {
+                    debug!(dome_id = %params.dome_id, "dome parked");
+                    return Ok(tool_success!({"status": "parked"}));
+                }
+                Ok(false) if tokio::time::Instant::now() < deadline => continue,
+                Ok(false) => break,
+                Err(e) => return Ok(tool_error!("error polling dome at_park: {}", e)),
+            }
+        }
+
+        Ok(tool_error!("timeout waiting for dome to park"))
+    }
+
+    #[tool(
+        description = "Rotate the dome to an absolute azimuth in degrees (0.0 <= azimuth < 360.0, north through east), blocking until it stops. Returns the read-back azimuth. Refused while the dome is slaved to the mount"
+    )]
+    pub(crate) async fn slew_dome_to_azimuth(
+        &self,
+        Parameters(params): Parameters<SlewDomeParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let sink = ProgressSink::from_request_context(&ctx);
+        self.slew_dome_to_azimuth_inner(params, sink.as_ref().map(ProgressSink::as_emitter))
+            .await
+    }
+
+    /// Body of the `slew_dome_to_azimuth` MCP tool; see
+    /// [`Self::open_shutter_inner`].
+    pub(crate) async fn slew_dome_to_azimuth_inner(
+        &self,
+        params: SlewDomeParams,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let azimuth = params.azimuth;
+        if !azimuth.is_finite() || !(0.0..360.0).contains(&azimuth) {
+            return Ok(tool_error!(
+                "slew_dome_to_azimuth: azimuth out of range: {} (expected 0.0 <= azimuth < 360.0)",
+                azimuth
+            ));
+        }
+        let (_entry, dome) = resolve_device!(self, find_dome, &params.dome_id, "dome");
+        if let Some(msg) = self.refuse_while_slaved("slew_dome_to_azimuth", &params.dome_id) {
+            return Ok(tool_error!("{}", msg));
+        }
+
+        debug!(dome_id = %params.dome_id, azimuth, "slewing dome");
+        if let Err(e) = dome.slew_to_azimuth(azimuth).await {
+            return Ok(tool_error!("failed to slew dome: {}", e));
+        }
+        let mut ticker = MotionProgress::new(progress, format!("slewing dome to {azimuth}°"));
+        let deadline = tokio::time::Instant::now() + DOME_MOTION_DEADLINE;
+        loop {
+            tokio::time::sleep(DOME_POLL_INTERVAL).await;
+            ticker.tick().await;
+            match dome.slewing().await {
+                Ok(false) => break,
+                Ok(true) if tokio::time::Instant::now() < deadline => continue,
+                Ok(true) => return Ok(tool_error!("timeout waiting for dome slew to finish")),
+                Err(e) => return Ok(tool_error!("error polling dome slewing: {}", e)),
+            }
+        }
+        match dome.azimuth().await {
+            Ok(actual) => {
+                debug!(dome_id = %params.dome_id, azimuth = actual, "dome slew complete");
+                Ok(tool_success!({"azimuth": actual}))
+            }
+            Err(e) => Ok(tool_error!("failed to read dome azimuth: {}", e)),
+        }
+    }
+
+    #[tool(
+        description = "Start (enabled=true) or stop (enabled=false) slaving the dome to the mount. While slaved, rp keeps the slit in front of the telescope using the dome's configured slaving geometry, and imaging-train captures wait for the slit to catch up before exposing. One dome at a time: enabling a dome replaces any other slaved dome. Requires the site block and the dome's slaving config"
+    )]
+    pub(crate) async fn set_dome_slaving(
+        &self,
+        Parameters(params): Parameters<SetDomeSlavingParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(entry) = self.equipment.find_dome(&params.dome_id) else {
+            return Ok(tool_error!("dome not found: {}", params.dome_id));
+        };
+        if !params.enabled {
+            let was = self.dome_slaving.slaved_dome();
+            if was.as_deref() == Some(params.dome_id.as_str()) {
+                self.dome_slaving.disable();
+            }
+            return Ok(tool_success!({"dome_id": params.dome_id, "slaved": false}));
+        }
+        let Some(slaving) = entry.config.slaving.as_ref() else {
+            return Ok(tool_error!(
+                "set_dome_slaving: dome {} has no slaving config (equipment.domes[].slaving)",
+                params.dome_id
+            ));
+        };
+        let Some(site) = self.site.as_ref() else {
+            return Ok(tool_error!(
+                "{}",
+                crate::planner::primitives::site_required_error()
+            ));
+        };
+        if let Err(e) = self
+            .dome_slaving
+            .enable(&params.dome_id, slaving, site.latitude_degrees)
+        {
+            return Ok(tool_error!("set_dome_slaving: {}", e));
+        }
+        Ok(tool_success!({"dome_id": params.dome_id, "slaved": true}))
+    }
+}
+
+impl McpHandler {
+    /// The refusal for a manual dome motion while `dome_id` is slaved,
+    /// or `None` when it is not.
+    fn refuse_while_slaved(&self, tool: &str, dome_id: &str) -> Option<String> {
+        (self.dome_slaving.slaved_dome().as_deref() == Some(dome_id)).then(|| {
+            format!(
+                "{tool}: dome {dome_id} is slaved to the mount; \
+                 call set_dome_slaving with enabled=false first"
+            )
+        })
+    }
+}
+
+/// Progress for one dome motion wait: a tick every
+/// [`PROGRESS_INTERVAL`] of elapsed seconds against the
+/// [`DOME_MOTION_DEADLINE`] ceiling. A no-op without an emitter.
+struct MotionProgress<'a> {
+    progress: Option<&'a dyn ProgressEmitter>,
+    message: String,
+    started_at: tokio::time::Instant,
+    last_progress_at: tokio::time::Instant,
+}
+
+impl<'a> MotionProgress<'a> {
+    fn new(progress: Option<&'a dyn ProgressEmitter>, message: impl Into<String>) -> Self {
+        let started_at = tokio::time::Instant::now();
+        Self {
+            progress,
+            message: message.into(),
+            started_at,
+            last_progress_at: started_at,
+        }
+    }
+
+    /// Called once per poll; emits when an interval has passed.
+    async fn tick(&mut self) {
+        let Some(sink) = self.progress else {
+            return;
+        };
+        let now = tokio::time::Instant::now();
+        if now.duration_since(self.last_progress_at) >= PROGRESS_INTERVAL {
+            sink.emit(
+                now.duration_since(self.started_at).as_secs_f64(),
+                Some(DOME_MOTION_DEADLINE.as_secs_f64()),
+                Some(self.message.clone()),
+            )
+            .await;
+            self.last_progress_at = now;
+        }
+    }
+}
+
+/// Poll the shutter until it reports `want`, failing fast on a
+/// shutter `Error` and after [`DOME_MOTION_DEADLINE`].
+async fn wait_for_shutter(
+    dome: &dyn ascom_alpaca::api::Dome,
+    want: ShutterState,
+    progress: Option<&dyn ProgressEmitter>,
+) -> Result<(), String> {
+    let mut ticker = MotionProgress::new(progress, format!("waiting for shutter {want:?}"));
+    let deadline = tokio::time::Instant::now() + DOME_MOTION_DEADLINE;
+    loop {
+        tokio::time::sleep(DOME_POLL_INTERVAL).await;
+        ticker.tick().await;
+        match dome.shutter_status().await {
+            Ok(state) if state == want => return Ok(()),
+            Ok(ShutterState::Error) => return Err("shutter reported an error state".to_string()),
+            Ok(_) if tokio::time::Instant::now() < deadline => continue,
+            Ok(state) => {
+                return Err(format!(
+                    "timeout waiting for shutter to reach {want:?} (last status {state:?})"
+                ))
+            }
+            Err(e) => return Err(format!("error polling shutter status: {e}")),
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Equipment tool category: `connect_device`, `disconnect_device`,
+//! `reload_equipment` (rp.md § Runtime Equipment Changes).
+//!
+//! The tools rebuild registry entries from the persisted config file
+//! without restarting rp; [`crate::equipment::SharedEquipment`] does the
+//! work and this module reports it. Every change that leaves a device
+//! connected emits `device_connected`; every change that takes a
+//! connected device away emits `device_disconnected` with the reason.
+
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::{tool, tool_router};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::super::handler::McpHandler;
+use super::super::{tool_error, tool_success};
+use crate::equipment::{DeviceChange, DeviceKind};
+use crate::events::EventBus;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DeviceParams {
+    /// Equipment kind, keyed like the config file's `equipment` block:
+    /// `cameras`, `filter_wheels`, `cover_calibrators`, `focusers`,
+    /// `safety_monitors`, `switches`, `rotators`,
+    /// `observing_conditions`, `domes`, or `mount`.
+    pub kind: DeviceKind,
+    /// Device id. Required for every kind except `mount`, which is
+    /// singular.
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReloadEquipmentParams {}
+
+/// Emit the events a change implies.
+fn emit_change(event_bus: &EventBus, change: &DeviceChange) {
+    if change.connected {
+        event_bus.emit(
+            "device_connected",
+            serde_json::json!({ "kind": change.kind, "id": change.id }),
+        );
+    } else if let Some(reason) = change.disconnect_reason() {
+        event_bus.emit(
+            "device_disconnected",
+            serde_json::json!({ "kind": change.kind, "id": change.id, "reason": reason }),
+        );
+    }
+}
+
+#[tool_router(router = tool_router_equipment, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "Connect one device from rp's config file without restarting rp: the entry is rebuilt from its persisted config (a device the running registry doesn't list yet is hot-added) and re-runs the connect-time reads — sensor size, pixel size and MaxADU for a camera, the site cross-check for the mount. Work already in progress keeps the device it started with. Errors if the config has no such device or it fails to connect"
+    )]
+    pub(crate) async fn connect_device(
+        &self,
+        Parameters(params): Parameters<DeviceParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(source) = &self.equipment_source else {
+            return Ok(tool_error!(
+                "connect_device: equipment source not configured"
+            ));
+        };
+        let equipment = match source.load_equipment() {
+            Ok(equipment) => equipment,
+            Err(e) => return Ok(tool_error!("connect_device: {}", e)),
+        };
+        let change = match self
+            .equipment
+            .connect_device(
+                params.kind,
+                params.id.as_deref(),
+                &equipment,
+                source.ca_cert_path.as_deref(),
+                source.site.as_ref(),
+            )
+            .await
+        {
+            Ok(change) => change,
+            Err(e) => return Ok(tool_error!("connect_device: {}", e)),
+        };
+        emit_change(&self.event_bus, &change);
+        if let Some(error) = &change.error {
+            return Ok(tool_error!("connect_device: {}", error));
+        }
+        Ok(tool_success!({
+            "kind": change.kind,
+            "id": change.id,
+            "connected": change.connected,
+        }))
+    }
+
+    #[tool(
+        description = "Release rp's connection to one device without restarting rp. The device stays listed as disconnected until connect_device or a reload brings it back; tools that need it report it not connected. Work already in progress keeps the device it started with. rp does not send Connected=false to the driver"
+    )]
+    pub(crate) async fn disconnect_device(
+        &self,
+        Parameters(params): Parameters<DeviceParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let change = match self
+            .equipment
+            .disconnect_device(params.kind, params.id.as_deref())
+            .await
+        {
+            Ok(change) => change,
+            Err(e) => return Ok(tool_error!("disconnect_device: {}", e)),
+        };
+        emit_change(&self.event_bus, &change);
+        Ok(tool_success!({
+            "kind": change.kind,
+            "id": change.id,
+            "was_connected": change.was_connected,
+        }))
+    }
+
+    #[tool(
+        description = "Bring rp's equipment in line with its config file without restarting: devices removed from the file are dropped, added or edited ones are connected (with the same connect-time reads as startup), unchanged ones are left alone. Returns one entry per device touched; a device that failed to connect is listed with its error rather than failing the call. Optical trains and other settings still need a restart"
+    )]
+    pub(crate) async fn reload_equipment(
+        &self,
+        Parameters(_params): Parameters<ReloadEquipmentParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(source) = &self.equipment_source else {
+            return Ok(tool_error!(
+                "reload_equipment: equipment source not configured"
+            ));
+        };
+        let equipment = match source.load_equipment() {
+            Ok(equipment) => equipment,
+            Err(e) => return Ok(tool_error!("reload_equipment: {}", e)),
+        };
+        let changes = self
+            .equipment
+            .reload_equipment(
+                &equipment,
+                source.ca_cert_path.as_deref(),
+                source.site.as_ref(),
+            )
+            .await;
+        for change in &changes {
+            emit_change(&self.event_bus, change);
+        }
+        Ok(tool_success!({ "changes": changes }))
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::time::Duration;
 
 use rmcp::handler::server::wrapper::Parameters;
@@ -31,7 +32,9 @@ pub struct FilterWheelIdParams {
 
 #[tool_router(router = tool_router_filter_wheel, vis = "pub")]
 impl McpHandler {
-    #[tool(description = "Set the active filter on a filter wheel")]
+    #[tool(
+        description = "Set the active filter on a filter wheel, addressed by filter_wheel_id or by train_id (the train's sole wheel). When a train containing the wheel configures focus_offsets for both the outgoing and the incoming filter, its terminal focuser is moved by the difference after the switch (deadline-bounded like move_focuser); each move is reported in focus_adjustments"
+    )]
     pub(crate) async fn set_filter(
         &self,
         Parameters(params): Parameters<SetFilterParams>,
@@ -43,47 +46,18 @@ impl McpHandler {
             Ok(id) => id,
             Err(e) => return Ok(*e),
         };
-        let (fw_entry, fw) =
-            resolve_device!(self, find_filter_wheel, &filter_wheel_id, "filter wheel");
-
-        let position = match fw_entry
-            .config
-            .filters
-            .iter()
-            .position(|f| f == &params.filter_name)
+        match self
+            .do_set_filter(&filter_wheel_id, &params.filter_name)
+            .await
         {
-            Some(p) => p,
-            None => return Ok(tool_error!("filter not found: {}", params.filter_name)),
-        };
-
-        if let Err(e) = fw.set_position(position).await {
-            return Ok(tool_error!("failed to set filter position: {}", e));
-        }
-
-        loop {
-            tokio::time::sleep(Duration::from_millis(100)).await;
-            match fw.position().await {
-                Ok(Some(p)) if p == position => break,
-                Ok(Some(_) | None) => continue,
-                Err(e) => {
-                    return Ok(tool_error!("error waiting for filter wheel: {}", e));
-                }
-            }
-        }
-
-        self.event_bus.emit(
-            "filter_switch",
-            serde_json::json!({
+            Ok(switch) => Ok(tool_success!({
                 "filter_wheel_id": filter_wheel_id,
                 "filter_name": params.filter_name,
-            }),
-        );
-
-        Ok(tool_success!({
-            "filter_wheel_id": filter_wheel_id,
-            "filter_name": params.filter_name,
-            "position": position,
-        }))
+                "position": switch.position,
+                "focus_adjustments": switch.focus_adjustments,
+            })),
+            Err(e) => Ok(tool_error!("{}", e)),
+        }
     }
 
     #[tool(description = "Get the current filter on a filter wheel")]
@@ -121,7 +95,146 @@ impl McpHandler {
     }
 }
 
+/// What [`McpHandler::do_set_filter`] did: the slot it settled on and
+/// the focus-offset moves it made.
+pub(crate) struct FilterSwitch {
+    pub position: usize,
+    pub focus_adjustments: Vec<serde_json::Value>,
+}
+
 impl McpHandler {
+    /// Switch `filter_wheel_id` to `filter_name`, wait for it to
+    /// settle, emit `filter_switch`, then apply the focus offsets of the
+    /// trains the wheel sits in — the shared body of the `set_filter`
+    /// tool and `measure_filter_offsets`' per-filter switch. Errors carry
+    /// the tool's wording, ready for `tool_error!`.
+    pub(crate) async fn do_set_filter(
+        &self,
+        filter_wheel_id: &str,
+        filter_name: &str,
+    ) -> Result<FilterSwitch, String> {
+        let fw_entry = self
+            .equipment
+            .find_filter_wheel(filter_wheel_id)
+            .ok_or_else(|| format!("filter wheel not found: {filter_wheel_id}"))?;
+        let fw = fw_entry
+            .device
+            .clone()
+            .ok_or_else(|| format!("filter wheel not connected: {filter_wheel_id}"))?;
+
+        let position = fw_entry
+            .config
+            .filters
+            .iter()
+            .position(|f| f == filter_name)
+            .ok_or_else(|| format!("filter not found: {filter_name}"))?;
+
+        // The outgoing filter, for the focus-offset difference. Best
+        // effort: a wheel that cannot say where it is (moving, read
+        // error) just gets no offset move.
+        let from_filter = match fw.position().await {
+            Ok(Some(p)) => fw_entry.config.filters.get(p).cloned(),
+            Ok(None) | Err(_) => None,
+        };
+
+        fw.set_position(position)
+            .await
+            .map_err(|e| format!("failed to set filter position: {e}"))?;
+
+        loop {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            match fw.position().await {
+                Ok(Some(p)) if p == position => break,
+                Ok(Some(_) | None) => continue,
+                Err(e) => return Err(format!("error waiting for filter wheel: {e}")),
+            }
+        }
+
+        self.event_bus.emit(
+            "filter_switch",
+            serde_json::json!({
+                "filter_wheel_id": filter_wheel_id,
+                "filter_name": filter_name,
+            }),
+        );
+
+        let focus_adjustments = self
+            .apply_filter_focus_offsets(filter_wheel_id, from_filter.as_deref(), filter_name)
+            .await
+            .map_err(|e| {
+                format!(
+                    "set_filter: filter changed to '{filter_name}' but the focus offset move \
+                     failed: {e}"
+                )
+            })?;
+
+        Ok(FilterSwitch {
+            position,
+            focus_adjustments,
+        })
+    }
+
+    /// Apply the per-filter focus offsets of every train `filter_wheel_id`
+    /// sits in (rp.md § Filter Focus Offsets): move the train's terminal
+    /// focuser by `offset[to] - offset[from]`. A train is skipped when
+    /// either filter has no offset, or the outgoing filter is unknown; a
+    /// focuser shared by two such trains moves once. Returns one
+    /// `{train_id, focuser_id, delta_steps, position}` entry per move.
+    async fn apply_filter_focus_offsets(
+        &self,
+        filter_wheel_id: &str,
+        from_filter: Option<&str>,
+        to_filter: &str,
+    ) -> Result<Vec<serde_json::Value>, String> {
+        let Some(from_filter) = from_filter else {
+            return Ok(Vec::new());
+        };
+        let mut adjustments = Vec::new();
+        let mut moved: HashSet<&str> = HashSet::new();
+        for train in self.trains.trains_with_device(filter_wheel_id) {
+            let delta_steps = {
+                let offsets = self
+                    .focus_offsets
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                let Some(train_offsets) = offsets.get(&train.id) else {
+                    continue;
+                };
+                match (train_offsets.get(from_filter), train_offsets.get(to_filter)) {
+                    (Some(from), Some(to)) => to.saturating_sub(*from),
+                    _ => continue,
+                }
+            };
+            let Some(focuser_id) = train.terminal_focuser() else {
+                continue;
+            };
+            if delta_steps == 0 || !moved.insert(focuser_id) {
+                continue;
+            }
+            let entry = self
+                .equipment
+                .find_focuser(focuser_id)
+                .ok_or_else(|| format!("focuser not found: {focuser_id}"))?;
+            let foc = entry
+                .device
+                .ok_or_else(|| format!("focuser not connected: {focuser_id}"))?;
+            let current = foc
+                .position()
+                .await
+                .map_err(|e| format!("failed to read focuser position: {e}"))?;
+            let position = self
+                .do_move_focuser_blocking(focuser_id, current.saturating_add(delta_steps), None)
+                .await?;
+            adjustments.push(serde_json::json!({
+                "train_id": train.id,
+                "focuser_id": focuser_id,
+                "delta_steps": delta_steps,
+                "position": position,
+            }));
+        }
+        Ok(adjustments)
+    }
+
     /// Resolve `set_filter`'s `filter_wheel_id` / `train_id`
     /// addressing: exactly one must be present, and a train must
     /// contain exactly one filter wheel (the sole-rotator rule of the
@@ -0,0 +1,314 @@
+//! Focus-model tool category: `get_focus_model`,
+//! `apply_temperature_compensation` (rp.md § Focus Temperature
+//! Compensation).
+//!
+//! [`crate::focus_model`] owns the history and the fit; this module
+//! records each successful auto-focus step into it and turns the fit
+//! into a focuser move. A compensation moves a focuser that an
+//! imaging train exposes through, so it takes the mount motion gate
+//! exclusively: in-flight subs finish first and no new one opens until
+//! the focuser has settled.
+
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_router, RoleServer};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tracing::debug;
+
+use super::super::handler::McpHandler;
+use super::super::progress::ProgressSink;
+use super::super::{tool_error, tool_success};
+use crate::config::TrainPurpose;
+use crate::equipment::trains::TrainDeviceKind;
+use crate::focus_model::{fit_model, FocusReference, FocusSample, ReferenceSource};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(extend("required" = ["train_id"]))]
+pub struct GetFocusModelParams {
+    /// The train whose model to report.
+    #[serde(default)]
+    pub train_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(extend("required" = ["train_id"]))]
+pub struct ApplyTemperatureCompensationParams {
+    /// The train whose terminal focuser to compensate.
+    #[serde(default)]
+    pub train_id: Option<String>,
+    /// Smallest predicted move (focuser steps) worth making. A smaller
+    /// prediction is reported and skipped, and the reference is kept so
+    /// the drift keeps accumulating. Positive integer; default 1.
+    #[serde(default)]
+    pub min_delta_steps: Option<i32>,
+}
+
+#[tool_router(router = tool_router_focus_model, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "Report an optical train's temperature-compensation model: the auto-focus samples recorded for its terminal focuser (temperature, best position, filter), the steps-per-°C slope fitted from them with outlier rejection (null with model_error when there are too few samples or too little temperature spread), the compensation reference, and — when the focuser reports a temperature — the move apply_temperature_compensation would make now"
+    )]
+    pub(crate) async fn get_focus_model(
+        &self,
+        Parameters(params): Parameters<GetFocusModelParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(train_id) = params.train_id else {
+            return Ok(tool_error!("missing required parameter: train_id"));
+        };
+        let Some(train) = self.trains.train(&train_id) else {
+            return Ok(tool_error!("train not found: {}", train_id));
+        };
+        let Some(focuser_id) = train.terminal_focuser() else {
+            return Ok(tool_error!("train '{}' has no focuser", train_id));
+        };
+        let history = self.focus_model.history(&train_id).await;
+        let samples = history.samples_for(focuser_id);
+        let (model, model_error) = match fit_model(&samples) {
+            Ok(fit) => (Some(fit), None),
+            Err(e) => (None, Some(e)),
+        };
+        let reference = history.reference.filter(|r| r.focuser_id == focuser_id);
+        // Best-effort: a focuser without a thermistor, or one that is
+        // disconnected, still has a model worth reporting.
+        let current_temperature_c = self
+            .focuser_reading(focuser_id)
+            .await
+            .ok()
+            .map(|(temperature_c, _)| temperature_c);
+        let predicted_delta_steps = match (&model, &reference, current_temperature_c) {
+            (Some(fit), Some(reference), Some(now_c)) => {
+                Some(fit.predicted_delta(reference.temperature_c, now_c))
+            }
+            _ => None,
+        };
+        Ok(tool_success!({
+            "train_id": train_id,
+            "focuser_id": focuser_id,
+            "samples": samples,
+            "model": model,
+            "model_error": model_error,
+            "reference": reference,
+            "current_temperature_c": current_temperature_c,
+            "predicted_delta_steps": predicted_delta_steps,
+        }))
+    }
+
+    #[tool(
+        description = "Move an optical train's terminal focuser by the focus model's predicted shift for the temperature change since the last auto-focus or compensation (slope × ΔT, rounded), and make the result the new reference. Call between exposures: when the focuser belongs to an imaging train the move waits for in-flight imaging exposures and holds new ones off (the mount motion gate, operation temperature_compensation). The move is deadline-bounded like move_focuser. Errors when the model cannot be fitted, no auto-focus reference exists, or the focuser reports no temperature"
+    )]
+    pub(crate) async fn apply_temperature_compensation(
+        &self,
+        Parameters(params): Parameters<ApplyTemperatureCompensationParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let progress_sink = ProgressSink::from_request_context(&ctx);
+        self.apply_temperature_compensation_inner(params, progress_sink)
+            .await
+    }
+
+    /// Body of the `apply_temperature_compensation` MCP tool — see
+    /// `auto_focus_inner` for why the split exists.
+    pub(crate) async fn apply_temperature_compensation_inner(
+        &self,
+        params: ApplyTemperatureCompensationParams,
+        progress_sink: Option<ProgressSink>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(train_id) = params.train_id else {
+            return Ok(tool_error!("missing required parameter: train_id"));
+        };
+        let min_delta_steps = params.min_delta_steps.unwrap_or(1);
+        if min_delta_steps < 1 {
+            return Ok(tool_error!(
+                "apply_temperature_compensation: min_delta_steps must be positive (got {})",
+                min_delta_steps
+            ));
+        }
+        let Some(train) = self.trains.train(&train_id) else {
+            return Ok(tool_error!("train not found: {}", train_id));
+        };
+        let Some(focuser_id) = train.terminal_focuser().map(str::to_string) else {
+            return Ok(tool_error!("train '{}' has no focuser", train_id));
+        };
+
+        // Model and reference first, so a call that cannot move never
+        // waits on the gate.
+        let history = self.focus_model.history(&train_id).await;
+        let fit = match fit_model(&history.samples_for(&focuser_id)) {
+            Ok(fit) => fit,
+            Err(e) => {
+                return Ok(tool_error!(
+                    "apply_temperature_compensation: no focus model for train '{}': {}",
+                    train_id,
+                    e
+                ))
+            }
+        };
+        let Some(reference) = history.reference.filter(|r| r.focuser_id == focuser_id) else {
+            return Ok(tool_error!(
+                "apply_temperature_compensation: train '{}' has no auto-focus reference",
+                train_id
+            ));
+        };
+
+        // The gate is held from the temperature read through the
+        // settle, so the delta is computed for the moment of the move.
+        let gated = self
+            .trains
+            .trains_with_device(&focuser_id)
+            .iter()
+            .any(|t| t.purpose == TrainPurpose::Imaging);
+        let _motion_permit = if gated {
+            Some(self.motion_gate.exclusive("temperature_compensation").await)
+        } else {
+            None
+        };
+
+        let (temperature_c, from_position) = match self.focuser_reading(&focuser_id).await {
+            Ok(reading) => reading,
+            Err(e) => return Ok(tool_error!("apply_temperature_compensation: {}", e)),
+        };
+        let delta_steps = fit.predicted_delta(reference.temperature_c, temperature_c);
+        if delta_steps.abs() < min_delta_steps {
+            return Ok(tool_success!({
+                "train_id": train_id,
+                "focuser_id": focuser_id,
+                "temperature_c": temperature_c,
+                "reference_temperature_c": reference.temperature_c,
+                "slope_steps_per_c": fit.slope_steps_per_c,
+                "delta_steps": delta_steps,
+                "from_position": from_position,
+                "to_position": from_position,
+                "moved": false,
+            }));
+        }
+
+        let emitter = progress_sink.as_ref().map(ProgressSink::as_emitter);
+        let to_position = match self
+            .do_move_focuser_blocking(
+                &focuser_id,
+                from_position.saturating_add(delta_steps),
+                emitter,
+            )
+            .await
+        {
+            Ok(position) => position,
+            Err(e) => return Ok(tool_error!("apply_temperature_compensation: {}", e)),
+        };
+        self.focus_model
+            .record_compensation(
+                &train_id,
+                FocusReference {
+                    focuser_id: focuser_id.clone(),
+                    temperature_c,
+                    position: to_position,
+                    source: ReferenceSource::Compensation,
+                    recorded_at: chrono::Utc::now().to_rfc3339(),
+                },
+            )
+            .await;
+        self.event_bus.emit(
+            "focus_compensated",
+            serde_json::json!({
+                "train_id": train_id,
+                "focuser_id": focuser_id,
+                "temperature_c": temperature_c,
+                "delta_steps": delta_steps,
+                "position": to_position,
+            }),
+        );
+
+        Ok(tool_success!({
+            "train_id": train_id,
+            "focuser_id": focuser_id,
+            "temperature_c": temperature_c,
+            "reference_temperature_c": reference.temperature_c,
+            "slope_steps_per_c": fit.slope_steps_per_c,
+            "delta_steps": delta_steps,
+            "from_position": from_position,
+            "to_position": to_position,
+            "moved": true,
+        }))
+    }
+}
+
+impl McpHandler {
+    /// Record a successful auto-focus step into `train_id`'s focus
+    /// model. Skipped when the focuser reported no temperature — such a
+    /// sample teaches the model nothing.
+    pub(crate) async fn record_focus_sample(
+        &self,
+        train_id: &str,
+        focuser_id: &str,
+        temperature_c: Option<f64>,
+        position: i32,
+    ) {
+        let Some(temperature_c) = temperature_c else {
+            debug!(
+                train_id,
+                focuser_id, "no focuser temperature; focus model sample not recorded"
+            );
+            return;
+        };
+        let filter = self.train_filter_name(train_id).await;
+        self.focus_model
+            .record_sample(
+                train_id,
+                FocusSample {
+                    focuser_id: focuser_id.to_string(),
+                    temperature_c,
+                    position,
+                    filter,
+                    recorded_at: chrono::Utc::now().to_rfc3339(),
+                },
+            )
+            .await;
+    }
+
+    /// The filter currently in `train_id`'s wheel, named as
+    /// `get_filter` names it. `None` when the train has no wheel (or
+    /// more than one) or the wheel cannot be read.
+    async fn train_filter_name(&self, train_id: &str) -> Option<String> {
+        let train = self.trains.train(train_id)?;
+        let mut wheels = train
+            .devices
+            .iter()
+            .filter(|d| d.kind == TrainDeviceKind::FilterWheel);
+        let wheel_id = wheels.next()?.id.clone();
+        if wheels.next().is_some() {
+            return None;
+        }
+        let entry = self.equipment.find_filter_wheel(&wheel_id)?;
+        let position = entry.device.as_ref()?.position().await.ok()??;
+        Some(
+            entry
+                .config
+                .filters
+                .get(position)
+                .cloned()
+                .unwrap_or_else(|| format!("Filter {position}")),
+        )
+    }
+
+    /// The focuser's temperature and position, with the standard
+    /// "not found" / "not connected" errors.
+    async fn focuser_reading(&self, focuser_id: &str) -> Result<(f64, i32), String> {
+        let entry = self
+            .equipment
+            .find_focuser(focuser_id)
+            .ok_or_else(|| format!("focuser not found: {focuser_id}"))?;
+        let foc = entry
+            .device
+            .ok_or_else(|| format!("focuser not connected: {focuser_id}"))?;
+        let temperature_c = foc
+            .temperature()
+            .await
+            .map_err(|e| format!("focuser '{focuser_id}' reports no temperature: {e}"))?;
+        let position = foc
+            .position()
+            .await
+            .map_err(|e| format!("failed to read focuser position: {e}"))?;
+        Ok((temperature_c, position))
+    }
+}
@@ -1,7 +1,8 @@
 //! Guider tool category: `start_guiding`, `stop_guiding`, `dither`,
-//! `pause_guiding`, `resume_guiding`, `get_guiding_stats`.
+//! `pause_guiding`, `resume_guiding`, `get_guiding_stats`,
+//! `get_guiding_settings`, `set_guiding_settings`.
 //!
-//! All six proxy to the guider rp-managed service (the `phd2-guider`
+//! All eight proxy to the guider rp-managed service (the `phd2-guider`
 //! binary's `serve` mode) through the `rp-guider` HTTP client on
 //! `McpHandler::guider`; `None` there means every tool errors with
 //! "guider not configured". Wire quantities are **guide-camera
@@ -19,7 +20,16 @@
 //! `stop_guiding` emits the `guide_stopped` point event with
 //! `reason: "requested"` (the safety enforcer emits the same event
 //! with `reason: "safety"`).
-
+//!
+//! `get_guiding_settings` / `set_guiding_settings` read and switch the
+//! guide algorithm profile — Dec guide mode, per-axis min-move and
+//! aggressiveness, the algorithms' own parameters, multi-star and Dec
+//! backlash compensation — so a workflow can tighten Dec for long
+//! narrowband subs and restore it afterwards. A setting the guider
+//! keeps out of its API (PHD2's multi-star and backlash compensation)
+//! reads back `null` and is refused by the service when set.
+
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use rmcp::handler::server::wrapper::Parameters;
@@ -138,6 +148,72 @@ pub struct ResumeGuidingParams {}
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetGuidingStatsParams {}
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct GetGuidingSettingsParams {}
+
+/// Declination guide mode, in PHD2's spelling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+pub enum DecGuideMode {
+    /// No Dec corrections.
+    Off,
+    /// Corrections in both directions.
+    Auto,
+    /// North corrections only.
+    North,
+    /// South corrections only.
+    South,
+}
+
+impl From<DecGuideMode> for rp_guider::DecGuideMode {
+    fn from(mode: DecGuideMode) -> Self {
+        match mode {
+            DecGuideMode::Off => Self::Off,
+            DecGuideMode::Auto => Self::Auto,
+            DecGuideMode::North => Self::North,
+            DecGuideMode::South => Self::South,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetGuidingSettingsParams {
+    /// Dec guide mode: `Off`, `Auto`, `North` or `South`.
+    #[serde(default)]
+    pub dec_guide_mode: Option<DecGuideMode>,
+    /// Smallest RA error corrected, in guide-camera pixels.
+    #[serde(default)]
+    pub ra_min_move_px: Option<f64>,
+    /// Fraction of each RA error corrected (0–1].
+    #[serde(default)]
+    pub ra_aggressiveness: Option<f64>,
+    /// Smallest Dec error corrected, in guide-camera pixels.
+    #[serde(default)]
+    pub dec_min_move_px: Option<f64>,
+    /// Fraction of each Dec error corrected (0–1].
+    #[serde(default)]
+    pub dec_aggressiveness: Option<f64>,
+    /// Other RA algorithm parameters by the guider's own names (as
+    /// `get_guiding_settings` lists them under `ra.params`).
+    #[serde(default)]
+    pub ra_params: Option<BTreeMap<String, f64>>,
+    /// Other Dec algorithm parameters by the guider's own names.
+    #[serde(default)]
+    pub dec_params: Option<BTreeMap<String, f64>>,
+    /// Guide on several stars at once.
+    #[serde(default)]
+    pub multi_star: Option<bool>,
+    /// Turn Dec backlash compensation on or off. Turning it on needs
+    /// `backlash_pulse`.
+    #[serde(default)]
+    pub backlash_compensation: Option<bool>,
+    /// Extra pulse added to the first Dec correction after a reversal
+    /// (humantime string, e.g. `"450ms"`). Given alone, it also turns
+    /// compensation on.
+    #[serde(default, with = "humantime_serde::option")]
+    #[schemars(with = "Option<String>")]
+    pub backlash_pulse: Option<Duration>,
+}
+
 #[tool_router(router = tool_router_guider, vis = "pub")]
 impl McpHandler {
     #[tool(
@@ -156,53 +232,18 @@ impl McpHandler {
             params.settle_timeout,
         );
         let recalibrate = params.recalibrate.unwrap_or(false);
-
-        let operation_id = uuid::Uuid::new_v4().to_string();
-        let started_at = chrono::Utc::now();
-        let started_payload = serde_json::json!({
-            "recalibrate": recalibrate,
-            "settle_pixels": settle.as_ref().and_then(|s| s.pixels),
-            "settle_time": humantime_or_null(settle.as_ref().and_then(|s| s.time)),
-            "settle_timeout": humantime_or_null(settle.as_ref().and_then(|s| s.timeout)),
-        });
-        self.event_bus.emit_operation(with_settle_deadlines(
-            EventEnvelope::started("guide", &operation_id, started_at, started_payload),
-            settle.as_ref(),
-        ));
-
-        match client
-            .start_guiding(rp_guider::StartGuidingRequest {
-                recalibrate,
-                settle,
-            })
+        match self
+            .do_start_guiding(client.as_ref(), recalibrate, settle)
             .await
         {
-            Ok(outcome) => {
-                self.event_bus.emit_operation(EventEnvelope::settled(
-                    "guide",
-                    &operation_id,
-                    started_at,
-                    settled_payload(&outcome),
-                ));
-                self.warn_if_guide_rotator_unmodeled(client.as_ref()).await;
-                Ok(tool_success!({
-                    "state": outcome.state,
-                    "rms_ra_px": outcome.rms_ra_px,
-                    "rms_dec_px": outcome.rms_dec_px,
-                    "total_rms_px": outcome.total_rms_px,
-                    "sample_count": outcome.sample_count,
-                }))
-            }
-            Err(e) => {
-                let message = guider_error_text("start_guiding", &e);
-                self.event_bus.emit_operation(EventEnvelope::failed(
-                    "guide",
-                    &operation_id,
-                    started_at,
-                    &message,
-                ));
-                Ok(tool_error!("{}", message))
-            }
+            Ok(outcome) => Ok(tool_success!({
+                "state": outcome.state,
+                "rms_ra_px": outcome.rms_ra_px,
+                "rms_dec_px": outcome.rms_dec_px,
+                "total_rms_px": outcome.total_rms_px,
+                "sample_count": outcome.sample_count,
+            })),
+            Err(message) => Ok(tool_error!("{}", message)),
         }
     }
 
@@ -398,6 +439,49 @@ impl McpHandler {
             )),
         }
     }
+
+    #[tool(
+        description = "Read the guider's guide algorithm settings: Dec guide mode (Off/Auto/North/South), per-axis min move (guide-camera pixels), aggressiveness and algorithm parameters, multi-star and Dec backlash compensation (null where the guider does not expose them)."
+    )]
+    pub(crate) async fn get_guiding_settings(
+        &self,
+        Parameters(_params): Parameters<GetGuidingSettingsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(client) = self.guider.clone() else {
+            return Ok(tool_error!("get_guiding_settings: guider not configured"));
+        };
+        match client.guiding_settings().await {
+            Ok(settings) => Ok(tool_success!(settings)),
+            Err(e) => Ok(tool_error!(
+                "{}",
+                guider_error_text("get_guiding_settings", &e)
+            )),
+        }
+    }
+
+    #[tool(
+        description = "Switch the guider's guide algorithm settings, e.g. a one-sided Dec guide mode and a larger Dec min move for long narrowband subs. Only the fields given change; returns the full settings as read back."
+    )]
+    pub(crate) async fn set_guiding_settings(
+        &self,
+        Parameters(params): Parameters<SetGuidingSettingsParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(client) = self.guider.clone() else {
+            return Ok(tool_error!("set_guiding_settings: guider not configured"));
+        };
+        let update = match settings_update(params) {
+            Ok(update) => update,
+            Err(message) => return Ok(tool_error!("set_guiding_settings: {}", message)),
+        };
+        debug!(?update, "set_guiding_settings: updating guide settings");
+        match client.update_guiding_settings(update).await {
+            Ok(settings) => Ok(tool_success!(settings)),
+            Err(e) => Ok(tool_error!(
+                "{}",
+                guider_error_text("set_guiding_settings", &e)
+            )),
+        }
+    }
 }
 
 impl McpHandler {
@@ -462,11 +546,64 @@ impl McpHandler {
         }
     }
 
+    /// Start guiding and block through the settle under the
+    /// `guide_started` / `guide_settled` / `guide_failed` triple. Shared
+    /// by `start_guiding` and `perform_meridian_flip`'s guiding restart;
+    /// the error is the finished tool-error text.
+    pub(crate) async fn do_start_guiding(
+        &self,
+        client: &dyn rp_guider::GuiderClient,
+        recalibrate: bool,
+        settle: Option<rp_guider::SettleOverride>,
+    ) -> Result<rp_guider::SettledOutcome, String> {
+        let operation_id = uuid::Uuid::new_v4().to_string();
+        let started_at = chrono::Utc::now();
+        let started_payload = serde_json::json!({
+            "recalibrate": recalibrate,
+            "settle_pixels": settle.as_ref().and_then(|s| s.pixels),
+            "settle_time": humantime_or_null(settle.as_ref().and_then(|s| s.time)),
+            "settle_timeout": humantime_or_null(settle.as_ref().and_then(|s| s.timeout)),
+        });
+        self.event_bus.emit_operation(with_settle_deadlines(
+            EventEnvelope::started("guide", &operation_id, started_at, started_payload),
+            settle.as_ref(),
+        ));
+
+        match client
+            .start_guiding(rp_guider::StartGuidingRequest {
+                recalibrate,
+                settle,
+            })
+            .await
+        {
+            Ok(outcome) => {
+                self.event_bus.emit_operation(EventEnvelope::settled(
+                    "guide",
+                    &operation_id,
+                    started_at,
+                    settled_payload(&outcome),
+                ));
+                self.warn_if_guide_rotator_unmodeled(client).await;
+                Ok(outcome)
+            }
+            Err(e) => {
+                let message = guider_error_text("start_guiding", &e);
+                self.event_bus.emit_operation(EventEnvelope::failed(
+                    "guide",
+                    &operation_id,
+                    started_at,
+                    &message,
+                ));
+                Err(message)
+            }
+        }
+    }
+
     /// Merge per-call settle parameters over the rp-config defaults,
     /// field by field. `None` when every field ends up unset — the
     /// wire then omits `settle` entirely and the guider service's own
     /// `settling` config applies.
-    fn merge_settle(
+    pub(crate) fn merge_settle(
         &self,
         pixels: Option<f64>,
         time: Option<Duration>,
@@ -541,15 +678,59 @@ fn with_settle_deadlines(
     envelope: EventEnvelope,
     settle: Option<&rp_guider::SettleOverride>,
 ) -> EventEnvelope {
-    let Some(timeout) = settle.and_then(|s| s.timeout) else {
-        return envelope;
-    };
+    match settle_deadlines_ms(settle) {
+        Some((predicted_ms, max_ms)) => envelope.with_deadlines(predicted_ms, max_ms),
+        None => envelope,
+    }
+}
+
+/// The `(predicted_ms, max_ms)` pair behind [`with_settle_deadlines`];
+/// `None` when the resolved settle pins no timeout. Also feeds the
+/// guiding-restart leg of `perform_meridian_flip`'s deadline.
+pub(crate) fn settle_deadlines_ms(
+    settle: Option<&rp_guider::SettleOverride>,
+) -> Option<(u64, u64)> {
+    let timeout = settle.and_then(|s| s.timeout)?;
     let predicted = settle.and_then(|s| s.time).unwrap_or(timeout).min(timeout);
     let max = timeout.saturating_add(SETTLE_BACKSTOP_GRACE);
-    envelope.with_deadlines(
+    Some((
         u64::try_from(predicted.as_millis()).unwrap_or(u64::MAX),
         u64::try_from(max.as_millis()).unwrap_or(u64::MAX),
-    )
+    ))
+}
+
+/// Build the service's partial settings update from the flat tool
+/// parameters. Backlash compensation is sent whole, so switching it on
+/// needs the pulse; switching it off sends a zero pulse the guider
+/// ignores.
+pub(crate) fn settings_update(
+    params: SetGuidingSettingsParams,
+) -> Result<rp_guider::GuidingSettingsUpdate, String> {
+    let backlash_compensation = match (params.backlash_compensation, params.backlash_pulse) {
+        (None, None) => None,
+        (Some(true), None) => {
+            return Err("backlash_compensation: true needs backlash_pulse".to_string());
+        }
+        (enabled, pulse) => Some(rp_guider::BacklashCompensation {
+            enabled: enabled.unwrap_or(true),
+            pulse: pulse.unwrap_or_default(),
+        }),
+    };
+    Ok(rp_guider::GuidingSettingsUpdate {
+        dec_guide_mode: params.dec_guide_mode.map(Into::into),
+        ra: rp_guider::AxisSettingsUpdate {
+            min_move_px: params.ra_min_move_px,
+            aggressiveness: params.ra_aggressiveness,
+            params: params.ra_params.unwrap_or_default(),
+        },
+        dec: rp_guider::AxisSettingsUpdate {
+            min_move_px: params.dec_min_move_px,
+            aggressiveness: params.dec_aggressiveness,
+            params: params.dec_params.unwrap_or_default(),
+        },
+        multi_star: params.multi_star,
+        backlash_compensation,
+    })
 }
 
 /// Payload shared by `guide_settled` / `dither_settled`: the settled
@@ -566,7 +747,7 @@ fn settled_payload(outcome: &rp_guider::SettledOutcome) -> serde_json::Value {
 /// Map a client error onto the tool-error text, mirroring
 /// `do_plate_solve`'s formatting: unreachable / structured envelope
 /// (code + message, details when present) / internal.
-fn guider_error_text(tool: &str, e: &rp_guider::GuiderError) -> String {
+pub(crate) fn guider_error_text(tool: &str, e: &rp_guider::GuiderError) -> String {
     match e {
         rp_guider::GuiderError::ServiceUnreachable(reason) => {
             format!("{tool}: service unreachable: {reason}")
@@ -216,22 +216,15 @@ impl McpHandler {
             },
         };
 
+        let payload = stats.payload();
         debug!(
             document_id = params.document_id.as_deref().unwrap_or(""),
             image_path = params.image_path.as_deref().unwrap_or(""),
-            median = stats.median_adu,
-            mean = %stats.mean_adu,
+            median = %payload["median_adu"],
+            mean = %payload["mean_adu"],
             "computed image stats"
         );
 
-        let payload = serde_json::json!({
-            "median_adu": stats.median_adu,
-            "mean_adu": stats.mean_adu,
-            "min_adu": stats.min_adu,
-            "max_adu": stats.max_adu,
-            "pixel_count": stats.pixel_count,
-        });
-
         if let Some(doc_id) = params.document_id.as_deref() {
             if let Err(e) = self
                 .image_cache
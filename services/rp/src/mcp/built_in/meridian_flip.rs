@@ -0,0 +1,464 @@
+//! Meridian-flip compound tool: `perform_meridian_flip` (rp.md §
+//! `perform_meridian_flip` Contract).
+//!
+//! The flip is a fixed ladder over existing building blocks: stop
+//! guiding → wait until the pointing is `meridian_flip.hour_angle_offset`
+//! past the meridian → re-slew to the target (forcing `SideOfPier`
+//! when the re-slew alone did not change it) → re-center by plate
+//! solve → rotate the train's rotator 180° → restart and settle
+//! guiding. The mount-motion gate is held exclusively (operation
+//! `meridian_flip`) from the guider stop through the pier-side check,
+//! so no imaging-train exposure can open across the flip; it is
+//! released before re-centering, whose captures and slews take the
+//! gate themselves. A failure at any step ends the ladder with
+//! guiding left stopped — the pointing is no longer trusted.
+
+use std::time::Duration;
+
+use ascom_alpaca::api::telescope::PierSide;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_router, RoleServer};
+use schemars::JsonSchema;
+use serde::Deserialize;
+use tracing::debug;
+
+use super::super::handler::McpHandler;
+use super::super::progress::{ProgressEmitter, ProgressSink, PROGRESS_INTERVAL};
+use super::super::{resolve_device, tool_error, tool_success};
+use crate::equipment::trains::TrainDeviceKind;
+use crate::events::EventEnvelope;
+use crate::imaging;
+
+/// Sidereal seconds per clock second: hour angle advances this much
+/// faster than wall-clock time.
+const SIDEREAL_RATE: f64 = 1.002_737_909_35;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(extend("oneOf" = [{"required": ["camera_id"]}, {"required": ["train_id"]}]))]
+pub struct PerformMeridianFlipParams {
+    /// Camera that captures the re-centering frames; mutually
+    /// exclusive with `train_id`.
+    #[serde(default)]
+    pub camera_id: Option<String>,
+    /// Optical train whose terminal camera re-centers and whose
+    /// rotator (if any) turns 180°; mutually exclusive with
+    /// `camera_id`.
+    #[serde(default)]
+    pub train_id: Option<String>,
+    /// Target right ascension, decimal hours, [0, 24).
+    #[serde(default)]
+    pub ra: Option<f64>,
+    /// Target declination, decimal degrees, [-90, 90].
+    #[serde(default)]
+    pub dec: Option<f64>,
+    /// Per-iteration re-centering exposure (humantime string).
+    #[serde(default, with = "humantime_serde::option")]
+    #[schemars(with = "Option<String>")]
+    pub duration: Option<Duration>,
+    /// Re-centering convergence threshold, in arcseconds.
+    #[serde(default)]
+    pub tolerance_arcsec: Option<f64>,
+    /// Re-centering iteration cap (at most 50).
+    #[serde(default)]
+    pub max_attempts: Option<usize>,
+    /// Force a fresh PHD2 calibration when guiding restarts. Defaults
+    /// to false: PHD2 flips an existing calibration itself when the
+    /// mount reports its pier side.
+    #[serde(default)]
+    pub recalibrate: Option<bool>,
+}
+
+#[tool_router(router = tool_router_meridian_flip, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "Perform a meridian flip on the current target: stop guiding, wait until the mount is meridian_flip.hour_angle_offset past the meridian (failing up front beyond meridian_flip.max_wait), re-slew to (ra, dec) to force the pier-side change, re-center by plate solve, rotate the train's rotator 180° when it has one, and restart and settle guiding if it was running. Singular mount required. See `perform_meridian_flip` Contract in rp.md."
+    )]
+    pub(crate) async fn perform_meridian_flip(
+        &self,
+        Parameters(params): Parameters<PerformMeridianFlipParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let progress_sink = ProgressSink::from_request_context(&ctx);
+        self.perform_meridian_flip_inner(params, progress_sink)
+            .await
+    }
+
+    /// Body of the `perform_meridian_flip` MCP tool, split out so unit
+    /// tests can pass `None` for the progress sink without
+    /// constructing a real rmcp `Peer`.
+    pub(crate) async fn perform_meridian_flip_inner(
+        &self,
+        params: PerformMeridianFlipParams,
+        progress_sink: Option<ProgressSink>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let camera_id = match self.resolve_camera_addressing(
+            "perform_meridian_flip",
+            params.camera_id.as_deref(),
+            params.train_id.as_deref(),
+        ) {
+            Ok(id) => id,
+            Err(e) => return Ok(*e),
+        };
+        let ra = match params.ra {
+            Some(v) => v,
+            None => return Ok(tool_error!("missing required parameter: ra")),
+        };
+        let dec = match params.dec {
+            Some(v) => v,
+            None => return Ok(tool_error!("missing required parameter: dec")),
+        };
+        let duration = match params.duration {
+            Some(d) => d,
+            None => return Ok(tool_error!("missing required parameter: duration")),
+        };
+        let tolerance_arcsec = match params.tolerance_arcsec {
+            Some(v) => v,
+            None => return Ok(tool_error!("missing required parameter: tolerance_arcsec")),
+        };
+        let max_attempts = match params.max_attempts {
+            Some(v) => v,
+            None => return Ok(tool_error!("missing required parameter: max_attempts")),
+        };
+        let cot_params = imaging::tools::center_on_target::CenterOnTargetParams {
+            ra,
+            dec,
+            duration,
+            tolerance_arcsec,
+            max_attempts,
+        };
+        // The re-center leg would reject these only after the flip has
+        // already moved the mount; check them before any motion.
+        if let Err(e) = imaging::tools::center_on_target::validate_params(&cot_params) {
+            return Ok(tool_error!("perform_meridian_flip: {}", e));
+        }
+
+        let (_cam_entry, _cam) = resolve_device!(self, find_camera, &camera_id, "camera");
+        let (mount_entry, mount) = match self.resolve_mount() {
+            Ok(pair) => pair,
+            Err(e) => return Ok(tool_error!("{}", e)),
+        };
+        let flip_config = mount_entry.config.meridian_flip.clone();
+        let settle_after = mount_entry.config.settle_after_slew.unwrap_or_default();
+        let slew_rate = mount_entry.config.slew_rate_arcsec_per_sec.value();
+
+        // The rotator to turn is the re-centering camera's train's sole
+        // rotator; a train with several is ambiguous and refused here.
+        let rotator_id = match self.trains.train_for_camera(&camera_id) {
+            Some(train) => {
+                let rotators: Vec<&str> = train
+                    .devices
+                    .iter()
+                    .filter(|d| d.kind == TrainDeviceKind::Rotator)
+                    .map(|d| d.id.as_str())
+                    .collect();
+                match rotators.as_slice() {
+                    [] => None,
+                    [id] => Some((*id).to_string()),
+                    many => {
+                        return Ok(tool_error!(
+                            "perform_meridian_flip: train '{}' has {} rotators",
+                            train.id,
+                            many.len()
+                        ))
+                    }
+                }
+            }
+            None => None,
+        };
+        let rotator = match &rotator_id {
+            Some(id) => Some(resolve_device!(self, find_rotator, id, "rotator").1),
+            None => None,
+        };
+
+        // Exclusive for the whole mount-side half of the flip. Taken
+        // before the hour-angle read, like `do_slew_blocking`, so the
+        // predicted wait never includes gate wait.
+        let motion_permit = self.motion_gate.exclusive("meridian_flip").await;
+
+        let lst = match mount.sidereal_time().await {
+            Ok(v) => v,
+            Err(e) => return Ok(tool_error!("failed to read mount sidereal_time: {}", e)),
+        };
+        let mount_ra = match mount.right_ascension().await {
+            Ok(v) => v,
+            Err(e) => return Ok(tool_error!("failed to read mount right_ascension: {}", e)),
+        };
+        let hour_angle = crate::planner::decision::signed_hour_angle(lst, mount_ra);
+        let offset_hours = flip_config.hour_angle_offset.as_secs_f64() / 3600.0;
+        let wait = flip_wait(hour_angle, offset_hours);
+        if wait > flip_config.max_wait {
+            return Ok(tool_error!(
+                "perform_meridian_flip: the mount is {:.1} min of hour angle short of the flip \
+                 point (hour angle {:.3} h, offset {:.3} h), beyond meridian_flip.max_wait {}",
+                (offset_hours - hour_angle) * 60.0,
+                hour_angle,
+                offset_hours,
+                humantime::format_duration(flip_config.max_wait)
+            ));
+        }
+        // A mount that cannot report its pier side cannot prove the
+        // flip happened; refuse rather than risk a pier collision.
+        let side_before = match mount.side_of_pier().await {
+            Ok(side @ (PierSide::East | PierSide::West)) => side,
+            Ok(_) => {
+                return Ok(tool_error!(
+                    "perform_meridian_flip: mount reports an unknown side_of_pier; cannot verify a flip"
+                ))
+            }
+            Err(e) => return Ok(tool_error!("failed to read mount side_of_pier: {}", e)),
+        };
+
+        // Guiding restarts only if it was running. A stats read that
+        // fails or reports not-guiding flips bare, as in
+        // `move_rotator`'s ladder decision.
+        let mut guide_client = None;
+        if let Some(client) = self.guider.clone() {
+            match client.guiding_stats().await {
+                Ok(stats) if stats.guiding => guide_client = Some(client),
+                Ok(_) => debug!("guider not guiding; flipping without a guiding restart"),
+                Err(e) => debug!(error = %e, "guider stats unreachable; flipping bare"),
+            }
+        }
+        let recalibrate = params.recalibrate.unwrap_or(false);
+        let settle = self.merge_settle(None, None, None);
+
+        let operation_id = uuid::Uuid::new_v4().to_string();
+        let started_at = chrono::Utc::now();
+        let centering_ms = super::super::internals::centering_deadlines(
+            max_attempts,
+            duration,
+            self.centering.solve_time_estimate,
+            self.centering.slew_overhead_estimate,
+        );
+        let guide_settle_ms = guide_client
+            .as_ref()
+            .and_then(|_| super::guider::settle_deadlines_ms(settle.as_ref()))
+            .unwrap_or((0, 0));
+        let rotator_max = if rotator.is_some() {
+            super::rotator::ROTATOR_MOVE_DEADLINE
+        } else {
+            Duration::ZERO
+        };
+        let (predicted_ms, max_ms) = super::super::internals::meridian_flip_deadlines(
+            wait,
+            slew_rate,
+            settle_after,
+            centering_ms,
+            guide_settle_ms,
+            rotator_max,
+        );
+        self.event_bus.emit_operation(
+            EventEnvelope::started(
+                "meridian_flip",
+                &operation_id,
+                started_at,
+                serde_json::json!({
+                    "camera_id": camera_id,
+                    "ra": ra,
+                    "dec": dec,
+                    "hour_angle_hours": hour_angle,
+                    "wait_secs": wait.as_secs_f64(),
+                    "side_of_pier_before": pier_side_name(side_before),
+                    "rotator_id": rotator_id,
+                    "guiding": guide_client.is_some(),
+                }),
+            )
+            .with_deadlines(predicted_ms, max_ms),
+        );
+
+        let emitter = progress_sink.as_ref().map(ProgressSink::as_emitter);
+        let flip: Result<serde_json::Value, String> = async {
+            if let Some(client) = &guide_client {
+                client
+                    .stop_guiding()
+                    .await
+                    .map_err(|e| super::guider::guider_error_text("perform_meridian_flip", &e))?;
+                self.event_bus.emit(
+                    "guide_stopped",
+                    serde_json::json!({ "reason": "meridian_flip" }),
+                );
+            }
+
+            wait_with_progress(wait, emitter).await;
+
+            self.do_slew_blocking_gated(ra, dec, settle_after, emitter)
+                .await
+                .map_err(|e| format!("perform_meridian_flip: re-slew failed: {e}"))?;
+            let mut side_after = read_pier_side(mount.as_ref()).await?;
+            let mut pier_side_forced = false;
+            if side_after == side_before {
+                let can_set = mount.can_set_pier_side().await.map_err(|e| {
+                    format!("perform_meridian_flip: failed to read can_set_pier_side: {e}")
+                })?;
+                if !can_set {
+                    return Err(format!(
+                        "perform_meridian_flip: the mount stayed on the {} pier side after the \
+                         re-slew and cannot set side_of_pier",
+                        pier_side_name(side_before)
+                    ));
+                }
+                self.force_pier_side_gated(mount.as_ref(), opposite(side_before), emitter)
+                    .await
+                    .map_err(|e| format!("perform_meridian_flip: {e}"))?;
+                side_after = read_pier_side(mount.as_ref()).await?;
+                if side_after == side_before {
+                    return Err(format!(
+                        "perform_meridian_flip: the mount is still on the {} pier side after \
+                         setting side_of_pier",
+                        pier_side_name(side_before)
+                    ));
+                }
+                pier_side_forced = true;
+            }
+            drop(motion_permit);
+
+            let centered = self
+                .do_center_on_target(&camera_id, cot_params, progress_sink.clone())
+                .await
+                .map_err(|e| format!("perform_meridian_flip: re-centering failed: {e}"))?;
+
+            let mut rotator_angle = serde_json::Value::Null;
+            if let Some(rot) = &rotator {
+                let current = rot.position().await.map_err(|e| {
+                    format!("perform_meridian_flip: failed to read rotator position: {e}")
+                })?;
+                let target = (current + 180.0).rem_euclid(360.0);
+                let (position, _mechanical) =
+                    super::rotator::move_rotator_blocking(rot.as_ref(), target)
+                        .await
+                        .map_err(|e| format!("perform_meridian_flip: {e}"))?;
+                rotator_angle = serde_json::json!(position);
+            }
+
+            let mut guiding = serde_json::Value::Null;
+            if let Some(client) = &guide_client {
+                let outcome = self
+                    .do_start_guiding(client.as_ref(), recalibrate, settle)
+                    .await
+                    .map_err(|e| format!("perform_meridian_flip: {e}"))?;
+                guiding = serde_json::json!({
+                    "state": outcome.state,
+                    "total_rms_px": outcome.total_rms_px,
+                });
+            }
+
+            Ok(serde_json::json!({
+                "side_of_pier_before": pier_side_name(side_before),
+                "side_of_pier_after": pier_side_name(side_after),
+                "pier_side_forced": pier_side_forced,
+                "waited_secs": wait.as_secs_f64(),
+                "final_error_arcsec": centered.final_error_arcsec,
+                "centering_attempts": centered.attempts,
+                "rotator_id": rotator_id,
+                "rotator_angle": rotator_angle,
+                "guiding": guiding,
+            }))
+        }
+        .await;
+
+        match flip {
+            Ok(result) => {
+                self.event_bus.emit_operation(EventEnvelope::complete(
+                    "meridian_flip",
+                    &operation_id,
+                    started_at,
+                    result.clone(),
+                ));
+                Ok(tool_success!(result))
+            }
+            Err(msg) => {
+                self.event_bus.emit_operation(EventEnvelope::failed(
+                    "meridian_flip",
+                    &operation_id,
+                    started_at,
+                    &msg,
+                ));
+                Ok(tool_error!("{}", msg))
+            }
+        }
+    }
+}
+
+/// Clock time until `hour_angle` (hours, signed) reaches `offset_hours`
+/// past the meridian; zero when it already has.
+fn flip_wait(hour_angle: f64, offset_hours: f64) -> Duration {
+    let sidereal_secs = (offset_hours - hour_angle) * 3600.0;
+    if sidereal_secs <= 0.0 {
+        return Duration::ZERO;
+    }
+    Duration::try_from_secs_f64(sidereal_secs / SIDEREAL_RATE).unwrap_or(Duration::MAX)
+}
+
+/// Sleep through the hour-angle wait, ticking progress every
+/// [`PROGRESS_INTERVAL`] so a long wait cannot trip rmcp's session
+/// keep-alive.
+async fn wait_with_progress(wait: Duration, progress: Option<&dyn ProgressEmitter>) {
+    let Some(sink) = progress else {
+        tokio::time::sleep(wait).await;
+        return;
+    };
+    let total = wait.as_secs_f64();
+    let mut remaining = wait;
+    while !remaining.is_zero() {
+        sink.emit(
+            total - remaining.as_secs_f64(),
+            Some(total),
+            Some("waiting for the meridian flip point".to_string()),
+        )
+        .await;
+        let step = remaining.min(PROGRESS_INTERVAL);
+        tokio::time::sleep(step).await;
+        remaining = remaining.saturating_sub(step);
+    }
+}
+
+async fn read_pier_side(
+    mount: &(dyn ascom_alpaca::api::Telescope + Send + Sync),
+) -> Result<PierSide, String> {
+    mount
+        .side_of_pier()
+        .await
+        .map_err(|e| format!("perform_meridian_flip: failed to read mount side_of_pier: {e}"))
+}
+
+const fn opposite(side: PierSide) -> PierSide {
+    match side {
+        PierSide::East => PierSide::West,
+        PierSide::West => PierSide::East,
+        other => other,
+    }
+}
+
+const fn pier_side_name(side: PierSide) -> &'static str {
+    match side {
+        PierSide::East => "east",
+        PierSide::West => "west",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use std::time::Duration;
+
+    use super::flip_wait;
+
+    #[test]
+    fn flip_wait_is_zero_once_past_the_offset() {
+        assert_eq!(flip_wait(0.2, 5.0 / 60.0), Duration::ZERO);
+        assert_eq!(flip_wait(5.0 / 60.0, 5.0 / 60.0), Duration::ZERO);
+    }
+
+    #[test]
+    fn flip_wait_converts_sidereal_hour_angle_to_clock_time() {
+        // Ten sidereal minutes short of the flip point is slightly
+        // less than ten clock minutes.
+        let wait = flip_wait(-5.0 / 60.0, 5.0 / 60.0);
+        let secs = wait.as_secs_f64();
+        assert!((secs - 600.0 / super::SIDEREAL_RATE).abs() < 1e-6, "{secs}");
+        assert!(secs < 600.0);
+    }
+}
@@ -9,14 +9,21 @@ pub mod auto_focus;
 pub mod camera;
 pub mod center_on_target;
 pub mod cover_calibrator;
+pub mod dome;
+pub mod equipment;
 pub mod filter_wheel;
+pub mod focus_model;
 pub mod focuser;
 pub mod guider;
 pub mod imaging;
+pub mod meridian_flip;
 pub mod mount;
+pub mod observing_conditions;
 pub mod plan_schema;
 pub mod plan_validation;
 pub mod planner;
 pub mod plate_solve;
 pub mod rotator;
+pub mod session;
+pub mod switch;
 pub mod targets;
@@ -0,0 +1,96 @@
+//! Observing-conditions tool category: `get_observing_conditions`
+//! (rp.md § Switch and Observing Conditions Tool Details).
+//!
+//! Every ASCOM ObservingConditions sensor is optional; a weather station
+//! implements the ones it has and answers NOT_IMPLEMENTED for the rest.
+//! The tool reads all of them and reports only the supported ones, each
+//! with its age, so a caller can tell a stale reading from a fresh one.
+
+use std::sync::Arc;
+
+use ascom_alpaca::api::ObservingConditions;
+use ascom_alpaca::{ASCOMErrorCode, ASCOMResult};
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::{tool, tool_router};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::super::handler::McpHandler;
+use super::super::{resolve_device, tool_success};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ObservingConditionsIdParams {
+    pub observing_conditions_id: String,
+}
+
+#[tool_router(router = tool_router_observing_conditions, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "Read every sensor an observing-conditions device supports (cloud_cover, dew_point, humidity, pressure, rain_rate, sky_brightness, sky_quality, sky_temperature, star_fwhm, temperature, wind_direction, wind_gust, wind_speed), each as {value, time_since_last_update_s}. Sensors the device does not implement are omitted; a supported sensor with no reading yet reports value null and the error. time_since_last_update_s is null when the device does not track it. Read-only"
+    )]
+    pub(crate) async fn get_observing_conditions(
+        &self,
+        Parameters(params): Parameters<ObservingConditionsIdParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, oc) = resolve_device!(
+            self,
+            find_observing_conditions,
+            &params.observing_conditions_id,
+            "observing conditions"
+        );
+        let mut sensors = serde_json::Map::new();
+        for (key, ascom_name, reading) in read_sensors(&oc).await {
+            let entry = match reading {
+                Ok(value) => serde_json::json!({
+                    "value": value,
+                    "time_since_last_update_s": sensor_age(&oc, ascom_name).await,
+                }),
+                Err(e) if e.code == ASCOMErrorCode::NOT_IMPLEMENTED => continue,
+                Err(e) => serde_json::json!({
+                    "value": null,
+                    "time_since_last_update_s": sensor_age(&oc, ascom_name).await,
+                    "error": e.to_string(),
+                }),
+            };
+            sensors.insert(key.to_string(), entry);
+        }
+        Ok(tool_success!({
+            "observing_conditions_id": params.observing_conditions_id,
+            "sensors": sensors,
+        }))
+    }
+}
+
+/// Every ObservingConditions sensor as (JSON key, ASCOM sensor name as
+/// `TimeSinceLastUpdate` takes it, reading).
+async fn read_sensors(
+    oc: &Arc<dyn ObservingConditions>,
+) -> Vec<(&'static str, &'static str, ASCOMResult<f64>)> {
+    vec![
+        ("cloud_cover", "CloudCover", oc.cloud_cover().await),
+        ("dew_point", "DewPoint", oc.dew_point().await),
+        ("humidity", "Humidity", oc.humidity().await),
+        ("pressure", "Pressure", oc.pressure().await),
+        ("rain_rate", "RainRate", oc.rain_rate().await),
+        ("sky_brightness", "SkyBrightness", oc.sky_brightness().await),
+        ("sky_quality", "SkyQuality", oc.sky_quality().await),
+        (
+            "sky_temperature",
+            "SkyTemperature",
+            oc.sky_temperature().await,
+        ),
+        ("star_fwhm", "StarFWHM", oc.star_fwhm().await),
+        ("temperature", "Temperature", oc.temperature().await),
+        ("wind_direction", "WindDirection", oc.wind_direction().await),
+        ("wind_gust", "WindGust", oc.wind_gust().await),
+        ("wind_speed", "WindSpeed", oc.wind_speed().await),
+    ]
+}
+
+/// Seconds since the device last updated `sensor`. `None` when the
+/// device does not track per-sensor age (or cannot report it now) — the
+/// reading itself is still worth returning.
+async fn sensor_age(oc: &Arc<dyn ObservingConditions>, sensor: &str) -> Option<f64> {
+    oc.time_since_last_update(sensor.to_string()).await.ok()
+}
@@ -95,7 +95,7 @@ impl McpHandler {
                 Ok(target) => plan_validation::validate_add_target(
                     &target,
                     &self.target_store_defaults.default_goals,
-                    &self.equipment,
+                    &self.equipment.snapshot(),
                 ),
                 Err(e) => vec![deserialize_error("target", &e)],
             },
@@ -105,7 +105,7 @@ impl McpHandler {
                 ) {
                     Ok(goals) => plan_validation::validate_goals(
                         &goals,
-                        &plan_validation::filter_roster(&self.equipment),
+                        &plan_validation::filter_roster(&self.equipment.snapshot()),
                         "goals",
                     )
                     .err()
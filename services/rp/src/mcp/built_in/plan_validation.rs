@@ -25,11 +25,11 @@ use rp_vocabulary::IcrsCoord;
 use rusty_photon_config::actions::FieldError;
 
 use crate::equipment::EquipmentRegistry;
-use crate::planner::goal_wire::GoalWire;
+use crate::planner::goal_wire::{GoalWire, MoonAvoidanceWire};
 
 use crate::config::target_store::GradingWire;
 
-use super::targets::{AddTargetParams, SchedulingWire};
+use super::targets::{AddTargetParams, MosaicWire, SchedulingWire};
 
 /// A `{path, msg}` pair, for the many small constructions below.
 fn err(path: impl Into<String>, msg: impl Into<String>) -> FieldError {
@@ -103,12 +103,28 @@ pub fn validate_goals(
             ));
         }
 
-        if let (Some(binning), Some(exposure_duration)) = (binning, exposure) {
+        let moon_avoidance = match goal
+            .moon_avoidance
+            .as_ref()
+            .map(MoonAvoidanceWire::validate)
+            .transpose()
+        {
+            Ok(m) => Some(m),
+            Err(e) => {
+                errors.push(err(at("moon_avoidance"), e));
+                None
+            }
+        };
+
+        if let (Some(binning), Some(exposure_duration), Some(moon_avoidance)) =
+            (binning, exposure, moon_avoidance)
+        {
             parsed.push(AcquisitionGoal {
                 filter: goal.filter.clone(),
                 binning,
                 exposure_duration,
                 desired_count: goal.desired_count,
+                moon_avoidance,
             });
         }
     }
@@ -199,6 +215,19 @@ pub fn validate_position_angle(value: Option<f64>, path: &str) -> Vec<FieldError
     }
 }
 
+/// Validates a mosaic grid by delegating to the store's own write-time
+/// invariant ([`rp_targets::validate_mosaic`]), attributed to the whole
+/// `mosaic` object — the rule spans fields (a 1×1 grid is two legal
+/// values that are illegal together), so no single field owns it.
+#[must_use]
+pub fn validate_mosaic(wire: &MosaicWire, path: &str) -> Vec<FieldError> {
+    match rp_targets::validate_mosaic(&(*wire).into()) {
+        Ok(()) => Vec::new(),
+        Err(rp_targets::TargetStoreError::InvalidMosaic { reason }) => vec![err(path, reason)],
+        Err(e) => vec![err(path, e.to_string())],
+    }
+}
+
 /// Validates a coordinate pair through the one validator
 /// ([`IcrsCoord::try_new`]), attributing the failure to whichever field
 /// the typed error names.
@@ -274,6 +303,7 @@ pub fn validate_add_target(
                 "position_angle_degrees",
             ),
             (params.grading.is_some(), "grading"),
+            (params.mosaic.is_some(), "mosaic"),
         ] {
             if present {
                 errors.push(err(field, "not accepted with `source` (the import form)"));
@@ -325,6 +355,9 @@ pub fn validate_add_target(
     if let Some(grading) = &params.grading {
         errors.extend(validate_grading(grading, "grading"));
     }
+    if let Some(mosaic) = &params.mosaic {
+        errors.extend(validate_mosaic(mosaic, "mosaic"));
+    }
 
     let roster = filter_roster(equipment);
     match &params.goals {
@@ -368,6 +401,7 @@ mod tests {
             binning: binning.to_string(),
             exposure_duration: exposure.to_string(),
             desired_count: count,
+            moon_avoidance: None,
         }
     }
 
@@ -437,6 +471,17 @@ mod tests {
         assert_eq!(paths(&errors), vec!["goals[0].exposure_duration"]);
     }
 
+    #[test]
+    fn an_out_of_range_moon_avoidance_is_reported_against_the_goal() {
+        let mut g = goal("Red", "1x1", "2m", 1);
+        g.moon_avoidance = Some(MoonAvoidanceWire {
+            distance_degrees: 0.0,
+            width_days: 7.0,
+        });
+        let errors = validate_goals(&[g], &[], "goals").unwrap_err();
+        assert_eq!(paths(&errors), vec!["goals[0].moon_avoidance"]);
+    }
+
     #[test]
     fn coordinate_failures_are_attributed_to_the_field_the_typed_error_names() {
         assert_eq!(paths(&validate_coord(25.0, 0.0)), vec!["ra_hours"]);
@@ -569,6 +614,10 @@ mod tests {
             ("notes", serde_json::json!("hi")),
             ("position_angle_degrees", serde_json::json!(10.0)),
             ("grading", serde_json::json!({"max_hfr_pixels": 3.0})),
+            (
+                "mosaic",
+                serde_json::json!({"columns": 2, "rows": 3, "overlap_percent": 10.0}),
+            ),
         ] {
             let payload = serde_json::json!({
                 "ra_hours": 5.0, "dec_degrees": 10.0, "source": source, field: value
@@ -615,4 +664,24 @@ mod tests {
         let errors = validate_position_angle(Some(400.0), "position_angle_degrees");
         assert_eq!(paths(&errors), vec!["position_angle_degrees"]);
     }
+
+    // The grid rules are the store's; a payload the store would reject
+    // is reported at `mosaic` before the write is attempted.
+    #[test]
+    fn a_mosaic_the_store_would_reject_is_reported_at_its_path() {
+        let wire = |columns, rows, overlap_percent| MosaicWire {
+            columns,
+            rows,
+            overlap_percent,
+        };
+        assert!(validate_mosaic(&wire(2, 3, 10.0), "mosaic").is_empty());
+        for bad in [wire(1, 1, 10.0), wire(0, 3, 10.0), wire(2, 3, 50.0)] {
+            assert_eq!(paths(&validate_mosaic(&bad, "mosaic")), vec!["mosaic"]);
+        }
+        let errors = add_target_paths(serde_json::json!({
+            "display_name": "Veil", "ra_hours": 20.8, "dec_degrees": 31.0,
+            "mosaic": {"columns": 11, "rows": 1, "overlap_percent": 10.0}
+        }));
+        assert_eq!(errors, vec!["mosaic".to_string()]);
+    }
 }
@@ -102,6 +102,17 @@ pub struct GetNextTargetParams {
     pub train_id: Option<String>,
 }
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ExplainPlannerDecisionParams {
+    #[serde(default)]
+    pub time: Option<String>,
+    /// As on `get_next_target`: the imaging train whose field of view
+    /// lays out mosaic panels and whose default position angle the
+    /// recommendation inherits.
+    #[serde(default)]
+    pub train_id: Option<String>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct RecordExposureParams {
     /// Slug of an active target-store row — the `target.name` a
@@ -120,6 +131,21 @@ pub struct GetSessionProgressParams {}
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetSiteParams {}
 
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct PreviewNightScheduleParams {
+    /// UTC `YYYY-MM-DD` keying the night (as `get_twilight` does); the
+    /// whole night is previewed from dusk. Omitted, the night in
+    /// progress (or next up) at `time` is previewed from `time`.
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub time: Option<String>,
+    /// Seconds added to every simulated frame for download, dither and
+    /// settle. Defaults to 0 (exposure time only).
+    #[serde(default)]
+    pub frame_overhead_secs: Option<f64>,
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetMeridianStatusParams {
     #[serde(default)]
@@ -153,8 +179,71 @@ impl McpHandler {
         }
     }
 
+    /// The config-level scheduling fallbacks every target's `None`
+    /// fields resolve against: `target_store.default_scheduling`, its
+    /// altitude floor falling back to the planner-wide
+    /// `planner.min_altitude_degrees`.
+    fn scheduling_defaults(&self) -> crate::planner::decision::SchedulingDefaults {
+        crate::planner::decision::SchedulingDefaults::from_config(
+            &self.target_store_defaults.default_scheduling,
+            self.default_min_altitude_degrees,
+        )
+    }
+
+    /// Every active store row with each mosaic expanded into its panel
+    /// rows (rp.md § Target Store → Mosaics) — the rows progress is
+    /// tracked against. Identity only: panel rows keep the parent's
+    /// coordinate, which no progress read looks at.
+    async fn active_panel_rows(&self) -> Vec<rp_targets::Target> {
+        self.active_targets()
+            .await
+            .iter()
+            .flat_map(crate::planner::mosaic::panel_rows)
+            .collect()
+    }
+
+    /// The field of view mosaic panels are laid out in: `train_id`'s
+    /// train, else the first imaging train. `None` when there is no such
+    /// train, it has no focal length, or its camera's sensor geometry
+    /// was not read at connect.
+    fn mosaic_field_of_view(
+        &self,
+        train_id: Option<&str>,
+    ) -> Option<crate::equipment::trains::FieldOfView> {
+        let train = match train_id {
+            Some(id) => self.trains.train(id)?,
+            None => self
+                .trains
+                .trains()
+                .iter()
+                .find(|t| t.purpose == crate::config::TrainPurpose::Imaging)?,
+        };
+        let camera = self.equipment.find_camera(train.camera_id()?)?;
+        train.field_of_view(&camera)
+    }
+
+    /// Layer two of the effective position angle (rp.md § Target Store
+    /// → Position angle): the named imaging train's default. An unknown
+    /// id is a caller bug — fail loudly rather than silently
+    /// recommending north-up framing all night.
+    fn train_default_position_angle(&self, train_id: Option<&str>) -> Result<Option<f64>, String> {
+        let Some(id) = train_id else {
+            return Ok(None);
+        };
+        match self.trains.train(id) {
+            Some(train) => Ok(train.default_position_angle_degrees),
+            None => Err(format!(
+                "unknown train_id `{id}`: not an equipment.optical_trains[] id"
+            )),
+        }
+    }
+
     /// The candidate set plus the progress snapshot to rank it against:
-    /// every active store row projected onto the decision type, and each
+    /// every active store row projected onto the decision type — a
+    /// mosaic as one sibling candidate per panel, placed in
+    /// `train_id`'s field of view at the target's effective position
+    /// angle (its own, else `train_default_position_angle_deg`, else
+    /// 0.0 — the same layering the recommendation reports) — and each
     /// one's per-goal counts derived from the frames on disk (rp.md §
     /// Progress derivation).
     ///
@@ -162,11 +251,33 @@ impl McpHandler {
     /// keeps `decision::next_target` a pure function of its arguments.
     async fn planner_snapshot(
         &self,
+        train_id: Option<&str>,
+        train_default_position_angle_deg: Option<f64>,
     ) -> (
         Vec<crate::planner::decision::PlannerTarget>,
         crate::planner::progress::PlanProgress,
     ) {
-        let targets = self.active_targets().await;
+        let fov = self.mosaic_field_of_view(train_id);
+        let targets: Vec<rp_targets::Target> = self
+            .active_targets()
+            .await
+            .iter()
+            .filter_map(|target| {
+                let position_angle_deg = target
+                    .position_angle_degrees
+                    .or(train_default_position_angle_deg)
+                    .unwrap_or(0.0);
+                let rows = crate::planner::mosaic::placed_rows(target, fov, position_angle_deg);
+                if rows.is_none() {
+                    tracing::debug!(
+                        slug = %target.slug,
+                        "mosaic target skipped: no imaging-train field of view to place its panels in"
+                    );
+                }
+                rows
+            })
+            .flatten()
+            .collect();
         let last_filter_key = self
             .progress
             .lock()
@@ -292,8 +403,9 @@ impl McpHandler {
     }
 
     #[tool(
-        description = "Rise / set times above min_alt_degrees on a given UTC date. \
-                       null bounds for circumpolar always-up or always-down. \
+        description = "Rise / set times above min_alt_degrees on a given UTC date, \
+                       raised per azimuth by the site's horizon profile when one is \
+                       configured. null bounds for circumpolar always-up or always-down. \
                        Requires `site`."
     )]
     pub(crate) async fn compute_rise_set(
@@ -328,6 +440,7 @@ impl McpHandler {
             target,
             date,
             params.min_alt_degrees,
+            &self.horizon,
         );
         Ok(CallToolResult::success(vec![ContentBlock::text(
             v.to_string(),
@@ -531,7 +644,12 @@ impl McpHandler {
 
     #[tool(description = "Sky position + progress for a target. Accepts either \
                        target_name (resolved via the embedded catalog) or a \
-                       raw ra/dec pair. progress is the per-goal list \
+                       raw ra/dec pair. Also reports the Moon's separation, \
+                       illumination and altitude, and blocked_by: the first \
+                       scheduling constraint (min_altitude / meridian_window / \
+                       moon_illumination / moon_separation) get_next_target \
+                       would eliminate the target on, null when eligible. \
+                       progress is the per-goal list \
                        {filter, binning, exposure_duration, desired_count, \
                        good, total} derived from the frames on disk when \
                        target_name (as given or catalog-resolved) slugifies \
@@ -592,7 +710,8 @@ impl McpHandler {
         // catalog-resolved form (`name`) and match those against the
         // active store rows. The ra/dec form has no name to match and
         // reports progress: null.
-        let progress = match params.target_name.as_deref() {
+        let defaults = self.scheduling_defaults();
+        let (progress, constraints) = match params.target_name.as_deref() {
             Some(raw) => {
                 // Both spellings, one derivation: the caller's
                 // `target_name` and its catalog-resolved form go through
@@ -610,22 +729,29 @@ impl McpHandler {
                 {
                     Some(matched) => {
                         let counts = self.derive_progress(matched).await;
-                        serde_json::to_value(crate::mcp::built_in::targets::progress_rows(
-                            matched, &counts,
-                        ))
-                        .unwrap_or(serde_json::Value::Null)
+                        (
+                            serde_json::to_value(crate::mcp::built_in::targets::progress_rows(
+                                matched, &counts,
+                            ))
+                            .unwrap_or(serde_json::Value::Null),
+                            // A store row is judged against its own
+                            // overrides, exactly as `get_next_target`
+                            // would judge it.
+                            defaults.overlay(&matched.scheduling.unwrap_or_default()),
+                        )
                     }
-                    None => serde_json::Value::Null,
+                    None => (serde_json::Value::Null, defaults),
                 }
             }
-            None => serde_json::Value::Null,
+            None => (serde_json::Value::Null, defaults),
         };
         match crate::planner::convenience::target_status_view(
             site,
+            &self.horizon,
             target,
             &name,
             time,
-            self.default_min_altitude_degrees,
+            &constraints,
             progress,
         ) {
             Ok(v) => Ok(CallToolResult::success(vec![ContentBlock::text(
@@ -637,13 +763,15 @@ impl McpHandler {
 
     #[tool(description = "Recommend the next target from the active rows of the \
                        target store (rp.md § Target Store), based on altitude / \
+                       meridian window / Moon illumination and separation / \
                        approaching transit / integration progress / \
-                       sun-elevation gating. A store-backed target's altitude \
-                       floor is target.scheduling.min_altitude_degrees, \
-                       falling back to target_store.default_scheduling.\
-                       min_altitude_degrees from config (Decision 9 — \
-                       altitude-gating parity, docs/plans/\
-                       planetarium-target-import.md). filter and \
+                       sun-elevation gating. Each target.scheduling field \
+                       falls back to target_store.default_scheduling from \
+                       config (min_altitude_degrees further to \
+                       planner.min_altitude_degrees); the Moon constraints \
+                       only apply while the Moon is above the horizon. \
+                       eliminated lists each {name, constraint} a constraint \
+                       removed. filter and \
                        duration_secs are the recommended target's first \
                        incomplete goal per the record_exposure \
                        counters (null when it has no goals) — a store-backed \
@@ -651,8 +779,11 @@ impl McpHandler {
                        none of its entries can recommend forever. Returns \
                        target=null and a structured reason \
                        (no_targets_configured / all_below_min_altitude / \
-                       wait_for_twilight / end_of_session) when no candidate is \
-                       viable: wait_for_twilight = the Sun is brighter than \
+                       outside_meridian_window / moon_too_bright / \
+                       moon_too_close / wait_for_twilight / end_of_session) \
+                       when no candidate is viable: under a dark sky a Moon or \
+                       meridian block is reported ahead of the altitude floor; \
+                       wait_for_twilight = the Sun is brighter than \
                        astronomical dusk and not rising (evening — wait and \
                        re-ask); end_of_session = brighter and rising (dawn) or \
                        every target's integration goal is met — the session is \
@@ -661,7 +792,14 @@ impl McpHandler {
                        the train_id train's default_position_angle_degrees, \
                        else 0.0 north-up; null when target is null) — pass \
                        train_id (the imaging train) so the per-train layer \
-                       applies. Requires `site`.")]
+                       applies. A mosaic target competes as one sibling \
+                       candidate per panel: target.name is the panel slug \
+                       (e.g. m31-r1c2, the name to capture and \
+                       record_exposure under) and target.coord the panel \
+                       center, laid out in the train_id train's field of \
+                       view (else the first imaging train's); a mosaic is \
+                       skipped while that field of view is unknown (camera \
+                       not connected). Requires `site`.")]
     pub(crate) async fn get_next_target(
         &self,
         Parameters(params): Parameters<GetNextTargetParams>,
@@ -679,40 +817,25 @@ impl McpHandler {
             Ok(t) => t,
             Err(e) => return Ok(tool_error!("{}", e)),
         };
-        // The named imaging train supplies layer two of the effective
-        // position angle (rp.md § Target Store → Position angle). An
-        // unknown id is a caller bug — fail loudly rather than
-        // silently recommending north-up framing all night.
-        let train_default_position_angle_deg = match params.train_id.as_deref() {
-            None => None,
-            Some(id) => match self.trains.train(id) {
-                Some(train) => train.default_position_angle_degrees,
-                None => {
-                    return Ok(tool_error!(
-                        "unknown train_id `{}`: not an equipment.optical_trains[] id",
-                        id
-                    ))
-                }
-            },
-        };
+        let train_default_position_angle_deg =
+            match self.train_default_position_angle(params.train_id.as_deref()) {
+                Ok(angle) => angle,
+                Err(e) => return Ok(tool_error!("{}", e)),
+            };
         let eph = rp_ephemeris::ErfarsEphemeris::new();
         // Candidates are every active store row (Decision 9), projected
         // onto the decision candidate type, paired with the progress
         // derived from their frames on disk.
-        let (candidates, progress) = self.planner_snapshot().await;
-        // A store-backed target's own default floor, falling back to
-        // the planner-wide default (`planner.min_altitude_degrees`).
-        let default_min_altitude_degrees = self
-            .target_store_defaults
-            .default_scheduling
-            .min_altitude_degrees
-            .unwrap_or(self.default_min_altitude_degrees);
+        let (candidates, progress) = self
+            .planner_snapshot(params.train_id.as_deref(), train_default_position_angle_deg)
+            .await;
         let rec = crate::planner::decision::next_target(
             &eph,
             site,
+            &self.horizon,
             time,
             &candidates,
-            default_min_altitude_degrees,
+            &self.scheduling_defaults(),
             train_default_position_angle_deg,
             &progress,
         );
@@ -725,6 +848,145 @@ impl McpHandler {
         )]))
     }
 
+    #[tool(description = "Explain get_next_target's decision: for every active \
+                       candidate (mosaic panels individually), each step the \
+                       planner took with the numbers behind it, stopping at \
+                       the one that removed it. Returns {time, recommendation, \
+                       sky, last_filter, targets}: recommendation is exactly \
+                       what get_next_target returns for the same time and \
+                       train_id; sky carries lst_hours, sun/moon altitude and \
+                       moon illumination; targets[] is {name, outcome, \
+                       constraint, steps} in target-store order, outcome one \
+                       of selected / lost_tie_break / outside_transit_band / \
+                       eliminated / exhausted / transform_failed. steps[] are \
+                       tagged by step: progress (completion_fraction, \
+                       exhausted), min_altitude (altitude_degrees, \
+                       azimuth_degrees, floor_degrees), meridian_window \
+                       (abs_hour_angle_hours, window_hours), \
+                       moon_illumination, moon_separation, \
+                       goal_moon_avoidance (the exposure it would shoot), \
+                       transit_band (abs_hour_angle_hours vs \
+                       best_abs_hour_angle_hours + band_hours), and \
+                       tie_break (completion_fraction, filter_matches_last, \
+                       abs_hour_angle_hours, rank — 1 is the pick); \
+                       elimination steps carry passed. Constraint steps \
+                       appear only when the constraint applies (Moon steps \
+                       only while the Moon is up). Requires `site`.")]
+    pub(crate) async fn explain_planner_decision(
+        &self,
+        Parameters(params): Parameters<ExplainPlannerDecisionParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let site = match self.site.as_ref() {
+            Some(s) => s,
+            None => {
+                return Ok(tool_error!(
+                    "{}",
+                    crate::planner::primitives::site_required_error()
+                ))
+            }
+        };
+        let time = match crate::planner::primitives::parse_time_or_now(params.time.as_deref()) {
+            Ok(t) => t,
+            Err(e) => return Ok(tool_error!("{}", e)),
+        };
+        let train_default_position_angle_deg =
+            match self.train_default_position_angle(params.train_id.as_deref()) {
+                Ok(angle) => angle,
+                Err(e) => return Ok(tool_error!("{}", e)),
+            };
+        let eph = rp_ephemeris::ErfarsEphemeris::new();
+        // The same snapshot get_next_target ranks, so the explanation's
+        // recommendation is the one it would give.
+        let (candidates, progress) = self
+            .planner_snapshot(params.train_id.as_deref(), train_default_position_angle_deg)
+            .await;
+        let explanation = crate::planner::explain::explain_decision(
+            &eph,
+            site,
+            &self.horizon,
+            time,
+            &candidates,
+            &self.scheduling_defaults(),
+            train_default_position_angle_deg,
+            &progress,
+        );
+        Ok(CallToolResult::success(vec![ContentBlock::text(
+            serde_json::to_value(&explanation)
+                .unwrap_or(serde_json::Value::Null)
+                .to_string(),
+        )]))
+    }
+
+    #[tool(
+        description = "Preview tonight's plan: step get_next_target's decision \
+                       forward in simulated time from astronomical dusk to dawn, \
+                       shooting each recommended exposure for its duration_secs \
+                       (plus frame_overhead_secs) against a simulated copy of the \
+                       on-disk progress. Returns {dusk_utc, dawn_utc, start_utc, \
+                       blocks, goals}: blocks is the timeline, [{start_utc, \
+                       end_utc, target, filter, duration_secs, frames, reason}] \
+                       with consecutive frames of one goal merged into one block \
+                       and idle gaps carrying get_next_target's reason \
+                       (target=null); goals lists every plan entry's {target, \
+                       filter, duration_secs, desired_count, good, \
+                       expected_frames}. date (UTC YYYY-MM-DD) previews that \
+                       whole night; omitted, the night in progress or next up at \
+                       time (default now) is previewed from time. The preview \
+                       stops early once every goal is met. Requires `site`."
+    )]
+    pub(crate) async fn preview_night_schedule(
+        &self,
+        Parameters(params): Parameters<PreviewNightScheduleParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let site = match self.site.as_ref() {
+            Some(s) => s,
+            None => {
+                return Ok(tool_error!(
+                    "{}",
+                    crate::planner::primitives::site_required_error()
+                ))
+            }
+        };
+        let time = match crate::planner::primitives::parse_time_or_now(params.time.as_deref()) {
+            Ok(t) => t,
+            Err(e) => return Ok(tool_error!("{}", e)),
+        };
+        let frame_overhead_secs = params.frame_overhead_secs.unwrap_or(0.0);
+        if !(frame_overhead_secs.is_finite() && frame_overhead_secs >= 0.0) {
+            return Ok(tool_error!(
+                "frame_overhead_secs must be a finite, non-negative number; got {}",
+                frame_overhead_secs
+            ));
+        }
+        let eph = rp_ephemeris::ErfarsEphemeris::new();
+        let (date, not_before) = match params.date.as_deref() {
+            Some(raw) => match crate::planner::primitives::parse_date(raw) {
+                Ok(d) => (d, chrono::DateTime::<chrono::Utc>::MIN_UTC),
+                Err(e) => return Ok(tool_error!("{}", e)),
+            },
+            None => (crate::planner::schedule::night_of(&eph, site, time), time),
+        };
+        let (candidates, progress) = self.planner_snapshot(None, None).await;
+        match crate::planner::schedule::preview_night(
+            &eph,
+            site,
+            &self.horizon,
+            date,
+            not_before,
+            &candidates,
+            &self.scheduling_defaults(),
+            &progress,
+            frame_overhead_secs,
+        ) {
+            Ok(schedule) => Ok(CallToolResult::success(vec![ContentBlock::text(
+                serde_json::to_value(&schedule)
+                    .unwrap_or(serde_json::Value::Null)
+                    .to_string(),
+            )])),
+            Err(e) => Ok(tool_error!("{}", e)),
+        }
+    }
+
     #[tool(
         description = "Read back an active target-store row's progress after a \
                        frame, and record filter as the session's most recent \
@@ -733,7 +995,8 @@ impl McpHandler {
                        the scan finds. Returns {target, filter, progress}, \
                        where progress is the per-goal list {filter, binning, \
                        exposure_duration, desired_count, good, total} derived \
-                       from the frames on disk. Omit filter (or pass null / \
+                       from the frames on disk. target is a slug or, for a \
+                       mosaic, a panel slug. Omit filter (or pass null / \
                        \"\") for an unfiltered frame."
     )]
     pub(crate) async fn record_exposure(
@@ -743,10 +1006,10 @@ impl McpHandler {
         // Recording against an unknown slug means the orchestrator
         // believes it is imaging a target the planner cannot see — a
         // typo'd call should fail loudly rather than silently succeed.
-        let targets = self.active_targets().await;
+        let targets = self.active_panel_rows().await;
         let Some(target) = targets.iter().find(|t| t.slug.as_str() == params.target) else {
             return Ok(tool_error!(
-                "unknown target `{}`: not an active target-store row",
+                "unknown target `{}`: not an active target-store row or mosaic panel",
                 params.target
             ));
         };
@@ -776,13 +1039,14 @@ impl McpHandler {
         description = "Full progress overview derived from the frames on disk: \
                        target slug -> the per-goal list {filter, binning, \
                        exposure_duration, desired_count, good, total}, for \
-                       every active target-store row."
+                       every active target-store row — a mosaic reported \
+                       per panel slug."
     )]
     pub(crate) async fn get_session_progress(
         &self,
         Parameters(_params): Parameters<GetSessionProgressParams>,
     ) -> Result<CallToolResult, rmcp::ErrorData> {
-        let targets = self.active_targets().await;
+        let targets = self.active_panel_rows().await;
         // BTreeMap so the payload's target order is stable across calls.
         let mut progress = std::collections::BTreeMap::new();
         for target in &targets {
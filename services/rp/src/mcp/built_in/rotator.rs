@@ -32,7 +32,7 @@ use crate::events::EventEnvelope;
 /// config to size a predictive deadline from; 120 s mirrors the
 /// focuser fallback ceiling and covers a worst-case half-turn on the
 /// slowest amateur rotators.
-const ROTATOR_MOVE_DEADLINE: Duration = Duration::from_mins(2);
+pub(crate) const ROTATOR_MOVE_DEADLINE: Duration = Duration::from_mins(2);
 
 #[derive(Debug, Deserialize, JsonSchema)]
 #[schemars(extend("oneOf" = [{"required": ["rotator_id"]}, {"required": ["train_id"]}]))]
@@ -174,34 +174,8 @@ impl McpHandler {
             }
         }
 
-        let move_result: Result<(f64, f64), String> = async {
-            debug!(rotator_id, angle, "moving rotator");
-            rot.move_absolute(angle)
-                .await
-                .map_err(|e| format!("failed to move rotator: {e}"))?;
-
-            let deadline = std::time::Instant::now() + ROTATOR_MOVE_DEADLINE;
-            loop {
-                tokio::time::sleep(Duration::from_millis(100)).await;
-                match rot.is_moving().await {
-                    Ok(false) => break,
-                    Ok(true) if std::time::Instant::now() < deadline => continue,
-                    Ok(true) => return Err("timeout waiting for rotator to settle".to_string()),
-                    Err(e) => return Err(format!("error polling rotator is_moving: {e}")),
-                }
-            }
-
-            let position = rot
-                .position()
-                .await
-                .map_err(|e| format!("failed to read rotator position: {e}"))?;
-            let mechanical = rot
-                .mechanical_position()
-                .await
-                .map_err(|e| format!("failed to read rotator mechanical position: {e}"))?;
-            Ok((position, mechanical))
-        }
-        .await;
+        debug!(rotator_id, angle, "moving rotator");
+        let move_result = move_rotator_blocking(rot.as_ref(), angle).await;
 
         match move_result {
             Ok((position, mechanical)) => {
@@ -425,6 +399,41 @@ impl McpHandler {
     }
 }
 
+/// Move to `angle` (sky frame) and poll `IsMoving` until idle under
+/// [`ROTATOR_MOVE_DEADLINE`], returning the read-back sky and
+/// mechanical angles. No events and no guiding ladder — `move_rotator`
+/// wraps it in both, and `perform_meridian_flip` calls it bare while
+/// guiding is already stopped.
+pub(crate) async fn move_rotator_blocking(
+    rot: &dyn ascom_alpaca::api::Rotator,
+    angle: f64,
+) -> Result<(f64, f64), String> {
+    rot.move_absolute(angle)
+        .await
+        .map_err(|e| format!("failed to move rotator: {e}"))?;
+
+    let deadline = std::time::Instant::now() + ROTATOR_MOVE_DEADLINE;
+    loop {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        match rot.is_moving().await {
+            Ok(false) => break,
+            Ok(true) if std::time::Instant::now() < deadline => continue,
+            Ok(true) => return Err("timeout waiting for rotator to settle".to_string()),
+            Err(e) => return Err(format!("error polling rotator is_moving: {e}")),
+        }
+    }
+
+    let position = rot
+        .position()
+        .await
+        .map_err(|e| format!("failed to read rotator position: {e}"))?;
+    let mechanical = rot
+        .mechanical_position()
+        .await
+        .map_err(|e| format!("failed to read rotator mechanical position: {e}"))?;
+    Ok((position, mechanical))
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -0,0 +1,89 @@
+//! Session tool category: `abort_session` (rp.md § Session Abort).
+//!
+//! The REST `POST /api/session/stop` ends a session gracefully; abort
+//! is the other half — discard what is in flight and put the rig away.
+//! It reuses the safety enforcer's teardown steps (`crate::safety`), in
+//! the same order and with the same best-effort semantics, minus the
+//! `/mcp` gate; MCP-session termination spares the session the abort
+//! arrived on, so the caller gets its result.
+
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::service::RequestContext;
+use rmcp::{tool, tool_router, RoleServer};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::super::handler::McpHandler;
+use super::super::{tool_error, tool_success};
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AbortSessionParams {}
+
+#[tool_router(router = tool_router_session, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "Abort the active (or safety-interrupted) session: end it now with session_stopped reason=aborted, close every other MCP session (cancelling the orchestrator's in-flight tool calls), then abort every camera's in-progress exposure (AbortExposure — the frames are discarded, not read out), stop guiding, and command the mount to park; cooled cameras ramp warm as on any session end. Unlike a graceful stop nothing in flight is allowed to finish. Every hardware step is best-effort: the result reports mcp_sessions_closed, aborted_exposures (camera ids that accepted the abort), guiding_stopped, and mount_park_commanded rather than failing the call. The park is commanded, not awaited. Errors when no session is active or interrupted"
+    )]
+    pub(crate) async fn abort_session(
+        &self,
+        Parameters(params): Parameters<AbortSessionParams>,
+        ctx: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        // The streamable-HTTP transport hands the tool the request's
+        // HTTP parts; the caller's session is its `Mcp-Session-Id`.
+        let caller_session = ctx
+            .extensions
+            .get::<axum::http::request::Parts>()
+            .and_then(|parts| parts.headers.get("mcp-session-id"))
+            .and_then(|id| id.to_str().ok())
+            .map(str::to_owned);
+        self.abort_session_inner(params, caller_session.as_deref())
+            .await
+    }
+
+    /// Body of the `abort_session` MCP tool, split out so unit tests can
+    /// name the caller's MCP session without a live transport.
+    pub(crate) async fn abort_session_inner(
+        &self,
+        _params: AbortSessionParams,
+        caller_session: Option<&str>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let Some(manager) = &self.session_manager else {
+            return Ok(tool_error!("abort_session: session manager not configured"));
+        };
+        // End the session first: the state transition is the atomic
+        // check, so a workflow completion (or a second abort) racing
+        // this one cannot also tear the rig down.
+        let ids = match manager.abort().await {
+            Ok(ids) => ids,
+            Err(e) => return Ok(tool_error!("abort_session: {}", e)),
+        };
+        // Cancel the orchestrator's in-flight tool calls — a `capture`
+        // still polling its camera, a slew still settling — before the
+        // hardware steps, as the unsafe transition does; the caller's
+        // own session stays open for the result.
+        let mcp_sessions_closed = match &self.mcp_sessions {
+            Some(sessions) => crate::safety::close_mcp_sessions(sessions, caller_session).await,
+            None => 0,
+        };
+        // One snapshot for the whole teardown, as on the unsafe
+        // transition; same order — the mount never moves under an
+        // exposing camera or an active guide loop.
+        let equipment = self.equipment.snapshot();
+        let aborted_exposures =
+            crate::safety::abort_exposures(&equipment, &self.equipment.released_cameras()).await;
+        let guiding_stopped =
+            crate::safety::stop_guiding(self.guider.as_ref(), &self.event_bus, "aborted").await;
+        let mount_park_commanded = crate::safety::park_mount(&equipment).await;
+
+        Ok(tool_success!({
+            "session_id": ids["session_id"],
+            "workflow_id": ids["workflow_id"],
+            "mcp_sessions_closed": mcp_sessions_closed,
+            "aborted_exposures": aborted_exposures,
+            "guiding_stopped": guiding_stopped,
+            "mount_park_commanded": mount_park_commanded,
+        }))
+    }
+}
@@ -0,0 +1,274 @@
+//! Switch tool category: `list_switches`, `get_switch`, `set_switch`
+//! (rp.md § Switch and Observing Conditions Tool Details).
+//!
+//! An Alpaca Switch device is a bank of numbered ports — power outlets,
+//! dew heaters, a USB hub — each either boolean or analog with a device
+//! reported `[min, max]` range and step. `set_switch` validates an
+//! analog value against those before writing, so a bad value is refused
+//! by rp with the port's limits in the message instead of surfacing as
+//! a bare driver error.
+
+use std::sync::Arc;
+
+use ascom_alpaca::api::Switch;
+use rmcp::handler::server::wrapper::Parameters;
+use rmcp::model::CallToolResult;
+use rmcp::{tool, tool_router};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::super::handler::McpHandler;
+use super::super::{resolve_device, tool_error, tool_success};
+
+/// How far off the step grid an analog value may sit, as a fraction of
+/// the step, and still count as on it. Absorbs the float noise of
+/// values like `0.1 * 3` without admitting a genuinely off-grid value.
+const STEP_TOLERANCE: f64 = 1e-6;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SwitchIdParams {
+    pub switch_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SwitchPortParams {
+    pub switch_id: String,
+    /// Port number, `0 <= index < port count` (see `list_switches`).
+    pub index: usize,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct SetSwitchParams {
+    pub switch_id: String,
+    /// Port number, `0 <= index < port count` (see `list_switches`).
+    pub index: usize,
+    /// Boolean write: `true` sets the port to its maximum, `false` to
+    /// its minimum. Give exactly one of `state` and `value`.
+    #[serde(default)]
+    pub state: Option<bool>,
+    /// Analog write: must lie in the port's `[min, max]` and on its
+    /// step grid counted from `min`. Give exactly one of `state` and
+    /// `value`.
+    #[serde(default)]
+    pub value: Option<f64>,
+}
+
+/// One port as the switch tools report it.
+#[derive(Debug, Serialize)]
+struct SwitchPort {
+    index: usize,
+    name: String,
+    /// Optional in practice; `None` when the driver won't say.
+    description: Option<String>,
+    can_write: bool,
+    state: bool,
+    value: f64,
+    min: f64,
+    max: f64,
+    step: f64,
+}
+
+#[tool_router(router = tool_router_switch, vis = "pub")]
+impl McpHandler {
+    #[tool(
+        description = "List every port of a switch device: index, name, description, can_write, boolean state, analog value, and the min/max/step the device reports for it. Read-only"
+    )]
+    pub(crate) async fn list_switches(
+        &self,
+        Parameters(params): Parameters<SwitchIdParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, switch) = resolve_device!(self, find_switch, &params.switch_id, "switch");
+        let count = match switch.max_switch().await {
+            Ok(n) => n,
+            Err(e) => return Ok(tool_error!("failed to read switch port count: {}", e)),
+        };
+        let mut ports = Vec::with_capacity(count);
+        for index in 0..count {
+            match read_port(&switch, index).await {
+                Ok(port) => ports.push(port),
+                Err(e) => return Ok(tool_error!("{}", e)),
+            }
+        }
+        Ok(tool_success!({
+            "switch_id": params.switch_id,
+            "ports": ports,
+        }))
+    }
+
+    #[tool(
+        description = "Read one switch port: name, description, can_write, boolean state, analog value, and min/max/step. Read-only"
+    )]
+    pub(crate) async fn get_switch(
+        &self,
+        Parameters(params): Parameters<SwitchPortParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, switch) = resolve_device!(self, find_switch, &params.switch_id, "switch");
+        if let Err(e) = check_index(&switch, params.index).await {
+            return Ok(tool_error!("{}", e));
+        }
+        match read_port(&switch, params.index).await {
+            Ok(port) => Ok(tool_success!({
+                "switch_id": params.switch_id,
+                "port": port,
+            })),
+            Err(e) => Ok(tool_error!("{}", e)),
+        }
+    }
+
+    #[tool(
+        description = "Drive one switch port — a power outlet, dew heater, or other Alpaca Switch port. Give exactly one of state (boolean write) or value (analog write). A value must lie within the port's min..max and on its step grid counted from min, as the device reports them; it is refused otherwise. Errors on a read-only port. Returns the port as read back after the write and emits switch_changed"
+    )]
+    pub(crate) async fn set_switch(
+        &self,
+        Parameters(params): Parameters<SetSwitchParams>,
+    ) -> Result<CallToolResult, rmcp::ErrorData> {
+        let (_entry, switch) = resolve_device!(self, find_switch, &params.switch_id, "switch");
+        let index = params.index;
+        if let Err(e) = check_index(&switch, index).await {
+            return Ok(tool_error!("{}", e));
+        }
+        match switch.can_write(index).await {
+            Ok(true) => {}
+            Ok(false) => return Ok(tool_error!("switch port {} is read-only", index)),
+            Err(e) => {
+                return Ok(tool_error!(
+                    "failed to read switch port {} can_write: {}",
+                    index,
+                    e
+                ))
+            }
+        }
+
+        let written = match (params.state, params.value) {
+            (Some(state), None) => switch.set_switch(index, state).await,
+            (None, Some(value)) => {
+                if let Err(e) = check_analog_value(&switch, index, value).await {
+                    return Ok(tool_error!("{}", e));
+                }
+                switch.set_switch_value(index, value).await
+            }
+            _ => {
+                return Ok(tool_error!(
+                    "set_switch: give exactly one of state and value"
+                ))
+            }
+        };
+        if let Err(e) = written {
+            return Ok(tool_error!("failed to set switch port {}: {}", index, e));
+        }
+
+        let port = match read_port(&switch, index).await {
+            Ok(port) => port,
+            Err(e) => return Ok(tool_error!("{}", e)),
+        };
+        self.event_bus.emit(
+            "switch_changed",
+            serde_json::json!({
+                "switch_id": params.switch_id,
+                "index": index,
+                "name": port.name,
+                "state": port.state,
+                "value": port.value,
+            }),
+        );
+        Ok(tool_success!({
+            "switch_id": params.switch_id,
+            "port": port,
+        }))
+    }
+}
+
+/// Refuse an index past the device's port count with a message naming
+/// the count, rather than whatever the driver says about it.
+async fn check_index(switch: &Arc<dyn Switch>, index: usize) -> Result<(), String> {
+    let count = switch
+        .max_switch()
+        .await
+        .map_err(|e| format!("failed to read switch port count: {e}"))?;
+    if index >= count {
+        return Err(format!(
+            "switch port {index} out of range: the device has {count} ports (0..{count})"
+        ));
+    }
+    Ok(())
+}
+
+/// Validate an analog write against the port's reported range and step.
+async fn check_analog_value(
+    switch: &Arc<dyn Switch>,
+    index: usize,
+    value: f64,
+) -> Result<(), String> {
+    if !value.is_finite() {
+        return Err(format!("switch port {index}: value must be finite"));
+    }
+    let read = |what: &str, e: ascom_alpaca::ASCOMError| {
+        format!("failed to read switch port {index} {what}: {e}")
+    };
+    let min = switch
+        .min_switch_value(index)
+        .await
+        .map_err(|e| read("min", e))?;
+    let max = switch
+        .max_switch_value(index)
+        .await
+        .map_err(|e| read("max", e))?;
+    let step = switch
+        .switch_step(index)
+        .await
+        .map_err(|e| read("step", e))?;
+    if value < min || value > max {
+        return Err(format!(
+            "switch port {index}: value {value} outside the port's range {min}..{max}"
+        ));
+    }
+    if step > 0.0 {
+        let steps = (value - min) / step;
+        if (steps - steps.round()).abs() > STEP_TOLERANCE {
+            let below = min + steps.floor() * step;
+            let above = (below + step).min(max);
+            return Err(format!(
+                "switch port {index}: value {value} is not on the port's step grid (min {min}, step {step}); nearest valid values are {below} and {above}"
+            ));
+        }
+    }
+    Ok(())
+}
+
+async fn read_port(switch: &Arc<dyn Switch>, index: usize) -> Result<SwitchPort, String> {
+    let read = |what: &str, e: ascom_alpaca::ASCOMError| {
+        format!("failed to read switch port {index} {what}: {e}")
+    };
+    Ok(SwitchPort {
+        index,
+        name: switch
+            .get_switch_name(index)
+            .await
+            .map_err(|e| read("name", e))?,
+        description: switch.get_switch_description(index).await.ok(),
+        can_write: switch
+            .can_write(index)
+            .await
+            .map_err(|e| read("can_write", e))?,
+        state: switch
+            .get_switch(index)
+            .await
+            .map_err(|e| read("state", e))?,
+        value: switch
+            .get_switch_value(index)
+            .await
+            .map_err(|e| read("value", e))?,
+        min: switch
+            .min_switch_value(index)
+            .await
+            .map_err(|e| read("min", e))?,
+        max: switch
+            .max_switch_value(index)
+            .await
+            .map_err(|e| read("max", e))?,
+        step: switch
+            .switch_step(index)
+            .await
+            .map_err(|e| read("step", e))?,
+    })
+}
@@ -81,6 +81,10 @@ pub struct AddTargetParams {
     /// config. Not accepted with `source` — imports carry no thresholds.
     #[serde(default)]
     pub grading: Option<GradingWire>,
+    /// Multi-panel framing (rp.md § Target Store → Mosaics). Omitted →
+    /// a single-frame target. Not accepted with `source`.
+    #[serde(default)]
+    pub mosaic: Option<MosaicWire>,
     /// Selects the import form (rp.md § Target Store → Import form):
     /// bare `ra_hours` + `dec_degrees` plus this typed provenance.
     #[serde(default)]
@@ -130,6 +134,31 @@ impl From<SchedulingWire> for rp_targets::SchedulingConstraints {
     }
 }
 
+/// The wire shape of `add_target`/`update_target`'s `mosaic` parameter
+/// — field-for-field [`rp_targets::MosaicSpec`], restated for the same
+/// no-`schemars`-in-`rp-targets` reason as [`SchedulingWire`].
+#[derive(Debug, Clone, Copy, Deserialize, JsonSchema)]
+pub struct MosaicWire {
+    /// Panels across the frame's width, 1-10.
+    pub columns: u32,
+    /// Panels along the frame's height, 1-10. At least two panels in
+    /// total.
+    pub rows: u32,
+    /// Overlap between adjacent panels as a percentage of the field,
+    /// at least 0 and below 50.
+    pub overlap_percent: f64,
+}
+
+impl From<MosaicWire> for rp_targets::MosaicSpec {
+    fn from(w: MosaicWire) -> Self {
+        Self {
+            columns: w.columns,
+            rows: w.rows,
+            overlap_percent: w.overlap_percent,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct GetTargetParams {
     pub slug: String,
@@ -177,6 +206,12 @@ pub struct UpdateTargetParams {
     #[serde(default, deserialize_with = "double_option_grading")]
     #[schemars(with = "Option<GradingWire>")]
     pub grading: Option<Option<GradingWire>>,
+    /// Replaces the mosaic framing when present; an explicit `null`
+    /// turns the target back into a single frame. Frames already
+    /// captured under panel slugs stay on disk either way.
+    #[serde(default, deserialize_with = "double_option_mosaic")]
+    #[schemars(with = "Option<MosaicWire>")]
+    pub mosaic: Option<Option<MosaicWire>>,
 }
 
 /// Distinguishes an absent field (`None` — leave untouched) from an
@@ -199,6 +234,14 @@ where
     Ok(Some(Option::<GradingWire>::deserialize(deserializer)?))
 }
 
+/// [`double_option`] for the `mosaic` object.
+fn double_option_mosaic<'de, D>(deserializer: D) -> Result<Option<Option<MosaicWire>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Some(Option::<MosaicWire>::deserialize(deserializer)?))
+}
+
 #[derive(Debug, Deserialize, JsonSchema)]
 pub struct DeleteTargetParams {
     pub slug: String,
@@ -231,9 +274,14 @@ impl McpHandler {
                        degrees east of north (at least 0.0, below 360.0); \
                        omitted, the imaging train's configured default \
                        applies at read time. \
+                       mosaic {columns, rows, overlap_percent} frames the \
+                       target as a grid of panels rotated with the \
+                       position angle; the planner images each panel as \
+                       its own sibling target (slug suffixed -r<row>c<col>) \
+                       carrying these goals. \
                        A third form for importers: bare ra_hours + \
                        dec_degrees + source {kind, client, received_at} — \
-                       catalog_ref, display_name, active, notes, and \
+                       catalog_ref, display_name, active, notes, mosaic, and \
                        position_angle_degrees are \
                        all rejected with source; the import lands paused \
                        (active: false), rp names it by reverse cone-search \
@@ -262,7 +310,7 @@ impl McpHandler {
         let payload_errors = super::plan_validation::validate_add_target(
             &params,
             &self.target_store_defaults.default_goals,
-            &self.equipment,
+            &self.equipment.snapshot(),
         );
         if !payload_errors.is_empty() {
             return Ok(tool_error!("{}", render_first(&payload_errors)));
@@ -365,7 +413,7 @@ impl McpHandler {
         let goals = match parse_goals(
             &params.goals,
             &self.target_store_defaults.default_goals,
-            &self.equipment,
+            &self.equipment.snapshot(),
         ) {
             Ok(g) => g,
             Err(e) => return Ok(tool_error!("{}", e)),
@@ -385,6 +433,7 @@ impl McpHandler {
             magnitude,
             size_arcmin,
             position_angle_degrees,
+            mosaic: params.mosaic.map(Into::into),
             priority: 0,
             active: params.active.unwrap_or(true),
             goals,
@@ -409,7 +458,10 @@ impl McpHandler {
 
     #[tool(description = "Fetch one target with progress derived from the \
                        frames on disk (per-goal {filter, binning, \
-                       exposure_duration, desired_count, good, total}).")]
+                       exposure_duration, desired_count, good, total}). A \
+                       mosaic also reports panels: [{slug, progress}], one \
+                       per panel in row-major order — its frames are \
+                       captured under the panel slugs, not its own.")]
     pub(crate) async fn get_target(
         &self,
         Parameters(params): Parameters<GetTargetParams>,
@@ -424,18 +476,23 @@ impl McpHandler {
         match store.get_target(&slug).await {
             Ok(Some(t)) => {
                 let counts = self.derive_progress(&t).await;
-                Ok(tool_success!({
+                let mut v = json!({
                     "target": target_to_json(&t),
                     "progress": progress_rows(&t, &counts),
-                }))
+                });
+                if let Some(panels) = self.panel_progress(&t).await {
+                    v["panels"] = panels;
+                }
+                Ok(tool_success!(v))
             }
             Ok(None) => Ok(tool_error!("no target with slug {:?}", params.slug)),
             Err(e) => Ok(tool_error!("target store error: {}", e)),
         }
     }
 
-    #[tool(description = "List all targets, each with derived progress, \
-                       optionally filtered to active == true.")]
+    #[tool(description = "List all targets, each with derived progress \
+                       (and, for a mosaic, per-panel panels as in \
+                       get_target), optionally filtered to active == true.")]
     pub(crate) async fn list_targets(
         &self,
         Parameters(params): Parameters<ListTargetsParams>,
@@ -455,6 +512,9 @@ impl McpHandler {
             let counts = self.derive_progress(t).await;
             let mut v = target_to_json(t);
             v["progress"] = json!(progress_rows(t, &counts));
+            if let Some(panels) = self.panel_progress(t).await {
+                v["panels"] = panels;
+            }
             items.push(v);
         }
         Ok(tool_success!({ "targets": items }))
@@ -467,7 +527,9 @@ impl McpHandler {
                        overrides object (not a field-wise merge). \
                        position_angle_degrees set to an explicit null \
                        clears the framing angle back to \
-                       inherit-the-train-default; omitted it is untouched.")]
+                       inherit-the-train-default; omitted it is untouched. \
+                       mosaic likewise: an object replaces the panel grid, \
+                       an explicit null makes the target a single frame.")]
     pub(crate) async fn update_target(
         &self,
         Parameters(params): Parameters<UpdateTargetParams>,
@@ -536,6 +598,16 @@ impl McpHandler {
             }
             target.grading = v.map(Into::into);
         }
+        // Same double-option shape for the panel grid.
+        if let Some(v) = params.mosaic {
+            if let Some(m) = &v {
+                let errors = super::plan_validation::validate_mosaic(m, "mosaic");
+                if !errors.is_empty() {
+                    return Ok(tool_error!("{}", render_first(&errors)));
+                }
+            }
+            target.mosaic = v.map(Into::into);
+        }
         target.updated_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
         target.updated_by = OPERATOR_WRITER.to_string();
         if let Err(e) = store.upsert_target(target.clone()).await {
@@ -580,7 +652,7 @@ impl McpHandler {
         };
         let goals = match super::plan_validation::validate_goals(
             &params.goals,
-            &super::plan_validation::filter_roster(&self.equipment),
+            &super::plan_validation::filter_roster(&self.equipment.snapshot()),
             "goals",
         ) {
             Ok(g) => g,
@@ -641,6 +713,12 @@ impl McpHandler {
                  target_store.default_grading until the operator sets thresholds"
             ));
         }
+        if params.mosaic.is_some() {
+            return Ok(tool_error!(
+                "mosaic is not accepted with source — imports are single frames \
+                 until the operator frames them"
+            ));
+        }
         if source.kind.trim().is_empty() || source.kind == OPERATOR_WRITER {
             return Ok(tool_error!(
                 "source.kind must be a non-empty writer identity other than {:?}",
@@ -659,7 +737,7 @@ impl McpHandler {
         let goals = match parse_goals(
             &params.goals,
             &self.target_store_defaults.default_goals,
-            &self.equipment,
+            &self.equipment.snapshot(),
         ) {
             Ok(g) => g,
             Err(e) => return Ok(tool_error!("{}", e)),
@@ -779,6 +857,7 @@ impl McpHandler {
             // Imports never carry a framing angle — the operator enters
             // one in the P4 inbox; until then the train default applies.
             position_angle_degrees: None,
+            mosaic: None,
             priority: 0,
             active: false,
             goals,
@@ -997,6 +1076,23 @@ impl McpHandler {
         )
         .await
     }
+
+    /// A mosaic's per-panel progress, `[{slug, progress}]` in row-major
+    /// panel order (rp.md § Target Store → Mosaics); `None` for a
+    /// single-frame target. Each panel is scanned under its own slug, so
+    /// these are the counts the planner ranks the sibling panels on.
+    async fn panel_progress(&self, target: &Target) -> Option<Value> {
+        target.mosaic?;
+        let mut panels = Vec::new();
+        for row in crate::planner::mosaic::panel_rows(target) {
+            let counts = self.derive_progress(&row).await;
+            panels.push(json!({
+                "slug": row.slug.as_str(),
+                "progress": progress_rows(&row, &counts),
+            }));
+        }
+        Some(Value::Array(panels))
+    }
 }
 
 /// Pair a target's goals with the counts the on-disk scan derived for
@@ -6,18 +6,20 @@
 //! per-category routers via the `+` operator on
 //! [`rmcp::handler::server::router::tool::ToolRouter`].
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use rmcp::handler::server::router::tool::ToolRouter;
+use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 
-use crate::equipment::EquipmentRegistry;
+use crate::equipment::SharedEquipment;
 use crate::events::EventBus;
 use crate::persistence::ImageCache;
 use crate::session::SessionConfig;
 
 #[derive(Clone)]
 pub struct McpHandler {
-    pub equipment: Arc<EquipmentRegistry>,
+    pub equipment: SharedEquipment,
     pub event_bus: Arc<EventBus>,
     pub session_config: SessionConfig,
     pub image_cache: ImageCache,
@@ -30,6 +32,11 @@ pub struct McpHandler {
     /// `Config.planner.min_altitude_degrees`, falling back to 20°
     /// when omitted.
     pub default_min_altitude_degrees: f64,
+    /// The site's horizon profile (rp.md §"Site Configuration" →
+    /// Horizon profile), raising the altitude floor per azimuth for
+    /// `get_next_target`, `get_target_status` and `compute_rise_set`.
+    /// Empty (a flat horizon) unless wired by `with_horizon`.
+    pub horizon: Arc<rp_ephemeris::HorizonProfile>,
     /// The `record_exposure` counters (rp.md §"Session Persistence"
     /// `progress` map, in-memory). Behind an `Arc` so every clone of
     /// the handler — rmcp clones it per MCP connection — shares one
@@ -49,6 +56,11 @@ pub struct McpHandler {
     /// counters are the resume payload). `None` in tests that only
     /// exercise the tools.
     pub session_manager: Option<Arc<crate::session::SessionManager>>,
+    /// The `/mcp` transport's session registry, shared with the safety
+    /// enforcer, so `abort_session` can close the orchestrator's
+    /// sessions and cancel their in-flight tool calls. `None` in tests
+    /// that do not exercise it.
+    pub mcp_sessions: Option<Arc<LocalSessionManager>>,
     /// Optional plate-solver HTTP client. `None` ⇒ `plate_solve`
     /// MCP tool returns "plate solver not configured". Wired by
     /// `with_plate_solver` from the `plate_solver` block in rp
@@ -78,6 +90,11 @@ pub struct McpHandler {
     /// `Arc` so every clone of the handler — rmcp clones it per MCP
     /// connection — contends on the same gate.
     pub motion_gate: Arc<crate::motion_gate::MotionGate>,
+    /// The dome-slaving follower (rp.md § Dome Slaving), shared across
+    /// handler clones like the motion gate. `set_dome_slaving` starts
+    /// and stops it; `do_capture` waits on it for imaging-train
+    /// exposures.
+    pub dome_slaving: Arc<crate::dome_slaving::DomeSlaving>,
     /// Per-rig estimates sizing the advisory `center_on_target` deadline
     /// (§2.5) carried on `centering_started`. Wired by
     /// `with_centering_config` from the `centering` block in rp config;
@@ -88,6 +105,10 @@ pub struct McpHandler {
     /// document. `None` in tests that only exercise the tools — frames
     /// then record no `cooler_setpoint_c`.
     pub cooling: Option<Arc<crate::cooling::CoolingController>>,
+    /// Dew-heater controller (rp.md § Dew Management), read by
+    /// `do_capture` to stamp heater duties on each exposure document.
+    /// `None` without a `dew` block, and in tests.
+    pub dew: Option<Arc<crate::dew::DewController>>,
     /// The target store (rp.md § Target Store). `None` in tests that
     /// only exercise other tool categories and configs where opening
     /// it failed to matter — the target CRUD tools then report "target
@@ -100,10 +121,35 @@ pub struct McpHandler {
     pub target_store_defaults: crate::config::TargetStoreConfig,
     /// `session.file_naming_pattern`/`directory_pattern`, compiled once
     /// at startup (Decision 11). `None` when `file_naming_pattern` is
-    /// unset — `do_capture` then keeps writing a flat `<doc_uuid_8>.fits`
+    /// unset — `do_capture` then keeps writing a flat `<doc_uuid_8>.<ext>`
     /// regardless of `capture`'s `target`/`frame_type` parameters. Wired
     /// by `with_naming_templates` from lib.rs.
     pub naming_templates: Option<Arc<crate::config::naming_template::NamingTemplates>>,
+    /// `session.fits_keywords` as header cards, layered over the
+    /// standard keywords `do_capture` derives for every frame. Empty
+    /// unless wired by `with_fits_keywords` from lib.rs.
+    pub fits_keywords: Arc<[rp_fits::writer::Keyword]>,
+    /// `session.image_format` with its compression option: how
+    /// `do_capture` encodes frames on disk, and the extension it gives
+    /// them. Plain FITS unless wired by `with_frame_encoding` from lib.rs.
+    pub frame_encoding: crate::config::FrameEncoding,
+    /// The persisted config file plus the running CA and site, read by
+    /// the runtime equipment tools (rp.md § Runtime Equipment Changes).
+    /// `None` ⇒ `connect_device` and `reload_equipment` report "not
+    /// configured"; `disconnect_device` needs no config and still works.
+    /// Wired by `with_equipment_source` from lib.rs.
+    pub equipment_source: Option<crate::equipment::EquipmentSource>,
+    /// The auto-focus history and temperature model (rp.md § Focus
+    /// Temperature Compensation), shared across handler clones. Every
+    /// successful auto-focus step records into it. In-memory unless
+    /// wired by `with_focus_model` from lib.rs.
+    pub focus_model: Arc<crate::focus_model::FocusModelStore>,
+    /// Per-filter focus offsets by train id, as `set_filter` applies
+    /// them. Seeded from the train model by `with_trains`;
+    /// `measure_filter_offsets` replaces a train's entry after writing
+    /// the config, so new offsets take effect without a restart. Shared
+    /// across handler clones; never held across an `.await`.
+    pub focus_offsets: Arc<std::sync::Mutex<BTreeMap<String, BTreeMap<String, i32>>>>,
     /// Merged tool catalog. Built by summing per-category routers
     /// in [`McpHandler::new`]; consumed by the
     /// `#[tool_handler(router = self.tool_router)]` `ServerHandler`
@@ -113,13 +159,17 @@ pub struct McpHandler {
 
 impl McpHandler {
     pub fn new(
-        equipment: Arc<EquipmentRegistry>,
+        equipment: SharedEquipment,
         event_bus: Arc<EventBus>,
         session_config: SessionConfig,
         image_cache: ImageCache,
         site: Option<rp_ephemeris::Site>,
     ) -> Self {
         let motion_gate = Arc::new(crate::motion_gate::MotionGate::new(event_bus.clone()));
+        let dome_slaving = Arc::new(crate::dome_slaving::DomeSlaving::new(
+            equipment.clone(),
+            event_bus.clone(),
+        ));
         Self {
             equipment,
             event_bus,
@@ -127,21 +177,30 @@ impl McpHandler {
             image_cache,
             site,
             default_min_altitude_degrees: 20.0,
+            horizon: Arc::new(rp_ephemeris::HorizonProfile::default()),
             progress: Arc::new(std::sync::Mutex::new(
                 crate::planner::progress::SessionProgress::default(),
             )),
             session_manager: None,
+            mcp_sessions: None,
             plate_solver: None,
             plate_solver_default_search_radius_deg: None,
             guider: None,
             guider_defaults: crate::config::GuiderDefaults::default(),
             trains: crate::equipment::trains::TrainModel::default(),
             motion_gate,
+            dome_slaving,
             centering: crate::config::CenteringConfig::default(),
             cooling: None,
+            dew: None,
             target_store: None,
             target_store_defaults: crate::config::TargetStoreConfig::default(),
             naming_templates: None,
+            fits_keywords: Arc::new([]),
+            frame_encoding: crate::config::FrameEncoding::default(),
+            equipment_source: None,
+            focus_model: Arc::new(crate::focus_model::FocusModelStore::in_memory()),
+            focus_offsets: Arc::new(std::sync::Mutex::new(BTreeMap::new())),
             // Pattern (c) merge: each `built_in/<category>.rs`
             // declares a `#[tool_router(router = tool_router_<name>,
             // vis = "pub")]` block whose generated associated function
@@ -160,9 +219,16 @@ impl McpHandler {
                 + Self::tool_router_plate_solve()
                 + Self::tool_router_guider()
                 + Self::tool_router_center_on_target()
+                + Self::tool_router_meridian_flip()
+                + Self::tool_router_dome()
                 + Self::tool_router_planner()
                 + Self::tool_router_targets()
-                + Self::tool_router_plan_schema(),
+                + Self::tool_router_plan_schema()
+                + Self::tool_router_equipment()
+                + Self::tool_router_session()
+                + Self::tool_router_focus_model()
+                + Self::tool_router_switch()
+                + Self::tool_router_observing_conditions(),
         }
     }
 
@@ -181,6 +247,15 @@ impl McpHandler {
         self
     }
 
+    /// Wire the site's horizon profile, loaded once at startup from
+    /// `site.horizon`. Tests that don't exercise the horizon keep the
+    /// empty profile `new()` installs.
+    #[must_use]
+    pub fn with_horizon(mut self, horizon: Arc<rp_ephemeris::HorizonProfile>) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
     /// Share the `record_exposure` counters with the rest of the
     /// process (lib.rs passes the same `Arc` to `SessionManager` so a
     /// fresh session start clears them). Tests that only exercise the
@@ -195,7 +270,7 @@ impl McpHandler {
 
     /// Wire the session manager so `record_exposure` can re-persist
     /// the session state file after each recorded frame (rp.md
-    /// § Write Strategy).
+    /// § Write Strategy) and `abort_session` can end the session.
     pub fn with_session_manager(
         mut self,
         session_manager: Arc<crate::session::SessionManager>,
@@ -204,6 +279,13 @@ impl McpHandler {
         self
     }
 
+    /// Wire the `/mcp` session registry `abort_session` closes the
+    /// orchestrator's sessions through.
+    pub fn with_mcp_sessions(mut self, mcp_sessions: Arc<LocalSessionManager>) -> Self {
+        self.mcp_sessions = Some(mcp_sessions);
+        self
+    }
+
     /// Wire the plate-solver HTTP client + operator-set search-radius
     /// default. `None` for `client` keeps the MCP tool reporting
     /// "not configured"; `None` for the radius means the wrapper
@@ -239,8 +321,17 @@ impl McpHandler {
     /// Wire the derived optical-train model. The lib.rs build path
     /// calls this with the model built from `equipment.optical_trains`;
     /// tests without trains keep the empty default (no optics block).
+    /// Also seeds the runtime focus offsets from each train's
+    /// `focus_offsets`.
     #[must_use]
     pub fn with_trains(mut self, trains: crate::equipment::trains::TrainModel) -> Self {
+        let offsets = trains
+            .trains()
+            .iter()
+            .filter(|t| !t.focus_offsets.is_empty())
+            .map(|t| (t.id.clone(), t.focus_offsets.clone()))
+            .collect();
+        self.focus_offsets = Arc::new(std::sync::Mutex::new(offsets));
         self.trains = trains;
         self
     }
@@ -265,6 +356,13 @@ impl McpHandler {
         self
     }
 
+    /// Wire the dew-heater controller so `do_capture` can stamp heater
+    /// duties on each exposure document (rp.md § Dew Management).
+    pub fn with_dew(mut self, dew: Option<Arc<crate::dew::DewController>>) -> Self {
+        self.dew = dew;
+        self
+    }
+
     /// Wire the target store (rp.md § Target Store) plus its config
     /// defaults. The lib.rs build path always calls this with `Some`
     /// (it opens the store unconditionally); tests that don't need
@@ -290,4 +388,40 @@ impl McpHandler {
         self.naming_templates = naming_templates;
         self
     }
+
+    /// Wire the operator's `session.fits_keywords` cards.
+    #[must_use]
+    pub fn with_fits_keywords(mut self, fits_keywords: Vec<rp_fits::writer::Keyword>) -> Self {
+        self.fits_keywords = fits_keywords.into();
+        self
+    }
+
+    /// Wire the session's frame encoding (see
+    /// [`crate::config::SessionConfig::frame_encoding`]).
+    #[must_use]
+    pub const fn with_frame_encoding(
+        mut self,
+        frame_encoding: crate::config::FrameEncoding,
+    ) -> Self {
+        self.frame_encoding = frame_encoding;
+        self
+    }
+
+    /// Wire where the runtime equipment tools (`connect_device`,
+    /// `reload_equipment`) read the persisted config from. Tests that
+    /// don't exercise them leave it unset — the tools then report
+    /// "not configured".
+    #[must_use]
+    pub fn with_equipment_source(mut self, source: crate::equipment::EquipmentSource) -> Self {
+        self.equipment_source = Some(source);
+        self
+    }
+
+    /// Wire the persisted focus model (`session.focus_model_file`).
+    /// Tests leave the in-memory store `new()` creates.
+    #[must_use]
+    pub fn with_focus_model(mut self, store: Arc<crate::focus_model::FocusModelStore>) -> Self {
+        self.focus_model = store;
+        self
+    }
 }
@@ -12,7 +12,8 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use ascom_alpaca::api::camera::CameraState;
+use ascom_alpaca::api::camera::{CameraState, SensorType};
+use ascom_alpaca::api::telescope::PierSide;
 use tokio::time::Instant;
 use tracing::debug;
 use uuid::Uuid;
@@ -100,6 +101,44 @@ pub(crate) fn centering_deadlines(
     (predicted_ms, max_ms)
 }
 
+/// Size the predictive `perform_meridian_flip` deadline for the
+/// `meridian_flip_started` envelope by summing its legs: the hour-angle
+/// wait (known exactly before the envelope goes out), a worst-case
+/// [`PARK_WORST_CASE_TRAVERSE_DEG`] re-slew at `slew_rate_arcsec_per_sec`
+/// plus settle, the re-centering pair from [`centering_deadlines`], and
+/// the guider settle pair (zero when no guiding restart is planned or
+/// no settle timeout is pinned). The re-slew's `max` leg uses slew's ×3
+/// headroom floored at [`MIN_SLEW_DEADLINE`]; `extra_max` carries legs
+/// with no prediction at all (the fixed rotator ceiling). Advisory only,
+/// like [`centering_deadlines`] — each inner operation carries and
+/// enforces its own deadline.
+pub(crate) fn meridian_flip_deadlines(
+    wait: Duration,
+    slew_rate_arcsec_per_sec: f64,
+    settle_after: Duration,
+    centering_ms: (u64, u64),
+    guide_settle_ms: (u64, u64),
+    extra_max: Duration,
+) -> (u64, u64) {
+    let slew_secs = PARK_WORST_CASE_TRAVERSE_DEG * 3600.0 / slew_rate_arcsec_per_sec
+        + settle_after.as_secs_f64();
+    let slew_max_secs = (slew_secs * 3.0).max(MIN_SLEW_DEADLINE.as_secs_f64());
+    let wait_ms = u64::try_from(wait.as_millis()).unwrap_or(u64::MAX);
+    let extra_max_ms = u64::try_from(extra_max.as_millis()).unwrap_or(u64::MAX);
+    // `as u64` saturates on overflow and maps NaN to 0; the rate is
+    // config-validated finite and positive.
+    let predicted_ms = wait_ms
+        .saturating_add((slew_secs * 1000.0).round() as u64)
+        .saturating_add(centering_ms.0)
+        .saturating_add(guide_settle_ms.0);
+    let max_ms = wait_ms
+        .saturating_add((slew_max_secs * 1000.0).round() as u64)
+        .saturating_add(centering_ms.1)
+        .saturating_add(guide_settle_ms.1)
+        .saturating_add(extra_max_ms);
+    (predicted_ms, max_ms)
+}
+
 /// Floor on the predictive slew deadline (§2.1 of the predictive-deadlines
 /// plan). A short slew still gets at least this long before it's considered
 /// overrun, covering fixed overheads that `distance / rate` ignores:
@@ -219,7 +258,19 @@ pub(crate) struct ResolvedMeasureStarsParams {
     pub(crate) stamp_half_size: usize,
 }
 
-/// Counts existing `.fits` frames in `dir` whose filename (parsed via
+/// `BAYERPAT` for an RGGB-family sensor from the camera's
+/// `BayerOffsetX`/`BayerOffsetY`: the offsets shift the readout origin
+/// by a column and/or a row, so only their parity matters.
+const fn bayer_pattern(offset_x: u8, offset_y: u8) -> &'static str {
+    match (offset_x % 2, offset_y % 2) {
+        (0, 0) => "RGGB",
+        (1, 0) => "GRBG",
+        (0, _) => "GBRG",
+        _ => "BGGR",
+    }
+}
+
+/// Counts existing frames (`.fits` or `.xisf`) in `dir` whose filename (parsed via
 /// `file_template`) shares this frame's `(filter, binning, exposure_duration)`
 /// sub-spec, and returns count + 1 — the `{frame_number}` value for a
 /// new frame in that sub-spec. Nothing is stored (rp-targets.md §
@@ -228,7 +279,9 @@ pub(crate) struct ResolvedMeasureStarsParams {
 /// observing night) rather than the whole data directory. `Ok(1)`
 /// when `dir` doesn't exist yet — this sub-spec's first frame here.
 /// Filters out `.json` sidecars explicitly: they share a filename stem
-/// with their FITS file, so counting both would double-count.
+/// with their frame file, so counting both would double-count. Both
+/// frame extensions count, so numbering carries on across a change of
+/// `session.image_format`.
 async fn next_frame_number(
     file_template: &naming_template::CompiledTemplate,
     dir: &std::path::Path,
@@ -254,7 +307,7 @@ async fn next_frame_number(
         .map_err(|e| format!("capture: failed to scan '{}': {}", dir.display(), e))?
     {
         let path = entry.path();
-        if path.extension().and_then(|e| e.to_str()) != Some("fits") {
+        if !persistence::is_frame_path(&path) {
             continue;
         }
         let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
@@ -282,9 +335,13 @@ impl McpHandler {
     pub(crate) async fn stats_via_document(
         &self,
         doc_id: &str,
-    ) -> crate::error::Result<imaging::ImageStats> {
+    ) -> crate::error::Result<FrameStats> {
         if let Some(cached) = self.image_cache.resolve(doc_id).await {
-            return crate::dispatch_pixels!(&cached.pixels, |arr| stats_outcome(arr));
+            return match &cached.pixels {
+                CachedPixels::U16(arr) => stats_outcome(arr.view()).map(FrameStats::Int),
+                CachedPixels::I32(arr) => stats_outcome(arr.view()).map(FrameStats::Int),
+                CachedPixels::F32(arr) => float_stats_outcome(arr.view()).map(FrameStats::Float),
+            };
         }
 
         debug!(document_id = %doc_id, "image cache miss, falling back to FITS");
@@ -298,15 +355,21 @@ impl McpHandler {
         self.stats_via_path(&doc.file_path).await
     }
 
-    pub(crate) async fn stats_via_path(
-        &self,
-        path: &str,
-    ) -> crate::error::Result<imaging::ImageStats> {
+    pub(crate) async fn stats_via_path(&self, path: &str) -> crate::error::Result<FrameStats> {
         let path_owned = path.to_string();
-        tokio::task::spawn_blocking(move || {
-            let (mut pixels, _w, _h) = persistence::read_fits_pixels(&path_owned)?;
-            imaging::compute_stats(&mut pixels)
-                .ok_or_else(|| crate::error::RpError::Imaging("image has no pixels".into()))
+        tokio::task::spawn_blocking(move || match persistence::read_frame_typed(&path_owned)? {
+            (persistence::FramePixels::Int(mut pixels), _w, _h) => {
+                imaging::compute_stats(&mut pixels)
+                    .map(FrameStats::Int)
+                    .ok_or_else(|| crate::error::RpError::Imaging("image has no pixels".into()))
+            }
+            (persistence::FramePixels::Float(pixels), width, height) => {
+                let arr =
+                    ndarray::Array2::from_shape_vec((width, height), pixels).map_err(|e| {
+                        crate::error::RpError::Imaging(format!("FITS shape mismatch: {e}"))
+                    })?;
+                float_stats_outcome(arr.view()).map(FrameStats::Float)
+            }
         })
         .await
         .map_err(|e| crate::error::RpError::Imaging(format!("task join error: {e}")))?
@@ -351,10 +414,10 @@ impl McpHandler {
         let min_a = params.min_area;
         let max_a = params.max_area;
         tokio::task::spawn_blocking(move || {
-            let (pixels, width, height) = persistence::read_fits_pixels(&path_owned)?;
-            let arr = ndarray::Array2::from_shape_vec((width, height), pixels)
-                .map_err(|e| crate::error::RpError::Imaging(format!("FITS shape mismatch: {e}")))?;
-            imaging::measure_basic(arr.view(), threshold, min_a, max_a, None)
+            let pixels = read_frame_for_analysis(&path_owned)?;
+            crate::dispatch_pixels!(&pixels, |arr| imaging::measure_basic(
+                arr, threshold, min_a, max_a, None
+            ))
         })
         .await
         .map_err(|e| crate::error::RpError::Imaging(format!("task join error: {e}")))?
@@ -389,10 +452,9 @@ impl McpHandler {
         let k = params.k;
         let max_iters = params.max_iters;
         tokio::task::spawn_blocking(move || {
-            let (pixels, width, height) = persistence::read_fits_pixels(&path_owned)?;
-            let arr = ndarray::Array2::from_shape_vec((width, height), pixels)
-                .map_err(|e| crate::error::RpError::Imaging(format!("FITS shape mismatch: {e}")))?;
-            clip_outcome(arr.view(), &ResolvedClipParams { k, max_iters })
+            let pixels = read_frame_for_analysis(&path_owned)?;
+            let params = ResolvedClipParams { k, max_iters };
+            crate::dispatch_pixels!(&pixels, |arr| clip_outcome(arr, &params))
         })
         .await
         .map_err(|e| crate::error::RpError::Imaging(format!("task join error: {e}")))?
@@ -434,10 +496,8 @@ impl McpHandler {
             max_area: params.max_area,
         };
         tokio::task::spawn_blocking(move || {
-            let (pixels, width, height) = persistence::read_fits_pixels(&path_owned)?;
-            let arr = ndarray::Array2::from_shape_vec((width, height), pixels)
-                .map_err(|e| crate::error::RpError::Imaging(format!("FITS shape mismatch: {e}")))?;
-            detect_outcome(arr.view(), &resolved, None)
+            let pixels = read_frame_for_analysis(&path_owned)?;
+            crate::dispatch_pixels!(&pixels, |arr| detect_outcome(arr, &resolved, None))
         })
         .await
         .map_err(|e| crate::error::RpError::Imaging(format!("task join error: {e}")))?
@@ -482,10 +542,10 @@ impl McpHandler {
         let max_a = params.max_area;
         let stamp = params.stamp_half_size;
         tokio::task::spawn_blocking(move || {
-            let (pixels, width, height) = persistence::read_fits_pixels(&path_owned)?;
-            let arr = ndarray::Array2::from_shape_vec((width, height), pixels)
-                .map_err(|e| crate::error::RpError::Imaging(format!("FITS shape mismatch: {e}")))?;
-            imaging::measure_stars(arr.view(), threshold, min_a, max_a, None, stamp)
+            let pixels = read_frame_for_analysis(&path_owned)?;
+            crate::dispatch_pixels!(&pixels, |arr| imaging::measure_stars(
+                arr, threshold, min_a, max_a, None, stamp
+            ))
         })
         .await
         .map_err(|e| crate::error::RpError::Imaging(format!("task join error: {e}")))?
@@ -528,10 +588,10 @@ impl McpHandler {
         let min_a = params.min_area;
         let max_a = params.max_area;
         tokio::task::spawn_blocking(move || {
-            let (pixels, width, height) = persistence::read_fits_pixels(&path_owned)?;
-            let arr = ndarray::Array2::from_shape_vec((width, height), pixels)
-                .map_err(|e| crate::error::RpError::Imaging(format!("FITS shape mismatch: {e}")))?;
-            imaging::compute_snr(arr.view(), threshold, min_a, max_a, None)
+            let pixels = read_frame_for_analysis(&path_owned)?;
+            crate::dispatch_pixels!(&pixels, |arr| imaging::compute_snr(
+                arr, threshold, min_a, max_a, None
+            ))
         })
         .await
         .map_err(|e| crate::error::RpError::Imaging(format!("task join error: {e}")))?
@@ -651,7 +711,13 @@ impl McpHandler {
         // guiding-train cameras bypass the gate — trains are
         // enrichment, not a gate. `exposure_started` below is emitted
         // only after the acquire, keeping its deadline honest.
+        //
+        // A slaved dome (rp.md § Dome Slaving) holds the exposure the
+        // same way until its slit has caught up with the pointing —
+        // checked before the gate, so a catch-up wait never blocks a
+        // queued slew.
         let _motion_permit = if self.trains.camera_in_imaging_train(camera_id) {
+            self.dome_slaving.wait_aligned().await?;
             Some(self.motion_gate.shared().await)
         } else {
             None
@@ -664,14 +730,19 @@ impl McpHandler {
         // `docs/plans/archive/image-evaluation-tools.md` and `rp.md` Persistence).
         // Operator-controlled `file_naming_pattern` rendering is reserved
         // until a token resolver lands; for now capture writes
-        // `<uuid8>.fits` regardless of any configured template.
+        // `<uuid8>.<ext>` regardless of any configured template, the
+        // extension following `session.image_format`.
         //
         // Taken from `time_low` rather than by slicing `document_id`: it is
         // the same eight hex digits the canonical form starts with, but the
         // width is guaranteed by the format rather than by a bounds check
         // that could quietly fall back to a different naming scheme.
         let uuid8 = format!("{:08x}", document_uuid.as_fields().0);
-        let mut image_path = format!("{}/{}.fits", self.session_config.data_directory, uuid8);
+        let extension = self.frame_encoding.extension();
+        let mut image_path = format!(
+            "{}/{}.{}",
+            self.session_config.data_directory, uuid8, extension
+        );
 
         let operation_id = Uuid::new_v4().to_string();
         let started_at = chrono::Utc::now();
@@ -695,6 +766,8 @@ impl McpHandler {
         // `exposure_started` above, under a shared `operation_id`. `?`
         // and early `return Err` inside resolve to this block's Result.
         let capture_result: std::result::Result<(), String> = async {
+            // `DATE-OBS` is the exposure's start, not its readout.
+            let exposure_start = chrono::Utc::now();
             cam.start_exposure(duration, true)
                 .await
                 .map_err(|e| format!("failed to start exposure: {e}"))?;
@@ -817,6 +890,22 @@ impl McpHandler {
                 .as_ref()
                 .and_then(|cooling| cooling.rung_for(camera_id));
             let sensor_temperature_c = cam.ccd_temperature().await.ok();
+            let dew_heater_duty_pct = self
+                .dew
+                .as_ref()
+                .map(|dew| dew.duties())
+                .unwrap_or_default();
+            // Readout settings, best-effort like the temperature: they
+            // key the frame to its dark library (gain, offset, binning
+            // and duration must all match). Binning is kept as the read
+            // result because the templated path below cannot render a
+            // name without it.
+            let gain = cam.gain().await.ok();
+            let offset = cam.offset().await.ok();
+            let binning = cam.bin().await.map(|bin| rp_targets::Binning {
+                x: bin[0] as u8,
+                y: bin[1] as u8,
+            });
 
             // Decision 11 (rp.md § Capture Tool Details): `frame_type`
             // stamps the document's `target`/`frame_type` fields.
@@ -824,28 +913,25 @@ impl McpHandler {
             // separate switch — `session.file_naming_pattern` — so a rig
             // with no pattern configured still records what a frame is
             // and what it is of, it just keeps the flat
-            // `<doc_uuid_8>.fits` name (and, having nothing on disk to
+            // `<doc_uuid_8>.<ext>` name (and, having nothing on disk to
             // attribute, derives no progress; rp.md § Progress
             // derivation).
             let mut exposure_target: Option<persistence::ExposureTarget> = None;
             let mut resolved_frame_type: Option<FrameType> = None;
             if let Some(frame_type) = frame_type {
-                let (target_field, target_slug) =
-                    self.resolve_capture_target(target, frame_type).await?;
+                let (target_field, target_slug) = self
+                    .resolve_capture_target(target, frame_type, camera_id)
+                    .await?;
                 exposure_target = Some(target_field);
                 resolved_frame_type = Some(frame_type);
 
                 if let Some(templates) = self.naming_templates.as_ref() {
                     let (filter_name, filter_position) =
                         self.resolve_capture_filter(camera_id, frame_type).await?;
-                    let bin = cam
-                        .bin()
-                        .await
+                    let binning = binning
+                        .as_ref()
+                        .copied()
                         .map_err(|e| format!("capture: failed to read binning: {e}"))?;
-                    let binning = rp_targets::Binning {
-                        x: bin[0] as u8,
-                        y: bin[1] as u8,
-                    };
                     let night_date = self.site.as_ref().map(|site| site.night_date(captured_at));
 
                     let mut fields = naming_template::TemplateFields {
@@ -881,7 +967,7 @@ impl McpHandler {
                     })?;
 
                     image_path = scan_dir
-                        .join(format!("{file_base}.fits"))
+                        .join(format!("{file_base}.{extension}"))
                         .to_string_lossy()
                         .into_owned();
                 }
@@ -969,6 +1055,38 @@ impl McpHandler {
                 None
             };
 
+            let doc = ExposureDocument {
+                id: document_id.clone(),
+                captured_at: captured_at.to_rfc3339(),
+                file_path: image_path.clone(),
+                width: doc_width,
+                height: doc_height,
+                camera_id: Some(camera_id.to_string()),
+                duration: Some(duration),
+                max_adu: captured_max_adu,
+                cooler_setpoint_c,
+                sensor_temperature_c,
+                dew_heater_duty_pct,
+                gain,
+                offset,
+                binning: binning.ok(),
+                optics,
+                target: exposure_target,
+                frame_type: resolved_frame_type,
+                filter: self.document_filter(camera_id, resolved_frame_type).await,
+                sections: serde_json::Map::new(),
+            };
+
+            // The FITS header (rp.md § Capture Tool Details → FITS
+            // header) mirrors the document, plus the best-effort device
+            // reads that have no document field, with the operator's
+            // `session.fits_keywords` layered on top.
+            let readings = self
+                .read_header_readings(camera_id, cam.as_ref(), exposure_start)
+                .await;
+            let standard = persistence::standard_header(&doc, &readings).keywords();
+            let header = rp_fits::writer::merge_keywords(standard, &self.fits_keywords);
+
             // Dispatch on max_adu, collecting pixels directly into the
             // narrowest type each path needs and reusing the same buffer
             // for the cache insert.
@@ -981,50 +1099,38 @@ impl McpHandler {
                         .map(|&p| p.clamp(0, max_adu_i32) as u16)
                         .collect();
                     drop(image_array);
-                    persistence::write_fits_u16(
+                    persistence::write_frame_u16(
                         &image_path,
                         &u16_pixels,
                         width,
                         height,
                         &document_id,
+                        &header,
+                        self.frame_encoding,
                     )
                     .await
-                    .map_err(|e| format!("failed to write FITS file: {e}"))?;
+                    .map_err(|e| format!("failed to write image file: {e}"))?;
                     CachedPixels::from_u16_pixels(u16_pixels, shape)
                 }
                 _ => {
                     let i32_pixels: Vec<i32> = image_array.iter().copied().collect();
                     drop(image_array);
-                    persistence::write_fits_i32(
+                    persistence::write_frame_i32(
                         &image_path,
                         &i32_pixels,
                         width,
                         height,
                         &document_id,
+                        &header,
+                        self.frame_encoding,
                     )
                     .await
-                    .map_err(|e| format!("failed to write FITS file: {e}"))?;
+                    .map_err(|e| format!("failed to write image file: {e}"))?;
                     captured_max_adu
                         .and_then(|m| CachedPixels::from_i32_pixels(i32_pixels, shape, m))
                 }
             };
 
-            let doc = ExposureDocument {
-                id: document_id.clone(),
-                captured_at: captured_at.to_rfc3339(),
-                file_path: image_path.clone(),
-                width: doc_width,
-                height: doc_height,
-                camera_id: Some(camera_id.to_string()),
-                duration: Some(duration),
-                max_adu: captured_max_adu,
-                cooler_setpoint_c,
-                sensor_temperature_c,
-                optics,
-                target: exposure_target,
-                frame_type: resolved_frame_type,
-                sections: serde_json::Map::new(),
-            };
             self.persist_capture_artifact(doc, cached_pixels, captured_max_adu)
                 .await;
 
@@ -1056,14 +1162,16 @@ impl McpHandler {
     /// document's `target` field plus the `TargetSlug` `render` needs
     /// (rp.md § Capture Tool Details, Decision 11). An explicit
     /// `target` always resolves against the store regardless of
-    /// `frame_type` (an unknown slug or an absent store both error).
-    /// Absent `target`: `Light` errors (a Light frame always needs a
-    /// real target), `Dark`/`Flat`/`Bias` fall back to
+    /// `frame_type` (an unknown slug or an absent store both error). A
+    /// mosaic panel slug resolves through its parent row (rp.md § Target
+    /// Store → Mosaics). Absent `target`: `Light` errors (a Light frame
+    /// always needs a real target), `Dark`/`Flat`/`Bias` fall back to
     /// [`rp_vocabulary::FrameType::calibration_slug`].
     async fn resolve_capture_target(
         &self,
         target: Option<&str>,
         frame_type: FrameType,
+        camera_id: &str,
     ) -> std::result::Result<(persistence::ExposureTarget, rp_targets::TargetSlug), String> {
         if let Some(target) = target {
             let slug = rp_targets::TargetSlug::new(target)
@@ -1072,12 +1180,24 @@ impl McpHandler {
                 .target_store
                 .as_ref()
                 .ok_or_else(|| "capture: target store not configured".to_string())?;
-            let found = store
-                .get_target(&slug)
-                .await
-                .map_err(|e| format!("capture: failed to look up target '{target}': {e}"))?
-                .ok_or_else(|| format!("capture: unknown target '{target}'"))?;
-            return Ok((persistence::ExposureTarget::from(&found), slug));
+            let lookup = |slug: rp_targets::TargetSlug| async move {
+                store
+                    .get_target(&slug)
+                    .await
+                    .map_err(|e| format!("capture: failed to look up target '{target}': {e}"))
+            };
+            if let Some(found) = lookup(slug.clone()).await? {
+                return Ok((persistence::ExposureTarget::from(&found), slug));
+            }
+            let unknown = || format!("capture: unknown target '{target}'");
+            let (parent_slug, panel) =
+                rp_targets::MosaicPanel::split_slug(&slug).ok_or_else(unknown)?;
+            let parent = lookup(parent_slug).await?.ok_or_else(unknown)?;
+            let spec = parent
+                .mosaic
+                .filter(|spec| spec.contains(panel))
+                .ok_or_else(unknown)?;
+            return Ok((self.panel_target(&parent, &spec, panel, camera_id), slug));
         }
 
         match frame_type.calibration_slug() {
@@ -1094,6 +1214,7 @@ impl McpHandler {
                     display_name: None,
                     ra_hours: None,
                     dec_degrees: None,
+                    panel: None,
                 };
                 Ok((field, slug))
             }
@@ -1101,6 +1222,47 @@ impl McpHandler {
         }
     }
 
+    /// The exposure document's `target` for one panel of the mosaic
+    /// `parent`: the panel's slug and name, the parent and grid cell, and
+    /// the panel's center as `get_next_target` places it — laid out in
+    /// `camera_id`'s train field of view at the target's effective
+    /// position angle. The center is left out when that field is unknown
+    /// (no train focal length or sensor geometry) rather than recorded as
+    /// the parent's, which no panel is centered on.
+    fn panel_target(
+        &self,
+        parent: &rp_targets::Target,
+        spec: &rp_targets::MosaicSpec,
+        panel: rp_targets::MosaicPanel,
+        camera_id: &str,
+    ) -> persistence::ExposureTarget {
+        let train = self.trains.train_for_camera(camera_id);
+        let fov = train
+            .zip(self.equipment.find_camera(camera_id))
+            .and_then(|(train, camera)| train.field_of_view(&camera));
+        let position_angle_deg = parent
+            .position_angle_degrees
+            .or_else(|| train.and_then(|t| t.default_position_angle_degrees))
+            .unwrap_or(0.0);
+        let center = fov.and_then(|fov| {
+            crate::planner::mosaic::panel_center(parent.coord, spec, panel, fov, position_angle_deg)
+        });
+        persistence::ExposureTarget {
+            slug: panel.slug(&parent.slug).as_str().to_string(),
+            display_name: Some(format!(
+                "{} (panel r{}c{})",
+                parent.display_name, panel.row, panel.column
+            )),
+            ra_hours: center.map(|c| c.ra_hours()),
+            dec_degrees: center.map(|c| c.dec_degrees()),
+            panel: Some(persistence::ExposurePanel {
+                parent: parent.slug.as_str().to_string(),
+                row: panel.row,
+                column: panel.column,
+            }),
+        }
+    }
+
     /// Resolves `do_capture`'s `{filter}`/`{filter_position}` naming-
     /// template values: a live read from the resolved camera's train
     /// filter wheel for `Light`/`Flat` when one is present, else the
@@ -1164,6 +1326,94 @@ impl McpHandler {
         Ok(Some((filter_name, position as u32)))
     }
 
+    /// The exposure document's best-effort `filter`: the live wheel
+    /// filter, following the naming template's rule of never reading
+    /// one for `Dark`/`Bias`. A failed read drops the field, never the
+    /// capture.
+    async fn document_filter(
+        &self,
+        camera_id: &str,
+        frame_type: Option<FrameType>,
+    ) -> Option<String> {
+        if matches!(frame_type, Some(FrameType::Dark | FrameType::Bias)) {
+            return None;
+        }
+        self.live_filter(camera_id)
+            .await
+            .ok()
+            .flatten()
+            .map(|(name, _)| name)
+    }
+
+    /// Best-effort device reads for a frame's FITS header — everything
+    /// `persistence::standard_header` needs that the exposure document
+    /// does not carry. A device that is absent, disconnected or fails
+    /// its read only drops its keywords, never the capture.
+    async fn read_header_readings(
+        &self,
+        camera_id: &str,
+        cam: &dyn ascom_alpaca::api::Camera,
+        exposure_start: chrono::DateTime<chrono::Utc>,
+    ) -> persistence::HeaderReadings {
+        let train = self.trains.train_for_camera(camera_id);
+        let mut readings = persistence::HeaderReadings {
+            exposure_start: Some(exposure_start),
+            site_deg: self
+                .site
+                .map(|site| (site.latitude_degrees, site.longitude_degrees)),
+            instrument: self
+                .equipment
+                .find_camera(camera_id)
+                .map(|entry| entry.config.name.clone())
+                .filter(|name| !name.is_empty()),
+            telescope: train.map(|t| t.id.clone()),
+            ..Default::default()
+        };
+
+        if let Ok(SensorType::RGGB) = cam.sensor_type().await {
+            if let (Ok(x), Ok(y)) = (cam.bayer_offset_x().await, cam.bayer_offset_y().await) {
+                readings.bayer_pattern = Some(bayer_pattern(x, y));
+            }
+        }
+
+        if let Ok((_entry, mount)) = self.resolve_mount() {
+            if let (Ok(ra_hours), Ok(dec_deg)) =
+                (mount.right_ascension().await, mount.declination().await)
+            {
+                readings.pointing_deg = Some((ra_hours * 15.0, dec_deg));
+            }
+            readings.altitude_deg = mount.altitude().await.ok();
+            readings.pier_side = match mount.side_of_pier().await {
+                Ok(PierSide::East) => Some("EAST"),
+                Ok(PierSide::West) => Some("WEST"),
+                _ => None,
+            };
+        }
+
+        if let Some(focuser) = self
+            .trains
+            .focuser_for_camera(camera_id)
+            .and_then(|id| self.equipment.find_focuser(id))
+            .and_then(|entry| entry.device.clone())
+        {
+            readings.focuser_position = focuser.position().await.ok();
+        }
+
+        if let Some(rotator) = train
+            .and_then(|t| {
+                t.devices
+                    .iter()
+                    .find(|d| d.kind == TrainDeviceKind::Rotator)
+            })
+            .and_then(|d| self.equipment.find_rotator(&d.id))
+            .and_then(|entry| entry.device.clone())
+        {
+            readings.rotator_angle_deg = rotator.position().await.ok();
+        }
+
+        readings
+    }
+
     /// Size the predictive `move_focuser` deadline from the focuser's
     /// current position, the requested target, and the configured step rate
     /// (§2.3): `predicted = |target − current| / steps_per_sec`,
@@ -1370,7 +1620,7 @@ impl McpHandler {
         &self,
     ) -> std::result::Result<
         (
-            &crate::equipment::MountEntry,
+            crate::equipment::MountEntry,
             Arc<dyn ascom_alpaca::api::Telescope>,
         ),
         String,
@@ -1468,7 +1718,21 @@ impl McpHandler {
         // before the pre-slew pointing read, so the deadline predicted
         // from it never includes gate wait and stays honest.
         let _motion_permit = self.motion_gate.exclusive("slew").await;
+        self.do_slew_blocking_gated(ra, dec, settle_after, progress)
+            .await
+    }
 
+    /// [`Self::do_slew_blocking`] for a caller that already holds the
+    /// motion gate exclusively — `perform_meridian_flip` keeps the gate
+    /// from the guider stop through the re-slew, and tokio's `RwLock`
+    /// is not reentrant. Same event triple, deadline, and error mapping.
+    pub(crate) async fn do_slew_blocking_gated(
+        &self,
+        ra: f64,
+        dec: f64,
+        settle_after: Duration,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> std::result::Result<(f64, f64), String> {
         let operation_id = Uuid::new_v4().to_string();
         let started_at = chrono::Utc::now();
 
@@ -1588,6 +1852,34 @@ impl McpHandler {
         Ok((actual_ra, actual_dec))
     }
 
+    /// Request a pier-side change through ASCOM `SideOfPier` and poll
+    /// `slewing()` until idle — `perform_meridian_flip`'s fallback when
+    /// the re-slew left the mount on its original pier side. The caller
+    /// holds the motion gate exclusively. The flip re-points the same
+    /// coordinates, so there is no distance to size a prediction from:
+    /// the poll runs under [`SLEW_DEADLINE_FALLBACK`], with the same
+    /// best-effort `abort_slew()` on expiry as [`Self::do_slew_blocking`].
+    pub(crate) async fn force_pier_side_gated(
+        &self,
+        mount: &(dyn ascom_alpaca::api::Telescope + Send + Sync),
+        side: ascom_alpaca::api::telescope::PierSide,
+        progress: Option<&dyn ProgressEmitter>,
+    ) -> std::result::Result<(), String> {
+        debug!(?side, "forcing mount pier side");
+        mount
+            .set_side_of_pier(side)
+            .await
+            .map_err(|e| format!("failed to set side_of_pier: {e}"))?;
+        match poll_slewing_until_idle(mount, SLEW_DEADLINE_FALLBACK, progress).await {
+            Ok(()) => Ok(()),
+            Err(PollIdleError::Timeout) => {
+                let _ = mount.abort_slew().await;
+                Err("timeout waiting for mount to settle after the pier-side change".to_string())
+            }
+            Err(PollIdleError::Read(e)) => Err(format!("error polling mount slewing: {e}")),
+        }
+    }
+
     /// Size the park deadline (§2.2). rp can't read the mount's park
     /// coordinates — the generic Alpaca `Telescope` trait exposes no
     /// park-position getter — so the deadline is the worst-case full-axis
@@ -2058,6 +2350,48 @@ pub(crate) struct DoPlateSolveOutput {
 // Free helpers
 // ---------------------------------------------------------------------------
 
+/// Read a frame for the `*_via_path` analysis tools as the cache would
+/// hold it, minus the `max_adu` narrowing (no camera context here): an
+/// integer frame as `I32`, a float frame as `F32`.
+fn read_frame_for_analysis(path: &str) -> crate::error::Result<CachedPixels> {
+    let (pixels, width, height) = persistence::read_frame_typed(path)?;
+    CachedPixels::from_frame_pixels(pixels, (width, height), None).ok_or_else(|| {
+        crate::error::RpError::Imaging(format!("FITS shape mismatch: {width}x{height}"))
+    })
+}
+
+/// `compute_image_stats` result. Integer frames report whole ADU; a
+/// float frame (e.g. a normalized [0, 1] master) reports in its own
+/// units, so truncating it to ADU would read as all zeros.
+#[derive(Debug, Clone)]
+pub(crate) enum FrameStats {
+    Int(imaging::ImageStats),
+    Float(imaging::FloatImageStats),
+}
+
+impl FrameStats {
+    /// The tool payload and `image_stats` section. Same keys for both
+    /// kinds; a float frame's values are JSON floats.
+    pub(crate) fn payload(&self) -> serde_json::Value {
+        match self {
+            Self::Int(s) => serde_json::json!({
+                "median_adu": s.median_adu,
+                "mean_adu": s.mean_adu,
+                "min_adu": s.min_adu,
+                "max_adu": s.max_adu,
+                "pixel_count": s.pixel_count,
+            }),
+            Self::Float(s) => serde_json::json!({
+                "median_adu": s.median,
+                "mean_adu": s.mean,
+                "min_adu": s.min,
+                "max_adu": s.max,
+                "pixel_count": s.pixel_count,
+            }),
+        }
+    }
+}
+
 pub(crate) fn stats_outcome<T: imaging::Pixel>(
     view: ndarray::ArrayView2<T>,
 ) -> crate::error::Result<imaging::ImageStats> {
@@ -2067,13 +2401,31 @@ pub(crate) fn stats_outcome<T: imaging::Pixel>(
     // path doesn't pay the second n × 4 bytes that an immutable slice
     // signature would force (caller copy + kernel-internal clone).
     // Negative pixels are clamped to 0 inside `compute_stats`, so the
-    // `to_u32() as i32` round-trip is safe for realistic camera
-    // ranges (u16 cameras + i32 scientific HDR ≤ i32::MAX).
-    let mut pixels: Vec<i32> = view.iter().map(|p| p.to_u32().cast_signed()).collect();
+    // `to_u32()` → `i32` round-trip is safe for realistic camera
+    // ranges (u16 cameras + i32 scientific HDR ≤ i32::MAX). Float
+    // frames go through [`float_stats_outcome`] instead.
+    let mut pixels: Vec<i32> = view
+        .iter()
+        .map(|p| i32::try_from(p.to_u32()).unwrap_or(i32::MAX))
+        .collect();
     imaging::compute_stats(&mut pixels)
         .ok_or_else(|| crate::error::RpError::Imaging("image has no pixels".into()))
 }
 
+/// [`stats_outcome`] for a float frame, in f64 so fractional values
+/// survive. Undefined (NaN) pixels are left out.
+pub(crate) fn float_stats_outcome(
+    view: ndarray::ArrayView2<f32>,
+) -> crate::error::Result<imaging::FloatImageStats> {
+    let mut pixels: Vec<f64> = view
+        .iter()
+        .map(|&p| f64::from(p))
+        .filter(|p| p.is_finite())
+        .collect();
+    imaging::compute_stats_f64(&mut pixels)
+        .ok_or_else(|| crate::error::RpError::Imaging("image has no pixels".into()))
+}
+
 pub(crate) fn clip_outcome<T: imaging::Pixel>(
     view: ndarray::ArrayView2<T>,
     params: &ResolvedClipParams,
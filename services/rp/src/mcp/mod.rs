@@ -108,7 +108,7 @@ macro_rules! tool_error {
 /// early-return a `tool_error` `CallToolResult` from the enclosing function.
 ///
 /// Usage: `let (entry, device) = resolve_device!(self, find_camera, &id, "camera");`
-/// (the `id` argument is forwarded into `EquipmentRegistry::find_*`,
+/// (the `id` argument is forwarded into `SharedEquipment::find_*`,
 /// which take `&str` — every real call site passes `&params.camera_id`,
 /// `&camera_id`, etc.)
 macro_rules! resolve_device {
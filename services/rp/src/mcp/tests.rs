@@ -8,12 +8,19 @@ use super::built_in::auto_focus::*;
 use super::built_in::camera::*;
 use super::built_in::center_on_target::*;
 use super::built_in::cover_calibrator::*;
+use super::built_in::dome::*;
+use super::built_in::equipment::*;
 use super::built_in::filter_wheel::*;
+use super::built_in::focus_model::*;
 use super::built_in::focuser::*;
 use super::built_in::imaging::*;
+use super::built_in::meridian_flip::*;
 use super::built_in::mount::*;
+use super::built_in::observing_conditions::*;
 use super::built_in::planner::*;
 use super::built_in::plate_solve::*;
+use super::built_in::session::*;
+use super::built_in::switch::*;
 use super::handler::McpHandler;
 use crate::persistence::{self, CachedPixels, ExposureDocument, ImageCache};
 use crate::session::SessionConfig;
@@ -102,6 +109,12 @@ struct MockCamera {
     /// and errors thereafter — drives the aborted-idle re-check's
     /// read-error arm.
     fail_image_ready_after: Option<u32>,
+    /// When set, `ccd_temperature` reports it; otherwise the read is
+    /// not implemented, as on a camera without a sensor probe.
+    ccd_temperature_c: Option<f64>,
+    /// Counts `abort_exposure` calls, which always succeed — the camera
+    /// reports `Exposing` unless a state knob says otherwise.
+    abort_exposure_calls: std::sync::atomic::AtomicU32,
 }
 
 impl_mock_device!(MockCamera);
@@ -244,6 +257,24 @@ impl ascom_alpaca::api::Camera for MockCamera {
     async fn set_start_y(&self, _start_y: u32) -> ascom_alpaca::ASCOMResult<()> {
         Ok(())
     }
+
+    async fn bin_x(&self) -> ascom_alpaca::ASCOMResult<u8> {
+        Ok(1)
+    }
+
+    async fn bin_y(&self) -> ascom_alpaca::ASCOMResult<u8> {
+        Ok(1)
+    }
+
+    async fn ccd_temperature(&self) -> ascom_alpaca::ASCOMResult<f64> {
+        self.ccd_temperature_c.ok_or(ASCOMError::NOT_IMPLEMENTED)
+    }
+
+    async fn abort_exposure(&self) -> ascom_alpaca::ASCOMResult<()> {
+        self.abort_exposure_calls
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        Ok(())
+    }
 }
 
 // -----------------------------------------------------------------------
@@ -341,16 +372,20 @@ struct MockFilterWheel {
     fail_set_position: bool,
     fail_position_poll: bool,
     report_moving: bool,
+    /// The slot `position` reports; `set_position` moves it. Starts at
+    /// slot 0.
+    slot: AtomicUsize,
 }
 
 impl_mock_device!(MockFilterWheel);
 
 #[async_trait::async_trait]
 impl ascom_alpaca::api::FilterWheel for MockFilterWheel {
-    async fn set_position(&self, _position: usize) -> ascom_alpaca::ASCOMResult<()> {
+    async fn set_position(&self, position: usize) -> ascom_alpaca::ASCOMResult<()> {
         if self.fail_set_position {
             return Err(ASCOMError::invalid_operation("wheel stuck"));
         }
+        self.slot.store(position, Ordering::SeqCst);
         Ok(())
     }
 
@@ -361,7 +396,7 @@ impl ascom_alpaca::api::FilterWheel for MockFilterWheel {
         if self.report_moving {
             return Ok(None);
         }
-        Ok(Some(0))
+        Ok(Some(self.slot.load(Ordering::SeqCst)))
     }
 
     async fn names(&self) -> ascom_alpaca::ASCOMResult<Vec<String>> {
@@ -813,7 +848,7 @@ impl ascom_alpaca::api::Telescope for MockTelescope {
 
 fn test_handler(registry: crate::equipment::EquipmentRegistry) -> McpHandler {
     McpHandler::new(
-        Arc::new(registry),
+        crate::equipment::SharedEquipment::new(registry),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: std::env::temp_dir()
@@ -1035,6 +1070,7 @@ fn mount_registry(
                 settle_after_slew,
                 slew_rate_arcsec_per_sec: Default::default(),
                 guiding: None,
+                meridian_flip: Default::default(),
                 auth: None,
             },
             device: Some(mount),
@@ -1082,6 +1118,7 @@ fn disconnected_mount_registry() -> crate::equipment::EquipmentRegistry {
                 settle_after_slew: None,
                 slew_rate_arcsec_per_sec: Default::default(),
                 guiding: None,
+                meridian_flip: Default::default(),
                 auth: None,
             },
             device: None,
@@ -1503,7 +1540,7 @@ async fn test_capture_write_fits_fails() {
     // another file fails on all OSes.
     let blocker = tempfile::NamedTempFile::new().unwrap();
     let handler = McpHandler::new(
-        Arc::new(registry),
+        crate::equipment::SharedEquipment::new(registry),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: blocker.path().to_string_lossy().to_string(),
@@ -1554,7 +1591,7 @@ async fn test_capture_caches_i32_when_max_adu_above_u16_max() {
     let temp = tempfile::tempdir().unwrap();
     let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
     let handler = McpHandler::new(
-        Arc::new(registry),
+        crate::equipment::SharedEquipment::new(registry),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: temp.path().to_string_lossy().to_string(),
@@ -1610,7 +1647,7 @@ async fn test_capture_filename_uses_uuid8_suffix() {
     let temp = tempfile::tempdir().unwrap();
     let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
     let handler = McpHandler::new(
-        Arc::new(camera_registry(Arc::new(cam))),
+        crate::equipment::SharedEquipment::new(camera_registry(Arc::new(cam))),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: temp.path().to_string_lossy().to_string(),
@@ -1656,6 +1693,61 @@ async fn test_capture_filename_uses_uuid8_suffix() {
     );
 }
 
+#[tokio::test]
+async fn test_capture_writes_xisf_when_configured() {
+    // `session.image_format = "xisf"` changes the extension and the
+    // encoding, not the uuid8 naming contract; the frame reads back and
+    // carries its DOC_ID like a FITS one.
+    let cam = MockCamera::default();
+    let temp = tempfile::tempdir().unwrap();
+    let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
+    let handler = McpHandler::new(
+        crate::equipment::SharedEquipment::new(camera_registry(Arc::new(cam))),
+        Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
+        SessionConfig {
+            data_directory: temp.path().to_string_lossy().to_string(),
+        },
+        cache,
+        None,
+    )
+    .with_frame_encoding(crate::config::FrameEncoding::Xisf(
+        crate::config::XisfCompression::Zlib,
+    ));
+    let result = handler
+        .capture_inner(
+            CaptureParams {
+                target: None,
+                frame_type: None,
+                camera_id: Some("cam".into()),
+                train_id: None,
+                duration: Duration::from_millis(100),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let text = result
+        .content
+        .first()
+        .and_then(|c| c.as_text())
+        .map(|tc| tc.text.clone())
+        .unwrap();
+    let json: serde_json::Value = serde_json::from_str(&text).unwrap();
+    let doc_id = json["document_id"].as_str().unwrap();
+    let image_path = json["image_path"].as_str().unwrap();
+    assert!(
+        image_path.ends_with(&format!("/{}.xisf", &doc_id[..8])),
+        "unexpected image_path {image_path}"
+    );
+    assert_eq!(
+        persistence::read_frame_doc_id(image_path)
+            .unwrap()
+            .as_deref(),
+        Some(doc_id)
+    );
+    assert!(persistence::read_frame_pixels(image_path).is_ok());
+}
+
 // -----------------------------------------------------------------------
 // Train addressing — capture / set_filter / center_on_target
 // -----------------------------------------------------------------------
@@ -1943,6 +2035,70 @@ async fn center_on_target_addressed_by_train_resolves_before_mount_checks() {
     assert_tool_error(result, "no mount configured");
 }
 
+fn flip_params(max_attempts: usize) -> PerformMeridianFlipParams {
+    PerformMeridianFlipParams {
+        camera_id: Some("cam".into()),
+        train_id: None,
+        ra: Some(1.0),
+        dec: Some(10.0),
+        duration: Some(Duration::from_millis(100)),
+        tolerance_arcsec: Some(60.0),
+        max_attempts: Some(max_attempts),
+        recalibrate: None,
+    }
+}
+
+#[tokio::test]
+async fn perform_meridian_flip_validates_centering_params_before_resolving_devices() {
+    // No camera or mount is configured: the re-center parameters are
+    // checked first so a doomed flip never moves the mount.
+    let handler = test_handler(empty_registry());
+    let result = handler
+        .perform_meridian_flip_inner(flip_params(0), None)
+        .await;
+    assert_tool_error(
+        result,
+        "perform_meridian_flip: max_attempts must be positive",
+    );
+}
+
+#[tokio::test]
+async fn perform_meridian_flip_refuses_a_wait_beyond_max_wait() {
+    // MockTelescope reports LST 0 h; pointing at RA 2 h puts the mount
+    // two hours east of the meridian, far past the 30 min default.
+    let mount = MockTelescope {
+        ra_value: 2.0,
+        ..Default::default()
+    };
+    let handler = test_handler(camera_mount_registry(
+        Arc::new(MockCamera::default()),
+        Arc::new(mount),
+    ));
+    let mut rx = handler.event_bus.subscribe();
+    let result = handler
+        .perform_meridian_flip_inner(flip_params(3), None)
+        .await;
+    assert_tool_error(result, "beyond meridian_flip.max_wait");
+    assert_no_more_events(&mut rx).await;
+}
+
+#[tokio::test]
+async fn perform_meridian_flip_refuses_a_mount_without_side_of_pier() {
+    // Hour angle 0 h is 5 min short of the default offset — inside
+    // max_wait — but MockTelescope leaves SideOfPier unimplemented,
+    // so the flip cannot be verified and must not start.
+    let handler = test_handler(camera_mount_registry(
+        Arc::new(MockCamera::default()),
+        Arc::new(MockTelescope::default()),
+    ));
+    let mut rx = handler.event_bus.subscribe();
+    let result = handler
+        .perform_meridian_flip_inner(flip_params(3), None)
+        .await;
+    assert_tool_error(result, "failed to read mount side_of_pier");
+    assert_no_more_events(&mut rx).await;
+}
+
 // -----------------------------------------------------------------------
 // capture — optics block in sidecar
 // -----------------------------------------------------------------------
@@ -1954,7 +2110,7 @@ async fn capture_and_read_sidecar(
     let temp = tempfile::tempdir().unwrap();
     let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
     let handler = McpHandler::new(
-        Arc::new(registry),
+        crate::equipment::SharedEquipment::new(registry),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: temp.path().to_string_lossy().to_string(),
@@ -2120,7 +2276,7 @@ async fn test_persist_capture_artifact_skips_cache_on_sidecar_failure() {
 
     let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
     let handler = McpHandler::new(
-        Arc::new(crate::equipment::EquipmentRegistry {
+        crate::equipment::SharedEquipment::new(crate::equipment::EquipmentRegistry {
             safety_monitors: vec![],
             cameras: vec![],
             filter_wheels: vec![],
@@ -2140,6 +2296,7 @@ async fn test_persist_capture_artifact_skips_cache_on_sidecar_failure() {
     let doc = ExposureDocument {
         target: None,
         frame_type: None,
+        filter: None,
         id: "doc-fail-1".to_string(),
         captured_at: "2026-04-30T00:00:00Z".to_string(),
         file_path: blocker.join("x.fits").to_string_lossy().into_owned(),
@@ -2150,6 +2307,10 @@ async fn test_persist_capture_artifact_skips_cache_on_sidecar_failure() {
         max_adu: Some(65535),
         cooler_setpoint_c: None,
         sensor_temperature_c: None,
+        dew_heater_duty_pct: Default::default(),
+        gain: None,
+        offset: None,
+        binning: None,
         optics: None,
         sections: serde_json::Map::new(),
     };
@@ -2366,7 +2527,7 @@ async fn test_calibrator_off_polling_error() {
 
 #[tokio::test]
 async fn test_compute_image_stats_bad_fits() {
-    // Write a non-FITS file so read_fits_pixels fails inside spawn_blocking
+    // Write a non-FITS file so read_frame_typed fails inside spawn_blocking
     let dir = tempfile::tempdir().unwrap();
     let bad_file = dir.path().join("bad.fits");
     std::fs::write(&bad_file, b"not a fits file").unwrap();
@@ -2439,6 +2600,7 @@ async fn test_compute_image_stats_persists_section_via_document_id() {
     let doc = ExposureDocument {
         target: None,
         frame_type: None,
+        filter: None,
         id: document_id.clone(),
         captured_at: "2026-05-08T00:00:00Z".to_string(),
         file_path: file_path.clone(),
@@ -2449,6 +2611,10 @@ async fn test_compute_image_stats_persists_section_via_document_id() {
         max_adu: Some(65535),
         cooler_setpoint_c: None,
         sensor_temperature_c: None,
+        dew_heater_duty_pct: Default::default(),
+        gain: None,
+        offset: None,
+        binning: None,
         optics: None,
         sections: serde_json::Map::new(),
     };
@@ -2466,7 +2632,7 @@ async fn test_compute_image_stats_persists_section_via_document_id() {
     );
 
     let handler = McpHandler::new(
-        Arc::new(crate::equipment::EquipmentRegistry {
+        crate::equipment::SharedEquipment::new(crate::equipment::EquipmentRegistry {
             safety_monitors: vec![],
             cameras: vec![],
             filter_wheels: vec![],
@@ -2519,6 +2685,101 @@ async fn test_compute_image_stats_persists_section_via_document_id() {
     assert_eq!(section["median_adu"], 250);
 }
 
+#[tokio::test]
+async fn test_compute_image_stats_keeps_float_frames_fractional() {
+    // A normalized [0, 1] master (the F32 cache variant) must report
+    // fractional stats, not values truncated to whole ADU (all zeros).
+    let temp = tempfile::tempdir().unwrap();
+    let cache = ImageCache::new(64, 4, temp.path().to_path_buf());
+
+    // The NaN is left out: five defined pixels, median 0.25, mean 1.75 / 5.
+    let pixel_buf: Vec<f32> = vec![0.0, 0.25, 0.5, 1.0, f32::NAN, 0.0];
+    let cached_pixels = CachedPixels::from_f32_pixels(pixel_buf, (2, 3)).unwrap();
+
+    let document_id = "doc-image-stats-f32".to_string();
+    let uuid8 = "doc-imgf"; // 8-char-stable suffix for the on-disk basename.
+    let file_path = temp
+        .path()
+        .join(format!("{uuid8}.fits"))
+        .to_string_lossy()
+        .into_owned();
+
+    let doc = ExposureDocument {
+        target: None,
+        frame_type: None,
+        filter: None,
+        id: document_id.clone(),
+        captured_at: "2026-05-08T00:00:00Z".to_string(),
+        file_path: file_path.clone(),
+        width: 2,
+        height: 3,
+        camera_id: Some("cam".into()),
+        duration: Some(Duration::from_millis(100)),
+        max_adu: Some(65535),
+        cooler_setpoint_c: None,
+        sensor_temperature_c: None,
+        dew_heater_duty_pct: Default::default(),
+        gain: None,
+        offset: None,
+        binning: None,
+        optics: None,
+        sections: serde_json::Map::new(),
+    };
+
+    cache.insert(
+        document_id.clone(),
+        crate::persistence::CachedImage::new(
+            cached_pixels,
+            2,
+            3,
+            std::path::PathBuf::from(&file_path),
+            65535,
+            doc,
+        ),
+    );
+
+    let handler = McpHandler::new(
+        crate::equipment::SharedEquipment::new(crate::equipment::EquipmentRegistry {
+            safety_monitors: vec![],
+            cameras: vec![],
+            filter_wheels: vec![],
+            cover_calibrators: vec![],
+            focusers: vec![],
+            mount: None,
+            ..Default::default()
+        }),
+        Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
+        SessionConfig {
+            data_directory: temp.path().to_string_lossy().to_string(),
+        },
+        cache.clone(),
+        None,
+    );
+
+    let call_result = handler
+        .compute_image_stats(Parameters(ComputeImageStatsParams {
+            document_id: Some(document_id.clone()),
+            image_path: None,
+        }))
+        .await
+        .unwrap();
+    assert!(!call_result.is_error.unwrap_or(false));
+
+    let payload_text = call_result
+        .content
+        .first()
+        .and_then(|c| c.as_text())
+        .map(|tc| tc.text.clone())
+        .unwrap();
+    let payload: serde_json::Value = serde_json::from_str(&payload_text).unwrap();
+    let field = |key: &str| payload[key].as_f64().unwrap();
+    assert_eq!(payload["pixel_count"], 5);
+    assert!(field("min_adu").abs() < 1e-9);
+    assert!((field("max_adu") - 1.0).abs() < 1e-9);
+    assert!((field("median_adu") - 0.25).abs() < 1e-9);
+    assert!((field("mean_adu") - 0.35).abs() < 1e-9);
+}
+
 // -----------------------------------------------------------------------
 // set_filter — filter not found
 // -----------------------------------------------------------------------
@@ -3598,7 +3859,7 @@ async fn test_abort_slew_alpaca_error() {
 
 fn test_handler_with_site(site: rp_ephemeris::Site) -> McpHandler {
     McpHandler::new(
-        Arc::new(empty_registry()),
+        crate::equipment::SharedEquipment::new(empty_registry()),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: std::env::temp_dir()
@@ -3973,6 +4234,11 @@ fn test_naming_templates() -> crate::config::naming_template::NamingTemplates {
             // `file_naming_pattern` is set.
             directory_pattern: None,
             session_state_file: String::new(),
+            focus_model_file: String::new(),
+            fits_keywords: Default::default(),
+            fits_compression: Default::default(),
+            image_format: Default::default(),
+            xisf_compression: Default::default(),
         },
     )
     .unwrap()
@@ -4005,6 +4271,7 @@ fn store_goal(filter: &str, secs: u64, desired_count: u32) -> rp_targets::Acquis
         binning: rp_targets::Binning { x: 1, y: 1 },
         exposure_duration: Duration::from_secs(secs),
         desired_count,
+        moon_avoidance: None,
     }
 }
 
@@ -4029,6 +4296,7 @@ fn test_store_target(
         magnitude: None,
         size_arcmin: None,
         position_angle_degrees: None,
+        mosaic: None,
         priority: 0,
         active: true,
         goals,
@@ -4161,79 +4429,330 @@ async fn get_next_target_rotates_the_plan_and_ends_when_goals_are_met() {
     assert!(v["target"].is_null());
 }
 
+/// A 2×1 mosaic of "Test Field" with one Red goal, on a site-configured
+/// handler. `with_field` adds the fixture camera (3.76 µm, 1024 px) and a
+/// 1000 mm imaging train over it, giving the planner a field of view to
+/// place the panels in.
+async fn handler_with_mosaic_target(with_field: bool) -> (McpHandler, tempfile::TempDir) {
+    let mut target = test_store_target(
+        "Test Field",
+        0.0,
+        0.0,
+        Some(-90.0),
+        vec![store_goal("Red", 120, 1)],
+    );
+    target.mosaic = Some(rp_targets::MosaicSpec {
+        columns: 2,
+        rows: 1,
+        overlap_percent: 10.0,
+    });
+    let (h, dir) = handler_with_store_target(target).await;
+    if !with_field {
+        return (h, dir);
+    }
+    let mut h = h.with_trains(cam_trains(1000.0));
+    h.equipment =
+        crate::equipment::SharedEquipment::new(camera_registry(Arc::new(MockCamera::default())));
+    (h, dir)
+}
+
 #[tokio::test]
-async fn a_rejected_frame_does_not_end_the_session() {
-    // Same loop, but the only frame is graded out: `total` advances and
-    // `good` does not, so the goal is unmet and the target stays in the
-    // rotation (rp.md § Decision Logic — exhaustion is judged on good).
-    let (h, store_dir) = handler_with_store_target_and_defaults(
-        test_store_target(
-            "Test Field",
-            0.0,
-            0.0,
-            Some(-90.0),
-            vec![store_goal("Red", 120, 1)],
-        ),
-        crate::config::TargetStoreConfig {
-            default_grading: Some(rp_targets::GradingThresholds {
-                max_hfr_pixels: Some(3.0),
-                ..Default::default()
-            }),
-            ..Default::default()
-        },
-    )
-    .await;
+async fn get_next_target_walks_mosaic_panels_as_sibling_targets() {
+    let (h, store_dir) = handler_with_mosaic_target(true).await;
+    let next = || {
+        h.get_next_target(Parameters(GetNextTargetParams {
+            time: None,
+            train_id: None,
+        }))
+    };
+    let v = ok_json(next().await);
+    let first = v["target"]["name"].as_str().unwrap().to_string();
+    assert!(
+        first == "test-field-r1c1" || first == "test-field-r1c2",
+        "a panel, not the parent: {v}"
+    );
+    // The panel is placed in the train's field, off the parent's center.
+    let ra = v["target"]["coord"]["ra_hours"].as_f64().unwrap();
+    assert!(ra != 0.0, "{v}");
+
     seed_frame(
         &store_dir,
-        "test-field/2026-07-30/Light/test-field_Red_1x1_0001_2m_fpos_1_-10C_aaaaaaa1.fits",
-        Some(serde_json::json!({ "hfr": 9.9 })),
+        &format!("{first}/2026-07-30/Light/{first}_Red_1x1_0001_2m_fpos_1_-10C_aaaaaaa1.fits"),
+        None,
     );
+    let v = ok_json(next().await);
+    let second = v["target"]["name"].as_str().unwrap().to_string();
+    assert_ne!(
+        second, first,
+        "the finished panel hands over to its sibling"
+    );
+
+    // Each panel is tracked under its own slug.
+    let progress = ok_json(
+        h.get_session_progress(Parameters(GetSessionProgressParams {}))
+            .await,
+    );
+    assert_eq!(progress["progress"][&first][0]["good"], 1);
+    assert_eq!(progress["progress"][&second][0]["good"], 0);
+    assert!(progress["progress"]["test-field"].is_null());
+
+    // record_exposure accepts a panel slug.
     let v = ok_json(
-        h.get_next_target(Parameters(GetNextTargetParams {
-            time: None,
-            train_id: None,
+        h.record_exposure(Parameters(RecordExposureParams {
+            target: second.clone(),
+            filter: Some("Red".into()),
         }))
         .await,
     );
-    assert_eq!(v["reason"], "best_transiting_candidate");
-    assert_eq!(v["exposure"]["filter"], "Red");
-}
+    assert_eq!(v["target"], second);
 
-#[tokio::test]
-async fn get_target_status_reports_progress_for_a_configured_target() {
-    // Stored under its catalog name so the same string both resolves
-    // coordinates and (derived to `m-31`) matches the progress map.
-    let (h, store_dir) = handler_with_store_target(test_store_target(
-        "M 31",
-        0.7123,
-        41.269,
-        None,
-        vec![store_goal("Red", 120, 4)],
-    ))
-    .await;
     seed_frame(
         &store_dir,
-        "m-31/2026-07-30/Light/m-31_Red_1x1_0001_2m_fpos_1_-10C_aaaaaaa1.fits",
+        &format!("{second}/2026-07-30/Light/{second}_Red_1x1_0001_2m_fpos_1_-10C_bbbbbbb1.fits"),
         None,
     );
+    let v = ok_json(next().await);
+    assert_eq!(v["reason"], "end_of_session", "every panel's goals are met");
+}
+
+#[tokio::test]
+async fn a_mosaic_without_a_field_of_view_is_not_a_candidate() {
+    let (h, _store_dir) = handler_with_mosaic_target(false).await;
     let v = ok_json(
-        h.get_target_status(Parameters(GetTargetStatusParams {
-            target_name: Some("M 31".into()),
-            ra: None,
-            dec: None,
+        h.get_next_target(Parameters(GetNextTargetParams {
             time: None,
+            train_id: None,
         }))
         .await,
     );
-    assert_eq!(
-        v["progress"][0],
-        serde_json::json!({
-            "filter": "Red", "binning": "1x1", "exposure_duration": "2m",
-            "desired_count": 4, "good": 1, "total": 1
-        })
-    );
-    // A case-variant spelling resolves through the catalog to the
-    // same canonical name, and the progress lookup must follow it —
+    assert!(v["target"].is_null(), "{v}");
+    assert_eq!(v["reason"], "no_targets_configured");
+}
+
+#[tokio::test]
+async fn capturing_a_mosaic_panel_stamps_the_panel_and_moves_its_progress() {
+    let mut target = test_store_target(
+        "Test Field",
+        0.0,
+        0.0,
+        Some(-90.0),
+        // Unfiltered: the fixture train has no filter wheel.
+        vec![store_goal("", 120, 2)],
+    );
+    target.mosaic = Some(rp_targets::MosaicSpec {
+        columns: 2,
+        rows: 1,
+        overlap_percent: 10.0,
+    });
+    let (h, _store_dir) = handler_with_store_target(target).await;
+    let mut h = h.with_trains(cam_trains(1000.0));
+    h.equipment = crate::equipment::SharedEquipment::new(camera_registry(Arc::new(MockCamera {
+        ccd_temperature_c: Some(-10.0),
+        ..Default::default()
+    })));
+
+    let json = ok_json(
+        h.capture_inner(
+            CaptureParams {
+                target: Some("test-field-r1c2".into()),
+                frame_type: Some(rp_vocabulary::FrameType::Light),
+                camera_id: Some("cam".into()),
+                train_id: None,
+                duration: Duration::from_secs(120),
+            },
+            None,
+        )
+        .await,
+    );
+    assert!(
+        json["image_path"]
+            .as_str()
+            .unwrap()
+            .contains("test-field-r1c2"),
+        "{json}"
+    );
+
+    // The document names the panel, its parent and grid cell, and the
+    // panel's center — west of the parent's for the right-hand column.
+    let doc = h
+        .image_cache
+        .resolve_document(json["document_id"].as_str().unwrap())
+        .await
+        .unwrap();
+    let stamped = doc.target.unwrap();
+    assert_eq!(stamped.slug, "test-field-r1c2");
+    assert_eq!(
+        stamped.panel,
+        Some(crate::persistence::ExposurePanel {
+            parent: "test-field".to_string(),
+            row: 1,
+            column: 2,
+        })
+    );
+    let ra = stamped.ra_hours.unwrap();
+    assert!(ra > 23.0, "west of the parent's RA 0h: {ra}");
+    assert!(stamped.dec_degrees.unwrap().abs() < 1e-9);
+
+    let progress = ok_json(
+        h.get_session_progress(Parameters(GetSessionProgressParams {}))
+            .await,
+    );
+    assert_eq!(progress["progress"]["test-field-r1c2"][0]["good"], 1);
+    assert_eq!(progress["progress"]["test-field-r1c1"][0]["good"], 0);
+}
+
+#[tokio::test]
+async fn capture_rejects_a_panel_outside_the_mosaic_grid() {
+    let (h, _store_dir) = handler_with_mosaic_target(true).await;
+    let result = h
+        .capture_inner(
+            CaptureParams {
+                target: Some("test-field-r2c1".into()),
+                frame_type: Some(rp_vocabulary::FrameType::Light),
+                camera_id: Some("cam".into()),
+                train_id: None,
+                duration: Duration::from_secs(120),
+            },
+            None,
+        )
+        .await;
+    assert_tool_error(result, "unknown target 'test-field-r2c1'");
+}
+
+#[tokio::test]
+async fn preview_night_schedule_errors_when_site_absent() {
+    let h = test_handler(empty_registry());
+    let r = h
+        .preview_night_schedule(Parameters(PreviewNightScheduleParams {
+            date: None,
+            time: None,
+            frame_overhead_secs: None,
+        }))
+        .await;
+    assert_tool_error(r, "site not configured");
+}
+
+#[tokio::test]
+async fn preview_night_schedule_rejects_a_negative_overhead() {
+    let h = test_handler_with_site(test_site());
+    let r = h
+        .preview_night_schedule(Parameters(PreviewNightScheduleParams {
+            date: Some("2026-11-01".into()),
+            time: None,
+            frame_overhead_secs: Some(-1.0),
+        }))
+        .await;
+    assert_tool_error(r, "frame_overhead_secs");
+}
+
+#[tokio::test]
+async fn preview_night_schedule_forecasts_the_plan_without_touching_disk() {
+    let (h, _store_dir) = handler_with_planned_target().await;
+    let v = ok_json(
+        h.preview_night_schedule(Parameters(PreviewNightScheduleParams {
+            date: Some("2026-11-01".into()),
+            time: None,
+            frame_overhead_secs: Some(10.0),
+        }))
+        .await,
+    );
+    let imaging: Vec<&serde_json::Value> = v["blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|b| b["frames"].as_u64() > Some(0))
+        .collect();
+    assert_eq!(imaging.len(), 2, "got: {v}");
+    assert_eq!(imaging[0]["target"], "test-field");
+    assert_eq!(imaging[0]["filter"], "Red");
+    assert_eq!(imaging[1]["filter"], "Blue");
+    assert_eq!(v["goals"][0]["expected_frames"], 1);
+    assert_eq!(v["goals"][1]["expected_frames"], 1);
+
+    // A preview is a simulation: the live recommendation is unchanged.
+    let next = ok_json(
+        h.get_next_target(Parameters(GetNextTargetParams {
+            time: None,
+            train_id: None,
+        }))
+        .await,
+    );
+    assert_eq!(next["exposure"]["filter"], "Red");
+}
+
+#[tokio::test]
+async fn a_rejected_frame_does_not_end_the_session() {
+    // Same loop, but the only frame is graded out: `total` advances and
+    // `good` does not, so the goal is unmet and the target stays in the
+    // rotation (rp.md § Decision Logic — exhaustion is judged on good).
+    let (h, store_dir) = handler_with_store_target_and_defaults(
+        test_store_target(
+            "Test Field",
+            0.0,
+            0.0,
+            Some(-90.0),
+            vec![store_goal("Red", 120, 1)],
+        ),
+        crate::config::TargetStoreConfig {
+            default_grading: Some(rp_targets::GradingThresholds {
+                max_hfr_pixels: Some(3.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+    )
+    .await;
+    seed_frame(
+        &store_dir,
+        "test-field/2026-07-30/Light/test-field_Red_1x1_0001_2m_fpos_1_-10C_aaaaaaa1.fits",
+        Some(serde_json::json!({ "hfr": 9.9 })),
+    );
+    let v = ok_json(
+        h.get_next_target(Parameters(GetNextTargetParams {
+            time: None,
+            train_id: None,
+        }))
+        .await,
+    );
+    assert_eq!(v["reason"], "best_transiting_candidate");
+    assert_eq!(v["exposure"]["filter"], "Red");
+}
+
+#[tokio::test]
+async fn get_target_status_reports_progress_for_a_configured_target() {
+    // Stored under its catalog name so the same string both resolves
+    // coordinates and (derived to `m-31`) matches the progress map.
+    let (h, store_dir) = handler_with_store_target(test_store_target(
+        "M 31",
+        0.7123,
+        41.269,
+        None,
+        vec![store_goal("Red", 120, 4)],
+    ))
+    .await;
+    seed_frame(
+        &store_dir,
+        "m-31/2026-07-30/Light/m-31_Red_1x1_0001_2m_fpos_1_-10C_aaaaaaa1.fits",
+        None,
+    );
+    let v = ok_json(
+        h.get_target_status(Parameters(GetTargetStatusParams {
+            target_name: Some("M 31".into()),
+            ra: None,
+            dec: None,
+            time: None,
+        }))
+        .await,
+    );
+    assert_eq!(
+        v["progress"][0],
+        serde_json::json!({
+            "filter": "Red", "binning": "1x1", "exposure_duration": "2m",
+            "desired_count": 4, "good": 1, "total": 1
+        })
+    );
+    // A case-variant spelling resolves through the catalog to the
+    // same canonical name, and the progress lookup must follow it —
     // "m 31" answers with "M 31"'s progress, not null.
     let v = ok_json(
         h.get_target_status(Parameters(GetTargetStatusParams {
@@ -4285,6 +4804,7 @@ fn handler_with_site_and_mount() -> McpHandler {
         settle_after_slew: None,
         slew_rate_arcsec_per_sec: Default::default(),
         guiding: None,
+        meridian_flip: Default::default(),
         auth: None,
     };
     // Skip the connect-time HTTP fetch by hand-building a registry
@@ -4303,7 +4823,7 @@ fn handler_with_site_and_mount() -> McpHandler {
         ..Default::default()
     };
     McpHandler::new(
-        Arc::new(registry),
+        crate::equipment::SharedEquipment::new(registry),
         Arc::new(crate::events::EventBus::from_config(&[], None).unwrap()),
         SessionConfig {
             data_directory: std::env::temp_dir()
@@ -7038,6 +7558,7 @@ async fn slew_deadline_overflow_falls_back_without_panic() {
                 )
                 .unwrap(),
                 guiding: None,
+                meridian_flip: Default::default(),
                 auth: None,
             },
             device: Some(Arc::new(MockTelescope::default())),
@@ -7372,6 +7893,23 @@ fn centering_deadlines_compose_per_iteration_over_max_attempts() {
     assert_eq!(max_ms, 200_500, "5 attempts × 40_100 ms");
 }
 
+#[test]
+fn meridian_flip_deadlines_sum_the_ladder_legs() {
+    // Wait 60 s; worst-case 180° re-slew at the 7200 arcsec/s default is
+    // 90 s (max ×3 = 270 s); centering and guide-settle pairs add as
+    // given; the rotator ceiling lands on max only.
+    let (predicted_ms, max_ms) = super::internals::meridian_flip_deadlines(
+        Duration::from_mins(1),
+        7200.0,
+        Duration::ZERO,
+        (40_100, 200_500),
+        (10_000, 70_000),
+        Duration::from_mins(2),
+    );
+    assert_eq!(predicted_ms, 200_100, "60 s + 90 s + 40.1 s + 10 s");
+    assert_eq!(max_ms, 720_500, "60 s + 270 s + 200.5 s + 70 s + 120 s");
+}
+
 #[tokio::test]
 async fn centering_started_carries_outer_loop_deadline() {
     // §2.5 wiring: center_on_target stamps the outer-loop deadline on
@@ -7464,8 +8002,9 @@ async fn unpark_failure_emits_started_then_failed() {
 
 use crate::config::GuiderDefaults;
 use crate::mcp::built_in::guider::{
-    DitherParams, DitherUnit, GetGuidingStatsParams, PauseGuidingParams, ResumeGuidingParams,
-    StartGuidingParams, StopGuidingParams,
+    DecGuideMode, DitherParams, DitherUnit, GetGuidingSettingsParams, GetGuidingStatsParams,
+    PauseGuidingParams, ResumeGuidingParams, SetGuidingSettingsParams, StartGuidingParams,
+    StopGuidingParams,
 };
 use rp_guider::{GuiderError, GuidingStats, MockGuiderClient, SettledOutcome};
 
@@ -8294,9 +8833,139 @@ async fn get_guiding_stats_passes_the_snapshot_through() {
     assert_eq!(json["star_mass"], serde_json::Value::Null);
 }
 
+fn guiding_settings() -> rp_guider::GuidingSettings {
+    rp_guider::GuidingSettings {
+        dec_guide_mode: rp_guider::DecGuideMode::North,
+        ra: rp_guider::AxisSettings {
+            min_move_px: Some(0.15),
+            aggressiveness: Some(0.7),
+            params: [("minMove".to_string(), 0.15)].into(),
+        },
+        dec: rp_guider::AxisSettings {
+            min_move_px: Some(0.3),
+            aggressiveness: Some(1.0),
+            params: [("minMove".to_string(), 0.3)].into(),
+        },
+        multi_star: None,
+        backlash_compensation: None,
+    }
+}
+
+fn set_settings_params_empty() -> SetGuidingSettingsParams {
+    SetGuidingSettingsParams {
+        dec_guide_mode: None,
+        ra_min_move_px: None,
+        ra_aggressiveness: None,
+        dec_min_move_px: None,
+        dec_aggressiveness: None,
+        ra_params: None,
+        dec_params: None,
+        multi_star: None,
+        backlash_compensation: None,
+        backlash_pulse: None,
+    }
+}
+
+#[tokio::test]
+async fn get_guiding_settings_passes_the_settings_through() {
+    let handler = handler_with_guider(
+        |mock| {
+            mock.expect_guiding_settings()
+                .returning(|| Ok(guiding_settings()));
+        },
+        GuiderDefaults::default(),
+    );
+    let result = handler
+        .get_guiding_settings(Parameters(GetGuidingSettingsParams {}))
+        .await
+        .unwrap();
+    let json = ok_text(result);
+    assert_eq!(json["dec_guide_mode"], "North");
+    assert_eq!(json["dec"]["min_move_px"], 0.3);
+    assert_eq!(json["ra"]["params"]["minMove"], 0.15);
+    // Settings the guider does not expose stay null, not absent.
+    assert_eq!(json["multi_star"], serde_json::Value::Null);
+    assert_eq!(json["backlash_compensation"], serde_json::Value::Null);
+}
+
+#[tokio::test]
+async fn set_guiding_settings_splits_the_flat_params_per_axis() {
+    let handler = handler_with_guider(
+        |mock| {
+            mock.expect_update_guiding_settings()
+                .withf(|update| {
+                    update.dec_guide_mode == Some(rp_guider::DecGuideMode::North)
+                        && update.dec.min_move_px == Some(0.3)
+                        && update.ra.is_empty()
+                        && update.dec.params.get("fastSwitch") == Some(&0.0)
+                        && update.multi_star.is_none()
+                        && update.backlash_compensation.is_none()
+                })
+                .times(1)
+                .returning(|_| Ok(guiding_settings()));
+        },
+        GuiderDefaults::default(),
+    );
+    let result = handler
+        .set_guiding_settings(Parameters(SetGuidingSettingsParams {
+            dec_guide_mode: Some(DecGuideMode::North),
+            dec_min_move_px: Some(0.3),
+            dec_params: Some([("fastSwitch".to_string(), 0.0)].into()),
+            ..set_settings_params_empty()
+        }))
+        .await
+        .unwrap();
+    let json = ok_text(result);
+    assert_eq!(json["dec_guide_mode"], "North");
+}
+
+#[tokio::test]
+async fn set_guiding_settings_needs_a_pulse_to_turn_backlash_compensation_on() {
+    let handler = handler_with_guider(
+        |mock| {
+            mock.expect_update_guiding_settings().never();
+        },
+        GuiderDefaults::default(),
+    );
+    assert_tool_error(
+        handler
+            .set_guiding_settings(Parameters(SetGuidingSettingsParams {
+                backlash_compensation: Some(true),
+                ..set_settings_params_empty()
+            }))
+            .await,
+        "backlash_compensation: true needs backlash_pulse",
+    );
+}
+
+#[tokio::test]
+async fn set_guiding_settings_surfaces_the_services_refusal() {
+    let handler = handler_with_guider(
+        |mock| {
+            mock.expect_update_guiding_settings().returning(|_| {
+                Err(GuiderError::Service {
+                    code: "invalid_request".to_string(),
+                    message: "multi_star and backlash_compensation are not reachable through PHD2's event server".to_string(),
+                    details: serde_json::Value::Null,
+                })
+            });
+        },
+        GuiderDefaults::default(),
+    );
+    assert_tool_error(
+        handler
+            .set_guiding_settings(Parameters(SetGuidingSettingsParams {
+                multi_star: Some(true),
+                ..set_settings_params_empty()
+            }))
+            .await,
+        "set_guiding_settings: invalid_request: multi_star",
+    );
+}
+
 #[tokio::test]
 async fn every_guider_tool_reports_not_configured_without_a_guider_block() {
-    // No `with_guider` call ⇒ each of the six tools errors cleanly.
+    // No `with_guider` call ⇒ each of the eight tools errors cleanly.
     let handler = test_handler(empty_registry());
     assert_tool_error(
         handler
@@ -8335,6 +9004,18 @@ async fn every_guider_tool_reports_not_configured_without_a_guider_block() {
             .await,
         "get_guiding_stats: guider not configured",
     );
+    assert_tool_error(
+        handler
+            .get_guiding_settings(Parameters(GetGuidingSettingsParams {}))
+            .await,
+        "get_guiding_settings: guider not configured",
+    );
+    assert_tool_error(
+        handler
+            .set_guiding_settings(Parameters(set_settings_params_empty()))
+            .await,
+        "set_guiding_settings: guider not configured",
+    );
 }
 
 /// The on-disk reverse-lookup key is written from the UUID's `time_low`
@@ -8351,3 +9032,1616 @@ fn test_uuid8_disk_key_matches_the_document_id_prefix() {
         assert_eq!(written, read_back, "uuid8 mismatch for {uuid}");
     }
 }
+
+// -----------------------------------------------------------------------
+// Dome tools and slaving
+// -----------------------------------------------------------------------
+
+/// Mock dome whose motions land instantly: `open_shutter` /
+/// `close_shutter` set the reported status (unless `shutter_fault`
+/// pins it to `Error`), `slew_to_azimuth` moves `azimuth` and counts
+/// the command, and `slewing` is false unless `slewing_polls` is
+/// non-zero — then it reports `true` for that many reads first.
+#[derive(Default)]
+struct MockDome {
+    shutter_fault: bool,
+    slewing_polls: std::sync::atomic::AtomicU32,
+    shutter: std::sync::Mutex<Option<ascom_alpaca::api::dome::ShutterState>>,
+    azimuth: std::sync::Mutex<f64>,
+    slews: std::sync::atomic::AtomicU32,
+}
+
+impl_mock_device!(MockDome);
+
+#[async_trait::async_trait]
+impl ascom_alpaca::api::Dome for MockDome {
+    async fn shutter_status(
+        &self,
+    ) -> ascom_alpaca::ASCOMResult<ascom_alpaca::api::dome::ShutterState> {
+        if self.shutter_fault {
+            return Ok(ascom_alpaca::api::dome::ShutterState::Error);
+        }
+        Ok(self
+            .shutter
+            .lock()
+            .unwrap()
+            .unwrap_or(ascom_alpaca::api::dome::ShutterState::Closed))
+    }
+
+    async fn open_shutter(&self) -> ascom_alpaca::ASCOMResult<()> {
+        *self.shutter.lock().unwrap() = Some(ascom_alpaca::api::dome::ShutterState::Open);
+        Ok(())
+    }
+
+    async fn close_shutter(&self) -> ascom_alpaca::ASCOMResult<()> {
+        *self.shutter.lock().unwrap() = Some(ascom_alpaca::api::dome::ShutterState::Closed);
+        Ok(())
+    }
+
+    async fn azimuth(&self) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(*self.azimuth.lock().unwrap())
+    }
+
+    async fn slewing(&self) -> ascom_alpaca::ASCOMResult<bool> {
+        Ok(self
+            .slewing_polls
+            .fetch_update(
+                std::sync::atomic::Ordering::SeqCst,
+                std::sync::atomic::Ordering::SeqCst,
+                |n| n.checked_sub(1),
+            )
+            .is_ok())
+    }
+
+    async fn slew_to_azimuth(&self, azimuth: f64) -> ascom_alpaca::ASCOMResult<()> {
+        self.slews.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        *self.azimuth.lock().unwrap() = azimuth;
+        Ok(())
+    }
+}
+
+fn dome_slaving_config() -> crate::config::dome::DomeSlavingConfig {
+    crate::config::dome::DomeSlavingConfig {
+        dome_radius_m: 2.0,
+        mount_offset_east_m: 0.0,
+        mount_offset_north_m: 0.0,
+        mount_offset_up_m: 0.0,
+        gem_offset_m: 0.0,
+        tolerance_deg: 3.0,
+        poll_interval: Duration::from_secs(2),
+        catch_up_timeout: Duration::from_mins(3),
+    }
+}
+
+/// A registry holding the mount plus one dome "dome", with or without
+/// a slaving block.
+fn dome_mount_registry(
+    dome: Arc<dyn ascom_alpaca::api::Dome>,
+    mount: Arc<dyn ascom_alpaca::api::Telescope>,
+    slaving: Option<crate::config::dome::DomeSlavingConfig>,
+) -> crate::equipment::EquipmentRegistry {
+    let mut registry = mount_registry(mount, None);
+    registry.domes = vec![crate::equipment::DomeEntry {
+        id: "dome".to_string(),
+        connected: true,
+        config: crate::config::DomeConfig {
+            id: "dome".to_string(),
+            name: None,
+            alpaca_url: "http://localhost:1".to_string(),
+            device_number: 0,
+            auth: None,
+            slaving,
+        },
+        device: Some(dome),
+    }];
+    registry
+}
+
+fn dome_id() -> DomeIdParams {
+    DomeIdParams {
+        dome_id: "dome".to_string(),
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn open_and_close_shutter_block_until_the_status_lands() {
+    let dome = Arc::new(MockDome::default());
+    let handler = test_handler(dome_mount_registry(
+        dome.clone(),
+        Arc::new(MockTelescope::default()),
+        None,
+    ));
+
+    let json = ok_json(handler.open_shutter_inner(dome_id(), None).await);
+    assert_eq!(json["status"], "open");
+    let json = ok_json(handler.close_shutter_inner(dome_id(), None).await);
+    assert_eq!(json["status"], "closed");
+}
+
+#[tokio::test(start_paused = true)]
+async fn open_shutter_fails_fast_on_a_shutter_error_state() {
+    let dome = Arc::new(MockDome {
+        shutter_fault: true,
+        ..Default::default()
+    });
+    let handler = test_handler(dome_mount_registry(
+        dome,
+        Arc::new(MockTelescope::default()),
+        None,
+    ));
+    assert_tool_error(
+        handler.open_shutter_inner(dome_id(), None).await,
+        "shutter reported an error state",
+    );
+}
+
+#[tokio::test]
+async fn dome_tools_report_an_unknown_dome() {
+    let handler = test_handler(empty_registry());
+    assert_tool_error(
+        handler
+            .close_shutter_inner(
+                DomeIdParams {
+                    dome_id: "nope".to_string(),
+                },
+                None,
+            )
+            .await,
+        "dome not found: nope",
+    );
+}
+
+#[tokio::test]
+async fn slew_dome_to_azimuth_rejects_an_out_of_range_azimuth() {
+    let handler = test_handler(empty_registry());
+    assert_tool_error(
+        handler
+            .slew_dome_to_azimuth_inner(
+                SlewDomeParams {
+                    dome_id: "dome".to_string(),
+                    azimuth: 360.0,
+                },
+                None,
+            )
+            .await,
+        "azimuth out of range",
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn slew_dome_to_azimuth_returns_the_read_back_azimuth() {
+    let dome = Arc::new(MockDome::default());
+    let handler = test_handler(dome_mount_registry(
+        dome,
+        Arc::new(MockTelescope::default()),
+        None,
+    ));
+    let json = ok_json(
+        handler
+            .slew_dome_to_azimuth_inner(
+                SlewDomeParams {
+                    dome_id: "dome".to_string(),
+                    azimuth: 123.0,
+                },
+                None,
+            )
+            .await,
+    );
+    assert_eq!(json["azimuth"], 123.0);
+}
+
+/// A dome rotation reporting `slewing` for ~12 s of simulated time must
+/// tick progress at the 5 s and 10 s marks, so a slow drive never
+/// outlives rmcp's 300 s session keep-alive.
+#[tokio::test(start_paused = true)]
+async fn slew_dome_to_azimuth_emits_progress_while_rotating() {
+    let dome = Arc::new(MockDome {
+        slewing_polls: std::sync::atomic::AtomicU32::new(12),
+        ..Default::default()
+    });
+    let handler = test_handler(dome_mount_registry(
+        dome,
+        Arc::new(MockTelescope::default()),
+        None,
+    ));
+    let sink = super::progress::test_support::CountingProgressEmitter::default();
+    ok_json(
+        handler
+            .slew_dome_to_azimuth_inner(
+                SlewDomeParams {
+                    dome_id: "dome".to_string(),
+                    azimuth: 90.0,
+                },
+                Some(&sink),
+            )
+            .await,
+    );
+    assert!(sink.count() >= 2, "got {} progress ticks", sink.count());
+    let (_, total, message) = sink.records()[0].clone();
+    assert_eq!(total, Some(300.0));
+    assert_eq!(message.as_deref(), Some("slewing dome to 90°"));
+}
+
+#[tokio::test]
+async fn set_dome_slaving_requires_the_slaving_config_and_the_site() {
+    let unconfigured = test_handler(dome_mount_registry(
+        Arc::new(MockDome::default()),
+        Arc::new(MockTelescope::default()),
+        None,
+    ));
+    assert_tool_error(
+        unconfigured
+            .set_dome_slaving(Parameters(SetDomeSlavingParams {
+                dome_id: "dome".to_string(),
+                enabled: true,
+            }))
+            .await,
+        "has no slaving config",
+    );
+
+    let siteless = test_handler(dome_mount_registry(
+        Arc::new(MockDome::default()),
+        Arc::new(MockTelescope::default()),
+        Some(dome_slaving_config()),
+    ));
+    assert_tool_error(
+        siteless
+            .set_dome_slaving(Parameters(SetDomeSlavingParams {
+                dome_id: "dome".to_string(),
+                enabled: true,
+            }))
+            .await,
+        "site",
+    );
+    assert!(siteless.dome_slaving.slaved_dome().is_none());
+}
+
+/// End to end: enabling slaving, then waiting as a capture would,
+/// drives the dome to the mount's pointing (the mock mount sits on the
+/// meridian at dec 0, so from 45° N the slit is due south) and
+/// announces the catch-up and the alignment. Manual motion is refused
+/// while slaved and allowed again once slaving stops.
+#[tokio::test(start_paused = true)]
+async fn slaved_dome_catches_up_before_an_exposure_may_start() {
+    let dome = Arc::new(MockDome::default());
+    let mut handler = test_handler(dome_mount_registry(
+        dome.clone(),
+        Arc::new(MockTelescope::default()),
+        Some(dome_slaving_config()),
+    ));
+    handler.site = Some(rp_ephemeris::Site::new(45.0, 0.0).unwrap());
+    let mut rx = handler.event_bus.subscribe();
+
+    let json = ok_json(
+        handler
+            .set_dome_slaving(Parameters(SetDomeSlavingParams {
+                dome_id: "dome".to_string(),
+                enabled: true,
+            }))
+            .await,
+    );
+    assert_eq!(json["slaved"], true);
+
+    handler.dome_slaving.wait_aligned().await.unwrap();
+    assert!((*dome.azimuth.lock().unwrap() - 180.0).abs() < 1e-6);
+
+    let catching_up = next_event(&mut rx).await;
+    assert_eq!(catching_up.event, "dome_slit_catching_up");
+    assert_eq!(catching_up.payload["dome_id"], "dome");
+    let aligned = next_event(&mut rx).await;
+    assert_eq!(aligned.event, "dome_slit_aligned");
+
+    let state = ok_json(handler.get_dome_state(Parameters(dome_id())).await);
+    assert_eq!(state["slaved"], true);
+    assert_tool_error(
+        handler.park_dome_inner(dome_id(), None).await,
+        "is slaved to the mount",
+    );
+
+    let json = ok_json(
+        handler
+            .set_dome_slaving(Parameters(SetDomeSlavingParams {
+                dome_id: "dome".to_string(),
+                enabled: false,
+            }))
+            .await,
+    );
+    assert_eq!(json["slaved"], false);
+    assert!(handler.dome_slaving.slaved_dome().is_none());
+    // Nothing slaved: the capture-side wait is a no-op.
+    handler.dome_slaving.wait_aligned().await.unwrap();
+}
+
+// -----------------------------------------------------------------------
+// Runtime equipment tools (rp.md § Runtime Equipment Changes)
+// -----------------------------------------------------------------------
+// The registry-side semantics (hot-add, in-flight handles, reload diff,
+// mount site cross-check) are pinned in equipment/live.rs; these cover
+// the tool wiring: config loading, events, and error surfacing.
+
+/// Write `equipment` as the persisted config and point the handler's
+/// equipment source at it.
+fn with_persisted_equipment(
+    handler: McpHandler,
+    equipment: serde_json::Value,
+) -> (McpHandler, tempfile::TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    let config_path = dir.path().join("rp.json");
+    let config = serde_json::json!({
+        "session": { "data_directory": dir.path().join("data").to_string_lossy() },
+        "equipment": equipment,
+    });
+    std::fs::write(&config_path, config.to_string()).unwrap();
+    let handler = handler.with_equipment_source(crate::equipment::EquipmentSource {
+        config_path,
+        ca_cert_path: None,
+        site: None,
+    });
+    (handler, dir)
+}
+
+fn focuser_params(id: &str) -> DeviceParams {
+    DeviceParams {
+        kind: crate::equipment::DeviceKind::Focusers,
+        id: Some(id.to_string()),
+    }
+}
+
+#[tokio::test]
+async fn disconnect_device_releases_the_device_and_emits_device_disconnected() {
+    let handler = test_handler(focuser_registry(
+        Arc::new(MockFocuser::default()),
+        None,
+        None,
+    ));
+    let mut rx = handler.event_bus.subscribe();
+
+    let json = ok_json(
+        handler
+            .disconnect_device(Parameters(focuser_params("foc")))
+            .await,
+    );
+    assert_eq!(json["was_connected"], true);
+
+    let event = next_event(&mut rx).await;
+    assert_eq!(event.event, "device_disconnected");
+    assert_eq!(event.payload["kind"], "focusers");
+    assert_eq!(event.payload["id"], "foc");
+    assert_eq!(event.payload["reason"], "requested");
+    assert_tool_error(
+        handler
+            .get_focuser_position(Parameters(FocuserIdParams {
+                focuser_id: "foc".to_string(),
+            }))
+            .await,
+        "focuser not connected",
+    );
+}
+
+#[tokio::test]
+async fn disconnect_device_of_an_unknown_device_is_a_tool_error() {
+    let handler = test_handler(empty_registry());
+    assert_tool_error(
+        handler
+            .disconnect_device(Parameters(focuser_params("ghost")))
+            .await,
+        "equipment not found",
+    );
+}
+
+#[tokio::test]
+async fn connect_device_without_an_equipment_source_is_not_configured() {
+    let handler = test_handler(empty_registry());
+    assert_tool_error(
+        handler
+            .connect_device(Parameters(focuser_params("foc")))
+            .await,
+        "equipment source not configured",
+    );
+}
+
+#[tokio::test]
+async fn connect_device_that_fails_reports_the_error_and_emits_connect_failed() {
+    let (handler, _dir) = with_persisted_equipment(
+        test_handler(focuser_registry(
+            Arc::new(MockFocuser::default()),
+            None,
+            None,
+        )),
+        serde_json::json!({ "focusers": [{ "id": "foc", "alpaca_url": "not-a-url" }] }),
+    );
+    let mut rx = handler.event_bus.subscribe();
+
+    assert_tool_error(
+        handler
+            .connect_device(Parameters(focuser_params("foc")))
+            .await,
+        "focusers 'foc' did not connect",
+    );
+    let event = next_event(&mut rx).await;
+    assert_eq!(event.event, "device_disconnected");
+    assert_eq!(event.payload["reason"], "connect_failed");
+    assert!(!handler.equipment.find_focuser("foc").unwrap().connected);
+}
+
+#[tokio::test]
+async fn connect_device_missing_from_the_config_file_is_a_tool_error() {
+    let (handler, _dir) =
+        with_persisted_equipment(test_handler(empty_registry()), serde_json::json!({}));
+    assert_tool_error(
+        handler
+            .connect_device(Parameters(focuser_params("foc")))
+            .await,
+        "not in the config file",
+    );
+}
+
+#[tokio::test]
+async fn reload_equipment_drops_a_device_removed_from_the_config_file() {
+    let (handler, _dir) = with_persisted_equipment(
+        test_handler(focuser_registry(
+            Arc::new(MockFocuser::default()),
+            None,
+            None,
+        )),
+        serde_json::json!({}),
+    );
+    let mut rx = handler.event_bus.subscribe();
+
+    let json = ok_json(
+        handler
+            .reload_equipment(Parameters(ReloadEquipmentParams {}))
+            .await,
+    );
+    assert_eq!(
+        json["changes"],
+        serde_json::json!([{
+            "kind": "focusers",
+            "id": "foc",
+            "action": "remove",
+            "was_connected": true,
+            "connected": false,
+        }])
+    );
+    let event = next_event(&mut rx).await;
+    assert_eq!(event.event, "device_disconnected");
+    assert_eq!(event.payload["reason"], "removed");
+    assert!(handler.equipment.find_focuser("foc").is_none());
+}
+
+// -----------------------------------------------------------------------
+// explain_planner_decision — the step-by-step math is pinned in
+// planner/explain.rs; these cover the tool wiring.
+// -----------------------------------------------------------------------
+
+#[tokio::test]
+async fn explain_planner_decision_errors_when_site_absent() {
+    let h = test_handler(empty_registry());
+    let r = h
+        .explain_planner_decision(Parameters(ExplainPlannerDecisionParams {
+            time: None,
+            train_id: None,
+        }))
+        .await;
+    assert_tool_error(r, "site not configured");
+}
+
+#[tokio::test]
+async fn explain_planner_decision_carries_get_next_target_s_answer() {
+    let (h, _store_dir) = handler_with_planned_target().await;
+    let time = Some("2026-07-30T06:00:00Z".to_string());
+
+    let next = ok_json(
+        h.get_next_target(Parameters(GetNextTargetParams {
+            time: time.clone(),
+            train_id: None,
+        }))
+        .await,
+    );
+    let explained = ok_json(
+        h.explain_planner_decision(Parameters(ExplainPlannerDecisionParams {
+            time,
+            train_id: None,
+        }))
+        .await,
+    );
+
+    assert_eq!(explained["recommendation"], next);
+    let target = &explained["targets"][0];
+    assert_eq!(target["name"], next["target"]["name"]);
+    assert_eq!(target["outcome"], "selected");
+    let steps = target["steps"].as_array().unwrap();
+    assert_eq!(steps[0]["step"], "progress");
+    assert_eq!(steps[0]["completion_fraction"], 0.0);
+    assert_eq!(steps.last().unwrap()["step"], "tie_break");
+    assert_eq!(steps.last().unwrap()["rank"], 1);
+}
+
+#[tokio::test]
+async fn explain_planner_decision_rejects_an_unknown_train() {
+    let h = test_handler_with_site(test_site());
+    let r = h
+        .explain_planner_decision(Parameters(ExplainPlannerDecisionParams {
+            time: None,
+            train_id: Some("nope".to_string()),
+        }))
+        .await;
+    assert_tool_error(r, "unknown train_id `nope`");
+}
+
+// -----------------------------------------------------------------------
+// abort_session (rp.md § Session Abort)
+// -----------------------------------------------------------------------
+
+/// A handler with a started session, a guider that confirms the stop,
+/// and an exposing camera `cam`. No orchestrator is registered, so the
+/// start invokes nothing.
+async fn handler_with_started_session() -> (McpHandler, Arc<MockCamera>) {
+    let mut mock = MockGuiderClient::new();
+    mock.expect_stop_guiding().times(1).returning(|| Ok(()));
+    let client: Arc<dyn rp_guider::GuiderClient> = Arc::new(mock);
+    let camera = Arc::new(MockCamera::default());
+    let h = test_handler(camera_registry(camera.clone()))
+        .with_guider(Some(client), GuiderDefaults::default());
+    let manager =
+        Arc::new(crate::session::SessionManager::new(h.event_bus.clone(), &[], None).unwrap());
+    manager.start().await.unwrap();
+    (h.with_session_manager(manager), camera)
+}
+
+#[tokio::test]
+async fn abort_session_ends_the_session_and_stops_guiding() {
+    let (h, camera) = handler_with_started_session().await;
+    let mut rx = h.event_bus.subscribe();
+
+    let json = ok_json(h.abort_session_inner(AbortSessionParams {}, None).await);
+    assert_eq!(json["aborted_exposures"], serde_json::json!(["cam"]));
+    assert_eq!(
+        camera
+            .abort_exposure_calls
+            .load(std::sync::atomic::Ordering::SeqCst),
+        1
+    );
+    assert_eq!(json["guiding_stopped"], true);
+    assert_eq!(json["mount_park_commanded"], false, "no mount configured");
+    assert_eq!(json["mcp_sessions_closed"], 0, "no MCP sessions wired");
+    assert!(json["session_id"].is_string());
+
+    let stopped = next_event(&mut rx).await;
+    assert_eq!(stopped.event, "session_stopped");
+    assert_eq!(stopped.payload["reason"], "aborted");
+    let guide = next_event(&mut rx).await;
+    assert_eq!(guide.event, "guide_stopped");
+    assert_eq!(guide.payload["reason"], "aborted");
+    assert_eq!(h.session_manager.as_ref().unwrap().status().await, "idle");
+}
+
+#[tokio::test]
+async fn abort_session_closes_every_mcp_session_but_the_callers() {
+    use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
+    use rmcp::transport::streamable_http_server::session::SessionManager as _;
+
+    let (h, _camera) = handler_with_started_session().await;
+    let sessions = Arc::new(LocalSessionManager::default());
+    let h = h.with_mcp_sessions(sessions.clone());
+    let (caller, _caller_transport) = sessions.create_session().await.unwrap();
+    let (orchestrator, _orchestrator_transport) = sessions.create_session().await.unwrap();
+
+    let json = ok_json(
+        h.abort_session_inner(AbortSessionParams {}, Some(caller.as_ref()))
+            .await,
+    );
+    assert_eq!(json["mcp_sessions_closed"], 1);
+    let open = sessions.sessions.read().await;
+    assert!(
+        open.contains_key(&caller),
+        "the caller's session stays open"
+    );
+    assert!(
+        !open.contains_key(&orchestrator),
+        "the orchestrator's session is closed"
+    );
+}
+
+#[tokio::test]
+async fn abort_session_with_no_live_session_is_a_tool_error() {
+    let (h, _camera) = handler_with_started_session().await;
+    ok_json(h.abort_session_inner(AbortSessionParams {}, None).await);
+    assert_tool_error(
+        h.abort_session_inner(AbortSessionParams {}, None).await,
+        "no session to abort",
+    );
+}
+
+#[tokio::test]
+async fn abort_session_without_a_session_manager_is_not_configured() {
+    let h = test_handler(empty_registry());
+    assert_tool_error(
+        h.abort_session_inner(AbortSessionParams {}, None).await,
+        "session manager not configured",
+    );
+}
+
+// -----------------------------------------------------------------------
+// Focus temperature compensation (rp.md § Focus Temperature Compensation)
+// -----------------------------------------------------------------------
+
+/// An imaging train terminating in the mock-focuser fixture's "foc".
+fn focus_model_trains() -> crate::equipment::trains::TrainModel {
+    let equipment: crate::config::EquipmentConfig = serde_json::from_value(serde_json::json!({
+        "cameras": [{"id": "cam", "alpaca_url": "http://localhost:1"}],
+        "focusers": [{"id": "foc", "alpaca_url": "http://localhost:1"}],
+        "optical_trains": [
+            {"id": "main", "purpose": "imaging", "devices": ["foc", "cam"]}
+        ]
+    }))
+    .unwrap();
+    crate::equipment::trains::TrainModel::try_from_equipment(&equipment).unwrap()
+}
+
+/// A focuser at position 5000 reading 10 °C, on the "main" train, with
+/// six recorded samples along -20 steps/°C from 0 °C to 5 °C — the last
+/// one (5 °C) the compensation reference.
+async fn focus_model_handler(focuser: MockFocuser) -> McpHandler {
+    let handler = test_handler(focuser_registry(Arc::new(focuser), None, None))
+        .with_trains(focus_model_trains());
+    for t in 0..6 {
+        handler
+            .focus_model
+            .record_sample(
+                "main",
+                crate::focus_model::FocusSample {
+                    focuser_id: "foc".to_string(),
+                    temperature_c: f64::from(t),
+                    position: 1000 - 20 * t,
+                    filter: None,
+                    recorded_at: "2026-10-01T22:00:00Z".to_string(),
+                },
+            )
+            .await;
+    }
+    handler
+}
+
+fn warm_focuser() -> MockFocuser {
+    MockFocuser {
+        position_value: 5000,
+        temperature_value: 10.0,
+        ..Default::default()
+    }
+}
+
+fn compensation_params(train_id: &str) -> ApplyTemperatureCompensationParams {
+    ApplyTemperatureCompensationParams {
+        train_id: Some(train_id.to_string()),
+        min_delta_steps: None,
+    }
+}
+
+#[tokio::test]
+async fn auto_focus_records_a_focus_model_sample() {
+    let foc = MockFocuser {
+        temperature_value: 12.5,
+        ..Default::default()
+    };
+    let start = foc.position_value;
+    let mock = scripted_metrics_guider(vec![9.0, 4.0, 9.0, 3.0, 9.0, 2.0, 9.0, 3.0, 9.0, 4.0]);
+    let client: Arc<dyn rp_guider::GuiderClient> = Arc::new(mock);
+    let handler = test_handler(focuser_registry(Arc::new(foc), None, None))
+        .with_trains(guide_sweep_trains())
+        .with_guider(Some(client), GuiderDefaults::default());
+
+    ok_text(
+        handler
+            .auto_focus_inner(af_params_with_train("guide"), None)
+            .await
+            .unwrap(),
+    );
+    let history = handler.focus_model.history("guide").await;
+    assert_eq!(history.samples.len(), 1);
+    assert_eq!(history.samples[0].focuser_id, "foc");
+    assert_eq!(history.samples[0].temperature_c, 12.5);
+    assert_eq!(history.samples[0].position, start);
+    assert_eq!(history.samples[0].filter, None);
+    assert_eq!(
+        history.reference.unwrap().source,
+        crate::focus_model::ReferenceSource::AutoFocus
+    );
+}
+
+#[tokio::test]
+async fn get_focus_model_reports_the_fit_and_the_predicted_move() {
+    let handler = focus_model_handler(warm_focuser()).await;
+    let json = ok_json(
+        handler
+            .get_focus_model(Parameters(GetFocusModelParams {
+                train_id: Some("main".to_string()),
+            }))
+            .await,
+    );
+    assert_eq!(json["focuser_id"], "foc");
+    assert_eq!(json["samples"].as_array().unwrap().len(), 6);
+    assert!((json["model"]["slope_steps_per_c"].as_f64().unwrap() + 20.0).abs() < 1e-9);
+    assert!(json["model_error"].is_null());
+    assert_eq!(json["reference"]["temperature_c"], 5.0);
+    assert_eq!(json["current_temperature_c"], 10.0);
+    assert_eq!(json["predicted_delta_steps"], -100);
+}
+
+#[tokio::test]
+async fn get_focus_model_without_history_reports_why() {
+    let handler = test_handler(focuser_registry(Arc::new(warm_focuser()), None, None))
+        .with_trains(focus_model_trains());
+    let json = ok_json(
+        handler
+            .get_focus_model(Parameters(GetFocusModelParams {
+                train_id: Some("main".to_string()),
+            }))
+            .await,
+    );
+    assert!(json["model"].is_null());
+    assert!(json["model_error"]
+        .as_str()
+        .unwrap()
+        .contains("0 auto-focus sample(s) recorded"));
+    assert!(json["predicted_delta_steps"].is_null());
+}
+
+#[tokio::test]
+async fn get_focus_model_rejects_an_unknown_train() {
+    let handler = focus_model_handler(warm_focuser()).await;
+    assert_tool_error(
+        handler
+            .get_focus_model(Parameters(GetFocusModelParams {
+                train_id: Some("nonexistent".to_string()),
+            }))
+            .await,
+        "train not found",
+    );
+}
+
+#[tokio::test]
+async fn apply_temperature_compensation_moves_by_the_predicted_delta() {
+    let handler = focus_model_handler(warm_focuser()).await;
+    let mut rx = handler.event_bus.subscribe();
+    let json = ok_json(
+        handler
+            .apply_temperature_compensation_inner(compensation_params("main"), None)
+            .await,
+    );
+    assert_eq!(json["delta_steps"], -100);
+    assert_eq!(json["from_position"], 5000);
+    assert_eq!(json["reference_temperature_c"], 5.0);
+    assert_eq!(json["moved"], true);
+
+    // The move_focuser triple, then the compensation event.
+    let mut events = Vec::new();
+    while let Ok(envelope) = rx.try_recv() {
+        events.push(envelope);
+    }
+    let compensated = events
+        .iter()
+        .find(|e| e.event == "focus_compensated")
+        .expect("expected focus_compensated");
+    assert_eq!(compensated.payload["train_id"], "main");
+    assert_eq!(compensated.payload["delta_steps"], -100);
+    assert!(events.iter().any(|e| e.event == "move_focuser_started"));
+
+    let reference = handler.focus_model.history("main").await.reference.unwrap();
+    assert_eq!(
+        reference.source,
+        crate::focus_model::ReferenceSource::Compensation
+    );
+    assert_eq!(reference.temperature_c, 10.0);
+}
+
+#[tokio::test]
+async fn apply_temperature_compensation_skips_a_move_below_the_deadband() {
+    let handler = focus_model_handler(warm_focuser()).await;
+    let mut params = compensation_params("main");
+    params.min_delta_steps = Some(200);
+    let json = ok_json(
+        handler
+            .apply_temperature_compensation_inner(params, None)
+            .await,
+    );
+    assert_eq!(json["delta_steps"], -100);
+    assert_eq!(json["moved"], false);
+    assert_eq!(json["to_position"], 5000);
+    // The reference stays put so the drift keeps accumulating.
+    let reference = handler.focus_model.history("main").await.reference.unwrap();
+    assert_eq!(
+        reference.source,
+        crate::focus_model::ReferenceSource::AutoFocus
+    );
+}
+
+#[tokio::test]
+async fn apply_temperature_compensation_waits_for_imaging_exposures() {
+    let handler = focus_model_handler(warm_focuser()).await;
+    let exposure = handler.motion_gate.shared().await;
+    let blocked = tokio::time::timeout(
+        Duration::from_millis(100),
+        handler.apply_temperature_compensation_inner(compensation_params("main"), None),
+    )
+    .await;
+    assert!(
+        blocked.is_err(),
+        "the move must wait while an imaging-train exposure holds the gate"
+    );
+    drop(exposure);
+    let json = ok_json(
+        handler
+            .apply_temperature_compensation_inner(compensation_params("main"), None)
+            .await,
+    );
+    assert_eq!(json["moved"], true);
+}
+
+#[tokio::test]
+async fn apply_temperature_compensation_needs_a_model() {
+    let handler = test_handler(focuser_registry(Arc::new(warm_focuser()), None, None))
+        .with_trains(focus_model_trains());
+    assert_tool_error(
+        handler
+            .apply_temperature_compensation_inner(compensation_params("main"), None)
+            .await,
+        "no focus model for train 'main'",
+    );
+}
+
+#[tokio::test]
+async fn apply_temperature_compensation_needs_a_focuser_temperature() {
+    let handler = focus_model_handler(MockFocuser {
+        temperature_not_implemented: true,
+        ..Default::default()
+    })
+    .await;
+    assert_tool_error(
+        handler
+            .apply_temperature_compensation_inner(compensation_params("main"), None)
+            .await,
+        "reports no temperature",
+    );
+}
+
+#[tokio::test]
+async fn apply_temperature_compensation_rejects_a_non_positive_deadband() {
+    let handler = focus_model_handler(warm_focuser()).await;
+    let mut params = compensation_params("main");
+    params.min_delta_steps = Some(0);
+    assert_tool_error(
+        handler
+            .apply_temperature_compensation_inner(params, None)
+            .await,
+        "min_delta_steps must be positive",
+    );
+}
+
+// -----------------------------------------------------------------------
+// Filter focus offsets — set_filter's offset move and
+// measure_filter_offsets (rp.md § Filter Focus Offsets)
+// -----------------------------------------------------------------------
+
+/// One imaging train [foc, fw, cam] over a Lum/Red wheel, with the
+/// fixture sweep block and the given `focus_offsets`.
+fn offset_equipment(focus_offsets: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "cameras": [{"id": "cam", "alpaca_url": "http://localhost:1"}],
+        "focusers": [{"id": "foc", "alpaca_url": "http://localhost:1"}],
+        "filter_wheels": [
+            {"id": "fw", "alpaca_url": "http://localhost:1", "filters": ["Lum", "Red"]}
+        ],
+        "optical_trains": [{
+            "id": "main",
+            "devices": ["foc", "fw", "cam"],
+            "auto_focus": {"duration": "100ms", "step_size": 20, "half_width": 100,
+                           "min_area": 4, "max_area": 2000},
+            "focus_offsets": focus_offsets
+        }]
+    })
+}
+
+fn offset_trains(focus_offsets: serde_json::Value) -> crate::equipment::trains::TrainModel {
+    let equipment: crate::config::EquipmentConfig =
+        serde_json::from_value(offset_equipment(focus_offsets)).unwrap();
+    crate::equipment::trains::TrainModel::try_from_equipment(&equipment).unwrap()
+}
+
+/// The auto-focus fixture rig plus the Lum/Red wheel, with enough
+/// fixture frames for `sweeps` V-curve runs.
+fn offset_registry(starting_position: i32, sweeps: usize) -> crate::equipment::EquipmentRegistry {
+    let mut registry = auto_focus_registry(starting_position);
+    registry.filter_wheels =
+        filter_wheel_registry(Arc::new(MockFilterWheel::default())).filter_wheels;
+    let frames = (0..sweeps)
+        .flat_map(|_| load_auto_focus_fixtures())
+        .collect();
+    registry.cameras[0].device = Some(Arc::new(FixtureCamera::new(frames)));
+    registry
+}
+
+fn set_filter_on_main(filter_name: &str) -> SetFilterParams {
+    SetFilterParams {
+        filter_wheel_id: None,
+        train_id: Some("main".into()),
+        filter_name: filter_name.into(),
+    }
+}
+
+#[tokio::test]
+async fn set_filter_moves_the_focuser_by_the_offset_difference() {
+    let handler = test_handler(offset_registry(10_000, 0))
+        .with_trains(offset_trains(serde_json::json!({"Lum": 0, "Red": 25})));
+
+    let body = ok_json(
+        handler
+            .set_filter(Parameters(set_filter_on_main("Red")))
+            .await,
+    );
+    assert_eq!(
+        body["focus_adjustments"],
+        serde_json::json!([
+            {"train_id": "main", "focuser_id": "foc", "delta_steps": 25, "position": 10_025}
+        ])
+    );
+
+    // And back: the difference runs the other way.
+    let body = ok_json(
+        handler
+            .set_filter(Parameters(set_filter_on_main("Lum")))
+            .await,
+    );
+    assert_eq!(body["focus_adjustments"][0]["delta_steps"], -25);
+    assert_eq!(body["focus_adjustments"][0]["position"], 10_000);
+}
+
+#[tokio::test]
+async fn set_filter_skips_the_move_when_a_filter_has_no_offset() {
+    let handler = test_handler(offset_registry(10_000, 0))
+        .with_trains(offset_trains(serde_json::json!({"Lum": 0})));
+    let body = ok_json(
+        handler
+            .set_filter(Parameters(set_filter_on_main("Red")))
+            .await,
+    );
+    assert_eq!(body["focus_adjustments"], serde_json::json!([]));
+}
+
+fn measure_params(filters: Option<Vec<&str>>) -> MeasureFilterOffsetsParams {
+    MeasureFilterOffsetsParams {
+        train_id: Some("main".into()),
+        reference_filter: None,
+        filters: filters.map(|f| f.into_iter().map(str::to_string).collect()),
+    }
+}
+
+#[tokio::test]
+async fn measure_filter_offsets_writes_the_config_and_takes_effect_at_once() {
+    const STARTING_POSITION: i32 = 11_000;
+    let (mut handler, dir) = with_persisted_equipment(
+        test_handler(offset_registry(STARTING_POSITION, 2))
+            .with_trains(offset_trains(serde_json::json!({}))),
+        offset_equipment(serde_json::json!({})),
+    );
+    handler.session_config = SessionConfig {
+        data_directory: dir.path().to_string_lossy().into_owned(),
+    };
+
+    let body = ok_json(
+        handler
+            .measure_filter_offsets_inner(measure_params(None), None)
+            .await,
+    );
+    assert_eq!(body["reference_filter"], "Lum");
+    let measurements = body["measurements"].as_array().unwrap();
+    assert_eq!(measurements.len(), 2);
+    assert_eq!(measurements[0]["filter"], "Lum");
+    assert_eq!(measurements[0]["offset"], 0);
+    let red = measurements[1]["offset"].as_i64().unwrap();
+    assert_eq!(
+        red,
+        measurements[1]["best_position"].as_i64().unwrap()
+            - measurements[0]["best_position"].as_i64().unwrap()
+    );
+    assert_eq!(
+        body["focus_offsets"],
+        serde_json::json!({"Lum": 0, "Red": red})
+    );
+    assert_eq!(body["dropped_filters"], serde_json::json!([]));
+
+    // Written through config apply…
+    let config = crate::config::load_config(&dir.path().join("rp.json")).unwrap();
+    let offsets = &config.equipment.optical_trains[0].focus_offsets;
+    assert_eq!(offsets.get("Red").copied().map(i64::from), Some(red));
+    // …and live for set_filter without a restart.
+    let live = handler
+        .focus_offsets
+        .lock()
+        .unwrap()
+        .get("main")
+        .cloned()
+        .unwrap();
+    assert_eq!(&live, offsets);
+}
+
+#[tokio::test]
+async fn measure_filter_offsets_needs_an_equipment_source() {
+    let handler =
+        test_handler(offset_registry(10_000, 0)).with_trains(offset_trains(serde_json::json!({})));
+    assert_tool_error(
+        handler
+            .measure_filter_offsets_inner(measure_params(None), None)
+            .await,
+        "equipment source not configured",
+    );
+}
+
+#[tokio::test]
+async fn measure_filter_offsets_rejects_an_unknown_filter_before_moving() {
+    let (handler, _dir) = with_persisted_equipment(
+        test_handler(offset_registry(10_000, 0)).with_trains(offset_trains(serde_json::json!({}))),
+        offset_equipment(serde_json::json!({})),
+    );
+    assert_tool_error(
+        handler
+            .measure_filter_offsets_inner(measure_params(Some(vec!["OIII"])), None)
+            .await,
+        "filter not found: OIII",
+    );
+}
+
+#[tokio::test]
+async fn measure_filter_offsets_needs_a_filter_besides_the_reference() {
+    let (handler, _dir) = with_persisted_equipment(
+        test_handler(offset_registry(10_000, 0)).with_trains(offset_trains(serde_json::json!({}))),
+        offset_equipment(serde_json::json!({})),
+    );
+    assert_tool_error(
+        handler
+            .measure_filter_offsets_inner(measure_params(Some(vec!["Lum"])), None)
+            .await,
+        "nothing to measure besides the reference filter 'Lum'",
+    );
+}
+
+fn offsets(pairs: &[(&str, i32)]) -> std::collections::BTreeMap<String, i32> {
+    pairs.iter().map(|(n, o)| ((*n).to_string(), *o)).collect()
+}
+
+fn filter_order(names: &[&str]) -> Vec<String> {
+    names.iter().map(|n| (*n).to_string()).collect()
+}
+
+#[test]
+fn merge_focus_offsets_rebases_skipped_filters_onto_the_reference() {
+    let previous = offsets(&[("Lum", 10), ("Red", 40), ("Ha", 70)]);
+    let measured = offsets(&[("Lum", 0), ("Red", 35)]);
+    let (merged, dropped) =
+        merge_focus_offsets(&previous, measured, &filter_order(&["Lum", "Red"]));
+    assert_eq!(merged, offsets(&[("Lum", 0), ("Red", 35), ("Ha", 60)]));
+    assert!(dropped.is_empty());
+}
+
+#[test]
+fn merge_focus_offsets_rebases_through_another_common_filter_when_the_reference_is_new() {
+    // The old set has no OIII; Red, measured in both runs, carries Ha
+    // across: Ha sat 30 steps past Red, and still does.
+    let previous = offsets(&[("Lum", 0), ("Red", 40), ("Ha", 70)]);
+    let measured = offsets(&[("OIII", 0), ("Red", -15)]);
+    let (merged, dropped) =
+        merge_focus_offsets(&previous, measured, &filter_order(&["OIII", "Red"]));
+    assert_eq!(
+        merged,
+        offsets(&[("OIII", 0), ("Red", -15), ("Lum", -55), ("Ha", 15)])
+    );
+    assert!(dropped.is_empty());
+}
+
+#[test]
+fn merge_focus_offsets_drops_and_reports_filters_with_nothing_in_common() {
+    let previous = offsets(&[("Lum", 0), ("Ha", 70)]);
+    let measured = offsets(&[("OIII", 0), ("SII", 20)]);
+    let (merged, dropped) =
+        merge_focus_offsets(&previous, measured, &filter_order(&["OIII", "SII"]));
+    assert_eq!(merged, offsets(&[("OIII", 0), ("SII", 20)]));
+    assert_eq!(dropped, vec!["Ha".to_string(), "Lum".to_string()]);
+}
+
+// -----------------------------------------------------------------------
+// Switch and observing-conditions tools
+// -----------------------------------------------------------------------
+
+/// Mock three-port switch: 0 "Main power" (boolean, writable), 1 "Dew
+/// heater" (analog 0..100 in steps of 5, writable), 2 "Input voltage"
+/// (read-only). Writes land in `values` immediately.
+struct MockSwitch {
+    values: std::sync::Mutex<[f64; 3]>,
+}
+
+impl Default for MockSwitch {
+    fn default() -> Self {
+        Self {
+            values: std::sync::Mutex::new([0.0, 0.0, 12.2]),
+        }
+    }
+}
+
+impl_mock_device!(MockSwitch);
+
+impl MockSwitch {
+    const NAMES: [&'static str; 3] = ["Main power", "Dew heater", "Input voltage"];
+    const RANGES: [(f64, f64, f64); 3] = [(0.0, 1.0, 1.0), (0.0, 100.0, 5.0), (0.0, 20.0, 0.1)];
+}
+
+#[async_trait::async_trait]
+impl ascom_alpaca::api::Switch for MockSwitch {
+    async fn max_switch(&self) -> ascom_alpaca::ASCOMResult<usize> {
+        Ok(3)
+    }
+
+    async fn can_write(&self, id: usize) -> ascom_alpaca::ASCOMResult<bool> {
+        Ok(id < 2)
+    }
+
+    async fn get_switch_name(&self, id: usize) -> ascom_alpaca::ASCOMResult<String> {
+        Ok(Self::NAMES[id].to_string())
+    }
+
+    async fn get_switch(&self, id: usize) -> ascom_alpaca::ASCOMResult<bool> {
+        Ok(self.values.lock().unwrap()[id] > Self::RANGES[id].0)
+    }
+
+    async fn set_switch(&self, id: usize, state: bool) -> ascom_alpaca::ASCOMResult<()> {
+        let (min, max, _) = Self::RANGES[id];
+        self.values.lock().unwrap()[id] = if state { max } else { min };
+        Ok(())
+    }
+
+    async fn get_switch_value(&self, id: usize) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(self.values.lock().unwrap()[id])
+    }
+
+    async fn set_switch_value(&self, id: usize, value: f64) -> ascom_alpaca::ASCOMResult<()> {
+        self.values.lock().unwrap()[id] = value;
+        Ok(())
+    }
+
+    async fn min_switch_value(&self, id: usize) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(Self::RANGES[id].0)
+    }
+
+    async fn max_switch_value(&self, id: usize) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(Self::RANGES[id].1)
+    }
+
+    async fn switch_step(&self, id: usize) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(Self::RANGES[id].2)
+    }
+}
+
+/// Mock weather station with temperature and humidity (humidity has no
+/// reading yet) and no other sensor; it tracks the age of temperature
+/// only.
+#[derive(Default)]
+struct MockObservingConditions;
+
+impl_mock_device!(MockObservingConditions);
+
+#[async_trait::async_trait]
+impl ascom_alpaca::api::ObservingConditions for MockObservingConditions {
+    async fn temperature(&self) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(4.5)
+    }
+
+    async fn humidity(&self) -> ascom_alpaca::ASCOMResult<f64> {
+        Err(ASCOMError::new(
+            ascom_alpaca::ASCOMErrorCode::VALUE_NOT_SET,
+            "no humidity reading yet",
+        ))
+    }
+
+    async fn time_since_last_update(&self, sensor_name: String) -> ascom_alpaca::ASCOMResult<f64> {
+        match sensor_name.as_str() {
+            "Temperature" => Ok(2.0),
+            _ => Err(ASCOMError::NOT_IMPLEMENTED),
+        }
+    }
+}
+
+fn switch_registry(
+    switch: Arc<dyn ascom_alpaca::api::Switch>,
+) -> crate::equipment::EquipmentRegistry {
+    crate::equipment::EquipmentRegistry {
+        switches: vec![crate::equipment::SwitchEntry {
+            id: "power".to_string(),
+            connected: true,
+            config: crate::config::SwitchConfig {
+                id: "power".to_string(),
+                name: None,
+                alpaca_url: "http://localhost:1".to_string(),
+                device_number: 0,
+                auth: None,
+            },
+            device: Some(switch),
+        }],
+        ..Default::default()
+    }
+}
+
+fn set_switch_params(index: usize, state: Option<bool>, value: Option<f64>) -> SetSwitchParams {
+    SetSwitchParams {
+        switch_id: "power".to_string(),
+        index,
+        state,
+        value,
+    }
+}
+
+#[tokio::test]
+async fn list_switches_reports_every_port_with_its_limits() {
+    let handler = test_handler(switch_registry(Arc::new(MockSwitch::default())));
+    let json = ok_json(
+        handler
+            .list_switches(Parameters(SwitchIdParams {
+                switch_id: "power".to_string(),
+            }))
+            .await,
+    );
+    let ports = json["ports"].as_array().unwrap();
+    assert_eq!(ports.len(), 3);
+    assert_eq!(ports[1]["name"], "Dew heater");
+    assert_eq!(ports[1]["max"], 100.0);
+    assert_eq!(ports[1]["step"], 5.0);
+    assert_eq!(ports[2]["can_write"], false);
+    assert_eq!(ports[2]["value"], 12.2);
+}
+
+#[tokio::test]
+async fn switch_tools_report_an_unknown_switch_and_index() {
+    let handler = test_handler(switch_registry(Arc::new(MockSwitch::default())));
+    assert_tool_error(
+        handler
+            .get_switch(Parameters(SwitchPortParams {
+                switch_id: "nope".to_string(),
+                index: 0,
+            }))
+            .await,
+        "switch not found: nope",
+    );
+    assert_tool_error(
+        handler
+            .get_switch(Parameters(SwitchPortParams {
+                switch_id: "power".to_string(),
+                index: 3,
+            }))
+            .await,
+        "switch port 3 out of range",
+    );
+}
+
+#[tokio::test]
+async fn set_switch_writes_a_boolean_and_emits_switch_changed() {
+    let switch = Arc::new(MockSwitch::default());
+    let handler = test_handler(switch_registry(switch.clone()));
+    let mut rx = handler.event_bus.subscribe();
+
+    let json = ok_json(
+        handler
+            .set_switch(Parameters(set_switch_params(0, Some(true), None)))
+            .await,
+    );
+    assert_eq!(json["port"]["state"], true);
+    assert_eq!(switch.values.lock().unwrap()[0], 1.0);
+
+    let event = next_event(&mut rx).await;
+    assert_eq!(event.event, "switch_changed");
+    assert_eq!(event.payload["switch_id"], "power");
+    assert_eq!(event.payload["index"], 0);
+    assert_eq!(event.payload["name"], "Main power");
+    assert_eq!(event.payload["state"], true);
+}
+
+#[tokio::test]
+async fn set_switch_writes_an_analog_value_on_the_step_grid() {
+    let switch = Arc::new(MockSwitch::default());
+    let handler = test_handler(switch_registry(switch.clone()));
+    let json = ok_json(
+        handler
+            .set_switch(Parameters(set_switch_params(1, None, Some(35.0))))
+            .await,
+    );
+    assert_eq!(json["port"]["value"], 35.0);
+    assert_eq!(switch.values.lock().unwrap()[1], 35.0);
+}
+
+#[tokio::test]
+async fn set_switch_refuses_an_out_of_range_or_off_grid_value() {
+    let switch = Arc::new(MockSwitch::default());
+    let handler = test_handler(switch_registry(switch.clone()));
+    let mut rx = handler.event_bus.subscribe();
+    assert_tool_error(
+        handler
+            .set_switch(Parameters(set_switch_params(1, None, Some(105.0))))
+            .await,
+        "outside the port's range 0..100",
+    );
+    assert_tool_error(
+        handler
+            .set_switch(Parameters(set_switch_params(1, None, Some(33.0))))
+            .await,
+        "nearest valid values are 30 and 35",
+    );
+    assert_eq!(switch.values.lock().unwrap()[1], 0.0);
+    assert_no_more_events(&mut rx).await;
+}
+
+#[tokio::test]
+async fn set_switch_refuses_a_read_only_port_and_ambiguous_params() {
+    let handler = test_handler(switch_registry(Arc::new(MockSwitch::default())));
+    assert_tool_error(
+        handler
+            .set_switch(Parameters(set_switch_params(2, None, Some(12.0))))
+            .await,
+        "switch port 2 is read-only",
+    );
+    assert_tool_error(
+        handler
+            .set_switch(Parameters(set_switch_params(0, Some(true), Some(1.0))))
+            .await,
+        "give exactly one of state and value",
+    );
+    assert_tool_error(
+        handler
+            .set_switch(Parameters(set_switch_params(0, None, None)))
+            .await,
+        "give exactly one of state and value",
+    );
+}
+
+#[tokio::test]
+async fn get_observing_conditions_reports_only_supported_sensors() {
+    let handler = test_handler(crate::equipment::EquipmentRegistry {
+        observing_conditions: vec![crate::equipment::ObservingConditionsEntry {
+            id: "weather".to_string(),
+            connected: true,
+            config: crate::config::ObservingConditionsConfig {
+                id: "weather".to_string(),
+                name: None,
+                alpaca_url: "http://localhost:1".to_string(),
+                device_number: 0,
+                auth: None,
+            },
+            device: Some(Arc::new(MockObservingConditions)),
+        }],
+        ..Default::default()
+    });
+    let json = ok_json(
+        handler
+            .get_observing_conditions(Parameters(ObservingConditionsIdParams {
+                observing_conditions_id: "weather".to_string(),
+            }))
+            .await,
+    );
+    let sensors = json["sensors"].as_object().unwrap();
+    assert_eq!(sensors.len(), 2);
+    assert_eq!(sensors["temperature"]["value"], 4.5);
+    assert_eq!(sensors["temperature"]["time_since_last_update_s"], 2.0);
+    assert!(sensors["humidity"]["value"].is_null());
+    assert!(sensors["humidity"]["time_since_last_update_s"].is_null());
+    assert!(sensors["humidity"]["error"]
+        .as_str()
+        .unwrap()
+        .contains("no humidity reading yet"));
+}
+
+#[tokio::test]
+async fn get_observing_conditions_reports_an_unknown_device() {
+    let handler = test_handler(empty_registry());
+    assert_tool_error(
+        handler
+            .get_observing_conditions(Parameters(ObservingConditionsIdParams {
+                observing_conditions_id: "nope".to_string(),
+            }))
+            .await,
+        "observing conditions not found: nope",
+    );
+}
+
+// -----------------------------------------------------------------------
+// Dew controller (drives MockSwitch's analog "Dew heater" port)
+// -----------------------------------------------------------------------
+
+/// Weather station reporting a settable ambient temperature and a fixed
+/// dew point.
+struct MockWeather {
+    temperature_c: std::sync::Mutex<f64>,
+    dew_point_c: f64,
+}
+
+impl_mock_device!(MockWeather);
+
+#[async_trait::async_trait]
+impl ascom_alpaca::api::ObservingConditions for MockWeather {
+    async fn temperature(&self) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(*self.temperature_c.lock().unwrap())
+    }
+
+    async fn dew_point(&self) -> ascom_alpaca::ASCOMResult<f64> {
+        Ok(self.dew_point_c)
+    }
+}
+
+/// A handler over MockSwitch "power" plus `weather`, and a controller
+/// driving port 1 of the switch with a 3 °C margin every 30 s, reading
+/// the optic's temperature from `temperature_sensor` when given.
+fn dew_fixture(
+    switch: Arc<MockSwitch>,
+    weather: Arc<MockWeather>,
+    temperature_sensor: Option<crate::config::DewSensorConfig>,
+) -> (McpHandler, Arc<crate::dew::DewController>) {
+    let mut registry = switch_registry(switch);
+    registry.observing_conditions = vec![crate::equipment::ObservingConditionsEntry {
+        id: "weather".to_string(),
+        connected: true,
+        config: crate::config::ObservingConditionsConfig {
+            id: "weather".to_string(),
+            name: None,
+            alpaca_url: "http://localhost:1".to_string(),
+            device_number: 0,
+            auth: None,
+        },
+        device: Some(weather),
+    }];
+    let handler = test_handler(registry);
+    let dew = Arc::new(crate::dew::DewController::new(
+        handler.equipment.clone(),
+        handler.event_bus.clone(),
+        crate::config::DewConfig {
+            observing_conditions_id: "weather".to_string(),
+            poll_interval: Duration::from_secs(30),
+            heaters: vec![crate::config::DewHeaterConfig {
+                id: "main".to_string(),
+                switch_id: "power".to_string(),
+                index: 1,
+                margin_c: 3.0,
+                kp: 10.0,
+                ki: 2.0,
+                min_duty_pct: 0.0,
+                max_duty_pct: 100.0,
+                temperature_sensor,
+            }],
+        },
+    ));
+    (handler, dew)
+}
+
+/// The optic's probe on MockSwitch port 2; tests set its reading
+/// through `values`.
+fn strap_probe() -> Option<crate::config::DewSensorConfig> {
+    Some(crate::config::DewSensorConfig {
+        switch_id: "power".to_string(),
+        index: 2,
+    })
+}
+
+fn weather(temperature_c: f64, dew_point_c: f64) -> Arc<MockWeather> {
+    Arc::new(MockWeather {
+        temperature_c: std::sync::Mutex::new(temperature_c),
+        dew_point_c,
+    })
+}
+
+#[tokio::test]
+async fn dew_controller_drives_the_heater_port_and_records_its_duty() {
+    let switch = Arc::new(MockSwitch::default());
+    // 2 °C short of dew point + margin: P = 20, I = 2 × 2 × 0.5 min = 2.
+    switch.values.lock().unwrap()[2] = 2.0;
+    let (_handler, dew) = dew_fixture(switch.clone(), weather(8.0, 1.0), strap_probe());
+
+    dew.tick().await;
+
+    // 22 % lands on the port's 5-step grid at 20.
+    assert_eq!(switch.values.lock().unwrap()[1], 20.0);
+    assert_eq!(dew.duties()["main"], 20.0);
+}
+
+#[tokio::test]
+async fn dew_controller_raises_dew_risk_once_per_episode_at_full_duty() {
+    let switch = Arc::new(MockSwitch::default());
+    switch.values.lock().unwrap()[2] = -10.0;
+    let (handler, dew) = dew_fixture(switch.clone(), weather(8.0, 1.0), strap_probe());
+    let mut rx = handler.event_bus.subscribe();
+
+    dew.tick().await;
+    dew.tick().await;
+    assert_eq!(switch.values.lock().unwrap()[1], 100.0);
+    assert_heater_switch_changed(next_event(&mut rx).await, 100.0);
+    let event = next_event(&mut rx).await;
+    assert_eq!(event.event, "dew_risk");
+    assert_eq!(event.payload["heater_id"], "main");
+    assert_eq!(event.payload["target_c"], 4.0);
+    assert_eq!(event.payload["duty_pct"], 100.0);
+    assert_no_more_events(&mut rx).await;
+
+    // The margin is held again, then lost again: a new episode.
+    switch.values.lock().unwrap()[2] = 10.0;
+    dew.tick().await;
+    switch.values.lock().unwrap()[2] = -10.0;
+    dew.tick().await;
+    assert_heater_switch_changed(next_event(&mut rx).await, 0.0);
+    assert_heater_switch_changed(next_event(&mut rx).await, 100.0);
+    assert_eq!(next_event(&mut rx).await.event, "dew_risk");
+}
+
+#[tokio::test]
+async fn dew_controller_runs_a_sensorless_heater_feed_forward_without_dew_risk() {
+    let switch = Arc::new(MockSwitch::default());
+    // Spread 1 °C inside a 3 °C margin: two thirds of the way up the
+    // ramp, 66.7 %, on the 5-step grid at 65.
+    let weather = weather(2.0, 1.0);
+    let (handler, dew) = dew_fixture(switch.clone(), weather.clone(), None);
+    let mut rx = handler.event_bus.subscribe();
+
+    dew.tick().await;
+    assert_eq!(dew.duties()["main"], 65.0);
+    assert_heater_switch_changed(next_event(&mut rx).await, 65.0);
+
+    // Below the dew point the heater sits at its ceiling, but with no
+    // optic temperature there is nothing to call short of target.
+    *weather.temperature_c.lock().unwrap() = -10.0;
+    dew.tick().await;
+    dew.tick().await;
+    assert_eq!(switch.values.lock().unwrap()[1], 100.0);
+    assert_heater_switch_changed(next_event(&mut rx).await, 100.0);
+    assert_no_more_events(&mut rx).await;
+}
+
+#[tokio::test]
+async fn dew_controller_stop_switches_the_heater_off() {
+    let switch = Arc::new(MockSwitch::default());
+    switch.values.lock().unwrap()[2] = 2.0;
+    let (handler, dew) = dew_fixture(switch.clone(), weather(8.0, 1.0), strap_probe());
+    dew.tick().await;
+    assert_eq!(switch.values.lock().unwrap()[1], 20.0);
+    let mut rx = handler.event_bus.subscribe();
+
+    dew.stop();
+    for _ in 0..50 {
+        if switch.values.lock().unwrap()[1] == 0.0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert_eq!(switch.values.lock().unwrap()[1], 0.0);
+    assert!(dew.duties().is_empty());
+    assert_heater_switch_changed(next_event(&mut rx).await, 0.0);
+}
+
+#[tokio::test]
+async fn dew_controller_announces_only_writes_that_change_the_port() {
+    let switch = Arc::new(MockSwitch::default());
+    // 1 °C short: the integral nudges the duty from 11 % to 12 %, and
+    // both land on the port's 5-step grid at 10.
+    switch.values.lock().unwrap()[2] = 3.0;
+    let (handler, dew) = dew_fixture(switch.clone(), weather(8.0, 1.0), strap_probe());
+    let mut rx = handler.event_bus.subscribe();
+
+    dew.tick().await;
+    assert_heater_switch_changed(next_event(&mut rx).await, 10.0);
+    dew.tick().await;
+    assert_eq!(switch.values.lock().unwrap()[1], 10.0);
+    assert_no_more_events(&mut rx).await;
+}
+
+/// A `switch_changed` for the "main" heater on MockSwitch port 1, in
+/// `set_switch`'s shape plus the heater id.
+fn assert_heater_switch_changed(event: crate::events::EventEnvelope, value: f64) {
+    assert_eq!(event.event, "switch_changed");
+    assert_eq!(event.payload["switch_id"], "power");
+    assert_eq!(event.payload["index"], 1);
+    assert_eq!(event.payload["name"], "Dew heater");
+    assert_eq!(event.payload["state"], value > 0.0);
+    assert_eq!(event.payload["value"], value);
+    assert_eq!(event.payload["heater_id"], "main");
+}
@@ -8,7 +8,8 @@
 //!
 //! Storage is `u16` for every consumer/prosumer astro camera (`max_adu` ≤
 //! 65535); the `I32` variant is the hatch for future scientific cameras whose
-//! `max_adu` exceeds 16-bit range.
+//! `max_adu` exceeds 16-bit range, and `F32` holds floating-point frames
+//! (calibrated masters, stacks) read back from disk.
 //!
 //! Eviction is LRU with two budgets — `cache_max_mib` and `cache_max_images`
 //! — whichever trips first.
@@ -23,6 +24,7 @@ use tokio::sync::RwLock;
 use tracing::debug;
 
 use super::document::ExposureDocument;
+use super::frame::FramePixels;
 
 /// Pixel storage variant. The design intent is per-camera selection at
 /// connect time (driven by the camera's `MaxADU`); the same camera always
@@ -30,18 +32,23 @@ use super::document::ExposureDocument;
 /// current implementation fetches `max_adu` per-frame in
 /// `mcp.rs:capture` — see the "Phase 3 follow-up: stash `max_adu` on
 /// `CameraEntry`" section in `docs/plans/archive/image-evaluation-tools.md`.
+///
+/// `F32` is never produced by `capture` — cameras deliver integers. It
+/// holds frames whose file stores floating-point pixels, with undefined
+/// pixels as NaN (see [`super::FramePixels`]).
 pub enum CachedPixels {
     U16(Array2<u16>),
     I32(Array2<i32>),
+    F32(Array2<f32>),
 }
 
 /// Dispatch on a `&CachedPixels` and run the body once per pixel variant.
 ///
 /// Generic image-analysis functions are statically dispatched, so the runtime
 /// tag in `CachedPixels` has to be unwrapped somewhere. This macro hides the
-/// two-arm match while still emitting a separate monomorphization per pixel
-/// type — the body is textually duplicated by the macro expander, then the
-/// compiler monomorphizes each arm with `T = u16` or `T = i32`.
+/// per-variant match while still emitting a separate monomorphization per
+/// pixel type — the body is textually duplicated by the macro expander, then
+/// the compiler monomorphizes each arm with `T = u16`, `i32` or `f32`.
 ///
 /// Caveat: because the body is duplicated before expansion, any non-`Copy`
 /// value moved by the body would only compile in one arm. Capture by reference
@@ -58,6 +65,10 @@ macro_rules! dispatch_pixels {
                 let $arr = __a.view();
                 $body
             }
+            $crate::persistence::CachedPixels::F32(__a) => {
+                let $arr = __a.view();
+                $body
+            }
         }
     };
 }
@@ -69,6 +80,7 @@ impl CachedPixels {
         match self {
             Self::U16(a) => a.len() * std::mem::size_of::<u16>(),
             Self::I32(a) => a.len() * std::mem::size_of::<i32>(),
+            Self::F32(a) => a.len() * std::mem::size_of::<f32>(),
         }
     }
 
@@ -100,6 +112,28 @@ impl CachedPixels {
     pub fn from_u16_pixels(pixels: Vec<u16>, shape: (usize, usize)) -> Option<Self> {
         Array2::from_shape_vec(shape, pixels).ok().map(Self::U16)
     }
+
+    /// Build the F32 variant from a float frame's pixels, kept as they
+    /// are — NaN included.
+    pub fn from_f32_pixels(pixels: Vec<f32>, shape: (usize, usize)) -> Option<Self> {
+        Array2::from_shape_vec(shape, pixels).ok().map(Self::F32)
+    }
+
+    /// Build a variant from a frame read off disk: integer frames go
+    /// through [`Self::from_i32_pixels`] when `max_adu` is known and stay
+    /// `I32` otherwise (nothing to narrow against), float frames become
+    /// `F32`.
+    pub fn from_frame_pixels(
+        pixels: FramePixels,
+        shape: (usize, usize),
+        max_adu: Option<u32>,
+    ) -> Option<Self> {
+        match (pixels, max_adu) {
+            (FramePixels::Int(p), Some(max_adu)) => Self::from_i32_pixels(p, shape, max_adu),
+            (FramePixels::Int(p), None) => Array2::from_shape_vec(shape, p).ok().map(Self::I32),
+            (FramePixels::Float(p), _) => Self::from_f32_pixels(p, shape),
+        }
+    }
 }
 
 /// A cached image plus the metadata tools need to make sense of it.
@@ -516,16 +550,19 @@ impl ImageCache {
 // Disk-fallback resolution helpers
 // ---------------------------------------------------------------------------
 
-/// Filename match: `<uuid8>.fits` (greenfield) or `<base>_<uuid8>.fits`
-/// (future operator-template form). Must match exactly at the suffix; we
-/// disambiguate by reading FITS `DOC_ID` afterwards, but the prefilter
-/// keeps the candidate set small.
+/// Filename match: `<uuid8>.<ext>` (greenfield) or `<base>_<uuid8>.<ext>`
+/// (operator-template form), for either frame extension
+/// ([`super::FRAME_EXTENSIONS`]). Must match exactly at the suffix; we
+/// disambiguate by reading the frame's `DOC_ID` afterwards, but the
+/// prefilter keeps the candidate set small.
 fn matches_uuid8_suffix(name: &str, uuid8: &str) -> bool {
-    let needle = format!("{uuid8}.fits");
-    name == needle || name.ends_with(&format!("_{needle}"))
+    super::FRAME_EXTENSIONS.iter().any(|ext| {
+        let needle = format!("{uuid8}.{ext}");
+        name == needle || name.ends_with(&format!("_{needle}"))
+    })
 }
 
-/// Find candidate FITS files in `dir` whose name suffix matches the
+/// Find candidate frame files in `dir` whose name suffix matches the
 /// document's UUID-8 prefilter.
 fn find_candidates_by_suffix(dir: &Path, full_uuid: &str) -> Vec<PathBuf> {
     let Some(uuid8) = full_uuid.get(..8) else {
@@ -561,11 +598,11 @@ fn find_candidates_by_suffix(dir: &Path, full_uuid: &str) -> Vec<PathBuf> {
 }
 
 /// Confirm that `fits_path` and its sidecar resolve to the requested
-/// `full_uuid`. FITS `DOC_ID` is the preferred authority; sidecar `id`
-/// is the fallback when the FITS is unreadable. Returns the sidecar
-/// path on match.
+/// `full_uuid`. The frame's `DOC_ID` is the preferred authority;
+/// sidecar `id` is the fallback when the frame is unreadable. Returns
+/// the sidecar path on match.
 fn confirm_candidate(fits_path: &Path, full_uuid: &str) -> Option<PathBuf> {
-    if let Ok(Some(doc_id)) = super::fits::read_fits_doc_id(fits_path) {
+    if let Ok(Some(doc_id)) = super::frame::read_frame_doc_id(fits_path) {
         if doc_id == full_uuid {
             return Some(fits_path.with_extension("json"));
         }
@@ -603,10 +640,10 @@ fn disk_resolve_to_cached_image(dir: &Path, full_uuid: &str) -> Option<CachedIma
             );
             return None;
         };
-        let (pixels, width, height) = match super::fits::read_fits_pixels(&fits_path) {
+        let (pixels, width, height) = match super::frame::read_frame_typed(&fits_path) {
             Ok(t) => t,
             Err(e) => {
-                debug!(?fits_path, error = %e, "disk_resolve: FITS read failed");
+                debug!(?fits_path, error = %e, "disk_resolve: frame read failed");
                 continue;
             }
         };
@@ -621,7 +658,7 @@ fn disk_resolve_to_cached_image(dir: &Path, full_uuid: &str) -> Option<CachedIma
             );
             continue;
         };
-        let cp = CachedPixels::from_i32_pixels(pixels, (width, height), max_adu)?;
+        let cp = CachedPixels::from_frame_pixels(pixels, (width, height), Some(max_adu))?;
         return Some(CachedImage::new(
             cp, wire_w, wire_h, fits_path, max_adu, doc,
         ));
@@ -629,13 +666,13 @@ fn disk_resolve_to_cached_image(dir: &Path, full_uuid: &str) -> Option<CachedIma
     None
 }
 
-/// Map `<base>.fits` → `Some(PathBuf("<base>.json"))`. Returns
-/// `None` when the path doesn't end in `.fits`, mirroring the rp
-/// capture-side filename convention. Case-sensitive — operators
-/// who supply `.FITS` are out of contract.
+/// Map `<base>.fits` or `<base>.xisf` → `Some(PathBuf("<base>.json"))`.
+/// Returns `None` when the path doesn't end in a frame extension,
+/// mirroring the rp capture-side filename convention. Case-sensitive —
+/// operators who supply `.FITS` are out of contract.
 fn derive_sidecar_path(fits_path: &str) -> Option<PathBuf> {
     let path = Path::new(fits_path);
-    if path.extension().and_then(|e| e.to_str()) != Some("fits") {
+    if !super::is_frame_path(path) {
         return None;
     }
     Some(path.with_extension("json"))
@@ -677,6 +714,7 @@ mod tests {
         ExposureDocument {
             target: None,
             frame_type: None,
+            filter: None,
             id: id.to_string(),
             captured_at: "2026-04-30T00:00:00Z".to_string(),
             file_path: format!("/tmp/{id}.fits"),
@@ -687,6 +725,10 @@ mod tests {
             max_adu: None,
             cooler_setpoint_c: None,
             sensor_temperature_c: None,
+            dew_heater_duty_pct: Default::default(),
+            gain: None,
+            offset: None,
+            binning: None,
             optics: None,
             sections: Map::new(),
         }
@@ -732,7 +774,7 @@ mod tests {
         assert_eq!(got.max_adu, 65535);
         match &got.pixels {
             CachedPixels::U16(arr) => assert_eq!(arr[[0, 0]], 42),
-            CachedPixels::I32(_) => panic!("expected u16 variant"),
+            _ => panic!("expected u16 variant"),
         }
     }
 
@@ -809,7 +851,7 @@ mod tests {
         let got = cache.get("doc-i").unwrap();
         match &got.pixels {
             CachedPixels::I32(arr) => assert_eq!(arr[[0, 0]], 100_000),
-            CachedPixels::U16(_) => panic!("expected i32 variant"),
+            _ => panic!("expected i32 variant"),
         }
         assert_eq!(got.max_adu, 1 << 20);
     }
@@ -862,9 +904,17 @@ mod tests {
         let uuid8 = &doc_uuid[..8];
         let fits_path = dir.join(format!("{uuid8}.fits"));
         let sidecar_path = dir.join(format!("{uuid8}.json"));
-        crate::persistence::write_fits_u16(&fits_path, pixels, width, height, doc_uuid)
-            .await
-            .unwrap();
+        crate::persistence::write_fits_u16(
+            &fits_path,
+            pixels,
+            width,
+            height,
+            doc_uuid,
+            &[],
+            crate::config::FitsCompression::None,
+        )
+        .await
+        .unwrap();
         let mut doc = dummy_document(doc_uuid);
         doc.file_path = fits_path.to_string_lossy().into_owned();
         doc.width = u32::try_from(width).unwrap();
@@ -896,6 +946,74 @@ mod tests {
         assert!(Arc::ptr_eq(&image, &again));
     }
 
+    #[tokio::test]
+    async fn resolve_rehydrates_an_xisf_frame() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc_uuid = "22222222-1111-1111-1111-111111111111";
+        let xisf_path = dir.path().join("M31_L_001_22222222.xisf");
+        crate::persistence::write_frame_u16(
+            &xisf_path,
+            &[5u16, 6, 7, 8],
+            2,
+            2,
+            doc_uuid,
+            &[],
+            crate::config::FrameEncoding::Xisf(crate::config::XisfCompression::Lz4),
+        )
+        .await
+        .unwrap();
+        let mut doc = dummy_document(doc_uuid);
+        doc.file_path = xisf_path.to_string_lossy().into_owned();
+        doc.width = 2;
+        doc.height = 2;
+        doc.max_adu = Some(65535);
+        std::fs::write(
+            dir.path().join("M31_L_001_22222222.json"),
+            serde_json::to_vec(&doc).unwrap(),
+        )
+        .unwrap();
+        let cache = ImageCache::new(64, 4, dir.path().to_path_buf());
+
+        let image = cache.resolve(doc_uuid).await.expect("disk resolve");
+        assert_eq!((image.width, image.height), (2, 2));
+        assert_eq!(image.fits_path, xisf_path);
+    }
+
+    #[tokio::test]
+    async fn resolve_rehydrates_a_float_frame_as_f32() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc_uuid = "33333333-1111-1111-1111-111111111111";
+        let fits_path = dir.path().join("master_33333333.fits");
+        let doc_id = [rp_fits::writer::Keyword::new(
+            "DOC_ID",
+            rp_fits::writer::KeywordValue::Str(doc_uuid.into()),
+        )
+        .unwrap()];
+        let mut file = std::fs::File::create(&fits_path).unwrap();
+        rp_fits::writer::write_f32_image(&mut file, &[0.5, f32::NAN, 2.0, 3.0], 2, 2, &doc_id)
+            .unwrap();
+        drop(file);
+        let mut doc = dummy_document(doc_uuid);
+        doc.file_path = fits_path.to_string_lossy().into_owned();
+        doc.width = 2;
+        doc.height = 2;
+        doc.max_adu = Some(65535);
+        std::fs::write(
+            dir.path().join("master_33333333.json"),
+            serde_json::to_vec(&doc).unwrap(),
+        )
+        .unwrap();
+        let cache = ImageCache::new(64, 4, dir.path().to_path_buf());
+
+        let image = cache.resolve(doc_uuid).await.expect("disk resolve");
+        let CachedPixels::F32(arr) = &image.pixels else {
+            panic!("expected the f32 variant");
+        };
+        assert_eq!(arr[[0, 0]], 0.5);
+        assert!(arr[[0, 1]].is_nan());
+        assert_eq!(image.pixels.nbytes(), 4 * 4);
+    }
+
     #[tokio::test]
     async fn resolve_returns_none_when_unknown() {
         let dir = tempfile::tempdir().unwrap();
@@ -924,9 +1042,17 @@ mod tests {
         // instead: a manually-renamed legacy file with `_<uuid8>.fits`
         // form sharing the suffix.
         let target_path = dir.path().join("deadbeef.fits");
-        crate::persistence::write_fits_u16(&target_path, &[10u16, 20, 30, 40], 2, 2, target_uuid)
-            .await
-            .unwrap();
+        crate::persistence::write_fits_u16(
+            &target_path,
+            &[10u16, 20, 30, 40],
+            2,
+            2,
+            target_uuid,
+            &[],
+            crate::config::FitsCompression::None,
+        )
+        .await
+        .unwrap();
         let mut target_doc = dummy_document(target_uuid);
         target_doc.file_path = target_path.to_string_lossy().into_owned();
         target_doc.width = 2;
@@ -940,9 +1066,17 @@ mod tests {
 
         // Ghost has the suffix `_deadbeef.fits` but a different DOC_ID.
         let ghost_path = dir.path().join("legacy_deadbeef.fits");
-        crate::persistence::write_fits_u16(&ghost_path, &[99u16; 4], 2, 2, ghost_uuid)
-            .await
-            .unwrap();
+        crate::persistence::write_fits_u16(
+            &ghost_path,
+            &[99u16; 4],
+            2,
+            2,
+            ghost_uuid,
+            &[],
+            crate::config::FitsCompression::None,
+        )
+        .await
+        .unwrap();
         let mut ghost_doc = dummy_document(ghost_uuid);
         ghost_doc.file_path = ghost_path.to_string_lossy().into_owned();
         ghost_doc.width = 2;
@@ -979,9 +1113,17 @@ mod tests {
         let doc_uuid = "22222222-2222-2222-2222-222222222222";
         let uuid8 = &doc_uuid[..8];
         let fits_path = dir.path().join(format!("{uuid8}.fits"));
-        crate::persistence::write_fits_u16(&fits_path, &[0u16; 4], 2, 2, doc_uuid)
-            .await
-            .unwrap();
+        crate::persistence::write_fits_u16(
+            &fits_path,
+            &[0u16; 4],
+            2,
+            2,
+            doc_uuid,
+            &[],
+            crate::config::FitsCompression::None,
+        )
+        .await
+        .unwrap();
         let mut doc = dummy_document(doc_uuid);
         doc.file_path = fits_path.to_string_lossy().into_owned();
         doc.width = 2;
@@ -1129,6 +1271,12 @@ mod tests {
         assert!(!matches_uuid8_suffix("deadbeef.fits", "550e8400"));
         // Wrong extension → reject.
         assert!(!matches_uuid8_suffix("550e8400.json", "550e8400"));
+        // XISF frames match in both forms.
+        assert!(matches_uuid8_suffix("550e8400.xisf", "550e8400"));
+        assert!(matches_uuid8_suffix(
+            "M31_L_5m_001_550e8400.xisf",
+            "550e8400"
+        ));
     }
 
     // ---------------------------------------------------------------
@@ -1139,6 +1287,8 @@ mod tests {
     fn derive_sidecar_path_replaces_fits_with_json() {
         let p = derive_sidecar_path("/data/lights/abcd1234.fits").unwrap();
         assert_eq!(p, PathBuf::from("/data/lights/abcd1234.json"));
+        let p = derive_sidecar_path("/data/lights/abcd1234.xisf").unwrap();
+        assert_eq!(p, PathBuf::from("/data/lights/abcd1234.json"));
     }
 
     #[test]
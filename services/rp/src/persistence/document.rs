@@ -17,6 +17,7 @@
 //! fallback resolution path together provide the "live as long as the file
 //! is on disk" contract.
 
+use std::collections::BTreeMap;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
@@ -68,6 +69,24 @@ pub struct ExposureDocument {
     /// misbehaved identifiable frame by frame.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sensor_temperature_c: Option<f64>,
+    /// Duty of each dew heater rp was driving at capture time, percent,
+    /// keyed by `dew.heaters[].id` (rp.md § Dew Management). Omitted
+    /// when the dew controller is not running.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dew_heater_duty_pct: BTreeMap<String, f64>,
+    /// Best-effort `Gain` read at capture time. Omitted when the read
+    /// fails or the camera does not implement it. With `offset`,
+    /// `binning` and `duration` this is the key a dark frame must match
+    /// to calibrate the light (rp.md § Core Fields).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gain: Option<i32>,
+    /// Best-effort `Offset` read at capture time; omitted like `gain`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i32>,
+    /// Best-effort `BinX`/`BinY` read at capture time, as `"AxB"`.
+    /// Omitted when the read fails.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binning: Option<rp_vocabulary::Binning>,
     /// Optical-train geometry resolved at capture time. Carries both the raw
     /// Alpaca camera readings (`pixel_size_*_um`, `sensor_*_px`) and the
     /// derived pixel scale and FOV that consumers like `plate_solve` and
@@ -89,6 +108,11 @@ pub struct ExposureDocument {
     /// the same condition as `target`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub frame_type: Option<rp_vocabulary::FrameType>,
+    /// The filter in the capturing train's wheel, read best-effort at
+    /// capture time. Omitted for a train with no wheel, a failed read,
+    /// and for `Dark`/`Bias` frames, which are never filter-dependent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
     #[serde(default)]
     pub sections: Map<String, Value>,
 }
@@ -96,7 +120,10 @@ pub struct ExposureDocument {
 /// The exposure document's `target` field (Decision 11). `display_name`/
 /// `ra_hours`/`dec_degrees` are populated only when `slug` resolved
 /// against a real target-store row — `None` for a `Dark`/`Flat`/`Bias`
-/// capture's reserved slug, which names no store entry.
+/// capture's reserved slug, which names no store entry. For a mosaic
+/// panel `slug` is the panel slug, `panel` names the parent row and the
+/// grid cell, and `ra_hours`/`dec_degrees` are the panel's center (rp.md
+/// § Target Store → Mosaics).
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ExposureTarget {
     pub slug: String,
@@ -106,6 +133,17 @@ pub struct ExposureTarget {
     pub ra_hours: Option<f64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub dec_degrees: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub panel: Option<ExposurePanel>,
+}
+
+/// The mosaic panel a frame belongs to: the parent target's slug and
+/// the panel's 1-based grid cell.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExposurePanel {
+    pub parent: String,
+    pub row: u32,
+    pub column: u32,
 }
 
 impl From<&rp_targets::Target> for ExposureTarget {
@@ -115,6 +153,7 @@ impl From<&rp_targets::Target> for ExposureTarget {
             display_name: Some(t.display_name.clone()),
             ra_hours: Some(t.coord.ra_hours()),
             dec_degrees: Some(t.coord.dec_degrees()),
+            panel: None,
         }
     }
 }
@@ -303,9 +342,14 @@ mod tests {
             max_adu: Some(65535),
             cooler_setpoint_c: None,
             sensor_temperature_c: None,
+            dew_heater_duty_pct: Default::default(),
+            gain: None,
+            offset: None,
+            binning: None,
             optics: None,
             target: None,
             frame_type: None,
+            filter: None,
             sections: Map::new(),
         }
     }
@@ -487,6 +531,7 @@ mod tests {
             magnitude: None,
             size_arcmin: None,
             position_angle_degrees: None,
+            mosaic: None,
             priority: 0,
             active: true,
             goals: Vec::new(),
@@ -513,6 +558,7 @@ mod tests {
             display_name: None,
             ra_hours: None,
             dec_degrees: None,
+            panel: None,
         });
         doc.frame_type = Some(rp_vocabulary::FrameType::Dark);
         let body = serde_json::to_string(&doc).unwrap();
@@ -6,38 +6,147 @@
 //!   CFITSIO). The on-disk format defaults to BITPIX=16+BZERO=32768
 //!   for the common 16-bit sensor case; cameras whose `max_adu`
 //!   exceeds `u16::MAX` go through [`write_fits_i32`] instead.
-//! - **Reads always return `Vec<i32>`**. `rp_fits::reader` applies
+//! - **Reads return `Vec<i32>`**. `rp_fits::reader` applies
 //!   BSCALE/BZERO and saturates floats so imaging code (which feeds
 //!   `Array2<i32>` into `measure_basic` etc.) does not need to care
-//!   about on-disk pixel type.
+//!   about on-disk pixel type. The exception is [`read_fits_typed`],
+//!   which keeps a float image (a calibrated master, a stack) as `f32`
+//!   for the analysis tools instead of truncating it.
 //! - **Atomic-write durability** (stage→fsync→rename→fsync-parent)
 //!   lives in `rp_fits::atomic`. This file is just the rp-specific
-//!   layer that stamps `DOC_ID` and translates errors to `RpError`.
+//!   layer that stamps `DOC_ID`, maps an exposure document onto the
+//!   conventional header keywords, and translates errors to `RpError`.
 
 use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 
 use rp_fits::atomic::write_atomic_with;
-use rp_fits::reader::{read_primary_as_i32, read_primary_keyword};
-use rp_fits::writer::{write_i32_image, write_u16_image, Keyword, KeywordValue};
+use rp_fits::reader::{read_primary, read_primary_as_i32, read_primary_keyword};
+use rp_fits::writer::{
+    write_i32_image, write_i32_image_rice, write_u16_image, write_u16_image_rice, Keyword,
+    KeywordValue, StandardHeader,
+};
 use rp_fits::FitsError;
+use rp_vocabulary::FrameType;
 use tracing::debug;
 
+use crate::config::FitsCompression;
 use crate::error::{Result, RpError};
+use crate::persistence::{ExposureDocument, FramePixels};
 
-const DOC_ID_KEY: &str = "DOC_ID";
+pub(super) const DOC_ID_KEY: &str = "DOC_ID";
 
 fn doc_id_keyword(doc_id: &str) -> Result<Keyword> {
     Keyword::new(DOC_ID_KEY, KeywordValue::Str(doc_id.to_string()))
         .map_err(|e| RpError::Imaging(format!("invalid DOC_ID keyword: {e}")))
 }
 
+/// `DOC_ID` first, then `header` minus any `DOC_ID` of its own: the
+/// document id is rp's lineage key and is never caller-supplied.
+pub(super) fn header_cards(doc_id: &str, header: &[Keyword]) -> Result<Vec<Keyword>> {
+    let mut cards = vec![doc_id_keyword(doc_id)?];
+    cards.extend(header.iter().filter(|k| k.key() != DOC_ID_KEY).cloned());
+    Ok(cards)
+}
+
+/// Capture-time readings that belong in the FITS header but not on
+/// the exposure document. Every field is best-effort: `do_capture`
+/// leaves one `None` when its device is absent or the read failed.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderReadings {
+    /// UTC instant the exposure was started (`DATE-OBS`).
+    pub exposure_start: Option<chrono::DateTime<chrono::Utc>>,
+    /// Mount pointing, degrees.
+    pub pointing_deg: Option<(f64, f64)>,
+    /// Mount altitude, degrees; feeds `AIRMASS`.
+    pub altitude_deg: Option<f64>,
+    pub pier_side: Option<&'static str>,
+    pub focuser_position: Option<i32>,
+    pub rotator_angle_deg: Option<f64>,
+    pub bayer_pattern: Option<&'static str>,
+    pub instrument: Option<String>,
+    pub telescope: Option<String>,
+    /// Site latitude / longitude, degrees.
+    pub site_deg: Option<(f64, f64)>,
+}
+
+/// The `IMAGETYP` spelling the common stacking tools recognize.
+const fn image_type(frame_type: FrameType) -> &'static str {
+    match frame_type {
+        FrameType::Light => "Light Frame",
+        FrameType::Dark => "Dark Frame",
+        FrameType::Flat => "Flat Field",
+        FrameType::Bias => "Bias Frame",
+    }
+}
+
+/// Relative airmass at `altitude_deg` (Pickering 2002), which stays
+/// finite down to the horizon. `None` at or below it.
+#[must_use]
+pub fn airmass(altitude_deg: f64) -> Option<f64> {
+    if !altitude_deg.is_finite() || altitude_deg <= 0.0 {
+        return None;
+    }
+    let h = altitude_deg;
+    let apparent = h + 244.0 / (165.0 + 47.0 * h.powf(1.1));
+    Some(1.0 / apparent.to_radians().sin())
+}
+
+/// Map an exposure document plus the capture's live readings onto
+/// the standard header (rp.md § Capture Tool Details → FITS header).
+///
+/// `OBJCTRA`/`OBJCTDEC` prefer the target's catalogue coordinates and
+/// fall back to the mount pointing for an untargeted frame. Pixel
+/// size is scaled by the binning, the convention `XPIXSZ` carries.
+#[must_use]
+pub fn standard_header(doc: &ExposureDocument, readings: &HeaderReadings) -> StandardHeader {
+    let target = doc.target.as_ref();
+    let target_coords = target.and_then(|t| Some((t.ra_hours? * 15.0, t.dec_degrees?)));
+    let pixel_size_um = doc.optics.as_ref().map(|o| {
+        let (bx, by) = doc
+            .binning
+            .map_or((1.0, 1.0), |b| (f64::from(b.x), f64::from(b.y)));
+        (o.pixel_size_x_um * bx, o.pixel_size_y_um * by)
+    });
+    StandardHeader {
+        image_type: doc.frame_type.map(|f| image_type(f).to_string()),
+        exposure_secs: doc.duration.map(|d| d.as_secs_f64()),
+        date_obs: readings
+            .exposure_start
+            .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3f").to_string()),
+        object: target.and_then(|t| t.display_name.clone()),
+        filter: doc.filter.clone(),
+        binning: doc.binning.map(|b| (b.x, b.y)),
+        ccd_temp_c: doc.sensor_temperature_c,
+        set_temp_c: doc.cooler_setpoint_c.map(f64::from),
+        gain: doc.gain,
+        offset: doc.offset,
+        pointing_deg: readings.pointing_deg,
+        object_deg: target_coords.or(readings.pointing_deg),
+        focal_length_mm: doc.optics.as_ref().map(|o| o.focal_length_mm),
+        pixel_size_um,
+        site_deg: readings.site_deg,
+        focuser_position: readings.focuser_position,
+        rotator_angle_deg: readings.rotator_angle_deg,
+        bayer_pattern: readings.bayer_pattern.map(str::to_string),
+        pier_side: readings.pier_side.map(str::to_string),
+        airmass: readings.altitude_deg.and_then(airmass),
+        instrument: readings.instrument.clone(),
+        telescope: readings.telescope.clone(),
+    }
+}
+
 /// Write u16 pixel data as a FITS file (BITPIX=16 + BZERO=32768).
 ///
 /// Atomic and durable: stages to a sibling temp file, fsyncs, renames
 /// onto `path`, fsyncs the parent dir. `doc_id` is stamped into the
-/// primary HDU header as `DOC_ID = '<full-uuid>'`.
+/// primary HDU header as `DOC_ID = '<full-uuid>'`, followed by the
+/// `header` cards (typically [`standard_header`]'s keywords merged
+/// with the operator's `session.fits_keywords`). With
+/// [`FitsCompression::Rice`] the image is Rice tile-compressed instead,
+/// the header cards moving to the compressed HDU; [`read_fits_pixels`]
+/// and [`read_fits_doc_id`] read either form.
 ///
 /// Used by the production capture path for the common 16-bit sensor
 /// case (QHY600 and similar). Cameras whose `max_adu` exceeds 65535
@@ -49,23 +158,30 @@ pub async fn write_fits_u16<P: AsRef<Path>>(
     width: usize,
     height: usize,
     doc_id: &str,
+    header: &[Keyword],
+    compression: FitsCompression,
 ) -> Result<()> {
     let path = path.as_ref().to_path_buf();
     let pixels = pixels.to_vec();
     let doc_id = doc_id.to_string();
+    let header = header.to_vec();
 
     debug!(
         width = width,
         height = height,
         path = %path.display(),
         doc_id = %doc_id,
+        ?compression,
         "writing u16 FITS image"
     );
 
     tokio::task::spawn_blocking(move || {
-        let kw = [doc_id_keyword(&doc_id)?];
-        write_atomic_with(&path, |w| write_u16_image(w, &pixels, width, height, &kw))
-            .map_err(translate_write_err)
+        let kw = header_cards(&doc_id, &header)?;
+        write_atomic_with(&path, |w| match compression {
+            FitsCompression::None => write_u16_image(w, &pixels, width, height, &kw),
+            FitsCompression::Rice => write_u16_image_rice(w, &pixels, width, height, &kw),
+        })
+        .map_err(translate_write_err)
     })
     .await
     .map_err(|e| RpError::Imaging(format!("task join error: {e}")))?
@@ -79,23 +195,30 @@ pub async fn write_fits_i32<P: AsRef<Path>>(
     width: usize,
     height: usize,
     doc_id: &str,
+    header: &[Keyword],
+    compression: FitsCompression,
 ) -> Result<()> {
     let path = path.as_ref().to_path_buf();
     let pixels = pixels.to_vec();
     let doc_id = doc_id.to_string();
+    let header = header.to_vec();
 
     debug!(
         width = width,
         height = height,
         path = %path.display(),
         doc_id = %doc_id,
+        ?compression,
         "writing i32 FITS image"
     );
 
     tokio::task::spawn_blocking(move || {
-        let kw = [doc_id_keyword(&doc_id)?];
-        write_atomic_with(&path, |w| write_i32_image(w, &pixels, width, height, &kw))
-            .map_err(translate_write_err)
+        let kw = header_cards(&doc_id, &header)?;
+        write_atomic_with(&path, |w| match compression {
+            FitsCompression::None => write_i32_image(w, &pixels, width, height, &kw),
+            FitsCompression::Rice => write_i32_image_rice(w, &pixels, width, height, &kw),
+        })
+        .map_err(translate_write_err)
     })
     .await
     .map_err(|e| RpError::Imaging(format!("task join error: {e}")))?
@@ -137,6 +260,36 @@ pub fn read_fits_pixels<P: AsRef<Path>>(path: P) -> Result<(Vec<i32>, usize, usi
     })
 }
 
+/// Read pixel data keeping its kind: an integer image as
+/// [`read_fits_pixels`] returns it, a floating-point one (BITPIX -32 or
+/// -64) as `f32` with BSCALE/BZERO applied and undefined pixels as NaN.
+/// Returns `(pixels, width, height)`.
+pub fn read_fits_typed<P: AsRef<Path>>(path: P) -> Result<(FramePixels, usize, usize)> {
+    let path = path.as_ref();
+    debug!(path = %path.display(), "reading typed FITS pixels");
+    let file = File::open(path).map_err(|e| {
+        RpError::Imaging(format!(
+            "failed to open FITS file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let img = read_primary(BufReader::new(file)).map_err(|e| {
+        RpError::Imaging(format!(
+            "failed to parse FITS file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let (width, height) = (img.width, img.height);
+    let pixels = if img.data.is_float() {
+        FramePixels::Float(img.into_f32())
+    } else {
+        FramePixels::Int(img.into_i32())
+    };
+    Ok((pixels, width, height))
+}
+
 /// Read the `DOC_ID` keyword from a FITS file's primary HDU.
 ///
 /// Returns `Ok(Some(uuid))` when the header is present and is a
@@ -189,7 +342,7 @@ mod tests {
         let path = dir.path().join("test.fits");
 
         let pixels = vec![100u16, 200, 300, 400];
-        write_fits_u16(&path, &pixels, 2, 2, "test-doc")
+        write_fits_u16(&path, &pixels, 2, 2, "test-doc", &[], FitsCompression::None)
             .await
             .unwrap();
 
@@ -207,7 +360,7 @@ mod tests {
         let path = dir.path().join("test.fits");
 
         let pixels = vec![100i32, -200, 0, 1_000_000];
-        write_fits_i32(&path, &pixels, 2, 2, "test-doc")
+        write_fits_i32(&path, &pixels, 2, 2, "test-doc", &[], FitsCompression::None)
             .await
             .unwrap();
 
@@ -221,9 +374,17 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("bad.fits");
 
-        let err = write_fits_u16(&path, &[1u16, 2, 3, 4], 2, 3, "test-doc")
-            .await
-            .unwrap_err();
+        let err = write_fits_u16(
+            &path,
+            &[1u16, 2, 3, 4],
+            2,
+            3,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap_err();
         assert!(
             err.to_string().contains("does not match"),
             "unexpected error: {err}"
@@ -238,17 +399,33 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("nope.fits");
 
-        let err = write_fits_u16(&path, &[], usize::MAX, usize::MAX, "test-doc")
-            .await
-            .unwrap_err();
+        let err = write_fits_u16(
+            &path,
+            &[],
+            usize::MAX,
+            usize::MAX,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap_err();
         assert!(
             err.to_string().contains("dimensions overflow"),
             "unexpected error: {err}"
         );
 
-        let err = write_fits_i32(&path, &[], usize::MAX, usize::MAX, "test-doc")
-            .await
-            .unwrap_err();
+        let err = write_fits_i32(
+            &path,
+            &[],
+            usize::MAX,
+            usize::MAX,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap_err();
         assert!(
             err.to_string().contains("dimensions overflow"),
             "unexpected error: {err}"
@@ -271,14 +448,71 @@ mod tests {
         let path = dir.path().join("img.fits");
         let doc_id = "550e8400-e29b-41d4-a716-446655440000";
 
-        write_fits_u16(&path, &[1u16, 2, 3, 4], 2, 2, doc_id)
-            .await
-            .unwrap();
+        write_fits_u16(
+            &path,
+            &[1u16, 2, 3, 4],
+            2,
+            2,
+            doc_id,
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
 
         let read_back = read_fits_doc_id(&path).unwrap();
         assert_eq!(read_back.as_deref(), Some(doc_id));
     }
 
+    #[tokio::test]
+    async fn rice_compressed_frames_read_back_like_plain_ones() {
+        let dir = tempfile::tempdir().unwrap();
+        let pixels: Vec<u16> = (0..64u16).map(|i| 1000 + i * 3).collect();
+        let u16_path = dir.path().join("u16.fits");
+        write_fits_u16(
+            &u16_path,
+            &pixels,
+            8,
+            8,
+            "u16-doc",
+            &[],
+            FitsCompression::Rice,
+        )
+        .await
+        .unwrap();
+        let (got, w, h) = read_fits_pixels(&u16_path).unwrap();
+        assert_eq!((w, h), (8, 8));
+        assert_eq!(
+            got,
+            pixels.iter().map(|&p| i32::from(p)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            read_fits_doc_id(&u16_path).unwrap().as_deref(),
+            Some("u16-doc")
+        );
+
+        let i32_path = dir.path().join("i32.fits");
+        write_fits_i32(
+            &i32_path,
+            &[-5, 0, 70_000, 3],
+            2,
+            2,
+            "i32-doc",
+            &[],
+            FitsCompression::Rice,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            read_fits_pixels(&i32_path).unwrap().0,
+            vec![-5, 0, 70_000, 3]
+        );
+        assert_eq!(
+            read_fits_doc_id(&i32_path).unwrap().as_deref(),
+            Some("i32-doc")
+        );
+    }
+
     #[test]
     fn read_fits_doc_id_nonexistent() {
         let err = read_fits_doc_id("/nonexistent/path.fits").unwrap_err();
@@ -307,14 +541,180 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn typed_read_keeps_floats_and_integers_apart() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // A calibrated master, as a stacking tool writes it.
+        let master = dir.path().join("master.fits");
+        let mut file = std::fs::File::create(&master).unwrap();
+        rp_fits::writer::write_f32_image(&mut file, &[0.25f32, f32::NAN, 1.5, -0.5], 2, 2, &[])
+            .unwrap();
+        drop(file);
+        let (pixels, w, h) = read_fits_typed(&master).unwrap();
+        assert_eq!((w, h), (2, 2));
+        let FramePixels::Float(v) = pixels else {
+            panic!("expected float pixels, got {pixels:?}");
+        };
+        assert_eq!((v[0], v[2], v[3]), (0.25, 1.5, -0.5));
+        assert!(v[1].is_nan());
+
+        let light = dir.path().join("light.fits");
+        write_fits_u16(
+            &light,
+            &[1, 2, 3, 4],
+            2,
+            2,
+            "doc",
+            &[],
+            FitsCompression::Rice,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            read_fits_typed(&light).unwrap(),
+            (FramePixels::Int(vec![1, 2, 3, 4]), 2, 2)
+        );
+    }
+
+    fn header_value(path: &Path, key: &str) -> Option<KeywordValue> {
+        read_primary_keyword(BufReader::new(File::open(path).unwrap()), key).unwrap()
+    }
+
+    #[tokio::test]
+    async fn header_cards_follow_doc_id_and_cannot_replace_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("header.fits");
+        let header = [
+            Keyword::new("DOC_ID", KeywordValue::Str("forged".into())).unwrap(),
+            Keyword::new("OBSERVER", KeywordValue::Str("Igor".into())).unwrap(),
+        ];
+        write_fits_i32(
+            &path,
+            &[0i32; 4],
+            2,
+            2,
+            "real-doc",
+            &header,
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            read_fits_doc_id(&path).unwrap().as_deref(),
+            Some("real-doc")
+        );
+        assert_eq!(
+            header_value(&path, "OBSERVER"),
+            Some(KeywordValue::Str("Igor".into()))
+        );
+    }
+
+    fn light_doc() -> ExposureDocument {
+        serde_json::from_value(serde_json::json!({
+            "id": "abc",
+            "captured_at": "2026-10-16T03:17:45Z",
+            "file_path": "/data/abc.fits",
+            "width": 100,
+            "height": 100,
+            "duration": "5m",
+            "cooler_setpoint_c": -10,
+            "sensor_temperature_c": -9.8,
+            "gain": 100,
+            "offset": 50,
+            "binning": "2x2",
+            "optics": {
+                "focal_length_mm": 530.0,
+                "pixel_size_x_um": 3.76,
+                "pixel_size_y_um": 3.76,
+                "sensor_width_px": 6248,
+                "sensor_height_px": 4176,
+                "pixel_scale_x_arcsec_per_pixel": 1.46,
+                "pixel_scale_y_arcsec_per_pixel": 1.46,
+                "fov_width_deg": 2.54,
+                "fov_height_deg": 1.7
+            },
+            "target": {
+                "slug": "m31",
+                "display_name": "Andromeda Galaxy",
+                "ra_hours": 0.712_3,
+                "dec_degrees": 41.269
+            },
+            "frame_type": "Light",
+            "filter": "Ha"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn standard_header_mirrors_the_document() {
+        let readings = HeaderReadings {
+            exposure_start: chrono::DateTime::parse_from_rfc3339("2026-10-16T03:12:45.5Z")
+                .ok()
+                .map(|t| t.with_timezone(&chrono::Utc)),
+            pointing_deg: Some((10.7, 41.3)),
+            ..Default::default()
+        };
+        let header = standard_header(&light_doc(), &readings);
+        assert_eq!(header.image_type.as_deref(), Some("Light Frame"));
+        assert_eq!(header.exposure_secs, Some(300.0));
+        assert_eq!(header.date_obs.as_deref(), Some("2026-10-16T03:12:45.500"));
+        assert_eq!(header.object.as_deref(), Some("Andromeda Galaxy"));
+        assert_eq!(header.filter.as_deref(), Some("Ha"));
+        assert_eq!(header.binning, Some((2, 2)));
+        assert_eq!(header.set_temp_c, Some(-10.0));
+        assert_eq!(header.ccd_temp_c, Some(-9.8));
+        assert_eq!(header.gain, Some(100));
+        assert_eq!(header.offset, Some(50));
+        assert_eq!(header.focal_length_mm, Some(530.0));
+        // XPIXSZ carries the binned pixel.
+        assert_eq!(header.pixel_size_um, Some((7.52, 7.52)));
+        // The target's catalogue position, not the pointing.
+        let (ra, dec) = header.object_deg.unwrap();
+        assert!((ra - 10.684_5).abs() < 1e-9 && (dec - 41.269).abs() < 1e-9);
+        assert_eq!(header.pointing_deg, Some((10.7, 41.3)));
+    }
+
+    #[test]
+    fn an_untargeted_frame_names_the_pointing_as_its_object() {
+        let mut doc = light_doc();
+        doc.target = None;
+        doc.frame_type = None;
+        let readings = HeaderReadings {
+            pointing_deg: Some((10.7, 41.3)),
+            ..Default::default()
+        };
+        let header = standard_header(&doc, &readings);
+        assert_eq!(header.image_type, None);
+        assert_eq!(header.object, None);
+        assert_eq!(header.object_deg, Some((10.7, 41.3)));
+    }
+
+    #[test]
+    fn airmass_is_one_at_zenith_and_undefined_below_the_horizon() {
+        assert!((airmass(90.0).unwrap() - 1.0).abs() < 1e-3);
+        assert!((airmass(30.0).unwrap() - 2.0).abs() < 0.01);
+        assert!(airmass(0.0).is_none());
+        assert!(airmass(-5.0).is_none());
+    }
+
     #[tokio::test]
     async fn write_fits_creates_parent_dirs() {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("sub").join("dir").join("image.fits");
 
-        write_fits_u16(&path, &[42u16], 1, 1, "test-doc")
-            .await
-            .unwrap();
+        write_fits_u16(
+            &path,
+            &[42u16],
+            1,
+            1,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
 
         assert!(path.exists());
     }
@@ -333,12 +733,28 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("img.fits");
 
-        write_fits_u16(&path, &[1u16, 2, 3, 4], 2, 2, "test-doc")
-            .await
-            .unwrap();
-        write_fits_u16(&path, &[10u16, 20, 30, 40], 2, 2, "test-doc")
-            .await
-            .unwrap();
+        write_fits_u16(
+            &path,
+            &[1u16, 2, 3, 4],
+            2,
+            2,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
+        write_fits_u16(
+            &path,
+            &[10u16, 20, 30, 40],
+            2,
+            2,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
 
         let (pixels, w, h) = read_fits_pixels(&path).unwrap();
         assert_eq!(pixels, vec![10i32, 20, 30, 40]);
@@ -350,9 +766,17 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("img.fits");
 
-        write_fits_u16(&path, &[1u16, 2, 3, 4], 2, 2, "test-doc")
-            .await
-            .unwrap();
+        write_fits_u16(
+            &path,
+            &[1u16, 2, 3, 4],
+            2,
+            2,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
 
         assert_eq!(
             entry_names(dir.path()),
@@ -369,9 +793,17 @@ mod tests {
         let path = dir.path().join("img.fits");
         std::fs::create_dir(&path).unwrap();
 
-        let err = write_fits_u16(&path, &[1u16, 2, 3, 4], 2, 2, "test-doc")
-            .await
-            .unwrap_err();
+        let err = write_fits_u16(
+            &path,
+            &[1u16, 2, 3, 4],
+            2,
+            2,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap_err();
         assert!(
             err.to_string().contains("failed to write FITS file"),
             "unexpected error: {err}"
@@ -389,9 +821,17 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("bad.fits");
 
-        let err = write_fits_i32(&path, &[1i32, 2, 3, 4], 2, 3, "test-doc")
-            .await
-            .unwrap_err();
+        let err = write_fits_i32(
+            &path,
+            &[1i32, 2, 3, 4],
+            2,
+            3,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap_err();
         assert!(
             err.to_string().contains("does not match"),
             "unexpected error: {err}"
@@ -445,18 +885,34 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("img.fits");
 
-        write_fits_u16(&path, &[1u16, 2, 3, 4], 2, 2, "test-doc")
-            .await
-            .unwrap();
+        write_fits_u16(
+            &path,
+            &[1u16, 2, 3, 4],
+            2,
+            2,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap();
 
         let original_perms = std::fs::metadata(dir.path()).unwrap().permissions();
         let mut readonly = original_perms.clone();
         readonly.set_mode(0o555);
         std::fs::set_permissions(dir.path(), readonly).unwrap();
 
-        let err = write_fits_u16(&path, &[9u16, 9, 9, 9], 2, 2, "test-doc")
-            .await
-            .unwrap_err();
+        let err = write_fits_u16(
+            &path,
+            &[9u16, 9, 9, 9],
+            2,
+            2,
+            "test-doc",
+            &[],
+            FitsCompression::None,
+        )
+        .await
+        .unwrap_err();
 
         // Restore so tempdir can clean up regardless of assertion outcomes.
         std::fs::set_permissions(dir.path(), original_perms).unwrap();
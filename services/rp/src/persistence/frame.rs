@@ -0,0 +1,170 @@
+//! Format-neutral frame I/O. Captured frames are FITS or XISF files
+//! depending on `session.image_format`; this module writes one per the
+//! session's [`FrameEncoding`] and reads one per its extension, so the
+//! capture path, the cache's disk fallback and the planner's progress
+//! scan handle `.fits` and `.xisf` frames alike — including a data
+//! directory that holds both after the option changed between nights.
+
+use std::path::Path;
+
+use rp_fits::writer::Keyword;
+
+use super::{fits, xisf};
+use crate::config::FrameEncoding;
+use crate::error::Result;
+
+/// Extensions of the frame files rp writes, without the dot.
+/// Case-sensitive, like the rest of rp's on-disk conventions.
+pub const FRAME_EXTENSIONS: [&str; 2] = ["fits", "xisf"];
+
+/// Whether `path` names a frame file (as opposed to a sidecar, a WCS
+/// file or anything else sharing its directory).
+#[must_use]
+pub fn is_frame_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| FRAME_EXTENSIONS.contains(&e))
+}
+
+fn is_xisf(path: &Path) -> bool {
+    path.extension().and_then(|e| e.to_str()) == Some("xisf")
+}
+
+/// Write a u16 frame in `encoding`; see [`fits::write_fits_u16`] and
+/// [`xisf::write_xisf_u16`]. The caller names `path` with
+/// [`FrameEncoding::extension`].
+pub async fn write_frame_u16<P: AsRef<Path>>(
+    path: P,
+    pixels: &[u16],
+    width: usize,
+    height: usize,
+    doc_id: &str,
+    header: &[Keyword],
+    encoding: FrameEncoding,
+) -> Result<()> {
+    match encoding {
+        FrameEncoding::Fits(c) => {
+            fits::write_fits_u16(path, pixels, width, height, doc_id, header, c).await
+        }
+        FrameEncoding::Xisf(c) => {
+            xisf::write_xisf_u16(path, pixels, width, height, doc_id, header, c).await
+        }
+    }
+}
+
+/// Write an i32 frame in `encoding`; see [`fits::write_fits_i32`] and
+/// [`xisf::write_xisf_i32`].
+pub async fn write_frame_i32<P: AsRef<Path>>(
+    path: P,
+    pixels: &[i32],
+    width: usize,
+    height: usize,
+    doc_id: &str,
+    header: &[Keyword],
+    encoding: FrameEncoding,
+) -> Result<()> {
+    match encoding {
+        FrameEncoding::Fits(c) => {
+            fits::write_fits_i32(path, pixels, width, height, doc_id, header, c).await
+        }
+        FrameEncoding::Xisf(c) => {
+            xisf::write_xisf_i32(path, pixels, width, height, doc_id, header, c).await
+        }
+    }
+}
+
+/// Read a frame's pixels as `(pixels, width, height)`: an `.xisf`
+/// path through [`xisf::read_xisf_pixels`], anything else as FITS.
+pub fn read_frame_pixels<P: AsRef<Path>>(path: P) -> Result<(Vec<i32>, usize, usize)> {
+    let path = path.as_ref();
+    if is_xisf(path) {
+        xisf::read_xisf_pixels(path)
+    } else {
+        fits::read_fits_pixels(path)
+    }
+}
+
+/// A frame's pixels as [`read_frame_typed`] returns them: integer
+/// frames (everything `capture` writes) as `i32`, floating-point ones
+/// (calibrated masters, stacks) as `f32` with undefined pixels as NaN.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FramePixels {
+    Int(Vec<i32>),
+    Float(Vec<f32>),
+}
+
+/// Read a frame's pixels without truncating a float image, dispatching
+/// on the extension like [`read_frame_pixels`]. Returns
+/// `(pixels, width, height)`.
+pub fn read_frame_typed<P: AsRef<Path>>(path: P) -> Result<(FramePixels, usize, usize)> {
+    let path = path.as_ref();
+    if is_xisf(path) {
+        xisf::read_xisf_typed(path)
+    } else {
+        fits::read_fits_typed(path)
+    }
+}
+
+/// Read a frame's `DOC_ID`, dispatching on the extension like
+/// [`read_frame_pixels`].
+pub fn read_frame_doc_id<P: AsRef<Path>>(path: P) -> Result<Option<String>> {
+    let path = path.as_ref();
+    if is_xisf(path) {
+        xisf::read_xisf_doc_id(path)
+    } else {
+        fits::read_fits_doc_id(path)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::config::{FitsCompression, XisfCompression};
+
+    #[test]
+    fn frame_paths_are_fits_or_xisf() {
+        assert!(is_frame_path(Path::new("/data/m31_0001_abcd1234.fits")));
+        assert!(is_frame_path(Path::new("/data/abcd1234.xisf")));
+        assert!(!is_frame_path(Path::new("/data/abcd1234.json")));
+        assert!(!is_frame_path(Path::new("/data/abcd1234.wcs")));
+        assert!(!is_frame_path(Path::new("/data/abcd1234.FITS")));
+        assert!(!is_frame_path(Path::new("/data/abcd1234")));
+    }
+
+    #[tokio::test]
+    async fn reads_follow_the_extension_the_encoding_named() {
+        let dir = tempfile::tempdir().unwrap();
+        for encoding in [
+            FrameEncoding::Fits(FitsCompression::None),
+            FrameEncoding::Fits(FitsCompression::Rice),
+            FrameEncoding::Xisf(XisfCompression::None),
+            FrameEncoding::Xisf(XisfCompression::Zlib),
+        ] {
+            let path = dir
+                .path()
+                .join(format!("{encoding:?}.{}", encoding.extension()));
+            write_frame_u16(&path, &[10, 20, 30, 40], 2, 2, "u16-doc", &[], encoding)
+                .await
+                .unwrap();
+            assert_eq!(
+                read_frame_pixels(&path).unwrap(),
+                (vec![10, 20, 30, 40], 2, 2),
+                "{encoding:?}"
+            );
+            assert_eq!(
+                read_frame_doc_id(&path).unwrap().as_deref(),
+                Some("u16-doc")
+            );
+
+            write_frame_i32(&path, &[1, 70_000], 2, 1, "i32-doc", &[], encoding)
+                .await
+                .unwrap();
+            assert_eq!(read_frame_pixels(&path).unwrap(), (vec![1, 70_000], 2, 1));
+            assert_eq!(
+                read_frame_doc_id(&path).unwrap().as_deref(),
+                Some("i32-doc")
+            );
+        }
+    }
+}
@@ -1,8 +1,8 @@
-//! Persistence layer: FITS I/O, the unified image+document cache, and
-//! exposure-document storage.
+//! Persistence layer: FITS and XISF frame I/O, the unified
+//! image+document cache, and exposure-document storage.
 //!
 //! As of Phase 7 (`docs/plans/archive/image-evaluation-tools.md`), the cache and
-//! the on-disk FITS+sidecar pair together form the document store: a
+//! the on-disk frame+sidecar pair together form the document store: a
 //! document is addressable by id as long as its files sit in
 //! `<data_directory>`. The lazy filesystem fallback in
 //! [`cache::ImageCache`] reads back entries that were evicted or
@@ -15,10 +15,19 @@
 pub mod cache;
 pub mod document;
 pub mod fits;
+pub mod frame;
+pub mod xisf;
 
 pub use cache::{CachedImage, CachedPixels, ImageCache};
 pub use document::{
     read_sidecar_sync, sidecar_path, write_sidecar, write_sidecar_at, ExposureDocument,
-    ExposureTarget, Optics,
+    ExposurePanel, ExposureTarget, Optics,
+};
+pub use fits::{
+    read_fits_doc_id, read_fits_pixels, read_fits_typed, standard_header, write_fits_i32,
+    write_fits_u16, HeaderReadings,
+};
+pub use frame::{
+    is_frame_path, read_frame_doc_id, read_frame_pixels, read_frame_typed, write_frame_i32,
+    write_frame_u16, FramePixels, FRAME_EXTENSIONS,
 };
-pub use fits::{read_fits_doc_id, read_fits_pixels, write_fits_i32, write_fits_u16};
@@ -0,0 +1,271 @@
+//! XISF file I/O for rp's persistence layer — the
+//! `session.image_format = "xisf"` counterpart of [`super::fits`].
+//!
+//! Same contract as the FITS side: writes go through `rp_fits::xisf`
+//! under the same atomic stage→fsync→rename helper, with `DOC_ID` and
+//! the header cards carried as the image's `FITSKeyword` elements;
+//! reads return `Vec<i32>`, or keep a float image as `f32` through
+//! [`read_xisf_typed`]. XISF has no signed integer sample format,
+//! so [`write_xisf_i32`] stores `UInt32` with negative values clamped
+//! to 0 — a camera never reports a negative ADU.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use rp_fits::atomic::write_atomic_with;
+use rp_fits::writer::{Keyword, KeywordValue};
+use rp_fits::xisf::{
+    read_image, read_image_as_i32, read_keyword, write_u16_image, write_u32_image,
+};
+use tracing::debug;
+
+use super::fits::{header_cards, DOC_ID_KEY};
+use crate::config::XisfCompression;
+use crate::error::{Result, RpError};
+use crate::persistence::FramePixels;
+
+const fn codec(compression: XisfCompression) -> rp_fits::xisf::Compression {
+    match compression {
+        XisfCompression::None => rp_fits::xisf::Compression::None,
+        XisfCompression::Zlib => rp_fits::xisf::Compression::Zlib,
+        XisfCompression::Lz4 => rp_fits::xisf::Compression::Lz4,
+    }
+}
+
+/// Write u16 pixel data as an XISF file (`UInt16`). Atomic and
+/// durable like [`super::fits::write_fits_u16`], with the same
+/// `DOC_ID`-first header.
+pub async fn write_xisf_u16<P: AsRef<Path>>(
+    path: P,
+    pixels: &[u16],
+    width: usize,
+    height: usize,
+    doc_id: &str,
+    header: &[Keyword],
+    compression: XisfCompression,
+) -> Result<()> {
+    let path = path.as_ref().to_path_buf();
+    let pixels = pixels.to_vec();
+    let doc_id = doc_id.to_string();
+    let header = header.to_vec();
+
+    debug!(
+        width = width,
+        height = height,
+        path = %path.display(),
+        doc_id = %doc_id,
+        ?compression,
+        "writing u16 XISF image"
+    );
+
+    tokio::task::spawn_blocking(move || {
+        let kw = header_cards(&doc_id, &header)?;
+        write_atomic_with(&path, |w| {
+            write_u16_image(w, &pixels, width, height, &kw, codec(compression))
+        })
+        .map_err(|e| RpError::Imaging(format!("failed to write XISF file: {e}")))
+    })
+    .await
+    .map_err(|e| RpError::Imaging(format!("task join error: {e}")))?
+}
+
+/// Write i32 pixel data as an XISF file (`UInt32`, negatives clamped
+/// to 0). Used for cameras whose `max_adu` exceeds `u16::MAX`.
+pub async fn write_xisf_i32<P: AsRef<Path>>(
+    path: P,
+    pixels: &[i32],
+    width: usize,
+    height: usize,
+    doc_id: &str,
+    header: &[Keyword],
+    compression: XisfCompression,
+) -> Result<()> {
+    let path = path.as_ref().to_path_buf();
+    let pixels: Vec<u32> = pixels
+        .iter()
+        .map(|&p| u32::try_from(p).unwrap_or(0))
+        .collect();
+    let doc_id = doc_id.to_string();
+    let header = header.to_vec();
+
+    debug!(
+        width = width,
+        height = height,
+        path = %path.display(),
+        doc_id = %doc_id,
+        ?compression,
+        "writing u32 XISF image"
+    );
+
+    tokio::task::spawn_blocking(move || {
+        let kw = header_cards(&doc_id, &header)?;
+        write_atomic_with(&path, |w| {
+            write_u32_image(w, &pixels, width, height, &kw, codec(compression))
+        })
+        .map_err(|e| RpError::Imaging(format!("failed to write XISF file: {e}")))
+    })
+    .await
+    .map_err(|e| RpError::Imaging(format!("task join error: {e}")))?
+}
+
+fn open(path: &Path) -> Result<BufReader<File>> {
+    File::open(path).map(BufReader::new).map_err(|e| {
+        RpError::Imaging(format!(
+            "failed to open XISF file '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Read pixel data from an XISF file, normalised to `i32` the way
+/// [`super::fits::read_fits_pixels`] normalises FITS. Returns
+/// `(pixels, width, height)`.
+pub fn read_xisf_pixels<P: AsRef<Path>>(path: P) -> Result<(Vec<i32>, usize, usize)> {
+    let path = path.as_ref();
+    debug!(path = %path.display(), "reading XISF pixels");
+    read_image_as_i32(open(path)?).map_err(|e| {
+        RpError::Imaging(format!(
+            "failed to parse XISF file '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Read pixel data keeping its kind, as [`super::fits::read_fits_typed`]
+/// does for FITS: `Float32`/`Float64` images as `f32`, integer ones as
+/// [`read_xisf_pixels`] returns them.
+pub fn read_xisf_typed<P: AsRef<Path>>(path: P) -> Result<(FramePixels, usize, usize)> {
+    let path = path.as_ref();
+    debug!(path = %path.display(), "reading typed XISF pixels");
+    let img = read_image(open(path)?).map_err(|e| {
+        RpError::Imaging(format!(
+            "failed to parse XISF file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let (width, height) = (img.width, img.height);
+    let pixels = if img.data.is_float() {
+        FramePixels::Float(img.into_f32())
+    } else {
+        FramePixels::Int(img.into_i32())
+    };
+    Ok((pixels, width, height))
+}
+
+/// Read the `DOC_ID` keyword from an XISF file's header; `Ok(None)`
+/// when it is absent, as [`super::fits::read_fits_doc_id`].
+pub fn read_xisf_doc_id<P: AsRef<Path>>(path: P) -> Result<Option<String>> {
+    let path = path.as_ref();
+    match read_keyword(open(path)?, DOC_ID_KEY) {
+        Ok(None) => Ok(None),
+        Ok(Some(KeywordValue::Str(s))) => Ok(Some(s)),
+        Ok(Some(_)) => Err(RpError::Imaging(
+            "DOC_ID header has non-string value".to_string(),
+        )),
+        Err(e) => Err(RpError::Imaging(format!(
+            "failed to read DOC_ID from '{}': {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn u16_frames_round_trip_with_their_doc_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("img.xisf");
+        let pixels: Vec<u16> = (0..64u16).map(|i| 1000 + i * 3).collect();
+        let header = [Keyword::new("OBJECT", KeywordValue::Str("M 31".into())).unwrap()];
+        for compression in [
+            XisfCompression::None,
+            XisfCompression::Zlib,
+            XisfCompression::Lz4,
+        ] {
+            write_xisf_u16(&path, &pixels, 8, 8, "doc-1", &header, compression)
+                .await
+                .unwrap();
+            let (got, w, h) = read_xisf_pixels(&path).unwrap();
+            assert_eq!((w, h), (8, 8));
+            assert_eq!(
+                got,
+                pixels.iter().map(|&p| i32::from(p)).collect::<Vec<_>>()
+            );
+            assert_eq!(read_xisf_doc_id(&path).unwrap().as_deref(), Some("doc-1"));
+        }
+    }
+
+    #[tokio::test]
+    async fn i32_frames_clamp_negatives_to_zero() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("img.xisf");
+        write_xisf_i32(
+            &path,
+            &[-5, 0, 70_000, i32::MAX],
+            2,
+            2,
+            "doc-2",
+            &[],
+            XisfCompression::None,
+        )
+        .await
+        .unwrap();
+        let (got, _, _) = read_xisf_pixels(&path).unwrap();
+        assert_eq!(got, vec![0, 0, 70_000, i32::MAX]);
+    }
+
+    #[tokio::test]
+    async fn write_errors_name_the_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = write_xisf_u16(
+            dir.path().join("bad.xisf"),
+            &[1, 2, 3],
+            2,
+            2,
+            "doc",
+            &[],
+            XisfCompression::None,
+        )
+        .await
+        .unwrap_err();
+        assert!(
+            err.to_string().contains("failed to write XISF file"),
+            "unexpected error: {err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn typed_read_of_an_integer_frame_is_int() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("img.xisf");
+        write_xisf_u16(&path, &[7, 8], 2, 1, "doc", &[], XisfCompression::Lz4)
+            .await
+            .unwrap();
+        assert_eq!(
+            read_xisf_typed(&path).unwrap(),
+            (FramePixels::Int(vec![7, 8]), 2, 1)
+        );
+    }
+
+    #[test]
+    fn reading_a_fits_file_as_xisf_is_a_parse_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not.xisf");
+        std::fs::write(&path, [b' '; 2880]).unwrap();
+        let err = read_xisf_pixels(&path).unwrap_err();
+        assert!(
+            err.to_string().contains("failed to parse XISF"),
+            "unexpected error: {err}"
+        );
+        assert!(read_xisf_doc_id(&path).is_err());
+        assert!(read_xisf_typed(&path).is_err());
+    }
+}
@@ -15,43 +15,59 @@
 //! could drift.
 
 use chrono::{DateTime, Utc};
-use rp_ephemeris::{Ephemeris, ErfarsEphemeris, IcrsCoord, SideOfPier, Site};
+use rp_ephemeris::{Ephemeris, ErfarsEphemeris, HorizonProfile, IcrsCoord, SideOfPier, Site};
 use serde::Serialize;
 use serde_json::Value;
 
-use super::decision::signed_hour_angle;
+use super::decision::{
+    signed_hour_angle, violated_constraint, Constraint, SchedulingDefaults, SkySnapshot,
+};
 
-/// The `get_target_status` result: sky position for one named target
-/// plus the caller-supplied `progress` (the per-filter map when the
-/// name matches a configured target, `null` otherwise).
+/// The `get_target_status` result: sky position for one named target,
+/// the planner's constraint verdict on it, plus the caller-supplied
+/// `progress` (the per-goal rows when the name matches an active
+/// target-store row, `null` otherwise).
 #[derive(Serialize)]
 struct TargetStatusView<'a> {
     target_name: &'a str,
     altitude_degrees: f64,
     azimuth_degrees: f64,
     hour_angle_hours: f64,
-    /// `null` when the target is circumpolar above `min_altitude` or
-    /// never reaches it on the supplied date.
+    /// `null` when the target is circumpolar above `min_altitude` (and
+    /// the horizon profile) or never clears it on the supplied date.
     time_to_set_seconds: Option<i64>,
+    moon_separation_degrees: f64,
+    moon_illumination_fraction: f64,
+    moon_altitude_degrees: f64,
+    /// The first effective scheduling constraint the target fails right
+    /// now — the same gate `get_next_target` applies — or `null` when
+    /// the planner would consider it.
+    blocked_by: Option<Constraint>,
     progress: Value,
 }
 
 /// Status of a single named target: alt, az, hour-angle, time-to-set,
-/// plus the caller-supplied `progress` (passed through verbatim).
+/// the Moon's geometry and the `constraints` verdict, plus the
+/// caller-supplied `progress` (passed through verbatim). `horizon`
+/// raises the altitude floor for both the verdict and time-to-set.
 pub fn target_status_view(
     site: &Site,
+    horizon: &HorizonProfile,
     target: IcrsCoord,
     target_name: &str,
     now: DateTime<Utc>,
-    min_altitude_degrees: f64,
+    constraints: &SchedulingDefaults,
     progress: Value,
 ) -> Result<Value, String> {
     let eph = ErfarsEphemeris::new();
     let aa = eph
         .alt_az(site, target, now)
         .map_err(|e| format!("alt/az transform failed: {e}"))?;
-    let lst = eph.sidereal_time(site, now).lst_hours;
-    let ha = signed_hour_angle(lst, target.ra_hours);
+    let sky = SkySnapshot::at(&eph, site, now);
+    let ha = signed_hour_angle(sky.lst_hours, target.ra_hours);
+    let blocked_by = violated_constraint(&eph, site, horizon, now, &sky, target, constraints)
+        .map_err(|e| format!("alt/az transform failed: {e}"))?;
+    let min_altitude_degrees = constraints.min_altitude_degrees;
 
     // `rise_set` answers for transits within a single UTC date. A
     // target that was up at the start of the UTC day (transit happened
@@ -71,9 +87,10 @@ pub fn target_status_view(
             }
         })
     };
-    let today_rs = eph.rise_set(site, target, today, min_altitude_degrees);
+    let today_rs = eph.rise_set(site, target, today, min_altitude_degrees, horizon);
     let time_to_set_seconds = pick_set(today_rs).or_else(|| {
-        yesterday.and_then(|d| pick_set(eph.rise_set(site, target, d, min_altitude_degrees)))
+        yesterday
+            .and_then(|d| pick_set(eph.rise_set(site, target, d, min_altitude_degrees, horizon)))
     });
 
     let view = TargetStatusView {
@@ -82,6 +99,10 @@ pub fn target_status_view(
         azimuth_degrees: aa.azimuth_degrees,
         hour_angle_hours: ha,
         time_to_set_seconds,
+        moon_separation_degrees: eph.moon_separation(target, now),
+        moon_illumination_fraction: sky.moon.illumination_fraction,
+        moon_altitude_degrees: sky.moon.alt_az.altitude_degrees,
+        blocked_by,
         progress,
     };
     Ok(serde_json::to_value(view).unwrap_or(Value::Null))
@@ -139,7 +160,16 @@ mod tests {
             dec_degrees: 89.2641111,
         };
         let now = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 11, 1, 6, 0, 0).unwrap();
-        let v = target_status_view(&site(), polaris, "Polaris", now, 20.0, Value::Null).unwrap();
+        let v = target_status_view(
+            &site(),
+            &HorizonProfile::default(),
+            polaris,
+            "Polaris",
+            now,
+            &SchedulingDefaults::altitude_only(20.0),
+            Value::Null,
+        )
+        .unwrap();
         assert_eq!(v["target_name"], "Polaris");
         assert!(v["altitude_degrees"].as_f64().is_some());
         assert!(v["azimuth_degrees"].as_f64().is_some());
@@ -150,6 +180,63 @@ mod tests {
         // The progress argument passes through verbatim (null here —
         // Polaris is not a configured target).
         assert!(v["progress"].is_null());
+        // Circumpolar at 20° and no other constraint configured.
+        assert!(v["blocked_by"].is_null());
+        assert!(v["moon_separation_degrees"].as_f64().is_some());
+    }
+
+    #[test]
+    fn target_status_reports_the_constraint_that_blocks_the_target() {
+        // A zero-hour meridian window is only met exactly at transit,
+        // so Polaris at any other hour angle is blocked by it.
+        let polaris = IcrsCoord {
+            ra_hours: 2.5301944,
+            dec_degrees: 89.2641111,
+        };
+        let now = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 11, 1, 6, 0, 0).unwrap();
+        let constraints = SchedulingDefaults {
+            meridian_window_hours: Some(0.0),
+            ..SchedulingDefaults::altitude_only(20.0)
+        };
+        let v = target_status_view(
+            &site(),
+            &HorizonProfile::default(),
+            polaris,
+            "Polaris",
+            now,
+            &constraints,
+            Value::Null,
+        )
+        .unwrap();
+        assert_eq!(v["blocked_by"], "meridian_window");
+    }
+
+    #[test]
+    fn target_status_applies_the_horizon_profile() {
+        // A single-point profile is a flat 60° horizon: Polaris (~47° at
+        // Seattle) clears the 20° floor but never the horizon.
+        let polaris = IcrsCoord {
+            ra_hours: 2.5301944,
+            dec_degrees: 89.2641111,
+        };
+        let now = chrono::TimeZone::with_ymd_and_hms(&Utc, 2026, 11, 1, 6, 0, 0).unwrap();
+        let horizon = HorizonProfile::new(vec![rp_ephemeris::HorizonPoint {
+            azimuth_degrees: 0.0,
+            altitude_degrees: 60.0,
+        }])
+        .unwrap();
+        let v = target_status_view(
+            &site(),
+            &horizon,
+            polaris,
+            "Polaris",
+            now,
+            &SchedulingDefaults::altitude_only(20.0),
+            Value::Null,
+        )
+        .unwrap();
+        assert_eq!(v["blocked_by"], "min_altitude");
+        assert!(v["time_to_set_seconds"].is_null());
     }
 
     #[test]
@@ -5,8 +5,11 @@
 //! deterministically in tests.
 //!
 //! v1 implements five of the rp.md §"Dynamic Planner" decision-logic
-//! bullets: altitude elimination (the first half of bullet 1),
-//! transit preference (bullet 2), progress + filter tie-breaking
+//! bullets: constraint elimination (the first half of bullet 1 — the
+//! altitude floor, raised per azimuth by the site's horizon profile,
+//! plus the target's meridian-window, Moon-illumination
+//! and Moon-separation constraints, each falling back to the config
+//! default), transit preference (bullet 2), progress + filter tie-breaking
 //! (bullets 3–4: survivors within [`TRANSIT_TIE_BAND_HOURS`] of the
 //! best |HA| count as equally transiting, and among them least
 //! completed-to-goal fraction wins, then a next-exposure filter
@@ -16,8 +19,10 @@
 //! all targets exhausted is `EndOfSession`, and otherwise when no
 //! target survives, the Sun-elevation cut-off plus the Sun's
 //! trend separates `WaitForTwilight` (dusk side), `EndOfSession`
-//! (dawn side: the night is over), and `AllBelowMinAltitude` (true
-//! astronomical night). Documented gaps: the set-time half of
+//! (dawn side: the night is over), and a true-astronomical-night
+//! answer naming the constraint that removed the candidates
+//! (`AllBelowMinAltitude`, `OutsideMeridianWindow`, `MoonTooBright`,
+//! `MoonTooClose`). Documented gaps: the set-time half of
 //! bullet 1 and explicit bullet 5 (meridian-flip-aware exposure-fit
 //! check; the choice of smallest-|HA| target satisfies it
 //! indirectly) — tracked in the rp.md §"v1 implementation status"
@@ -27,7 +32,7 @@
 //! a planner plugin can branch without parsing free-form text.
 
 use chrono::{DateTime, Utc};
-use rp_ephemeris::{Ephemeris, Site};
+use rp_ephemeris::{Ephemeris, EphemerisError, HorizonProfile, MoonInfo, Site};
 use rp_targets::IcrsCoord;
 use serde::Serialize;
 
@@ -52,7 +57,7 @@ const SUN_TREND_SAMPLE_SECS: i64 = 60;
 /// them. Half an hour of hour angle costs a negligible fraction of a
 /// degree of altitude near culmination, so trading it for balanced
 /// integration (and fewer filter changes) is free.
-const TRANSIT_TIE_BAND_HOURS: f64 = 0.5;
+pub const TRANSIT_TIE_BAND_HOURS: f64 = 0.5;
 
 /// A planner decision candidate: a target's stable identity (`name` =
 /// its store slug), validated ICRS coordinate, altitude floor, and
@@ -70,6 +75,23 @@ pub struct PlannerTarget {
     /// Per-target altitude floor. `None` falls back to the
     /// planner-wide minimum supplied by the caller.
     pub min_altitude_degrees: Option<f64>,
+    /// Per-target minimum Moon separation (degrees). `None` falls back
+    /// to [`SchedulingDefaults::min_moon_separation_degrees`]. A
+    /// decision input only, skipped on the wire.
+    #[serde(skip)]
+    pub min_moon_separation_degrees: Option<f64>,
+    /// Per-target maximum Moon illumination fraction (`0.0`–`1.0`).
+    /// `None` falls back to
+    /// [`SchedulingDefaults::max_moon_illumination_fraction`]. A
+    /// decision input only, skipped on the wire.
+    #[serde(skip)]
+    pub max_moon_illumination_fraction: Option<f64>,
+    /// Per-target meridian window: the largest |hour angle| (hours)
+    /// the target may be imaged at. `None` falls back to
+    /// [`SchedulingDefaults::meridian_window_hours`]. A decision input
+    /// only, skipped on the wire.
+    #[serde(skip)]
+    pub meridian_window_hours: Option<f64>,
     /// The target's own framing angle (degrees east of north), layer
     /// one of the effective position angle. A decision input only,
     /// skipped on the wire — the recommendation surfaces the resolved
@@ -104,6 +126,120 @@ pub struct ExposureSpec {
     /// wire — a decision input, not part of the tool result.
     #[serde(skip)]
     pub count: Option<u32>,
+    /// The goal's filter-aware Moon avoidance curve, if it has one. A
+    /// decision input only, skipped on the wire: while the Moon is up
+    /// and closer than [`required_moon_separation`], `next_target`
+    /// passes over this entry for the target's next one.
+    #[serde(skip)]
+    pub moon_avoidance: Option<rp_targets::MoonAvoidance>,
+}
+
+/// The config-level fallbacks for a [`PlannerTarget`]'s `None`
+/// scheduling fields: `target_store.default_scheduling`, with the
+/// altitude floor already resolved against the planner-wide
+/// `planner.min_altitude_degrees`. A `None` here means the constraint
+/// is not applied at all.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SchedulingDefaults {
+    pub min_altitude_degrees: f64,
+    pub min_moon_separation_degrees: Option<f64>,
+    pub max_moon_illumination_fraction: Option<f64>,
+    pub meridian_window_hours: Option<f64>,
+}
+
+impl SchedulingDefaults {
+    /// An altitude floor and nothing else — the planner's behaviour
+    /// before the Moon and meridian constraints were enforced.
+    #[must_use]
+    pub const fn altitude_only(min_altitude_degrees: f64) -> Self {
+        Self {
+            min_altitude_degrees,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
+        }
+    }
+
+    /// The config defaults: `default_scheduling`'s fields as given, its
+    /// `min_altitude_degrees` falling back to the planner-wide floor.
+    #[must_use]
+    pub fn from_config(
+        default_scheduling: &rp_targets::SchedulingConstraints,
+        planner_min_altitude_degrees: f64,
+    ) -> Self {
+        Self {
+            min_altitude_degrees: default_scheduling
+                .min_altitude_degrees
+                .unwrap_or(planner_min_altitude_degrees),
+            min_moon_separation_degrees: default_scheduling.min_moon_separation_degrees,
+            max_moon_illumination_fraction: default_scheduling.max_moon_illumination_fraction,
+            meridian_window_hours: default_scheduling.meridian_window_hours,
+        }
+    }
+
+    /// This default set with `scheduling`'s `Some` fields laid over it
+    /// — the effective constraints for one target.
+    #[must_use]
+    pub fn overlay(&self, scheduling: &rp_targets::SchedulingConstraints) -> Self {
+        Self {
+            min_altitude_degrees: scheduling
+                .min_altitude_degrees
+                .unwrap_or(self.min_altitude_degrees),
+            min_moon_separation_degrees: scheduling
+                .min_moon_separation_degrees
+                .or(self.min_moon_separation_degrees),
+            max_moon_illumination_fraction: scheduling
+                .max_moon_illumination_fraction
+                .or(self.max_moon_illumination_fraction),
+            meridian_window_hours: scheduling
+                .meridian_window_hours
+                .or(self.meridian_window_hours),
+        }
+    }
+}
+
+/// A scheduling constraint that removed a candidate, in the order the
+/// elimination step checks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Constraint {
+    /// Below the effective `min_altitude_degrees`, or below the site's
+    /// horizon profile at the target's azimuth, whichever is higher.
+    MinAltitude,
+    /// |hour angle| beyond the effective `meridian_window_hours`.
+    MeridianWindow,
+    /// The Moon is up and more illuminated than the effective
+    /// `max_moon_illumination_fraction`.
+    MoonIllumination,
+    /// The Moon is up and closer than the effective
+    /// `min_moon_separation_degrees`.
+    MoonSeparation,
+    /// The target itself is eligible, but the Moon is up and inside the
+    /// `moon_avoidance` curve of every goal it has left.
+    GoalMoonAvoidance,
+}
+
+impl Constraint {
+    /// The no-survivors reason this constraint reports when it is the
+    /// one that emptied the candidate set under a dark sky.
+    const fn reason(self) -> NextTargetReason {
+        match self {
+            Self::MinAltitude => NextTargetReason::AllBelowMinAltitude,
+            Self::MeridianWindow => NextTargetReason::OutsideMeridianWindow,
+            Self::MoonIllumination => NextTargetReason::MoonTooBright,
+            Self::MoonSeparation | Self::GoalMoonAvoidance => NextTargetReason::MoonTooClose,
+        }
+    }
+}
+
+/// One candidate a scheduling constraint removed, surfaced on the
+/// recommendation so an orchestrator can see *why* a target it expected
+/// was skipped.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EliminatedTarget {
+    /// The target's slug.
+    pub name: String,
+    pub constraint: Constraint,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -112,18 +248,31 @@ pub enum NextTargetReason {
     BestTransitingCandidate,
     NoTargetsConfigured,
     AllBelowMinAltitude,
+    /// Under a dark sky, every remaining candidate sits outside its
+    /// meridian window.
+    OutsideMeridianWindow,
+    /// Under a dark sky, the Moon is up and brighter than the remaining
+    /// candidates' illumination limit.
+    MoonTooBright,
+    /// Under a dark sky, the Moon is up and too close to the remaining
+    /// candidates.
+    MoonTooClose,
     WaitForTwilight,
     EndOfSession,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct NextTargetRecommendation {
-    /// `None` when `reason` is `NoTargetsConfigured`,
-    /// `AllBelowMinAltitude`, `WaitForTwilight`, or `EndOfSession`.
+    /// `None` for every `reason` other than `BestTransitingCandidate`.
     pub target: Option<PlannerTarget>,
     pub reason: NextTargetReason,
+    /// Every candidate a scheduling constraint removed, in target-store
+    /// list order (exhausted targets are not listed — they are done,
+    /// not blocked).
+    pub eliminated: Vec<EliminatedTarget>,
     /// The recommended target's first *incomplete* `exposures[]`
-    /// entry in plan order — what the `filter` / `duration_secs`
+    /// entry in plan order that the Moon does not block tonight (see
+    /// [`first_unblocked_entry`]) — what the `filter` / `duration_secs`
     /// fields of the tool result surface. `None` when there is no
     /// target or its plan is empty (the orchestrator's own exposure
     /// parameters apply).
@@ -140,12 +289,14 @@ pub struct NextTargetRecommendation {
 /// of its arguments, so tests can drive it with a hand-rolled
 /// `Ephemeris` mock, a frozen `now`, and a hand-filled progress
 /// store.
+#[allow(clippy::too_many_arguments)]
 pub fn next_target(
     eph: &impl Ephemeris,
     site: &Site,
+    horizon: &HorizonProfile,
     now: DateTime<Utc>,
     targets: &[PlannerTarget],
-    default_min_altitude_deg: f64,
+    defaults: &SchedulingDefaults,
     train_default_position_angle_deg: Option<f64>,
     progress: &PlanProgress,
 ) -> NextTargetRecommendation {
@@ -153,21 +304,28 @@ pub fn next_target(
         return NextTargetRecommendation {
             target: None,
             reason: NextTargetReason::NoTargetsConfigured,
+            eliminated: Vec::new(),
             exposure: None,
             position_angle_degrees: None,
         };
     }
 
-    // Step 1: eliminate. A target whose computed alt is below
-    // `min_altitude_degrees` (per-target if set, else default) is
-    // dropped, and so is an exhausted one — every `exposures[]`
-    // entry's `count` met per the `record_exposure` counters
-    // (rp.md §"Dynamic Planner" bullet 6's "met its integration
-    // goal"). Set-time elimination (the "will set before one
-    // exposure can complete" half of rp.md §"Dynamic Planner"
-    // bullet 1) is a documented v1 gap — see the §"v1 implementation
-    // status" callout in `docs/services/rp.md`.
-    let mut survivors: Vec<&PlannerTarget> = Vec::new();
+    // Step 1: eliminate. An exhausted target is dropped — every
+    // `exposures[]` entry's `count` met per the `record_exposure`
+    // counters (rp.md §"Dynamic Planner" bullet 6's "met its
+    // integration goal") — and so is one that fails any of its
+    // effective scheduling constraints (per-target value, else the
+    // config default; see `violated_constraint`), or whose every
+    // remaining goal the Moon blocks (`first_unblocked_entry`). The
+    // sky-wide inputs (LST, the Moon) are read once for the whole
+    // candidate set.
+    // Set-time elimination (the "will set before one exposure can
+    // complete" half of rp.md §"Dynamic Planner" bullet 1) is a
+    // documented v1 gap — see the §"v1 implementation status"
+    // callout in `docs/services/rp.md`.
+    let sky = SkySnapshot::at(eph, site, now);
+    let mut survivors: Vec<(&PlannerTarget, Option<&ExposureSpec>)> = Vec::new();
+    let mut eliminated: Vec<EliminatedTarget> = Vec::new();
     for t in targets {
         if progress.is_exhausted(t) {
             tracing::debug!(
@@ -181,8 +339,32 @@ pub fn next_target(
         // bridge — a valid plan coord is always a valid transform
         // input).
         let coords: rp_ephemeris::IcrsCoord = t.coord.into();
-        let aa = match eph.alt_az(site, coords, now) {
-            Ok(aa) => aa,
+        let verdict = violated_constraint(
+            eph,
+            site,
+            horizon,
+            now,
+            &sky,
+            coords,
+            &t.effective(defaults),
+        )
+        .map(|violated| match violated {
+            Some(constraint) => Err(constraint),
+            None => first_unblocked_entry(eph, now, &sky, coords, t, progress),
+        });
+        match verdict {
+            Ok(Ok(entry)) => survivors.push((t, entry)),
+            Ok(Err(constraint)) => {
+                tracing::debug!(
+                    target = %t.name,
+                    ?constraint,
+                    "scheduling constraint failed; eliminated from next_target evaluation"
+                );
+                eliminated.push(EliminatedTarget {
+                    name: t.name.clone(),
+                    constraint,
+                });
+            }
             Err(e) => {
                 // ERFA can refuse the alt/az transform at degenerate
                 // sites (e.g. exactly the pole). Log it so a
@@ -194,12 +376,7 @@ pub fn next_target(
                     error = %e,
                     "alt/az transform failed; skipping target in next_target evaluation"
                 );
-                continue;
             }
-        };
-        let floor = t.min_altitude_degrees.unwrap_or(default_min_altitude_deg);
-        if aa.altitude_degrees >= floor {
-            survivors.push(t);
         }
     }
 
@@ -212,22 +389,23 @@ pub fn next_target(
             return NextTargetRecommendation {
                 target: None,
                 reason: NextTargetReason::EndOfSession,
+                eliminated,
                 exposure: None,
                 position_angle_degrees: None,
             };
         }
-        // Distinguish "the sky is too bright to image" from "all
-        // targets are genuinely below the altitude floor": below the
-        // Sun-altitude threshold for astronomical twilight (-18°,
-        // true astronomical night) every target under its floor is
-        // `AllBelowMinAltitude`. Brighter than that, the Sun's own
-        // trend tells the two bright ends of the night apart: a
-        // climbing Sun (re-sampled `SUN_TREND_SAMPLE_SECS` ahead) is
-        // the dawn side — the night is over, `EndOfSession` — while
-        // a descending Sun matches rp.md's "astronomical dusk has
-        // not yet begun", `WaitForTwilight`. A level Sun (only at
-        // the culminations) ties to waiting, because a wait loop
-        // re-asks and self-corrects while ending a session is final.
+        // Distinguish "the sky is too bright to image" from "the
+        // targets are genuinely blocked": below the Sun-altitude
+        // threshold for astronomical twilight (-18°, true astronomical
+        // night) the answer names the blocking constraint. Brighter
+        // than that, the Sun's own trend tells the two bright ends of
+        // the night apart: a climbing Sun (re-sampled
+        // `SUN_TREND_SAMPLE_SECS` ahead) is the dawn side — the night
+        // is over, `EndOfSession` — while a descending Sun matches
+        // rp.md's "astronomical dusk has not yet begun",
+        // `WaitForTwilight`. A level Sun (only at the culminations)
+        // ties to waiting, because a wait loop re-asks and
+        // self-corrects while ending a session is final.
         let sun_alt = eph.sun_position(site, now).alt_az.altitude_degrees;
         let reason = if sun_alt > ASTRONOMICAL_DUSK_DEG {
             let resample = now + chrono::Duration::seconds(SUN_TREND_SAMPLE_SECS);
@@ -238,11 +416,23 @@ pub fn next_target(
                 NextTargetReason::WaitForTwilight
             }
         } else {
-            NextTargetReason::AllBelowMinAltitude
+            // Dark sky: a Moon or meridian block outranks the altitude
+            // floor, because it is the one the operator can act on
+            // (it names the constraint to relax); the first such
+            // elimination in list order decides. Only when every
+            // candidate is merely low is the answer the generic
+            // `AllBelowMinAltitude`.
+            eliminated
+                .iter()
+                .map(|e| e.constraint)
+                .find(|c| *c != Constraint::MinAltitude)
+                .unwrap_or(Constraint::MinAltitude)
+                .reason()
         };
         return NextTargetRecommendation {
             target: None,
             reason,
+            eliminated,
             exposure: None,
             position_angle_degrees: None,
         };
@@ -252,15 +442,15 @@ pub fn next_target(
     // (bullet 2), with survivors inside `TRANSIT_TIE_BAND_HOURS` of
     // that best |HA| treated as ties for the progress and filter
     // tie-breakers (bullets 3–4) to order: least completed-to-goal
-    // fraction first, then a next exposure whose filter matches the
-    // last recorded frame's, then target-store list order (survivors
-    // keep the store's list order, so the scan's strict `<` is that
-    // final tie-break).
-    let lst = eph.sidereal_time(site, now).lst_hours;
+    // fraction first, then a next exposure (the Moon-aware entry step 1
+    // chose) whose filter matches the last recorded frame's, then
+    // target-store list order (survivors keep the store's list order,
+    // so the scan's strict `<` is that final tie-break).
+    let lst = sky.lst_hours;
     let abs_ha = |t: &PlannerTarget| signed_hour_angle(lst, t.coord.ra_hours()).abs();
     let Some(best_ha) = survivors
         .iter()
-        .map(|t| abs_ha(t))
+        .map(|(t, _)| abs_ha(t))
         .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
     else {
         // Unreachable: the empty-survivors branch returns above. If a
@@ -270,49 +460,37 @@ pub fn next_target(
         return NextTargetRecommendation {
             target: None,
             reason: NextTargetReason::AllBelowMinAltitude,
+            eliminated,
             exposure: None,
             position_angle_degrees: None,
         };
     };
-    let mut chosen: Option<(&PlannerTarget, (f64, bool, f64))> = None;
-    for t in &survivors {
+    let mut chosen: Option<(&PlannerTarget, Option<&ExposureSpec>, (f64, bool, f64))> = None;
+    for &(t, entry) in &survivors {
         let ha = abs_ha(t);
         if ha > best_ha + TRANSIT_TIE_BAND_HOURS {
             continue;
         }
-        let filter_matches_last = match (
-            progress.last_filter_key(),
-            progress.next_incomplete_entry(t),
-        ) {
-            (Some(last), Some(entry)) => {
-                super::progress::filter_key(entry.filter.as_deref()) == last
-            }
-            _ => false,
-        };
-        // Sort key inside the band, in bullet order: least fraction
-        // (bullet 3), then a matching filter (bullet 4 — negated so
-        // `false` = match sorts first), then the in-band |HA| itself
-        // so two otherwise-equal candidates still prefer the closer
-        // transit. Config order wins remaining exact ties via the
-        // strict `<`.
-        let key = (progress.fraction(t), !filter_matches_last, ha);
+        // Config order wins exact key ties via the strict `<`.
+        let key = tie_break_key(progress, t, entry, ha);
         let better = match &chosen {
             None => true,
-            Some((_, k)) => key
+            Some((_, _, k)) => key
                 .partial_cmp(k)
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .is_lt(),
         };
         if better {
-            chosen = Some((t, key));
+            chosen = Some((t, entry, key));
         }
     }
-    let Some((chosen, _)) = chosen else {
+    let Some((chosen, entry, _)) = chosen else {
         // Unreachable for the same reason as above: at least the
         // best-|HA| survivor is inside its own band.
         return NextTargetRecommendation {
             target: None,
             reason: NextTargetReason::AllBelowMinAltitude,
+            eliminated,
             exposure: None,
             position_angle_degrees: None,
         };
@@ -329,11 +507,181 @@ pub fn next_target(
     NextTargetRecommendation {
         target: Some(chosen.clone()),
         reason: NextTargetReason::BestTransitingCandidate,
-        exposure: progress.next_incomplete_entry(chosen).cloned(),
+        eliminated,
+        exposure: entry.cloned(),
         position_angle_degrees,
     }
 }
 
+/// Whether `entry`'s filter matches the last recorded frame's — the
+/// bullet 4 tie-breaker. `false` when either is unknown.
+fn filter_matches_last(progress: &PlanProgress, entry: Option<&ExposureSpec>) -> bool {
+    match (progress.last_filter_key(), entry) {
+        (Some(last), Some(entry)) => super::progress::filter_key(entry.filter.as_deref()) == last,
+        _ => false,
+    }
+}
+
+/// The sort key `next_target` ranks in-band survivors by, smallest
+/// first, in bullet order: least completed-to-goal fraction (bullet 3),
+/// then a next exposure matching the last recorded filter (bullet 4 —
+/// negated so `false` = match sorts first), then the in-band |HA|
+/// itself so two otherwise-equal candidates still prefer the closer
+/// transit.
+#[must_use]
+pub fn tie_break_key(
+    progress: &PlanProgress,
+    target: &PlannerTarget,
+    entry: Option<&ExposureSpec>,
+    abs_hour_angle_hours: f64,
+) -> (f64, bool, f64) {
+    (
+        progress.fraction(target),
+        !filter_matches_last(progress, entry),
+        abs_hour_angle_hours,
+    )
+}
+
+impl PlannerTarget {
+    /// This target's effective constraints: its own `Some` fields,
+    /// else `defaults`.
+    #[must_use]
+    pub fn effective(&self, defaults: &SchedulingDefaults) -> SchedulingDefaults {
+        defaults.overlay(&rp_targets::SchedulingConstraints {
+            min_altitude_degrees: self.min_altitude_degrees,
+            min_moon_separation_degrees: self.min_moon_separation_degrees,
+            max_moon_illumination_fraction: self.max_moon_illumination_fraction,
+            meridian_window_hours: self.meridian_window_hours,
+        })
+    }
+}
+
+/// The target-independent sky state the constraint checks share:
+/// local sidereal time and the Moon, read once per decision.
+#[derive(Debug, Clone, Copy)]
+pub struct SkySnapshot {
+    pub lst_hours: f64,
+    pub moon: MoonInfo,
+}
+
+impl SkySnapshot {
+    #[must_use]
+    pub fn at(eph: &impl Ephemeris, site: &Site, now: DateTime<Utc>) -> Self {
+        Self {
+            lst_hours: eph.sidereal_time(site, now).lst_hours,
+            moon: eph.moon_position(site, now),
+        }
+    }
+
+    /// The Moon only brightens the sky while it is above the horizon;
+    /// a set Moon blocks nothing, however full or close it is.
+    #[must_use]
+    pub fn moon_is_up(&self) -> bool {
+        self.moon.alt_az.altitude_degrees > 0.0
+    }
+}
+
+/// The first constraint in `constraints` that `target` fails at `now`,
+/// checked in [`Constraint`] order, or `None` when the target is
+/// eligible. Shared by `next_target` and `get_target_status` so the
+/// status tool reports exactly the gate the planner applies. The
+/// altitude floor is raised per azimuth by `horizon` (a tree line in
+/// the east eliminates a target there that would clear a flat floor).
+///
+/// # Errors
+///
+/// Propagates an `alt_az` transform failure.
+pub fn violated_constraint(
+    eph: &impl Ephemeris,
+    site: &Site,
+    horizon: &HorizonProfile,
+    now: DateTime<Utc>,
+    sky: &SkySnapshot,
+    target: rp_ephemeris::IcrsCoord,
+    constraints: &SchedulingDefaults,
+) -> Result<Option<Constraint>, EphemerisError> {
+    let aa = eph.alt_az(site, target, now)?;
+    if aa.altitude_degrees < horizon.floor_at(aa.azimuth_degrees, constraints.min_altitude_degrees)
+    {
+        return Ok(Some(Constraint::MinAltitude));
+    }
+    if let Some(window) = constraints.meridian_window_hours {
+        if signed_hour_angle(sky.lst_hours, target.ra_hours).abs() > window {
+            return Ok(Some(Constraint::MeridianWindow));
+        }
+    }
+    if sky.moon_is_up() {
+        if let Some(max) = constraints.max_moon_illumination_fraction {
+            if sky.moon.illumination_fraction > max {
+                return Ok(Some(Constraint::MoonIllumination));
+            }
+        }
+        if let Some(min) = constraints.min_moon_separation_degrees {
+            if eph.moon_separation(target, now) < min {
+                return Ok(Some(Constraint::MoonSeparation));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The Moon separation `avoidance` asks for tonight: the Lorentzian
+/// `distance / (1 + (days_from_full / width)²)` over the Moon's age
+/// from full — the full `distance_degrees` at full Moon, half of it
+/// `width_days` either side, and little by new Moon.
+#[must_use]
+pub fn required_moon_separation(avoidance: &rp_targets::MoonAvoidance, moon: &MoonInfo) -> f64 {
+    let x = moon.days_from_full() / avoidance.width_days;
+    avoidance.distance_degrees / (1.0 + x * x)
+}
+
+/// The entry `next_target` recommends for an otherwise-eligible
+/// `target`: its first incomplete `exposures[]` entry whose
+/// `moon_avoidance` the Moon does not violate, so a broadband goal
+/// blocked tonight yields to the target's narrowband one instead of
+/// taking the whole target out. A set Moon blocks no goal. `Ok(None)`
+/// when the plan has no incomplete entry (empty, or every goal
+/// met); `Err(Constraint::GoalMoonAvoidance)` when it has some and the
+/// Moon blocks every one.
+///
+/// # Errors
+///
+/// [`Constraint::GoalMoonAvoidance`], as above.
+pub fn first_unblocked_entry<'a>(
+    eph: &impl Ephemeris,
+    now: DateTime<Utc>,
+    sky: &SkySnapshot,
+    coords: rp_ephemeris::IcrsCoord,
+    target: &'a PlannerTarget,
+    progress: &PlanProgress,
+) -> Result<Option<&'a ExposureSpec>, Constraint> {
+    let mut remaining = progress.incomplete_entries(target).peekable();
+    if remaining.peek().is_none() {
+        return Ok(None);
+    }
+    if !sky.moon_is_up() {
+        return Ok(remaining.next());
+    }
+    // Read lazily, and once: most plans carry no avoidance curve at all.
+    let mut separation: Option<f64> = None;
+    for entry in remaining {
+        let Some(avoidance) = &entry.moon_avoidance else {
+            return Ok(Some(entry));
+        };
+        let actual = *separation.get_or_insert_with(|| eph.moon_separation(coords, now));
+        if actual >= required_moon_separation(avoidance, &sky.moon) {
+            return Ok(Some(entry));
+        }
+        tracing::debug!(
+            target = %target.name,
+            filter = ?entry.filter,
+            separation_degrees = actual,
+            "goal blocked by its moon avoidance; trying the next goal"
+        );
+    }
+    Err(Constraint::GoalMoonAvoidance)
+}
+
 /// Hour angle of `target_ra_hours` at `lst_hours`, normalised to
 /// the half-open interval `(-12, 12]` (negative = east of meridian,
 /// positive = west).
@@ -363,6 +711,11 @@ impl From<&rp_targets::Target> for PlannerTarget {
             name: t.slug.as_str().to_string(),
             coord: t.coord,
             min_altitude_degrees: t.scheduling.and_then(|s| s.min_altitude_degrees),
+            min_moon_separation_degrees: t.scheduling.and_then(|s| s.min_moon_separation_degrees),
+            max_moon_illumination_fraction: t
+                .scheduling
+                .and_then(|s| s.max_moon_illumination_fraction),
+            meridian_window_hours: t.scheduling.and_then(|s| s.meridian_window_hours),
             position_angle_degrees: t.position_angle_degrees,
             exposures: t
                 .goals
@@ -371,6 +724,7 @@ impl From<&rp_targets::Target> for PlannerTarget {
                     filter: (!g.filter.is_empty()).then(|| g.filter.clone()),
                     duration_secs: g.exposure_duration.as_secs_f64(),
                     count: Some(g.desired_count),
+                    moon_avoidance: g.moon_avoidance,
                 })
                 .collect(),
         }
@@ -418,6 +772,15 @@ mod tests {
         /// reads as the dusk side).
         sun_alt_rate_deg_per_min: f64,
         lst_hours: f64,
+        /// Moon altitude; the default `0.0` is "on the horizon", which
+        /// leaves the Moon constraints unevaluated.
+        moon_alt: f64,
+        moon_illumination: f64,
+        /// Sun-Moon elongation; the default `0.0` is new Moon.
+        moon_phase_degrees: f64,
+        /// (`ra_hours`, `dec_degrees`) → Moon separation in degrees;
+        /// unlisted targets sit 180° from the Moon.
+        moon_sep_overrides: Vec<((f64, f64), f64)>,
     }
 
     impl Ephemeris for MockEphemeris {
@@ -450,8 +813,8 @@ mod tests {
                 azimuth_degrees: 0.0,
             })
         }
-        // The decision logic only consults `alt_az` and
-        // `sun_position`; the remaining trait methods exist to satisfy
+        // The decision logic only consults `alt_az`, `sun_position`,
+        // LST and the Moon; the remaining trait methods exist to satisfy
         // the impl block but are never called from these tests.
         // Mark them coverage-skip so they don't depress the patch %.
         #[cfg_attr(coverage_nightly, coverage(off))]
@@ -470,6 +833,7 @@ mod tests {
             _target: IcrsCoord,
             _date: chrono::NaiveDate,
             _min: f64,
+            _horizon: &HorizonProfile,
         ) -> Option<RiseSet> {
             None
         }
@@ -508,7 +872,6 @@ mod tests {
                 end_utc: None,
             }
         }
-        #[cfg_attr(coverage_nightly, coverage(off))]
         fn moon_position(&self, _site: &Site, _t: DateTime<Utc>) -> MoonInfo {
             MoonInfo {
                 coords: IcrsCoord {
@@ -516,23 +879,37 @@ mod tests {
                     dec_degrees: 0.0,
                 },
                 alt_az: AltAz {
-                    altitude_degrees: 0.0,
+                    altitude_degrees: self.moon_alt,
                     azimuth_degrees: 0.0,
                 },
-                phase_degrees: 0.0,
-                illumination_fraction: 0.5,
+                phase_degrees: self.moon_phase_degrees,
+                illumination_fraction: self.moon_illumination,
             }
         }
-        #[cfg_attr(coverage_nightly, coverage(off))]
-        fn moon_separation(&self, _target: IcrsCoord, _t: DateTime<Utc>) -> f64 {
-            0.0
+        fn moon_separation(&self, target: IcrsCoord, _t: DateTime<Utc>) -> f64 {
+            self.moon_sep_overrides
+                .iter()
+                .find_map(|((ra, dec), sep)| {
+                    ((ra - target.ra_hours).abs() < 1e-9 && (dec - target.dec_degrees).abs() < 1e-9)
+                        .then_some(*sep)
+                })
+                .unwrap_or(180.0)
         }
     }
 
+    /// The 20° planner-wide floor with no Moon or meridian defaults —
+    /// what most decision tests run against.
+    const DEFAULT_FLOOR: SchedulingDefaults = SchedulingDefaults::altitude_only(20.0);
+
     fn site() -> Site {
         Site::new(47.6062, -122.3321).unwrap()
     }
 
+    /// No horizon profile: only the altitude floors apply.
+    fn flat() -> HorizonProfile {
+        HorizonProfile::default()
+    }
+
     fn now() -> DateTime<Utc> {
         Utc.with_ymd_and_hms(2026, 11, 1, 6, 0, 0).unwrap()
     }
@@ -542,9 +919,10 @@ mod tests {
         let rec = next_target(
             &MockEphemeris::default(),
             &site(),
+            &flat(),
             now(),
             &[],
-            20.0,
+            &DEFAULT_FLOOR,
             None,
             &PlanProgress::default(),
         );
@@ -559,20 +937,25 @@ mod tests {
             sun_alt: -25.0, // true astronomical night (sun < -18°)
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -580,6 +963,66 @@ mod tests {
         assert_eq!(rec.reason, NextTargetReason::AllBelowMinAltitude);
     }
 
+    #[test]
+    fn horizon_profile_raises_the_altitude_floor_at_the_targets_azimuth() {
+        // 25° clears the flat 20° floor, but the mock puts every target
+        // due north, where the horizon profile has a 30° tree line.
+        let eph = MockEphemeris {
+            alt_overrides: vec![((0.7123, 41.27), 25.0)],
+            sun_alt: -25.0,
+            lst_hours: 0.7,
+            ..Default::default()
+        };
+        let targets = vec![PlannerTarget {
+            name: "M31".into(),
+            coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
+            min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
+            position_angle_degrees: None,
+            exposures: Vec::new(),
+        }];
+        let trees = HorizonProfile::new(vec![
+            rp_ephemeris::HorizonPoint {
+                azimuth_degrees: 0.0,
+                altitude_degrees: 30.0,
+            },
+            rp_ephemeris::HorizonPoint {
+                azimuth_degrees: 180.0,
+                altitude_degrees: 0.0,
+            },
+        ])
+        .unwrap();
+        let progress = PlanProgress::default();
+
+        let rec = next_target(
+            &eph,
+            &site(),
+            &trees,
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &progress,
+        );
+        assert!(rec.target.is_none());
+        assert_eq!(rec.reason, NextTargetReason::AllBelowMinAltitude);
+        assert_eq!(rec.eliminated[0].constraint, Constraint::MinAltitude);
+
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &progress,
+        );
+        assert_eq!(rec.target.expect("expected a target").name, "M31");
+    }
+
     #[test]
     fn a_level_daytime_sun_is_wait_for_twilight() {
         let eph = MockEphemeris {
@@ -587,20 +1030,25 @@ mod tests {
             sun_alt: 30.0, // the Sun is up and, frozen at rate 0, not climbing
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -618,20 +1066,25 @@ mod tests {
             sun_alt: -10.0,
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -647,20 +1100,25 @@ mod tests {
             sun_alt: -10.0,
             sun_alt_rate_deg_per_min: -0.2,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -676,20 +1134,25 @@ mod tests {
             sun_alt: -10.0,
             sun_alt_rate_deg_per_min: 0.2,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -706,20 +1169,25 @@ mod tests {
             sun_alt: 30.0,
             sun_alt_rate_deg_per_min: 0.2,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -737,20 +1205,25 @@ mod tests {
             sun_alt: -25.0,
             sun_alt_rate_deg_per_min: 0.2,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -764,6 +1237,9 @@ mod tests {
             name: "below floor".into(),
             coord: rp_targets::IcrsCoord::try_new(0.0, 0.0).unwrap(),
             min_altitude_degrees: Some(90.0),
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }]
@@ -789,9 +1265,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site,
+            &flat(),
             t,
             &never_visible_target(),
-            20.0,
+            &DEFAULT_FLOOR,
             None,
             &PlanProgress::default(),
         );
@@ -811,9 +1288,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site,
+            &flat(),
             t,
             &never_visible_target(),
-            20.0,
+            &DEFAULT_FLOOR,
             None,
             &PlanProgress::default(),
         );
@@ -829,20 +1307,25 @@ mod tests {
             sun_alt: -25.0,
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "M31".into(),
             coord: rp_targets::IcrsCoord::try_new(0.7123, 41.27).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -859,12 +1342,16 @@ mod tests {
             sun_alt: -20.0,
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![
             PlannerTarget {
                 name: "M31".into(),
                 coord: rp_targets::IcrsCoord::try_new(0.7, 41.0).unwrap(),
                 min_altitude_degrees: None,
+                min_moon_separation_degrees: None,
+                max_moon_illumination_fraction: None,
+                meridian_window_hours: None,
                 position_angle_degrees: None,
                 exposures: Vec::new(),
             },
@@ -872,6 +1359,9 @@ mod tests {
                 name: "M42".into(),
                 coord: rp_targets::IcrsCoord::try_new(11.0, -5.0).unwrap(),
                 min_altitude_degrees: None,
+                min_moon_separation_degrees: None,
+                max_moon_illumination_fraction: None,
+                meridian_window_hours: None,
                 position_angle_degrees: None,
                 exposures: Vec::new(),
             },
@@ -879,9 +1369,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            20.0,
+            &DEFAULT_FLOOR,
             None,
             &PlanProgress::default(),
         );
@@ -899,6 +1390,9 @@ mod tests {
             name: name.into(),
             coord: rp_targets::IcrsCoord::try_new(ra_hours, 0.0).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures,
         }
@@ -909,6 +1403,7 @@ mod tests {
             filter: Some(filter.into()),
             duration_secs: 60.0,
             count: Some(count),
+            moon_avoidance: None,
         }
     }
 
@@ -920,6 +1415,7 @@ mod tests {
             sun_alt: -25.0,
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         }
     }
 
@@ -931,9 +1427,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &[t],
-            20.0,
+            &DEFAULT_FLOOR,
             Some(254.0),
             &PlanProgress::default(),
         );
@@ -947,9 +1444,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            20.0,
+            &DEFAULT_FLOOR,
             Some(254.0),
             &PlanProgress::default(),
         );
@@ -957,9 +1455,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            20.0,
+            &DEFAULT_FLOOR,
             None,
             &PlanProgress::default(),
         );
@@ -971,9 +1470,10 @@ mod tests {
         let rec = next_target(
             &MockEphemeris::default(),
             &site(),
+            &flat(),
             now(),
             &[],
-            20.0,
+            &DEFAULT_FLOOR,
             Some(254.0),
             &PlanProgress::default(),
         );
@@ -990,7 +1490,16 @@ mod tests {
             target_with_plan("M42", 10.0, vec![spec("L", 1)]),
         ];
         let p = met(&[("M31", &[1])]);
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert_eq!(rec.target.expect("expected a target").name, "M42");
     }
 
@@ -1002,7 +1511,16 @@ mod tests {
         let eph = night_eph(&[12.0]);
         let targets = vec![target_with_plan("M31", 12.0, vec![spec("L", 1)])];
         let p = met(&[("M31", &[1])]);
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert!(rec.target.is_none());
         assert_eq!(rec.reason, NextTargetReason::EndOfSession);
     }
@@ -1018,13 +1536,23 @@ mod tests {
             sun_alt: -25.0,
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 12.0,
+            ..Default::default()
         };
         let targets = vec![
             target_with_plan("done", 12.0, vec![spec("L", 1)]),
             target_with_plan("still rising", 10.0, vec![spec("L", 1)]),
         ];
         let p = met(&[("done", &[1])]);
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert!(rec.target.is_none());
         assert_eq!(rec.reason, NextTargetReason::AllBelowMinAltitude);
     }
@@ -1038,13 +1566,31 @@ mod tests {
             vec![spec("L", 1), spec("R", 1)],
         )];
         let p = PlanProgress::default();
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert_eq!(
             rec.exposure.expect("plan entry").filter.as_deref(),
             Some("L")
         );
         let p = met(&[("M31", &[1, 0])]);
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert_eq!(
             rec.exposure.expect("plan entry").filter.as_deref(),
             Some("R"),
@@ -1063,7 +1609,16 @@ mod tests {
             target_with_plan("fresh", 11.7, vec![spec("L", 2)]),
         ];
         let p = met(&[("closer", &[1])]);
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert_eq!(rec.target.expect("expected a target").name, "fresh");
     }
 
@@ -1079,7 +1634,16 @@ mod tests {
             target_with_plan("red next", 11.7, vec![spec("Red", 5)]),
         ];
         let p = PlanProgress::new(Some("Red".to_string()));
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert_eq!(rec.target.expect("expected a target").name, "red next");
     }
 
@@ -1094,7 +1658,16 @@ mod tests {
             target_with_plan("far and fresh", 10.9, vec![spec("L", 10)]),
         ];
         let p = met(&[("transiting", &[9])]);
-        let rec = next_target(&eph, &site(), now(), &targets, 20.0, None, &p);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &p,
+        );
         assert_eq!(rec.target.expect("expected a target").name, "transiting");
     }
 
@@ -1105,11 +1678,15 @@ mod tests {
             sun_alt: -20.0,
             sun_alt_rate_deg_per_min: 0.0,
             lst_hours: 1.0,
+            ..Default::default()
         };
         let targets = vec![PlannerTarget {
             name: "T1".into(),
             coord: rp_targets::IcrsCoord::try_new(1.0, 0.0).unwrap(),
             min_altitude_degrees: Some(20.0),
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures: Vec::new(),
         }];
@@ -1117,9 +1694,10 @@ mod tests {
         let rec = next_target(
             &eph,
             &site(),
+            &flat(),
             now(),
             &targets,
-            30.0,
+            &SchedulingDefaults::altitude_only(30.0),
             None,
             &PlanProgress::default(),
         );
@@ -1129,6 +1707,344 @@ mod tests {
         );
     }
 
+    // --- Moon and meridian-window constraints ---------------------------
+
+    fn moon_defaults(sep: Option<f64>, illum: Option<f64>) -> SchedulingDefaults {
+        SchedulingDefaults {
+            min_moon_separation_degrees: sep,
+            max_moon_illumination_fraction: illum,
+            ..SchedulingDefaults::altitude_only(20.0)
+        }
+    }
+
+    #[test]
+    fn a_target_too_close_to_a_risen_moon_is_eliminated_and_reported() {
+        // "near moon" transits but sits 15° from an up Moon; the 30°
+        // default separation hands the pick to the farther target.
+        let mut eph = night_eph(&[12.0, 11.0]);
+        eph.moon_alt = 30.0;
+        eph.moon_sep_overrides = vec![((12.0, 0.0), 15.0)];
+        let targets = vec![
+            target_with_plan("near moon", 12.0, Vec::new()),
+            target_with_plan("clear", 11.0, Vec::new()),
+        ];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &moon_defaults(Some(30.0), None),
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.target.expect("expected a target").name, "clear");
+        assert_eq!(
+            rec.eliminated,
+            vec![EliminatedTarget {
+                name: "near moon".into(),
+                constraint: Constraint::MoonSeparation,
+            }]
+        );
+    }
+
+    #[test]
+    fn a_bright_moon_blocking_every_target_is_moon_too_bright() {
+        let mut eph = night_eph(&[12.0]);
+        eph.moon_alt = 40.0;
+        eph.moon_illumination = 0.95;
+        let targets = vec![target_with_plan("M31", 12.0, Vec::new())];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &moon_defaults(None, Some(0.5)),
+            None,
+            &PlanProgress::default(),
+        );
+        assert!(rec.target.is_none());
+        assert_eq!(rec.reason, NextTargetReason::MoonTooBright);
+    }
+
+    #[test]
+    fn a_set_moon_blocks_nothing() {
+        // Full and "close" on the sky, but below the horizon.
+        let mut eph = night_eph(&[12.0]);
+        eph.moon_alt = -10.0;
+        eph.moon_illumination = 1.0;
+        eph.moon_sep_overrides = vec![((12.0, 0.0), 5.0)];
+        let targets = vec![target_with_plan("M31", 12.0, Vec::new())];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &moon_defaults(Some(30.0), Some(0.5)),
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.target.expect("expected a target").name, "M31");
+        assert!(rec.eliminated.is_empty());
+    }
+
+    #[test]
+    fn a_narrowband_target_override_tolerates_the_moon_the_default_rejects() {
+        // The broadband default wants 60° from an 80 %-lit Moon; the
+        // narrowband target's own 20° / 1.0 overrides keep it in play.
+        let mut eph = night_eph(&[12.0, 11.9]);
+        eph.moon_alt = 35.0;
+        eph.moon_illumination = 0.8;
+        eph.moon_sep_overrides = vec![((12.0, 0.0), 40.0), ((11.9, 0.0), 40.0)];
+        let mut narrowband = target_with_plan("narrowband", 11.9, Vec::new());
+        narrowband.min_moon_separation_degrees = Some(20.0);
+        narrowband.max_moon_illumination_fraction = Some(1.0);
+        let targets = vec![target_with_plan("broadband", 12.0, Vec::new()), narrowband];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &moon_defaults(Some(60.0), Some(0.5)),
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.target.expect("expected a target").name, "narrowband");
+        assert_eq!(rec.eliminated[0].constraint, Constraint::MoonIllumination);
+    }
+
+    // --- Per-goal Moon avoidance ----------------------------------------
+
+    /// A broadband-style curve (120° at full, 7-day width) and a
+    /// narrowband-style one (30° at full, 2-day width).
+    const BROADBAND: rp_targets::MoonAvoidance = rp_targets::MoonAvoidance {
+        distance_degrees: 120.0,
+        width_days: 7.0,
+    };
+    const NARROWBAND: rp_targets::MoonAvoidance = rp_targets::MoonAvoidance {
+        distance_degrees: 30.0,
+        width_days: 2.0,
+    };
+
+    fn avoiding(filter: &str, avoidance: rp_targets::MoonAvoidance) -> ExposureSpec {
+        ExposureSpec {
+            moon_avoidance: Some(avoidance),
+            ..spec(filter, 10)
+        }
+    }
+
+    /// A full Moon, up, 60° from every dec-0 target at `ras`.
+    fn full_moon_eph(ras: &[f64]) -> MockEphemeris {
+        let mut eph = night_eph(ras);
+        eph.moon_alt = 30.0;
+        eph.moon_phase_degrees = 180.0;
+        eph.moon_sep_overrides = ras.iter().map(|ra| ((*ra, 0.0), 60.0)).collect();
+        eph
+    }
+
+    #[test]
+    fn the_lorentzian_halves_at_one_width_from_full() {
+        let moon = |phase_degrees| MoonInfo {
+            phase_degrees,
+            ..MockEphemeris::default().moon_position(&site(), now())
+        };
+        assert!((required_moon_separation(&BROADBAND, &moon(180.0)) - 120.0).abs() < 1e-9);
+        // 7 days from full is an elongation of 180 − 7/14.765·180.
+        let seven_days = 180.0 - 7.0 / (29.530_589 / 2.0) * 180.0;
+        assert!((required_moon_separation(&BROADBAND, &moon(seven_days)) - 60.0).abs() < 1e-6);
+        assert!(required_moon_separation(&NARROWBAND, &moon(0.0)) < 1.0);
+    }
+
+    #[test]
+    fn a_moon_blocked_broadband_goal_yields_to_the_targets_narrowband_goal() {
+        // 60° from a full Moon: inside L's 120° curve, clear of Ha's 30°.
+        let eph = full_moon_eph(&[12.0]);
+        let targets = vec![target_with_plan(
+            "M42",
+            12.0,
+            vec![avoiding("L", BROADBAND), avoiding("Ha", NARROWBAND)],
+        )];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.target.expect("expected a target").name, "M42");
+        assert_eq!(rec.exposure.unwrap().filter.as_deref(), Some("Ha"));
+        assert!(rec.eliminated.is_empty());
+    }
+
+    #[test]
+    fn the_same_broadband_goal_is_recommended_by_new_moon() {
+        let mut eph = full_moon_eph(&[12.0]);
+        eph.moon_phase_degrees = 0.0;
+        let targets = vec![target_with_plan(
+            "M42",
+            12.0,
+            vec![avoiding("L", BROADBAND), avoiding("Ha", NARROWBAND)],
+        )];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.exposure.unwrap().filter.as_deref(), Some("L"));
+    }
+
+    #[test]
+    fn a_target_whose_every_remaining_goal_is_moon_blocked_is_eliminated() {
+        // "rgb" only has broadband goals left; "ha" is picked even
+        // though "rgb" transits.
+        let eph = full_moon_eph(&[12.0, 11.0]);
+        let targets = vec![
+            target_with_plan(
+                "rgb",
+                12.0,
+                vec![avoiding("R", BROADBAND), avoiding("G", BROADBAND)],
+            ),
+            target_with_plan("ha", 11.0, vec![avoiding("Ha", NARROWBAND)]),
+        ];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.target.expect("expected a target").name, "ha");
+        assert_eq!(
+            rec.eliminated,
+            vec![EliminatedTarget {
+                name: "rgb".into(),
+                constraint: Constraint::GoalMoonAvoidance,
+            }]
+        );
+
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets[..1],
+            &DEFAULT_FLOOR,
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.reason, NextTargetReason::MoonTooClose);
+    }
+
+    #[test]
+    fn a_met_goal_is_not_what_the_moon_avoidance_walk_falls_back_to() {
+        // Ha's goal is met, so the blocked L goal has nothing to yield to.
+        let eph = full_moon_eph(&[12.0]);
+        let targets = vec![target_with_plan(
+            "M42",
+            12.0,
+            vec![avoiding("Ha", NARROWBAND), avoiding("L", BROADBAND)],
+        )];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &DEFAULT_FLOOR,
+            None,
+            &met(&[("M42", &[10, 0])]),
+        );
+        assert!(rec.target.is_none());
+        assert_eq!(rec.eliminated[0].constraint, Constraint::GoalMoonAvoidance);
+    }
+
+    #[test]
+    fn a_target_outside_its_meridian_window_is_eliminated() {
+        // LST 12 h: "west" is 3 h past the meridian, outside its 2 h
+        // window, and is the only candidate.
+        let eph = night_eph(&[9.0]);
+        let mut t = target_with_plan("west", 9.0, Vec::new());
+        t.meridian_window_hours = Some(2.0);
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &[t],
+            &DEFAULT_FLOOR,
+            None,
+            &PlanProgress::default(),
+        );
+        assert!(rec.target.is_none());
+        assert_eq!(rec.reason, NextTargetReason::OutsideMeridianWindow);
+        assert_eq!(rec.eliminated[0].constraint, Constraint::MeridianWindow);
+    }
+
+    #[test]
+    fn a_moon_block_outranks_the_altitude_floor_in_the_no_survivor_reason() {
+        // One target is merely low, the other is up but Moon-blocked:
+        // the actionable constraint is the one reported.
+        let eph = MockEphemeris {
+            alt_overrides: vec![((12.0, 0.0), 5.0), ((11.0, 0.0), 50.0)],
+            sun_alt: -25.0,
+            lst_hours: 12.0,
+            moon_alt: 20.0,
+            moon_sep_overrides: vec![((11.0, 0.0), 10.0)],
+            ..Default::default()
+        };
+        let targets = vec![
+            target_with_plan("low", 12.0, Vec::new()),
+            target_with_plan("moonlit", 11.0, Vec::new()),
+        ];
+        let rec = next_target(
+            &eph,
+            &site(),
+            &flat(),
+            now(),
+            &targets,
+            &moon_defaults(Some(30.0), None),
+            None,
+            &PlanProgress::default(),
+        );
+        assert_eq!(rec.reason, NextTargetReason::MoonTooClose);
+        assert_eq!(rec.eliminated.len(), 2);
+    }
+
+    #[test]
+    fn scheduling_defaults_fall_back_to_the_planner_floor_and_overlay_per_field() {
+        let config = rp_targets::SchedulingConstraints {
+            min_moon_separation_degrees: Some(45.0),
+            meridian_window_hours: Some(3.0),
+            ..Default::default()
+        };
+        let defaults = SchedulingDefaults::from_config(&config, 25.0);
+        assert_eq!(defaults.min_altitude_degrees, 25.0);
+        assert_eq!(defaults.min_moon_separation_degrees, Some(45.0));
+        let effective = defaults.overlay(&rp_targets::SchedulingConstraints {
+            min_altitude_degrees: Some(40.0),
+            min_moon_separation_degrees: Some(15.0),
+            ..Default::default()
+        });
+        assert_eq!(effective.min_altitude_degrees, 40.0);
+        assert_eq!(effective.min_moon_separation_degrees, Some(15.0));
+        assert_eq!(effective.meridian_window_hours, Some(3.0));
+        assert_eq!(effective.max_moon_illumination_fraction, None);
+    }
+
     #[test]
     fn signed_hour_angle_wraps_correctly() {
         assert!((signed_hour_angle(0.0, 23.5) - 0.5).abs() < 1e-9);
@@ -1150,6 +2066,7 @@ mod tests {
             magnitude: None,
             size_arcmin: None,
             position_angle_degrees: None,
+            mosaic: None,
             priority: 0,
             active: true,
             goals,
@@ -1186,6 +2103,24 @@ mod tests {
         assert_eq!(PlannerTarget::from(&t).min_altitude_degrees, Some(35.0));
     }
 
+    #[test]
+    fn from_store_target_reads_the_moon_and_meridian_overrides() {
+        let t = store_target(
+            "ngc7000",
+            Some(rp_targets::SchedulingConstraints {
+                min_moon_separation_degrees: Some(20.0),
+                max_moon_illumination_fraction: Some(0.9),
+                meridian_window_hours: Some(4.0),
+                ..Default::default()
+            }),
+            Vec::new(),
+        );
+        let planner_target = PlannerTarget::from(&t);
+        assert_eq!(planner_target.min_moon_separation_degrees, Some(20.0));
+        assert_eq!(planner_target.max_moon_illumination_fraction, Some(0.9));
+        assert_eq!(planner_target.meridian_window_hours, Some(4.0));
+    }
+
     #[test]
     fn from_store_target_converts_goals_to_finite_exposure_specs() {
         let goal = rp_targets::AcquisitionGoal {
@@ -1193,6 +2128,7 @@ mod tests {
             binning: rp_targets::Binning { x: 1, y: 1 },
             exposure_duration: std::time::Duration::from_mins(5),
             desired_count: 20,
+            moon_avoidance: None,
         };
         let t = store_target("ngc7000", None, vec![goal]);
         let planner_target = PlannerTarget::from(&t);
@@ -1202,6 +2138,7 @@ mod tests {
                 filter: Some("L".to_string()),
                 duration_secs: 300.0,
                 count: Some(20),
+                moon_avoidance: None,
             }]
         );
     }
@@ -1217,6 +2154,7 @@ mod tests {
         let rec = NextTargetRecommendation {
             target: None,
             reason: NextTargetReason::NoTargetsConfigured,
+            eliminated: Vec::new(),
             exposure: None,
             position_angle_degrees: None,
         };
@@ -1233,18 +2171,24 @@ mod tests {
                 name: "M31".into(),
                 coord: rp_targets::IcrsCoord::try_new(0.7, 41.0).unwrap(),
                 min_altitude_degrees: Some(25.0),
+                min_moon_separation_degrees: None,
+                max_moon_illumination_fraction: None,
+                meridian_window_hours: None,
                 position_angle_degrees: None,
                 exposures: vec![ExposureSpec {
                     filter: Some("Luminance".to_string()),
                     duration_secs: 300.0,
                     count: Some(1),
+                    moon_avoidance: None,
                 }],
             }),
             reason: NextTargetReason::BestTransitingCandidate,
+            eliminated: Vec::new(),
             exposure: Some(ExposureSpec {
                 filter: Some("Red".to_string()),
                 duration_secs: 120.0,
                 count: Some(2),
+                moon_avoidance: None,
             }),
             position_angle_degrees: Some(25.0),
         };
@@ -1283,16 +2227,21 @@ mod tests {
             filter: None,
             duration_secs: 60.0,
             count: None,
+            moon_avoidance: None,
         };
         let rec = NextTargetRecommendation {
             target: Some(PlannerTarget {
                 name: "OSC Field".into(),
                 coord: rp_targets::IcrsCoord::try_new(0.7, 41.0).unwrap(),
                 min_altitude_degrees: None,
+                min_moon_separation_degrees: None,
+                max_moon_illumination_fraction: None,
+                meridian_window_hours: None,
                 position_angle_degrees: None,
                 exposures: vec![entry.clone()],
             }),
             reason: NextTargetReason::BestTransitingCandidate,
+            eliminated: Vec::new(),
             exposure: Some(entry),
             position_angle_degrees: Some(0.0),
         };
@@ -0,0 +1,580 @@
+//! Planner introspection: `explain_planner_decision`.
+//!
+//! Walks every candidate through [`super::decision::next_target`]'s
+//! steps — exhaustion, the constraint eliminations in
+//! [`Constraint`] order, the Moon-aware goal pick, the transit tie
+//! band, and the progress / filter tie-breakers — and reports each step
+//! it reached with the numbers behind it. A target's steps stop at the
+//! one that removed it, exactly as the planner does.
+//!
+//! The verdicts are the decision module's own: `violated_constraint`
+//! decides eliminations, `first_unblocked_entry` the goal pick,
+//! `tie_break_key` the ranking, and the recommendation is
+//! `next_target`'s, so the explanation cannot disagree with what
+//! `get_next_target` answers for the same instant. Only the readings
+//! shown beside each verdict (altitude, |HA|, Moon figures) are
+//! recomputed here.
+
+use chrono::{DateTime, Utc};
+use rp_ephemeris::{Ephemeris, HorizonProfile, Site};
+use serde::Serialize;
+
+use super::decision::{
+    first_unblocked_entry, next_target, signed_hour_angle, tie_break_key, violated_constraint,
+    Constraint, ExposureSpec, NextTargetRecommendation, PlannerTarget, SchedulingDefaults,
+    SkySnapshot, TRANSIT_TIE_BAND_HOURS,
+};
+use super::progress::PlanProgress;
+
+/// Where one candidate ended up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TargetOutcome {
+    /// The recommendation.
+    Selected,
+    /// Inside the transit tie band, ranked below the selected target.
+    LostTieBreak,
+    /// Survived elimination, but its |HA| is outside the tie band of
+    /// the best-transiting survivor.
+    OutsideTransitBand,
+    /// A scheduling constraint removed it; `constraint` names which.
+    Eliminated,
+    /// Every plan entry met its goal.
+    Exhausted,
+    /// The alt/az transform failed; the planner skips such a target.
+    TransformFailed,
+}
+
+/// One step of the decision as it applied to one target, in the order
+/// `next_target` takes them. `passed: false` is the step that removed
+/// the target; no later step is listed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum DecisionStep {
+    /// Bullet 6: a target whose every goal is met is done.
+    Progress {
+        completion_fraction: f64,
+        exhausted: bool,
+    },
+    /// The altitude floor, raised by the horizon profile at the
+    /// target's azimuth.
+    MinAltitude {
+        altitude_degrees: f64,
+        azimuth_degrees: f64,
+        floor_degrees: f64,
+        passed: bool,
+    },
+    /// Only listed when a meridian window applies to the target.
+    MeridianWindow {
+        abs_hour_angle_hours: f64,
+        window_hours: f64,
+        passed: bool,
+    },
+    /// Only listed while the Moon is up and a limit applies.
+    MoonIllumination {
+        illumination_fraction: f64,
+        max_fraction: f64,
+        passed: bool,
+    },
+    /// Only listed while the Moon is up and a limit applies.
+    MoonSeparation {
+        separation_degrees: f64,
+        min_degrees: f64,
+        passed: bool,
+    },
+    /// The first incomplete goal the Moon's avoidance curves leave
+    /// open (`null` for a plan with none left). Fails when the Moon
+    /// blocks every remaining goal.
+    GoalMoonAvoidance {
+        exposure: Option<ExposureSpec>,
+        /// `null` while the Moon is down — it blocks nothing then.
+        moon_separation_degrees: Option<f64>,
+        passed: bool,
+    },
+    /// Bullet 2: survivors within `band_hours` of the best |HA| are
+    /// tied for the tie-breakers.
+    TransitBand {
+        abs_hour_angle_hours: f64,
+        best_abs_hour_angle_hours: f64,
+        band_hours: f64,
+        passed: bool,
+    },
+    /// Bullets 3–4, then the closer transit, then list order: `rank` 1
+    /// is the recommendation.
+    TieBreak {
+        completion_fraction: f64,
+        filter_matches_last: bool,
+        abs_hour_angle_hours: f64,
+        rank: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TargetExplanation {
+    /// The candidate's slug (a mosaic panel's own slug).
+    pub name: String,
+    pub outcome: TargetOutcome,
+    /// Set exactly when `outcome` is `eliminated`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub constraint: Option<Constraint>,
+    /// Set exactly when `outcome` is `transform_failed`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub steps: Vec<DecisionStep>,
+}
+
+/// The sky-wide inputs every target's steps were read against.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct SkyReadings {
+    pub lst_hours: f64,
+    pub sun_altitude_degrees: f64,
+    pub moon_altitude_degrees: f64,
+    pub moon_illumination_fraction: f64,
+    pub moon_up: bool,
+}
+
+/// The `explain_planner_decision` result.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannerExplanation {
+    pub time: DateTime<Utc>,
+    /// Exactly what `get_next_target` returns for the same inputs.
+    pub recommendation: NextTargetRecommendation,
+    pub sky: SkyReadings,
+    /// Filter of the last recorded frame (`""` = unfiltered), `null`
+    /// before the session records one.
+    pub last_filter: Option<String>,
+    /// Every candidate, in target-store list order.
+    pub targets: Vec<TargetExplanation>,
+}
+
+/// Explain the decision [`next_target`] makes for these arguments.
+#[allow(clippy::too_many_arguments)]
+pub fn explain_decision(
+    eph: &impl Ephemeris,
+    site: &Site,
+    horizon: &HorizonProfile,
+    now: DateTime<Utc>,
+    targets: &[PlannerTarget],
+    defaults: &SchedulingDefaults,
+    train_default_position_angle_deg: Option<f64>,
+    progress: &PlanProgress,
+) -> PlannerExplanation {
+    let recommendation = next_target(
+        eph,
+        site,
+        horizon,
+        now,
+        targets,
+        defaults,
+        train_default_position_angle_deg,
+        progress,
+    );
+    let sky = SkySnapshot::at(eph, site, now);
+
+    let mut explained = Vec::with_capacity(targets.len());
+    let mut survivors: Vec<(usize, &PlannerTarget, Option<&ExposureSpec>)> = Vec::new();
+    for target in targets {
+        let (explanation, survivor) =
+            eliminate(eph, site, horizon, now, &sky, target, defaults, progress);
+        if let Some(entry) = survivor {
+            survivors.push((explained.len(), target, entry));
+        }
+        explained.push(explanation);
+    }
+    let selected = recommendation.target.as_ref().map(|t| t.name.as_str());
+    rank(&mut explained, &survivors, &sky, progress, selected);
+
+    PlannerExplanation {
+        time: now,
+        sky: SkyReadings {
+            lst_hours: sky.lst_hours,
+            sun_altitude_degrees: eph.sun_position(site, now).alt_az.altitude_degrees,
+            moon_altitude_degrees: sky.moon.alt_az.altitude_degrees,
+            moon_illumination_fraction: sky.moon.illumination_fraction,
+            moon_up: sky.moon_is_up(),
+        },
+        last_filter: progress.last_filter_key().map(String::from),
+        recommendation,
+        targets: explained,
+    }
+}
+
+fn abs_hour_angle(sky: &SkySnapshot, target: &PlannerTarget) -> f64 {
+    signed_hour_angle(sky.lst_hours, target.coord.ra_hours()).abs()
+}
+
+/// Step 1 for one target: its elimination steps up to the one that
+/// removed it, and — when it survived — the plan entry it would shoot.
+#[allow(clippy::too_many_arguments)]
+fn eliminate<'a>(
+    eph: &impl Ephemeris,
+    site: &Site,
+    horizon: &HorizonProfile,
+    now: DateTime<Utc>,
+    sky: &SkySnapshot,
+    target: &'a PlannerTarget,
+    defaults: &SchedulingDefaults,
+    progress: &PlanProgress,
+) -> (TargetExplanation, Option<Option<&'a ExposureSpec>>) {
+    let mut explanation = TargetExplanation {
+        name: target.name.clone(),
+        outcome: TargetOutcome::Eliminated,
+        constraint: None,
+        error: None,
+        steps: Vec::new(),
+    };
+    let exhausted = progress.is_exhausted(target);
+    explanation.steps.push(DecisionStep::Progress {
+        completion_fraction: progress.fraction(target),
+        exhausted,
+    });
+    if exhausted {
+        explanation.outcome = TargetOutcome::Exhausted;
+        return (explanation, None);
+    }
+
+    let coords: rp_ephemeris::IcrsCoord = target.coord.into();
+    let constraints = target.effective(defaults);
+    let read = violated_constraint(eph, site, horizon, now, sky, coords, &constraints)
+        .and_then(|verdict| eph.alt_az(site, coords, now).map(|aa| (verdict, aa)));
+    let (verdict, aa) = match read {
+        Ok(read) => read,
+        Err(e) => {
+            explanation.outcome = TargetOutcome::TransformFailed;
+            explanation.error = Some(e.to_string());
+            return (explanation, None);
+        }
+    };
+
+    // Every check that applies to this target, in `violated_constraint`'s
+    // order; the list is cut after the one that failed.
+    let passed = |constraint: Constraint| verdict != Some(constraint);
+    let mut checks = vec![(
+        Constraint::MinAltitude,
+        DecisionStep::MinAltitude {
+            altitude_degrees: aa.altitude_degrees,
+            azimuth_degrees: aa.azimuth_degrees,
+            floor_degrees: horizon.floor_at(aa.azimuth_degrees, constraints.min_altitude_degrees),
+            passed: passed(Constraint::MinAltitude),
+        },
+    )];
+    if let Some(window) = constraints.meridian_window_hours {
+        checks.push((
+            Constraint::MeridianWindow,
+            DecisionStep::MeridianWindow {
+                abs_hour_angle_hours: abs_hour_angle(sky, target),
+                window_hours: window,
+                passed: passed(Constraint::MeridianWindow),
+            },
+        ));
+    }
+    if sky.moon_is_up() {
+        if let Some(max) = constraints.max_moon_illumination_fraction {
+            checks.push((
+                Constraint::MoonIllumination,
+                DecisionStep::MoonIllumination {
+                    illumination_fraction: sky.moon.illumination_fraction,
+                    max_fraction: max,
+                    passed: passed(Constraint::MoonIllumination),
+                },
+            ));
+        }
+        if let Some(min) = constraints.min_moon_separation_degrees {
+            checks.push((
+                Constraint::MoonSeparation,
+                DecisionStep::MoonSeparation {
+                    separation_degrees: eph.moon_separation(coords, now),
+                    min_degrees: min,
+                    passed: passed(Constraint::MoonSeparation),
+                },
+            ));
+        }
+    }
+    for (constraint, step) in checks {
+        explanation.steps.push(step);
+        if verdict == Some(constraint) {
+            explanation.constraint = verdict;
+            return (explanation, None);
+        }
+    }
+
+    let pick = first_unblocked_entry(eph, now, sky, coords, target, progress);
+    explanation.steps.push(DecisionStep::GoalMoonAvoidance {
+        exposure: pick.ok().flatten().cloned(),
+        moon_separation_degrees: sky.moon_is_up().then(|| eph.moon_separation(coords, now)),
+        passed: pick.is_ok(),
+    });
+    match pick {
+        Ok(entry) => (explanation, Some(entry)),
+        Err(constraint) => {
+            explanation.constraint = Some(constraint);
+            (explanation, None)
+        }
+    }
+}
+
+/// Step 2 over the survivors: the transit band, then the tie-break
+/// ranking inside it, in `next_target`'s order (a stable sort on the
+/// shared key keeps list order for exact ties, as its strict `<` scan
+/// does).
+fn rank(
+    explained: &mut [TargetExplanation],
+    survivors: &[(usize, &PlannerTarget, Option<&ExposureSpec>)],
+    sky: &SkySnapshot,
+    progress: &PlanProgress,
+    selected: Option<&str>,
+) {
+    let Some(best) = survivors
+        .iter()
+        .map(|(_, t, _)| abs_hour_angle(sky, t))
+        .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+    else {
+        return;
+    };
+    let mut in_band = Vec::new();
+    for &(index, target, entry) in survivors {
+        let ha = abs_hour_angle(sky, target);
+        let passed = ha <= best + TRANSIT_TIE_BAND_HOURS;
+        let Some(explanation) = explained.get_mut(index) else {
+            continue;
+        };
+        explanation.steps.push(DecisionStep::TransitBand {
+            abs_hour_angle_hours: ha,
+            best_abs_hour_angle_hours: best,
+            band_hours: TRANSIT_TIE_BAND_HOURS,
+            passed,
+        });
+        if passed {
+            in_band.push((index, tie_break_key(progress, target, entry, ha)));
+        } else {
+            explanation.outcome = TargetOutcome::OutsideTransitBand;
+        }
+    }
+    in_band.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    for (position, (index, (completion_fraction, filter_mismatch, ha))) in
+        in_band.into_iter().enumerate()
+    {
+        let Some(explanation) = explained.get_mut(index) else {
+            continue;
+        };
+        explanation.steps.push(DecisionStep::TieBreak {
+            completion_fraction,
+            filter_matches_last: !filter_mismatch,
+            abs_hour_angle_hours: ha,
+            rank: position + 1,
+        });
+        explanation.outcome = if selected == Some(explanation.name.as_str()) {
+            TargetOutcome::Selected
+        } else {
+            TargetOutcome::LostTieBreak
+        };
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rp_ephemeris::ErfarsEphemeris;
+    use rp_targets::IcrsCoord;
+
+    fn site() -> Site {
+        Site::new(47.6062, -122.3321).unwrap()
+    }
+
+    /// Deep astronomical night at the site above, Moon down.
+    fn midnight() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 15, 8, 0, 0).unwrap()
+    }
+
+    fn target(name: &str, ra_hours: f64, dec_degrees: f64) -> PlannerTarget {
+        PlannerTarget {
+            name: name.to_string(),
+            coord: IcrsCoord::try_new(ra_hours, dec_degrees).unwrap(),
+            min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
+            position_angle_degrees: None,
+            exposures: Vec::new(),
+        }
+    }
+
+    fn explain(targets: &[PlannerTarget], defaults: &SchedulingDefaults) -> PlannerExplanation {
+        explain_decision(
+            &ErfarsEphemeris::new(),
+            &site(),
+            &HorizonProfile::default(),
+            midnight(),
+            targets,
+            defaults,
+            None,
+            &PlanProgress::default(),
+        )
+    }
+
+    fn lst() -> f64 {
+        ErfarsEphemeris::new()
+            .sidereal_time(&site(), midnight())
+            .lst_hours
+    }
+
+    #[test]
+    fn the_selected_target_is_the_recommendation_and_ranks_first() {
+        let lst = lst();
+        // Both high and near transit: inside the tie band together, so
+        // the closer transit decides.
+        let targets = [
+            target("near", (lst + 0.3).rem_euclid(24.0), 47.0),
+            target("on", lst, 47.0),
+        ];
+        let explanation = explain(&targets, &SchedulingDefaults::altitude_only(20.0));
+
+        let recommended = explanation.recommendation.target.as_ref().unwrap();
+        assert_eq!(recommended.name, "on");
+        let on = &explanation.targets[1];
+        assert_eq!(on.outcome, TargetOutcome::Selected);
+        assert!(matches!(
+            on.steps.last(),
+            Some(DecisionStep::TieBreak { rank: 1, .. })
+        ));
+        assert_eq!(explanation.targets[0].outcome, TargetOutcome::LostTieBreak);
+        assert!(matches!(
+            explanation.targets[0].steps.last(),
+            Some(DecisionStep::TieBreak { rank: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn an_eliminated_target_stops_at_the_failing_step_with_its_numbers() {
+        let lst = lst();
+        let targets = [
+            target("up", lst, 47.0),
+            // Antitransit at the site's latitude: well below 20°.
+            target("down", (lst + 12.0).rem_euclid(24.0), 0.0),
+        ];
+        let explanation = explain(&targets, &SchedulingDefaults::altitude_only(20.0));
+
+        let down = &explanation.targets[1];
+        assert_eq!(down.outcome, TargetOutcome::Eliminated);
+        assert_eq!(down.constraint, Some(Constraint::MinAltitude));
+        assert_eq!(down.steps.len(), 2, "{:?}", down.steps);
+        let DecisionStep::MinAltitude {
+            altitude_degrees,
+            floor_degrees,
+            passed,
+            ..
+        } = down.steps[1]
+        else {
+            panic!("expected the altitude step, got {:?}", down.steps[1]);
+        };
+        assert!(!passed);
+        assert!(altitude_degrees < floor_degrees);
+        assert!((floor_degrees - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn a_meridian_window_step_is_listed_only_when_one_applies() {
+        let lst = lst();
+        let targets = [target("on", lst, 47.0)];
+        let windowed = SchedulingDefaults {
+            meridian_window_hours: Some(2.0),
+            ..SchedulingDefaults::altitude_only(20.0)
+        };
+
+        let plain = explain(&targets, &SchedulingDefaults::altitude_only(20.0));
+        assert!(!plain.targets[0]
+            .steps
+            .iter()
+            .any(|s| matches!(s, DecisionStep::MeridianWindow { .. })));
+        let with_window = explain(&targets, &windowed);
+        assert!(with_window.targets[0].steps.iter().any(|s| matches!(
+            s,
+            DecisionStep::MeridianWindow {
+                window_hours,
+                passed: true,
+                ..
+            } if (*window_hours - 2.0).abs() < 1e-9
+        )));
+    }
+
+    #[test]
+    fn a_survivor_far_from_transit_is_outside_the_band() {
+        let lst = lst();
+        let targets = [
+            target("on", lst, 47.0),
+            target("east", (lst - 3.0).rem_euclid(24.0), 60.0),
+        ];
+        let explanation = explain(&targets, &SchedulingDefaults::altitude_only(10.0));
+
+        let east = &explanation.targets[1];
+        assert_eq!(east.outcome, TargetOutcome::OutsideTransitBand);
+        let Some(DecisionStep::TransitBand {
+            abs_hour_angle_hours,
+            passed,
+            ..
+        }) = east.steps.last()
+        else {
+            panic!("expected the band step last, got {:?}", east.steps);
+        };
+        assert!(!passed);
+        assert!((abs_hour_angle_hours - 3.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn an_exhausted_target_lists_only_its_progress() {
+        let mut done = target("done", lst(), 47.0);
+        done.exposures = vec![ExposureSpec {
+            filter: None,
+            duration_secs: 60.0,
+            count: Some(2),
+            moon_avoidance: None,
+        }];
+        let mut progress = PlanProgress::default();
+        progress.insert(
+            "done",
+            vec![super::super::progress_scan::GoalProgress { good: 2, total: 2 }],
+        );
+        let explanation = explain_decision(
+            &ErfarsEphemeris::new(),
+            &site(),
+            &HorizonProfile::default(),
+            midnight(),
+            std::slice::from_ref(&done),
+            &SchedulingDefaults::altitude_only(20.0),
+            None,
+            &progress,
+        );
+
+        let done = &explanation.targets[0];
+        assert_eq!(done.outcome, TargetOutcome::Exhausted);
+        assert_eq!(
+            done.steps,
+            vec![DecisionStep::Progress {
+                completion_fraction: 1.0,
+                exhausted: true
+            }]
+        );
+    }
+
+    #[test]
+    fn steps_serialize_tagged_by_name() {
+        let step = DecisionStep::TransitBand {
+            abs_hour_angle_hours: 0.25,
+            best_abs_hour_angle_hours: 0.0,
+            band_hours: TRANSIT_TIE_BAND_HOURS,
+            passed: true,
+        };
+        assert_eq!(
+            serde_json::to_value(step).unwrap(),
+            serde_json::json!({
+                "step": "transit_band",
+                "abs_hour_angle_hours": 0.25,
+                "best_abs_hour_angle_hours": 0.0,
+                "band_hours": 0.5,
+                "passed": true,
+            })
+        );
+    }
+}
@@ -12,13 +12,13 @@
 //! [`crate::config::target_store`] (parsing `targets.default_goals`) so
 //! the two stay byte-for-byte consistent.
 
-use rp_targets::{AcquisitionGoal, Binning};
+use rp_targets::{AcquisitionGoal, Binning, MoonAvoidance};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// The wire shape of one `goals[]` entry, as accepted by `add_target`,
 /// `set_goals`, and `targets.default_goals` in config.
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema)]
 pub struct GoalWire {
     pub filter: String,
     /// `"AxB"`, e.g. `"1x1"`, `"2x2"`.
@@ -27,6 +27,50 @@ pub struct GoalWire {
     /// humantime rolls whole minutes up, so it reads back as `"5m"`).
     pub exposure_duration: String,
     pub desired_count: u32,
+    /// Optional filter-aware Moon avoidance (rp.md § Dynamic Planner →
+    /// Moon avoidance). Omitted ⇒ only the target's scheduling
+    /// constraints apply to this goal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub moon_avoidance: Option<MoonAvoidanceWire>,
+}
+
+/// The wire shape of a goal's `moon_avoidance` — the schemars-able
+/// mirror of [`rp_targets::MoonAvoidance`], which stays schemars-free.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize, JsonSchema)]
+pub struct MoonAvoidanceWire {
+    /// Required Moon separation at full Moon, degrees, in `(0, 180]`.
+    pub distance_degrees: f64,
+    /// Days either side of full Moon at which the required separation
+    /// has halved; positive.
+    pub width_days: f64,
+}
+
+impl MoonAvoidanceWire {
+    /// The domain check both `add_target`/`set_goals` and
+    /// `targets.default_goals` apply, as a message for the caller to
+    /// locate.
+    ///
+    /// # Errors
+    ///
+    /// Names the first field outside its domain.
+    pub fn validate(&self) -> Result<MoonAvoidance, String> {
+        if !(self.distance_degrees > 0.0 && self.distance_degrees <= 180.0) {
+            return Err(format!(
+                "moon_avoidance.distance_degrees must be in (0, 180], got {}",
+                self.distance_degrees
+            ));
+        }
+        if !(self.width_days.is_finite() && self.width_days > 0.0) {
+            return Err(format!(
+                "moon_avoidance.width_days must be a finite positive number, got {}",
+                self.width_days
+            ));
+        }
+        Ok(MoonAvoidance {
+            distance_degrees: self.distance_degrees,
+            width_days: self.width_days,
+        })
+    }
 }
 
 impl TryFrom<&GoalWire> for AcquisitionGoal {
@@ -36,8 +80,9 @@ impl TryFrom<&GoalWire> for AcquisitionGoal {
     type Error = String;
 
     /// Parses one wire-format goal, rejecting a `binning` that isn't
-    /// `"AxB"` (per [`Binning`]'s `FromStr`) or an `exposure_duration`
-    /// that isn't a valid humantime string.
+    /// `"AxB"` (per [`Binning`]'s `FromStr`), an `exposure_duration`
+    /// that isn't a valid humantime string, or a `moon_avoidance`
+    /// outside its domain.
     fn try_from(g: &GoalWire) -> Result<Self, Self::Error> {
         Ok(Self {
             filter: g.filter.clone(),
@@ -45,6 +90,11 @@ impl TryFrom<&GoalWire> for AcquisitionGoal {
             exposure_duration: humantime::parse_duration(&g.exposure_duration)
                 .map_err(|e| format!("goal exposure_duration {:?}: {e}", g.exposure_duration))?,
             desired_count: g.desired_count,
+            moon_avoidance: g
+                .moon_avoidance
+                .as_ref()
+                .map(MoonAvoidanceWire::validate)
+                .transpose()?,
         })
     }
 }
@@ -64,6 +114,10 @@ impl From<&AcquisitionGoal> for GoalWire {
             binning: g.binning.to_string(),
             exposure_duration: humantime::format_duration(g.exposure_duration).to_string(),
             desired_count: g.desired_count,
+            moon_avoidance: g.moon_avoidance.map(|m| MoonAvoidanceWire {
+                distance_degrees: m.distance_degrees,
+                width_days: m.width_days,
+            }),
         }
     }
 }
@@ -82,6 +136,7 @@ mod tests {
             binning: binning.to_string(),
             exposure_duration: exposure_duration.to_string(),
             desired_count,
+            moon_avoidance: None,
         }
     }
 
@@ -114,6 +169,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn moon_avoidance_round_trips_and_keeps_the_shapes_locked() {
+        let mut w = wire("L", "1x1", "5m", 20);
+        w.moon_avoidance = Some(MoonAvoidanceWire {
+            distance_degrees: 120.0,
+            width_days: 14.0,
+        });
+        let goal = AcquisitionGoal::try_from(&w).unwrap();
+        assert_eq!(
+            goal.moon_avoidance,
+            Some(MoonAvoidance {
+                distance_degrees: 120.0,
+                width_days: 14.0,
+            })
+        );
+        assert_eq!(GoalWire::from(&goal), w);
+        assert_eq!(
+            serde_json::to_value(&goal).unwrap(),
+            serde_json::to_value(GoalWire::from(&goal)).unwrap()
+        );
+    }
+
+    #[test]
+    fn try_from_wire_rejects_a_non_positive_moon_avoidance_width() {
+        let mut w = wire("Ha", "1x1", "5m", 20);
+        w.moon_avoidance = Some(MoonAvoidanceWire {
+            distance_degrees: 30.0,
+            width_days: 0.0,
+        });
+        let err = AcquisitionGoal::try_from(&w).unwrap_err();
+        assert!(err.contains("width_days"), "{err}");
+    }
+
     #[test]
     fn wire_from_goal_encodes_a_sub_second_bias_exposure() {
         // The motivating case: a sub-second exposure the old whole-second
@@ -2,7 +2,10 @@
 //! lookup) and `rp-ephemeris` (positions, transit, twilight, etc.),
 //! plus the decision logic that composes those primitives into the
 //! convenience tools `get_target_status` / `get_next_target` /
-//! `get_meridian_status`.
+//! `get_meridian_status`, the `preview_night_schedule` simulation
+//! that steps `get_next_target`'s decision across a night, the
+//! `explain_planner_decision` trace of that decision, and the
+//! expansion of mosaic targets into their sibling panels.
 //!
 //! The math and data live in their respective crates; this module is
 //! purely the MCP-tool wrapping plus the small amount of decision
@@ -12,7 +15,10 @@
 pub mod catalog;
 pub mod convenience;
 pub mod decision;
+pub mod explain;
 pub mod goal_wire;
+pub mod mosaic;
 pub mod primitives;
 pub mod progress;
 pub mod progress_scan;
+pub mod schedule;
@@ -0,0 +1,264 @@
+//! Mosaic expansion (rp.md § Target Store → Mosaics): a store row with a
+//! [`rp_targets::MosaicSpec`] stands for one sibling row per panel, and
+//! everything downstream — the progress scan, the decision logic, the
+//! night preview — sees those panel rows rather than the parent.
+//!
+//! Panel identity is the store's ([`rp_targets::MosaicPanel::slug`]);
+//! this module adds the part that needs the rig: each panel's center,
+//! laid out in the imaging train's field of view and rotated to the
+//! target's effective position angle. Offsets are placed on the tangent
+//! plane at the target's `coord` and projected back to the sphere
+//! (inverse gnomonic), which is how a rectilinear camera sees the sky —
+//! adjacent panels keep their overlap at any declination rather than
+//! pinching toward the pole.
+
+use rp_targets::{IcrsCoord, MosaicPanel, MosaicSpec, Target};
+
+use crate::equipment::trains::FieldOfView;
+
+/// The rows `target` stands for: itself for a single-frame target, or
+/// one row per panel for a mosaic — each a clone of the parent under the
+/// panel's slug, with `mosaic` cleared. Panel rows keep the parent's
+/// `coord`; this is the identity-only expansion the progress reads use,
+/// where only the slug (the on-disk `{target}` token) and the goals
+/// matter. [`placed_rows`] is the planner's form.
+#[must_use]
+pub fn panel_rows(target: &Target) -> Vec<Target> {
+    let Some(spec) = target.mosaic else {
+        return vec![target.clone()];
+    };
+    spec.panels()
+        .map(|panel| panel_row(target, panel))
+        .collect()
+}
+
+/// [`panel_rows`] with each panel moved to its center in `fov`, the
+/// grid rotated to `position_angle_deg` (the target's effective framing
+/// angle, degrees east of north). A single-frame target comes back
+/// unchanged. `None` when `target` is a mosaic and `fov` is unknown —
+/// without the field there is nowhere to put a panel, and imaging the
+/// parent's center instead would fill no panel's goals.
+#[must_use]
+pub fn placed_rows(
+    target: &Target,
+    fov: Option<FieldOfView>,
+    position_angle_deg: f64,
+) -> Option<Vec<Target>> {
+    let Some(spec) = target.mosaic else {
+        return Some(vec![target.clone()]);
+    };
+    let fov = fov?;
+    spec.panels()
+        .map(|panel| {
+            let mut row = panel_row(target, panel);
+            row.coord = panel_center(target.coord, &spec, panel, fov, position_angle_deg)?;
+            Some(row)
+        })
+        .collect()
+}
+
+/// One panel's row: the parent under the panel's slug, named after it.
+fn panel_row(parent: &Target, panel: MosaicPanel) -> Target {
+    Target {
+        slug: panel.slug(&parent.slug),
+        display_name: format!(
+            "{} (panel r{}c{})",
+            parent.display_name, panel.row, panel.column
+        ),
+        mosaic: None,
+        ..parent.clone()
+    }
+}
+
+/// The center of `panel` in a `spec` grid around `center`. The step
+/// between adjacent panels is the field along that axis less the
+/// overlap; the grid is centered on `center`, row 1 toward the frame's
+/// top (the position angle) and column 1 toward its left — east at
+/// position angle 0, as a north-up image is displayed.
+///
+/// `None` only if the projection leaves the valid coordinate domain,
+/// which a validated spec over a real field of view cannot do.
+#[must_use]
+pub fn panel_center(
+    center: IcrsCoord,
+    spec: &MosaicSpec,
+    panel: MosaicPanel,
+    fov: FieldOfView,
+    position_angle_deg: f64,
+) -> Option<IcrsCoord> {
+    let keep = 1.0 - spec.overlap_percent / 100.0;
+    // Frame offsets, degrees: `right` toward the frame's right edge,
+    // `up` toward its top.
+    let right = (f64::from(panel.column) - 1.0 - (f64::from(spec.columns) - 1.0) / 2.0)
+        * fov.width_deg
+        * keep;
+    let up =
+        ((f64::from(spec.rows) - 1.0) / 2.0 - (f64::from(panel.row) - 1.0)) * fov.height_deg * keep;
+
+    // Frame axes on the sky: up points along the position angle, right
+    // 90° clockwise from it (west at PA 0).
+    let (sin_pa, cos_pa) = position_angle_deg.to_radians().sin_cos();
+    let xi = (-right * cos_pa + up * sin_pa).to_radians();
+    let eta = (right * sin_pa + up * cos_pa).to_radians();
+
+    // Inverse gnomonic projection about `center`.
+    let ra0 = (center.ra_hours() * 15.0).to_radians();
+    let (sin_dec0, cos_dec0) = center.dec_degrees().to_radians().sin_cos();
+    let denom = cos_dec0 - eta * sin_dec0;
+    let ra = ra0 + xi.atan2(denom);
+    let dec = (sin_dec0 + eta * cos_dec0).atan2(xi.hypot(denom));
+
+    let ra_hours = (ra.to_degrees() / 15.0).rem_euclid(24.0);
+    // `rem_euclid` can round a tiny negative up to exactly 24.0.
+    let ra_hours = if ra_hours >= 24.0 { 0.0 } else { ra_hours };
+    IcrsCoord::try_new(ra_hours, dec.to_degrees()).ok()
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn spec(columns: u32, rows: u32, overlap_percent: f64) -> MosaicSpec {
+        MosaicSpec {
+            columns,
+            rows,
+            overlap_percent,
+        }
+    }
+
+    fn fov(width_deg: f64, height_deg: f64) -> FieldOfView {
+        FieldOfView {
+            width_deg,
+            height_deg,
+        }
+    }
+
+    fn coord(ra_hours: f64, dec_degrees: f64) -> IcrsCoord {
+        IcrsCoord::try_new(ra_hours, dec_degrees).unwrap()
+    }
+
+    fn target(mosaic: Option<MosaicSpec>) -> Target {
+        Target {
+            slug: rp_targets::TargetSlug::new("m31").unwrap(),
+            display_name: "M 31".to_string(),
+            coord: coord(0.7123, 41.2688),
+            catalog_ref: None,
+            object_type: None,
+            magnitude: None,
+            size_arcmin: None,
+            position_angle_degrees: None,
+            mosaic,
+            priority: 0,
+            active: true,
+            goals: Vec::new(),
+            scheduling: None,
+            grading: None,
+            notes: None,
+            created_at: "2026-07-31T00:00:00Z".to_string(),
+            updated_at: "2026-07-31T00:00:00Z".to_string(),
+            created_by: "operator".to_string(),
+            updated_by: "operator".to_string(),
+        }
+    }
+
+    fn panel(row: u32, column: u32) -> MosaicPanel {
+        MosaicPanel { row, column }
+    }
+
+    #[test]
+    fn columns_straddle_the_center_east_to_west_at_position_angle_zero() {
+        let c = coord(0.0, 0.0);
+        let s = spec(2, 1, 0.0);
+        let east = panel_center(c, &s, panel(1, 1), fov(1.0, 1.0), 0.0).unwrap();
+        let west = panel_center(c, &s, panel(1, 2), fov(1.0, 1.0), 0.0).unwrap();
+        // Half a field either side on the equator: ±atan(0.5°) of RA.
+        let half = 0.5_f64.to_radians().atan().to_degrees() / 15.0;
+        assert!((east.ra_hours() - half).abs() < 1e-12, "{east:?}");
+        assert!((west.ra_hours() - (24.0 - half)).abs() < 1e-12, "{west:?}");
+        assert!(east.dec_degrees().abs() < 1e-12);
+        assert!(west.dec_degrees().abs() < 1e-12);
+    }
+
+    #[test]
+    fn overlap_shrinks_the_step_between_panels() {
+        let c = coord(0.0, 0.0);
+        let s = spec(1, 2, 20.0);
+        let top = panel_center(c, &s, panel(1, 1), fov(1.0, 2.0), 0.0).unwrap();
+        let bottom = panel_center(c, &s, panel(2, 1), fov(1.0, 2.0), 0.0).unwrap();
+        // Step = 2° × 0.8 = 1.6°, split ±0.8° about the center.
+        let expected = 0.8_f64.to_radians().atan().to_degrees();
+        assert!((top.dec_degrees() - expected).abs() < 1e-12, "{top:?}");
+        assert!(
+            (bottom.dec_degrees() + expected).abs() < 1e-12,
+            "{bottom:?}"
+        );
+    }
+
+    #[test]
+    fn the_grid_rotates_with_the_position_angle() {
+        let c = coord(0.0, 0.0);
+        let s = spec(1, 2, 0.0);
+        // At PA 90 the frame's top points east, so row 1 moves east.
+        let top = panel_center(c, &s, panel(1, 1), fov(1.0, 1.0), 90.0).unwrap();
+        let half = 0.5_f64.to_radians().atan().to_degrees() / 15.0;
+        assert!((top.ra_hours() - half).abs() < 1e-12, "{top:?}");
+        assert!(top.dec_degrees().abs() < 1e-12, "{top:?}");
+    }
+
+    #[test]
+    fn panels_widen_in_ra_away_from_the_equator() {
+        // The same angular step is more RA at dec 60 than at dec 0.
+        let s = spec(2, 1, 0.0);
+        let ra_span = |dec: f64| {
+            let c = coord(12.0, dec);
+            let a = panel_center(c, &s, panel(1, 1), fov(1.0, 1.0), 0.0).unwrap();
+            let b = panel_center(c, &s, panel(1, 2), fov(1.0, 1.0), 0.0).unwrap();
+            a.ra_hours() - b.ra_hours()
+        };
+        let ratio = ra_span(60.0) / ra_span(0.0);
+        assert!((ratio - 2.0).abs() < 0.01, "{ratio}");
+    }
+
+    #[test]
+    fn a_single_frame_target_expands_to_itself() {
+        let t = target(None);
+        assert_eq!(panel_rows(&t), vec![t.clone()]);
+        assert_eq!(placed_rows(&t, None, 0.0), Some(vec![t]));
+    }
+
+    #[test]
+    fn a_mosaic_expands_to_sibling_rows_under_panel_slugs() {
+        let t = target(Some(spec(3, 2, 10.0)));
+        let rows = panel_rows(&t);
+        let slugs: Vec<&str> = rows.iter().map(|r| r.slug.as_str()).collect();
+        assert_eq!(
+            slugs,
+            ["m31-r1c1", "m31-r1c2", "m31-r1c3", "m31-r2c1", "m31-r2c2", "m31-r2c3"]
+        );
+        assert!(rows
+            .iter()
+            .all(|r| r.mosaic.is_none() && r.coord == t.coord));
+        assert_eq!(rows[1].display_name, "M 31 (panel r1c2)");
+    }
+
+    #[test]
+    fn a_mosaic_cannot_be_placed_without_a_field_of_view() {
+        let t = target(Some(spec(2, 2, 10.0)));
+        assert_eq!(placed_rows(&t, None, 0.0), None);
+
+        let placed = placed_rows(&t, Some(fov(1.5, 1.0)), 30.0).unwrap();
+        assert_eq!(placed.len(), 4);
+        // Four distinct centers, symmetric about the parent.
+        let mean_dec = placed.iter().map(|r| r.coord.dec_degrees()).sum::<f64>() / 4.0;
+        assert!(
+            (mean_dec - t.coord.dec_degrees()).abs() < 0.01,
+            "{mean_dec}"
+        );
+        for (i, a) in placed.iter().enumerate() {
+            for b in &placed[i + 1..] {
+                assert_ne!(a.coord, b.coord);
+            }
+        }
+    }
+}
@@ -10,7 +10,9 @@
 //! convenience tools (Phase 7) reuse the same primitive calls.
 
 use chrono::{DateTime, NaiveDate, Utc};
-use rp_ephemeris::{Ephemeris, ErfarsEphemeris, IcrsCoord, SideOfPier, Site, TwilightKind};
+use rp_ephemeris::{
+    Ephemeris, ErfarsEphemeris, HorizonProfile, IcrsCoord, SideOfPier, Site, TwilightKind,
+};
 use serde_json::{json, Value};
 
 /// Parse a humantime / RFC3339 timestamp, defaulting to `Utc::now()`
@@ -114,8 +116,9 @@ pub fn compute_rise_set(
     target: IcrsCoord,
     date: NaiveDate,
     min_alt_degrees: f64,
+    horizon: &HorizonProfile,
 ) -> Value {
-    let result = ErfarsEphemeris::new().rise_set(site, target, date, min_alt_degrees);
+    let result = ErfarsEphemeris::new().rise_set(site, target, date, min_alt_degrees, horizon);
     match result {
         Some(rs) => json!({
             "rise_utc": rs.rise_utc.to_rfc3339(),
@@ -322,7 +325,7 @@ mod tests {
             ra_hours: 2.5301944,
             dec_degrees: 89.2641111,
         };
-        let v = compute_rise_set(&site, polaris, date, 10.0);
+        let v = compute_rise_set(&site, polaris, date, 10.0, &HorizonProfile::default());
         assert!(v["rise_utc"].is_null());
         assert!(v["set_utc"].is_null());
     }
@@ -335,7 +338,7 @@ mod tests {
             ra_hours: 0.7123,
             dec_degrees: 41.27,
         };
-        let v = compute_rise_set(&site, m31, date, 30.0);
+        let v = compute_rise_set(&site, m31, date, 30.0, &HorizonProfile::default());
         // Either both bounds or both null.
         match (v["rise_utc"].as_str(), v["set_utc"].as_str()) {
             (Some(_), Some(_)) => {}
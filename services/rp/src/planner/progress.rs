@@ -62,13 +62,18 @@ impl SessionProgress {
 /// session's last filter — everything `decision::next_target` needs to
 /// rank candidates, gathered before the pure call.
 ///
+/// Keyed by candidate name — a store slug, or for a mosaic each panel's
+/// own slug ([`super::mosaic`]). A mosaic therefore has no counts of its
+/// own: every panel is tracked separately, exhausts separately, and
+/// competes with its siblings on its own completion fraction.
+///
 /// Counts are positional: `counts[i]` belongs to `target.exposures[i]`,
 /// because both project from the same `Target::goals` list in order
 /// (`PlannerTarget::from` and
 /// [`super::progress_scan::scan_target`] respectively). A target with
 /// no entry, or an entry shorter than its plan, reads as zero rather
 /// than panicking.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct PlanProgress {
     per_target: HashMap<String, Vec<GoalProgress>>,
     last_filter_key: Option<String>,
@@ -104,6 +109,37 @@ impl PlanProgress {
             .unwrap_or_default()
     }
 
+    /// Good frames counted against plan entry `index` of `target`.
+    #[must_use]
+    pub fn good_at(&self, target: &PlannerTarget, index: usize) -> u32 {
+        self.counts_at(target, index).good
+    }
+
+    /// Count one good frame against `entry` — the first incomplete plan
+    /// entry of `target` equal to it — and make its filter the last one.
+    /// The real planner never calls this (capture's file is the count);
+    /// it is how the night preview advances a simulated snapshot.
+    pub fn record_frame(&mut self, target: &PlannerTarget, entry: &ExposureSpec) {
+        self.last_filter_key = Some(filter_key(entry.filter.as_deref()));
+        let Some(index) = target.exposures.iter().enumerate().position(|(i, e)| {
+            e == entry
+                && match e.count {
+                    None => true,
+                    Some(goal) => self.counts_at(target, i).good < goal,
+                }
+        }) else {
+            return;
+        };
+        let counts = self.per_target.entry(target.name.clone()).or_default();
+        if counts.len() <= index {
+            counts.resize(index + 1, GoalProgress::default());
+        }
+        if let Some(goal) = counts.get_mut(index) {
+            goal.good += 1;
+            goal.total += 1;
+        }
+    }
+
     /// The first `exposures[]` entry whose goal the good frames on disk
     /// have not met, in plan order — the entry `get_next_target`
     /// recommends. An entry without a `count` has no finite goal and is
@@ -115,11 +151,23 @@ impl PlanProgress {
     /// same entry rather than retiring it.
     #[must_use]
     pub fn next_incomplete_entry<'a>(&self, target: &'a PlannerTarget) -> Option<&'a ExposureSpec> {
+        self.incomplete_entries(target).next()
+    }
+
+    /// Every `exposures[]` entry whose goal is unmet, in plan order —
+    /// the candidates `decision::next_target` walks when the Moon rules
+    /// some of a target's goals out for the night. Same "met on `good`"
+    /// and "uncounted is never complete" rules as
+    /// [`Self::next_incomplete_entry`], which is this walk's first item.
+    pub fn incomplete_entries<'s, 'a: 's>(
+        &'s self,
+        target: &'a PlannerTarget,
+    ) -> impl Iterator<Item = &'a ExposureSpec> + 's {
         target
             .exposures
             .iter()
             .enumerate()
-            .find(|(index, entry)| match entry.count {
+            .filter(move |(index, entry)| match entry.count {
                 None => true,
                 Some(goal) => self.counts_at(target, *index).good < goal,
             })
@@ -165,6 +213,9 @@ mod tests {
             name: name.to_string(),
             coord: rp_targets::IcrsCoord::try_new(0.0, 0.0).unwrap(),
             min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
             position_angle_degrees: None,
             exposures,
         }
@@ -175,6 +226,7 @@ mod tests {
             filter: filter.map(String::from),
             duration_secs: 60.0,
             count,
+            moon_avoidance: None,
         }
     }
 
@@ -221,6 +273,26 @@ mod tests {
         assert_eq!(p.fraction(&t), 0.0);
     }
 
+    // Sibling panels carry the parent's plan under their own names, so
+    // finishing one panel neither advances nor exhausts the others.
+    #[test]
+    fn sibling_panels_progress_independently() {
+        let plan = || vec![entry(Some("Ha"), Some(2))];
+        let left = target("m31-r1c1", plan());
+        let right = target("m31-r1c2", plan());
+        let mut p = PlanProgress::default();
+        p.insert("m31-r1c1", good(&[2]));
+        p.insert("m31-r1c2", good(&[1]));
+
+        assert!(p.is_exhausted(&left));
+        assert!(!p.is_exhausted(&right));
+        assert_eq!(p.fraction(&right), 0.5);
+
+        p.record_frame(&right, &plan()[0]);
+        assert!(p.is_exhausted(&right));
+        assert_eq!(p.good_at(&left, 0), 2, "the sibling's count is untouched");
+    }
+
     #[test]
     fn next_incomplete_entry_walks_the_plan_in_order() {
         let t = target(
@@ -330,6 +402,25 @@ mod tests {
         assert_eq!(p.fraction(&t), 0.0);
     }
 
+    #[test]
+    fn record_frame_advances_the_matching_goal_and_the_last_filter() {
+        let t = target(
+            "M31",
+            vec![entry(Some("L"), Some(1)), entry(Some("R"), Some(2))],
+        );
+        let mut p = PlanProgress::default();
+        let red = t.exposures[1].clone();
+        p.record_frame(&t, &red);
+        assert_eq!(p.good_at(&t, 0), 0);
+        assert_eq!(p.good_at(&t, 1), 1);
+        assert_eq!(p.last_filter_key(), Some("R"));
+
+        let lum = t.exposures[0].clone();
+        p.record_frame(&t, &lum);
+        p.record_frame(&t, &red);
+        assert!(p.is_exhausted(&t));
+    }
+
     #[test]
     fn a_short_counts_vector_reads_as_zero_rather_than_panicking() {
         let t = target(
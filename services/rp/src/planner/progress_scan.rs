@@ -14,7 +14,8 @@
 //! Good-vs-rejected is decided against the target's *effective* grading
 //! thresholds (its own overrides, field-wise over
 //! `target_store.default_grading`), read from each frame's sidecar
-//! `grading` section. A frame is rejected only on evidence: no sidecar,
+//! `grading` section, together with the grading plugin's own
+//! `rejected` verdict. A frame is rejected only on evidence: no sidecar,
 //! no section, or no value for the judged metric all count as good.
 //! When no threshold is effective there is nothing a sidecar could
 //! contradict, so the sidecar reads are skipped entirely and
@@ -118,9 +119,15 @@ pub fn effective_thresholds(
 /// The grading metrics `rp` reads from a frame's sidecar
 /// `sections.grading`. Every field is optional and unknown keys are
 /// ignored: the section belongs to the grading plugin, and `rp` only
-/// picks out the four metrics its thresholds judge.
+/// picks out the four metrics its thresholds judge plus the plugin's
+/// own verdict.
 #[derive(Debug, Default, serde::Deserialize)]
 struct GradingMetrics {
+    /// The plugin's verdict on what the thresholds cannot express — a
+    /// frame measurably worse than its target/filter baseline (thin
+    /// cloud, dew). `Some(true)` rejects outright.
+    #[serde(default)]
+    rejected: Option<bool>,
     #[serde(default)]
     hfr: Option<f64>,
     #[serde(default)]
@@ -132,9 +139,13 @@ struct GradingMetrics {
 }
 
 impl GradingMetrics {
-    /// Whether any present metric violates its threshold. Absent metrics
-    /// never reject — a frame is rejected only on evidence.
+    /// Whether the plugin rejected the frame or any present metric
+    /// violates its threshold. Absent metrics never reject — a frame is
+    /// rejected only on evidence.
     fn violates(&self, thresholds: &GradingThresholds) -> bool {
+        if self.rejected == Some(true) {
+            return true;
+        }
         let over = |value: Option<f64>, limit: Option<f64>| matches!((value, limit), (Some(v), Some(l)) if v > l);
         let under = |value: Option<f64>, limit: Option<f64>| matches!((value, limit), (Some(v), Some(l)) if v < l);
         over(self.hfr, thresholds.max_hfr_pixels)
@@ -151,7 +162,7 @@ impl GradingMetrics {
 ///
 /// Returns all-zero counts (one per goal) when no naming templates are
 /// configured: without `session.file_naming_pattern` `capture` writes
-/// flat `<doc_uuid_8>.fits` files that carry no target, so there is
+/// flat `<doc_uuid_8>.<ext>` files that carry no target, so there is
 /// nothing on disk to attribute. Filesystem errors are logged and
 /// treated as "no frames here" rather than propagated — a progress read
 /// must never be the thing that ends a night.
@@ -277,7 +288,9 @@ async fn candidate_directories(
 }
 
 impl Scan<'_> {
-    /// Count every `.fits` frame in one candidate directory into `buckets`.
+    /// Count every frame (`.fits` or `.xisf`, whichever
+    /// `session.image_format` wrote) in one candidate directory into
+    /// `buckets`.
     async fn collect_frames(
         &self,
         dir: &Path,
@@ -301,9 +314,9 @@ impl Scan<'_> {
                 }
             };
             let path = entry.path();
-            // Sidecars share a stem with their FITS file; counting both
+            // Sidecars share a stem with their frame file; counting both
             // would double-count every frame.
-            if path.extension().and_then(|e| e.to_str()) != Some("fits") {
+            if !crate::persistence::is_frame_path(&path) {
                 continue;
             }
             let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
@@ -448,6 +461,20 @@ mod tests {
         .violates(&limits));
     }
 
+    #[test]
+    fn the_plugin_verdict_rejects_whatever_the_metrics_say() {
+        let limits = thresholds(Some(3.0), Some(100));
+        let metrics = |rejected| GradingMetrics {
+            rejected,
+            hfr: Some(2.1),
+            star_count: Some(900),
+            ..Default::default()
+        };
+        assert!(metrics(Some(true)).violates(&limits));
+        assert!(!metrics(Some(false)).violates(&limits));
+        assert!(!metrics(None).violates(&limits));
+    }
+
     #[test]
     fn a_metric_exactly_at_its_threshold_is_good() {
         // The contract is `>` / `<`, not `>=` / `<=`: a frame sitting
@@ -539,6 +566,7 @@ mod tests {
             binning: Binning { x: 1, y: 1 },
             exposure_duration: Duration::from_mins(5),
             desired_count,
+            moon_avoidance: None,
         }
     }
 
@@ -554,6 +582,7 @@ mod tests {
             active: true,
             priority: 0,
             position_angle_degrees: None,
+            mosaic: None,
             goals: vec![goal("L", 4)],
             scheduling: None,
             grading: None,
@@ -0,0 +1,385 @@
+//! Whole-night schedule preview: `preview_night_schedule`.
+//!
+//! Steps [`super::decision::next_target`] forward in simulated time
+//! from astronomical dusk to dawn (`Ephemeris::twilight`), exactly as an
+//! orchestrator looping on `get_next_target` would: each recommendation
+//! is "shot" for its `duration_secs` (plus an optional per-frame
+//! overhead), counted against a cloned [`PlanProgress`] snapshot, and
+//! the planner asked again. When nothing is recommended the clock
+//! advances by [`IDLE_STEP_SECS`] and the planner's `reason` labels the
+//! gap; `end_of_session` ends the preview early.
+//!
+//! Consecutive frames of the same target and plan entry collapse into
+//! one [`ScheduleBlock`], as do consecutive idle steps with the same
+//! reason, so the timeline is a short list of bars a UI can lay out as
+//! a Gantt chart. Like the decision logic it drives, the simulation is
+//! a pure function of its arguments — the on-disk progress is derived
+//! once at the tool boundary and never written back.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rp_ephemeris::{Ephemeris, HorizonProfile, Site, TwilightKind};
+use serde::Serialize;
+
+use super::decision::{next_target, NextTargetReason, PlannerTarget, SchedulingDefaults};
+use super::progress::PlanProgress;
+
+/// How far the simulated clock advances when the planner recommends
+/// nothing (twilight, every target low or Moon-blocked). Five minutes
+/// keeps a gap's edges within one step of the real transition without
+/// re-running the decision thousands of times across a long night.
+const IDLE_STEP_SECS: i64 = 300;
+
+/// One bar of the timeline: a run of frames on one target and plan
+/// entry, or an idle gap.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScheduleBlock {
+    pub start_utc: DateTime<Utc>,
+    pub end_utc: DateTime<Utc>,
+    /// The target's slug; `None` for an idle gap.
+    pub target: Option<String>,
+    /// The plan entry's filter; `None` when unfiltered or idle.
+    pub filter: Option<String>,
+    /// The plan entry's exposure length; `None` for an idle gap or a
+    /// target without a plan.
+    pub duration_secs: Option<f64>,
+    /// Frames the block is expected to gain (`0` when idle).
+    pub frames: u32,
+    /// `best_transiting_candidate` for an imaging block, otherwise
+    /// the reason the planner gave for recommending nothing.
+    pub reason: NextTargetReason,
+}
+
+/// What the night is expected to add to one plan entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GoalForecast {
+    /// The target's slug.
+    pub target: String,
+    pub filter: Option<String>,
+    pub duration_secs: f64,
+    pub desired_count: Option<u32>,
+    /// Good frames already on disk when the preview started.
+    pub good: u32,
+    /// Frames the preview expects tonight.
+    pub expected_frames: u32,
+}
+
+/// The `preview_night_schedule` result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NightSchedule {
+    /// Astronomical dusk of the previewed night.
+    pub dusk_utc: DateTime<Utc>,
+    /// Astronomical dawn of the previewed night.
+    pub dawn_utc: DateTime<Utc>,
+    /// Where the simulation started: dusk, or the caller's `not_before`
+    /// when that is later.
+    pub start_utc: DateTime<Utc>,
+    pub blocks: Vec<ScheduleBlock>,
+    /// Every plan entry of every candidate, in target then goal order.
+    pub goals: Vec<GoalForecast>,
+}
+
+/// The UTC date `Ephemeris::twilight` keys the night in progress or
+/// next up at `time` on. `twilight` brackets the night from the date's
+/// local solar noon, so the candidate is the date of the last solar
+/// noon; once that night's dawn has passed, the next one is tonight.
+#[must_use]
+pub fn night_of(eph: &impl Ephemeris, site: &Site, time: DateTime<Utc>) -> NaiveDate {
+    // Shift to local mean solar time (4 minutes per degree east), then
+    // back half a day so the date rolls over at solar noon.
+    let solar =
+        time + Duration::milliseconds((site.longitude_degrees * 240_000.0) as i64 - 43_200_000);
+    let date = solar.date_naive();
+    match eph.twilight(site, date, TwilightKind::Astronomical).end_utc {
+        Some(dawn) if dawn <= time => date.succ_opt().unwrap_or(date),
+        _ => date,
+    }
+}
+
+/// Simulate the planner across the astronomical night of `date`,
+/// starting no earlier than `not_before`. `progress` is the on-disk
+/// snapshot; the simulation advances a clone of it.
+///
+/// # Errors
+///
+/// A message when the Sun never crosses −18° on `date` at this site
+/// (polar day or polar night) — there is no night to preview.
+#[allow(clippy::too_many_arguments)]
+pub fn preview_night(
+    eph: &impl Ephemeris,
+    site: &Site,
+    horizon: &HorizonProfile,
+    date: NaiveDate,
+    not_before: DateTime<Utc>,
+    targets: &[PlannerTarget],
+    defaults: &SchedulingDefaults,
+    progress: &PlanProgress,
+    frame_overhead_secs: f64,
+) -> Result<NightSchedule, String> {
+    let window = eph.twilight(site, date, TwilightKind::Astronomical);
+    let (Some(dusk_utc), Some(dawn_utc)) = (window.begin_utc, window.end_utc) else {
+        return Err(format!(
+            "no astronomical night on {date} at this site: the Sun never crosses -18°"
+        ));
+    };
+    let start_utc = dusk_utc.max(not_before);
+    let mut simulated = progress.clone();
+    let mut blocks: Vec<ScheduleBlock> = Vec::new();
+    let mut now = start_utc;
+    while now < dawn_utc {
+        let rec = next_target(eph, site, horizon, now, targets, defaults, None, &simulated);
+        let Some(target) = rec.target else {
+            if rec.reason == NextTargetReason::EndOfSession {
+                break;
+            }
+            let end = (now + Duration::seconds(IDLE_STEP_SECS)).min(dawn_utc);
+            push_block(
+                &mut blocks,
+                ScheduleBlock {
+                    start_utc: now,
+                    end_utc: end,
+                    target: None,
+                    filter: None,
+                    duration_secs: None,
+                    frames: 0,
+                    reason: rec.reason,
+                },
+            );
+            now = end;
+            continue;
+        };
+        // A target without a plan is recommended with no exposure: the
+        // orchestrator's own parameters apply, which the preview cannot
+        // know, so it holds the target for an idle step's worth.
+        let Some(entry) = rec.exposure else {
+            let end = (now + Duration::seconds(IDLE_STEP_SECS)).min(dawn_utc);
+            push_block(
+                &mut blocks,
+                ScheduleBlock {
+                    start_utc: now,
+                    end_utc: end,
+                    target: Some(target.name),
+                    filter: None,
+                    duration_secs: None,
+                    frames: 0,
+                    reason: rec.reason,
+                },
+            );
+            now = end;
+            continue;
+        };
+        let frame_ms = ((entry.duration_secs + frame_overhead_secs) * 1000.0) as i64;
+        let end = now + Duration::milliseconds(frame_ms);
+        if end > dawn_utc {
+            // The frame would run into dawn; an orchestrator would not
+            // start it.
+            break;
+        }
+        simulated.record_frame(&target, &entry);
+        push_block(
+            &mut blocks,
+            ScheduleBlock {
+                start_utc: now,
+                end_utc: end,
+                target: Some(target.name),
+                filter: entry.filter,
+                duration_secs: Some(entry.duration_secs),
+                frames: 1,
+                reason: rec.reason,
+            },
+        );
+        now = end;
+    }
+
+    let goals = targets
+        .iter()
+        .flat_map(|t| {
+            let simulated = &simulated;
+            t.exposures.iter().enumerate().map(move |(index, e)| {
+                let good = progress.good_at(t, index);
+                GoalForecast {
+                    target: t.name.clone(),
+                    filter: e.filter.clone(),
+                    duration_secs: e.duration_secs,
+                    desired_count: e.count,
+                    good,
+                    expected_frames: simulated.good_at(t, index).saturating_sub(good),
+                }
+            })
+        })
+        .collect();
+    Ok(NightSchedule {
+        dusk_utc,
+        dawn_utc,
+        start_utc,
+        blocks,
+        goals,
+    })
+}
+
+/// Append `block`, extending the last one instead when it is the same
+/// bar continuing (same target, entry and reason, back to back).
+fn push_block(blocks: &mut Vec<ScheduleBlock>, block: ScheduleBlock) {
+    if let Some(last) = blocks.last_mut() {
+        if last.end_utc == block.start_utc
+            && last.target == block.target
+            && last.filter == block.filter
+            && last.duration_secs == block.duration_secs
+            && last.reason == block.reason
+        {
+            last.end_utc = block.end_utc;
+            last.frames += block.frames;
+            return;
+        }
+    }
+    blocks.push(block);
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rp_ephemeris::ErfarsEphemeris;
+
+    use super::super::decision::ExposureSpec;
+
+    fn site() -> Site {
+        Site::new(47.6062, -122.3321).unwrap()
+    }
+
+    fn target(name: &str, ra: f64, dec: f64, exposures: Vec<ExposureSpec>) -> PlannerTarget {
+        PlannerTarget {
+            name: name.into(),
+            coord: rp_targets::IcrsCoord::try_new(ra, dec).unwrap(),
+            min_altitude_degrees: None,
+            min_moon_separation_degrees: None,
+            max_moon_illumination_fraction: None,
+            meridian_window_hours: None,
+            position_angle_degrees: None,
+            exposures,
+        }
+    }
+
+    fn entry(filter: &str, duration_secs: f64, count: u32) -> ExposureSpec {
+        ExposureSpec {
+            filter: Some(filter.into()),
+            duration_secs,
+            count: Some(count),
+            moon_avoidance: None,
+        }
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+    }
+
+    fn preview(targets: &[PlannerTarget], progress: &PlanProgress) -> NightSchedule {
+        preview_night(
+            &ErfarsEphemeris::new(),
+            &site(),
+            &HorizonProfile::default(),
+            date(),
+            DateTime::<Utc>::MIN_UTC,
+            targets,
+            &SchedulingDefaults::altitude_only(20.0),
+            progress,
+            0.0,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn a_goal_is_shot_in_one_block_then_the_session_ends() {
+        // M31 is high over Seattle all of an early-November night.
+        let m31 = target(
+            "m31",
+            0.7123,
+            41.27,
+            vec![entry("L", 300.0, 6), entry("R", 300.0, 2)],
+        );
+        let s = preview(&[m31], &PlanProgress::default());
+        assert!(s.dusk_utc < s.dawn_utc);
+        assert_eq!(s.start_utc, s.dusk_utc);
+
+        let imaging: Vec<&ScheduleBlock> = s.blocks.iter().filter(|b| b.frames > 0).collect();
+        assert_eq!(imaging.len(), 2, "L then R: {:?}", s.blocks);
+        assert_eq!(imaging[0].filter.as_deref(), Some("L"));
+        assert_eq!(imaging[0].frames, 6);
+        assert_eq!(
+            imaging[0].end_utc - imaging[0].start_utc,
+            Duration::seconds(6 * 300)
+        );
+        assert_eq!(imaging[1].filter.as_deref(), Some("R"));
+        assert_eq!(imaging[1].frames, 2);
+        // Every goal met: the rest of the night is not simulated.
+        assert_eq!(s.blocks.last().unwrap().end_utc, imaging[1].end_utc);
+        assert_eq!(s.goals[0].expected_frames, 6);
+        assert_eq!(s.goals[1].expected_frames, 2);
+    }
+
+    #[test]
+    fn frames_already_on_disk_are_not_forecast_again() {
+        let m31 = target("m31", 0.7123, 41.27, vec![entry("L", 300.0, 4)]);
+        let mut progress = PlanProgress::default();
+        progress.insert(
+            "m31",
+            vec![super::super::progress_scan::GoalProgress { good: 3, total: 3 }],
+        );
+        let s = preview(&[m31], &progress);
+        assert_eq!(s.goals[0].good, 3);
+        assert_eq!(s.goals[0].expected_frames, 1);
+    }
+
+    #[test]
+    fn a_never_visible_target_leaves_one_idle_block_to_dawn() {
+        let south = target("south", 12.0, -85.0, vec![entry("L", 300.0, 10)]);
+        let s = preview(&[south], &PlanProgress::default());
+        let below: Vec<&ScheduleBlock> = s
+            .blocks
+            .iter()
+            .filter(|b| b.reason == NextTargetReason::AllBelowMinAltitude)
+            .collect();
+        assert_eq!(below.len(), 1, "idle steps merge: {:?}", s.blocks);
+        assert!(s.blocks.iter().all(|b| b.frames == 0 && b.target.is_none()));
+        assert_eq!(s.blocks.last().unwrap().end_utc, s.dawn_utc);
+        assert_eq!(s.goals[0].expected_frames, 0);
+    }
+
+    #[test]
+    fn the_preview_starts_no_earlier_than_not_before() {
+        let eph = ErfarsEphemeris::new();
+        let dusk = eph
+            .twilight(&site(), date(), TwilightKind::Astronomical)
+            .begin_utc
+            .unwrap();
+        let later = dusk + Duration::hours(2);
+        let s = preview_night(
+            &eph,
+            &site(),
+            &HorizonProfile::default(),
+            date(),
+            later,
+            &[],
+            &SchedulingDefaults::altitude_only(20.0),
+            &PlanProgress::default(),
+            0.0,
+        )
+        .unwrap();
+        assert_eq!(s.start_utc, later);
+        assert_eq!(s.blocks[0].reason, NextTargetReason::NoTargetsConfigured);
+    }
+
+    #[test]
+    fn night_of_picks_the_night_in_progress_then_the_next_one() {
+        let eph = ErfarsEphemeris::new();
+        // 20:00 local (PST) on 1 Nov is 04:00 UTC on the 2nd.
+        let evening = Utc.with_ymd_and_hms(2026, 11, 2, 4, 0, 0).unwrap();
+        assert_eq!(night_of(&eph, &site(), evening), date());
+        // Mid-morning local on the 2nd: that night is over.
+        let morning = Utc.with_ymd_and_hms(2026, 11, 2, 18, 0, 0).unwrap();
+        assert_eq!(
+            night_of(&eph, &site(), morning),
+            NaiveDate::from_ymd_opt(2026, 11, 2).unwrap()
+        );
+    }
+}
@@ -16,7 +16,7 @@ use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 use crate::config_actions::RpConfigDriver;
-use crate::equipment::EquipmentRegistry;
+use crate::equipment::SharedEquipment;
 use crate::events::{EventEnvelope, Subscription};
 use crate::mcp::McpHandler;
 use crate::persistence::{CachedPixels, ImageCache};
@@ -26,14 +26,19 @@ use crate::session::SessionManager;
 const TRANSMISSION_U16: i32 = 8;
 /// ASCOM `TransmissionElementType` code for `i32` payloads.
 const TRANSMISSION_I32: i32 = 2;
-/// ASCOM `ImageElementType` is always `Int32` (the logical type required by
-/// the Alpaca API). The transmission type may differ.
+/// ASCOM `TransmissionElementType` code for `f32` payloads (`Single`).
+const TRANSMISSION_F32: i32 = 4;
+/// ASCOM `ImageElementType` is `Int32` for integer images (the logical
+/// type required by the Alpaca API). The transmission type may differ.
 const IMAGE_ELEMENT_I32: i32 = 2;
+/// ASCOM `ImageElementType` for floating-point images (`Double`), sent
+/// as `Single` since that is what the cache holds.
+const IMAGE_ELEMENT_DOUBLE: i32 = 3;
 const IMAGEBYTES_HEADER_LEN: usize = 44;
 
 #[derive(Clone)]
 pub struct AppState {
-    pub equipment: Arc<EquipmentRegistry>,
+    pub equipment: SharedEquipment,
     pub mcp: McpHandler,
     pub session: Arc<SessionManager>,
     pub image_cache: ImageCache,
@@ -356,13 +361,78 @@ async fn session_status(State(state): State<AppState>) -> Json<Value> {
     Json(serde_json::json!({"status": status}))
 }
 
+/// The parts of a completion body rp acts on. Every field is optional
+/// and unknown keys (`status`, an orchestrator's `result`) are ignored,
+/// so an empty body and every existing completion still parse.
+#[derive(Debug, Default, serde::Deserialize)]
+struct PluginCompletion {
+    /// The document `sections` are written to. Required when any are.
+    #[serde(default)]
+    document_id: Option<String>,
+    /// Plugin sections to merge into the document (rp.md § Plugin
+    /// Section Updates), keyed by section name.
+    #[serde(default)]
+    sections: serde_json::Map<String, Value>,
+}
+
+/// `POST /api/plugins/{id}/complete` — an orchestrator's workflow
+/// completion or an event plugin's, keyed by the workflow or event id.
+/// An event plugin's completion may carry `sections` for `document_id`;
+/// each is persisted before the completion is acknowledged and
+/// announced with `document_updated`.
 async fn workflow_complete(
     State(state): State<AppState>,
     Path(workflow_id): Path<String>,
-) -> StatusCode {
-    debug!(workflow_id = %workflow_id, "received workflow completion");
+    body: axum::body::Bytes,
+) -> Response {
+    debug!(workflow_id = %workflow_id, "received plugin completion");
+    let completion = if body.is_empty() {
+        PluginCompletion::default()
+    } else {
+        match serde_json::from_slice::<PluginCompletion>(&body) {
+            Ok(c) => c,
+            Err(e) => return bad_request(format!("invalid completion body: {e}")),
+        }
+    };
+
+    if !completion.sections.is_empty() {
+        let Some(document_id) = completion.document_id.as_deref() else {
+            return bad_request("completion sections need a document_id".to_string());
+        };
+        if state
+            .image_cache
+            .resolve_document(document_id)
+            .await
+            .is_none()
+        {
+            return not_found(format!("document not found: {document_id}"));
+        }
+        for (name, data) in completion.sections {
+            if let Err(e) = state
+                .image_cache
+                .put_section(document_id, &name, data)
+                .await
+            {
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({
+                        "error": format!("failed to write section '{name}': {e}")
+                    })),
+                )
+                    .into_response();
+            }
+            state.mcp.event_bus.emit(
+                "document_updated",
+                serde_json::json!({
+                    "document_id": document_id,
+                    "section_name": name,
+                }),
+            );
+        }
+    }
+
     state.session.workflow_complete(&workflow_id).await;
-    StatusCode::OK
+    StatusCode::OK.into_response()
 }
 
 async fn get_document(
@@ -401,6 +471,7 @@ async fn get_image_metadata(
     let bitpix = cached.as_ref().map(|c| match &c.pixels {
         CachedPixels::U16(_) => 16,
         CachedPixels::I32(_) => 32,
+        CachedPixels::F32(_) => -32,
     });
     let doc = match cached {
         Some(c) => c.document.read().await.clone(),
@@ -437,18 +508,34 @@ async fn get_image_pixels(
     };
     let (width, height) = (cached.width, cached.height);
     let body = match &cached.pixels {
-        CachedPixels::U16(arr) => imagebytes(width, height, TRANSMISSION_U16, |buf| {
-            buf.reserve(arr.len() * 2);
-            for &v in arr {
-                buf.extend_from_slice(&v.to_le_bytes());
-            }
-        }),
-        CachedPixels::I32(arr) => imagebytes(width, height, TRANSMISSION_I32, |buf| {
-            buf.reserve(arr.len() * 4);
-            for &v in arr {
-                buf.extend_from_slice(&v.to_le_bytes());
-            }
-        }),
+        CachedPixels::U16(arr) => {
+            imagebytes(width, height, IMAGE_ELEMENT_I32, TRANSMISSION_U16, |buf| {
+                buf.reserve(arr.len() * 2);
+                for &v in arr {
+                    buf.extend_from_slice(&v.to_le_bytes());
+                }
+            })
+        }
+        CachedPixels::I32(arr) => {
+            imagebytes(width, height, IMAGE_ELEMENT_I32, TRANSMISSION_I32, |buf| {
+                buf.reserve(arr.len() * 4);
+                for &v in arr {
+                    buf.extend_from_slice(&v.to_le_bytes());
+                }
+            })
+        }
+        CachedPixels::F32(arr) => imagebytes(
+            width,
+            height,
+            IMAGE_ELEMENT_DOUBLE,
+            TRANSMISSION_F32,
+            |buf| {
+                buf.reserve(arr.len() * 4);
+                for &v in arr {
+                    buf.extend_from_slice(&v.to_le_bytes());
+                }
+            },
+        ),
     };
     imagebytes_response(body)
 }
@@ -460,6 +547,7 @@ async fn get_image_pixels(
 fn imagebytes(
     width: u32,
     height: u32,
+    image_element_type: i32,
     transmission_element_type: i32,
     write_pixels: impl FnOnce(&mut Vec<u8>),
 ) -> Vec<u8> {
@@ -470,7 +558,7 @@ fn imagebytes(
         0,                            // client_transaction_id
         0,                            // server_transaction_id
         IMAGEBYTES_HEADER_LEN as i32, // data_start
-        IMAGE_ELEMENT_I32,            // image_element_type (logical, always Int32)
+        image_element_type,           // image_element_type (logical)
         transmission_element_type,    // transmission_element_type
         2,                            // rank
         width.cast_signed(),          // dimension_1
@@ -494,6 +582,14 @@ fn imagebytes_response(body: Vec<u8>) -> Response {
         .into_response()
 }
 
+fn bad_request(msg: String) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(serde_json::json!({"error": msg})),
+    )
+        .into_response()
+}
+
 fn not_found(msg: String) -> Response {
     (
         StatusCode::NOT_FOUND,
@@ -531,15 +627,16 @@ mod tests {
         config_path: PathBuf,
     ) -> AppState {
         let event_bus = Arc::new(EventBus::from_config(&[], None).unwrap());
-        let equipment = Arc::new(crate::equipment::EquipmentRegistry {
-            safety_monitors: vec![],
-            cameras: vec![],
-            filter_wheels: vec![],
-            cover_calibrators: vec![],
-            focusers: vec![],
-            mount: None,
-            ..Default::default()
-        });
+        let equipment =
+            crate::equipment::SharedEquipment::new(crate::equipment::EquipmentRegistry {
+                safety_monitors: vec![],
+                cameras: vec![],
+                filter_wheels: vec![],
+                cover_calibrators: vec![],
+                focusers: vec![],
+                mount: None,
+                ..Default::default()
+            });
         let session =
             Arc::new(crate::session::SessionManager::new(event_bus.clone(), &[], None).unwrap());
         let mcp = McpHandler::new(
@@ -756,6 +853,7 @@ mod tests {
         ExposureDocument {
             target: None,
             frame_type: None,
+            filter: None,
             id: "doc-1".to_string(),
             captured_at: "2026-04-30T00:00:00Z".to_string(),
             file_path: file_path.to_string(),
@@ -766,6 +864,10 @@ mod tests {
             max_adu: None,
             cooler_setpoint_c: None,
             sensor_temperature_c: None,
+            dew_heater_duty_pct: Default::default(),
+            gain: None,
+            offset: None,
+            binning: None,
             optics: None,
             sections: serde_json::Map::new(),
         }
@@ -822,6 +924,32 @@ mod tests {
         assert_eq!(&body[44..], &expected[..]);
     }
 
+    #[tokio::test]
+    async fn pixels_serves_f32_from_cache_as_single() {
+        let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
+        let arr = ndarray::Array2::from_shape_vec((2, 1), vec![0.5f32, -1.25]).unwrap();
+        cache.insert(
+            "doc-1".to_string(),
+            CachedImage::new(
+                CachedPixels::F32(arr),
+                2,
+                1,
+                PathBuf::from("/tmp/fake.fits"),
+                65535,
+                doc_at("/tmp/fake.fits"),
+            ),
+        );
+        let response =
+            get_image_pixels(State(test_app_state(cache)), Path("doc-1".to_string())).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = body_bytes(response).await;
+        assert_eq!(body.len(), IMAGEBYTES_HEADER_LEN + 4 * 2);
+        assert_eq!(&body[20..24], &IMAGE_ELEMENT_DOUBLE.to_le_bytes());
+        assert_eq!(&body[24..28], &TRANSMISSION_F32.to_le_bytes());
+        assert_eq!(&body[44..48], &0.5f32.to_le_bytes());
+        assert_eq!(&body[48..52], &(-1.25f32).to_le_bytes());
+    }
+
     #[tokio::test]
     async fn pixels_returns_404_on_cache_miss() {
         // `ImageCache::resolve()` falls back to reloading from disk on an
@@ -840,6 +968,100 @@ mod tests {
         assert_eq!(response.status(), StatusCode::NOT_FOUND);
     }
 
+    #[tokio::test]
+    async fn completion_writes_sections_and_announces_them() {
+        let dir = tempfile::tempdir().unwrap();
+        let fits_path = dir.path().join("abcd1234.fits");
+        let cache = ImageCache::new(64, 4, dir.path().to_path_buf());
+        cache.insert(
+            "doc-1".to_string(),
+            CachedImage::new(
+                CachedPixels::U16(ndarray::Array2::from_elem((2, 2), 0u16)),
+                2,
+                2,
+                fits_path.clone(),
+                65535,
+                doc_at(&fits_path.to_string_lossy()),
+            ),
+        );
+        let state = test_app_state(cache.clone());
+        let mut events = state.mcp.event_bus.subscribe();
+
+        let body = serde_json::json!({
+            "status": "complete",
+            "document_id": "doc-1",
+            "sections": {"grading": {"hfr": 2.3, "star_count": 412}}
+        });
+        let response = workflow_complete(
+            State(state),
+            Path("evt-1".to_string()),
+            serde_json::to_vec(&body).unwrap().into(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let doc = cache.resolve_document("doc-1").await.unwrap();
+        assert_eq!(doc.sections["grading"]["star_count"], 412);
+        let sidecar: Value =
+            serde_json::from_slice(&std::fs::read(dir.path().join("abcd1234.json")).unwrap())
+                .unwrap();
+        assert_eq!(sidecar["sections"]["grading"]["hfr"], 2.3);
+        let event = events.try_recv().unwrap();
+        assert_eq!(event.event, "document_updated");
+        assert_eq!(event.payload["section_name"], "grading");
+    }
+
+    #[tokio::test]
+    async fn completion_without_sections_needs_no_body() {
+        let state = test_app_state(ImageCache::new(64, 4, PathBuf::from("/nonexistent")));
+        let response = workflow_complete(
+            State(state.clone()),
+            Path("wf-1".to_string()),
+            axum::body::Bytes::new(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        // An orchestrator's `result` is not a section and is ignored.
+        let body = br#"{"status": "complete", "result": {"reason": "done"}}"#;
+        let response = workflow_complete(
+            State(state),
+            Path("wf-1".to_string()),
+            axum::body::Bytes::from_static(body),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn completion_sections_need_a_known_document() {
+        let state = test_app_state(ImageCache::new(64, 4, PathBuf::from("/nonexistent")));
+        let response = workflow_complete(
+            State(state.clone()),
+            Path("evt-1".to_string()),
+            axum::body::Bytes::from_static(br#"{"sections": {"grading": {}}}"#),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let response = workflow_complete(
+            State(state.clone()),
+            Path("evt-1".to_string()),
+            axum::body::Bytes::from_static(
+                br#"{"document_id": "missing", "sections": {"grading": {}}}"#,
+            ),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = workflow_complete(
+            State(state),
+            Path("evt-1".to_string()),
+            axum::body::Bytes::from_static(b"not json"),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
     #[tokio::test]
     async fn metadata_reports_bitpix_16_for_u16_cached() {
         let cache = ImageCache::new(64, 4, std::path::PathBuf::from("/nonexistent"));
@@ -883,12 +1105,21 @@ mod tests {
         let doc_uuid = "44444444-4444-4444-4444-444444444444";
         let uuid8 = &doc_uuid[..8];
         let fits_path = dir.path().join(format!("{uuid8}.fits"));
-        crate::persistence::write_fits_u16(&fits_path, &[0u16; 4], 2, 2, doc_uuid)
-            .await
-            .unwrap();
+        crate::persistence::write_fits_u16(
+            &fits_path,
+            &[0u16; 4],
+            2,
+            2,
+            doc_uuid,
+            &[],
+            crate::config::FitsCompression::None,
+        )
+        .await
+        .unwrap();
         let doc = ExposureDocument {
             target: None,
             frame_type: None,
+            filter: None,
             id: doc_uuid.to_string(),
             captured_at: "2026-04-30T00:00:00Z".to_string(),
             file_path: fits_path.to_string_lossy().into_owned(),
@@ -899,6 +1130,10 @@ mod tests {
             max_adu: None,
             cooler_setpoint_c: None,
             sensor_temperature_c: None,
+            dew_heater_duty_pct: Default::default(),
+            gain: None,
+            offset: None,
+            binning: None,
             optics: None,
             sections: serde_json::Map::new(),
         };
@@ -938,7 +1173,7 @@ mod tests {
 
     #[test]
     fn imagebytes_header_layout_u16() {
-        let body = imagebytes(4, 3, TRANSMISSION_U16, |buf| {
+        let body = imagebytes(4, 3, IMAGE_ELEMENT_I32, TRANSMISSION_U16, |buf| {
             buf.extend_from_slice(&[1u8, 2, 3, 4]);
         });
         assert_eq!(body.len(), IMAGEBYTES_HEADER_LEN + 4);
@@ -964,7 +1199,7 @@ mod tests {
 
     #[test]
     fn imagebytes_header_layout_i32() {
-        let body = imagebytes(2, 2, TRANSMISSION_I32, |_| {});
+        let body = imagebytes(2, 2, IMAGE_ELEMENT_I32, TRANSMISSION_I32, |_| {});
         assert_eq!(body.len(), IMAGEBYTES_HEADER_LEN);
         assert_eq!(&body[24..28], &2i32.to_le_bytes());
     }
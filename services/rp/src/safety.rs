@@ -1,9 +1,11 @@
-//! Safety enforcement (rp.md § Safety): poll every configured ASCOM
-//! `SafetyMonitor`, and on the overall safe → unsafe transition gate the
+//! Safety enforcement (rp.md § Safety): poll every ASCOM `SafetyMonitor`
+//! the equipment registry lists — re-read each pass, so monitors added
+//! or removed at runtime are picked up — and on the overall safe → unsafe transition gate the
 //! `/mcp` endpoint, terminate all open MCP sessions (cancelling in-flight
 //! tool calls), interrupt the active session, abort in-progress
 //! exposures, stop guiding (emitting `guide_stopped` with
-//! `reason: "safety"`), and park the mount. On unsafe → safe, lift the
+//! `reason: "safety"`), park the mount, and — once it reports parked —
+//! close every dome shutter. On unsafe → safe, lift the
 //! gate and resume the interrupted session by re-invoking the
 //! orchestrator with recovery context.
 //!
@@ -20,11 +22,12 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use ascom_alpaca::api::{Camera, Telescope};
 use rmcp::transport::streamable_http_server::session::local::LocalSessionManager;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, info, warn};
 
-use crate::equipment::EquipmentRegistry;
+use crate::equipment::{EquipmentRegistry, SharedEquipment};
 use crate::events::EventBus;
 use crate::session::SessionManager;
 
@@ -35,10 +38,42 @@ pub trait SafetyProbe: Send + Sync {
     fn is_safe(&self) -> impl Future<Output = Result<bool, String>> + Send;
 }
 
+/// The set of probes one polling pass reads. A seam like [`SafetyProbe`]:
+/// production re-reads the equipment registry every pass, tests hand in
+/// a fixed list.
+pub trait SafetyRoster: Send + Sync {
+    type Probe: SafetyProbe;
+    fn probes(&self) -> Vec<Self::Probe>;
+}
+
+/// Production roster: the registry's safety monitors as of this pass.
+pub struct RegistrySafetyRoster {
+    equipment: SharedEquipment,
+}
+
+impl SafetyRoster for RegistrySafetyRoster {
+    type Probe = AlpacaSafetyProbe;
+
+    fn probes(&self) -> Vec<AlpacaSafetyProbe> {
+        self.equipment
+            .snapshot()
+            .safety_monitors
+            .iter()
+            .map(|entry| AlpacaSafetyProbe {
+                id: entry.id.clone(),
+                equipment: self.equipment.clone(),
+            })
+            .collect()
+    }
+}
+
 /// Production probe over a connected (or not) ASCOM Alpaca `SafetyMonitor`.
+/// The device is looked up on every poll, so a monitor reconnected at
+/// runtime is read through its new handle and one disconnected or
+/// removed reads as unsafe.
 pub struct AlpacaSafetyProbe {
     id: String,
-    device: Option<Arc<dyn ascom_alpaca::api::SafetyMonitor>>,
+    equipment: SharedEquipment,
 }
 
 impl SafetyProbe for AlpacaSafetyProbe {
@@ -47,7 +82,11 @@ impl SafetyProbe for AlpacaSafetyProbe {
     }
 
     async fn is_safe(&self) -> Result<bool, String> {
-        let Some(device) = &self.device else {
+        let Some(device) = self
+            .equipment
+            .find_safety_monitor(&self.id)
+            .and_then(|entry| entry.device)
+        else {
             return Err("safety monitor is not connected".to_string());
         };
         device.is_safe().await.map_err(|e| e.to_string())
@@ -55,13 +94,13 @@ impl SafetyProbe for AlpacaSafetyProbe {
 }
 
 /// The polling loop plus everything it drives on a transition.
-pub struct SafetyEnforcer<P: SafetyProbe> {
-    probes: Vec<P>,
+pub struct SafetyEnforcer<R: SafetyRoster> {
+    roster: R,
     poll_interval: Duration,
     event_bus: Arc<EventBus>,
     session: Arc<SessionManager>,
     mcp_sessions: Arc<LocalSessionManager>,
-    equipment: Arc<EquipmentRegistry>,
+    equipment: SharedEquipment,
     /// Guider-service client shared with `McpHandler`; the unsafe
     /// transition stops guiding through it. `None` when no `guider`
     /// block is configured — the step is skipped.
@@ -69,36 +108,30 @@ pub struct SafetyEnforcer<P: SafetyProbe> {
     /// Read by the `/mcp` gate in `routes`: `false` rejects every MCP
     /// request with 503 while conditions are unsafe.
     safety_ok: Arc<AtomicBool>,
+    /// How long the unsafe transition waits for `AtPark` before giving
+    /// up on closing the shutters; [`SAFETY_PARK_WAIT`] outside tests.
+    park_wait: Duration,
 }
 
-impl SafetyEnforcer<AlpacaSafetyProbe> {
-    /// Build the enforcer over the registry's connected safety monitors.
-    /// Returns `None` when none are configured — the loop never starts
-    /// and sessions run ungated.
+impl SafetyEnforcer<RegistrySafetyRoster> {
+    /// Build the enforcer over the registry's safety monitors. It runs
+    /// whether or not any are configured: with none the gate stays open
+    /// and sessions run ungated, and the first monitor connected at
+    /// runtime is polled from the next pass.
     #[allow(clippy::too_many_arguments)]
     pub fn from_registry(
-        equipment: Arc<EquipmentRegistry>,
+        equipment: SharedEquipment,
         event_bus: Arc<EventBus>,
         session: Arc<SessionManager>,
         mcp_sessions: Arc<LocalSessionManager>,
         safety_ok: Arc<AtomicBool>,
         guider: Option<Arc<dyn rp_guider::GuiderClient>>,
         poll_interval: Duration,
-    ) -> Option<Self> {
-        if equipment.safety_monitors.is_empty() {
-            debug!("no safety monitors configured; safety polling disabled");
-            return None;
-        }
-        let probes = equipment
-            .safety_monitors
-            .iter()
-            .map(|entry| AlpacaSafetyProbe {
-                id: entry.id.clone(),
-                device: entry.device.clone(),
-            })
-            .collect();
-        Some(Self {
-            probes,
+    ) -> Self {
+        Self {
+            roster: RegistrySafetyRoster {
+                equipment: equipment.clone(),
+            },
             poll_interval,
             event_bus,
             session,
@@ -106,11 +139,12 @@ impl SafetyEnforcer<AlpacaSafetyProbe> {
             equipment,
             guider,
             safety_ok,
-        })
+            park_wait: SAFETY_PARK_WAIT,
+        }
     }
 }
 
-impl<P: SafetyProbe> SafetyEnforcer<P> {
+impl<R: SafetyRoster> SafetyEnforcer<R> {
     /// Poll until cancelled (rp shutdown).
     pub async fn run(self, cancel: CancellationToken) {
         // Assumed-safe baselines: transitions are relative to these, so
@@ -136,7 +170,7 @@ impl<P: SafetyProbe> SafetyEnforcer<P> {
         mut overall: bool,
     ) {
         info!(
-            monitors = self.probes.len(),
+            monitors = self.roster.probes().len(),
             interval = ?self.poll_interval,
             "safety monitoring started"
         );
@@ -152,16 +186,21 @@ impl<P: SafetyProbe> SafetyEnforcer<P> {
         }
     }
 
-    /// One polling pass: read every probe, emit per-monitor
-    /// `safety_changed` events, and act when the overall state flips.
-    /// Returns the new overall state.
+    /// One polling pass: read every probe on the roster, emit
+    /// per-monitor `safety_changed` events, and act when the overall
+    /// state flips. Returns the new overall state. A monitor no longer
+    /// on the roster is forgotten — it stops counting toward the
+    /// overall state, and if it comes back its first reading is again
+    /// relative to the safe baseline.
     pub(crate) async fn poll_once(
         &self,
         per_monitor: &mut HashMap<String, bool>,
         prev_overall: bool,
     ) -> bool {
+        let probes = self.roster.probes();
+        per_monitor.retain(|id, _| probes.iter().any(|probe| probe.id() == id));
         let mut overall = true;
-        for probe in &self.probes {
+        for probe in &probes {
             let reading = match probe.is_safe().await {
                 Ok(reading) => reading,
                 Err(e) => {
@@ -198,17 +237,25 @@ impl<P: SafetyProbe> SafetyEnforcer<P> {
 
     /// Overall safe → unsafe: gate first so nothing new gets in, then
     /// tear down the workflow's transport and stop the hardware —
-    /// abort exposures, stop guiding, park the mount, in that order
-    /// (the mount must not move under an exposing camera or an active
-    /// guide loop).
+    /// abort exposures, stop guiding, park the mount, close the dome
+    /// shutters, in that order (the mount must not move under an
+    /// exposing camera or an active guide loop). The shutters wait for
+    /// the park to land: a roll-off roof closing over a slewing OTA
+    /// hits it, so they are left open with a warning if the mount
+    /// never reports `AtPark`. The wait holds up this poll pass, not
+    /// the gate, which closed first.
     async fn on_unsafe(&self) {
         warn!("conditions unsafe; cancelling the active workflow");
         self.safety_ok.store(false, Ordering::SeqCst);
         let interrupted = self.session.interrupt().await;
         close_all_mcp_sessions(&self.mcp_sessions).await;
-        abort_exposures(&self.equipment).await;
-        stop_guiding(self.guider.as_ref(), &self.event_bus).await;
-        park_mount(&self.equipment).await;
+        // One snapshot for the whole teardown: a runtime equipment
+        // change landing mid-sequence must not split it across rosters.
+        let equipment = self.equipment.snapshot();
+        abort_exposures(&equipment, &self.equipment.released_cameras()).await;
+        stop_guiding(self.guider.as_ref(), &self.event_bus, "safety").await;
+        let park_commanded = park_mount(&equipment).await;
+        close_shutters_once_parked(&equipment, park_commanded, self.park_wait).await;
         if interrupted {
             info!("session interrupted; awaiting safe conditions to resume");
         }
@@ -229,7 +276,25 @@ impl<P: SafetyProbe> SafetyEnforcer<P> {
 /// orchestrator's next call on a closed session surfaces as a terminated
 /// session (the engine exits without completion and keeps its state).
 async fn close_all_mcp_sessions(manager: &LocalSessionManager) {
-    let handles: Vec<_> = manager.sessions.write().await.drain().collect();
+    close_mcp_sessions(manager, None).await;
+}
+
+/// Close every open MCP session but `keep`, cancelling their in-flight
+/// tool calls — `abort_session`'s step 2, which spares the session the
+/// abort itself arrived on. Returns how many were closed.
+pub(crate) async fn close_mcp_sessions(manager: &LocalSessionManager, keep: Option<&str>) -> usize {
+    let handles: Vec<_> = {
+        let mut sessions = manager.sessions.write().await;
+        let ids: Vec<_> = sessions
+            .keys()
+            .filter(|id| Some(id.as_ref()) != keep)
+            .cloned()
+            .collect();
+        ids.into_iter()
+            .filter_map(|id| sessions.remove_entry(&id))
+            .collect()
+    };
+    let closed = handles.len();
     for (id, handle) in handles {
         debug!(mcp_session = %id, "terminating MCP session");
         if let Err(e) = handle.close().await {
@@ -237,25 +302,41 @@ async fn close_all_mcp_sessions(manager: &LocalSessionManager) {
             debug!(mcp_session = %id, error = %e, "MCP session close reported an error");
         }
     }
+    closed
 }
 
 /// Best-effort `AbortExposure` on every connected camera — the tool task
 /// driving an exposure was just cancelled with its MCP session, so the
-/// camera would otherwise keep exposing into the (unsafe) night.
-async fn abort_exposures(equipment: &EquipmentRegistry) {
-    for camera in &equipment.cameras {
-        let Some(device) = &camera.device else {
-            continue;
-        };
+/// camera would otherwise keep exposing into the (unsafe) night. Also
+/// `abort_session`'s step 3. `released` are the camera handles a
+/// runtime change dropped while in-flight work still holds them
+/// ([`SharedEquipment::released_cameras`]): a camera disconnected
+/// mid-exposure is no longer listed but may still be exposing. Returns
+/// the cameras that accepted the abort.
+pub(crate) async fn abort_exposures(
+    equipment: &EquipmentRegistry,
+    released: &[(String, Arc<dyn Camera>)],
+) -> Vec<String> {
+    let listed = equipment
+        .cameras
+        .iter()
+        .filter_map(|camera| Some((&camera.id, camera.device.as_ref()?)));
+    let held = released.iter().map(|(id, device)| (id, device));
+    let mut aborted = Vec::new();
+    for (id, device) in listed.chain(held) {
         match device.abort_exposure().await {
-            Ok(()) => debug!(camera = %camera.id, "aborted in-progress exposure"),
+            Ok(()) => {
+                debug!(camera = %id, "aborted in-progress exposure");
+                aborted.push(id.clone());
+            }
             Err(e) => {
                 // Usually just "no exposure in progress" — worth a debug
                 // line, not an operator-facing warning.
-                debug!(camera = %camera.id, error = %e, "abort_exposure failed");
+                debug!(camera = %id, error = %e, "abort_exposure failed");
             }
         }
     }
+    aborted
 }
 
 /// Upper bound on how long the unsafe transition waits for stop-guiding
@@ -270,46 +351,138 @@ const SAFETY_STOP_GUIDING_TIMEOUT: Duration = Duration::from_secs(15);
 
 /// Best-effort stop-guiding through the shared guider client — the
 /// guide loop must not keep dragging the mount while conditions are
-/// unsafe. Emits `guide_stopped` with `reason: "safety"` on a
-/// confirmed stop; a failure (service down, PHD2 gone) or a stop that
+/// unsafe (or the session is being aborted). Emits `guide_stopped`
+/// with the given `reason` on a confirmed stop and returns whether it
+/// confirmed; a failure (service down, PHD2 gone) or a stop that
 /// doesn't confirm within [`SAFETY_STOP_GUIDING_TIMEOUT`] is logged and
 /// swallowed so the park below still runs promptly.
-async fn stop_guiding(guider: Option<&Arc<dyn rp_guider::GuiderClient>>, event_bus: &EventBus) {
+pub(crate) async fn stop_guiding(
+    guider: Option<&Arc<dyn rp_guider::GuiderClient>>,
+    event_bus: &EventBus,
+    reason: &'static str,
+) -> bool {
     let Some(client) = guider else {
-        return;
+        return false;
     };
     match tokio::time::timeout(SAFETY_STOP_GUIDING_TIMEOUT, client.stop_guiding()).await {
         Ok(Ok(())) => {
-            debug!("guiding stopped on unsafe transition");
-            event_bus.emit("guide_stopped", serde_json::json!({ "reason": "safety" }));
+            debug!(reason, "guiding stopped");
+            event_bus.emit("guide_stopped", serde_json::json!({ "reason": reason }));
+            true
         }
         Ok(Err(e)) => {
-            debug!(error = %e, "stop_guiding failed during unsafe transition");
+            debug!(reason, error = %e, "stop_guiding failed");
+            false
         }
         Err(_) => {
             debug!(
+                reason,
                 timeout = ?SAFETY_STOP_GUIDING_TIMEOUT,
-                "stop_guiding did not confirm in time during unsafe transition; proceeding to park"
+                "stop_guiding did not confirm in time; proceeding to park"
             );
+            false
         }
     }
 }
 
 /// Best-effort park on the configured mount — fire-and-forget like
 /// [`abort_exposures`]: the Alpaca `Park` is issued and logged, but
-/// the enforcer does not block on `AtPark` (Sentinel's watchdog owns
-/// escalation if the mount never gets there).
-async fn park_mount(equipment: &EquipmentRegistry) {
+/// the caller does not block on `AtPark` (Sentinel's watchdog owns
+/// escalation if the mount never gets there). Returns whether the
+/// park was commanded.
+pub(crate) async fn park_mount(equipment: &EquipmentRegistry) -> bool {
     let Some(mount) = &equipment.mount else {
-        return;
+        return false;
     };
     let Some(device) = &mount.device else {
-        debug!("mount not connected; skipping park on unsafe transition");
-        return;
+        debug!("mount not connected; skipping park");
+        return false;
     };
     match device.park().await {
-        Ok(()) => debug!("mount park commanded on unsafe transition"),
-        Err(e) => debug!(error = %e, "mount park failed during unsafe transition"),
+        Ok(()) => {
+            debug!("mount park commanded");
+            true
+        }
+        Err(e) => {
+            debug!(error = %e, "mount park failed");
+            false
+        }
+    }
+}
+
+/// Upper bound on how long the unsafe transition waits for the mount to
+/// report `AtPark` before closing the dome shutters — the same 5 min
+/// ceiling the `park` tool falls back to when it cannot size its
+/// deadline from the slew rate.
+const SAFETY_PARK_WAIT: Duration = Duration::from_mins(5);
+
+/// `AtPark` poll cadence while [`close_shutters_once_parked`] waits.
+const SAFETY_PARK_POLL: Duration = Duration::from_millis(500);
+
+/// Close the dome shutters once the mount is parked. With no mount
+/// configured there is nothing to close onto, so they close at once;
+/// otherwise the mount must confirm `AtPark` within `park_wait`. A
+/// mount that is disconnected, refused the park, or is still moving
+/// when the wait runs out leaves the shutters open with a `warn` — an
+/// open roof in bad weather is the operator's call, a roof driven into
+/// the telescope is not.
+async fn close_shutters_once_parked(
+    equipment: &EquipmentRegistry,
+    park_commanded: bool,
+    park_wait: Duration,
+) {
+    if !equipment.domes.iter().any(|dome| dome.device.is_some()) {
+        return;
+    }
+    if let Some(mount) = &equipment.mount {
+        let parked = match (&mount.device, park_commanded) {
+            (Some(device), true) => wait_for_park(device.as_ref(), park_wait).await,
+            _ => false,
+        };
+        if !parked {
+            warn!(
+                wait = ?park_wait,
+                "mount not confirmed parked; leaving dome shutters open rather than closing them onto the telescope"
+            );
+            return;
+        }
+    }
+    close_shutters(equipment).await;
+}
+
+/// Poll `AtPark` until it reads `true` or `wait` elapses. A failed read
+/// is logged and retried: one dropped request must not decide whether
+/// the roof closes.
+async fn wait_for_park(mount: &dyn Telescope, wait: Duration) -> bool {
+    let poll = async {
+        loop {
+            match mount.at_park().await {
+                Ok(true) => return,
+                Ok(false) => {}
+                Err(e) => debug!(error = %e, "at_park read failed while waiting to close shutters"),
+            }
+            tokio::time::sleep(SAFETY_PARK_POLL).await;
+        }
+    };
+    tokio::time::timeout(wait, poll).await.is_ok()
+}
+
+/// Best-effort `CloseShutter` on every connected dome — fire-and-forget
+/// like [`park_mount`]. Errors are logged at `warn`: unlike an abort
+/// with no exposure in progress, a shutter that refuses to close in
+/// unsafe weather is something the operator must hear about.
+async fn close_shutters(equipment: &EquipmentRegistry) {
+    for dome in &equipment.domes {
+        let Some(device) = &dome.device else {
+            debug!(dome = %dome.id, "dome not connected; skipping shutter close on unsafe transition");
+            continue;
+        };
+        match device.close_shutter().await {
+            Ok(()) => debug!(dome = %dome.id, "shutter close commanded on unsafe transition"),
+            Err(e) => {
+                warn!(dome = %dome.id, error = %e, "shutter close failed during unsafe transition");
+            }
+        }
     }
 }
 
@@ -324,21 +497,31 @@ mod tests {
     use super::*;
 
     /// Probe whose readings are scripted: pops the front of the queue,
-    /// repeating the last entry once drained.
+    /// repeating the last entry once drained. Clones share the queue, so
+    /// a fixed roster hands out the same script every pass.
+    #[derive(Clone)]
     struct ScriptedProbe {
         id: String,
-        readings: Mutex<Vec<Result<bool, String>>>,
+        readings: Arc<Mutex<Vec<Result<bool, String>>>>,
     }
 
     impl ScriptedProbe {
         fn new(id: &str, readings: Vec<Result<bool, String>>) -> Self {
             Self {
                 id: id.to_string(),
-                readings: Mutex::new(readings),
+                readings: Arc::new(Mutex::new(readings)),
             }
         }
     }
 
+    impl SafetyRoster for Vec<ScriptedProbe> {
+        type Probe = ScriptedProbe;
+
+        fn probes(&self) -> Vec<ScriptedProbe> {
+            self.clone()
+        }
+    }
+
     impl SafetyProbe for ScriptedProbe {
         fn id(&self) -> &str {
             &self.id
@@ -354,8 +537,8 @@ mod tests {
         }
     }
 
-    fn empty_registry() -> Arc<EquipmentRegistry> {
-        Arc::new(EquipmentRegistry {
+    fn empty_registry() -> SharedEquipment {
+        SharedEquipment::new(EquipmentRegistry {
             cameras: vec![],
             filter_wheels: vec![],
             cover_calibrators: vec![],
@@ -366,11 +549,11 @@ mod tests {
         })
     }
 
-    fn enforcer_with(probes: Vec<ScriptedProbe>) -> SafetyEnforcer<ScriptedProbe> {
+    fn enforcer_with(probes: Vec<ScriptedProbe>) -> SafetyEnforcer<Vec<ScriptedProbe>> {
         let event_bus = Arc::new(EventBus::from_config(&[], None).unwrap());
         let session = Arc::new(SessionManager::new(event_bus.clone(), &[], None).unwrap());
         SafetyEnforcer {
-            probes,
+            roster: probes,
             poll_interval: Duration::from_millis(1),
             event_bus,
             session,
@@ -378,6 +561,7 @@ mod tests {
             equipment: empty_registry(),
             guider: None,
             safety_ok: Arc::new(AtomicBool::new(true)),
+            park_wait: SAFETY_PARK_WAIT,
         }
     }
 
@@ -385,7 +569,7 @@ mod tests {
     fn enforcer_with_guider(
         probes: Vec<ScriptedProbe>,
         configure: impl FnOnce(&mut rp_guider::MockGuiderClient),
-    ) -> SafetyEnforcer<ScriptedProbe> {
+    ) -> SafetyEnforcer<Vec<ScriptedProbe>> {
         let mut mock = rp_guider::MockGuiderClient::new();
         configure(&mut mock);
         let mut enforcer = enforcer_with(probes);
@@ -616,6 +800,19 @@ mod tests {
         async fn reselect_star(&self) -> Result<(), rp_guider::GuiderError> {
             unreachable!("not exercised by this test")
         }
+
+        async fn guiding_settings(
+            &self,
+        ) -> Result<rp_guider::GuidingSettings, rp_guider::GuiderError> {
+            unreachable!("not exercised by this test")
+        }
+
+        async fn update_guiding_settings(
+            &self,
+            _update: rp_guider::GuidingSettingsUpdate,
+        ) -> Result<rp_guider::GuidingSettings, rp_guider::GuiderError> {
+            unreachable!("not exercised by this test")
+        }
     }
 
     /// A guider service that never confirms the stop must not delay
@@ -708,20 +905,61 @@ mod tests {
         assert!(enforcer.mcp_sessions.sessions.read().await.is_empty());
     }
 
+    /// An enforcer started with no monitors configured keeps the gate
+    /// open, and polls a monitor hot-added at runtime from the next
+    /// pass: one that cannot connect reads unsafe and closes the gate.
     #[tokio::test]
-    async fn from_registry_is_none_without_monitors() {
+    async fn a_monitor_hot_added_at_runtime_engages_the_gate() {
+        let equipment = empty_registry();
         let event_bus = Arc::new(EventBus::from_config(&[], None).unwrap());
         let session = Arc::new(SessionManager::new(event_bus.clone(), &[], None).unwrap());
         let enforcer = SafetyEnforcer::from_registry(
-            empty_registry(),
-            event_bus,
+            equipment.clone(),
+            event_bus.clone(),
             session,
             Arc::new(LocalSessionManager::default()),
             Arc::new(AtomicBool::new(true)),
             None,
             Duration::from_secs(10),
         );
-        assert!(enforcer.is_none());
+        let mut events = event_bus.subscribe();
+
+        let mut state = HashMap::new();
+        let overall = enforcer.poll_once(&mut state, true).await;
+        assert!(overall, "no monitors: nothing to gate on");
+        assert!(enforcer.safety_ok.load(Ordering::SeqCst));
+
+        let equipment_cfg = crate::config::EquipmentConfig {
+            safety_monitors: vec![crate::config::SafetyMonitorConfig {
+                id: "roof-sensor".to_string(),
+                // Client construction fails instantly on a bad URL, so
+                // the hot-added entry is listed disconnected.
+                alpaca_url: "not-a-url".to_string(),
+                device_number: 0,
+                auth: None,
+            }],
+            ..Default::default()
+        };
+        equipment
+            .connect_device(
+                crate::equipment::DeviceKind::SafetyMonitors,
+                Some("roof-sensor"),
+                &equipment_cfg,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let overall = enforcer.poll_once(&mut state, overall).await;
+        assert!(!overall);
+        assert!(
+            !enforcer.safety_ok.load(Ordering::SeqCst),
+            "the hot-added monitor must gate /mcp"
+        );
+        let changed = events.recv().await.unwrap();
+        assert_eq!(changed.payload["monitor"], "roof-sensor");
+        assert_eq!(changed.payload["new_state"], "unsafe");
     }
 
     /// The unsafe transition parks a connected mount: a registry with
@@ -774,13 +1012,14 @@ mod tests {
                 settle_after_slew: None,
                 slew_rate_arcsec_per_sec: Default::default(),
                 guiding: None,
+                meridian_flip: Default::default(),
                 auth: None,
             }),
             ..Default::default()
         };
-        let equipment = Arc::new(EquipmentRegistry::new(&equipment_cfg, None).await);
+        let equipment = SharedEquipment::new(EquipmentRegistry::new(&equipment_cfg, None).await);
         assert!(
-            equipment.mount.as_ref().is_some_and(|m| m.connected),
+            equipment.find_mount().is_some_and(|m| m.connected),
             "test setup: the stubbed mount must connect"
         );
 
@@ -796,6 +1035,229 @@ mod tests {
         );
     }
 
+    /// The unsafe transition closes a connected dome's shutter: a
+    /// registry with a stubbed-Alpaca dome must receive `PUT closeshutter`.
+    #[tokio::test]
+    async fn unsafe_transition_closes_the_dome_shutter() {
+        use axum::routing::{get, put};
+        use axum::{Json, Router};
+
+        let close_called = Arc::new(AtomicBool::new(false));
+        let close_flag = close_called.clone();
+        let app = Router::new()
+            .route(
+                "/management/v1/configureddevices",
+                get(|| async {
+                    Json(serde_json::json!({
+                        "Value": [
+                            {
+                                "DeviceName": "Dome 0",
+                                "DeviceType": "Dome",
+                                "DeviceNumber": 0,
+                                "UniqueID": "test-dome-uid"
+                            }
+                        ],
+                        "ErrorNumber": 0,
+                        "ErrorMessage": ""
+                    }))
+                }),
+            )
+            .route(
+                "/api/v1/dome/0/connected",
+                put(|| async { Json(serde_json::json!({"ErrorNumber": 0, "ErrorMessage": ""})) }),
+            )
+            .route(
+                "/api/v1/dome/0/closeshutter",
+                put(move || {
+                    let close_flag = close_flag.clone();
+                    async move {
+                        close_flag.store(true, Ordering::SeqCst);
+                        Json(serde_json::json!({"ErrorNumber": 0, "ErrorMessage": ""}))
+                    }
+                }),
+            );
+        let stub = crate::equipment::test_support::spawn_stub(app).await;
+
+        let equipment_cfg = crate::config::EquipmentConfig {
+            domes: vec![crate::config::DomeConfig {
+                id: "roll-off".to_string(),
+                name: None,
+                alpaca_url: stub.url(),
+                device_number: 0,
+                auth: None,
+                slaving: None,
+            }],
+            ..Default::default()
+        };
+        let equipment = SharedEquipment::new(EquipmentRegistry::new(&equipment_cfg, None).await);
+        assert!(
+            equipment
+                .snapshot()
+                .domes
+                .first()
+                .is_some_and(|d| d.connected),
+            "test setup: the stubbed dome must connect"
+        );
+
+        let mut enforcer = enforcer_with(vec![ScriptedProbe::new("sm", vec![Ok(false)])]);
+        enforcer.equipment = equipment;
+
+        let mut state = HashMap::new();
+        enforcer.poll_once(&mut state, true).await;
+
+        assert!(
+            close_called.load(Ordering::SeqCst),
+            "the unsafe transition must command CloseShutter on the connected dome"
+        );
+    }
+
+    /// A stubbed rig with a mount and a roll-off dome. The mount's
+    /// `atpark` reads `false` for its first `at_park_false_reads` polls
+    /// (forever when `None`) — a slow park. Returns the registry, the
+    /// `atpark` read count, and the read count at which `closeshutter`
+    /// arrived (`None` until it does).
+    async fn slow_parking_rig(
+        at_park_false_reads: Option<u32>,
+    ) -> (
+        SharedEquipment,
+        Arc<std::sync::atomic::AtomicU32>,
+        Arc<Mutex<Option<u32>>>,
+        crate::equipment::test_support::AlpacaStub,
+    ) {
+        use axum::routing::{get, put};
+        use axum::{Json, Router};
+        use std::sync::atomic::AtomicU32;
+
+        let at_park_reads = Arc::new(AtomicU32::new(0));
+        let closed_after = Arc::new(Mutex::new(None));
+        let reads = at_park_reads.clone();
+        let reads_at_close = at_park_reads.clone();
+        let close_log = closed_after.clone();
+        let ok = || async { Json(serde_json::json!({"ErrorNumber": 0, "ErrorMessage": ""})) };
+        let app = Router::new()
+            .route(
+                "/management/v1/configureddevices",
+                get(|| async {
+                    Json(serde_json::json!({
+                        "Value": [
+                            {
+                                "DeviceName": "Telescope 0",
+                                "DeviceType": "Telescope",
+                                "DeviceNumber": 0,
+                                "UniqueID": "test-scope-uid"
+                            },
+                            {
+                                "DeviceName": "Dome 0",
+                                "DeviceType": "Dome",
+                                "DeviceNumber": 0,
+                                "UniqueID": "test-dome-uid"
+                            }
+                        ],
+                        "ErrorNumber": 0,
+                        "ErrorMessage": ""
+                    }))
+                }),
+            )
+            .route("/api/v1/telescope/0/connected", put(ok))
+            .route("/api/v1/telescope/0/park", put(ok))
+            .route(
+                "/api/v1/telescope/0/atpark",
+                get(move || {
+                    let n = reads.fetch_add(1, Ordering::SeqCst) + 1;
+                    let parked = at_park_false_reads.is_some_and(|false_reads| n > false_reads);
+                    async move {
+                        Json(serde_json::json!({
+                            "Value": parked,
+                            "ErrorNumber": 0,
+                            "ErrorMessage": ""
+                        }))
+                    }
+                }),
+            )
+            .route("/api/v1/dome/0/connected", put(ok))
+            .route(
+                "/api/v1/dome/0/closeshutter",
+                put(move || {
+                    *close_log.lock().unwrap() = Some(reads_at_close.load(Ordering::SeqCst));
+                    ok()
+                }),
+            );
+        let stub = crate::equipment::test_support::spawn_stub(app).await;
+
+        let equipment_cfg = crate::config::EquipmentConfig {
+            mount: Some(crate::config::MountConfig {
+                alpaca_url: stub.url(),
+                device_number: 0,
+                settle_after_slew: None,
+                slew_rate_arcsec_per_sec: Default::default(),
+                guiding: None,
+                meridian_flip: Default::default(),
+                auth: None,
+            }),
+            domes: vec![crate::config::DomeConfig {
+                id: "roll-off".to_string(),
+                name: None,
+                alpaca_url: stub.url(),
+                device_number: 0,
+                auth: None,
+                slaving: None,
+            }],
+            ..Default::default()
+        };
+        let equipment = SharedEquipment::new(EquipmentRegistry::new(&equipment_cfg, None).await);
+        let registry = equipment.snapshot();
+        assert!(
+            registry.mount.as_ref().is_some_and(|m| m.connected)
+                && registry.domes.first().is_some_and(|d| d.connected),
+            "test setup: the stubbed mount and dome must connect"
+        );
+        (equipment, at_park_reads, closed_after, stub)
+    }
+
+    /// A roll-off roof must not close onto a telescope still slewing to
+    /// park: with a mount that takes a few polls to report `AtPark`,
+    /// `CloseShutter` arrives only after the parked reading.
+    #[tokio::test]
+    async fn unsafe_transition_closes_the_shutter_only_after_the_mount_parks() {
+        let (equipment, at_park_reads, closed_after, _stub) = slow_parking_rig(Some(2)).await;
+        let mut enforcer = enforcer_with(vec![ScriptedProbe::new("sm", vec![Ok(false)])]);
+        enforcer.equipment = equipment;
+
+        let mut state = HashMap::new();
+        enforcer.poll_once(&mut state, true).await;
+
+        assert_eq!(at_park_reads.load(Ordering::SeqCst), 3);
+        assert_eq!(
+            *closed_after.lock().unwrap(),
+            Some(3),
+            "CloseShutter must follow the first parked reading, not the park command"
+        );
+    }
+
+    /// A mount that never reports parked within the wait leaves the
+    /// shutters open — the transition still gates and returns.
+    #[tokio::test]
+    async fn unsafe_transition_leaves_the_shutter_open_when_the_park_never_lands() {
+        let (equipment, at_park_reads, closed_after, _stub) = slow_parking_rig(None).await;
+        let mut enforcer = enforcer_with(vec![ScriptedProbe::new("sm", vec![Ok(false)])]);
+        enforcer.equipment = equipment;
+        enforcer.park_wait = Duration::from_secs(1);
+
+        let mut state = HashMap::new();
+        enforcer.poll_once(&mut state, true).await;
+
+        assert!(!enforcer.safety_ok.load(Ordering::SeqCst));
+        assert!(
+            at_park_reads.load(Ordering::SeqCst) >= 2,
+            "the park was polled"
+        );
+        assert_eq!(
+            *closed_after.lock().unwrap(),
+            None,
+            "the shutter must stay open while the mount is unparked"
+        );
+    }
+
     /// A configured-but-unreachable mount is skipped without error —
     /// the transition still gates.
     #[tokio::test]
@@ -809,11 +1271,12 @@ mod tests {
                 settle_after_slew: None,
                 slew_rate_arcsec_per_sec: Default::default(),
                 guiding: None,
+                meridian_flip: Default::default(),
                 auth: None,
             }),
             ..Default::default()
         };
-        let equipment = Arc::new(EquipmentRegistry::new(&equipment_cfg, None).await);
+        let equipment = SharedEquipment::new(EquipmentRegistry::new(&equipment_cfg, None).await);
 
         let mut enforcer = enforcer_with(vec![ScriptedProbe::new("sm", vec![Ok(false)])]);
         enforcer.equipment = equipment;
@@ -941,7 +1404,7 @@ mod tests {
             ],
             ..Default::default()
         };
-        let equipment = Arc::new(EquipmentRegistry::new(&equipment_cfg, None).await);
+        let equipment = SharedEquipment::new(EquipmentRegistry::new(&equipment_cfg, None).await);
         let event_bus = Arc::new(EventBus::from_config(&[], None).unwrap());
         let session = Arc::new(SessionManager::new(event_bus.clone(), &[], None).unwrap());
         let enforcer = SafetyEnforcer::from_registry(
@@ -952,8 +1415,7 @@ mod tests {
             Arc::new(AtomicBool::new(true)),
             None,
             Duration::from_millis(1),
-        )
-        .expect("monitors are configured");
+        );
         let mut events = event_bus.subscribe();
 
         let mut state = HashMap::new();
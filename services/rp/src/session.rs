@@ -232,6 +232,11 @@ pub struct SessionManager {
     /// and defers to the resume path). `None` in tests that only
     /// exercise the state machine.
     cooling: Option<Arc<crate::cooling::CoolingController>>,
+    /// Dew-heater controller (rp.md § Dew Management): runs alongside
+    /// the cooler — started with the session, held through an
+    /// interruption, stopped (heaters off) at every transition to idle.
+    /// `None` when no `dew` block is configured.
+    dew: Option<Arc<crate::dew::DewController>>,
 }
 
 impl SessionManager {
@@ -263,6 +268,7 @@ impl SessionManager {
             planner_progress: None,
             state_path: None,
             cooling: None,
+            dew: None,
         })
     }
 
@@ -290,6 +296,13 @@ impl SessionManager {
         self
     }
 
+    /// Wire the dew-heater controller so session transitions start and
+    /// stop it (rp.md § Dew Management).
+    pub fn with_dew(mut self, dew: Option<Arc<crate::dew::DewController>>) -> Self {
+        self.dew = dew;
+        self
+    }
+
     pub async fn set_mcp_base_url(&self, url: String) {
         *self.mcp_base_url.write().await = url;
     }
@@ -354,6 +367,9 @@ impl SessionManager {
         if let Some(cooling) = &self.cooling {
             cooling.start_cooldown();
         }
+        if let Some(dew) = &self.dew {
+            dew.start();
+        }
 
         Ok(serde_json::json!({
             "session_id": session_id,
@@ -423,6 +439,9 @@ impl SessionManager {
         if let Some(cooling) = &self.cooling {
             cooling.recover();
         }
+        if let Some(dew) = &self.dew {
+            dew.start();
+        }
 
         debug!(session_id = %session_id, workflow_id = %workflow_id,
                "conditions safe again; re-invoking the orchestrator with recovery context");
@@ -492,6 +511,9 @@ impl SessionManager {
         if let Some(cooling) = &self.cooling {
             cooling.start_warmup();
         }
+        if let Some(dew) = &self.dew {
+            dew.stop();
+        }
     }
 
     pub async fn stop(&self) -> Result<(), String> {
@@ -514,10 +536,60 @@ impl SessionManager {
         if let Some(cooling) = &self.cooling {
             cooling.start_warmup();
         }
+        if let Some(dew) = &self.dew {
+            dew.stop();
+        }
 
         Ok(())
     }
 
+    /// End the live session — active or interrupted — as aborted
+    /// (`abort_session`, rp.md § Session Abort): back to `Idle`, state
+    /// file deleted, `session_stopped` with `reason: "aborted"`, cooled
+    /// cameras ramped warm. Only the bookkeeping half — the caller has
+    /// already stopped the hardware. Returns the ended session's ids;
+    /// errors when there is no session to abort.
+    pub async fn abort(&self) -> Result<Value, String> {
+        let mut state = self.state.write().await;
+        let (session_id, workflow_id) = match &*state {
+            SessionState::Active {
+                session_id,
+                workflow_id,
+                ..
+            }
+            | SessionState::Interrupted {
+                session_id,
+                workflow_id,
+                ..
+            } => (session_id.clone(), workflow_id.clone()),
+            SessionState::Idle => return Err("no session to abort".to_string()),
+        };
+        *state = SessionState::Idle;
+        self.delete_state_file().await;
+        drop(state);
+
+        info!(session_id = %session_id, workflow_id = %workflow_id, "session aborted");
+        self.event_bus.emit(
+            "session_stopped",
+            serde_json::json!({
+                "reason": "aborted",
+                "session_id": session_id,
+                "workflow_id": workflow_id,
+            }),
+        );
+        if let Some(cooling) = &self.cooling {
+            cooling.start_warmup();
+        }
+        if let Some(dew) = &self.dew {
+            dew.stop();
+        }
+
+        Ok(serde_json::json!({
+            "session_id": session_id,
+            "workflow_id": workflow_id,
+        }))
+    }
+
     pub async fn status(&self) -> String {
         let state = self.state.read().await;
         match *state {
@@ -558,6 +630,9 @@ impl SessionManager {
             if let Some(cooling) = &self.cooling {
                 cooling.start_warmup();
             }
+            if let Some(dew) = &self.dew {
+                dew.stop();
+            }
         } else {
             debug!(workflow_id = %workflow_id, "workflow_complete received but no matching active session");
         }
@@ -693,6 +768,9 @@ impl SessionManager {
         if let Some(cooling) = &self.cooling {
             cooling.recover();
         }
+        if let Some(dew) = &self.dew {
+            dew.start();
+        }
 
         info!(session_id = %persisted.session_id, workflow_id = %persisted.workflow_id,
               persisted_status = ?persisted.status, started_at = %persisted.started_at,
@@ -1222,6 +1300,32 @@ mod tests {
         assert!(!path.exists(), "stop must delete the session state file");
     }
 
+    #[tokio::test]
+    async fn abort_ends_an_interrupted_session_with_the_aborted_reason() {
+        let stub = spawn_invoke_stub(vec![StatusCode::OK]).await;
+        let dir = tempfile::tempdir().unwrap();
+        let path = state_path(&dir);
+        let (manager, _) = manager_with_state(&stub.url, path.clone());
+        let started = manager.start().await.unwrap();
+        manager.interrupt().await;
+        let mut rx = manager.event_bus.subscribe();
+
+        let aborted = manager.abort().await.unwrap();
+        assert_eq!(aborted, started);
+        assert_eq!(manager.status().await, "idle");
+        assert!(!path.exists(), "abort must delete the session state file");
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.event, "session_stopped");
+        assert_eq!(event.payload["reason"], "aborted");
+        assert_eq!(event.payload["session_id"], started["session_id"]);
+
+        assert_eq!(
+            manager.abort().await.unwrap_err(),
+            "no session to abort",
+            "an idle manager has nothing to abort"
+        );
+    }
+
     #[tokio::test]
     async fn interrupt_and_resume_rewrite_the_persisted_status() {
         let stub = spawn_invoke_stub(vec![StatusCode::OK]).await;
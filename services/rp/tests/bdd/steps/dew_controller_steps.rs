@@ -0,0 +1,219 @@
+//! BDD step definitions for the dew-heater controller
+//! (`dew_controller.feature`).
+//!
+//! rp's `dew` block names a switch port by index, so the heater port has
+//! to be known before rp starts: the Givens read `OmniSim`'s switch over
+//! Alpaca and pick a writable analog port whose range is a whole number
+//! of steps, so 0 % and 100 % duty land exactly on `min` and `max`.
+//! Session, capture, document and webhook steps are shared with
+//! `session_steps.rs`, `tool_steps.rs`, `document_http_api_steps.rs`,
+//! `event_steps.rs` and `cooling_steps.rs`.
+
+use std::time::Duration;
+
+use cucumber::{given, then};
+use serde_json::Value;
+
+use bdd_infra::rp_harness::{ObservingConditionsConfig, SwitchConfig};
+
+use crate::steps::tool_steps::{add_camera, ensure_omnisim, start_rp};
+use crate::world::RpWorld;
+
+// --- Given steps ---
+
+/// A sensorless heater with its duty floor and ceiling both at `duty`:
+/// feed-forward then writes `duty` whatever the simulated weather.
+#[given(
+    expr = "rp is running with a dew heater held at {float} % duty on the simulator and the test orchestrator"
+)]
+async fn rp_with_held_dew_heater(world: &mut RpWorld, duty: f64) {
+    let index = prepare_dew_rig(world).await;
+    world.dew_config = Some(dew_block(serde_json::json!({
+        "id": "main",
+        "switch_id": "main-switch",
+        "index": index,
+        "min_duty_pct": duty,
+        "max_duty_pct": duty,
+    })));
+    start_rp(world).await;
+}
+
+/// A sensed heater whose margin no simulated reading can meet: every
+/// PI step pins it at full duty still short of target — `dew_risk`.
+#[given(
+    "rp is running with a sensed dew heater that cannot reach its target on the simulator and the test orchestrator"
+)]
+async fn rp_with_overwhelmed_dew_heater(world: &mut RpWorld) {
+    let index = prepare_dew_rig(world).await;
+    let sensor = usize::from(index == 0);
+    world.dew_config = Some(dew_block(serde_json::json!({
+        "id": "main",
+        "switch_id": "main-switch",
+        "index": index,
+        "margin_c": 1000.0,
+        "temperature_sensor": { "switch_id": "main-switch", "index": sensor },
+    })));
+    start_rp(world).await;
+}
+
+// --- Then steps ---
+
+#[then(expr = "the dew heater port should read {float} % duty on the simulator")]
+async fn heater_port_reads_duty(world: &mut RpWorld, duty: f64) {
+    let (index, min, max) = heater_port(world);
+    let expected = min + duty / 100.0 * (max - min);
+    let mut value = f64::NAN;
+    for _ in 0..40 {
+        value = simulator_switch_number(world, "getswitchvalue", Some(index)).await;
+        if (value - expected).abs() < 1e-6 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    panic!("heater port {index} reads {value}, expected {expected} ({duty} % duty)");
+}
+
+#[then(
+    expr = "the test webhook receiver should receive a \"switch_changed\" event for the dew heater at {float} % duty"
+)]
+async fn switch_changed_for_heater(world: &mut RpWorld, duty: f64) {
+    let (index, min, max) = heater_port(world);
+    let expected = min + duty / 100.0 * (max - min);
+    for _ in 0..40 {
+        if world.received_events.read().await.iter().any(|e| {
+            e.event_type == "switch_changed"
+                && e.payload["heater_id"] == "main"
+                && e.payload["switch_id"] == "main-switch"
+                && e.payload["index"] == index
+                && e.payload["value"]
+                    .as_f64()
+                    .is_some_and(|v| (v - expected).abs() < 1e-6)
+        }) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    let seen: Vec<Value> = world
+        .received_events
+        .read()
+        .await
+        .iter()
+        .filter(|e| e.event_type == "switch_changed")
+        .map(|e| e.payload.clone())
+        .collect();
+    panic!("no switch_changed for the dew heater at value {expected} within 10s; saw {seen:?}");
+}
+
+#[then(expr = "the document should record the dew heater at {float} % duty")]
+fn document_records_heater_duty(world: &mut RpWorld, duty: f64) {
+    let doc = world
+        .last_document_response_body
+        .as_ref()
+        .expect("no document fetched — add an 'I fetch the document ...' step first");
+    let recorded = doc["dew_heater_duty_pct"]["main"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no dew_heater_duty_pct for heater 'main' in {doc}"));
+    assert!(
+        (recorded - duty).abs() < 1e-6,
+        "document records {recorded} % duty for heater 'main', expected {duty}"
+    );
+}
+
+// --- Helpers ---
+
+/// Configure the camera, switch and observing-conditions device a dew
+/// scenario needs and pick the heater port. Returns its index.
+async fn prepare_dew_rig(world: &mut RpWorld) -> usize {
+    ensure_omnisim(world).await;
+    add_camera(world);
+    let url = world.omnisim_url();
+    world.switches.push(SwitchConfig {
+        id: "main-switch".to_string(),
+        alpaca_url: url.clone(),
+        device_number: 0,
+    });
+    world.observing_conditions.push(ObservingConditionsConfig {
+        id: "main-oc".to_string(),
+        alpaca_url: url,
+        device_number: 0,
+    });
+    let port = choose_heater_port(world).await;
+    world.dew_heater_port = Some(port);
+    port.0
+}
+
+/// The `dew` block around one heater, stepping every 500 ms so the
+/// scenarios see a write within a second of the session starting.
+fn dew_block(heater: Value) -> Value {
+    serde_json::json!({
+        "observing_conditions_id": "main-oc",
+        "poll_interval": "500ms",
+        "heaters": [heater],
+    })
+}
+
+fn heater_port(world: &RpWorld) -> (usize, f64, f64) {
+    world
+        .dew_heater_port
+        .expect("start rp with a dew heater before asserting on it")
+}
+
+/// The first writable simulator switch port with at least two whole
+/// steps between `min` and `max`, as `(index, min, max)`.
+async fn choose_heater_port(world: &RpWorld) -> (usize, f64, f64) {
+    let count = simulator_switch_number(world, "maxswitch", None).await;
+    for index in 0..count as usize {
+        if simulator_switch(world, "canwrite", Some(index))
+            .await
+            .as_bool()
+            != Some(true)
+        {
+            continue;
+        }
+        let min = simulator_switch_number(world, "minswitchvalue", Some(index)).await;
+        let max = simulator_switch_number(world, "maxswitchvalue", Some(index)).await;
+        let step = simulator_switch_number(world, "switchstep", Some(index)).await;
+        let steps = (max - min) / step;
+        if step > 0.0 && steps >= 2.0 && (steps - steps.round()).abs() < 1e-9 {
+            return (index, min, max);
+        }
+    }
+    panic!("the simulator switch has no writable analog port with a whole-step range");
+}
+
+async fn simulator_switch_number(world: &RpWorld, property: &str, index: Option<usize>) -> f64 {
+    let value = simulator_switch(world, property, index).await;
+    value
+        .as_f64()
+        .unwrap_or_else(|| panic!("switch {property} is not a number: {value}"))
+}
+
+/// Read a property straight from the simulator switch — connecting
+/// first, as `OmniSim` rejects reads on a disconnected device for a
+/// client other than rp.
+async fn simulator_switch(world: &RpWorld, property: &str, index: Option<usize>) -> Value {
+    let base = world.omnisim_url();
+    let client = reqwest::Client::new();
+    client
+        .put(format!("{base}/api/v1/switch/0/connected"))
+        .form(&[("Connected", "true"), ("ClientID", "9090")])
+        .send()
+        .await
+        .expect("failed to connect to the simulator switch");
+    let id = index.map(|i| format!("&Id={i}")).unwrap_or_default();
+    let url = format!("{base}/api/v1/switch/0/{property}?ClientID=9090{id}");
+    let body: Value = client
+        .get(&url)
+        .send()
+        .await
+        .unwrap_or_else(|e| panic!("GET {url} failed: {e}"))
+        .json()
+        .await
+        .unwrap_or_else(|e| panic!("GET {url} returned no JSON: {e}"));
+    assert_eq!(
+        body.get("ErrorNumber").and_then(Value::as_i64),
+        Some(0),
+        "switch {property} read failed: {body}"
+    );
+    body["Value"].clone()
+}
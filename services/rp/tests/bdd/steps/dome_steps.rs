@@ -0,0 +1,236 @@
+//! BDD step definitions for Dome MCP tools
+
+use std::time::Duration;
+
+use cucumber::{given, then, when};
+use serde_json::Value;
+
+use bdd_infra::rp_harness::{DomeConfig, MountConfig, OmniSimHandle};
+
+use crate::steps::tool_steps::{ensure_mcp_client, start_rp};
+use crate::world::RpWorld;
+
+/// How long a dome motion may take on the simulator before a
+/// following assertion gives up — well under rp's own five-minute
+/// motion ceiling.
+const DOME_FOLLOW_TIMEOUT: Duration = Duration::from_mins(2);
+
+// --- Given steps ---
+
+#[given("rp is running with a dome on the simulator")]
+async fn rp_running_with_dome(world: &mut RpWorld) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+    if world.domes.is_empty() {
+        let url = world.omnisim_url();
+        world.domes.push(DomeConfig {
+            id: "main-dome".to_string(),
+            alpaca_url: url,
+            device_number: 0,
+            slaving: None,
+        });
+    }
+    start_rp(world).await;
+}
+
+/// Mount, site, and a dome with a slaving block, all on the simulator.
+/// The site is read back from the simulated mount so rp's mount-site
+/// validation passes whatever profile `OmniSim` carries; zero offsets
+/// put the slit azimuth on the mount's own azimuth.
+#[given("rp is running with a mount and a slaving-capable dome on the simulator")]
+async fn rp_running_with_mount_and_slaving_dome(world: &mut RpWorld) {
+    if world.omnisim.is_none() {
+        world.omnisim = Some(OmniSimHandle::start().await);
+    }
+    let url = world.omnisim_url();
+    world.site = Some(
+        OmniSimHandle::get_telescope_site()
+            .await
+            .expect("failed to read the simulated mount's site"),
+    );
+    world.mount = Some(MountConfig {
+        alpaca_url: url.clone(),
+        device_number: 0,
+        settle_after_slew: None,
+    });
+    world.domes.push(DomeConfig {
+        id: "main-dome".to_string(),
+        alpaca_url: url,
+        device_number: 0,
+        slaving: Some(serde_json::json!({
+            "dome_radius_m": 2.0,
+            "tolerance_deg": 3.0,
+            "poll_interval": "1s",
+        })),
+    });
+    start_rp(world).await;
+}
+
+// --- When steps ---
+
+#[when(expr = "the MCP client calls {string} with dome {string}")]
+async fn mcp_call_dome_tool(world: &mut RpWorld, tool_name: String, dome_id: String) {
+    ensure_mcp_client(world).await;
+    let result = world
+        .mcp()
+        .call_tool(&tool_name, serde_json::json!({"dome_id": dome_id}))
+        .await;
+    world.last_tool_result = Some(result);
+}
+
+#[when(expr = "the MCP client slews dome {string} to azimuth {float}")]
+async fn mcp_slew_dome(world: &mut RpWorld, dome_id: String, azimuth: f64) {
+    ensure_mcp_client(world).await;
+    let result = world
+        .mcp()
+        .call_tool(
+            "slew_dome_to_azimuth",
+            serde_json::json!({"dome_id": dome_id, "azimuth": azimuth}),
+        )
+        .await;
+    world.last_tool_result = Some(result);
+}
+
+#[when(expr = "the MCP client sets dome {string} slaving to {word}")]
+async fn mcp_set_dome_slaving(world: &mut RpWorld, dome_id: String, enabled: String) {
+    let enabled: bool = enabled
+        .parse()
+        .unwrap_or_else(|_| panic!("expected true|false for slaving, got {enabled}"));
+    ensure_mcp_client(world).await;
+    world
+        .mcp()
+        .call_tool(
+            "set_dome_slaving",
+            serde_json::json!({"dome_id": dome_id, "enabled": enabled}),
+        )
+        .await
+        .expect("set_dome_slaving should succeed");
+}
+
+/// Slew to the meridian 30° south of the zenith (hour angle 0,
+/// dec = latitude − 30°): azimuth 180° from any site north of −60°.
+#[when("the mount slews to the meridian 30 degrees south of the zenith")]
+async fn mount_slews_south_of_zenith(world: &mut RpWorld) {
+    let (latitude, _) = world.site.expect("the slaving scenario configures a site");
+    ensure_mcp_client(world).await;
+    let lst = world
+        .mcp()
+        .call_tool("get_local_sidereal_time", serde_json::json!({}))
+        .await
+        .expect("get_local_sidereal_time should succeed");
+    let ra = lst["lst_hours"]
+        .as_f64()
+        .expect("get_local_sidereal_time reports lst_hours");
+    world
+        .mcp()
+        .call_tool(
+            "slew",
+            serde_json::json!({"ra": ra, "dec": latitude - 30.0}),
+        )
+        .await
+        .expect("slew should succeed");
+}
+
+// --- Then steps ---
+
+#[then(expr = "the dome tool result {string} should be {string}")]
+fn dome_tool_result_field(world: &mut RpWorld, field: String, expected: String) {
+    let result = world
+        .last_tool_result
+        .as_ref()
+        .expect("no tool call was made")
+        .as_ref()
+        .expect("the dome tool call failed");
+    assert_eq!(
+        result[&field].as_str(),
+        Some(expected.as_str()),
+        "unexpected {field} in {result}"
+    );
+}
+
+#[then(expr = "the dome tool result azimuth should be within {float} degrees of {float}")]
+fn dome_tool_result_azimuth(world: &mut RpWorld, tolerance: f64, expected: f64) {
+    let result = world
+        .last_tool_result
+        .as_ref()
+        .expect("no tool call was made")
+        .as_ref()
+        .expect("the dome tool call failed");
+    let azimuth = result["azimuth"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no azimuth in {result}"));
+    assert!(
+        azimuth_separation(azimuth, expected) <= tolerance,
+        "dome azimuth {azimuth}° is not within {tolerance}° of {expected}°"
+    );
+}
+
+#[then(expr = "dome {string} should report shutter {string}")]
+async fn dome_reports_shutter(world: &mut RpWorld, dome_id: String, expected: String) {
+    let state = dome_state(world, &dome_id).await;
+    assert_eq!(
+        state["shutter_status"].as_str(),
+        Some(expected.as_str()),
+        "unexpected dome state {state}"
+    );
+}
+
+#[then(expr = "dome {string} should report at_park")]
+async fn dome_reports_at_park(world: &mut RpWorld, dome_id: String) {
+    let state = dome_state(world, &dome_id).await;
+    assert_eq!(state["at_park"], true, "unexpected dome state {state}");
+}
+
+#[then(expr = "dome {string} should report azimuth within {float} degrees of {float}")]
+async fn dome_reports_azimuth(world: &mut RpWorld, dome_id: String, tolerance: f64, expected: f64) {
+    let state = dome_state(world, &dome_id).await;
+    let azimuth = state["azimuth"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no azimuth in dome state {state}"));
+    assert!(
+        azimuth_separation(azimuth, expected) <= tolerance,
+        "dome azimuth {azimuth}° is not within {tolerance}° of {expected}°"
+    );
+}
+
+/// The follower runs in the background, so poll the dome until it has
+/// come round to `expected` (and stopped) rather than reading it once.
+#[then(expr = "dome {string} should follow the mount to within {float} degrees of azimuth {float}")]
+async fn dome_follows_mount(world: &mut RpWorld, dome_id: String, tolerance: f64, expected: f64) {
+    let deadline = tokio::time::Instant::now() + DOME_FOLLOW_TIMEOUT;
+    loop {
+        let state = dome_state(world, &dome_id).await;
+        let settled = state["slaved"] == true
+            && state["slewing"] == false
+            && state["azimuth"]
+                .as_f64()
+                .is_some_and(|az| azimuth_separation(az, expected) <= tolerance);
+        if settled {
+            return;
+        }
+        assert!(
+            tokio::time::Instant::now() < deadline,
+            "dome did not follow the mount to {expected}° within {DOME_FOLLOW_TIMEOUT:?}; \
+             last state {state}"
+        );
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+// --- Helpers ---
+
+async fn dome_state(world: &mut RpWorld, dome_id: &str) -> Value {
+    ensure_mcp_client(world).await;
+    world
+        .mcp()
+        .call_tool("get_dome_state", serde_json::json!({"dome_id": dome_id}))
+        .await
+        .expect("get_dome_state should succeed")
+}
+
+/// Smallest angle between two azimuths, degrees.
+fn azimuth_separation(a: f64, b: f64) -> f64 {
+    let d = (a - b).rem_euclid(360.0);
+    d.min(360.0 - d)
+}
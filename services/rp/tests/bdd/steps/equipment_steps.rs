@@ -1,6 +1,17 @@
 //! BDD step definitions for equipment connectivity feature
+//!
+//! The runtime-change scenarios drive `connect_device`,
+//! `disconnect_device` and `reload_equipment` over MCP against the
+//! config file `rp starts` wrote, and watch `device_connected` /
+//! `device_disconnected` through the test webhook receiver
+//! (`event_steps.rs`). `the tool call should succeed` lives in
+//! `cover_calibrator_steps.rs`; the `get_filter` call and the error
+//! assertions in `tool_steps.rs`.
+
+use std::time::Duration;
 
 use cucumber::{given, then, when};
+use serde_json::Value;
 
 use bdd_infra::rp_harness::{
     CameraConfig, DomeConfig, FilterWheelConfig, ObservingConditionsConfig, OmniSimHandle,
@@ -8,6 +19,7 @@ use bdd_infra::rp_harness::{
 };
 use bdd_infra::ServiceHandle;
 
+use crate::steps::tool_steps::ensure_mcp_client;
 use crate::world::RpWorld;
 
 // --- Given steps ---
@@ -162,6 +174,7 @@ fn configured_with_dome(world: &mut RpWorld) {
         id: "main-dome".to_string(),
         alpaca_url: url,
         device_number: 0,
+        slaving: None,
     });
 }
 
@@ -171,6 +184,7 @@ fn configured_with_dome_at(world: &mut RpWorld, url: String, device_number: i32)
         id: "main-dome".to_string(),
         alpaca_url: url,
         device_number: device_number.cast_unsigned(),
+        slaving: None,
     });
 }
 
@@ -194,6 +208,7 @@ async fn rp_starts(world: &mut RpWorld) {
         .expect("failed to write config");
 
     world.rp = Some(ServiceHandle::start(env!("CARGO_PKG_NAME"), &config_path).await);
+    world.rp_config_path = Some(config_path);
 
     assert!(
         world.wait_for_rp_healthy().await,
@@ -201,6 +216,61 @@ async fn rp_starts(world: &mut RpWorld) {
     );
 }
 
+#[when(expr = "the MCP client disconnects {word} device {string}")]
+async fn mcp_disconnect_device(world: &mut RpWorld, kind: String, id: String) {
+    call_equipment_tool(
+        world,
+        "disconnect_device",
+        serde_json::json!({ "kind": kind, "id": id }),
+    )
+    .await;
+}
+
+#[when(expr = "the MCP client connects {word} device {string}")]
+async fn mcp_connect_device(world: &mut RpWorld, kind: String, id: String) {
+    call_equipment_tool(
+        world,
+        "connect_device",
+        serde_json::json!({ "kind": kind, "id": id }),
+    )
+    .await;
+}
+
+#[when("the MCP client reloads the equipment")]
+async fn mcp_reload_equipment(world: &mut RpWorld) {
+    call_equipment_tool(world, "reload_equipment", serde_json::json!({})).await;
+}
+
+/// Edit the running rp's config file the way an operator (or the
+/// config page) would: append a filter wheel on the simulator that the
+/// registry has never seen.
+#[when("a filter wheel on the simulator is added to rp's config file")]
+async fn add_filter_wheel_to_config_file(world: &mut RpWorld) {
+    let path = world
+        .rp_config_path
+        .clone()
+        .expect("rp must be started with 'When rp starts' before its config is edited");
+    let mut config: Value = serde_json::from_str(
+        &tokio::fs::read_to_string(&path)
+            .await
+            .expect("failed to read rp's config file"),
+    )
+    .expect("rp's config file is JSON");
+    let filter_wheel = serde_json::json!({
+        "id": "main-fw",
+        "alpaca_url": world.omnisim_url(),
+        "device_number": 0,
+        "filters": ["Luminance", "Red", "Green", "Blue"],
+    });
+    match config["equipment"]["filter_wheels"].as_array_mut() {
+        Some(filter_wheels) => filter_wheels.push(filter_wheel),
+        None => config["equipment"]["filter_wheels"] = serde_json::json!([filter_wheel]),
+    }
+    tokio::fs::write(&path, serde_json::to_string_pretty(&config).unwrap())
+        .await
+        .expect("failed to write rp's config file");
+}
+
 // --- Then steps ---
 
 #[then("the equipment status should show the camera as connected")]
@@ -420,3 +490,75 @@ async fn dome_should_be_connected(world: &mut RpWorld) {
 async fn dome_should_be_disconnected(world: &mut RpWorld) {
     assert_device_connected(world, "domes", "main-dome", false).await;
 }
+
+#[then(expr = "the reload result should list {word} device {string} as connected")]
+fn reload_lists_connected(world: &mut RpWorld, kind: String, id: String) {
+    let result = world
+        .last_tool_result
+        .as_ref()
+        .expect("no tool result")
+        .as_ref()
+        .expect("reload_equipment failed");
+    let changes = result["changes"]
+        .as_array()
+        .unwrap_or_else(|| panic!("no changes array in {result}"));
+    let change = changes
+        .iter()
+        .find(|c| c["kind"] == kind.as_str() && c["id"] == id.as_str())
+        .unwrap_or_else(|| panic!("{kind} '{id}' not among the reload changes: {result}"));
+    assert_eq!(change["action"], "connect", "{change}");
+    assert_eq!(change["was_connected"], false, "{change}");
+    assert_eq!(change["connected"], true, "{change}");
+    assert!(change.get("error").is_none(), "{change}");
+}
+
+#[then(
+    expr = "the test webhook receiver should receive a {string} event for {word} device {string}"
+)]
+async fn received_device_event(world: &mut RpWorld, event_type: String, kind: String, id: String) {
+    let payload = wait_for_device_event(world, &event_type, &kind, &id).await;
+    assert!(
+        payload.get("reason").is_none(),
+        "'{event_type}' carries no reason: {payload}"
+    );
+}
+
+#[then(
+    expr = "the test webhook receiver should receive a {string} event for {word} device {string} with reason {string}"
+)]
+async fn received_device_event_with_reason(
+    world: &mut RpWorld,
+    event_type: String,
+    kind: String,
+    id: String,
+    reason: String,
+) {
+    let payload = wait_for_device_event(world, &event_type, &kind, &id).await;
+    assert_eq!(
+        payload["reason"].as_str(),
+        Some(reason.as_str()),
+        "unexpected '{event_type}' payload {payload}"
+    );
+}
+
+// --- Helpers ---
+
+async fn call_equipment_tool(world: &mut RpWorld, tool: &str, args: Value) {
+    ensure_mcp_client(world).await;
+    let result = world.mcp().call_tool(tool, args).await;
+    world.last_tool_result = Some(result);
+}
+
+/// The payload of the first `event_type` event naming `kind`/`id`,
+/// waiting up to 10 s for the asynchronous webhook delivery.
+async fn wait_for_device_event(world: &RpWorld, event_type: &str, kind: &str, id: &str) -> Value {
+    for _ in 0..40 {
+        if let Some(event) = world.received_events.read().await.iter().find(|e| {
+            e.event_type == event_type && e.payload["kind"] == kind && e.payload["id"] == id
+        }) {
+            return event.payload.clone();
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    panic!("no '{event_type}' event for {kind} '{id}' within 10s");
+}
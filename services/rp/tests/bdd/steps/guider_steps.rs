@@ -228,6 +228,21 @@ async fn call_get_guiding_stats(world: &mut RpWorld) {
     call_guider_tool(world, "get_guiding_stats", Map::new()).await;
 }
 
+#[when("the MCP client calls \"get_guiding_settings\"")]
+async fn call_get_guiding_settings(world: &mut RpWorld) {
+    call_guider_tool(world, "get_guiding_settings", Map::new()).await;
+}
+
+#[when(
+    expr = "the MCP client calls \"set_guiding_settings\" with dec_guide_mode {string} and dec_min_move_px {float}"
+)]
+async fn call_set_guiding_settings(world: &mut RpWorld, mode: String, min_move_px: f64) {
+    let mut params = Map::new();
+    params.insert("dec_guide_mode".to_string(), Value::String(mode));
+    params.insert("dec_min_move_px".to_string(), Value::from(min_move_px));
+    call_guider_tool(world, "set_guiding_settings", params).await;
+}
+
 #[when(expr = "the MCP client calls the guider tool {string} with empty arguments")]
 async fn call_named_guider_tool(world: &mut RpWorld, tool: String) {
     call_guider_tool(world, &tool, Map::new()).await;
@@ -258,6 +273,15 @@ async fn guider_result_number_field(world: &mut RpWorld, field: String, expected
     );
 }
 
+#[then(expr = "the guider result should contain {string} as null")]
+async fn guider_result_null_field(world: &mut RpWorld, field: String) {
+    let result = last_guider_result(world);
+    assert!(
+        result.get(&field).is_some_and(Value::is_null),
+        "expected field '{field}' present and null in {result}"
+    );
+}
+
 // --- Then steps: stub request assertions -----------------------------
 
 #[then("the stub guider should have received a start request without a settle override")]
@@ -360,6 +384,27 @@ async fn stub_dither_request(world: &mut RpWorld, amount_px: f64, ra_only: Strin
     );
 }
 
+#[then(
+    expr = "the stub guider should have received a settings request with dec_guide_mode {string} and Dec min_move_px {float}"
+)]
+async fn stub_settings_request(world: &mut RpWorld, mode: String, min_move_px: f64) {
+    let request = last_stub_request_to(world, "/guiding/settings").await;
+    assert_eq!(
+        request.get("dec_guide_mode").and_then(Value::as_str),
+        Some(mode.as_str()),
+        "dec_guide_mode mismatch in {request}"
+    );
+    let actual = request["dec"]["min_move_px"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("expected dec.min_move_px in {request}"));
+    assert!(
+        (actual - min_move_px).abs() < 1e-9,
+        "dec.min_move_px: expected {min_move_px}, got {actual}"
+    );
+    // An axis the call did not touch is omitted, not sent empty.
+    assert!(request.get("ra").is_none(), "unexpected ra in {request}");
+}
+
 #[then("the stub guider should have received a stop request")]
 async fn stub_stop_request(world: &mut RpWorld) {
     let stops = guider_stub(world).requests_to("/guiding/stop").await;
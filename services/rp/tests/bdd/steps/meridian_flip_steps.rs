@@ -0,0 +1,234 @@
+//! BDD step definitions for the `perform_meridian_flip` compound tool
+//! — `meridian_flip.feature`.
+//!
+//! The happy path needs a target that crosses the meridian in test
+//! time, so the target is computed rather than hard-coded: one minute
+//! of hour angle east of the meridian at the simulated mount's own
+//! site, with the flip offset pinned to 30 s. The mount is slewed there
+//! while the target is still east (the west pier side), so the flip's
+//! re-slew after the short hour-angle wait lands on the other side.
+//! The plate-solver stub answers every re-centering solve with the
+//! target itself, so the re-center converges on its first iteration.
+//!
+//! Shared steps live in `tool_steps.rs` (MCP client, tool listing),
+//! `cover_calibrator_steps.rs` (`the tool call should succeed`),
+//! `mount_steps.rs` (`the mount is unparked`, tracking), and
+//! `event_steps.rs` (webhook receiver, emission order).
+
+use cucumber::{given, then, when};
+use rp_ephemeris::{Ephemeris, ErfarsEphemeris, Site};
+use serde_json::Value;
+
+use bdd_infra::rp_harness::{
+    CannedGuiding, CannedWcs, GuiderConfig, GuiderStub, GuiderStubBehavior, MountConfig,
+    PlateSolverConfig, PlateSolverStub, RotatorConfig, StubBehavior,
+};
+
+use crate::steps::rotator_steps::push_train;
+use crate::steps::tool_steps::{add_camera, ensure_mcp_client, ensure_omnisim, start_rp};
+use crate::world::RpWorld;
+
+/// How far east of the meridian the computed target starts, in hours
+/// of hour angle.
+const TARGET_HOUR_ANGLE: f64 = -1.0 / 60.0;
+
+// --- Given steps ---
+
+/// Compute the flip target and start a plate-solver stub that solves
+/// every frame to it.
+#[given("a flip target just east of the meridian at the simulated mount's site")]
+async fn flip_target_east_of_meridian(world: &mut RpWorld) {
+    ensure_omnisim(world).await;
+    let (latitude, longitude) = bdd_infra::rp_harness::OmniSimHandle::get_telescope_site()
+        .await
+        .expect("failed to read the simulated mount's site");
+    let site = Site::new(latitude, longitude).expect("the simulated mount reports a valid site");
+    let lst = ErfarsEphemeris::new()
+        .sidereal_time(&site, chrono::Utc::now())
+        .lst_hours;
+    let ra = (lst - TARGET_HOUR_ANGLE).rem_euclid(24.0);
+    // 30° south of the zenith: well clear of the pole and the horizon
+    // from any site north of −60°.
+    let dec = latitude - 30.0;
+    world.flip_target = Some((ra, dec));
+
+    let stub = PlateSolverStub::start(StubBehavior::Canned(CannedWcs {
+        ra_center: ra * 15.0,
+        dec_center: dec,
+        pixel_scale_arcsec: 1.05,
+        rotation_deg: 12.3,
+        solver: "stub-astap-1.0".to_string(),
+        wcs_matrix: None,
+    }))
+    .await;
+    world.plate_solver = Some(PlateSolverConfig {
+        url: stub.url.clone(),
+        timeout: None,
+        default_search_radius_deg: None,
+    });
+    world.plate_solver_stub = Some(stub);
+}
+
+#[given(
+    expr = "rp is running with a flip rig: camera and rotator in train {string}, a mount with a {string} flip offset, and a guiding stub guider"
+)]
+async fn rp_with_flip_rig(world: &mut RpWorld, train_id: String, offset: String) {
+    ensure_omnisim(world).await;
+    add_camera(world);
+    let url = world.omnisim_url();
+    world.rotators.push(RotatorConfig {
+        id: "main-rotator".to_string(),
+        alpaca_url: url.clone(),
+        device_number: 0,
+    });
+    push_train(
+        world,
+        &train_id,
+        vec!["main-rotator".to_string(), "main-cam".to_string()],
+    );
+    world.mount = Some(MountConfig {
+        alpaca_url: url,
+        device_number: 0,
+        settle_after_slew: None,
+    });
+    world.meridian_flip_config = Some(serde_json::json!({ "hour_angle_offset": offset }));
+    let stub = GuiderStub::start(GuiderStubBehavior::Canned(CannedGuiding::default())).await;
+    world.guider = Some(GuiderConfig::url_only(stub.url.clone()));
+    world.guider_stub = Some(stub);
+    start_rp(world).await;
+}
+
+#[given("the mount has slewed to the flip target")]
+async fn mount_slewed_to_flip_target(world: &mut RpWorld) {
+    let (ra, dec) = flip_target(world);
+    ensure_mcp_client(world).await;
+    world
+        .mcp()
+        .call_tool("slew", serde_json::json!({ "ra": ra, "dec": dec }))
+        .await
+        .expect("slew to the flip target should succeed in scenario setup");
+}
+
+// --- When steps ---
+
+/// Read the rotator angle first so the 180° turn can be checked, then
+/// flip on the computed target.
+#[when(expr = "the MCP client performs a meridian flip with train {string}")]
+async fn perform_meridian_flip(world: &mut RpWorld, train_id: String) {
+    let (ra, dec) = flip_target(world);
+    ensure_mcp_client(world).await;
+    let rotator = world
+        .mcp()
+        .call_tool(
+            "get_rotator_position",
+            serde_json::json!({ "rotator_id": "main-rotator" }),
+        )
+        .await
+        .expect("get_rotator_position should succeed before the flip");
+    world.rotator_angle_before_flip = rotator["angle"].as_f64();
+    let result = world
+        .mcp()
+        .call_tool(
+            "perform_meridian_flip",
+            serde_json::json!({
+                "train_id": train_id,
+                "ra": ra,
+                "dec": dec,
+                "duration": "100ms",
+                "tolerance_arcsec": 60.0,
+                "max_attempts": 3,
+            }),
+        )
+        .await;
+    world.last_tool_result = Some(result);
+}
+
+// --- Then steps ---
+
+#[then("the flip result should report a pier-side change")]
+fn flip_changed_pier_side(world: &mut RpWorld) {
+    let result = flip_result(world);
+    let before = result["side_of_pier_before"].as_str();
+    let after = result["side_of_pier_after"].as_str();
+    assert!(
+        matches!(before, Some("east" | "west")) && before != after,
+        "expected the pier side to change: {result}"
+    );
+}
+
+#[then(expr = "the flip result should report re-centering within {float} arcseconds")]
+fn flip_recentered(world: &mut RpWorld, tolerance: f64) {
+    let result = flip_result(world);
+    let error = result["final_error_arcsec"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no final_error_arcsec in {result}"));
+    assert!(
+        error <= tolerance,
+        "re-centering ended {error}\" off target: {result}"
+    );
+    assert!(result["centering_attempts"].as_u64() >= Some(1), "{result}");
+}
+
+#[then("the flip result should report the rotator turned 180 degrees")]
+fn flip_turned_rotator(world: &mut RpWorld) {
+    let result = flip_result(world);
+    let before = world
+        .rotator_angle_before_flip
+        .expect("the rotator angle was read before the flip");
+    assert_eq!(result["rotator_id"], "main-rotator", "{result}");
+    let after = result["rotator_angle"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no rotator_angle in {result}"));
+    let turned = (after - before).rem_euclid(360.0);
+    assert!(
+        (turned - 180.0).abs() < 1.0,
+        "expected a 180° turn from {before}°, ended at {after}°"
+    );
+}
+
+#[then("the flip result should report guiding restarted")]
+fn flip_restarted_guiding(world: &mut RpWorld) {
+    let result = flip_result(world);
+    assert_eq!(
+        result["guiding"]["state"], "guiding",
+        "expected a settled guiding restart: {result}"
+    );
+}
+
+#[then("the stub guider should have received a stop request before a start request")]
+async fn stub_guider_stop_then_start(world: &mut RpWorld) {
+    let requests = world
+        .guider_stub
+        .as_ref()
+        .expect("no stub guider started")
+        .requests()
+        .await;
+    let stop = requests
+        .iter()
+        .position(|(path, _)| path.ends_with("/guiding/stop"));
+    let start = requests
+        .iter()
+        .rposition(|(path, _)| path.ends_with("/guiding/start"));
+    assert!(
+        matches!((stop, start), (Some(stop), Some(start)) if stop < start),
+        "expected a guiding stop followed by a start; got {:?}",
+        requests.iter().map(|(path, _)| path).collect::<Vec<_>>()
+    );
+}
+
+// --- Helpers ---
+
+fn flip_target(world: &RpWorld) -> (f64, f64) {
+    world
+        .flip_target
+        .expect("compute the flip target with 'Given a flip target ...' first")
+}
+
+fn flip_result(world: &RpWorld) -> &Value {
+    world
+        .last_tool_result
+        .as_ref()
+        .expect("perform_meridian_flip was not called")
+        .as_ref()
+        .unwrap_or_else(|e| panic!("perform_meridian_flip failed: {e}"))
+}
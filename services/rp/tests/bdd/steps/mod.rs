@@ -11,8 +11,10 @@ pub mod config_rest_steps;
 pub mod cooling_steps;
 pub mod cover_calibrator_steps;
 pub mod detect_stars_steps;
+pub mod dew_controller_steps;
 pub mod doctor_steps;
 pub mod document_http_api_steps;
+pub mod dome_steps;
 pub mod ephemeris_steps;
 pub mod equipment_steps;
 pub mod estimate_background_steps;
@@ -26,8 +28,10 @@ pub mod mcp_auth_steps;
 pub mod mcp_host_allowlist_steps;
 pub mod measure_basic_steps;
 pub mod measure_stars_steps;
+pub mod meridian_flip_steps;
 pub mod motion_gate_steps;
 pub mod mount_steps;
+pub mod observing_conditions_steps;
 pub mod operation_event_steps;
 pub mod optical_trains_steps;
 pub mod plan_schema_validation_steps;
@@ -39,6 +43,7 @@ pub mod session_steps;
 pub mod sky_survey_camera_steps;
 pub mod sse_steps;
 pub mod startup_recovery_steps;
+pub mod switch_steps;
 pub mod target_naming_template_steps;
 pub mod target_position_angle_steps;
 pub mod target_store_crud_steps;
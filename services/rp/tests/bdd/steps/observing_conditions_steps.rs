@@ -0,0 +1,95 @@
+//! BDD step definitions for `get_observing_conditions` —
+//! `observing_conditions.feature`.
+//!
+//! Which sensors `OmniSim` implements is the simulator's business, so
+//! the assertions hold for every sensor reported rather than naming
+//! any. Shared steps live in `tool_steps.rs` (MCP client, error
+//! assertions) and `cover_calibrator_steps.rs` (`the tool call should
+//! succeed`).
+
+use cucumber::{given, then, when};
+use serde_json::{Map, Value};
+
+use bdd_infra::rp_harness::ObservingConditionsConfig;
+
+use crate::steps::tool_steps::{ensure_mcp_client, ensure_omnisim, start_rp};
+use crate::world::RpWorld;
+
+// --- Given steps ----------------------------------------------------
+
+#[given("rp is running with an observing conditions device on the simulator")]
+async fn rp_with_observing_conditions(world: &mut RpWorld) {
+    ensure_omnisim(world).await;
+    let url = world.omnisim_url();
+    world.observing_conditions.push(ObservingConditionsConfig {
+        id: "main-oc".to_string(),
+        alpaca_url: url,
+        device_number: 0,
+    });
+    start_rp(world).await;
+}
+
+// --- When steps -----------------------------------------------------
+
+#[when(expr = "the MCP client reads observing conditions {string}")]
+async fn mcp_get_observing_conditions(world: &mut RpWorld, id: String) {
+    ensure_mcp_client(world).await;
+    let result = world
+        .mcp()
+        .call_tool(
+            "get_observing_conditions",
+            serde_json::json!({ "observing_conditions_id": id }),
+        )
+        .await;
+    world.last_tool_result = Some(result);
+}
+
+// --- Then steps -----------------------------------------------------
+
+#[then("at least one sensor should be reported")]
+fn at_least_one_sensor(world: &mut RpWorld) {
+    let sensors = sensors(world);
+    assert!(!sensors.is_empty(), "no sensors reported");
+}
+
+#[then("every reported sensor should carry a value and its age")]
+fn every_sensor_has_age(world: &mut RpWorld) {
+    let sensors = sensors(world);
+    for (name, sensor) in sensors {
+        let value = &sensor["value"];
+        assert!(
+            value.is_f64() || value.is_i64() || (value.is_null() && sensor["error"].is_string()),
+            "{name}: a reading or an error expected, got {sensor}"
+        );
+        let age = &sensor["time_since_last_update_s"];
+        assert!(
+            age.is_null() || age.as_f64().is_some_and(|s| s >= 0.0),
+            "{name}: age must be null or non-negative seconds, got {sensor}"
+        );
+    }
+}
+
+#[then("at least one sensor should report its age in seconds")]
+fn some_sensor_has_numeric_age(world: &mut RpWorld) {
+    let sensors = sensors(world);
+    assert!(
+        sensors
+            .values()
+            .any(|s| s["time_since_last_update_s"].is_number()),
+        "no sensor reported an age: {sensors:?}"
+    );
+}
+
+// --- Helpers --------------------------------------------------------
+
+fn sensors(world: &RpWorld) -> &Map<String, Value> {
+    let result = world
+        .last_tool_result
+        .as_ref()
+        .expect("no tool call was made")
+        .as_ref()
+        .unwrap_or_else(|e| panic!("the tool call failed: {e}"));
+    result["sensors"]
+        .as_object()
+        .unwrap_or_else(|| panic!("no sensors object in {result}"))
+}
@@ -0,0 +1,256 @@
+//! BDD step definitions for the switch MCP tools (`list_switches`,
+//! `get_switch`, `set_switch`) — `switch.feature`.
+//!
+//! The scenarios do not hard-code `OmniSim`'s port layout: a Given step
+//! lists the ports and picks a writable boolean or analog one, and the
+//! later steps derive their values from its reported min/max/step.
+//! Shared steps live in `tool_steps.rs` (MCP client, error assertions),
+//! `cover_calibrator_steps.rs` (`the tool call should succeed`) and
+//! `event_steps.rs` (webhook receiver).
+
+use std::time::Duration;
+
+use cucumber::{given, then, when};
+use serde_json::Value;
+
+use bdd_infra::rp_harness::SwitchConfig;
+
+use crate::steps::tool_steps::{ensure_mcp_client, ensure_omnisim, start_rp};
+use crate::world::RpWorld;
+
+// --- Given steps ----------------------------------------------------
+
+#[given("rp is running with a switch on the simulator")]
+async fn rp_with_switch(world: &mut RpWorld) {
+    ensure_omnisim(world).await;
+    let url = world.omnisim_url();
+    world.switches.push(SwitchConfig {
+        id: "main-switch".to_string(),
+        alpaca_url: url,
+        device_number: 0,
+    });
+    start_rp(world).await;
+}
+
+/// A writable port whose only values are its min and max.
+#[given(expr = "a writable boolean port on switch {string}")]
+async fn writable_boolean_port(world: &mut RpWorld, switch_id: String) {
+    let port = choose_port(world, &switch_id, |min, max, step| {
+        step > 0.0 && (max - min - step).abs() < f64::EPSILON
+    })
+    .await;
+    world.switch_port = Some(port);
+}
+
+/// A writable port with at least one grid value strictly between its
+/// min and max.
+#[given(expr = "a writable analog port on switch {string}")]
+async fn writable_analog_port(world: &mut RpWorld, switch_id: String) {
+    let port = choose_port(world, &switch_id, |min, max, step| {
+        step > 0.0 && (max - min) / step >= 2.0
+    })
+    .await;
+    world.switch_port = Some(port);
+}
+
+// --- When steps -----------------------------------------------------
+
+#[when(expr = "the MCP client lists the ports of switch {string}")]
+async fn mcp_list_switches(world: &mut RpWorld, switch_id: String) {
+    ensure_mcp_client(world).await;
+    let result = world
+        .mcp()
+        .call_tool(
+            "list_switches",
+            serde_json::json!({ "switch_id": switch_id }),
+        )
+        .await;
+    world.last_tool_result = Some(result);
+}
+
+#[when(expr = "the MCP client sets the chosen port's state to {word}")]
+async fn mcp_set_port_state(world: &mut RpWorld, state: String) {
+    let state: bool = state
+        .parse()
+        .unwrap_or_else(|_| panic!("expected true|false for the port state, got {state}"));
+    let port = chosen_port(world).clone();
+    set_switch(world, &port, serde_json::json!({ "state": state })).await;
+}
+
+/// `offset` is in steps and relative to `anchor` — `min` or `max` — so
+/// a scenario can name an on-grid value, an off-grid one, or one past
+/// the range without knowing the port's numbers.
+#[when(expr = "the MCP client sets the chosen port's value {float} steps from its {word}")]
+async fn mcp_set_port_value(world: &mut RpWorld, offset: f64, anchor: String) {
+    let port = chosen_port(world).clone();
+    let value = port_value(&port, offset, &anchor);
+    set_switch(world, &port, serde_json::json!({ "value": value })).await;
+}
+
+// --- Then steps -----------------------------------------------------
+
+#[then("every listed port should report its state, value and limits")]
+fn every_port_has_limits(world: &mut RpWorld) {
+    let result = tool_result(world);
+    let ports = result["ports"]
+        .as_array()
+        .unwrap_or_else(|| panic!("no ports array in {result}"));
+    assert!(!ports.is_empty(), "the switch reported no ports");
+    for (i, port) in ports.iter().enumerate() {
+        assert_eq!(port["index"], i, "{port}");
+        assert!(port["name"].is_string(), "{port}");
+        assert!(port["can_write"].is_boolean(), "{port}");
+        assert!(port["state"].is_boolean(), "{port}");
+        let number = |key: &str| {
+            port[key]
+                .as_f64()
+                .unwrap_or_else(|| panic!("no numeric {key} in {port}"))
+        };
+        let (min, max, value) = (number("min"), number("max"), number("value"));
+        assert!(number("step") >= 0.0, "{port}");
+        assert!(min <= value && value <= max, "{port}");
+    }
+}
+
+#[then(expr = "the returned port should read state {word}")]
+fn returned_port_state(world: &mut RpWorld, state: String) {
+    let result = tool_result(world);
+    assert_eq!(
+        result["port"]["state"].to_string(),
+        state,
+        "unexpected port read-back {result}"
+    );
+}
+
+#[then(expr = "the returned port should read the value {float} steps from its {word}")]
+fn returned_port_value(world: &mut RpWorld, offset: f64, anchor: String) {
+    let expected = port_value(chosen_port(world), offset, &anchor);
+    let result = tool_result(world);
+    let value = result["port"]["value"]
+        .as_f64()
+        .unwrap_or_else(|| panic!("no port value in {result}"));
+    assert!(
+        (value - expected).abs() < 1e-9,
+        "expected the port to read back {expected}, got {result}"
+    );
+}
+
+#[then("the test webhook receiver should receive a \"switch_changed\" event for the chosen port")]
+async fn switch_changed_for_port(world: &mut RpWorld) {
+    let port = chosen_port(world).clone();
+    let result = tool_result(world).clone();
+    let payload = wait_for_switch_changed(world, &port).await;
+    assert_eq!(payload["switch_id"], "main-switch", "{payload}");
+    assert_eq!(payload["name"], port["name"], "{payload}");
+    assert_eq!(payload["state"], result["port"]["state"], "{payload}");
+    assert_eq!(payload["value"], result["port"]["value"], "{payload}");
+}
+
+#[then("the chosen port should be unchanged")]
+async fn chosen_port_unchanged(world: &mut RpWorld) {
+    let port = chosen_port(world).clone();
+    ensure_mcp_client(world).await;
+    let read = world
+        .mcp()
+        .call_tool(
+            "get_switch",
+            serde_json::json!({ "switch_id": "main-switch", "index": port["index"] }),
+        )
+        .await
+        .expect("get_switch should succeed");
+    assert_eq!(read["port"]["value"], port["value"], "{read}");
+}
+
+// --- Helpers --------------------------------------------------------
+
+/// The first writable port of `switch_id` whose limits satisfy `fits`.
+async fn choose_port(
+    world: &mut RpWorld,
+    switch_id: &str,
+    fits: impl Fn(f64, f64, f64) -> bool,
+) -> Value {
+    ensure_mcp_client(world).await;
+    let listed = world
+        .mcp()
+        .call_tool(
+            "list_switches",
+            serde_json::json!({ "switch_id": switch_id }),
+        )
+        .await
+        .expect("list_switches should succeed in scenario setup");
+    let ports = listed["ports"]
+        .as_array()
+        .unwrap_or_else(|| panic!("no ports array in {listed}"));
+    ports
+        .iter()
+        .find(|p| {
+            p["can_write"] == true
+                && match (p["min"].as_f64(), p["max"].as_f64(), p["step"].as_f64()) {
+                    (Some(min), Some(max), Some(step)) => fits(min, max, step),
+                    _ => false,
+                }
+        })
+        .cloned()
+        .unwrap_or_else(|| panic!("the simulator has no suitable writable port: {listed}"))
+}
+
+fn chosen_port(world: &RpWorld) -> &Value {
+    world
+        .switch_port
+        .as_ref()
+        .expect("choose a port with 'Given a writable ... port on switch ...' first")
+}
+
+/// `anchor` (`min` or `max`) plus `offset` steps.
+fn port_value(port: &Value, offset: f64, anchor: &str) -> f64 {
+    let limit = |key: &str| {
+        port[key]
+            .as_f64()
+            .unwrap_or_else(|| panic!("no numeric {key} in {port}"))
+    };
+    let base = match anchor {
+        "min" | "max" => limit(anchor),
+        other => panic!("expected min|max as the anchor, got {other}"),
+    };
+    base + offset * limit("step")
+}
+
+async fn set_switch(world: &mut RpWorld, port: &Value, write: Value) {
+    let mut args = serde_json::json!({ "switch_id": "main-switch", "index": port["index"] });
+    if let (Some(args), Some(write)) = (args.as_object_mut(), write.as_object()) {
+        args.extend(write.clone());
+    }
+    ensure_mcp_client(world).await;
+    let result = world.mcp().call_tool("set_switch", args).await;
+    world.last_tool_result = Some(result);
+}
+
+fn tool_result(world: &RpWorld) -> &Value {
+    world
+        .last_tool_result
+        .as_ref()
+        .expect("no tool call was made")
+        .as_ref()
+        .unwrap_or_else(|e| panic!("the tool call failed: {e}"))
+}
+
+/// The `switch_changed` payload for `port`, waiting up to 10 s for the
+/// asynchronous webhook delivery.
+async fn wait_for_switch_changed(world: &RpWorld, port: &Value) -> Value {
+    for _ in 0..40 {
+        if let Some(event) = world
+            .received_events
+            .read()
+            .await
+            .iter()
+            .find(|e| e.event_type == "switch_changed" && e.payload["index"] == port["index"])
+        {
+            return event.payload.clone();
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    panic!(
+        "no switch_changed event for port {} within 10s",
+        port["index"]
+    );
+}
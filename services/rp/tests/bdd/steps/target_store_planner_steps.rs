@@ -1,5 +1,6 @@
-//! BDD step definitions for planner altitude-gating parity against the
-//! target store (`target_store_planner.feature`, Decision 9, P1).
+//! BDD step definitions for planner constraint gating against the
+//! target store (`target_store_planner.feature`): altitude-gating parity
+//! (Decision 9, P1) plus the meridian-window constraint.
 //!
 //! Unlike the other `target_store`_*.feature suites these scenarios boot
 //! rp the ordinary OmniSim/mount way (`tool_steps::start_rp`), matching
@@ -22,6 +23,13 @@ fn default_min_altitude(world: &mut RpWorld, degrees: f64) {
     }));
 }
 
+#[given(expr = "rp is configured with a target-store default meridian window of {float} hours")]
+fn default_meridian_window(world: &mut RpWorld, hours: f64) {
+    world.target_store_config = Some(serde_json::json!({
+        "default_scheduling": { "meridian_window_hours": hours }
+    }));
+}
+
 // -----------------------------------------------------------------------
 // Always-/never-visible seeds for the `planner` feature. A store target
 // pinned at `scheduling.min_altitude_degrees` -90 survives altitude
@@ -134,6 +142,25 @@ async fn added_target_with_altitude_floor(
     .await;
 }
 
+// A zero-hour meridian window is met only at the exact instant of
+// transit, so with the -90 floor this target is blocked by the window
+// and nothing else at any wall-clock.
+#[given(
+    expr = "the MCP client has added the always-visible target {string} with a zero-hour meridian window"
+)]
+async fn added_always_visible_target_with_zero_window(world: &mut RpWorld, display_name: String) {
+    add_target_fixture(
+        world,
+        serde_json::json!({
+            "display_name": display_name,
+            "ra_hours": 0.0,
+            "dec_degrees": 0.0,
+            "scheduling": { "min_altitude_degrees": -90.0, "meridian_window_hours": 0.0 }
+        }),
+    )
+    .await;
+}
+
 // "the MCP client has added a target named {string} at ra_hours {float}
 // dec_degrees {float}" (no altitude override) is reused from
 // target_store_crud_steps.rs; "the MCP client calls \"get_next_target\""
@@ -133,7 +133,7 @@ async fn set_target_grading(world: &mut RpWorld, max_hfr_pixels: f64) {
 /// (joined per-component so the test works on Windows). `sidecar` is
 /// one of:
 ///
-/// - `absent` — write the `.fits` file only, no sidecar at all;
+/// - `absent` — write the frame file only, no sidecar at all;
 /// - `no-grading` — write a sidecar whose `sections` carries no
 ///   `grading` key;
 /// - a JSON object — write it as the sidecar's `sections.grading`.
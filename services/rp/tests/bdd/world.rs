@@ -230,6 +230,28 @@ pub struct RpWorld {
     /// same way `target_store_config` is. `None` ⇒ field omitted, so rp
     /// derives the advertised URL from its listener.
     pub advertised_url: Option<String>,
+    /// `equipment.mount.meridian_flip` override (`meridian_flip.feature`
+    /// pins a short hour-angle offset so the flip runs in test time),
+    /// merged over [`RpConfigBuilder::build`]'s output the same way
+    /// `target_store_config` is. Requires a mount. `None` ⇒ rp's
+    /// defaults.
+    pub meridian_flip_config: Option<Value>,
+    /// The top-level `dew` block (`dew_controller.feature`), merged over
+    /// [`RpConfigBuilder::build`]'s output the same way
+    /// `target_store_config` is — the builder has no typed dew block.
+    /// `None` ⇒ no dew controller.
+    pub dew_config: Option<Value>,
+    /// `(index, min, max)` of the simulator switch port the dew
+    /// scenarios configure as the heater, picked from `OmniSim`'s own
+    /// port list before rp starts.
+    pub dew_heater_port: Option<(usize, f64, f64)>,
+    /// `(ra_hours, dec_degrees)` of the target the meridian-flip
+    /// scenario computes from the simulated mount's site and the
+    /// clock, just east of the meridian.
+    pub flip_target: Option<(f64, f64)>,
+    /// Rotator sky angle read just before `perform_meridian_flip`, so
+    /// the 180° turn can be asserted against it.
+    pub rotator_angle_before_flip: Option<f64>,
 
     // --- REST API state ---
     /// Last REST API response status code
@@ -305,6 +327,18 @@ pub struct RpWorld {
     /// Parsed JSON body of the last config-endpoint response, when JSON.
     pub last_config_response_json: Option<Value>,
 
+    // --- Runtime equipment changes (equipment_connectivity.feature) ---
+    /// Path of the config file `rp starts` wrote. `connect_device` and
+    /// `reload_equipment` rebuild entries from it, so the hot-add
+    /// scenario edits it in place.
+    pub rp_config_path: Option<String>,
+
+    // --- Switch test state (switch.feature) ---
+    /// The port a switch scenario chose from `list_switches` — a
+    /// writable boolean or analog port, whichever the scenario needs,
+    /// since `OmniSim`'s port layout is the simulator's to decide.
+    pub switch_port: Option<Value>,
+
     // --- Phase 4 closed-loop centering: sky-survey-camera follow mode ---
     /// Running `sky-survey-camera` process when the centering scenario
     /// uses it as `main-cam`. Held on the world so its child stays
@@ -492,6 +526,12 @@ impl RpWorld {
         if let Some(url) = &self.advertised_url {
             config["server"]["advertised_url"] = Value::String(url.clone());
         }
+        if let Some(flip) = &self.meridian_flip_config {
+            config["equipment"]["mount"]["meridian_flip"] = flip.clone();
+        }
+        if let Some(dew) = &self.dew_config {
+            config["dew"] = dew.clone();
+        }
         config
     }
 
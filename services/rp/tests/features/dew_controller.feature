@@ -0,0 +1,54 @@
+@serial
+Feature: Dew-heater controller
+  With a dew block configured, rp steps every heater each poll_interval
+  while a session is live: it reads ambient Temperature and DewPoint
+  from the observing-conditions device, turns them into a duty cycle
+  clamped to the heater's [min_duty_pct, max_duty_pct], and writes it
+  to the heater's analog switch port on the port's range and step grid.
+  A write that changes the port's value emits switch_changed with the
+  heater_id. Every exposure document records each heater's duty under
+  dew_heater_duty_pct. Session stop switches every driven heater off. A
+  heater with a temperature sensor that is pinned at its ceiling and
+  still short of dew point + margin raises dew_risk, once per episode.
+
+  The heater port is picked from the simulator's own switch: a writable
+  analog port whose range is a whole number of steps, so 0 % and 100 %
+  duty are exactly its min and max.
+
+  Background:
+    Given a running Alpaca simulator
+    And a test orchestrator that waits for a stop signal
+
+  Scenario: The controller writes the heater's duty once the session starts
+    Given a test webhook receiver subscribed to "switch_changed"
+    And rp is running with a dew heater held at 100 % duty on the simulator and the test orchestrator
+    When a session is started via the REST API
+    Then the dew heater port should read 100 % duty on the simulator
+    And the test webhook receiver should receive a "switch_changed" event for the dew heater at 100 % duty
+
+  Scenario: Exposure documents record the heater's duty
+    Given rp is running with a dew heater held at 100 % duty on the simulator and the test orchestrator
+    When a session is started via the REST API
+    Then the dew heater port should read 100 % duty on the simulator
+    When an MCP client connected to rp
+    And the MCP client calls "capture" with camera "main-cam" for 100 ms
+    And I fetch the document for the captured document_id
+    Then the document should record the dew heater at 100 % duty
+
+  Scenario: Session stop switches the heater off
+    Given a test webhook receiver subscribed to "switch_changed"
+    And rp is running with a dew heater held at 100 % duty on the simulator and the test orchestrator
+    When a session is started via the REST API
+    Then the dew heater port should read 100 % duty on the simulator
+    When the session is stopped via the REST API
+    Then the dew heater port should read 0 % duty on the simulator
+    And the test webhook receiver should receive a "switch_changed" event for the dew heater at 0 % duty
+
+  Scenario: A sensed heater pinned at full duty short of its target raises dew_risk
+    Given a test webhook receiver subscribed to "dew_risk"
+    And rp is running with a sensed dew heater that cannot reach its target on the simulator and the test orchestrator
+    When a session is started via the REST API
+    Then the test webhook receiver should receive a "dew_risk" event
+    And the "dew_risk" event payload field "heater_id" should be "main"
+    And the "dew_risk" event payload field "duty_pct" should be the number 100
+    And the dew heater port should read 100 % duty on the simulator
@@ -0,0 +1,71 @@
+@serial
+Feature: Dome tools
+  rp exposes Dome device operations as MCP tools. open_shutter and
+  close_shutter drive the shutter (or a roll-off roof) and block until
+  it reports the requested state; park_dome and slew_dome_to_azimuth
+  rotate the dome. set_dome_slaving makes rp keep the slit in front of
+  the mount, holding imaging-train exposures while it catches up.
+
+  Scenario: Tool catalog includes the dome tools
+    Given a running Alpaca simulator
+    And rp is running with a dome on the simulator
+    And an MCP client connected to rp
+    When the MCP client lists available tools
+    Then the tool list should include "open_shutter"
+    And the tool list should include "close_shutter"
+    And the tool list should include "park_dome"
+    And the tool list should include "slew_dome_to_azimuth"
+    And the tool list should include "get_dome_state"
+    And the tool list should include "set_dome_slaving"
+
+  Scenario: open_shutter blocks until the shutter reports Open
+    Given a running Alpaca simulator
+    And rp is running with a dome on the simulator
+    And an MCP client connected to rp
+    When the MCP client calls "open_shutter" with dome "main-dome"
+    Then the tool call should succeed
+    And the dome tool result "status" should be "open"
+    And dome "main-dome" should report shutter "Open"
+
+  Scenario: close_shutter blocks until the shutter reports Closed
+    Given a running Alpaca simulator
+    And rp is running with a dome on the simulator
+    And an MCP client connected to rp
+    When the MCP client calls "open_shutter" with dome "main-dome"
+    And the MCP client calls "close_shutter" with dome "main-dome"
+    Then the tool call should succeed
+    And the dome tool result "status" should be "closed"
+    And dome "main-dome" should report shutter "Closed"
+
+  Scenario: park_dome blocks until the dome reports at_park
+    Given a running Alpaca simulator
+    And rp is running with a dome on the simulator
+    And an MCP client connected to rp
+    When the MCP client slews dome "main-dome" to azimuth 90.0
+    And the MCP client calls "park_dome" with dome "main-dome"
+    Then the tool call should succeed
+    And the dome tool result "status" should be "parked"
+    And dome "main-dome" should report at_park
+
+  Scenario: slew_dome_to_azimuth returns the read-back azimuth
+    Given a running Alpaca simulator
+    And rp is running with a dome on the simulator
+    And an MCP client connected to rp
+    When the MCP client slews dome "main-dome" to azimuth 135.0
+    Then the tool call should succeed
+    And the dome tool result azimuth should be within 1.0 degrees of 135.0
+    And dome "main-dome" should report azimuth within 1.0 degrees of 135.0
+
+  Scenario: A slaved dome follows the mount through a slew
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to the events "dome_slit_catching_up, dome_slit_aligned"
+    And rp is running with a mount and a slaving-capable dome on the simulator
+    And an MCP client connected to rp
+    And the mount is unparked
+    And the mount tracking is set to true
+    When the MCP client slews dome "main-dome" to azimuth 0.0
+    And the MCP client sets dome "main-dome" slaving to true
+    And the mount slews to the meridian 30 degrees south of the zenith
+    Then dome "main-dome" should follow the mount to within 5.0 degrees of azimuth 180.0
+    And the test webhook receiver should receive a "dome_slit_aligned" event
+    And the "dome_slit_catching_up" event should have been emitted before the "dome_slit_aligned" event
@@ -101,3 +101,50 @@ Feature: Equipment connectivity
     Given rp is configured with a dome at "http://localhost:1" device 0
     When rp starts
     Then the equipment status should show the dome as disconnected
+
+  # Runtime changes: `disconnect_device`, `connect_device` and
+  # `reload_equipment` rebuild registry entries from the config file
+  # rp was started from, without a restart (rp.md § Runtime Equipment
+  # Changes).
+
+  Scenario: A disconnected device is reported not connected by its tools
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to the events "device_connected, device_disconnected"
+    And rp is configured with a filter wheel on the simulator
+    When rp starts
+    And the MCP client disconnects filter_wheels device "main-fw"
+    Then the tool call should succeed
+    And the equipment status should show the filter wheel as disconnected
+    And the test webhook receiver should receive a "device_disconnected" event for filter_wheels device "main-fw" with reason "requested"
+    When the MCP client calls "get_filter" with filter wheel "main-fw"
+    Then the tool call should return an error
+    And the error message should contain "filter wheel not connected: main-fw"
+
+  Scenario: A disconnected device is brought back with connect_device
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to the events "device_connected, device_disconnected"
+    And rp is configured with a filter wheel on the simulator
+    When rp starts
+    And the MCP client disconnects filter_wheels device "main-fw"
+    And the MCP client connects filter_wheels device "main-fw"
+    Then the tool call should succeed
+    And the equipment status should show the filter wheel as connected
+    And the test webhook receiver should receive a "device_connected" event for filter_wheels device "main-fw"
+    When the MCP client calls "get_filter" with filter wheel "main-fw"
+    Then the tool call should succeed
+
+  Scenario: A device added to the config file is hot-added by reload_equipment
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to the events "device_connected, device_disconnected"
+    And rp is configured with a camera on the simulator
+    When rp starts
+    And a filter wheel on the simulator is added to rp's config file
+    And the MCP client reloads the equipment
+    Then the tool call should succeed
+    And the reload result should list filter_wheels device "main-fw" as connected
+    And the equipment status should show the filter wheel as connected
+    And the equipment status should show the camera as connected
+    And the test webhook receiver should receive a "device_connected" event for filter_wheels device "main-fw"
+    And the test webhook receiver should not have received a "device_disconnected" event
+    When the MCP client calls "get_filter" with filter wheel "main-fw"
+    Then the tool call should succeed
@@ -1,7 +1,8 @@
 @serial
 Feature: Guider MCP tools
   The guiding tools (start_guiding, stop_guiding, dither, pause_guiding,
-  resume_guiding, get_guiding_stats) proxy to the guider rp-managed
+  resume_guiding, get_guiding_stats, get_guiding_settings,
+  set_guiding_settings) proxy to the guider rp-managed
   service over HTTP. All quantities are guide-camera pixels. The
   guider is configured at equipment.mount.guiding — guiding is
   mount-scoped, so the block cannot exist without a mount. Settle
@@ -38,6 +39,8 @@ Feature: Guider MCP tools
     And the tool list should include "pause_guiding"
     And the tool list should include "resume_guiding"
     And the tool list should include "get_guiding_stats"
+    And the tool list should include "get_guiding_settings"
+    And the tool list should include "set_guiding_settings"
 
   Scenario: start_guiding returns the settled RMS snapshot
     Given a running Alpaca simulator
@@ -234,6 +237,25 @@ Feature: Guider MCP tools
     And the guider result should contain "star_mass" with number 5432.0
     And the guider result should contain "sample_count" with number 12
 
+  Scenario: get_guiding_settings returns the guide algorithm settings
+    Given a running Alpaca simulator
+    And a stub guider returning canned guiding stats
+    And rp is running with a camera on the simulator
+    And an MCP client connected to rp
+    When the MCP client calls "get_guiding_settings"
+    Then the guider result should contain "dec_guide_mode" with value "Auto"
+    And the guider result should contain "multi_star" as null
+    And the guider result should contain "backlash_compensation" as null
+
+  Scenario: set_guiding_settings forwards a Dec profile switch per axis
+    Given a running Alpaca simulator
+    And a stub guider returning canned guiding stats
+    And rp is running with a camera on the simulator
+    And an MCP client connected to rp
+    When the MCP client calls "set_guiding_settings" with dec_guide_mode "North" and dec_min_move_px 0.3
+    Then the guider result should contain "dec_guide_mode" with value "North"
+    And the stub guider should have received a settings request with dec_guide_mode "North" and Dec min_move_px 0.3
+
   Scenario Outline: Guider tools without a configured guider return an error
     Given a running Alpaca simulator
     And rp is running with a camera on the simulator
@@ -243,12 +265,14 @@ Feature: Guider MCP tools
     And the error message should contain "guider not configured"
 
     Examples:
-      | tool              |
-      | start_guiding     |
-      | stop_guiding      |
-      | pause_guiding     |
-      | resume_guiding    |
-      | get_guiding_stats |
+      | tool                 |
+      | start_guiding        |
+      | stop_guiding         |
+      | pause_guiding        |
+      | resume_guiding       |
+      | get_guiding_stats    |
+      | get_guiding_settings |
+      | set_guiding_settings |
 
   Scenario: dither without a configured guider returns an error
     Given a running Alpaca simulator
@@ -0,0 +1,47 @@
+@serial
+Feature: Perform meridian flip compound tool
+  The perform_meridian_flip MCP tool stops guiding, waits until the
+  mount is mount.meridian_flip.hour_angle_offset past the meridian,
+  re-slews to (ra, dec) to force the pier-side change (setting
+  SideOfPier when the re-slew alone did not), re-centers with the
+  center_on_target loop, rotates the train's rotator 180° when it has
+  one, and restarts and settles guiding if it was running. It holds
+  the mount motion gate exclusively from the guider stop through the
+  pier-side check and emits meridian_flip_started /
+  meridian_flip_complete / meridian_flip_failed with an advisory
+  deadline. Inputs mirror center_on_target (camera_id or train_id, ra,
+  dec, duration, tolerance_arcsec, max_attempts) plus an optional
+  recalibrate.
+
+  Scenario: Tool catalog includes perform_meridian_flip
+    Given a running Alpaca simulator
+    And a stub plate solver returning a canned WCS
+    And rp is running with a camera and a mount on the simulator
+    And the mount tracking is set to true
+    And an MCP client connected to rp
+    When the MCP client lists available tools
+    Then the tool list should include "perform_meridian_flip"
+
+  # The target is computed one minute of hour angle east of the
+  # meridian at the simulated mount's site and the flip offset is
+  # pinned to 30 s, so the wait is about a minute and a half at most.
+  # The mount is slewed there before the target transits, so the
+  # flip's re-slew is what carries it to the other pier side.
+  Scenario: Happy path flips the pier side, re-centers, turns the rotator and restarts guiding
+    Given a running Alpaca simulator
+    And a flip target just east of the meridian at the simulated mount's site
+    And a test webhook receiver subscribed to the events "meridian_flip_started, meridian_flip_complete"
+    And rp is running with a flip rig: camera and rotator in train "main", a mount with a "30s" flip offset, and a guiding stub guider
+    And an MCP client connected to rp
+    And the mount is unparked
+    And the mount tracking is set to true
+    And the mount has slewed to the flip target
+    When the MCP client performs a meridian flip with train "main"
+    Then the tool call should succeed
+    And the flip result should report a pier-side change
+    And the flip result should report re-centering within 60.0 arcseconds
+    And the flip result should report the rotator turned 180 degrees
+    And the flip result should report guiding restarted
+    And the stub guider should have received a stop request before a start request
+    And the test webhook receiver should receive a "meridian_flip_complete" event
+    And the "meridian_flip_started" event should have been emitted before the "meridian_flip_complete" event
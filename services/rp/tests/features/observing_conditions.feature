@@ -0,0 +1,23 @@
+@serial
+Feature: Observing conditions MCP tool
+  get_observing_conditions reads every sensor the device implements,
+  each as a value plus time_since_last_update_s — the age of the
+  reading in seconds, or null when the device does not track it. A
+  sensor the device implements but cannot read yet reports a null
+  value and the error; sensors it does not implement are omitted.
+
+  Background:
+    Given a running Alpaca simulator
+    And rp is running with an observing conditions device on the simulator
+
+  Scenario: Every implemented sensor is reported with its age
+    When the MCP client reads observing conditions "main-oc"
+    Then the tool call should succeed
+    And at least one sensor should be reported
+    And every reported sensor should carry a value and its age
+    And at least one sensor should report its age in seconds
+
+  Scenario: An unknown device id is refused
+    When the MCP client reads observing conditions "no-such-oc"
+    Then the tool call should return an error
+    And the error message should contain "observing conditions not found"
@@ -45,6 +45,7 @@ Feature: Planner convenience tools
     And the tool list should include "get_meridian_status"
     And the tool list should include "record_exposure"
     And the tool list should include "get_session_progress"
+    And the tool list should include "preview_night_schedule"
 
   Scenario: get_target_status accepts a catalog name
     Given a running Alpaca simulator
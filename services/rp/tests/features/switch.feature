@@ -0,0 +1,57 @@
+@serial
+Feature: Switch MCP tools
+  list_switches reports every port of an Alpaca Switch with its
+  boolean state, analog value and the min/max/step the device reports;
+  set_switch writes exactly one of a boolean state or an analog value,
+  reads the port back and emits switch_changed. An analog value outside
+  the port's range or off its step grid counted from min is refused by
+  rp before anything reaches the device. The scenarios pick their port
+  from what the simulator lists rather than hard-coding its layout.
+
+  Background:
+    Given a running Alpaca simulator
+    And a test webhook receiver subscribed to the events "switch_changed"
+    And rp is running with a switch on the simulator
+
+  Scenario: list_switches reports every port with its limits
+    When the MCP client lists the ports of switch "main-switch"
+    Then the tool call should succeed
+    And every listed port should report its state, value and limits
+
+  Scenario: A boolean port is switched on
+    Given a writable boolean port on switch "main-switch"
+    When the MCP client sets the chosen port's state to true
+    Then the tool call should succeed
+    And the returned port should read state true
+    And the returned port should read the value 0 steps from its max
+    And the test webhook receiver should receive a "switch_changed" event for the chosen port
+
+  Scenario: An analog port is set to a value on its step grid
+    Given a writable analog port on switch "main-switch"
+    When the MCP client sets the chosen port's value 1 steps from its min
+    Then the tool call should succeed
+    And the returned port should read the value 1 steps from its min
+    And the test webhook receiver should receive a "switch_changed" event for the chosen port
+
+  Scenario: An analog value above the port's max is refused
+    Given a writable analog port on switch "main-switch"
+    When the MCP client sets the chosen port's value 1 steps from its max
+    Then the tool call should return an error
+    And the error message should contain "outside the port's range"
+    And the chosen port should be unchanged
+    And the test webhook receiver should not have received a "switch_changed" event
+
+  Scenario: An analog value below the port's min is refused
+    Given a writable analog port on switch "main-switch"
+    When the MCP client sets the chosen port's value -1 steps from its min
+    Then the tool call should return an error
+    And the error message should contain "outside the port's range"
+    And the test webhook receiver should not have received a "switch_changed" event
+
+  Scenario: An analog value off the port's step grid is refused
+    Given a writable analog port on switch "main-switch"
+    When the MCP client sets the chosen port's value 0.5 steps from its min
+    Then the tool call should return an error
+    And the error message should contain "not on the port's step grid"
+    And the chosen port should be unchanged
+    And the test webhook receiver should not have received a "switch_changed" event
@@ -10,7 +10,11 @@ Feature: Planner altitude-gating parity against the target store (P1)
   `targets.default_scheduling.min_altitude_degrees` from config — the
   same two-level per-target-then-default fallback the config-array
   planner already applies today (rp.md § Target Store, § Dynamic
-  Planner).
+  Planner). The meridian-window constraint falls back the same way; a
+  dark-sky no-survivor answer names it (`outside_meridian_window`)
+  rather than the altitude floor. The Moon constraints depend on the
+  Moon's real position on the pinned date, so decision.rs unit tests
+  cover them against a mock ephemeris instead.
 
   # The evaluation time is pinned to true astronomical night at this
   # site (matching planner.feature's equinox convention) — otherwise
@@ -36,3 +40,24 @@ Feature: Planner altitude-gating parity against the target store (P1)
     When the MCP client calls "get_next_target" at time "2026-03-20T22:00:00Z"
     Then the tool call should succeed
     And the result reason should be "all_below_min_altitude"
+
+  Scenario: A store-backed target outside its meridian window is eliminated
+    Given a running Alpaca simulator
+    And rp is configured with site latitude 51.0786 longitude -0.2944
+    And rp is running with a mount on the simulator
+    And an MCP client connected to rp
+    And the MCP client has added the always-visible target "Window Bound" with a zero-hour meridian window
+    When the MCP client calls "get_next_target" at time "2026-03-20T22:00:00Z"
+    Then the tool call should succeed
+    And the result reason should be "outside_meridian_window"
+
+  Scenario: The configured default meridian window applies to a target without its own
+    Given rp is configured with a target-store default meridian window of 0 hours
+    And a running Alpaca simulator
+    And rp is configured with site latitude 51.0786 longitude -0.2944
+    And rp is running with a mount on the simulator
+    And an MCP client connected to rp
+    And the MCP client has added the always-visible target "Default Window"
+    When the MCP client calls "get_next_target" at time "2026-03-20T22:00:00Z"
+    Then the tool call should succeed
+    And the result reason should be "outside_meridian_window"
@@ -64,6 +64,24 @@ Feature: Target progress derivation (P1)
       | Luminance | 1x1     | 5m       | 2    | 2     | 40      |
       | Red       | 1x1     | 5m       | 1    | 1     | 20      |
 
+  Scenario: XISF frames count alongside FITS frames
+    Given rp is running with a target store, filter roster "Luminance", and frame naming configured
+    And an MCP client connected to rp
+    And the MCP client has added a target named "M31" at ra_hours 0.712 dec_degrees 41.269
+    And the MCP client has set its goals to:
+      | filter    | binning | exposure_duration | desired_count |
+      | Luminance | 1x1     | 5m       | 40            |
+    And the data directory contains these frames:
+      | path                                                                     | sidecar |
+      | m31/2026-07-30/Light/m31_Luminance_1x1_0001_5m_fpos_1_-10C_aaaaaaa1.fits | absent  |
+      | m31/2026-07-30/Light/m31_Luminance_1x1_0002_5m_fpos_1_-10C_aaaaaaa2.xisf | absent  |
+      | m31/2026-07-30/Light/m31_Luminance_1x1_0002_5m_fpos_1_-10C_aaaaaaa2.wcs  | absent  |
+    When the MCP client calls "get_target" for slug "m31"
+    Then the tool call should succeed
+    And the reported progress should be exactly:
+      | filter    | binning | exposure_duration | good | total | desired_count |
+      | Luminance | 1x1     | 5m       | 2    | 2     | 40      |
+
   Scenario: Frames from earlier nights accumulate into the same goal
     Given rp is running with a target store, filter roster "Luminance", and frame naming configured
     And an MCP client connected to rp
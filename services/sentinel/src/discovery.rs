@@ -54,8 +54,11 @@ pub const NON_ALPACA_SERVICES: &[&str] = &[
     "plate-solver",
     "session-runner",
     "calibrator-flats",
+    "calibrator-darks",
+    "frame-grader",
     "polar-align",
     "phd2-guider",
+    "native-guider",
     "ui-htmx",
 ];
 
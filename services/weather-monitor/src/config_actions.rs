@@ -0,0 +1,232 @@
+//! weather-monitor's [`ConfigurableDriver`] implementation — the
+//! driver-specific half of the `config.get` / `config.apply` /
+//! `config.schema` protocol.
+//!
+//! The generic machinery lives in [`rusty_photon_config::actions`]; this
+//! module supplies only what varies for weather-monitor: its `Config` type,
+//! validation, secret locations, and editability tiers. Single ASCOM device
+//! (the `SafetyMonitor`); the binary has no CLI overrides beyond `--config`,
+//! so `Overrides = ()`. See [`docs/services/weather-monitor.md`] "Config
+//! actions".
+//!
+//! [`docs/services/weather-monitor.md`]: ../../../docs/services/weather-monitor.md
+
+use std::collections::HashSet;
+
+use rusty_photon_config::actions::{ConfigurableDriver, FieldError};
+
+use crate::Config;
+
+/// Re-exported so tests can name the redaction sentinel.
+pub use rusty_photon_config::actions::REDACTED;
+
+/// Driver marker wiring weather-monitor's `Config` into the generic
+/// config-action protocol via [`rusty_photon_config::actions`].
+pub struct WeatherMonitorDriver;
+
+impl ConfigurableDriver for WeatherMonitorDriver {
+    type Config = Config;
+    /// No CLI overrides — the binary only takes `--config` / `--log-level`.
+    type Overrides = ();
+
+    fn normalize(_config: &mut Config) {}
+
+    fn validate(config: &Config) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+        if config.device.unique_id.trim().is_empty() {
+            errors.push(FieldError {
+                path: "device.unique_id".to_string(),
+                msg: "must not be empty (it is the device's stable ASCOM UniqueID)".to_string(),
+            });
+        }
+
+        if config.sources.is_empty() {
+            errors.push(FieldError {
+                path: "sources".to_string(),
+                msg: "must list at least one observing conditions source".to_string(),
+            });
+        }
+        let mut ids = HashSet::new();
+        for (i, source) in config.sources.iter().enumerate() {
+            if source.id.trim().is_empty() {
+                errors.push(FieldError {
+                    path: format!("sources.{i}.id"),
+                    msg: "must not be empty".to_string(),
+                });
+            } else if !ids.insert(source.id.as_str()) {
+                errors.push(FieldError {
+                    path: format!("sources.{i}.id"),
+                    msg: format!("duplicate source id '{}'", source.id),
+                });
+            }
+            if let Err(e) = reqwest::Url::parse(&source.alpaca_url) {
+                errors.push(FieldError {
+                    path: format!("sources.{i}.alpaca_url"),
+                    msg: format!("invalid URL: {e}"),
+                });
+            }
+        }
+
+        let timing = &config.timing;
+        if timing.polling_interval.is_zero() {
+            errors.push(FieldError {
+                path: "timing.polling_interval".to_string(),
+                msg: "must be greater than 0".to_string(),
+            });
+        }
+        // Between polls every reading ages by up to one interval; a shorter
+        // staleness bound would flag fresh data stale before the next poll.
+        if timing.stale_after < timing.polling_interval {
+            errors.push(FieldError {
+                path: "timing.stale_after".to_string(),
+                msg: format!(
+                    "must be at least polling_interval ({}s); got {}s",
+                    timing.polling_interval.as_secs(),
+                    timing.stale_after.as_secs()
+                ),
+            });
+        }
+
+        // No rules would mean permanently safe — never what an operator
+        // wants from a safety monitor.
+        if config.rules.is_empty() {
+            errors.push(FieldError {
+                path: "rules".to_string(),
+                msg: "must list at least one rule".to_string(),
+            });
+        }
+        for (i, rule) in config.rules.iter().enumerate() {
+            if !rule.threshold.is_finite() {
+                errors.push(FieldError {
+                    path: format!("rules.{i}.threshold"),
+                    msg: format!("must be a finite number; got {}", rule.threshold),
+                });
+            }
+            match &rule.source {
+                Some(id) if !config.sources.iter().any(|s| &s.id == id) => {
+                    errors.push(FieldError {
+                        path: format!("rules.{i}.source"),
+                        msg: format!("no sources entry with id '{id}'"),
+                    });
+                }
+                None if config.sources.len() > 1 => {
+                    errors.push(FieldError {
+                        path: format!("rules.{i}.source"),
+                        msg: "required when more than one source is configured".to_string(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        errors
+    }
+
+    /// The server-auth password hash, and each source's client password.
+    /// `TlsConfig` and `ca_cert` store file *paths*, not key material.
+    fn secret_pointers() -> &'static [&'static str] {
+        &["/server/auth/password_hash", "/sources/*/auth/password"]
+    }
+
+    fn override_paths(_overrides: &()) -> Vec<String> {
+        Vec::new()
+    }
+
+    fn apply_overrides(_config: &mut Config, _overrides: &()) {}
+
+    /// The device owns its ASCOM `UniqueID`; editing it is an escape hatch for
+    /// a misbehaving driver, not routine configuration.
+    fn locked_paths() -> &'static [&'static str] {
+        &["device.unique_id"]
+    }
+
+    /// `server.port` is a self-lockout field: the BFF can't follow a rebind to
+    /// a new port.
+    fn read_only_paths() -> &'static [&'static str] {
+        &["server.port"]
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::SourceConfig;
+    use std::time::Duration;
+
+    fn valid_config() -> Config {
+        Config::default()
+    }
+
+    fn paths(config: &Config) -> Vec<String> {
+        WeatherMonitorDriver::validate(config)
+            .into_iter()
+            .map(|e| e.path)
+            .collect()
+    }
+
+    #[test]
+    fn validate_accepts_default_config() {
+        assert!(WeatherMonitorDriver::validate(&valid_config()).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_empty_sources_and_rules() {
+        let mut config = valid_config();
+        config.sources.clear();
+        config.rules.clear();
+        assert_eq!(paths(&config), vec!["sources", "rules"]);
+    }
+
+    #[test]
+    fn validate_flags_bad_sources() {
+        let mut config = valid_config();
+        config.sources.push(SourceConfig {
+            id: "ppba".to_string(),
+            alpaca_url: "not a url".to_string(),
+            device_number: 0,
+            auth: None,
+        });
+        for rule in &mut config.rules {
+            rule.source = Some("ppba".to_string());
+        }
+        assert_eq!(paths(&config), vec!["sources.1.id", "sources.1.alpaca_url"]);
+    }
+
+    #[test]
+    fn validate_requires_rule_sources_with_several_sources() {
+        let mut config = valid_config();
+        config.sources.push(SourceConfig {
+            id: "cloudwatcher".to_string(),
+            alpaca_url: "http://127.0.0.1:11200".to_string(),
+            device_number: 0,
+            auth: None,
+        });
+        config.rules[1].source = Some("boltwood".to_string());
+        assert_eq!(paths(&config), vec!["rules.0.source", "rules.1.source"]);
+    }
+
+    #[test]
+    fn validate_flags_timing_and_thresholds() {
+        let mut config = valid_config();
+        config.timing.polling_interval = Duration::from_secs(60);
+        config.timing.stale_after = Duration::from_secs(30);
+        config.rules[0].threshold = f64::NAN;
+        assert_eq!(
+            paths(&config),
+            vec!["timing.stale_after", "rules.0.threshold"]
+        );
+
+        config.timing.polling_interval = Duration::ZERO;
+        assert!(paths(&config).contains(&"timing.polling_interval".to_string()));
+    }
+
+    #[test]
+    fn editability_tiers_and_secrets() {
+        assert_eq!(WeatherMonitorDriver::locked_paths(), &["device.unique_id"]);
+        assert_eq!(WeatherMonitorDriver::read_only_paths(), &["server.port"]);
+        assert_eq!(
+            WeatherMonitorDriver::secret_pointers(),
+            &["/server/auth/password_hash", "/sources/*/auth/password"]
+        );
+    }
+}
@@ -0,0 +1,748 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod config_actions;
+pub mod doctor;
+pub mod rules;
+mod source;
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use ascom_alpaca::api::{CargoServerInfo, Device, SafetyMonitor};
+use ascom_alpaca::{ASCOMError, ASCOMResult, Server};
+use rp_auth::config::ClientAuthConfig;
+pub use rusty_photon_server_config::AlpacaServerConfig;
+use rusty_photon_service_lifecycle::ReloadSignal;
+use rusty_photon_tls::config::TlsConfig;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tokio::time::{interval, Duration, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::config_actions::WeatherMonitorDriver;
+use crate::rules::Hysteresis;
+use crate::source::Source;
+use rusty_photon_config::actions::ConfigurableDriver;
+use rusty_photon_driver::ConfigActionCtx;
+
+/// `deny_unknown_fields` so typoed or removed keys fail loudly at load
+/// instead of being silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub device: DeviceConfig,
+    pub sources: Vec<SourceConfig>,
+    /// PEM CA certificate trusted for `https://` source URLs signed by the
+    /// observatory CA. `None` uses the platform trust store.
+    #[serde(default)]
+    pub ca_cert: Option<String>,
+    pub timing: TimingConfig,
+    pub rules: Vec<Rule>,
+    pub server: AlpacaServerConfig,
+}
+
+/// `deny_unknown_fields` so typoed or removed keys fail loudly at load
+/// instead of being silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceConfig {
+    pub name: String,
+    pub unique_id: String,
+    pub description: String,
+}
+
+/// One Alpaca ObservingConditions device the monitor polls.
+///
+/// `deny_unknown_fields` so typoed or removed keys fail loudly at load
+/// instead of being silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SourceConfig {
+    /// Names the source in rules and log lines.
+    pub id: String,
+    pub alpaca_url: String,
+    /// Index among the server's ObservingConditions devices. Defaults to 0.
+    #[serde(default)]
+    pub device_number: u32,
+    #[serde(default)]
+    pub auth: Option<ClientAuthConfig>,
+}
+
+/// `deny_unknown_fields` so typoed or removed keys fail loudly at load
+/// instead of being silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TimingConfig {
+    // `humantime_serde` stores the duration as a string (e.g. "30s"); tell
+    // schemars to describe it as a string so the generated schema matches the
+    // wire form rather than the `{secs, nanos}` auto-derive.
+    #[serde(with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub polling_interval: Duration,
+    /// A reading older than this — by the device's own
+    /// `TimeSinceLastUpdate` plus the time since it was polled — counts as
+    /// missing, and a rule missing its reading is unsafe.
+    #[serde(with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub stale_after: Duration,
+    /// How long conditions must stay unsafe before `IsSafe` turns false.
+    #[serde(with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub unsafe_delay: Duration,
+    /// How long conditions must stay safe before `IsSafe` turns true again.
+    #[serde(with = "humantime_serde")]
+    #[schemars(with = "String")]
+    pub safe_delay: Duration,
+}
+
+/// `deny_unknown_fields` so typoed or removed keys fail loudly at load
+/// instead of being silently ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Rule {
+    #[serde(rename = "type")]
+    pub rule_type: RuleType,
+    /// The limit, in the unit of the measured quantity; see [`RuleType`]
+    /// for which side of it is unsafe.
+    pub threshold: f64,
+    /// The `sources[].id` to read. May be omitted when exactly one source
+    /// is configured.
+    #[serde(default)]
+    pub source: Option<String>,
+}
+
+/// What a rule measures, and which side of its threshold is unsafe.
+///
+/// Unit-variant-only enum deserialized from a bare string (e.g.
+/// `"humidity"`), not a JSON object — `deny_unknown_fields` has no
+/// meaningful effect here, so it is intentionally omitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleType {
+    /// Relative humidity, %; unsafe above the threshold.
+    Humidity,
+    /// Temperature minus dew point, °C; unsafe below the threshold.
+    DewPointSpread,
+    /// Wind speed, m/s; unsafe above the threshold.
+    WindSpeed,
+    /// Wind gust, m/s; unsafe above the threshold.
+    WindGust,
+    /// Rain rate, mm/h; unsafe above the threshold (0 = any rain).
+    RainRate,
+    /// Ambient temperature minus sky temperature, °C; unsafe below the
+    /// threshold. A clear sky reads far colder than the air, cloud close
+    /// to it.
+    Clouds,
+    /// Sky quality, mag/arcsec²; unsafe below the threshold (twilight,
+    /// moonlit haze).
+    SkyQuality,
+    /// Cloud cover, %; unsafe above the threshold.
+    CloudCover,
+}
+
+impl Config {
+    /// The source a rule reads: its own `source`, or the only configured
+    /// source when it names none.
+    #[must_use]
+    pub fn rule_source<'a>(&'a self, rule: &'a Rule) -> Option<&'a str> {
+        match (&rule.source, self.sources.as_slice()) {
+            (Some(id), _) => Some(id.as_str()),
+            (None, [only]) => Some(only.id.as_str()),
+            (None, _) => None,
+        }
+    }
+
+    /// [`Config::ca_cert`] as a `Path`, for `rusty_photon_tls::client`.
+    pub fn ca_cert_path(&self) -> Option<&Path> {
+        self.ca_cert.as_deref().map(Path::new)
+    }
+}
+
+impl Default for Config {
+    /// The packaged first-start default: the PPBA's ObservingConditions on
+    /// its default port, judged on humidity and dew-point spread. Every
+    /// other rule needs a sensor the PPBA does not have.
+    fn default() -> Self {
+        Self {
+            device: DeviceConfig {
+                name: "Weather Safety Monitor".to_string(),
+                unique_id: "weather-monitor-001".to_string(),
+                description: "ASCOM Alpaca SafetyMonitor that aggregates weather sensors"
+                    .to_string(),
+            },
+            sources: vec![SourceConfig {
+                id: "ppba".to_string(),
+                alpaca_url: "http://127.0.0.1:11112".to_string(),
+                device_number: 0,
+                auth: None,
+            }],
+            ca_cert: None,
+            timing: TimingConfig {
+                polling_interval: Duration::from_secs(30),
+                stale_after: Duration::from_mins(5),
+                unsafe_delay: Duration::from_mins(2),
+                safe_delay: Duration::from_mins(20),
+            },
+            rules: vec![
+                Rule {
+                    rule_type: RuleType::Humidity,
+                    threshold: 90.0,
+                    source: None,
+                },
+                Rule {
+                    rule_type: RuleType::DewPointSpread,
+                    threshold: 2.0,
+                    source: None,
+                },
+            ],
+            server: AlpacaServerConfig::new(11127),
+        }
+    }
+}
+
+/// The evaluation state the polling task updates and `IsSafe` reads.
+#[derive(Debug)]
+struct MonitorState {
+    hysteresis: Hysteresis,
+    /// Why conditions were unsafe at the last poll; empty when they were
+    /// safe.
+    reasons: Vec<String>,
+    /// Whether a poll has run since connect.
+    polled: bool,
+}
+
+impl MonitorState {
+    fn new(timing: &TimingConfig) -> Self {
+        Self {
+            hysteresis: Hysteresis::new(timing.unsafe_delay, timing.safe_delay),
+            reasons: Vec::new(),
+            polled: false,
+        }
+    }
+
+    /// Feed one poll's reasons through the hysteresis, logging every change
+    /// of the reported verdict. The verdict starts out unsafe, so the first
+    /// poll after connect logs its reasons too.
+    fn update(&mut self, reasons: Vec<String>, now: Instant) {
+        let before = self.hysteresis.current();
+        let safe = self.hysteresis.update(reasons.is_empty(), now);
+        let first = !std::mem::replace(&mut self.polled, true);
+        if before != safe || (first && !reasons.is_empty()) {
+            if safe {
+                info!("conditions safe");
+            } else {
+                warn!("conditions unsafe: {}", reasons.join("; "));
+            }
+        } else if !reasons.is_empty() && reasons != self.reasons {
+            debug!("unsafe conditions: {}", reasons.join("; "));
+        }
+        self.reasons = reasons;
+    }
+
+    /// Polling stopped: report unsafe until a reconnect has seen a clear
+    /// sky for `safe_delay`.
+    fn disconnected(&mut self) {
+        self.hysteresis.hold_unsafe();
+        self.reasons.clear();
+        self.polled = false;
+    }
+}
+
+#[derive(derive_more::Debug)]
+pub struct WeatherMonitorDevice {
+    config: Config,
+    connected: Arc<RwLock<bool>>,
+    state: Arc<Mutex<MonitorState>>,
+    polling_handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+    /// `Some` when the driver was built with a config source (the normal path
+    /// through `ServerBuilder`); `None` for focused unit-test devices that
+    /// don't exercise config actions.
+    #[debug(skip)]
+    config_ctx: Option<ConfigActionCtx<WeatherMonitorDriver>>,
+}
+
+impl WeatherMonitorDevice {
+    #[must_use]
+    pub fn new(config: Config) -> Self {
+        let state = MonitorState::new(&config.timing);
+        Self {
+            config,
+            connected: Arc::new(RwLock::new(false)),
+            state: Arc::new(Mutex::new(state)),
+            polling_handle: Arc::new(Mutex::new(None)),
+            config_ctx: None,
+        }
+    }
+
+    /// Attach the config-action context, enabling `config.get` / `config.apply`.
+    #[must_use]
+    pub fn with_config_actions(mut self, ctx: ConfigActionCtx<WeatherMonitorDriver>) -> Self {
+        self.config_ctx = Some(ctx);
+        self
+    }
+
+    /// Poll every source once per `polling_interval` and re-evaluate. The
+    /// first tick fires immediately, so the first verdict lands as soon as
+    /// the sources answer; until then `IsSafe` reports unsafe.
+    async fn start_polling(&self) {
+        let config = self.config.clone();
+        let state = Arc::clone(&self.state);
+        let connected = Arc::clone(&self.connected);
+        let ca_cert = config.ca_cert_path().map(Path::to_path_buf);
+        let mut sources: Vec<Source> = config
+            .sources
+            .iter()
+            .map(|source| Source::new(source, &config, ca_cert.as_deref()))
+            .collect();
+
+        let handle = tokio::spawn(async move {
+            let mut interval = interval(config.timing.polling_interval);
+            // A source timing out can stretch one poll past the interval;
+            // resume the cadence from there rather than bursting to catch up.
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                interval.tick().await;
+
+                // Check if still connected
+                if !*connected.read().await {
+                    break;
+                }
+
+                for source in &mut sources {
+                    source.poll().await;
+                }
+                let readings: HashMap<&str, &rules::Readings> = sources
+                    .iter()
+                    .map(|source| (source.id(), source.readings()))
+                    .collect();
+                let now = Instant::now();
+                let reasons = rules::unsafe_reasons(&config, &readings, now);
+                state.lock().await.update(reasons, now);
+            }
+        });
+
+        let mut polling_handle = self.polling_handle.lock().await;
+        *polling_handle = Some(handle);
+    }
+
+    async fn stop_polling(&self) {
+        let mut handle = self.polling_handle.lock().await;
+        if let Some(h) = handle.take() {
+            h.abort();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Device for WeatherMonitorDevice {
+    fn static_name(&self) -> &str {
+        &self.config.device.name
+    }
+
+    fn unique_id(&self) -> &str {
+        &self.config.device.unique_id
+    }
+
+    async fn description(&self) -> ASCOMResult<String> {
+        Ok(self.config.device.description.clone())
+    }
+
+    async fn connected(&self) -> ASCOMResult<bool> {
+        Ok(*self.connected.read().await)
+    }
+
+    async fn set_connected(&self, connected: bool) -> Result<(), ASCOMError> {
+        let mut conn_state = self.connected.write().await;
+        if connected == *conn_state {
+            return Ok(());
+        }
+        *conn_state = connected;
+        drop(conn_state);
+
+        if connected {
+            // A source that is down does not fail the connect: its rules
+            // read as missing, which is unsafe, until it answers.
+            self.start_polling().await;
+        } else {
+            self.stop_polling().await;
+            self.state.lock().await.disconnected();
+        }
+
+        Ok(())
+    }
+
+    async fn driver_info(&self) -> ASCOMResult<String> {
+        Ok(self.config.device.description.clone())
+    }
+
+    async fn driver_version(&self) -> ASCOMResult<String> {
+        Ok(env!("CARGO_PKG_VERSION").to_string())
+    }
+
+    async fn supported_actions(&self) -> ASCOMResult<Vec<String>> {
+        Ok(rusty_photon_driver::supported_actions(&self.config_ctx))
+    }
+
+    async fn action(&self, action: String, parameters: String) -> ASCOMResult<String> {
+        rusty_photon_driver::dispatch::<WeatherMonitorDriver>(&self.config_ctx, action, parameters)
+            .await
+    }
+}
+
+#[async_trait::async_trait]
+impl SafetyMonitor for WeatherMonitorDevice {
+    async fn is_safe(&self) -> ASCOMResult<bool> {
+        if !*self.connected.read().await {
+            return Err(ASCOMError::NOT_CONNECTED);
+        }
+
+        Ok(self.state.lock().await.hysteresis.current())
+    }
+}
+
+/// Parse and validate the config. Unlike a device driver, a safety monitor
+/// must not start on a config it cannot honour — no rules would read as
+/// permanently safe — so the `config.apply` validation runs here too.
+pub fn load_config(path: &Path) -> Result<Config, Box<dyn std::error::Error + Send + Sync>> {
+    let content = std::fs::read_to_string(path)?;
+    let config: Config = serde_json::from_str(&content)?;
+    let errors = WeatherMonitorDriver::validate(&config);
+    if !errors.is_empty() {
+        let errors: Vec<String> = errors
+            .iter()
+            .map(|e| format!("{}: {}", e.path, e.msg))
+            .collect();
+        return Err(format!("invalid config: {}", errors.join("; ")).into());
+    }
+    Ok(config)
+}
+
+/// Builder for the ASCOM Alpaca weather-monitor server.
+///
+/// The returned [`BoundServer`] can be inspected (e.g. `listen_addr()`)
+/// before calling `start()`.
+pub struct ServerBuilder {
+    config: Config,
+    /// Where `config.apply` persists and reload re-reads. `Some` enables the
+    /// config actions (together with `reload`).
+    config_path: Option<PathBuf>,
+    /// Reload trigger handed to the device for fire-after-response reload.
+    reload: Option<ReloadSignal>,
+}
+
+impl ServerBuilder {
+    #[must_use]
+    pub const fn new(config: Config) -> Self {
+        Self {
+            config,
+            config_path: None,
+            reload: None,
+        }
+    }
+
+    /// Set the config source (persist path) for the `config.get` /
+    /// `config.apply` actions. Together with [`Self::with_reload_signal`],
+    /// this enables config editing.
+    #[must_use]
+    pub fn with_config_source(mut self, path: PathBuf) -> Self {
+        self.config_path = Some(path);
+        self
+    }
+
+    /// Provide the reload trigger `config.apply` fires after its response
+    /// flushes. Together with [`Self::with_config_source`], this enables
+    /// config editing.
+    #[must_use]
+    pub fn with_reload_signal(mut self, reload: ReloadSignal) -> Self {
+        self.reload = Some(reload);
+        self
+    }
+
+    pub async fn build(self) -> Result<BoundServer, Box<dyn std::error::Error + Send + Sync>> {
+        let mut device = WeatherMonitorDevice::new(self.config.clone());
+        let config_ctx: Option<ConfigActionCtx<WeatherMonitorDriver>> =
+            match (self.config_path.clone(), self.reload.clone()) {
+                (Some(path), Some(reload)) => Some(ConfigActionCtx {
+                    effective: self.config.clone(),
+                    path,
+                    overrides: (),
+                    reload,
+                }),
+                _ => None,
+            };
+        if let Some(ctx) = config_ctx {
+            device = device.with_config_actions(ctx);
+        }
+
+        let mut server = Server::new(CargoServerInfo!());
+        server.listen_addr = self.config.server.socket_addr();
+        server.devices.register(device);
+
+        info!(
+            "Starting ASCOM Alpaca server on port {}",
+            self.config.server.port
+        );
+        info!(
+            "Device: {} ({})",
+            self.config.device.name, self.config.device.unique_id
+        );
+        for source in &self.config.sources {
+            info!(
+                "Source {}: {} (device {})",
+                source.id, source.alpaca_url, source.device_number
+            );
+        }
+
+        let tls = self.config.server.tls.clone();
+        let router = axum::Router::new().fallback_service(server.into_service());
+
+        // Layer authentication if configured
+        let router = match &self.config.server.auth {
+            Some(auth) => {
+                if self.config.server.tls.is_none() {
+                    tracing::warn!(
+                        "Authentication is enabled but TLS is not. \
+                         Credentials will be transmitted in cleartext. \
+                         Consider enabling TLS (see `doctor --fix`)."
+                    );
+                }
+                rp_auth::layer(router, auth)
+            }
+            None => router,
+        };
+
+        let listener =
+            rusty_photon_tls::server::bind_dual_stack_tokio(self.config.server.socket_addr())
+                .await?;
+        let local_addr = listener.local_addr()?;
+
+        // Opt-in Alpaca UDP discovery responder (config `discovery_port`);
+        // bound here so a taken port fails startup, run in start().
+        let discovery =
+            rusty_photon_driver::discovery::bind(local_addr, self.config.server.discovery_port)
+                .await?;
+
+        // Console mode only: stdout is a dead handle under the Windows SCM,
+        // and the only stdout consumer (bdd-infra's port parser) never runs
+        // services with --service.
+        if !rusty_photon_service_lifecycle::is_scm_service() {
+            println!("Bound Alpaca server bound_addr={local_addr}");
+        }
+
+        Ok(BoundServer {
+            listener,
+            router,
+            local_addr,
+            tls,
+            discovery,
+        })
+    }
+}
+
+/// A fully bound weather-monitor server ready to accept connections.
+pub struct BoundServer {
+    listener: tokio::net::TcpListener,
+    router: axum::Router,
+    local_addr: SocketAddr,
+    tls: Option<TlsConfig>,
+    /// Alpaca UDP discovery responder, when the config opts in. Runs inside
+    /// `start()`'s select so its socket closes when serving ends (reload).
+    discovery: Option<ascom_alpaca::discovery::BoundDiscoveryServer>,
+}
+
+impl BoundServer {
+    pub const fn listen_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    pub async fn start(
+        self,
+        shutdown: impl Future<Output = ()> + Send + 'static,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Self {
+            listener,
+            router,
+            local_addr,
+            tls,
+            discovery,
+        } = self;
+        let serve = async {
+            if let Some(ref tls_config) = tls {
+                info!("weather-monitor started on {} (TLS)", local_addr);
+                rusty_photon_tls::server::serve_tls(listener, router, tls_config, shutdown).await
+            } else {
+                info!("weather-monitor started on {}", local_addr);
+                rusty_photon_tls::server::serve_plain(listener, router, shutdown).await
+            }
+        };
+        rusty_photon_driver::discovery::serve_with(discovery, serve).await?;
+        debug!("weather-monitor shut down");
+        Ok(())
+    }
+}
+
+/// Build a fresh `BoundServer` from a `Config` and run it until the
+/// `shutdown` future resolves.
+pub async fn start_server(
+    config: Config,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ServerBuilder::new(config)
+        .build()
+        .await?
+        .start(shutdown)
+        .await
+}
+
+/// Run the server in a config-reload loop until `shutdown` fires. Both
+/// `shutdown` and a `config.apply`-fired `reload` feed the same stop future,
+/// so either drains in-flight requests before the loop returns or rebuilds
+/// from the freshly-persisted config — the same shape as filemonitor's.
+pub async fn run_server_loop(
+    config_path: &Path,
+    shutdown: CancellationToken,
+    reload: ReloadSignal,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    loop {
+        let config = load_config(config_path)?;
+        info!(
+            "Starting weather-monitor server on port {}",
+            config.server.port
+        );
+        let bound = ServerBuilder::new(config)
+            .with_config_source(config_path.to_path_buf())
+            .with_reload_signal(reload.clone())
+            .build()
+            .await?;
+
+        let reloaded = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let stop = {
+            let reloaded = Arc::clone(&reloaded);
+            let shutdown = shutdown.clone().cancelled_owned();
+            let reload = reload.clone();
+            async move {
+                tokio::select! {
+                    () = shutdown => {}
+                    () = reload.recv() => reloaded.store(true, std::sync::atomic::Ordering::SeqCst),
+                }
+            }
+        };
+        bound.start(stop).await?;
+
+        if reloaded.load(std::sync::atomic::Ordering::SeqCst) {
+            info!("Reloading configuration");
+            continue;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config_targets_the_ppba_on_its_own_port() {
+        let config = Config::default();
+        assert_eq!(config.server.port, 11127);
+        assert_eq!(config.sources.len(), 1);
+        assert_eq!(config.sources[0].alpaca_url, "http://127.0.0.1:11112");
+        assert_eq!(config.timing.safe_delay, Duration::from_mins(20));
+        assert!(WeatherMonitorDriver::validate(&config).is_empty());
+    }
+
+    #[test]
+    fn minimal_source_gets_defaults_and_rule_source_falls_back_to_it() {
+        let config: Config = serde_json::from_value(serde_json::json!({
+            "device": {"name": "W", "unique_id": "w-1", "description": "W"},
+            "sources": [{"id": "roof", "alpaca_url": "http://127.0.0.1:11112"}],
+            "timing": {
+                "polling_interval": "30s",
+                "stale_after": "5m",
+                "unsafe_delay": "2m",
+                "safe_delay": "20m"
+            },
+            "rules": [{"type": "rain_rate", "threshold": 0}],
+            "server": {"port": 0}
+        }))
+        .unwrap();
+        assert_eq!(config.sources[0].device_number, 0);
+        assert!(config.sources[0].auth.is_none());
+        assert!(config.ca_cert.is_none());
+        assert_eq!(config.rules[0].rule_type, RuleType::RainRate);
+        assert_eq!(config.rule_source(&config.rules[0]), Some("roof"));
+    }
+
+    #[test]
+    fn a_typoed_rule_field_is_rejected_loudly() {
+        let err = serde_json::from_str::<Rule>(r#"{"type": "humidity", "treshold": 90}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("treshold"), "{err}");
+    }
+
+    #[test]
+    fn an_unknown_rule_type_is_rejected() {
+        let err = serde_json::from_str::<Rule>(r#"{"type": "lightning", "threshold": 1}"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("lightning"), "{err}");
+    }
+
+    #[test]
+    fn load_config_refuses_a_config_without_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("weather-monitor.json");
+        let mut config = Config::default();
+        config.rules.clear();
+        std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+        let err = load_config(&path).unwrap_err().to_string();
+        assert!(err.contains("rules: must list at least one rule"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn is_safe_requires_connection() {
+        let device = WeatherMonitorDevice::new(Config::default());
+        let err = device.is_safe().await.unwrap_err();
+        assert_eq!(err.code, ascom_alpaca::ASCOMErrorCode::NOT_CONNECTED);
+    }
+
+    /// An unreachable source does not fail the connect, and before any
+    /// clear spell the device reports unsafe.
+    #[tokio::test]
+    async fn connect_with_unreachable_source_reports_unsafe() {
+        let mut config = Config::default();
+        config.sources[0].alpaca_url = "not-a-url".to_string();
+        let device = WeatherMonitorDevice::new(config);
+        device.set_connected(true).await.unwrap();
+        assert!(!device.is_safe().await.unwrap());
+        device.set_connected(false).await.unwrap();
+        assert!(!device.connected().await.unwrap());
+    }
+
+    #[test]
+    fn a_reconnected_monitor_holds_unsafe_for_safe_delay() {
+        let timing = Config::default().timing;
+        let safe_delay = timing.safe_delay;
+        let mut state = MonitorState::new(&timing);
+        let t0 = Instant::now();
+        state.update(Vec::new(), t0);
+        assert!(!state.hysteresis.current());
+        state.update(Vec::new(), t0 + safe_delay);
+        assert!(state.hysteresis.current());
+
+        state.disconnected();
+        assert!(!state.hysteresis.current());
+        let t1 = t0 + 3 * safe_delay;
+        state.update(Vec::new(), t1);
+        assert!(!state.hysteresis.current());
+        state.update(Vec::new(), t1 + safe_delay);
+        assert!(state.hysteresis.current());
+    }
+}
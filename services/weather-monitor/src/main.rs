@@ -0,0 +1,83 @@
+use clap::Parser;
+use rusty_photon_service_lifecycle::{ServiceResult, ServiceRunner};
+use std::path::PathBuf;
+use tracing::{debug, Level};
+use weather_monitor::{run_server_loop, Config};
+
+#[derive(Parser)]
+#[command(name = "weather-monitor")]
+#[command(about = "ASCOM Alpaca SafetyMonitor that aggregates weather sensors")]
+// A top-level `--config` alongside a subcommand would parse but be
+// silently ignored (the subcommand carries its own); reject the mixed
+// form outright, same as rp's CLI.
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Path to configuration file. Defaults to the platform config
+    /// directory (e.g. `~/.config/rusty-photon/weather-monitor.json` on Linux);
+    /// created with defaults on first start if absent.
+    #[arg(short, long)]
+    config: Option<PathBuf>,
+
+    /// Log level
+    #[arg(short, long, default_value = "info", value_parser = clap::value_parser!(Level))]
+    log_level: Level,
+
+    /// Run as a Windows service (used by the service control manager).
+    /// No-op on non-Windows targets.
+    #[arg(long, hide = true)]
+    service: bool,
+}
+
+/// Subcommands; running with none starts the ASCOM Alpaca server.
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Diagnose this service's configuration without starting it
+    /// (docs/services/doctor.md). Read-only; exits 1 on failing checks.
+    Doctor {
+        /// Path to configuration file
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Print the report as JSON instead of text
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+fn main() -> ServiceResult {
+    let args = Args::parse();
+
+    if let Some(Command::Doctor { config, json }) = args.command {
+        weather_monitor::doctor::run(config, json);
+    }
+
+    // In Windows SCM service mode logs go to the rolling file under
+    // %PROGRAMDATA%\rusty-photon\logs\; hold the guard until process exit so
+    // the final lines flush on SCM Stop. Console mode logs to stderr as before.
+    let _tracing_guard = rusty_photon_service_lifecycle::init_service_tracing(
+        "weather-monitor",
+        args.log_level,
+        args.service,
+    );
+
+    debug!(
+        "Parsed command line arguments: config={:?}, log_level={:?}, service={}",
+        args.config, args.log_level, args.service
+    );
+
+    let config_path = rusty_photon_config::resolve_and_init(
+        "weather-monitor",
+        args.config,
+        &serde_json::to_value(Config::default())?,
+        &[],
+    )?;
+    ServiceRunner::new("weather-monitor")
+        .with_reload()
+        .scm_mode(args.service)
+        .run_with_reload(move |shutdown, reload| async move {
+            run_server_loop(&config_path, shutdown.token(), reload).await
+        })
+}
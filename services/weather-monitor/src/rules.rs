@@ -0,0 +1,506 @@
+//! Rule evaluation, staleness, and the unsafe/safe hysteresis
+//! (docs/services/weather-monitor.md § Evaluation).
+//!
+//! Everything here is pure — readings and `now` in, a verdict out — so the
+//! timing behaviour is testable without a clock or a weather station.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::{Config, Rule, RuleType};
+
+/// An ObservingConditions sensor some rule reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Sensor {
+    CloudCover,
+    DewPoint,
+    Humidity,
+    RainRate,
+    SkyQuality,
+    SkyTemperature,
+    Temperature,
+    WindGust,
+    WindSpeed,
+}
+
+impl Sensor {
+    /// The sensor name ASCOM's `TimeSinceLastUpdate` takes.
+    #[must_use]
+    pub const fn ascom_name(self) -> &'static str {
+        match self {
+            Self::CloudCover => "CloudCover",
+            Self::DewPoint => "DewPoint",
+            Self::Humidity => "Humidity",
+            Self::RainRate => "RainRate",
+            Self::SkyQuality => "SkyQuality",
+            Self::SkyTemperature => "SkyTemperature",
+            Self::Temperature => "Temperature",
+            Self::WindGust => "WindGust",
+            Self::WindSpeed => "WindSpeed",
+        }
+    }
+
+    /// The name in log lines and unsafe reasons.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::CloudCover => "cloud_cover",
+            Self::DewPoint => "dew_point",
+            Self::Humidity => "humidity",
+            Self::RainRate => "rain_rate",
+            Self::SkyQuality => "sky_quality",
+            Self::SkyTemperature => "sky_temperature",
+            Self::Temperature => "temperature",
+            Self::WindGust => "wind_gust",
+            Self::WindSpeed => "wind_speed",
+        }
+    }
+}
+
+impl RuleType {
+    /// The sensors the rule reads, all from its one source.
+    #[must_use]
+    pub const fn sensors(self) -> &'static [Sensor] {
+        match self {
+            Self::Humidity => &[Sensor::Humidity],
+            Self::DewPointSpread => &[Sensor::Temperature, Sensor::DewPoint],
+            Self::WindSpeed => &[Sensor::WindSpeed],
+            Self::WindGust => &[Sensor::WindGust],
+            Self::RainRate => &[Sensor::RainRate],
+            Self::Clouds => &[Sensor::Temperature, Sensor::SkyTemperature],
+            Self::SkyQuality => &[Sensor::SkyQuality],
+            Self::CloudCover => &[Sensor::CloudCover],
+        }
+    }
+
+    /// The name in unsafe reasons — the config's `type` string.
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Humidity => "humidity",
+            Self::DewPointSpread => "dew_point_spread",
+            Self::WindSpeed => "wind_speed",
+            Self::WindGust => "wind_gust",
+            Self::RainRate => "rain_rate",
+            Self::Clouds => "clouds",
+            Self::SkyQuality => "sky_quality",
+            Self::CloudCover => "cloud_cover",
+        }
+    }
+
+    /// `true` when a measurement *below* the threshold is unsafe.
+    const fn unsafe_below(self) -> bool {
+        matches!(self, Self::DewPointSpread | Self::Clouds | Self::SkyQuality)
+    }
+}
+
+/// One sensor value as last read from a source.
+#[derive(Debug, Clone, Copy)]
+pub struct Reading {
+    pub value: f64,
+    /// When the monitor read it.
+    pub read_at: Instant,
+    /// How old the device said the value was at `read_at`.
+    pub device_age: DeviceAge,
+}
+
+/// A sensor's `TimeSinceLastUpdate` as the device answered it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeviceAge {
+    /// The device does not track per-sensor age (the call failed or
+    /// answered negative).
+    Untracked,
+    /// The value was this old at `read_at`.
+    Known(Duration),
+    /// The device answered NaN or infinity: how old the value is cannot
+    /// be known, so it is never taken as fresh.
+    NonFinite(f64),
+}
+
+impl Reading {
+    /// Age of the underlying measurement at `now`. A non-finite device age
+    /// saturates, which reads as stale.
+    #[must_use]
+    pub fn age(&self, now: Instant) -> Duration {
+        let device_age = match self.device_age {
+            DeviceAge::Untracked => Duration::ZERO,
+            DeviceAge::Known(age) => age,
+            DeviceAge::NonFinite(_) => Duration::MAX,
+        };
+        now.saturating_duration_since(self.read_at)
+            .saturating_add(device_age)
+    }
+}
+
+/// The latest reading of each sensor from one source.
+pub type Readings = HashMap<Sensor, Reading>;
+
+/// Every reason conditions are unsafe at `now`, one per failing rule; empty
+/// means safe. A rule whose reading is missing, older than `stale_after`,
+/// or not a finite number (value or device-reported age) fails like a
+/// tripped one — no data is never taken as good news.
+#[must_use]
+pub fn unsafe_reasons(
+    config: &Config,
+    readings: &HashMap<&str, &Readings>,
+    now: Instant,
+) -> Vec<String> {
+    config
+        .rules
+        .iter()
+        .filter_map(|rule| {
+            let Some(source) = config.rule_source(rule) else {
+                return Some(format!("{} rule names no source", rule.rule_type.label()));
+            };
+            let readings = readings.get(source).copied();
+            check_rule(rule, source, readings, now, config.timing.stale_after).err()
+        })
+        .collect()
+}
+
+/// One rule against one source's readings: `Err` carries the reason it is
+/// unsafe.
+fn check_rule(
+    rule: &Rule,
+    source: &str,
+    readings: Option<&Readings>,
+    now: Instant,
+    stale_after: Duration,
+) -> Result<(), String> {
+    let value = |sensor: Sensor| -> Result<f64, String> {
+        let Some(reading) = readings.and_then(|r| r.get(&sensor)) else {
+            return Err(format!(
+                "no {} reading from source '{source}'",
+                sensor.label()
+            ));
+        };
+        if !reading.value.is_finite() {
+            return Err(format!(
+                "{} reading from source '{source}' is not a finite number ({})",
+                sensor.label(),
+                reading.value
+            ));
+        }
+        if let DeviceAge::NonFinite(seconds) = reading.device_age {
+            return Err(format!(
+                "{} reading from source '{source}' has an unknowable age (TimeSinceLastUpdate {seconds})",
+                sensor.label()
+            ));
+        }
+        let age = reading.age(now);
+        if age > stale_after {
+            return Err(format!(
+                "{} reading from source '{source}' is stale ({}s old, stale_after {}s)",
+                sensor.label(),
+                age.as_secs(),
+                stale_after.as_secs()
+            ));
+        }
+        Ok(reading.value)
+    };
+
+    let measured = match rule.rule_type {
+        RuleType::Humidity => value(Sensor::Humidity)?,
+        RuleType::DewPointSpread => value(Sensor::Temperature)? - value(Sensor::DewPoint)?,
+        RuleType::WindSpeed => value(Sensor::WindSpeed)?,
+        RuleType::WindGust => value(Sensor::WindGust)?,
+        RuleType::RainRate => value(Sensor::RainRate)?,
+        RuleType::Clouds => value(Sensor::Temperature)? - value(Sensor::SkyTemperature)?,
+        RuleType::SkyQuality => value(Sensor::SkyQuality)?,
+        RuleType::CloudCover => value(Sensor::CloudCover)?,
+    };
+    let (tripped, side) = if rule.rule_type.unsafe_below() {
+        (measured < rule.threshold, "below")
+    } else {
+        (measured > rule.threshold, "above")
+    };
+    if tripped {
+        return Err(format!(
+            "{} {measured:.1} from source '{source}' is {side} threshold {}",
+            rule.rule_type.label(),
+            rule.threshold
+        ));
+    }
+    Ok(())
+}
+
+/// Debounces the per-poll verdict into the reported `IsSafe`: a change
+/// only sticks once the new verdict has held for its delay —
+/// `unsafe_delay` to close, `safe_delay` to reopen. Any poll agreeing with
+/// the reported verdict restarts the wait.
+///
+/// The reported verdict starts out unsafe, so a fresh start has to hold a
+/// clear sky for `safe_delay` like any other reopening: nothing has yet
+/// shown it clear for that long.
+#[derive(Debug)]
+pub struct Hysteresis {
+    unsafe_delay: Duration,
+    safe_delay: Duration,
+    reported: bool,
+    /// Since when the verdict has disagreed with `reported`.
+    pending_since: Option<Instant>,
+}
+
+impl Hysteresis {
+    #[must_use]
+    pub const fn new(unsafe_delay: Duration, safe_delay: Duration) -> Self {
+        Self {
+            unsafe_delay,
+            safe_delay,
+            reported: false,
+            pending_since: None,
+        }
+    }
+
+    /// The reported verdict.
+    #[must_use]
+    pub const fn current(&self) -> bool {
+        self.reported
+    }
+
+    /// Feed the verdict of one poll at `now`; returns the reported one.
+    pub fn update(&mut self, safe: bool, now: Instant) -> bool {
+        if safe == self.reported {
+            self.pending_since = None;
+            return self.reported;
+        }
+        let since = *self.pending_since.get_or_insert(now);
+        let delay = if safe {
+            self.safe_delay
+        } else {
+            self.unsafe_delay
+        };
+        if now.saturating_duration_since(since) >= delay {
+            self.reported = safe;
+            self.pending_since = None;
+        }
+        self.reported
+    }
+
+    /// Drop to unsafe with nothing pending, as at a fresh start. Polling
+    /// has stopped, so the verdict says nothing about the sky by the time
+    /// polling resumes; reopening must earn `safe_delay` again.
+    pub const fn hold_unsafe(&mut self) {
+        self.reported = false;
+        self.pending_since = None;
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const MIN: Duration = Duration::from_secs(60);
+
+    fn config(rules: serde_json::Value) -> Config {
+        let mut config = Config::default();
+        config.rules = serde_json::from_value(rules).unwrap();
+        config
+    }
+
+    fn fresh(now: Instant, values: &[(Sensor, f64)]) -> Readings {
+        values
+            .iter()
+            .map(|&(sensor, value)| {
+                (
+                    sensor,
+                    Reading {
+                        value,
+                        read_at: now,
+                        device_age: DeviceAge::Untracked,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn reasons(config: &Config, readings: &Readings, now: Instant) -> Vec<String> {
+        let by_source = HashMap::from([("ppba", readings)]);
+        unsafe_reasons(config, &by_source, now)
+    }
+
+    #[test]
+    fn each_rule_trips_on_its_own_side_of_the_threshold() {
+        let now = Instant::now();
+        let config = config(serde_json::json!([
+            {"type": "humidity", "threshold": 90},
+            {"type": "dew_point_spread", "threshold": 2},
+            {"type": "wind_gust", "threshold": 12},
+            {"type": "rain_rate", "threshold": 0},
+            {"type": "clouds", "threshold": 20},
+            {"type": "sky_quality", "threshold": 19.5},
+        ]));
+        let calm = fresh(
+            now,
+            &[
+                (Sensor::Humidity, 70.0),
+                (Sensor::Temperature, 10.0),
+                (Sensor::DewPoint, 4.0),
+                (Sensor::WindGust, 5.0),
+                (Sensor::RainRate, 0.0),
+                (Sensor::SkyTemperature, -18.0),
+                (Sensor::SkyQuality, 20.8),
+            ],
+        );
+        assert!(reasons(&config, &calm, now).is_empty());
+
+        let bad = fresh(
+            now,
+            &[
+                (Sensor::Humidity, 95.0),
+                (Sensor::Temperature, 10.0),
+                (Sensor::DewPoint, 9.0),
+                (Sensor::WindGust, 15.0),
+                (Sensor::RainRate, 0.2),
+                (Sensor::SkyTemperature, 2.0),
+                (Sensor::SkyQuality, 17.0),
+            ],
+        );
+        let got = reasons(&config, &bad, now);
+        assert_eq!(got.len(), 6, "{got:?}");
+        assert!(got[0].starts_with("humidity 95.0"), "{got:?}");
+        assert!(got[1].contains("below threshold 2"), "{got:?}");
+        assert!(got[4].starts_with("clouds 8.0"), "{got:?}");
+    }
+
+    #[test]
+    fn a_reading_exactly_at_the_threshold_is_safe() {
+        let now = Instant::now();
+        let config = config(serde_json::json!([{"type": "humidity", "threshold": 90}]));
+        let readings = fresh(now, &[(Sensor::Humidity, 90.0)]);
+        assert!(reasons(&config, &readings, now).is_empty());
+    }
+
+    #[test]
+    fn missing_and_stale_readings_are_unsafe() {
+        let now = Instant::now();
+        let config = config(serde_json::json!([
+            {"type": "humidity", "threshold": 90},
+            {"type": "wind_speed", "threshold": 10},
+        ]));
+        let mut readings = fresh(now, &[(Sensor::Humidity, 50.0)]);
+        let got = reasons(&config, &readings, now);
+        assert_eq!(got, vec!["no wind_speed reading from source 'ppba'"]);
+
+        // Device-reported age counts on top of the time since the poll.
+        readings.insert(
+            Sensor::WindSpeed,
+            Reading {
+                value: 1.0,
+                read_at: now,
+                device_age: DeviceAge::Known(4 * MIN),
+            },
+        );
+        assert!(reasons(&config, &readings, now).is_empty());
+        let later = now + 2 * MIN;
+        let got = reasons(&config, &readings, later);
+        assert_eq!(got.len(), 1, "{got:?}");
+        assert!(
+            got[0].contains("wind_speed reading from source 'ppba' is stale (360s old"),
+            "{got:?}"
+        );
+    }
+
+    #[test]
+    fn non_finite_readings_and_ages_are_unsafe() {
+        let now = Instant::now();
+        let config = config(serde_json::json!([
+            {"type": "humidity", "threshold": 90},
+            {"type": "wind_gust", "threshold": 12},
+            {"type": "clouds", "threshold": 20},
+        ]));
+        // A NaN would compare false against either side of any threshold,
+        // and a NaN age would read as fresh — neither may pass as safe.
+        let mut readings = fresh(
+            now,
+            &[
+                (Sensor::Humidity, f64::NAN),
+                (Sensor::WindGust, 5.0),
+                (Sensor::Temperature, 10.0),
+                (Sensor::SkyTemperature, f64::NEG_INFINITY),
+            ],
+        );
+        readings.insert(
+            Sensor::WindGust,
+            Reading {
+                value: 5.0,
+                read_at: now,
+                device_age: DeviceAge::NonFinite(f64::NAN),
+            },
+        );
+        let got = reasons(&config, &readings, now);
+        assert_eq!(
+            got,
+            vec![
+                "humidity reading from source 'ppba' is not a finite number (NaN)",
+                "wind_gust reading from source 'ppba' has an unknowable age (TimeSinceLastUpdate NaN)",
+                "sky_temperature reading from source 'ppba' is not a finite number (-inf)",
+            ]
+        );
+    }
+
+    #[test]
+    fn an_unpolled_source_makes_its_rules_unsafe() {
+        let now = Instant::now();
+        let config = config(serde_json::json!([{"type": "humidity", "threshold": 90}]));
+        let got = unsafe_reasons(&config, &HashMap::new(), now);
+        assert_eq!(got, vec!["no humidity reading from source 'ppba'"]);
+    }
+
+    #[test]
+    fn a_fresh_hysteresis_holds_unsafe_for_safe_delay() {
+        let t0 = Instant::now();
+        let mut h = Hysteresis::new(2 * MIN, 20 * MIN);
+        assert!(!h.current());
+        assert!(!h.update(true, t0));
+        assert!(!h.update(true, t0 + 19 * MIN));
+        assert!(h.update(true, t0 + 20 * MIN));
+
+        // Held unsafe again (a disconnect): the clear sky has to hold for
+        // safe_delay once more, however long it was clear before.
+        h.hold_unsafe();
+        assert!(!h.current());
+        assert!(!h.update(true, t0 + 60 * MIN));
+        assert!(!h.update(true, t0 + 79 * MIN));
+        assert!(h.update(true, t0 + 80 * MIN));
+    }
+
+    #[test]
+    fn a_single_gust_does_not_close() {
+        let t0 = Instant::now();
+        let mut h = Hysteresis::new(2 * MIN, 20 * MIN);
+        h.update(true, t0);
+        assert!(h.update(true, t0 + 20 * MIN));
+        let t0 = t0 + 20 * MIN;
+        assert!(h.update(false, t0 + MIN));
+        // Back to safe before unsafe_delay elapsed: the wait resets.
+        assert!(h.update(true, t0 + 2 * MIN));
+        assert!(h.update(false, t0 + 3 * MIN));
+        assert!(h.update(false, t0 + 4 * MIN));
+        assert!(!h.update(false, t0 + 5 * MIN));
+    }
+
+    #[test]
+    fn reopening_waits_for_safe_delay_of_continuous_clear() {
+        let t0 = Instant::now();
+        let mut h = Hysteresis::new(2 * MIN, 20 * MIN);
+        h.update(false, t0);
+        assert!(!h.update(true, t0 + MIN));
+        assert!(!h.update(true, t0 + 20 * MIN));
+        // A shower restarts the clock.
+        assert!(!h.update(false, t0 + 20 * MIN + MIN / 2));
+        assert!(!h.update(true, t0 + 22 * MIN));
+        assert!(!h.update(true, t0 + 41 * MIN));
+        assert!(h.update(true, t0 + 42 * MIN));
+    }
+
+    #[test]
+    fn zero_delays_follow_every_verdict() {
+        let t0 = Instant::now();
+        let mut h = Hysteresis::new(Duration::ZERO, Duration::ZERO);
+        assert!(h.update(true, t0));
+        assert!(!h.update(false, t0 + MIN));
+        assert!(h.update(true, t0 + 2 * MIN));
+    }
+}
@@ -0,0 +1,267 @@
+//! One polled ObservingConditions source: its Alpaca client, the device
+//! handle, and the latest reading of each sensor the rules need from it.
+//!
+//! The handle is resolved lazily and dropped when a whole poll fails, so a
+//! weather station that restarts (or comes up after this service) is
+//! picked up again without a reconnect. Readings survive failed polls and
+//! simply age; `rules` decides when they are too old to trust.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ascom_alpaca::api::{ObservingConditions, TypedDevice};
+use ascom_alpaca::{ASCOMResult, Client};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rp_auth::config::ClientAuthConfig;
+use tracing::{debug, info, warn};
+
+use crate::rules::{DeviceAge, Reading, Readings, Sensor};
+use crate::{Config, SourceConfig};
+
+/// Connect-phase timeout for every request to a source. A LAN Alpaca
+/// server connects in well under this; it keeps a dead host from stalling
+/// the poll.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Per-read inactivity timeout. reqwest has no default request timeout
+/// and the Alpaca client adds none, so without it one wedged source would
+/// stall the polling loop — and with it every other source — forever.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub(crate) struct Source {
+    id: String,
+    device_number: u32,
+    /// `Err` when the client could not be built (bad URL or CA file); the
+    /// source then never reads and its rules stay unsafe.
+    client: Result<Client, String>,
+    device: Option<Arc<dyn ObservingConditions>>,
+    /// The sensors some rule reads from this source.
+    sensors: BTreeSet<Sensor>,
+    readings: Readings,
+    /// The last resolve failure, so a source that stays down logs once
+    /// rather than every poll.
+    last_error: Option<String>,
+}
+
+impl Source {
+    pub(crate) fn new(source: &SourceConfig, config: &Config, ca_cert: Option<&Path>) -> Self {
+        let sensors = config
+            .rules
+            .iter()
+            .filter(|rule| config.rule_source(rule) == Some(source.id.as_str()))
+            .flat_map(|rule| rule.rule_type.sensors().iter().copied())
+            .collect();
+        Self {
+            id: source.id.clone(),
+            device_number: source.device_number,
+            client: build_client(&source.alpaca_url, source.auth.as_ref(), ca_cert)
+                .map_err(|e| e.to_string()),
+            device: None,
+            sensors,
+            readings: Readings::new(),
+            last_error: None,
+        }
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub(crate) const fn readings(&self) -> &Readings {
+        &self.readings
+    }
+
+    /// Read every sensor this source serves. A failed read keeps the
+    /// previous value, which ages toward stale.
+    pub(crate) async fn poll(&mut self) {
+        if self.sensors.is_empty() {
+            return;
+        }
+        let Some(oc) = self.resolve().await else {
+            return;
+        };
+        let mut any_read = false;
+        for &sensor in &self.sensors {
+            match read_sensor(&oc, sensor).await {
+                Ok(value) => {
+                    any_read = true;
+                    let device_age = sensor_age(&oc, sensor).await;
+                    self.readings.insert(
+                        sensor,
+                        Reading {
+                            value,
+                            read_at: Instant::now(),
+                            device_age,
+                        },
+                    );
+                }
+                Err(e) => {
+                    debug!(source = %self.id, sensor = sensor.label(), error = %e, "sensor read failed")
+                }
+            }
+        }
+        if !any_read {
+            // Nothing answered: most likely the station restarted or went
+            // away. Re-resolve next poll.
+            self.device = None;
+        }
+    }
+
+    /// The device handle, discovering and connecting it when there is none.
+    async fn resolve(&mut self) -> Option<Arc<dyn ObservingConditions>> {
+        if let Some(oc) = &self.device {
+            return Some(Arc::clone(oc));
+        }
+        match self.connect().await {
+            Ok(oc) => {
+                info!(source = %self.id, "observing conditions source connected");
+                self.last_error = None;
+                self.device = Some(Arc::clone(&oc));
+                Some(oc)
+            }
+            Err(e) => {
+                if self.last_error.as_ref() != Some(&e) {
+                    warn!(source = %self.id, error = %e, "observing conditions source unavailable");
+                }
+                self.last_error = Some(e);
+                None
+            }
+        }
+    }
+
+    async fn connect(&self) -> Result<Arc<dyn ObservingConditions>, String> {
+        let client = self.client.as_ref().map_err(String::clone)?;
+        let devices = client
+            .get_devices()
+            .await
+            .map_err(|e| format!("get_devices: {e}"))?;
+        let oc = devices
+            .into_iter()
+            .filter_map(|device| match device {
+                TypedDevice::ObservingConditions(oc) => Some(oc),
+                _ => None,
+            })
+            .nth(self.device_number as usize)
+            .ok_or_else(|| {
+                format!(
+                    "observing conditions at index {} not found on Alpaca server",
+                    self.device_number
+                )
+            })?;
+        oc.set_connected(true)
+            .await
+            .map_err(|e| format!("set_connected: {e}"))?;
+        Ok(oc)
+    }
+}
+
+async fn read_sensor(oc: &Arc<dyn ObservingConditions>, sensor: Sensor) -> ASCOMResult<f64> {
+    match sensor {
+        Sensor::CloudCover => oc.cloud_cover().await,
+        Sensor::DewPoint => oc.dew_point().await,
+        Sensor::Humidity => oc.humidity().await,
+        Sensor::RainRate => oc.rain_rate().await,
+        Sensor::SkyQuality => oc.sky_quality().await,
+        Sensor::SkyTemperature => oc.sky_temperature().await,
+        Sensor::Temperature => oc.temperature().await,
+        Sensor::WindGust => oc.wind_gust().await,
+        Sensor::WindSpeed => oc.wind_speed().await,
+    }
+}
+
+/// How old the device says `sensor` is: untracked when the call fails or
+/// answers negative, non-finite for NaN or infinity (which the rules
+/// treat as unsafe). A finite value too large for a `Duration` (the PPBA
+/// reports `f64::MAX` before its first sample) saturates, which reads as
+/// stale.
+async fn sensor_age(oc: &Arc<dyn ObservingConditions>, sensor: Sensor) -> DeviceAge {
+    let Ok(seconds) = oc
+        .time_since_last_update(sensor.ascom_name().to_string())
+        .await
+    else {
+        return DeviceAge::Untracked;
+    };
+    if !seconds.is_finite() {
+        return DeviceAge::NonFinite(seconds);
+    }
+    if seconds < 0.0 {
+        return DeviceAge::Untracked;
+    }
+    DeviceAge::Known(Duration::try_from_secs_f64(seconds).unwrap_or(Duration::MAX))
+}
+
+/// Build an Alpaca client with per-request timeouts, optional HTTP Basic
+/// Auth credentials, and optional CA certificate trust — the same shape rp
+/// uses for its equipment.
+fn build_client(
+    url: &str,
+    auth: Option<&ClientAuthConfig>,
+    ca_cert: Option<&Path>,
+) -> Result<Client, Box<dyn std::error::Error + Send + Sync>> {
+    let mut builder = rusty_photon_tls::client::client_builder(ca_cert)?
+        .user_agent("rusty-photon-weather-monitor")
+        .connect_timeout(CONNECT_TIMEOUT)
+        .read_timeout(READ_TIMEOUT);
+    if let Some(a) = auth {
+        let encoded = BASE64.encode(format!("{}:{}", a.username, a.password));
+        // `set_sensitive` keeps the credential out of `Client`'s `Debug`.
+        let mut header_value: reqwest::header::HeaderValue = format!("Basic {encoded}").parse()?;
+        header_value.set_sensitive(true);
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("authorization", header_value);
+        builder = builder.default_headers(headers);
+    }
+    Ok(Client::new_with_client(url, builder.build()?)?)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_source_reads_only_what_its_rules_need() {
+        let mut config = Config::default();
+        config.sources.push(SourceConfig {
+            id: "cloudwatcher".to_string(),
+            alpaca_url: "http://127.0.0.1:11200".to_string(),
+            device_number: 0,
+            auth: None,
+        });
+        config.rules = serde_json::from_value(serde_json::json!([
+            {"type": "dew_point_spread", "threshold": 2, "source": "ppba"},
+            {"type": "clouds", "threshold": 20, "source": "cloudwatcher"},
+            {"type": "rain_rate", "threshold": 0, "source": "cloudwatcher"},
+        ]))
+        .unwrap();
+
+        let ppba = Source::new(&config.sources[0], &config, None);
+        assert_eq!(
+            ppba.sensors.iter().copied().collect::<Vec<_>>(),
+            vec![Sensor::DewPoint, Sensor::Temperature]
+        );
+        let cloudwatcher = Source::new(&config.sources[1], &config, None);
+        assert_eq!(
+            cloudwatcher.sensors.iter().copied().collect::<Vec<_>>(),
+            vec![
+                Sensor::RainRate,
+                Sensor::SkyTemperature,
+                Sensor::Temperature
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn a_bad_url_never_resolves() {
+        let mut config = Config::default();
+        config.sources[0].alpaca_url = "not-a-url".to_string();
+        let mut source = Source::new(&config.sources[0], &config, None);
+        assert!(source.client.is_err());
+        source.poll().await;
+        assert!(source.readings().is_empty());
+        assert!(source.last_error.is_some());
+    }
+}
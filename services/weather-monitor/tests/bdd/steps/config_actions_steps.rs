@@ -0,0 +1,63 @@
+//! Step definitions for `config_actions.feature`.
+
+use cucumber::{then, when};
+
+use crate::world::WeatherMonitorWorld;
+
+#[when("config.apply sets stale_after below the polling interval")]
+async fn apply_short_stale_after(world: &mut WeatherMonitorWorld) {
+    let mut config = world.current_config().await;
+    config["timing"]["polling_interval"] = serde_json::json!("30s");
+    config["timing"]["stale_after"] = serde_json::json!("10s");
+    world.call_config_apply(config).await;
+}
+
+#[when("config.apply removes every rule")]
+async fn apply_no_rules(world: &mut WeatherMonitorWorld) {
+    let mut config = world.current_config().await;
+    config["rules"] = serde_json::json!([]);
+    world.call_config_apply(config).await;
+}
+
+#[when(regex = r"^config\.apply sets the unsafe delay to (\S+)$")]
+async fn apply_unsafe_delay(world: &mut WeatherMonitorWorld, value: String) {
+    let mut config = world.current_config().await;
+    config["timing"]["unsafe_delay"] = serde_json::json!(value);
+    world.call_config_apply(config).await;
+}
+
+#[then(regex = r"^the apply status should be (\w+)$")]
+async fn assert_apply_status(world: &mut WeatherMonitorWorld, expected: String) {
+    let response = world.last_response.as_ref().expect("no response stashed");
+    assert_eq!(
+        response["status"].as_str(),
+        Some(expected.as_str()),
+        "{response}"
+    );
+}
+
+#[then(regex = r"^the validation errors should name (\S+)$")]
+async fn assert_validation_error_path(world: &mut WeatherMonitorWorld, path: String) {
+    let response = world.last_response.as_ref().expect("no response stashed");
+    let errors = response["errors"]
+        .as_array()
+        .expect("`errors` is not an array");
+    assert!(
+        errors
+            .iter()
+            .any(|e| e["path"].as_str() == Some(path.as_str())),
+        "validation errors {errors:?} do not name {path}"
+    );
+}
+
+#[then(regex = r"^the reload list should include (\S+)$")]
+async fn assert_reload_includes(world: &mut WeatherMonitorWorld, path: String) {
+    let response = world.last_response.as_ref().expect("no response stashed");
+    let reload = response["reload"]
+        .as_array()
+        .expect("`reload` is not an array");
+    assert!(
+        reload.iter().any(|p| p.as_str() == Some(path.as_str())),
+        "reload list {reload:?} does not include {path}"
+    );
+}
@@ -0,0 +1,57 @@
+//! Step definitions for `configuration.feature`: a safety monitor must
+//! refuse to start on a config it cannot honour.
+
+use cucumber::{given, then, when};
+
+use crate::world::WeatherMonitorWorld;
+
+#[given("a configuration with no rules")]
+fn config_without_rules(world: &mut WeatherMonitorWorld) {
+    let mut config = world.build_config_json();
+    config["rules"] = serde_json::json!([]);
+    world.config_override = Some(config);
+}
+
+#[given("a configuration with no sources")]
+fn config_without_sources(world: &mut WeatherMonitorWorld) {
+    let mut config = world.build_config_json();
+    config["sources"] = serde_json::json!([]);
+    world.config_override = Some(config);
+}
+
+#[given("a configuration whose stale_after is shorter than its polling interval")]
+fn config_with_short_stale_after(world: &mut WeatherMonitorWorld) {
+    let mut config = world.build_config_json();
+    config["timing"]["polling_interval"] = serde_json::json!("10s");
+    config["timing"]["stale_after"] = serde_json::json!("5s");
+    world.config_override = Some(config);
+}
+
+#[given("a configuration whose rule names an unknown source")]
+fn config_with_unknown_rule_source(world: &mut WeatherMonitorWorld) {
+    let mut config = world.build_config_json();
+    config["rules"] = serde_json::json!([
+        {"type": "humidity", "threshold": 90, "source": "no-such-station"},
+    ]);
+    world.config_override = Some(config);
+}
+
+#[given("a configuration with an unknown rule type")]
+fn config_with_unknown_rule_type(world: &mut WeatherMonitorWorld) {
+    let mut config = world.build_config_json();
+    config["rules"] = serde_json::json!([{"type": "seeing", "threshold": 2}]);
+    world.config_override = Some(config);
+}
+
+#[when("I try to start weather-monitor with this configuration")]
+async fn try_start_with_config(world: &mut WeatherMonitorWorld) {
+    world.try_start_weather_monitor().await;
+}
+
+#[then("the binary should fail to start")]
+fn binary_should_fail(world: &mut WeatherMonitorWorld) {
+    assert!(
+        world.last_error.is_some(),
+        "expected the binary to fail but it started successfully"
+    );
+}
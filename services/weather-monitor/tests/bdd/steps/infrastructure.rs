@@ -0,0 +1,200 @@
+//! Test infrastructure: weather-monitor process management and the fake
+//! weather station it polls.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use ascom_alpaca::api::{CargoServerInfo, Device, ObservingConditions};
+use ascom_alpaca::{ASCOMError, ASCOMResult, Server};
+use axum::response::IntoResponse;
+
+pub use bdd_infra::ServiceHandle;
+
+/// What the fake station reports, shared between the Alpaca device and the
+/// steps that change it mid-scenario.
+#[derive(Debug, Default)]
+pub struct StationState {
+    /// Sensor values by rule-config sensor name (`humidity`, `dew_point`,
+    /// …); a sensor absent here is not implemented.
+    values: Mutex<HashMap<String, f64>>,
+    /// `TimeSinceLastUpdate` for every sensor, in seconds.
+    age_secs: Mutex<f64>,
+    /// While set, every request — management API included — answers 503,
+    /// as a station that has gone away would fail.
+    offline: AtomicBool,
+}
+
+impl StationState {
+    pub fn set(&self, sensor: &str, value: f64) {
+        self.values
+            .lock()
+            .unwrap()
+            .insert(sensor.to_string(), value);
+    }
+
+    pub fn remove(&self, sensor: &str) {
+        self.values.lock().unwrap().remove(sensor);
+    }
+
+    pub fn set_age(&self, seconds: f64) {
+        *self.age_secs.lock().unwrap() = seconds;
+    }
+
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::SeqCst);
+    }
+
+    fn read(&self, sensor: &str) -> ASCOMResult<f64> {
+        self.values
+            .lock()
+            .unwrap()
+            .get(sensor)
+            .copied()
+            .ok_or(ASCOMError::NOT_IMPLEMENTED)
+    }
+}
+
+/// The ObservingConditions device the station serves.
+#[derive(Debug)]
+struct FakeStation {
+    state: Arc<StationState>,
+    connected: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl Device for FakeStation {
+    fn static_name(&self) -> &str {
+        "BDD Fake Weather Station"
+    }
+
+    fn unique_id(&self) -> &str {
+        "bdd-fake-weather-001"
+    }
+
+    async fn connected(&self) -> ASCOMResult<bool> {
+        Ok(self.connected.load(Ordering::SeqCst))
+    }
+
+    async fn set_connected(&self, connected: bool) -> ASCOMResult<()> {
+        self.connected.store(connected, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn description(&self) -> ASCOMResult<String> {
+        Ok("Scenario-controlled readings for the weather-monitor BDD suite".to_string())
+    }
+
+    async fn driver_info(&self) -> ASCOMResult<String> {
+        Ok("fake weather".to_string())
+    }
+
+    async fn driver_version(&self) -> ASCOMResult<String> {
+        Ok("0.1.0".to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl ObservingConditions for FakeStation {
+    async fn average_period(&self) -> ASCOMResult<f64> {
+        Ok(0.0)
+    }
+
+    async fn set_average_period(&self, _period: f64) -> ASCOMResult<()> {
+        Ok(())
+    }
+
+    async fn cloud_cover(&self) -> ASCOMResult<f64> {
+        self.state.read("cloud_cover")
+    }
+
+    async fn dew_point(&self) -> ASCOMResult<f64> {
+        self.state.read("dew_point")
+    }
+
+    async fn humidity(&self) -> ASCOMResult<f64> {
+        self.state.read("humidity")
+    }
+
+    async fn rain_rate(&self) -> ASCOMResult<f64> {
+        self.state.read("rain_rate")
+    }
+
+    async fn sky_quality(&self) -> ASCOMResult<f64> {
+        self.state.read("sky_quality")
+    }
+
+    async fn sky_temperature(&self) -> ASCOMResult<f64> {
+        self.state.read("sky_temperature")
+    }
+
+    async fn temperature(&self) -> ASCOMResult<f64> {
+        self.state.read("temperature")
+    }
+
+    async fn wind_gust(&self) -> ASCOMResult<f64> {
+        self.state.read("wind_gust")
+    }
+
+    async fn wind_speed(&self) -> ASCOMResult<f64> {
+        self.state.read("wind_speed")
+    }
+
+    async fn time_since_last_update(&self, _sensor_name: String) -> ASCOMResult<f64> {
+        Ok(*self.state.age_secs.lock().unwrap())
+    }
+}
+
+/// A fake weather station served in-process on an ephemeral port, the
+/// source every scenario points weather-monitor at.
+#[derive(Debug)]
+pub struct StationHandle {
+    pub state: Arc<StationState>,
+    addr: SocketAddr,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl StationHandle {
+    pub async fn spawn() -> Self {
+        let state = Arc::new(StationState::default());
+        let mut server = Server::new(CargoServerInfo!());
+        server.devices.register(FakeStation {
+            state: Arc::clone(&state),
+            connected: AtomicBool::new(false),
+        });
+        let gate = Arc::clone(&state);
+        let router = axum::Router::new()
+            .fallback_service(server.into_service())
+            .layer(axum::middleware::from_fn(
+                move |request: axum::extract::Request, next: axum::middleware::Next| {
+                    let gate = Arc::clone(&gate);
+                    async move {
+                        if gate.offline.load(Ordering::SeqCst) {
+                            axum::http::StatusCode::SERVICE_UNAVAILABLE.into_response()
+                        } else {
+                            next.run(request).await
+                        }
+                    }
+                },
+            ));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind the fake station");
+        let addr = listener.local_addr().unwrap();
+        let task = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+        Self { state, addr, task }
+    }
+
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+}
+
+impl Drop for StationHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
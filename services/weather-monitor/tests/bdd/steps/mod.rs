@@ -0,0 +1,5 @@
+pub mod config_actions_steps;
+pub mod config_steps;
+pub mod infrastructure;
+pub mod monitor_steps;
+pub mod station_steps;
@@ -0,0 +1,95 @@
+//! Step definitions for starting weather-monitor, connecting it, and
+//! reading its verdict.
+
+use std::time::Duration;
+
+use ascom_alpaca::ASCOMErrorCode;
+use cucumber::{given, then, when};
+
+use crate::world::WeatherMonitorWorld;
+
+#[given(expr = "a {word} rule with threshold {float}")]
+fn rule_with_threshold(world: &mut WeatherMonitorWorld, rule_type: String, threshold: f64) {
+    world
+        .rules
+        .push(serde_json::json!({"type": rule_type, "threshold": threshold}));
+}
+
+#[given(expr = "an unsafe delay of {int} seconds")]
+const fn unsafe_delay(world: &mut WeatherMonitorWorld, seconds: u64) {
+    world.unsafe_delay_secs = Some(seconds);
+}
+
+#[given(expr = "a safe delay of {int} seconds")]
+const fn safe_delay(world: &mut WeatherMonitorWorld, seconds: u64) {
+    world.safe_delay_secs = Some(seconds);
+}
+
+#[given(expr = "readings go stale after {int} seconds")]
+const fn stale_after(world: &mut WeatherMonitorWorld, seconds: u64) {
+    world.stale_after_secs = Some(seconds);
+}
+
+#[given("weather-monitor is running")]
+async fn weather_monitor_running(world: &mut WeatherMonitorWorld) {
+    world.start_weather_monitor().await;
+}
+
+#[given("weather-monitor is running against an unreachable source")]
+async fn weather_monitor_running_unreachable(world: &mut WeatherMonitorWorld) {
+    world.station = None;
+    world.start_weather_monitor().await;
+}
+
+#[given("the device is connected")]
+#[when("I connect the device")]
+async fn connect_device(world: &mut WeatherMonitorWorld) {
+    world.monitor().set_connected(true).await.unwrap();
+}
+
+#[when("I disconnect the device")]
+async fn disconnect_device(world: &mut WeatherMonitorWorld) {
+    world.monitor().set_connected(false).await.unwrap();
+}
+
+#[then(expr = "is_safe should become {word} within {int} seconds")]
+async fn is_safe_becomes(world: &mut WeatherMonitorWorld, expected: String, seconds: u64) {
+    world
+        .wait_for_is_safe(expected == "true", Duration::from_secs(seconds))
+        .await;
+}
+
+#[then(expr = "is_safe should stay {word} for {int} seconds")]
+async fn is_safe_stays(world: &mut WeatherMonitorWorld, expected: String, seconds: u64) {
+    world
+        .assert_is_safe_holds(expected == "true", Duration::from_secs(seconds))
+        .await;
+}
+
+#[then("is_safe should fail with a not connected error")]
+async fn is_safe_should_fail_not_connected(world: &mut WeatherMonitorWorld) {
+    let err = world
+        .monitor()
+        .is_safe()
+        .await
+        .expect_err("expected NotConnected error but got Ok");
+    assert_eq!(
+        err.code,
+        ASCOMErrorCode::NOT_CONNECTED,
+        "expected NOT_CONNECTED error code but got {:?}: {}",
+        err.code,
+        err
+    );
+}
+
+#[then("the device should be connected")]
+async fn device_should_be_connected(world: &mut WeatherMonitorWorld) {
+    let connected = world.monitor().connected().await.unwrap();
+    assert!(connected, "expected connected but device is disconnected");
+}
+
+#[then("the device should be disconnected")]
+async fn device_should_be_disconnected(world: &mut WeatherMonitorWorld) {
+    let connected = world.monitor().connected().await.unwrap();
+    assert!(!connected, "expected disconnected but device is connected");
+}
@@ -0,0 +1,40 @@
+//! Step definitions driving the fake weather station.
+
+use cucumber::{given, when};
+
+use crate::world::WeatherMonitorWorld;
+
+#[given(expr = "a weather station reporting {word} {float}")]
+async fn station_reporting(world: &mut WeatherMonitorWorld, sensor: String, value: f64) {
+    world.station().await.state.set(&sensor, value);
+}
+
+#[given(expr = "a weather station that does not report {word}")]
+async fn station_not_reporting(world: &mut WeatherMonitorWorld, sensor: String) {
+    world.station().await.state.remove(&sensor);
+}
+
+#[given(expr = "a weather station whose readings are {int} seconds old")]
+async fn station_readings_aged(world: &mut WeatherMonitorWorld, seconds: u32) {
+    world.station().await.state.set_age(f64::from(seconds));
+}
+
+#[when(expr = "the station reports {word} {float}")]
+async fn station_reports(world: &mut WeatherMonitorWorld, sensor: String, value: f64) {
+    world.station().await.state.set(&sensor, value);
+}
+
+#[when(expr = "the station's readings become {int} seconds old")]
+async fn station_readings_age(world: &mut WeatherMonitorWorld, seconds: u32) {
+    world.station().await.state.set_age(f64::from(seconds));
+}
+
+#[when("the station stops answering")]
+async fn station_goes_down(world: &mut WeatherMonitorWorld) {
+    world.station().await.state.set_offline(true);
+}
+
+#[when("the station answers again")]
+async fn station_comes_back(world: &mut WeatherMonitorWorld) {
+    world.station().await.state.set_offline(false);
+}
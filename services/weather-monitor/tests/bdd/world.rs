@@ -0,0 +1,198 @@
+use ascom_alpaca::api::{SafetyMonitor, TypedDevice};
+use ascom_alpaca::Client as AlpacaClient;
+use cucumber::World;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+use crate::steps::infrastructure::{ServiceHandle, StationHandle};
+
+#[derive(Debug, Default, World)]
+pub struct WeatherMonitorWorld {
+    // Process handle
+    pub weather_monitor: Option<ServiceHandle>,
+    pub monitor: Option<Arc<dyn SafetyMonitor>>,
+
+    // The fake weather station the config's one source points at
+    pub station: Option<StationHandle>,
+
+    // Config building
+    pub rules: Vec<Value>,
+    pub stale_after_secs: Option<u64>,
+    pub unsafe_delay_secs: Option<u64>,
+    pub safe_delay_secs: Option<u64>,
+    /// Replaces the built config wholesale — the rejection scenarios stage
+    /// a deliberately broken one.
+    pub config_override: Option<Value>,
+
+    pub temp_dir: Option<TempDir>,
+
+    // Result capture
+    pub last_error: Option<String>,
+
+    // Config actions test state
+    pub last_response: Option<Value>,
+}
+
+impl WeatherMonitorWorld {
+    /// The fake station, spawned on first use.
+    pub async fn station(&mut self) -> &StationHandle {
+        if self.station.is_none() {
+            self.station = Some(StationHandle::spawn().await);
+        }
+        self.station.as_ref().unwrap()
+    }
+
+    /// Convenience accessor for the typed `SafetyMonitor` device.
+    pub fn monitor(&self) -> &Arc<dyn SafetyMonitor> {
+        self.monitor.as_ref().expect("monitor not acquired")
+    }
+
+    /// Build a JSON config from the accumulated world state: one source
+    /// (the fake station, or an unreachable URL when none was set up),
+    /// 1 s polling, and delays of zero unless a step set them.
+    pub fn build_config_json(&self) -> Value {
+        let alpaca_url = self
+            .station
+            .as_ref()
+            .map_or_else(|| "http://127.0.0.1:9".to_string(), StationHandle::url);
+        let rules = if self.rules.is_empty() {
+            vec![serde_json::json!({"type": "humidity", "threshold": 90})]
+        } else {
+            self.rules.clone()
+        };
+        let secs = |value: Option<u64>, default: u64| format!("{}s", value.unwrap_or(default));
+
+        serde_json::json!({
+            "device": {
+                "name": "Test",
+                "unique_id": "test-001",
+                "description": "Test device",
+            },
+            "sources": [
+                {"id": "station", "alpaca_url": alpaca_url},
+            ],
+            "timing": {
+                "polling_interval": "1s",
+                "stale_after": secs(self.stale_after_secs, 5),
+                "unsafe_delay": secs(self.unsafe_delay_secs, 0),
+                "safe_delay": secs(self.safe_delay_secs, 0),
+            },
+            "rules": rules,
+            "server": {
+                "port": 0,
+            },
+        })
+    }
+
+    /// Write the config to the temp dir and return its path.
+    fn write_config(&mut self) -> String {
+        let config = self
+            .config_override
+            .clone()
+            .unwrap_or_else(|| self.build_config_json());
+        let dir = self
+            .temp_dir
+            .get_or_insert_with(|| TempDir::new().expect("failed to create temp dir"));
+        let config_path = dir.path().join("config.json");
+        std::fs::write(&config_path, config.to_string()).expect("failed to write config");
+        config_path.to_string_lossy().to_string()
+    }
+
+    /// Write config to temp dir, start the binary, acquire typed client.
+    pub async fn start_weather_monitor(&mut self) {
+        let config_path = self.write_config();
+        let handle = ServiceHandle::start(env!("CARGO_PKG_NAME"), &config_path).await;
+        let monitor = acquire_monitor(&handle).await;
+        self.monitor = Some(monitor);
+        self.weather_monitor = Some(handle);
+    }
+
+    /// As [`Self::start_weather_monitor`], recording a failed start in
+    /// `last_error` instead of panicking.
+    pub async fn try_start_weather_monitor(&mut self) {
+        let config_path = self.write_config();
+        match ServiceHandle::try_start(env!("CARGO_PKG_NAME"), &config_path).await {
+            Ok(handle) => {
+                let monitor = acquire_monitor(&handle).await;
+                self.monitor = Some(monitor);
+                self.weather_monitor = Some(handle);
+                self.last_error = None;
+            }
+            Err(e) => self.last_error = Some(e),
+        }
+    }
+
+    /// Poll `IsSafe` until it reads `expected`, panicking after `within`.
+    pub async fn wait_for_is_safe(&self, expected: bool, within: Duration) {
+        let deadline = Instant::now() + within;
+        loop {
+            let reading = self.monitor().is_safe().await.unwrap();
+            if reading == expected {
+                return;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "is_safe did not become {expected} within {within:?}"
+            );
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+
+    /// Poll `IsSafe` for `duration`, panicking on any reading other than
+    /// `expected`.
+    pub async fn assert_is_safe_holds(&self, expected: bool, duration: Duration) {
+        let deadline = Instant::now() + duration;
+        while Instant::now() < deadline {
+            let reading = self.monitor().is_safe().await.unwrap();
+            assert_eq!(
+                reading, expected,
+                "is_safe changed to {reading} before {duration:?} had passed"
+            );
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+
+    /// Call `config.get` and return the `config` object (so a When step can
+    /// edit a field and re-`config.apply` it).
+    pub async fn current_config(&mut self) -> Value {
+        let monitor = Arc::clone(self.monitor());
+        let body = monitor
+            .action("config.get".to_string(), String::new())
+            .await
+            .expect("config.get failed");
+        let parsed: Value = serde_json::from_str(&body).expect("config.get returned invalid JSON");
+        parsed
+            .get("config")
+            .cloned()
+            .expect("config.get response missing `config`")
+    }
+
+    /// Call `config.apply` with `params` and stash the parsed response.
+    pub async fn call_config_apply(&mut self, params: Value) {
+        let monitor = Arc::clone(self.monitor());
+        let body = monitor
+            .action("config.apply".to_string(), params.to_string())
+            .await
+            .expect("config.apply failed");
+        self.last_response =
+            Some(serde_json::from_str(&body).expect("config.apply returned invalid JSON"));
+    }
+}
+
+/// Poll until the server returns a `SafetyMonitor` device via the typed client.
+async fn acquire_monitor(handle: &ServiceHandle) -> Arc<dyn SafetyMonitor> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], handle.port));
+    let client = AlpacaClient::new_from_addr(addr);
+    for _ in 0..60 {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        if let Ok(mut devices) = client.get_devices().await {
+            if let Some(TypedDevice::SafetyMonitor(monitor)) = devices.next() {
+                return monitor;
+            }
+        }
+    }
+    panic!("weather-monitor did not become healthy within 30 seconds");
+}
@@ -0,0 +1,164 @@
+#![cfg(feature = "conformu")]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ascom_alpaca::api::{CargoServerInfo, Device, ObservingConditions};
+use ascom_alpaca::{ASCOMResult, Server};
+use bdd_infra::{ConformuRun, ServiceHandle};
+use tracing_subscriber::{fmt, EnvFilter};
+
+/// A clear, dry weather station: the source the monitor under test polls.
+#[derive(Debug, Default)]
+struct FakeWeather {
+    connected: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl Device for FakeWeather {
+    fn static_name(&self) -> &str {
+        "ConformU Fake Weather"
+    }
+
+    fn unique_id(&self) -> &str {
+        "conformu-fake-weather-001"
+    }
+
+    async fn connected(&self) -> ASCOMResult<bool> {
+        Ok(self.connected.load(Ordering::SeqCst))
+    }
+
+    async fn set_connected(&self, connected: bool) -> ASCOMResult<()> {
+        self.connected.store(connected, Ordering::SeqCst);
+        Ok(())
+    }
+
+    async fn description(&self) -> ASCOMResult<String> {
+        Ok("Fixed readings for the weather-monitor ConformU run".to_string())
+    }
+
+    async fn driver_info(&self) -> ASCOMResult<String> {
+        Ok("fake weather".to_string())
+    }
+
+    async fn driver_version(&self) -> ASCOMResult<String> {
+        Ok("0.1.0".to_string())
+    }
+}
+
+#[async_trait::async_trait]
+impl ObservingConditions for FakeWeather {
+    async fn average_period(&self) -> ASCOMResult<f64> {
+        Ok(0.0)
+    }
+
+    async fn set_average_period(&self, _period: f64) -> ASCOMResult<()> {
+        Ok(())
+    }
+
+    async fn temperature(&self) -> ASCOMResult<f64> {
+        Ok(10.0)
+    }
+
+    async fn humidity(&self) -> ASCOMResult<f64> {
+        Ok(60.0)
+    }
+
+    async fn dew_point(&self) -> ASCOMResult<f64> {
+        Ok(2.6)
+    }
+
+    async fn time_since_last_update(&self, _sensor_name: String) -> ASCOMResult<f64> {
+        Ok(0.0)
+    }
+}
+
+#[tokio::test]
+async fn conformu_compliance_tests() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Initialize tracing to capture ConformU detailed output
+    let _ = fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| EnvFilter::new("ascom_alpaca::conformu=trace,info")),
+        )
+        .with_test_writer()
+        .try_init();
+
+    // Serve the fake weather station in-process on an ephemeral port.
+    let mut weather = Server::new(CargoServerInfo!());
+    weather.devices.register(FakeWeather::default());
+    let weather_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let weather_addr = weather_listener.local_addr()?;
+    let weather_router = axum::Router::new().fallback_service(weather.into_service());
+    let weather_task =
+        tokio::spawn(async move { axum::serve(weather_listener, weather_router).await });
+
+    // Create test config
+    let test_dir = std::env::temp_dir().join("weather_monitor_conformu_test");
+    std::fs::create_dir_all(&test_dir)?;
+
+    let config_path = test_dir.join("config.json");
+
+    let config = serde_json::json!({
+        "device": {
+            "name": "ConformU Test Weather Monitor",
+            "unique_id": "conformu-test-001",
+            "description": "Test SafetyMonitor for ConformU compliance"
+        },
+        "sources": [
+            {
+                "id": "fake",
+                "alpaca_url": format!("http://{weather_addr}")
+            }
+        ],
+        "timing": {
+            "polling_interval": "1s",
+            "stale_after": "10s",
+            "unsafe_delay": "0s",
+            "safe_delay": "0s"
+        },
+        "rules": [
+            {"type": "humidity", "threshold": 90},
+            {"type": "dew_point_spread", "threshold": 2}
+        ],
+        "server": {
+            "port": 0
+        }
+    });
+
+    std::fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
+
+    let mut handle = ServiceHandle::try_start(
+        env!("CARGO_PKG_NAME"),
+        config_path
+            .to_str()
+            .expect("conformu temp path must be UTF-8"),
+    )
+    .await?;
+
+    println!("::group::ConformU Compliance Test Results");
+    println!(
+        "Running ASCOM Alpaca compliance tests on port {}...",
+        handle.port
+    );
+
+    let result = bdd_infra::run_conformu("safetymonitor", &handle.base_url, 0, None).await;
+
+    handle.stop().await;
+    weather_task.abort();
+    std::fs::remove_dir_all(&test_dir).ok();
+
+    match result? {
+        ConformuRun::Skipped => {
+            println!("CONFORMU_PATH not set; skipped");
+        }
+        ConformuRun::Passed => {
+            println!("ConformU compliance tests PASSED");
+            println!("All ASCOM Alpaca compliance requirements met");
+        }
+    }
+
+    println!("::endgroup::");
+
+    Ok(())
+}
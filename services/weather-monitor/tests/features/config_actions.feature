@@ -0,0 +1,24 @@
+Feature: Configuration actions
+  config.apply validates the proposed configuration with the same rules
+  as startup. An invalid one is refused with the offending paths and
+  nothing is written; a valid one is persisted and applied by an
+  in-process reload.
+
+  Background:
+    Given a weather station reporting humidity 60.0
+    And weather-monitor is running
+
+  Scenario: A stale_after below the polling interval is refused
+    When config.apply sets stale_after below the polling interval
+    Then the apply status should be invalid
+    And the validation errors should name timing.stale_after
+
+  Scenario: Removing every rule is refused
+    When config.apply removes every rule
+    Then the apply status should be invalid
+    And the validation errors should name rules
+
+  Scenario: A new unsafe delay is applied by reload
+    When config.apply sets the unsafe delay to 30s
+    Then the apply status should be applying
+    And the reload list should include timing.unsafe_delay
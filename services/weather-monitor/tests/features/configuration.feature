@@ -0,0 +1,16 @@
+Feature: Configuration validation
+  weather-monitor refuses to start on a configuration it cannot evaluate
+  safely, rather than serving a verdict built on a half-understood one.
+
+  Scenario Outline: Reject an unusable configuration
+    Given <configuration>
+    When I try to start weather-monitor with this configuration
+    Then the binary should fail to start
+
+    Examples:
+      | configuration                                                            |
+      | a configuration with no rules                                            |
+      | a configuration with no sources                                          |
+      | a configuration whose stale_after is shorter than its polling interval   |
+      | a configuration whose rule names an unknown source                       |
+      | a configuration with an unknown rule type                                |
@@ -0,0 +1,39 @@
+Feature: Device connection lifecycle
+  The SafetyMonitor starts disconnected and refuses IsSafe until a client
+  connects. Connecting starts the source polling; it never fails because
+  a source is down — the verdict just reads unsafe. A reconnect starts
+  the debounce over, so a fresh connection holds unsafe for safe_delay.
+
+  Scenario: The device starts disconnected
+    Given a weather station reporting humidity 60.0
+    And weather-monitor is running
+    Then the device should be disconnected
+    And is_safe should fail with a not connected error
+
+  Scenario: Connect and disconnect
+    Given a weather station reporting humidity 60.0
+    And weather-monitor is running
+    When I connect the device
+    Then the device should be connected
+    When I disconnect the device
+    Then the device should be disconnected
+    And is_safe should fail with a not connected error
+
+  Scenario: An unreachable source connects but reads unsafe
+    Given a humidity rule with threshold 90.0
+    And weather-monitor is running against an unreachable source
+    When I connect the device
+    Then the device should be connected
+    And is_safe should stay false for 3 seconds
+
+  Scenario: A reconnect holds unsafe through the safe delay
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And a safe delay of 4 seconds
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should become true within 8 seconds
+    When I disconnect the device
+    And I connect the device
+    Then is_safe should stay false for 2 seconds
+    And is_safe should become true within 6 seconds
@@ -0,0 +1,49 @@
+Feature: Unsafe and safe delays
+  The per-poll verdict is debounced: a change is only reported once it
+  has held for unsafe_delay (to close) or safe_delay (to reopen), and any
+  poll agreeing with the reported verdict restarts the wait. The reported
+  verdict starts out unsafe, so even a clear first poll has to hold for
+  safe_delay.
+
+  Scenario: A clear sky at startup still waits out the safe delay
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And a safe delay of 4 seconds
+    And weather-monitor is running
+    When I connect the device
+    Then is_safe should stay false for 2 seconds
+    And is_safe should become true within 6 seconds
+
+  Scenario: Sustained bad weather closes after the unsafe delay
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And an unsafe delay of 4 seconds
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should become true within 5 seconds
+    When the station reports humidity 95.0
+    Then is_safe should stay true for 2 seconds
+    And is_safe should become false within 6 seconds
+
+  Scenario: A gust shorter than the unsafe delay does not close
+    Given a weather station reporting wind_gust 5.0
+    And a wind_gust rule with threshold 12.0
+    And an unsafe delay of 6 seconds
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should become true within 5 seconds
+    When the station reports wind_gust 15.0
+    Then is_safe should stay true for 2 seconds
+    When the station reports wind_gust 5.0
+    Then is_safe should stay true for 8 seconds
+
+  Scenario: Reopening waits for the safe delay of continuous clear
+    Given a weather station reporting humidity 95.0
+    And a humidity rule with threshold 90.0
+    And a safe delay of 4 seconds
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should stay false for 2 seconds
+    When the station reports humidity 60.0
+    Then is_safe should stay false for 2 seconds
+    And is_safe should become true within 6 seconds
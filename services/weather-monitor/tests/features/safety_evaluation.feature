@@ -0,0 +1,60 @@
+Feature: Safety evaluation rules
+  weather-monitor polls its ObservingConditions source and evaluates every
+  configured rule against the readings. Conditions are safe only while no
+  rule trips; a reading exactly at a threshold is safe. With both delays
+  at zero the reported verdict follows the latest poll.
+
+  Scenario: A clear, dry station reads safe
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And weather-monitor is running
+    When I connect the device
+    Then is_safe should become true within 5 seconds
+
+  Scenario Outline: Each rule trips on its own side of the threshold
+    Given a weather station reporting temperature 10.0
+    And a weather station reporting <sensor> <value>
+    And a <rule> rule with threshold <threshold>
+    And weather-monitor is running
+    When I connect the device
+    Then is_safe should become <expected> within 5 seconds
+
+    Examples:
+      | rule             | sensor          | value | threshold | expected |
+      | humidity         | humidity        | 95.0  | 90.0      | false    |
+      | humidity         | humidity        | 90.0  | 90.0      | true     |
+      | dew_point_spread | dew_point       | 9.0   | 2.0       | false    |
+      | dew_point_spread | dew_point       | 4.0   | 2.0       | true     |
+      | wind_gust        | wind_gust       | 15.0  | 12.0      | false    |
+      | rain_rate        | rain_rate       | 0.2   | 0.0       | false    |
+      | clouds           | sky_temperature | 2.0   | 20.0      | false    |
+      | clouds           | sky_temperature | -18.0 | 20.0      | true     |
+
+  Scenario: Any tripped rule makes conditions unsafe
+    Given a weather station reporting humidity 60.0
+    And a weather station reporting wind_gust 15.0
+    And a humidity rule with threshold 90.0
+    And a wind_gust rule with threshold 12.0
+    And weather-monitor is running
+    When I connect the device
+    Then is_safe should stay false for 3 seconds
+
+  Scenario: Conditions that change mid-session change the verdict
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should become true within 5 seconds
+    When the station reports humidity 95.0
+    Then is_safe should become false within 5 seconds
+    When the station reports humidity 70.0
+    Then is_safe should become true within 5 seconds
+
+  Scenario: A sensor the station does not report is unsafe
+    Given a weather station reporting humidity 60.0
+    And a weather station that does not report wind_gust
+    And a humidity rule with threshold 90.0
+    And a wind_gust rule with threshold 12.0
+    And weather-monitor is running
+    When I connect the device
+    Then is_safe should stay false for 3 seconds
@@ -0,0 +1,39 @@
+Feature: Stale data and source outages
+  A reading older than stale_after — by the station's own
+  TimeSinceLastUpdate or because the last successful poll is that old —
+  cannot vouch for the sky, so the rule reading it is unsafe. A source
+  that stops answering goes stale the same way; one that comes back is
+  trusted again once a fresh poll lands.
+
+  Scenario: Readings the station reports as old are unsafe
+    Given a weather station reporting humidity 60.0
+    And a weather station whose readings are 30 seconds old
+    And a humidity rule with threshold 90.0
+    And readings go stale after 10 seconds
+    And weather-monitor is running
+    When I connect the device
+    Then is_safe should stay false for 3 seconds
+
+  Scenario: Readings that age past stale_after turn unsafe
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And readings go stale after 10 seconds
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should become true within 5 seconds
+    When the station's readings become 30 seconds old
+    Then is_safe should become false within 5 seconds
+    When the station's readings become 0 seconds old
+    Then is_safe should become true within 5 seconds
+
+  Scenario: A station that stops answering goes stale and recovers
+    Given a weather station reporting humidity 60.0
+    And a humidity rule with threshold 90.0
+    And readings go stale after 3 seconds
+    And weather-monitor is running
+    And the device is connected
+    Then is_safe should become true within 5 seconds
+    When the station stops answering
+    Then is_safe should become false within 8 seconds
+    When the station answers again
+    Then is_safe should become true within 8 seconds